@@ -1,7 +1,7 @@
 use crate::{
     absm::{
         connection::{self, Connection},
-        node::AbsmBaseNode,
+        node::{AbsmBaseNode, AbsmNode},
         segment::SegmentMessage,
         selectable::{Selectable, SelectableMessage},
         socket::{Socket, SocketDirection, SocketMessage},
@@ -10,6 +10,7 @@ use crate::{
     utils::fetch_node_screen_center_ui,
 };
 use fyrox::{
+    animation::machine::State,
     core::{
         algebra::{Matrix3, Point2, Vector2},
         color::Color,
@@ -54,6 +55,13 @@ pub(super) enum Mode {
         source_pos: Vector2<f32>,
         dest_pos: Vector2<f32>,
     },
+    /// Shift + drag from a state, a transition is created when the mouse is released over other
+    /// state. Releasing it anywhere else cancels the creation.
+    DragTransition {
+        source: Handle<UiNode>,
+        source_pos: Vector2<f32>,
+        dest_pos: Vector2<f32>,
+    },
     CreateConnection {
         source: Handle<UiNode>,
         source_pos: Vector2<f32>,
@@ -77,6 +85,8 @@ pub(super) enum AbsmCanvasMessage {
     },
     SelectionChanged(Vec<Handle<UiNode>>),
     ForceSyncDependentObjects,
+    SnapToGrid(bool),
+    GridStep(f32),
 }
 
 impl AbsmCanvasMessage {
@@ -86,8 +96,12 @@ impl AbsmCanvasMessage {
     define_constructor!(AbsmCanvasMessage:CommitDrag => fn commit_drag(entries: Vec<Entry>), layout: false);
     define_constructor!(AbsmCanvasMessage:SelectionChanged => fn selection_changed(Vec<Handle<UiNode>>), layout: false);
     define_constructor!(AbsmCanvasMessage:ForceSyncDependentObjects => fn force_sync_dependent_objects(), layout: true);
+    define_constructor!(AbsmCanvasMessage:SnapToGrid => fn snap_to_grid(bool), layout: false);
+    define_constructor!(AbsmCanvasMessage:GridStep => fn grid_step(f32), layout: false);
 }
 
+pub const DEFAULT_GRID_STEP: f32 = 50.0;
+
 #[derive(Clone)]
 pub struct AbsmCanvas {
     widget: Widget,
@@ -100,11 +114,26 @@ pub struct AbsmCanvas {
     mode: Mode,
     // A handle to a node that was under the cursor at the moment of release of left mouse button.
     lmb_released_node: Cell<Handle<UiNode>>,
+    snap_to_grid: bool,
+    grid_step: f32,
 }
 
 define_widget_deref!(AbsmCanvas);
 
 impl AbsmCanvas {
+    /// Converts screen position to local position of the canvas and snaps it to the grid.
+    pub fn screen_to_snapped_local(
+        canvas: Handle<UiNode>,
+        screen_position: Vector2<f32>,
+        ui: &UserInterface,
+    ) -> Vector2<f32> {
+        let canvas_ref = ui.node(canvas);
+        let local_position = canvas_ref.screen_to_local(screen_position);
+        canvas_ref
+            .query_component::<AbsmCanvas>()
+            .map_or(local_position, |c| c.snap_position(local_position))
+    }
+
     pub fn point_to_local_space(&self, point: Vector2<f32>) -> Vector2<f32> {
         self.visual_transform()
             .try_inverse()
@@ -113,6 +142,18 @@ impl AbsmCanvas {
             .coords
     }
 
+    /// Snaps the given local position to the grid, if snapping is enabled.
+    pub fn snap_position(&self, position: Vector2<f32>) -> Vector2<f32> {
+        if self.snap_to_grid {
+            Vector2::new(
+                round_to_step(position.x, self.grid_step),
+                round_to_step(position.y, self.grid_step),
+            )
+        } else {
+            position
+        }
+    }
+
     pub fn update_transform(&self, ui: &UserInterface) {
         let transform =
             Matrix3::new_translation(&-self.view_position) * Matrix3::new_scaling(self.zoom);
@@ -318,7 +359,7 @@ impl Control for AbsmCanvas {
             CommandTexture::None,
             None,
         );
-        let step_size = 50.0;
+        let step_size = self.grid_step;
 
         let mut local_left_bottom = local_bounds.left_top_corner();
         local_left_bottom.x = round_to_step(local_left_bottom.x, step_size);
@@ -366,6 +407,11 @@ impl Control for AbsmCanvas {
                 source_pos,
                 dest_pos,
                 ..
+            }
+            | Mode::DragTransition {
+                source_pos,
+                dest_pos,
+                ..
             } => {
                 transition::draw_transition(
                     ctx,
@@ -463,9 +509,19 @@ impl Control for AbsmCanvas {
                     }
                     Mode::Normal => {
                         if dest_node_handle.is_some() {
-                            self.mode = Mode::Drag {
-                                drag_context: self.make_drag_context(ui),
-                            }
+                            self.mode = if ui.keyboard_modifiers().shift
+                                && ui.node(dest_node_handle).has_component::<AbsmNode<State>>()
+                            {
+                                Mode::DragTransition {
+                                    source: dest_node_handle,
+                                    source_pos: ui.node(dest_node_handle).center(),
+                                    dest_pos: self.screen_to_local(ui.cursor_position()),
+                                }
+                            } else {
+                                Mode::Drag {
+                                    drag_context: self.make_drag_context(ui),
+                                }
+                            };
                         } else {
                             self.set_selection(&[], ui);
                         }
@@ -491,6 +547,25 @@ impl Control for AbsmCanvas {
 
                         self.mode = Mode::Normal;
                     }
+                    Mode::DragTransition { source, .. } => {
+                        let released_node = self.lmb_released_node.get();
+                        let dest_node_handle = if released_node.is_some() {
+                            self.fetch_dest_node_component::<AbsmNode<State>>(released_node, ui)
+                        } else {
+                            Handle::NONE
+                        };
+
+                        if dest_node_handle.is_some() && dest_node_handle != source {
+                            ui.send_message(AbsmCanvasMessage::commit_transition(
+                                self.handle(),
+                                MessageDirection::FromWidget,
+                                source,
+                                dest_node_handle,
+                            ));
+                        }
+
+                        self.mode = Mode::Normal;
+                    }
                     Mode::CreateConnection { source, .. } => {
                         let dest_socket_handle = self
                             .fetch_dest_node_component::<Socket>(self.lmb_released_node.get(), ui);
@@ -544,8 +619,10 @@ impl Control for AbsmCanvas {
                     for entry in drag_context.entries.iter() {
                         let local_cursor_pos = self.point_to_local_space(*pos);
 
-                        let new_position = entry.initial_position
-                            + (local_cursor_pos - drag_context.initial_cursor_position);
+                        let new_position = self.snap_position(
+                            entry.initial_position
+                                + (local_cursor_pos - drag_context.initial_cursor_position),
+                        );
 
                         ui.send_message(WidgetMessage::desired_position(
                             entry.node,
@@ -556,6 +633,9 @@ impl Control for AbsmCanvas {
                 }
                 Mode::CreateTransition {
                     ref mut dest_pos, ..
+                }
+                | Mode::DragTransition {
+                    ref mut dest_pos, ..
                 } => {
                     *dest_pos = local_cursor_position;
                 }
@@ -591,6 +671,12 @@ impl Control for AbsmCanvas {
                     AbsmCanvasMessage::ForceSyncDependentObjects => {
                         self.force_sync_dependent_objects(ui);
                     }
+                    AbsmCanvasMessage::SnapToGrid(snap) => {
+                        self.snap_to_grid = *snap;
+                    }
+                    AbsmCanvasMessage::GridStep(step) => {
+                        self.grid_step = step.max(1.0);
+                    }
                     _ => (),
                 }
             }
@@ -656,6 +742,8 @@ impl AbsmCanvasBuilder {
             zoom: 1.0,
             mode: Mode::Normal,
             lmb_released_node: Default::default(),
+            snap_to_grid: true,
+            grid_step: DEFAULT_GRID_STEP,
         };
 
         ctx.add_node(UiNode::new(canvas))
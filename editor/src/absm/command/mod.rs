@@ -5,6 +5,7 @@ use fyrox::{
         algebra::Vector2,
         pool::{Handle, Ticket},
     },
+    fxhash::FxHashMap,
     scene::{animation::absm::AnimationBlendingStateMachine, node::Node},
 };
 use std::fmt::Debug;
@@ -300,6 +301,15 @@ impl AddPoseNodeCommand {
             node,
         }
     }
+
+    /// Returns a handle of the added node, it is valid only after execution of the command.
+    pub fn handle(&self) -> Handle<PoseNode> {
+        if let Self::Executed { handle, .. } = self {
+            *handle
+        } else {
+            Handle::NONE
+        }
+    }
 }
 
 impl Command for AddPoseNodeCommand {
@@ -403,6 +413,104 @@ impl Command for AddPoseNodeCommand {
     }
 }
 
+/// Adds a set of pose nodes (a copy of other nodes) to a state. Connections between the nodes
+/// are remapped to the new handles, connections to nodes outside of the set are removed.
+#[derive(Debug)]
+pub struct PastePoseNodesCommand {
+    node_handle: Handle<Node>,
+    layer_index: usize,
+    // Original handle + a copy of a node, used only on first execution.
+    nodes: Vec<(Handle<PoseNode>, PoseNode)>,
+    commands: Vec<AddPoseNodeCommand>,
+}
+
+impl PastePoseNodesCommand {
+    /// Creates new command, `parent_state` of each node should already be set to the destination
+    /// state.
+    pub fn new(
+        node_handle: Handle<Node>,
+        layer_index: usize,
+        mut nodes: Vec<(Handle<PoseNode>, PoseNode)>,
+    ) -> Self {
+        // Put nodes that aren't sources of other nodes first, this way the first node becomes
+        // the root of an empty state.
+        let sources = nodes
+            .iter()
+            .flat_map(|(_, node)| node.children())
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|(handle, _)| sources.contains(handle));
+
+        Self {
+            node_handle,
+            layer_index,
+            nodes,
+            commands: Default::default(),
+        }
+    }
+}
+
+fn remap_pose_sources(node: &mut PoseNode, map: &FxHashMap<Handle<PoseNode>, Handle<PoseNode>>) {
+    let remap = |handle: &mut Handle<PoseNode>| {
+        *handle = map.get(handle).cloned().unwrap_or_default();
+    };
+
+    match node {
+        PoseNode::PlayAnimation(_) => {}
+        PoseNode::BlendAnimations(blend_animations) => {
+            for pose in blend_animations.pose_sources.iter_mut() {
+                remap(&mut pose.pose_source);
+            }
+        }
+        PoseNode::BlendAnimationsByIndex(blend_animations) => {
+            for input in blend_animations.inputs.iter_mut() {
+                remap(&mut input.pose_source);
+            }
+        }
+    }
+}
+
+impl Command for PastePoseNodesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Paste Pose Nodes".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if self.commands.is_empty() {
+            let mut map = FxHashMap::default();
+
+            for (original_handle, node) in self.nodes.drain(..) {
+                let mut command = AddPoseNodeCommand::new(self.node_handle, self.layer_index, node);
+                command.execute(context);
+                map.insert(original_handle, command.handle());
+                self.commands.push(command);
+            }
+
+            // Remapped data is preserved on revert, so it is done only once.
+            let layer =
+                &mut fetch_machine(context, self.node_handle).layers_mut()[self.layer_index];
+            for &handle in map.values() {
+                remap_pose_sources(&mut layer.nodes_mut()[handle], &map);
+            }
+        } else {
+            for command in self.commands.iter_mut() {
+                command.execute(context);
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        for command in self.commands.iter_mut().rev() {
+            command.revert(context);
+        }
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        for command in self.commands.iter_mut() {
+            command.finalize(context);
+        }
+    }
+}
+
 macro_rules! define_move_command {
     ($name:ident, $ent_type:ty, $container:ident) => {
         #[derive(Debug)]
@@ -3,12 +3,14 @@ use crate::{
         command::blend::{AddInputCommand, AddPoseSourceCommand},
         node::{AbsmNode, AbsmNodeMessage},
         parameter::ParameterPanel,
-        selection::AbsmSelection,
+        selection::{AbsmSelection, SelectedEntity},
         state_graph::StateGraphViewer,
         state_viewer::StateViewer,
         toolbar::{Toolbar, ToolbarAction},
+        validation::AbsmIssue,
     },
     scene::{EditorScene, Selection},
+    settings::keys::KeyBindings,
     Message,
 };
 use fyrox::fxhash::FxHashSet;
@@ -46,6 +48,7 @@ mod state_graph;
 mod state_viewer;
 mod toolbar;
 mod transition;
+pub mod validation;
 
 const NORMAL_BACKGROUND: Color = Color::opaque(60, 60, 60);
 const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
@@ -83,6 +86,22 @@ fn fetch_selection(editor_selection: &Selection) -> AbsmSelection {
     }
 }
 
+fn highlight_views<T: 'static>(
+    ui: &UserInterface,
+    canvas: Handle<UiNode>,
+    has_warning: impl Fn(Handle<T>) -> bool,
+) {
+    for &view in ui.node(canvas).children() {
+        if let Some(view_ref) = ui.node(view).query_component::<AbsmNode<T>>() {
+            ui.send_message(AbsmNodeMessage::warning(
+                view,
+                MessageDirection::ToWidget,
+                has_warning(view_ref.model_handle),
+            ));
+        }
+    }
+}
+
 pub struct AbsmEditor {
     pub window: Handle<UiNode>,
     state_graph_viewer: StateGraphViewer,
@@ -130,7 +149,7 @@ impl AbsmEditor {
         )
         .build(ctx);
 
-        let toolbar = Toolbar::new(ctx);
+        let toolbar = Toolbar::new(ctx, vec![state_graph_viewer.canvas, state_viewer.canvas]);
 
         let content = GridBuilder::new(
             WidgetBuilder::new()
@@ -142,11 +161,16 @@ impl AbsmEditor {
         .add_column(Column::stretch())
         .build(ctx);
 
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(800.0).with_height(500.0))
-            .open(false)
-            .with_content(content)
-            .with_title(WindowTitle::text("ABSM Editor"))
-            .build(ctx);
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_name("AbsmEditor")
+                .with_width(800.0)
+                .with_height(500.0),
+        )
+        .open(false)
+        .with_content(content)
+        .with_title(WindowTitle::text("ABSM Editor"))
+        .build(ctx);
 
         Self {
             window,
@@ -344,12 +368,42 @@ impl AbsmEditor {
         }
     }
 
+    /// Marks views of the states and pose nodes of the currently edited layer that have
+    /// validation issues.
+    pub fn highlight_issues(
+        &self,
+        issues: &[AbsmIssue],
+        editor_scene: &EditorScene,
+        ui: &UserInterface,
+    ) {
+        let selection = fetch_selection(&editor_scene.selection);
+
+        let is_current = |issue: &&AbsmIssue| {
+            issue.absm_node_handle == selection.absm_node_handle
+                && Some(issue.layer_index) == selection.layer
+        };
+
+        highlight_views(ui, self.state_graph_viewer.canvas, |state| {
+            issues
+                .iter()
+                .filter(is_current)
+                .any(|i| i.entity == SelectedEntity::State(state))
+        });
+        highlight_views(ui, self.state_viewer.canvas, |pose_node| {
+            issues
+                .iter()
+                .filter(is_current)
+                .any(|i| i.entity == SelectedEntity::PoseNode(pose_node))
+        });
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         engine: &mut Engine,
         sender: &Sender<Message>,
         editor_scene: &mut EditorScene,
+        key_bindings: &KeyBindings,
     ) {
         let scene = &mut engine.scenes[editor_scene.scene];
         let ui = &mut engine.user_interface;
@@ -369,6 +423,7 @@ impl AbsmEditor {
                     absm_node,
                     layer_index,
                     editor_scene,
+                    key_bindings,
                 );
                 self.state_graph_viewer.handle_ui_message(
                     message,
@@ -44,6 +44,8 @@ where
     normal_color: Color,
     selected_color: Color,
     name: Handle<UiNode>,
+    active: bool,
+    has_warning: bool,
 }
 
 impl<T> Clone for AbsmNode<T>
@@ -63,6 +65,8 @@ where
             normal_color: self.normal_color,
             selected_color: self.selected_color,
             name: self.name,
+            active: self.active,
+            has_warning: self.has_warning,
         }
     }
 }
@@ -102,8 +106,31 @@ where
             }),
         ));
     }
+
+    fn update_border(&self, ui: &UserInterface) {
+        let (thickness, color) = if self.active {
+            (Thickness::uniform(3.0), Color::opaque(120, 80, 60))
+        } else if self.has_warning {
+            (Thickness::uniform(2.0), WARNING_BORDER_COLOR)
+        } else {
+            (Thickness::uniform(1.0), BORDER_COLOR)
+        };
+
+        ui.send_message(BorderMessage::stroke_thickness(
+            self.background,
+            MessageDirection::ToWidget,
+            thickness,
+        ));
+        ui.send_message(WidgetMessage::foreground(
+            self.background,
+            MessageDirection::ToWidget,
+            Brush::Solid(color),
+        ));
+    }
 }
 
+const WARNING_BORDER_COLOR: Color = Color::opaque(200, 60, 40);
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AbsmNodeMessage {
     Name(String),
@@ -113,6 +140,8 @@ pub enum AbsmNodeMessage {
     NormalColor(Color),
     SelectedColor(Color),
     SetActive(bool),
+    /// Highlights the node as the one having problems (see [`crate::absm::validation`]).
+    Warning(bool),
 }
 
 impl AbsmNodeMessage {
@@ -123,6 +152,7 @@ impl AbsmNodeMessage {
     define_constructor!(AbsmNodeMessage:NormalColor => fn normal_color(Color), layout: false);
     define_constructor!(AbsmNodeMessage:SelectedColor => fn selected_color(Color), layout: false);
     define_constructor!(AbsmNodeMessage:SetActive => fn set_active(bool), layout: false);
+    define_constructor!(AbsmNodeMessage:Warning => fn warning(bool), layout: false);
 }
 
 impl<T> Control for AbsmNode<T>
@@ -221,22 +251,14 @@ where
                         }
                     }
                     AbsmNodeMessage::SetActive(active) => {
-                        let (thickness, color) = if *active {
-                            (Thickness::uniform(3.0), Color::opaque(120, 80, 60))
-                        } else {
-                            (Thickness::uniform(1.0), BORDER_COLOR)
-                        };
-
-                        ui.send_message(BorderMessage::stroke_thickness(
-                            self.background,
-                            MessageDirection::ToWidget,
-                            thickness,
-                        ));
-                        ui.send_message(WidgetMessage::foreground(
-                            self.background,
-                            MessageDirection::ToWidget,
-                            Brush::Solid(color),
-                        ));
+                        self.active = *active;
+                        self.update_border(ui);
+                    }
+                    AbsmNodeMessage::Warning(has_warning) => {
+                        if self.has_warning != *has_warning {
+                            self.has_warning = *has_warning;
+                            self.update_border(ui);
+                        }
                     }
                     _ => (),
                 }
@@ -445,6 +467,8 @@ where
             normal_color: self.normal_color,
             selected_color: self.selected_color,
             name,
+            active: false,
+            has_warning: false,
         };
 
         ctx.add_node(UiNode::new(node))
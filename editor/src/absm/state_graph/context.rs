@@ -1,6 +1,6 @@
 use crate::{
     absm::{
-        canvas::{AbsmCanvasMessage, Mode},
+        canvas::{AbsmCanvas, AbsmCanvasMessage, Mode},
         command::{
             AddStateCommand, DeleteStateCommand, DeleteTransitionCommand,
             SetMachineEntryStateCommand,
@@ -76,7 +76,11 @@ impl CanvasContextMenu {
                         absm_node_handle,
                         layer_index,
                         State {
-                            position: ui.node(self.canvas).screen_to_local(screen_position),
+                            position: AbsmCanvas::screen_to_snapped_local(
+                                self.canvas,
+                                screen_position,
+                                ui,
+                            ),
                             name: "New State".to_string(),
                             root: Default::default(),
                         },
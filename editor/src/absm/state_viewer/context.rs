@@ -1,5 +1,6 @@
 use crate::{
     absm::{
+        canvas::AbsmCanvas,
         command::{
             blend::{
                 SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
@@ -94,9 +95,11 @@ impl CanvasContextMenu {
         layer_index: usize,
     ) {
         if let Some(MenuItemMessage::Click) = message.data() {
-            let position = ui
-                .node(self.canvas)
-                .screen_to_local(ui.node(self.menu).screen_position());
+            let position = AbsmCanvas::screen_to_snapped_local(
+                self.canvas,
+                ui.node(self.menu).screen_position(),
+                ui,
+            );
 
             let pose_node = if message.destination() == self.create_play_animation {
                 Some(PoseNode::PlayAnimation(PlayAnimation {
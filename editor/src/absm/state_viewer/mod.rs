@@ -5,7 +5,7 @@ use crate::{
             blend::{
                 SetBlendAnimationByIndexInputPoseSourceCommand, SetBlendAnimationsPoseSourceCommand,
             },
-            MovePoseNodeCommand,
+            MovePoseNodeCommand, PastePoseNodesCommand,
         },
         connection::{Connection, ConnectionBuilder},
         fetch_selection,
@@ -19,16 +19,19 @@ use crate::{
         commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
         EditorScene, Selection,
     },
-    send_sync_message, Message,
+    send_sync_message,
+    settings::keys::KeyBindings,
+    Message,
 };
 use fyrox::{
     animation::{
         machine::{MachineLayer, PoseNode, State},
         Animation,
     },
-    core::pool::Handle,
+    core::{algebra::Vector2, pool::Handle},
     gui::{
         border::BorderBuilder,
+        key::HotKey,
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
@@ -46,14 +49,19 @@ mod context;
 
 pub struct StateViewer {
     pub window: Handle<UiNode>,
-    canvas: Handle<UiNode>,
+    pub canvas: Handle<UiNode>,
     state: Handle<State>,
     canvas_context_menu: CanvasContextMenu,
     node_context_menu: NodeContextMenu,
     connection_context_menu: ConnectionContextMenu,
     last_selection: AbsmSelection,
+    // Copies of pose nodes with their original handles.
+    clipboard: Vec<(Handle<PoseNode>, PoseNode)>,
 }
 
+/// Offset of pasted nodes relative to their originals on the canvas.
+const PASTE_OFFSET: f32 = 20.0;
+
 fn create_socket(
     direction: SocketDirection,
     index: usize,
@@ -165,9 +173,64 @@ impl StateViewer {
             node_context_menu,
             connection_context_menu,
             last_selection: AbsmSelection::default(),
+            clipboard: Default::default(),
+        }
+    }
+
+    fn copy_selection(&mut self, editor_scene: &EditorScene, layer: &MachineLayer) {
+        let selection = fetch_selection(&editor_scene.selection);
+
+        let clipboard = selection
+            .entities
+            .iter()
+            .filter_map(|entity| {
+                if let SelectedEntity::PoseNode(handle) = entity {
+                    layer
+                        .nodes()
+                        .try_borrow(*handle)
+                        .map(|node| (*handle, node.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !clipboard.is_empty() {
+            self.clipboard = clipboard;
         }
     }
 
+    fn paste(
+        &self,
+        sender: &Sender<Message>,
+        layer: &MachineLayer,
+        absm_node_handle: Handle<Node>,
+        layer_index: usize,
+    ) {
+        if self.clipboard.is_empty() || !layer.states().is_valid_handle(self.state) {
+            return;
+        }
+
+        let nodes = self
+            .clipboard
+            .iter()
+            .map(|(handle, node)| {
+                let mut node = node.clone();
+                node.parent_state = self.state;
+                node.position += Vector2::new(PASTE_OFFSET, PASTE_OFFSET);
+                (*handle, node)
+            })
+            .collect::<Vec<_>>();
+
+        sender
+            .send(Message::do_scene_command(PastePoseNodesCommand::new(
+                absm_node_handle,
+                layer_index,
+                nodes,
+            )))
+            .unwrap();
+    }
+
     pub fn set_state(
         &mut self,
         state: Handle<State>,
@@ -237,10 +300,33 @@ impl StateViewer {
         absm_node: &AnimationBlendingStateMachine,
         layer_index: usize,
         editor_scene: &EditorScene,
+        key_bindings: &KeyBindings,
     ) {
         let machine = absm_node.machine();
 
         if let Some(layer) = machine.layers().get(layer_index) {
+            if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+                if !message.handled()
+                    && (message.destination() == self.canvas
+                        || ui.is_node_child_of(message.destination(), self.canvas))
+                {
+                    let hot_key = HotKey::Some {
+                        code: *key,
+                        modifiers: ui.keyboard_modifiers(),
+                    };
+
+                    // Mark the key as handled, otherwise the editor will copy or paste scene nodes
+                    // as well.
+                    if hot_key == key_bindings.copy_selection {
+                        self.copy_selection(editor_scene, layer);
+                        message.set_handled(true);
+                    } else if hot_key == key_bindings.paste {
+                        self.paste(sender, layer, absm_node_handle, layer_index);
+                        message.set_handled(true);
+                    }
+                }
+            }
+
             if message.destination() == self.canvas {
                 if let Some(msg) = message.data::<AbsmCanvasMessage>() {
                     match msg {
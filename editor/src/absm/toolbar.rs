@@ -1,5 +1,6 @@
 use crate::{
     absm::{
+        canvas::{AbsmCanvasMessage, DEFAULT_GRID_STEP},
         command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
         fetch_selection,
         selection::AbsmSelection,
@@ -23,6 +24,7 @@ use fyrox::{
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
         image::ImageBuilder,
         message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
         text_box::{TextBox, TextBoxBuilder},
@@ -48,6 +50,9 @@ pub struct Toolbar {
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
+    pub snap_to_grid: Handle<UiNode>,
+    pub grid_step: Handle<UiNode>,
+    canvases: Vec<Handle<UiNode>>,
 }
 
 pub enum ToolbarAction {
@@ -57,8 +62,11 @@ pub enum ToolbarAction {
 }
 
 impl Toolbar {
-    pub fn new(ctx: &mut BuildContext) -> Self {
+    /// `canvases` - canvases that will be affected by grid snapping settings.
+    pub fn new(ctx: &mut BuildContext, canvases: Vec<Handle<UiNode>>) -> Self {
         let preview;
+        let snap_to_grid;
+        let grid_step;
         let layers;
         let layer_name;
         let add_layer;
@@ -146,6 +154,41 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    snap_to_grid = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Snap positions of nodes to the grid when creating or moving them.",
+                            )),
+                    )
+                    .with_content(
+                        TextBuilder::new(
+                            WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Center),
+                        )
+                        .with_text("Snap")
+                        .build(ctx),
+                    )
+                    .checked(Some(true))
+                    .build(ctx);
+                    snap_to_grid
+                })
+                .with_child({
+                    grid_step = NumericUpDownBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(ctx, "Grid spacing."))
+                            .with_width(60.0),
+                    )
+                    .with_min_value(5.0f32)
+                    .with_max_value(500.0)
+                    .with_step(5.0)
+                    .with_precision(0)
+                    .with_value(DEFAULT_GRID_STEP)
+                    .build(ctx);
+                    grid_step
                 }),
         )
         .with_orientation(Orientation::Horizontal)
@@ -160,6 +203,9 @@ impl Toolbar {
             remove_layer,
             edit_mask,
             node_selector: Handle::NONE,
+            snap_to_grid,
+            grid_step,
+            canvases,
         }
     }
 
@@ -182,6 +228,28 @@ impl Toolbar {
                 } else {
                     ToolbarAction::LeavePreviewMode
                 };
+            } else if message.destination() == self.snap_to_grid
+                && message.direction() == MessageDirection::FromWidget
+            {
+                for &canvas in self.canvases.iter() {
+                    ui.send_message(AbsmCanvasMessage::snap_to_grid(
+                        canvas,
+                        MessageDirection::ToWidget,
+                        *value,
+                    ));
+                }
+            }
+        } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+            if message.destination() == self.grid_step
+                && message.direction() == MessageDirection::FromWidget
+            {
+                for &canvas in self.canvases.iter() {
+                    ui.send_message(AbsmCanvasMessage::grid_step(
+                        canvas,
+                        MessageDirection::ToWidget,
+                        *value,
+                    ));
+                }
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.destination() == self.layers
@@ -0,0 +1,138 @@
+//! Validation of animation blending state machines. It does not prevent saving, it just reports
+//! problems that most likely will make a machine behave incorrectly.
+
+use crate::absm::selection::SelectedEntity;
+use fyrox::{
+    animation::machine::{Machine, MachineLayer, PoseNode},
+    core::pool::Handle,
+    fxhash::FxHashSet,
+    scene::{animation::absm::AnimationBlendingStateMachine, node::Node, Scene},
+};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsmIssue {
+    pub absm_node_handle: Handle<Node>,
+    pub layer_index: usize,
+    pub entity: SelectedEntity,
+    pub message: String,
+}
+
+impl Display for AbsmIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entity = match self.entity {
+            SelectedEntity::Transition(handle) => format!("Transition {}", handle),
+            SelectedEntity::State(handle) => format!("State {}", handle),
+            SelectedEntity::PoseNode(handle) => format!("Pose node {}", handle),
+        };
+
+        write!(
+            f,
+            "Layer {}: {} - {}",
+            self.layer_index, entity, self.message
+        )
+    }
+}
+
+fn validate_layer(
+    absm_node_handle: Handle<Node>,
+    layer_index: usize,
+    layer: &MachineLayer,
+    issues: &mut Vec<AbsmIssue>,
+) {
+    let mut push = |entity, message: &str| {
+        issues.push(AbsmIssue {
+            absm_node_handle,
+            layer_index,
+            entity,
+            message: message.to_owned(),
+        })
+    };
+
+    // Find every state that can be reached from the entry state.
+    let mut reachable = FxHashSet::default();
+    let mut stack = vec![layer.entry_state()];
+    while let Some(state) = stack.pop() {
+        if layer.states().is_valid_handle(state) && reachable.insert(state) {
+            stack.extend(
+                layer
+                    .transitions()
+                    .iter()
+                    .filter(|t| t.source() == state)
+                    .map(|t| t.dest()),
+            );
+        }
+    }
+
+    for (handle, _) in layer.states().pair_iter() {
+        if !reachable.contains(&handle) {
+            push(
+                SelectedEntity::State(handle),
+                "The state is unreachable from the entry state.",
+            );
+        }
+    }
+
+    for (handle, transition) in layer.transitions().pair_iter() {
+        if transition.rule().is_empty() {
+            push(
+                SelectedEntity::Transition(handle),
+                "The transition has no rule parameter, it will never be activated.",
+            );
+        }
+    }
+
+    for (handle, node) in layer.nodes().pair_iter() {
+        let entity = SelectedEntity::PoseNode(handle);
+        let has_invalid_sources = node
+            .children()
+            .iter()
+            .any(|c| !layer.nodes().is_valid_handle(*c));
+
+        match node {
+            PoseNode::PlayAnimation(play_animation) => {
+                if play_animation.animation.is_none() {
+                    push(entity, "No animation is assigned.");
+                }
+            }
+            PoseNode::BlendAnimations(blend_animations) => {
+                if blend_animations.pose_sources.is_empty() {
+                    push(entity, "The node has no pose sources.");
+                } else if has_invalid_sources {
+                    push(entity, "Some of the pose sources are not connected.");
+                }
+            }
+            PoseNode::BlendAnimationsByIndex(blend_animations) => {
+                if blend_animations.index_parameter.is_empty() {
+                    push(entity.clone(), "Index parameter is not set.");
+                }
+                if blend_animations.inputs.is_empty() {
+                    push(entity, "The node has no inputs.");
+                } else if has_invalid_sources {
+                    push(entity, "Some of the inputs are not connected.");
+                }
+            }
+        }
+    }
+}
+
+pub fn validate_machine(absm_node_handle: Handle<Node>, machine: &Machine) -> Vec<AbsmIssue> {
+    let mut issues = Vec::new();
+    for (layer_index, layer) in machine.layers().iter().enumerate() {
+        validate_layer(absm_node_handle, layer_index, layer, &mut issues);
+    }
+    issues
+}
+
+/// Validates every animation blending state machine in the scene.
+pub fn validate_scene(scene: &Scene) -> Vec<AbsmIssue> {
+    scene
+        .graph
+        .pair_iter()
+        .filter_map(|(handle, node)| {
+            node.query_component_ref::<AnimationBlendingStateMachine>()
+                .map(|absm| validate_machine(handle, absm.machine()))
+        })
+        .flatten()
+        .collect()
+}
@@ -34,6 +34,7 @@ pub enum AssetKind {
     Sound,
     Shader,
     Absm,
+    Video,
 }
 
 impl Deref for AssetItem {
@@ -147,6 +148,11 @@ impl AssetItemBuilder {
                         kind = AssetKind::Texture;
                         Some(into_gui_texture(resource_manager.request_texture(&path)))
                     }
+                    "gif" => {
+                        // Texture loader takes the first frame, so it is used as a thumbnail.
+                        kind = AssetKind::Video;
+                        Some(into_gui_texture(resource_manager.request_texture(&path)))
+                    }
                     "fbx" | "rgs" => {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
@@ -8,10 +8,13 @@ use crate::{
             AssetInspector,
         },
         item::AssetItemBuilder,
+        texture_memory::TextureMemoryPanel,
+        video::VideoPreview,
     },
     gui::AssetItemMessage,
     preview::PreviewPanel,
-    utils::window_content,
+    settings::asset_browser::AssetBrowserSettings,
+    utils::{open_in_file_explorer, show_in_file_explorer, window_content},
     AssetItem, AssetKind, GameEngine, Message, Mode,
 };
 use fyrox::{
@@ -22,6 +25,7 @@ use fyrox::{
     gui::{
         border::BorderBuilder,
         brush::Brush,
+        button::{ButtonBuilder, ButtonContent, ButtonMessage},
         copypasta::ClipboardProvider,
         file_browser::{FileBrowserBuilder, FileBrowserMessage, Filter},
         grid::{Column, GridBuilder, Row},
@@ -31,23 +35,30 @@ use fyrox::{
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
         wrap_panel::WrapPanelBuilder,
-        BuildContext, HorizontalAlignment, Orientation, UiNode, UserInterface, VerticalAlignment,
-        BRUSH_DARK,
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment, BRUSH_DARK,
+    },
+    scene::{
+        base::BaseBuilder,
+        node::Node,
+        sound::{SoundBuilder, Status},
     },
     utils::log::Log,
 };
 use std::{
     ffi::OsStr,
     path::{Path, PathBuf},
-    process::Command,
     sync::mpsc::Sender,
 };
 
 mod inspector;
 pub mod item;
+mod texture_memory;
+mod video;
 
 struct ContextMenu {
     menu: Handle<UiNode>,
@@ -59,24 +70,6 @@ struct ContextMenu {
     placement_target: Handle<UiNode>,
 }
 
-fn execute_command(command: &mut Command) {
-    match command.spawn() {
-        Ok(mut process) => Log::verify(process.wait()),
-        Err(err) => Log::err(format!(
-            "Failed to show asset item in explorer. Reason: {:?}",
-            err
-        )),
-    }
-}
-
-fn show_in_explorer<P: AsRef<OsStr>>(path: P) {
-    execute_command(Command::new("explorer").arg("/select,").arg(path))
-}
-
-fn open_in_explorer<P: AsRef<OsStr>>(path: P) {
-    execute_command(Command::new("explorer").arg(path))
-}
-
 fn put_path_to_clipboard(engine: &mut Engine, path: &OsStr) {
     if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
         Log::verify(clipboard.set_contents(path.to_string_lossy().to_string()));
@@ -120,7 +113,7 @@ impl ContextMenu {
                         })
                         .with_child({
                             show_in_explorer = MenuItemBuilder::new(WidgetBuilder::new())
-                                .with_content(MenuItemContent::text("Show In Explorer"))
+                                .with_content(MenuItemContent::text("Show In File Explorer"))
                                 .build(ctx);
                             show_in_explorer
                         }),
@@ -154,11 +147,9 @@ impl ContextMenu {
                 if message.destination() == self.delete {
                     Log::verify(std::fs::remove_file(&item.path))
                 } else if message.destination() == self.show_in_explorer {
-                    if let Ok(canonical_path) = item.path.canonicalize() {
-                        show_in_explorer(canonical_path)
-                    }
+                    show_in_file_explorer(&item.path)
                 } else if message.destination() == self.open {
-                    open_in_explorer(&item.path)
+                    open_in_file_explorer(&item.path)
                 } else if message.destination() == self.copy_path {
                     if let Ok(canonical_path) = item.path.canonicalize() {
                         put_path_to_clipboard(engine, canonical_path.as_os_str())
@@ -173,17 +164,35 @@ impl ContextMenu {
     }
 }
 
+/// A sound that is played when the mouse cursor hovers over a sound asset.
+struct SoundPreview {
+    item: Handle<UiNode>,
+    sound: Handle<Node>,
+    elapsed: f32,
+}
+
 pub struct AssetBrowser {
     pub window: Handle<UiNode>,
     content_panel: Handle<UiNode>,
     folder_browser: Handle<UiNode>,
     scroll_panel: Handle<UiNode>,
     selected_properties: Handle<UiNode>,
+    /// Path of the selected item, it is used to refresh the properties when the selected texture
+    /// is loaded.
+    selected_path: Option<PathBuf>,
+    texture_total: Handle<UiNode>,
+    show_texture_memory: Handle<UiNode>,
+    texture_memory: TextureMemoryPanel,
+    pause_watcher: Handle<UiNode>,
+    /// Last shown state of the resource watcher: pause flag and amount of pending reloads.
+    watcher_state: (bool, usize),
     preview: PreviewPanel,
+    video_preview: VideoPreview,
     items: Vec<Handle<UiNode>>,
     item_to_select: Option<PathBuf>,
     inspector: AssetInspector,
     context_menu: ContextMenu,
+    sound_preview: Option<SoundPreview>,
 }
 
 impl AssetBrowser {
@@ -192,12 +201,16 @@ impl AssetBrowser {
         let ctx = &mut engine.user_interface.build_ctx();
 
         let inspector = AssetInspector::new(ctx, 1, 0);
+        let video_preview = VideoPreview::new(0, ctx);
 
         let content_panel;
         let folder_browser;
         let selected_properties;
+        let texture_total;
+        let show_texture_memory;
+        let pause_watcher;
         let scroll_panel;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("AssetBrowser"))
             .can_minimize(false)
             .with_title(WindowTitle::text("Asset Browser"))
             .with_content(
@@ -222,12 +235,59 @@ impl AssetBrowser {
                             GridBuilder::new(
                                 WidgetBuilder::new()
                                     .on_column(1)
-                                    .with_child({
-                                        selected_properties =
-                                            TextBuilder::new(WidgetBuilder::new().on_row(0))
-                                                .build(ctx);
-                                        selected_properties
-                                    })
+                                    .with_child(
+                                        GridBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(0)
+                                                .with_child({
+                                                    selected_properties =
+                                                        TextBuilder::new(WidgetBuilder::new())
+                                                            .build(ctx);
+                                                    selected_properties
+                                                })
+                                                .with_child({
+                                                    texture_total = TextBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .on_column(1)
+                                                            .with_margin(Thickness::left(5.0)),
+                                                    )
+                                                    .build(ctx);
+                                                    texture_total
+                                                })
+                                                .with_child({
+                                                    show_texture_memory = ButtonBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .on_column(2)
+                                                            .with_margin(Thickness::left(5.0)),
+                                                    )
+                                                    .with_text("Texture Memory...")
+                                                    .build(ctx);
+                                                    show_texture_memory
+                                                })
+                                                .with_child({
+                                                    pause_watcher = ButtonBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .on_column(3)
+                                                            .with_margin(Thickness::left(5.0))
+                                                            .with_tooltip(make_simple_tooltip(
+                                                                ctx,
+                                                                "Pauses reloading of changed \
+                                                                assets, every changed asset is \
+                                                                reloaded once when resumed.",
+                                                            )),
+                                                    )
+                                                    .with_text("Pause Watcher")
+                                                    .build(ctx);
+                                                    pause_watcher
+                                                }),
+                                        )
+                                        .add_row(Row::stretch())
+                                        .add_column(Column::stretch())
+                                        .add_column(Column::auto())
+                                        .add_column(Column::strict(110.0))
+                                        .add_column(Column::strict(130.0))
+                                        .build(ctx),
+                                    )
                                     .with_child({
                                         scroll_panel = ScrollViewerBuilder::new(
                                             WidgetBuilder::new().on_row(1),
@@ -264,6 +324,7 @@ impl AssetBrowser {
                                         GridBuilder::new(
                                             WidgetBuilder::new()
                                                 .with_child(preview.root)
+                                                .with_child(video_preview.root)
                                                 .with_child(inspector.container),
                                         )
                                         .add_column(Column::stretch())
@@ -284,23 +345,50 @@ impl AssetBrowser {
             .build(ctx);
 
         let context_menu = ContextMenu::new(ctx);
+        let texture_memory = TextureMemoryPanel::new(ctx);
 
         Self {
             window,
             content_panel,
             folder_browser,
             preview,
+            video_preview,
             scroll_panel,
             selected_properties,
+            selected_path: None,
+            texture_total,
+            show_texture_memory,
+            texture_memory,
+            pause_watcher,
+            watcher_state: (false, 0),
             items: Default::default(),
             item_to_select: None,
             inspector,
             context_menu,
+            sound_preview: None,
         }
     }
 
     pub fn clear_preview(&mut self, engine: &mut GameEngine) {
         self.preview.clear(engine);
+        self.set_video_preview(None, &engine.user_interface);
+    }
+
+    /// Shows preview of the video at the given path instead of the 3D preview, or brings the 3D
+    /// preview back if there is no video (or it cannot be loaded).
+    fn set_video_preview(&mut self, path: Option<&Path>, ui: &UserInterface) {
+        let shown = match path {
+            Some(path) => self.video_preview.set_video(path, ui),
+            None => {
+                self.video_preview.clear(ui);
+                false
+            }
+        };
+        ui.send_message(WidgetMessage::visibility(
+            self.preview.root,
+            MessageDirection::ToWidget,
+            !shown,
+        ));
     }
 
     pub fn set_working_directory(&mut self, engine: &mut GameEngine, dir: &Path) {
@@ -323,7 +411,10 @@ impl AssetBrowser {
 
         self.inspector.handle_ui_message(message, engine);
         self.preview.handle_message(message, engine);
+        self.video_preview.handle_ui_message(message);
         self.context_menu.handle_ui_message(message, engine);
+        self.texture_memory
+            .handle_ui_message(message, &mut engine.user_interface);
 
         let ui = &mut engine.user_interface;
 
@@ -341,11 +432,16 @@ impl AssetBrowser {
                 .node(message.destination())
                 .cast::<AssetItem>()
                 .expect("Must be AssetItem");
-            ui.send_message(TextMessage::text(
-                self.selected_properties,
-                MessageDirection::ToWidget,
-                format!("Path: {:?}", item.path),
-            ));
+            self.selected_path = Some(item.path.clone());
+            self.sync_selected_properties(ui);
+            self.set_video_preview(
+                if item.kind == AssetKind::Video {
+                    Some(item.path.as_path())
+                } else {
+                    None
+                },
+                ui,
+            );
 
             match item.kind {
                 AssetKind::Unknown => {}
@@ -359,11 +455,15 @@ impl AssetBrowser {
                         sender,
                     )
                 }
-                AssetKind::Texture => self.inspector.inspect_resource_import_options(
-                    TextureImportOptionsHandler::new(&item.path),
-                    &mut engine.user_interface,
-                    sender,
-                ),
+                // Animated image is still loaded as a texture (its first frame) when it is
+                // assigned to a material, so it has the same import options.
+                AssetKind::Texture | AssetKind::Video => {
+                    self.inspector.inspect_resource_import_options(
+                        TextureImportOptionsHandler::new(&item.path),
+                        &mut engine.user_interface,
+                        sender,
+                    )
+                }
                 AssetKind::Sound => self.inspector.inspect_resource_import_options(
                     SoundBufferImportOptionsHandler::new(&item.path),
                     &mut engine.user_interface,
@@ -376,12 +476,21 @@ impl AssetBrowser {
                     Log::warn("Implement me!");
                 }
             }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.show_texture_memory {
+                self.texture_memory.open(ui);
+            } else if message.destination() == self.pause_watcher {
+                let mut state = engine.resource_manager.state();
+                let paused = state.is_watcher_paused();
+                state.set_watcher_paused(!paused);
+            }
         } else if let Some(FileBrowserMessage::Path(path)) = message.data::<FileBrowserMessage>() {
             if message.destination() == self.folder_browser
                 && message.direction() == MessageDirection::FromWidget
             {
                 let item_to_select = self.item_to_select.take();
                 let mut handle_to_select = Handle::NONE;
+                let mut textures = Vec::new();
 
                 // Clean content panel first.
                 for child in self.items.drain(..) {
@@ -401,6 +510,7 @@ impl AssetBrowser {
                                     | "tga"
                                     | "png"
                                     | "bmp"
+                                    | "gif"
                                     | "ogg"
                                     | "wav"
                                     | "shader"
@@ -420,6 +530,12 @@ impl AssetBrowser {
 
                                 self.items.push(asset_item);
 
+                                if ui.node(asset_item).cast::<AssetItem>().unwrap().kind
+                                    == AssetKind::Texture
+                                {
+                                    textures.push(entry_path.clone());
+                                }
+
                                 ui.send_message(WidgetMessage::link(
                                     asset_item,
                                     MessageDirection::ToWidget,
@@ -436,6 +552,14 @@ impl AssetBrowser {
                     }
                 }
 
+                self.texture_memory
+                    .set_textures(textures, &engine.resource_manager, ui);
+                ui.send_message(TextMessage::text(
+                    self.texture_total,
+                    MessageDirection::ToWidget,
+                    self.texture_memory.total_text(),
+                ));
+
                 if handle_to_select.is_some() {
                     ui.send_message(AssetItemMessage::select(
                         handle_to_select,
@@ -463,8 +587,154 @@ impl AssetBrowser {
         self.item_to_select = Some(path);
     }
 
-    pub fn update(&mut self, engine: &mut GameEngine) {
-        self.preview.update(engine)
+    pub fn update(&mut self, engine: &mut GameEngine, settings: &AssetBrowserSettings, dt: f32) {
+        self.preview.update(engine);
+        self.video_preview.update(dt, &engine.user_interface);
+        self.update_sound_preview(engine, settings, dt);
+
+        self.sync_watcher_state(engine);
+
+        let ui = &mut engine.user_interface;
+        if self.texture_memory.update(ui) {
+            ui.send_message(TextMessage::text(
+                self.texture_total,
+                MessageDirection::ToWidget,
+                self.texture_memory.total_text(),
+            ));
+            self.sync_selected_properties(ui);
+        }
+    }
+
+    /// Shows whether the resource watcher is paused, so it is clear why changed assets are not
+    /// reloaded.
+    fn sync_watcher_state(&mut self, engine: &GameEngine) {
+        let watcher_state = {
+            let state = engine.resource_manager.state();
+            (state.is_watcher_paused(), state.pending_reload_count())
+        };
+
+        if self.watcher_state == watcher_state {
+            return;
+        }
+        self.watcher_state = watcher_state;
+
+        let (paused, pending) = watcher_state;
+        let ui = &engine.user_interface;
+        ui.send_message(ButtonMessage::content(
+            self.pause_watcher,
+            MessageDirection::ToWidget,
+            ButtonContent::text(if paused {
+                format!("Resume Watcher ({})", pending)
+            } else {
+                "Pause Watcher".to_owned()
+            }),
+        ));
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(if paused {
+                "Asset Browser - Watcher Paused"
+            } else {
+                "Asset Browser"
+            }),
+        ));
+    }
+
+    fn sync_selected_properties(&self, ui: &UserInterface) {
+        let text = match self.selected_path.as_ref() {
+            Some(path) => match self.texture_memory.describe(path) {
+                Some(description) => format!("Path: {:?}, {}", path, description),
+                None => format!("Path: {:?}", path),
+            },
+            None => String::new(),
+        };
+        ui.send_message(TextMessage::text(
+            self.selected_properties,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    fn hovered_sound_item(&self, ui: &UserInterface) -> Handle<UiNode> {
+        let picked = ui.hit_test(ui.cursor_position());
+        if picked.is_none() {
+            return Handle::NONE;
+        }
+
+        let item = ui.find_by_criteria_up(picked, |n| n.cast::<AssetItem>().is_some());
+        if self.items.contains(&item)
+            && ui.node(item).cast::<AssetItem>().unwrap().kind == AssetKind::Sound
+        {
+            item
+        } else {
+            Handle::NONE
+        }
+    }
+
+    fn stop_sound_preview(&mut self, engine: &mut GameEngine) {
+        if let Some(sound_preview) = self.sound_preview.take() {
+            let graph = &mut engine.scenes[self.preview.scene()].graph;
+            if graph.is_valid_handle(sound_preview.sound) {
+                graph.remove_node(sound_preview.sound);
+            }
+        }
+    }
+
+    fn update_sound_preview(
+        &mut self,
+        engine: &mut GameEngine,
+        settings: &AssetBrowserSettings,
+        dt: f32,
+    ) {
+        let hovered = if settings.sound_preview_on_hover {
+            self.hovered_sound_item(&engine.user_interface)
+        } else {
+            Handle::NONE
+        };
+
+        if self.sound_preview.as_ref().map(|p| p.item) != Some(hovered) {
+            self.stop_sound_preview(engine);
+
+            if hovered.is_some() {
+                let path = engine
+                    .user_interface
+                    .node(hovered)
+                    .cast::<AssetItem>()
+                    .unwrap()
+                    .path
+                    .clone();
+                let buffer = engine.resource_manager.request_sound_buffer(path);
+
+                let sound = SoundBuilder::new(BaseBuilder::new())
+                    .with_buffer(Some(buffer))
+                    .with_gain(settings.sound_preview_volume)
+                    .with_spatial_blend_factor(0.0)
+                    .with_status(Status::Playing)
+                    .build(&mut engine.scenes[self.preview.scene()].graph);
+
+                self.sound_preview = Some(SoundPreview {
+                    item: hovered,
+                    sound,
+                    elapsed: 0.0,
+                });
+            }
+        }
+
+        if let Some(sound_preview) = self.sound_preview.as_mut() {
+            sound_preview.elapsed += dt;
+
+            // Only a short fragment is needed to identify the sound. The item is still
+            // remembered, so the preview won't start again until the cursor leaves it.
+            if sound_preview.elapsed >= settings.sound_preview_duration
+                && sound_preview.sound.is_some()
+            {
+                let graph = &mut engine.scenes[self.preview.scene()].graph;
+                if graph.is_valid_handle(sound_preview.sound) {
+                    graph.remove_node(sound_preview.sound);
+                }
+                sound_preview.sound = Handle::NONE;
+            }
+        }
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
@@ -0,0 +1,446 @@
+//! Texture memory report for textures of the current folder of the asset browser. It shows
+//! dimensions, pixel format and estimated memory footprint (including mip levels) of every
+//! texture, sorted by the selected column.
+
+use fyrox::{
+    asset::ResourceState,
+    core::pool::Handle,
+    engine::resource_manager::ResourceManager,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    resource::texture::{Texture, TextureKind, TexturePixelKind},
+};
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+const COLUMN_WIDTHS: [f32; 5] = [90.0, 80.0, 40.0, 80.0, 90.0];
+
+/// Formats amount of bytes in a human-readable form.
+pub fn format_memory(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+
+    let bytes_f = bytes as f64;
+    if bytes_f >= MIB {
+        format!("{:.2} MiB", bytes_f / MIB)
+    } else if bytes_f >= KIB {
+        format!("{:.1} KiB", bytes_f / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn format_dimensions(kind: TextureKind) -> String {
+    match kind {
+        TextureKind::Line { length } => format!("{}", length),
+        TextureKind::Rectangle { width, height } => format!("{}x{}", width, height),
+        TextureKind::Cube { width, height } => format!("{}x{}x6", width, height),
+        TextureKind::Volume {
+            width,
+            height,
+            depth,
+        } => format!("{}x{}x{}", width, height, depth),
+        TextureKind::Array {
+            width,
+            height,
+            layers,
+        } => format!("{}x{}, {} layer(s)", width, height, layers),
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TextureInfo {
+    kind: TextureKind,
+    pixel_kind: TexturePixelKind,
+    mip_count: u32,
+    memory: usize,
+    uncompressed_memory: usize,
+}
+
+impl TextureInfo {
+    fn describe(&self) -> String {
+        let mut text = format!(
+            "{} {:?}, {} mip(s), {}",
+            format_dimensions(self.kind),
+            self.pixel_kind,
+            self.mip_count,
+            format_memory(self.memory)
+        );
+        if self.pixel_kind.is_compressed() {
+            text += &format!(
+                " (uncompressed {})",
+                format_memory(self.uncompressed_memory)
+            );
+        }
+        text
+    }
+}
+
+enum EntryState {
+    Loading,
+    Failed,
+    Loaded(TextureInfo),
+}
+
+struct TextureEntry {
+    path: PathBuf,
+    texture: Texture,
+    state: EntryState,
+}
+
+impl TextureEntry {
+    fn info(&self) -> Option<&TextureInfo> {
+        match self.state {
+            EntryState::Loaded(ref info) => Some(info),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SortKey {
+    Name,
+    Dimensions,
+    Format,
+    Memory,
+}
+
+pub struct TextureMemoryPanel {
+    pub window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    total: Handle<UiNode>,
+    headers: Vec<(Handle<UiNode>, SortKey)>,
+    entries: Vec<TextureEntry>,
+    sort_key: SortKey,
+    descending: bool,
+}
+
+fn make_header(ctx: &mut BuildContext, text: &str, column: usize) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_cell(ctx: &mut BuildContext, text: String, column: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_column(column)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_text(text)
+    .build(ctx)
+}
+
+fn add_columns(grid: GridBuilder) -> GridBuilder {
+    COLUMN_WIDTHS
+        .iter()
+        .fold(grid.add_column(Column::stretch()), |grid, width| {
+            grid.add_column(Column::strict(*width))
+        })
+}
+
+impl TextureMemoryPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let total;
+        let headers = vec![
+            (make_header(ctx, "Name", 0), SortKey::Name),
+            (make_header(ctx, "Size", 1), SortKey::Dimensions),
+            (make_header(ctx, "Format", 2), SortKey::Format),
+            (make_header(ctx, "Memory", 4), SortKey::Memory),
+        ];
+        let mips = make_header(ctx, "Mips", 3);
+        let uncompressed = make_header(ctx, "Uncompressed", 5);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(560.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Texture Memory"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            add_columns(GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_children(headers.iter().map(|(h, _)| *h))
+                                    .with_child(mips)
+                                    .with_child(uncompressed),
+                            ))
+                            .add_row(Row::stretch())
+                            .build(ctx),
+                        )
+                        .with_child({
+                            list = ListViewBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+                            list
+                        })
+                        .with_child({
+                            total = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_horizontal_text_alignment(HorizontalAlignment::Right)
+                            .build(ctx);
+                            total
+                        }),
+                )
+                .add_row(Row::strict(24.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(22.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            list,
+            total,
+            headers,
+            entries: Default::default(),
+            sort_key: SortKey::Memory,
+            descending: true,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Starts tracking given set of textures, previous set is discarded.
+    pub fn set_textures(
+        &mut self,
+        paths: Vec<PathBuf>,
+        resource_manager: &ResourceManager,
+        ui: &mut UserInterface,
+    ) {
+        self.entries = paths
+            .into_iter()
+            .map(|path| TextureEntry {
+                texture: resource_manager.request_texture(&path),
+                path,
+                state: EntryState::Loading,
+            })
+            .collect();
+
+        self.sync_to_model(ui);
+    }
+
+    /// Checks loading textures and refreshes the report when some of them are loaded. Returns
+    /// `true` if the report has changed.
+    pub fn update(&mut self, ui: &mut UserInterface) -> bool {
+        let mut changed = false;
+
+        for entry in self.entries.iter_mut() {
+            if !matches!(entry.state, EntryState::Loading) {
+                continue;
+            }
+
+            let new_state = match *entry.texture.state() {
+                ResourceState::Pending { .. } => continue,
+                ResourceState::LoadError { .. } => EntryState::Failed,
+                ResourceState::Ok(ref data) => EntryState::Loaded(TextureInfo {
+                    kind: data.kind(),
+                    pixel_kind: data.pixel_kind(),
+                    mip_count: data.mip_count(),
+                    memory: data.memory_usage(),
+                    uncompressed_memory: data.uncompressed_memory_usage(),
+                }),
+            };
+
+            entry.state = new_state;
+            changed = true;
+        }
+
+        if changed {
+            self.sync_to_model(ui);
+        }
+
+        changed
+    }
+
+    /// Returns total memory footprint of loaded textures with and without compression.
+    pub fn total(&self) -> (usize, usize) {
+        self.entries
+            .iter()
+            .filter_map(|e| e.info())
+            .fold((0, 0), |(memory, uncompressed), info| {
+                (
+                    memory + info.memory,
+                    uncompressed + info.uncompressed_memory,
+                )
+            })
+    }
+
+    /// Returns a short summary of the total memory footprint of textures in the folder.
+    pub fn total_text(&self) -> String {
+        if self.entries.is_empty() {
+            return String::new();
+        }
+
+        let (memory, uncompressed) = self.total();
+        let loading = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e.state, EntryState::Loading))
+            .count();
+
+        let mut text = format!(
+            "Textures: {} ({} uncompressed)",
+            format_memory(memory),
+            format_memory(uncompressed)
+        );
+        if loading > 0 {
+            text += &format!(", {} loading...", loading);
+        }
+        text
+    }
+
+    /// Returns description of a texture at given path, if it is loaded.
+    pub fn describe(&self, path: &Path) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|e| e.path == path)
+            .and_then(|e| e.info())
+            .map(|info| info.describe())
+    }
+
+    fn compare(&self, a: &TextureEntry, b: &TextureEntry) -> Ordering {
+        let ordering = match (a.info(), b.info()) {
+            (Some(info_a), Some(info_b)) => match self.sort_key {
+                SortKey::Name => a.name().cmp(&b.name()),
+                SortKey::Dimensions => {
+                    let pixels = |info: &TextureInfo| match info.kind {
+                        TextureKind::Line { length } => length as u64,
+                        TextureKind::Rectangle { width, height } => width as u64 * height as u64,
+                        TextureKind::Cube { width, height } => 6 * width as u64 * height as u64,
+                        TextureKind::Volume {
+                            width,
+                            height,
+                            depth,
+                        } => width as u64 * height as u64 * depth as u64,
+                        TextureKind::Array {
+                            width,
+                            height,
+                            layers,
+                        } => width as u64 * height as u64 * layers as u64,
+                    };
+                    pixels(info_a).cmp(&pixels(info_b))
+                }
+                SortKey::Format => {
+                    format!("{:?}", info_a.pixel_kind).cmp(&format!("{:?}", info_b.pixel_kind))
+                }
+                SortKey::Memory => info_a.memory.cmp(&info_b.memory),
+            },
+            // Textures without information are always at the end of the list.
+            (Some(_), None) => return Ordering::Less,
+            (None, Some(_)) => return Ordering::Greater,
+            (None, None) => a.name().cmp(&b.name()),
+        };
+
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    fn sync_to_model(&mut self, ui: &mut UserInterface) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_by(|a, b| self.compare(a, b));
+        self.entries = entries;
+
+        let items = {
+            let ctx = &mut ui.build_ctx();
+            self.entries
+                .iter()
+                .map(|entry| {
+                    let name = make_cell(ctx, entry.name(), 0);
+                    let cells = match entry.state {
+                        EntryState::Loaded(info) => vec![
+                            make_cell(ctx, format_dimensions(info.kind), 1),
+                            make_cell(ctx, format!("{:?}", info.pixel_kind), 2),
+                            make_cell(ctx, info.mip_count.to_string(), 3),
+                            make_cell(ctx, format_memory(info.memory), 4),
+                            make_cell(
+                                ctx,
+                                if info.pixel_kind.is_compressed() {
+                                    format_memory(info.uncompressed_memory)
+                                } else {
+                                    "-".to_owned()
+                                },
+                                5,
+                            ),
+                        ],
+                        EntryState::Loading => vec![make_cell(ctx, "Loading...".to_owned(), 1)],
+                        EntryState::Failed => vec![make_cell(ctx, "Failed to load".to_owned(), 1)],
+                    };
+
+                    add_columns(GridBuilder::new(
+                        WidgetBuilder::new().with_child(name).with_children(cells),
+                    ))
+                    .add_row(Row::strict(20.0))
+                    .build(ctx)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(TextMessage::text(
+            self.total,
+            MessageDirection::ToWidget,
+            self.total_text(),
+        ));
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &mut UserInterface) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if let Some((_, key)) = self
+                .headers
+                .iter()
+                .find(|(header, _)| *header == message.destination())
+            {
+                if self.sort_key == *key {
+                    self.descending = !self.descending;
+                } else {
+                    self.sort_key = *key;
+                    // Larger textures are more interesting, names are sorted alphabetically.
+                    self.descending = *key != SortKey::Name;
+                }
+
+                self.sync_to_model(ui);
+            }
+        }
+    }
+}
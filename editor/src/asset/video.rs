@@ -0,0 +1,249 @@
+//! Preview of animated images (videos) in the asset browser. The video is played on a texture
+//! which is shown instead of the 3D preview.
+
+use fyrox::{
+    core::{futures::executor::block_on, pool::Handle},
+    gui::{
+        button::{ButtonBuilder, ButtonContent, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        grid::{Column, GridBuilder, Row},
+        image::{ImageBuilder, ImageMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::video::{Video, VideoPlayer},
+    utils::{into_gui_texture, log::Log},
+};
+use std::path::Path;
+
+/// Maximum size of the image that shows the video, the video is scaled to fit it keeping its
+/// aspect ratio.
+const MAX_IMAGE_WIDTH: f32 = 240.0;
+const MAX_IMAGE_HEIGHT: f32 = 200.0;
+
+pub struct VideoPreview {
+    pub root: Handle<UiNode>,
+    image: Handle<UiNode>,
+    play: Handle<UiNode>,
+    rewind: Handle<UiNode>,
+    loop_check: Handle<UiNode>,
+    info: Handle<UiNode>,
+    player: Option<VideoPlayer>,
+    looping: bool,
+    /// Last shown state of the play button.
+    playing: bool,
+}
+
+impl VideoPreview {
+    pub fn new(row: usize, ctx: &mut BuildContext) -> Self {
+        let image;
+        let play;
+        let rewind;
+        let loop_check;
+        let info;
+        let root = GridBuilder::new(
+            WidgetBuilder::new()
+                .on_row(row)
+                .with_visibility(false)
+                .with_margin(Thickness::uniform(2.0))
+                .with_child(
+                    StackPanelBuilder::new(
+                        WidgetBuilder::new()
+                            .with_height(22.0)
+                            .on_row(0)
+                            .with_child({
+                                play = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(50.0)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Pause")
+                                .build(ctx);
+                                play
+                            })
+                            .with_child({
+                                rewind = ButtonBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Rewind")
+                                .build(ctx);
+                                rewind
+                            })
+                            .with_child({
+                                loop_check = CheckBoxBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                )
+                                .checked(Some(true))
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Loop")
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                loop_check
+                            }),
+                    )
+                    .with_orientation(Orientation::Horizontal)
+                    .build(ctx),
+                )
+                .with_child({
+                    image = ImageBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .with_horizontal_alignment(HorizontalAlignment::Center)
+                            .with_vertical_alignment(VerticalAlignment::Center),
+                    )
+                    .build(ctx);
+                    image
+                })
+                .with_child({
+                    info = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(2)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .build(ctx);
+                    info
+                }),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::stretch())
+        .add_row(Row::auto())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        Self {
+            root,
+            image,
+            play,
+            rewind,
+            loop_check,
+            info,
+            player: None,
+            looping: true,
+            playing: true,
+        }
+    }
+
+    /// Loads a video from the given path and starts playing it. Returns `false` if the video
+    /// cannot be loaded.
+    pub fn set_video(&mut self, path: &Path, ui: &UserInterface) -> bool {
+        self.clear(ui);
+
+        let video = match block_on(Video::load_from_file(path)) {
+            Ok(video) => video,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load video {}. Reason: {}",
+                    path.display(),
+                    e
+                ));
+                return false;
+            }
+        };
+
+        let scale =
+            (MAX_IMAGE_WIDTH / video.width() as f32).min(MAX_IMAGE_HEIGHT / video.height() as f32);
+        ui.send_message(WidgetMessage::width(
+            self.image,
+            MessageDirection::ToWidget,
+            video.width() as f32 * scale,
+        ));
+        ui.send_message(WidgetMessage::height(
+            self.image,
+            MessageDirection::ToWidget,
+            video.height() as f32 * scale,
+        ));
+        ui.send_message(TextMessage::text(
+            self.info,
+            MessageDirection::ToWidget,
+            format!(
+                "{}x{}, {} frames, {:.2} s",
+                video.width(),
+                video.height(),
+                video.frames().len(),
+                video.duration()
+            ),
+        ));
+
+        let mut player = VideoPlayer::new(video);
+        player.set_looping(self.looping);
+        ui.send_message(ImageMessage::texture(
+            self.image,
+            MessageDirection::ToWidget,
+            Some(into_gui_texture(player.texture())),
+        ));
+        self.player = Some(player);
+
+        ui.send_message(WidgetMessage::visibility(
+            self.root,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        true
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        if self.player.take().is_some() {
+            ui.send_message(ImageMessage::texture(
+                self.image,
+                MessageDirection::ToWidget,
+                None,
+            ));
+            ui.send_message(WidgetMessage::visibility(
+                self.root,
+                MessageDirection::ToWidget,
+                false,
+            ));
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if let Some(player) = self.player.as_mut() {
+                if message.destination() == self.play {
+                    if player.is_playing() {
+                        player.pause();
+                    } else {
+                        player.play();
+                    }
+                } else if message.destination() == self.rewind {
+                    player.rewind();
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.loop_check
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.looping = *value;
+                if let Some(player) = self.player.as_mut() {
+                    player.set_looping(*value);
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, ui: &UserInterface) {
+        if let Some(player) = self.player.as_mut() {
+            player.update(dt);
+
+            // Non-looping video stops by itself, so the button is synced here.
+            if player.is_playing() != self.playing {
+                self.playing = player.is_playing();
+                ui.send_message(ButtonMessage::content(
+                    self.play,
+                    MessageDirection::ToWidget,
+                    ButtonContent::text(if self.playing { "Pause" } else { "Play" }),
+                ));
+            }
+        }
+    }
+}
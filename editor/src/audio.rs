@@ -59,7 +59,7 @@ impl AudioPanel {
         let edit_context;
         let add_effect;
         let effects;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("AudioPanel"))
             .with_content(
                 GridBuilder::new(
                     WidgetBuilder::new()
@@ -1,15 +1,22 @@
 use crate::settings::keys::KeyBindings;
-use crate::{settings::camera::CameraSettings, utils::built_in_skybox, SceneCameraSettings};
+use crate::{
+    scene::calculate_scene_bounding_box, settings::camera::CameraSettings, utils::built_in_skybox,
+    SceneCameraSettings,
+};
 use fyrox::{
     core::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
-        math::{plane::Plane, ray::Ray, Matrix4Ext, TriangleDefinition, Vector3Ext},
+        arrayvec::ArrayVec,
+        math::{
+            aabb::AxisAlignedBoundingBox, lerpf, plane::Plane, ray::Ray, Matrix4Ext,
+            TriangleDefinition, Vector3Ext,
+        },
         pool::Handle,
     },
     gui::message::{KeyCode, MouseButton},
     scene::{
         base::BaseBuilder,
-        camera::{Camera, CameraBuilder, Exposure, Projection},
+        camera::{Camera, CameraBuilder, Exposure, OrthographicProjection, Projection},
         graph::Graph,
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
@@ -19,6 +26,7 @@ use fyrox::{
         node::Node,
         pivot::PivotBuilder,
         sound::listener::ListenerBuilder,
+        terrain::{Terrain, TerrainRayCastResult},
         transform::TransformBuilder,
     },
 };
@@ -29,6 +37,12 @@ use std::{
 
 pub const DEFAULT_Z_OFFSET: f32 = -3.0;
 
+/// Exposure of the editor camera when post-processing preview is disabled.
+pub const NEUTRAL_EXPOSURE: Exposure = Exposure::Manual(std::f32::consts::E);
+
+// How fast the camera approaches its destination when framing, larger values mean faster movement.
+const FLY_SPEED: f32 = 10.0;
+
 pub struct CameraController {
     pub pivot: Handle<Node>,
     pub camera: Handle<Node>,
@@ -48,6 +62,24 @@ pub struct CameraController {
     stack: Vec<Handle<Node>>,
     editor_context: PickContext,
     scene_context: PickContext,
+    follow: Option<FollowState>,
+    fly_to: Option<FlyToState>,
+}
+
+// Destination of a smooth camera movement.
+struct FlyToState {
+    position: Vector3<f32>,
+    yaw: f32,
+    pitch: f32,
+    // Only used by orthographic projection.
+    vertical_size: f32,
+}
+
+struct FollowState {
+    node: Handle<Node>,
+    // Offset of the camera pivot relative to the followed node.
+    offset: Vector3<f32>,
+    last_position: Vector3<f32>,
 }
 
 #[derive(Clone)]
@@ -97,10 +129,18 @@ impl CameraController {
                             .with_children(&[ListenerBuilder::new(BaseBuilder::new()).build(graph)])
                             .with_name("EditorCamera"),
                     )
-                    .with_exposure(Exposure::Manual(std::f32::consts::E))
+                    .with_exposure(NEUTRAL_EXPOSURE)
                     .with_skybox(built_in_skybox())
                     .with_z_far(512.0)
                     .build(graph);
+                    if settings.orthographic {
+                        graph[camera]
+                            .as_camera_mut()
+                            .set_projection(Projection::Orthographic(OrthographicProjection {
+                                vertical_size: settings.vertical_size,
+                                ..Default::default()
+                            }));
+                    }
                     camera
                 }])
                 .with_name("EditorCameraPivot")
@@ -133,6 +173,151 @@ impl CameraController {
             stack: Default::default(),
             editor_context: Default::default(),
             scene_context: Default::default(),
+            follow: None,
+            fly_to: None,
+        }
+    }
+
+    /// Smoothly moves the camera so the given world-space bounding box fits into the view. Current
+    /// orientation of the camera is preserved.
+    pub fn frame_bounding_box(&mut self, graph: &Graph, bounding_box: &AxisAlignedBoundingBox) {
+        let center = bounding_box.center();
+        let half_extents = bounding_box.half_extents();
+        // Prevent zero-sized boxes from putting the camera right into the object.
+        let radius = half_extents.norm().max(0.1);
+
+        let (position, vertical_size) = match graph[self.camera].as_camera().projection() {
+            Projection::Perspective(perspective) => {
+                let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.yaw)
+                    * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.pitch);
+                let look = rotation * Vector3::z();
+                let distance = radius / (perspective.fov * 0.5).sin().max(f32::EPSILON);
+                (center - look.scale(distance), radius)
+            }
+            Projection::Orthographic(_) => (
+                Vector3::new(center.x, center.y, DEFAULT_Z_OFFSET),
+                half_extents.x.max(half_extents.y).max(0.1) * 1.1,
+            ),
+        };
+
+        self.follow = None;
+        self.fly_to = Some(FlyToState {
+            position,
+            yaw: self.yaw,
+            pitch: self.pitch,
+            vertical_size,
+        });
+    }
+
+    /// Smoothly moves the camera so the whole scene fits into the view. Editor objects are ignored.
+    /// If the scene is empty, the camera returns to its default view.
+    pub fn frame_scene(&mut self, graph: &Graph, editor_objects_root: Handle<Node>) {
+        if let Some(bounding_box) = calculate_scene_bounding_box(graph, editor_objects_root, true) {
+            self.frame_bounding_box(graph, &bounding_box);
+        } else {
+            let settings = SceneCameraSettings::default();
+            self.follow = None;
+            self.fly_to = Some(FlyToState {
+                position: settings.position,
+                yaw: settings.yaw,
+                pitch: settings.pitch,
+                vertical_size: OrthographicProjection::default().vertical_size,
+            });
+        }
+    }
+
+    /// Makes the camera follow the given node. Current offset between the camera and the node
+    /// is preserved while following.
+    pub fn follow(&mut self, graph: &Graph, node: Handle<Node>) {
+        if let Some(target) = graph.try_get(node) {
+            let position = **graph[self.pivot].local_transform().position();
+            self.follow = Some(FollowState {
+                node,
+                offset: position - target.global_position(),
+                last_position: position,
+            });
+        }
+    }
+
+    /// Returns `true` if the camera is being moved by the user right now (rotated, dragged or moved
+    /// by the keys).
+    pub fn is_moving(&self) -> bool {
+        self.rotate
+            || self.drag
+            || self.move_left
+            || self.move_right
+            || self.move_forward
+            || self.move_backward
+            || self.move_up
+            || self.move_down
+    }
+
+    /// Stops following a node, the camera stays where it is.
+    pub fn stop_following(&mut self) {
+        self.follow = None;
+    }
+
+    pub fn followed_node(&self) -> Handle<Node> {
+        self.follow
+            .as_ref()
+            .map_or_else(Handle::default, |f| f.node)
+    }
+
+    /// Returns normalized direction the camera is looking at.
+    pub fn look_direction(&self) -> Vector3<f32> {
+        (UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.yaw)
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.pitch))
+            * Vector3::z()
+    }
+
+    /// Instantly moves the camera to the given position and orients it along the given look
+    /// direction, the direction does not need to be normalized. Returns `false` and leaves the
+    /// camera untouched if the position is not finite or the direction has zero length.
+    pub fn teleport(
+        &mut self,
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        look_direction: Vector3<f32>,
+    ) -> bool {
+        if !position.iter().all(|c| c.is_finite()) || !look_direction.iter().all(|c| c.is_finite())
+        {
+            return false;
+        }
+
+        let look = match look_direction.try_normalize(f32::EPSILON) {
+            Some(look) => look,
+            None => return false,
+        };
+
+        self.pitch = (-look.y).clamp(-1.0, 1.0).asin();
+        // Yaw is undefined when looking straight up or down, keep the current one.
+        if look.x.abs() > f32::EPSILON || look.z.abs() > f32::EPSILON {
+            self.yaw = look.x.atan2(look.z);
+        }
+
+        self.follow = None;
+        self.fly_to = None;
+        graph[self.pivot]
+            .local_transform_mut()
+            .set_position(position);
+
+        true
+    }
+
+    /// Returns index of the world axis (0 - X, 1 - Y, 2 - Z) the camera looks along, if the camera
+    /// is orthographic and its view is aligned with one of the world axes. `None` otherwise.
+    pub fn orthographic_view_axis(&self, graph: &Graph) -> Option<usize> {
+        let camera = graph[self.camera].as_camera();
+        if !matches!(camera.projection(), Projection::Orthographic(_)) {
+            return None;
+        }
+
+        let look = camera.look_vector().try_normalize(f32::EPSILON)?;
+        let axis = look.iamax();
+        if look[axis].abs() > 0.999 {
+            Some(axis)
+        } else {
+            None
         }
     }
 
@@ -268,7 +453,54 @@ impl CameraController {
         graph[self.pivot].global_position()
     }
 
+    /// Returns current state of the camera, that can be used to restore it later.
+    pub fn scene_camera_settings(&self, graph: &Graph) -> SceneCameraSettings {
+        let mut settings = SceneCameraSettings {
+            position: self.position(graph),
+            yaw: self.yaw,
+            pitch: self.pitch,
+            ..Default::default()
+        };
+        if let Projection::Orthographic(ortho) = graph[self.camera].as_camera().projection() {
+            settings.orthographic = true;
+            settings.vertical_size = ortho.vertical_size;
+        }
+        settings
+    }
+
     pub fn update(&mut self, graph: &mut Graph, settings: &CameraSettings, dt: f32) {
+        // Any manual movement interrupts framing.
+        if self.is_moving() {
+            self.fly_to = None;
+        }
+
+        if let Some(fly_to) = self.fly_to.as_ref() {
+            let k = 1.0 - (-FLY_SPEED * dt).exp();
+
+            self.yaw = lerpf(self.yaw, fly_to.yaw, k);
+            self.pitch = lerpf(self.pitch, fly_to.pitch, k);
+
+            let camera = graph[self.camera].as_camera_mut();
+            let mut size_reached = true;
+            if let Projection::Orthographic(ortho) = camera.projection_mut() {
+                ortho.vertical_size = lerpf(ortho.vertical_size, fly_to.vertical_size, k);
+                size_reached = (ortho.vertical_size - fly_to.vertical_size).abs() < 0.001;
+            }
+
+            let transform = graph[self.pivot].local_transform_mut();
+            let position = **transform.position();
+            let new_position = position.lerp(&fly_to.position, k);
+            transform.set_position(new_position);
+
+            if size_reached
+                && new_position.metric_distance(&fly_to.position) < 0.001
+                && (self.yaw - fly_to.yaw).abs() < 0.001
+                && (self.pitch - fly_to.pitch).abs() < 0.001
+            {
+                self.fly_to = None;
+            }
+        }
+
         let camera = graph[self.camera].as_camera_mut();
 
         match camera.projection_value() {
@@ -367,6 +599,21 @@ impl CameraController {
 
         self.drag_side = 0.0;
         self.drag_up = 0.0;
+
+        if let Some(follow) = self.follow.as_mut() {
+            if let Some(target) = graph.try_get(follow.node).map(|n| n.global_position()) {
+                let transform = graph[self.pivot].local_transform_mut();
+                // Manual camera movement changes the offset, so the view still can be adjusted
+                // while following.
+                follow.offset += **transform.position() - follow.last_position;
+                let new_position = target + follow.offset;
+                transform.set_position(new_position);
+                follow.last_position = new_position;
+            } else {
+                // Followed node was deleted.
+                self.follow = None;
+            }
+        }
     }
 
     pub fn pick<F>(&mut self, options: PickingOptions<'_, F>) -> Option<CameraPickResult>
@@ -456,7 +703,9 @@ impl CameraController {
                 .pick_list
                 .sort_by(|a, b| a.toi.partial_cmp(&b.toi).unwrap());
 
-            if use_picking_loop {
+            // Picks without the loop (hover highlight, drag'n'drop, etc.) must not affect the loop
+            // state, otherwise they would break cycling of overlapping objects by clicks.
+            let index = if use_picking_loop {
                 let mut hasher = DefaultHasher::new();
                 for result in context.pick_list.iter() {
                     result.node.hash(&mut hasher);
@@ -476,15 +725,14 @@ impl CameraController {
                     context.pick_index = 0;
                 }
                 context.old_selection_hash = selection_hash;
+                context.old_cursor_pos = cursor_pos;
+                context.pick_index
             } else {
-                context.pick_index = 0;
-            }
-            context.old_cursor_pos = cursor_pos;
+                0
+            };
 
-            if !context.pick_list.is_empty() {
-                if let Some(result) = context.pick_list.get(context.pick_index) {
-                    return Some(result.clone());
-                }
+            if let Some(result) = context.pick_list.get(index) {
+                return Some(result.clone());
             }
         }
 
@@ -575,3 +823,91 @@ fn precise_ray_test(
 
     closest_point.map(|pt| (closest_distance, pt))
 }
+
+// Length of the ray used to find a surface below a point, it should be enough for any sane scene.
+const SURFACE_RAY_LENGTH: f32 = 10000.0;
+
+/// Casts a ray straight down from the given point and returns world-space normal of the closest
+/// surface (a mesh triangle or a terrain) hit by the ray. Returned normal always faces up, so it
+/// can be used directly as an "up" axis for objects standing on the surface. Editor objects,
+/// invisible nodes and nodes rejected by the filter are ignored.
+pub fn surface_normal_below<F>(
+    graph: &Graph,
+    point: Vector3<f32>,
+    editor_objects_root: Handle<Node>,
+    mut filter: F,
+) -> Option<Vector3<f32>>
+where
+    F: FnMut(Handle<Node>, &Node) -> bool,
+{
+    let ray = Ray::new(point, Vector3::new(0.0, -SURFACE_RAY_LENGTH, 0.0));
+
+    let mut closest_toi = f32::MAX;
+    let mut closest_normal = None;
+
+    let mut stack = vec![graph.get_root()];
+    while let Some(handle) = stack.pop() {
+        if handle == editor_objects_root {
+            continue;
+        }
+
+        let node = &graph[handle];
+
+        stack.extend_from_slice(node.children());
+
+        if handle == graph.get_root() || !node.global_visibility() || !filter(handle, node) {
+            continue;
+        }
+
+        if let Some(terrain) = node.cast::<Terrain>() {
+            let mut results = ArrayVec::<TerrainRayCastResult, 1>::new();
+            if terrain.raycast(ray, &mut results, true) {
+                let result = &results[0];
+                if result.toi < closest_toi {
+                    closest_toi = result.toi;
+                    closest_normal = Some(result.normal);
+                }
+            }
+        } else if let Some(mesh) = node.cast::<Mesh>() {
+            let object_space_ray =
+                ray.transform(node.global_transform().try_inverse().unwrap_or_default());
+
+            // Do coarse, but fast, intersection test with bounding box first.
+            if object_space_ray
+                .aabb_intersection(&node.local_bounding_box())
+                .is_none()
+            {
+                continue;
+            }
+
+            let transform = mesh.global_transform();
+
+            for surface in mesh.surfaces().iter() {
+                let data = surface.data();
+                let data = data.lock();
+
+                for triangle in data
+                    .geometry_buffer
+                    .iter()
+                    .filter_map(|t| read_triangle(&data, t, &transform))
+                {
+                    if let Some(pt) = ray.triangle_intersection_point(&triangle) {
+                        let toi = pt.metric_distance(&ray.origin) / SURFACE_RAY_LENGTH;
+                        if toi < closest_toi {
+                            let normal =
+                                (triangle[1] - triangle[0]).cross(&(triangle[2] - triangle[0]));
+                            if let Some(normal) = normal.try_normalize(f32::EPSILON) {
+                                closest_toi = toi;
+                                // Back faces are hit too, so flip the normal to face the ray.
+                                closest_normal =
+                                    Some(if normal.y < 0.0 { -normal } else { normal });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    closest_normal
+}
@@ -6,18 +6,60 @@ pub mod universal;
 
 #[macro_export]
 macro_rules! define_command_stack {
-    ($command_trait:ident, $command_stack:ident, $context:ty) => {
+    ($command_trait:ident, $command_stack:ident, $context:ty, $transaction:ident) => {
         pub trait $command_trait: Debug + 'static {
             fn name(&mut self, context: &$context) -> String;
             fn execute(&mut self, context: &mut $context);
             fn revert(&mut self, context: &mut $context);
             fn finalize(&mut self, _: &mut $context) {}
+            /// Returns `true` if the command changes nothing but the selection.
+            fn is_selection_change(&self) -> bool {
+                false
+            }
+        }
+
+        /// A named group of commands that were done between `begin_transaction` and
+        /// `end_transaction` calls. It is shown as a single entry in the command stack and it is
+        /// undone and redone as a whole.
+        #[derive(Debug)]
+        pub struct $transaction {
+            name: String,
+            commands: Vec<Box<dyn $command_trait>>,
+        }
+
+        impl $command_trait for $transaction {
+            fn name(&mut self, _context: &$context) -> String {
+                self.name.clone()
+            }
+
+            fn execute(&mut self, context: &mut $context) {
+                for command in self.commands.iter_mut() {
+                    command.execute(context);
+                }
+            }
+
+            fn revert(&mut self, context: &mut $context) {
+                // Revert must be done in reverse order.
+                for command in self.commands.iter_mut().rev() {
+                    command.revert(context);
+                }
+            }
+
+            fn finalize(&mut self, context: &mut $context) {
+                for mut command in self.commands.drain(..) {
+                    command.finalize(context);
+                }
+            }
         }
 
         pub struct $command_stack {
             commands: Vec<Box<dyn $command_trait>>,
             top: Option<usize>,
             debug: bool,
+            transaction: Option<$transaction>,
+            transaction_depth: usize,
+            max_size: usize,
+            trimmed: usize,
         }
 
         impl $command_stack {
@@ -26,6 +68,60 @@ macro_rules! define_command_stack {
                     commands: Default::default(),
                     top: None,
                     debug,
+                    transaction: None,
+                    transaction_depth: 0,
+                    max_size: 0,
+                    trimmed: 0,
+                }
+            }
+
+            /// Sets maximum amount of entries in the stack, 0 means unlimited. When the limit is
+            /// exceeded, the oldest commands are dropped. The limit is applied when next command
+            /// is done.
+            pub fn set_max_size(&mut self, max_size: usize) {
+                self.max_size = max_size;
+            }
+
+            /// Returns amount of the oldest commands that were dropped because of the size limit
+            /// since the last [`Self::clear`] call.
+            pub fn trimmed(&self) -> usize {
+                self.trimmed
+            }
+
+            /// Starts a named transaction, every command done until the matching
+            /// [`Self::end_transaction`] call will be put in a single entry of the stack. Nested
+            /// transactions are merged into the outermost one, their names are ignored.
+            pub fn begin_transaction(&mut self, name: &str) {
+                self.transaction_depth += 1;
+                if self.transaction.is_none() {
+                    self.transaction = Some($transaction {
+                        name: name.to_owned(),
+                        commands: Default::default(),
+                    });
+                }
+            }
+
+            /// Ends current transaction. When the outermost transaction is ended, all the
+            /// commands that were done in it are put in the stack as a single entry.
+            pub fn end_transaction(&mut self) {
+                match self.transaction_depth {
+                    0 => {
+                        if self.debug {
+                            println!("Attempt to end a transaction that wasn't started!");
+                        }
+                    }
+                    1 => self.commit_transaction(),
+                    _ => self.transaction_depth -= 1,
+                }
+            }
+
+            fn commit_transaction(&mut self) {
+                self.transaction_depth = 0;
+                if let Some(transaction) = self.transaction.take() {
+                    // Empty transaction does not occupy a place in the stack, see `do_command`.
+                    if !transaction.commands.is_empty() {
+                        self.commands.push(Box::new(transaction));
+                    }
                 }
             }
 
@@ -34,24 +130,14 @@ macro_rules! define_command_stack {
                 mut command: Box<dyn $command_trait>,
                 mut context: $context,
             ) {
-                if self.commands.is_empty() {
-                    self.top = Some(0);
-                } else {
-                    // Advance top
-                    match self.top.as_mut() {
-                        None => self.top = Some(0),
-                        Some(top) => *top += 1,
-                    }
-                    // Drop everything after top.
-                    let top = self.top.unwrap_or(0);
-                    if top < self.commands.len() {
-                        for mut dropped_command in self.commands.drain(top..) {
-                            if self.debug {
-                                println!("Finalizing command {:?}", dropped_command);
-                            }
-                            dropped_command.finalize(&mut context);
-                        }
-                    }
+                // Commands of a transaction share a single place in the stack, which is reserved
+                // by the first command.
+                if self
+                    .transaction
+                    .as_ref()
+                    .map_or(true, |t| t.commands.is_empty())
+                {
+                    self.advance_top(&mut context);
                 }
 
                 if self.debug {
@@ -60,10 +146,56 @@ macro_rules! define_command_stack {
 
                 command.execute(&mut context);
 
-                self.commands.push(command);
+                if let Some(transaction) = self.transaction.as_mut() {
+                    transaction.commands.push(command);
+                } else {
+                    self.commands.push(command);
+                }
+            }
+
+            fn advance_top(&mut self, context: &mut $context) {
+                // Drop everything after top, these commands cannot be redone anymore.
+                let first_undone = self.top.map_or(0, |top| top + 1);
+                if first_undone < self.commands.len() {
+                    for mut dropped_command in self.commands.drain(first_undone..) {
+                        if self.debug {
+                            println!("Finalizing command {:?}", dropped_command);
+                        }
+                        dropped_command.finalize(context);
+                    }
+                }
+
+                // Drop the oldest commands to make a place for the new one if the stack is full.
+                if self.max_size > 0 {
+                    self.trim(self.max_size - 1, context);
+                }
+
+                self.top = Some(self.commands.len());
+            }
+
+            /// Drops the oldest done commands until there is no more than `max_len` commands in
+            /// the stack.
+            fn trim(&mut self, max_len: usize, context: &mut $context) {
+                let done = self.top.map_or(0, |top| top + 1);
+                let count = self.commands.len().saturating_sub(max_len).min(done);
+                if count == 0 {
+                    return;
+                }
+
+                for mut dropped_command in self.commands.drain(..count) {
+                    if self.debug {
+                        println!("Finalizing trimmed command {:?}", dropped_command);
+                    }
+                    dropped_command.finalize(context);
+                }
+
+                self.top = self.top.and_then(|top| top.checked_sub(count));
+                self.trimmed += count;
             }
 
             pub fn undo(&mut self, mut context: $context) {
+                self.commit_transaction();
+
                 if !self.commands.is_empty() {
                     if let Some(top) = self.top.as_mut() {
                         if let Some(command) = self.commands.get_mut(*top) {
@@ -82,6 +214,8 @@ macro_rules! define_command_stack {
             }
 
             pub fn redo(&mut self, mut context: $context) {
+                self.commit_transaction();
+
                 if !self.commands.is_empty() {
                     let command = match self.top.as_mut() {
                         None => {
@@ -108,7 +242,28 @@ macro_rules! define_command_stack {
                 }
             }
 
+            /// Reverts every done command in reverse order and then clears the stack, so the
+            /// context returns to exactly the same state it had before the first command.
+            pub fn revert_all(&mut self, mut context: $context) {
+                self.commit_transaction();
+
+                if let Some(top) = self.top.take() {
+                    for command in self.commands.iter_mut().take(top + 1).rev() {
+                        if self.debug {
+                            println!("Reverting command {:?}", command);
+                        }
+                        command.revert(&mut context);
+                    }
+                }
+
+                self.clear(context);
+            }
+
             pub fn clear(&mut self, mut context: $context) {
+                self.commit_transaction();
+
+                self.trimmed = 0;
+
                 for mut dropped_command in self.commands.drain(..) {
                     if self.debug {
                         println!("Finalizing command {:?}", dropped_command);
@@ -120,4 +275,103 @@ macro_rules! define_command_stack {
     };
 }
 
-define_command_stack!(Command, CommandStack, SceneContext);
+define_command_stack!(Command, CommandStack, SceneContext, CommandTransaction);
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+    #[derive(Clone, Default)]
+    struct TestContext {
+        values: Rc<RefCell<Vec<i32>>>,
+    }
+
+    define_command_stack!(
+        TestCommand,
+        TestCommandStack,
+        TestContext,
+        TestCommandTransaction
+    );
+
+    #[derive(Debug)]
+    struct PushCommand(i32);
+
+    impl TestCommand for PushCommand {
+        fn name(&mut self, _: &TestContext) -> String {
+            "Push".to_owned()
+        }
+
+        fn execute(&mut self, context: &mut TestContext) {
+            context.values.borrow_mut().push(self.0);
+        }
+
+        fn revert(&mut self, context: &mut TestContext) {
+            context.values.borrow_mut().pop();
+        }
+    }
+
+    #[test]
+    fn test_revert_all_restores_previous_stack_state() {
+        let context = TestContext::default();
+
+        let mut edit_stack = TestCommandStack::new(false);
+        edit_stack.do_command(Box::new(PushCommand(1)), context.clone());
+
+        // Play mode uses its own stack, edit mode stack is put aside.
+        let mut play_stack = TestCommandStack::new(false);
+        play_stack.do_command(Box::new(PushCommand(2)), context.clone());
+        play_stack.do_command(Box::new(PushCommand(3)), context.clone());
+        play_stack.undo(context.clone());
+        play_stack.do_command(Box::new(PushCommand(4)), context.clone());
+        assert_eq!(*context.values.borrow(), vec![1, 2, 4]);
+
+        play_stack.revert_all(context.clone());
+        assert_eq!(*context.values.borrow(), vec![1]);
+
+        // Nothing to undo in the play mode stack after revert.
+        play_stack.undo(context.clone());
+        assert_eq!(*context.values.borrow(), vec![1]);
+
+        // Edit mode history is untouched.
+        edit_stack.undo(context.clone());
+        assert!(context.values.borrow().is_empty());
+        edit_stack.redo(context.clone());
+        assert_eq!(*context.values.borrow(), vec![1]);
+    }
+
+    #[test]
+    fn test_max_size_drops_oldest_commands() {
+        let context = TestContext::default();
+
+        let mut stack = TestCommandStack::new(false);
+        stack.set_max_size(2);
+        for i in 1..=4 {
+            stack.do_command(Box::new(PushCommand(i)), context.clone());
+        }
+        assert_eq!(*context.values.borrow(), vec![1, 2, 3, 4]);
+        assert_eq!(stack.commands.len(), 2);
+        assert_eq!(stack.trimmed(), 2);
+
+        // Only two latest commands can be undone.
+        for _ in 0..3 {
+            stack.undo(context.clone());
+        }
+        assert_eq!(*context.values.borrow(), vec![1, 2]);
+
+        stack.redo(context.clone());
+        assert_eq!(*context.values.borrow(), vec![1, 2, 3]);
+
+        // Undone commands are dropped first, done ones are kept if they fit in the limit.
+        stack.do_command(Box::new(PushCommand(5)), context.clone());
+        assert_eq!(*context.values.borrow(), vec![1, 2, 3, 5]);
+        assert_eq!(stack.trimmed(), 2);
+
+        // Unlimited stack keeps everything.
+        stack.set_max_size(0);
+        stack.do_command(Box::new(PushCommand(6)), context.clone());
+        assert_eq!(stack.commands.len(), 3);
+
+        stack.clear(context);
+        assert_eq!(stack.trimmed(), 0);
+    }
+}
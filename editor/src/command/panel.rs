@@ -35,7 +35,7 @@ impl CommandStackViewer {
         let undo;
         let redo;
         let clear;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("CommandStackPanel"))
             .with_title(WindowTitle::Text("Command Stack".to_owned()))
             .with_content(
                 GridBuilder::new(
@@ -136,7 +136,8 @@ impl CommandStackViewer {
         scope_profile!();
 
         let top = command_stack.top;
-        let items = command_stack
+        let trimmed = command_stack.trimmed;
+        let mut items = command_stack
             .commands
             .iter_mut()
             .enumerate()
@@ -156,7 +157,21 @@ impl CommandStackViewer {
                     .with_text(cmd.name(ctx))
                     .build(&mut ui.build_ctx())
             })
-            .collect();
+            .collect::<Vec<_>>();
+
+        // Let the user know that the history does not go back to the beginning of the session.
+        if trimmed > 0 {
+            items.push(
+                TextBuilder::new(
+                    WidgetBuilder::new().with_foreground(Brush::Solid(Color::opaque(200, 150, 50))),
+                )
+                .with_text(format!(
+                    "... {} oldest command(s) dropped, see max command stack size in settings",
+                    trimmed
+                ))
+                .build(&mut ui.build_ctx()),
+            );
+        }
 
         send_sync_message(
             ui,
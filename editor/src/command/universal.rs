@@ -65,7 +65,8 @@ macro_rules! define_universal_commands {
                 )),
                 // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
                 // for InheritableVariable<T>.
-                fyrox::gui::inspector::PropertyAction::Revert => None
+                fyrox::gui::inspector::PropertyAction::Revert
+                | fyrox::gui::inspector::PropertyAction::ResetToDefault => None
             }
         }
 
@@ -1,5 +1,6 @@
 use crate::{GameEngine, Message};
 use fyrox::gui::text::TextMessage;
+use fyrox::utils::log::Log;
 use fyrox::{
     core::{
         algebra::Vector2,
@@ -28,11 +29,40 @@ use std::{
     env,
     path::{Path, PathBuf},
     sync::mpsc::Sender,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-#[derive(Default, Eq, PartialEq, Visit)]
+#[derive(Default, Visit)]
 struct HistoryEntry {
     work_dir: PathBuf,
+    /// Time (in seconds since UNIX epoch) when the project was opened last time. Zero means
+    /// that the time is unknown.
+    #[visit(optional)] // Backward compatibility.
+    last_opened: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn format_last_opened(last_opened: u64) -> String {
+    if last_opened == 0 {
+        return "Unknown".to_string();
+    }
+
+    let elapsed = now().saturating_sub(last_opened);
+    if elapsed < 60 {
+        "Just now".to_string()
+    } else if elapsed < 60 * 60 {
+        format!("{} min. ago", elapsed / 60)
+    } else if elapsed < 24 * 60 * 60 {
+        format!("{} h. ago", elapsed / (60 * 60))
+    } else {
+        format!("{} d. ago", elapsed / (24 * 60 * 60))
+    }
 }
 
 pub const HISTORY_PATH: &str = "history.bin";
@@ -60,10 +90,29 @@ fn make_history_entry_widget(ctx: &mut BuildContext, entry: &HistoryEntry) -> Ha
                 bottom: 1.0,
             })
             .with_child(
-                TextBuilder::new(WidgetBuilder::new())
-                    .with_text(format!("{}", entry.work_dir.display(),))
-                    .with_vertical_text_alignment(VerticalAlignment::Center)
-                    .build(ctx),
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(WidgetBuilder::new().on_column(0))
+                                .with_text(format!("{}", entry.work_dir.display(),))
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .build(ctx),
+                        )
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_column(1)
+                                    .with_margin(Thickness::left(4.0)),
+                            )
+                            .with_text(format_last_opened(entry.last_opened))
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .add_column(Column::auto())
+                .build(ctx),
             ),
     ))
     .build(ctx)
@@ -101,16 +150,19 @@ impl Configurator {
             .filter(|e| e.work_dir.exists())
             .collect::<Vec<_>>();
 
+        // Most recent projects first.
+        history.sort_by(|a, b| b.last_opened.cmp(&a.last_opened));
+
         let message = "Please select the working directory of \
         your current project. In most cases it will be the root folder \
-        of your project";
+        of your project. Click on a recent project to open it right away.";
 
         let lv_history;
         let window = WindowBuilder::new(
             WidgetBuilder::new()
-                .with_width(370.0)
-                .with_height(250.0)
-                .with_min_size(Vector2::new(370.0, 250.0)),
+                .with_width(420.0)
+                .with_height(300.0)
+                .with_min_size(Vector2::new(420.0, 300.0)),
         )
         .with_title(WindowTitle::Text("Configure Editor".into()))
         .open(false)
@@ -165,7 +217,7 @@ impl Configurator {
                                             .on_column(2)
                                             .with_margin(Thickness::uniform(1.0)),
                                     )
-                                    .with_text("...")
+                                    .with_text("Browse...")
                                     .build(ctx);
                                     select_work_dir
                                 }),
@@ -173,7 +225,7 @@ impl Configurator {
                         .add_row(Row::strict(25.0))
                         .add_column(Column::strict(120.0))
                         .add_column(Column::stretch())
-                        .add_column(Column::strict(25.0))
+                        .add_column(Column::strict(80.0))
                         .build(ctx),
                     )
                     .with_child(
@@ -182,7 +234,7 @@ impl Configurator {
                                 .with_margin(Thickness::uniform(5.0))
                                 .on_row(2),
                         )
-                        .with_text("Previous Configurations")
+                        .with_text("Recent Projects")
                         .with_horizontal_text_alignment(HorizontalAlignment::Center)
                         .build(ctx),
                     )
@@ -223,8 +275,8 @@ impl Configurator {
             .add_row(Row::auto())
             .add_row(Row::auto())
             .add_row(Row::auto())
-            .add_row(Row::strict(80.0))
             .add_row(Row::stretch())
+            .add_row(Row::auto())
             .add_column(Column::stretch())
             .build(ctx),
         )
@@ -252,6 +304,61 @@ impl Configurator {
         ));
     }
 
+    fn sync_history(&self, engine: &mut GameEngine) {
+        let ctx = &mut engine.user_interface.build_ctx();
+        let items = self
+            .history
+            .iter()
+            .map(|entry| make_history_entry_widget(ctx, entry))
+            .collect();
+
+        engine.user_interface.send_message(ListViewMessage::items(
+            self.lv_history,
+            MessageDirection::ToWidget,
+            items,
+        ));
+    }
+
+    fn configure(&mut self, engine: &mut GameEngine) {
+        self.sender
+            .send(Message::Configure {
+                working_directory: self.work_dir.clone(),
+            })
+            .unwrap();
+
+        let last_opened = now();
+        if let Some(entry) = self
+            .history
+            .iter_mut()
+            .find(|e| e.work_dir == self.work_dir)
+        {
+            entry.last_opened = last_opened;
+        } else {
+            self.history.push(HistoryEntry {
+                work_dir: self.work_dir.clone(),
+                last_opened,
+            });
+        }
+        self.history
+            .sort_by(|a, b| b.last_opened.cmp(&a.last_opened));
+
+        // Entries are re-created, so the selection must be reset too, otherwise it won't be
+        // possible to select the same entry again when the configurator is opened next time.
+        engine
+            .user_interface
+            .send_message(ListViewMessage::selection(
+                self.lv_history,
+                MessageDirection::ToWidget,
+                None,
+            ));
+        self.sync_history(engine);
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
     pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut GameEngine) {
         scope_profile!();
 
@@ -268,16 +375,29 @@ impl Configurator {
             if message.destination() == self.lv_history
                 && message.direction() == MessageDirection::FromWidget
             {
-                let entry = &self.history[index];
-                self.work_dir = entry.work_dir.clone();
+                if let Some(entry) = self.history.get(index) {
+                    if entry.work_dir.exists() {
+                        self.work_dir = entry.work_dir.clone();
+
+                        engine.user_interface.send_message(TextMessage::text(
+                            self.tb_work_dir,
+                            MessageDirection::ToWidget,
+                            self.work_dir.to_string_lossy().to_string(),
+                        ));
 
-                engine.user_interface.send_message(TextMessage::text(
-                    self.tb_work_dir,
-                    MessageDirection::ToWidget,
-                    self.work_dir.to_string_lossy().to_string(),
-                ));
+                        self.configure(engine);
+                    } else {
+                        // The directory could be deleted while the configurator is opened.
+                        Log::warn(format!(
+                            "Project directory {} does not exist anymore, it was removed from \
+                            the list of recent projects.",
+                            entry.work_dir.display()
+                        ));
 
-                self.validate(engine);
+                        self.history.remove(index);
+                        self.sync_history(engine);
+                    }
+                }
             }
         } else if let Some(FileSelectorMessage::Commit(path)) =
             message.data::<FileSelectorMessage>()
@@ -296,36 +416,7 @@ impl Configurator {
             }
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.ok {
-                self.sender
-                    .send(Message::Configure {
-                        working_directory: self.work_dir.clone(),
-                    })
-                    .unwrap();
-
-                let new_entry = HistoryEntry {
-                    work_dir: self.work_dir.clone(),
-                };
-                if !self.history.iter().any(|e| e == &new_entry) {
-                    self.history.push(new_entry);
-
-                    let widget = make_history_entry_widget(
-                        &mut engine.user_interface.build_ctx(),
-                        self.history.last().unwrap(),
-                    );
-
-                    engine
-                        .user_interface
-                        .send_message(ListViewMessage::add_item(
-                            self.lv_history,
-                            MessageDirection::ToWidget,
-                            widget,
-                        ));
-                }
-
-                engine.user_interface.send_message(WindowMessage::close(
-                    self.window,
-                    MessageDirection::ToWidget,
-                ));
+                self.configure(engine);
             } else if message.destination() == self.select_work_dir {
                 engine
                     .user_interface
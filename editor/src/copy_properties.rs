@@ -0,0 +1,365 @@
+//! Copying of property values of a node as human-readable text and pasting them to other nodes.
+//! Every line of the text is `path = value`, where the path is the path of the property in the
+//! inspector. This way exact settings can be shared in bug reports or docs and applied back.
+//! Pasting sets every property that exists in a selected node and has a compatible value, other
+//! properties are skipped.
+
+use crate::scene::{
+    commands::{CommandGroup, SceneCommand, SetPropertyCommand},
+    EditorScene, Selection,
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        reflect::{Reflect, ReflectArray, ResolvePath},
+    },
+    engine::Engine,
+    gui::copypasta::ClipboardProvider,
+    scene::node::Node,
+    utils::log::Log,
+};
+use std::{fmt::Write, path::PathBuf, str::FromStr};
+
+/// Collections with more items are skipped, there's no point in text with thousands of heights of
+/// a terrain.
+const MAX_COLLECTION_LEN: usize = 64;
+const MAX_DEPTH: usize = 16;
+
+fn format_numbers<T: ToString>(numbers: &[T]) -> String {
+    let numbers = numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    format!("({})", numbers.join(", "))
+}
+
+fn parse_numbers<T: FromStr>(text: &str, count: usize) -> Option<Vec<T>> {
+    let numbers = text
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split(',')
+        .map(|n| n.trim().parse().ok())
+        .collect::<Option<Vec<T>>>()?;
+    if numbers.len() == count {
+        Some(numbers)
+    } else {
+        None
+    }
+}
+
+/// Parses a string written in the format of `{:?}`.
+fn parse_quoted(text: &str) -> Option<String> {
+    let text = text.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut string = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'u' => {
+                let code = chars
+                    .by_ref()
+                    .skip_while(|&c| c == '{')
+                    .take_while(|&c| c != '}')
+                    .collect::<String>();
+                char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+            }
+            other => other,
+        };
+        string.push(escaped);
+    }
+    Some(string)
+}
+
+macro_rules! define_leaf_types {
+    ($($ty:ty),*) => {
+        /// Returns text of a value that is written as a whole, or `None` if the value should be
+        /// written property by property.
+        fn format_leaf(value: &dyn Reflect) -> Option<String> {
+            $(
+                if let Some(value) = value.downcast_ref::<$ty>() {
+                    return Some(value.to_string());
+                }
+            )*
+            if let Some(value) = value.downcast_ref::<String>() {
+                Some(format!("{:?}", value))
+            } else if let Some(value) = value.downcast_ref::<PathBuf>() {
+                Some(format!("{:?}", value.to_string_lossy()))
+            } else if let Some(v) = value.downcast_ref::<Vector2<f32>>() {
+                Some(format_numbers(v.as_slice()))
+            } else if let Some(v) = value.downcast_ref::<Vector3<f32>>() {
+                Some(format_numbers(v.as_slice()))
+            } else if let Some(v) = value.downcast_ref::<Vector4<f32>>() {
+                Some(format_numbers(v.as_slice()))
+            } else if let Some(v) = value.downcast_ref::<UnitQuaternion<f32>>() {
+                // Euler angles in degrees, the same as in the inspector.
+                let (x, y, z) = v.euler_angles();
+                Some(format_numbers(&[x.to_degrees(), y.to_degrees(), z.to_degrees()]))
+            } else if let Some(c) = value.downcast_ref::<Color>() {
+                Some(format_numbers(&[c.r, c.g, c.b, c.a]))
+            } else {
+                None
+            }
+        }
+
+        /// Parses text of a value of the same type as the target. Returns `None` if the target
+        /// is not written as a whole or if the text is not a valid value.
+        fn parse_leaf(target: &dyn Reflect, text: &str) -> Option<Box<dyn Reflect>> {
+            $(
+                if target.downcast_ref::<$ty>().is_some() {
+                    return Some(Box::new(text.trim().parse::<$ty>().ok()?));
+                }
+            )*
+            if target.downcast_ref::<String>().is_some() {
+                Some(Box::new(parse_quoted(text)?))
+            } else if target.downcast_ref::<PathBuf>().is_some() {
+                Some(Box::new(PathBuf::from(parse_quoted(text)?)))
+            } else if target.downcast_ref::<Vector2<f32>>().is_some() {
+                Some(Box::new(Vector2::from_column_slice(&parse_numbers(text, 2)?)))
+            } else if target.downcast_ref::<Vector3<f32>>().is_some() {
+                Some(Box::new(Vector3::from_column_slice(&parse_numbers(text, 3)?)))
+            } else if target.downcast_ref::<Vector4<f32>>().is_some() {
+                Some(Box::new(Vector4::from_column_slice(&parse_numbers(text, 4)?)))
+            } else if target.downcast_ref::<UnitQuaternion<f32>>().is_some() {
+                let angles = parse_numbers::<f32>(text, 3)?;
+                Some(Box::new(UnitQuaternion::from_euler_angles(
+                    angles[0].to_radians(),
+                    angles[1].to_radians(),
+                    angles[2].to_radians(),
+                )))
+            } else if target.downcast_ref::<Color>().is_some() {
+                let c = parse_numbers::<u8>(text, 4)?;
+                Some(Box::new(Color::from_rgba(c[0], c[1], c[2], c[3])))
+            } else {
+                None
+            }
+        }
+    };
+}
+
+define_leaf_types!(f32, f64, bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn write_value(value: &dyn Reflect, path: &str, depth: usize, text: &mut String) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    // Items of collections can't be set by path, only their fields.
+    let is_item = path.ends_with(']');
+
+    if let Some(value_text) = format_leaf(value) {
+        if !is_item {
+            let _ = writeln!(text, "{} = {}", path, value_text);
+        }
+        return;
+    }
+
+    if let (Some(names), Some(index)) = (value.variant_names(), value.variant_index()) {
+        if !is_item {
+            let _ = writeln!(text, "{} = {}", path, names[index]);
+        }
+    }
+
+    if let Some(array) = value.as_array() {
+        if array.reflect_len() > MAX_COLLECTION_LEN {
+            let _ = writeln!(
+                text,
+                "# {} has {} items and is skipped",
+                path,
+                array.reflect_len()
+            );
+        } else {
+            for i in 0..array.reflect_len() {
+                if let Some(item) = array.reflect_index(i) {
+                    write_value(item, &format!("{}[{}]", path, i), depth + 1, text);
+                }
+            }
+        }
+        return;
+    }
+
+    for field_info in value.fields_info() {
+        if field_info.read_only {
+            continue;
+        }
+        if let Some(field) = value.field(field_info.name) {
+            write_value(field, &join_path(path, field_info.name), depth + 1, text);
+        }
+    }
+}
+
+/// Writes values of all editable properties of the node as text.
+pub fn node_properties_to_text(node: &Node) -> String {
+    let node = node.as_reflect();
+    let mut text = format!("# {}\n", node.type_name());
+    write_value(node, "", 0, &mut text);
+    text
+}
+
+/// Parses a line of the text to a new value of the property of the node. Returns `Ok(None)` if
+/// the property already has the value.
+fn parse_property(
+    node: &dyn Reflect,
+    path: &str,
+    value_text: &str,
+) -> Result<Option<Box<dyn Reflect>>, String> {
+    let target = node
+        .resolve_path(path)
+        .map_err(|_| "there's no such property".to_owned())?;
+
+    if let Some(value) = parse_leaf(target, value_text) {
+        if format_leaf(&*value) == format_leaf(target) {
+            Ok(None)
+        } else {
+            Ok(Some(value))
+        }
+    } else if let (Some(names), Some(current), Some(constructor)) = (
+        target.variant_names(),
+        target.variant_index(),
+        target.variant_constructor(),
+    ) {
+        let index = names
+            .iter()
+            .position(|&name| name == value_text)
+            .ok_or_else(|| format!("{} is not a variant of the property", value_text))?;
+        if index == current {
+            Ok(None)
+        } else {
+            constructor(index)
+                .map(Some)
+                .ok_or_else(|| format!("{} variant can't be created without its data", value_text))
+        }
+    } else {
+        Err(format!(
+            "{} is not a valid value of {} type",
+            value_text,
+            target.type_name()
+        ))
+    }
+}
+
+/// Creates a command that sets properties from the text to each selected node. Properties that
+/// can't be set are skipped with a log message.
+pub fn make_paste_properties_command(
+    editor_scene: &EditorScene,
+    engine: &Engine,
+    text: &str,
+) -> Option<SceneCommand> {
+    let selection = match &editor_scene.selection {
+        Selection::Graph(selection) => selection,
+        _ => return None,
+    };
+    let graph = &engine.scenes[editor_scene.scene].graph;
+
+    let mut commands = Vec::new();
+    for &handle in selection.nodes() {
+        let node = &graph[handle];
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (path, value_text) = match line.split_once('=') {
+                Some((path, value_text)) => (path.trim(), value_text.trim()),
+                None => {
+                    Log::warn(format!("Skipping a line without a value: {}", line));
+                    continue;
+                }
+            };
+
+            match parse_property(node.as_reflect(), path, value_text) {
+                Ok(Some(value)) => commands.push(SceneCommand::new(SetPropertyCommand::new(
+                    handle,
+                    path.to_owned(),
+                    value,
+                ))),
+                Ok(None) => (),
+                Err(reason) => Log::warn(format!(
+                    "Property {} is not pasted to {}: {}.",
+                    path,
+                    node.name(),
+                    reason
+                )),
+            }
+        }
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+/// Puts properties of the first selected node to the clipboard.
+pub fn copy_properties(editor_scene: &EditorScene, engine: &Engine) {
+    if let Selection::Graph(selection) = &editor_scene.selection {
+        if let Some(&first) = selection.nodes().first() {
+            let text = node_properties_to_text(&engine.scenes[editor_scene.scene].graph[first]);
+            if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
+                Log::verify(clipboard.set_contents(text));
+            }
+        }
+    }
+}
+
+/// Creates a command that sets properties from the clipboard to each selected node.
+pub fn make_paste_properties_from_clipboard_command(
+    editor_scene: &EditorScene,
+    engine: &Engine,
+) -> Option<SceneCommand> {
+    let text = engine
+        .user_interface
+        .clipboard_mut()
+        .and_then(|mut clipboard| clipboard.get_contents().ok())?;
+    make_paste_properties_command(editor_scene, engine, &text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip<T: Reflect + Default>(value: T) -> Option<T> {
+        let text = format_leaf(&value)?;
+        parse_leaf(&T::default(), &text)?.take().ok()
+    }
+
+    #[test]
+    fn test_leaf_round_trip() {
+        assert_eq!(round_trip(0.1f32), Some(0.1));
+        assert_eq!(round_trip(-42i32), Some(-42));
+        assert_eq!(round_trip(true), Some(true));
+        assert_eq!(
+            round_trip(Vector3::new(1.5, -2.0, 3.25)),
+            Some(Vector3::new(1.5, -2.0, 3.25))
+        );
+        assert_eq!(
+            round_trip(Color::from_rgba(1, 2, 3, 4)),
+            Some(Color::from_rgba(1, 2, 3, 4))
+        );
+        assert_eq!(
+            round_trip("Line\n\"quoted\"\\ \u{1}".to_owned()),
+            Some("Line\n\"quoted\"\\ \u{1}".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(parse_leaf(&0.0f32, "abc").is_none());
+        assert!(parse_leaf(&Vector3::<f32>::default(), "(1, 2)").is_none());
+        assert!(parse_leaf(&String::new(), "not quoted").is_none());
+    }
+}
@@ -0,0 +1,486 @@
+//! Cube map window. A cube map is made of six face textures, that are dropped from the asset
+//! browser, or of a single image with the faces in a cross layout. The cube map is previewed as
+//! the sky around a sphere and it can be assigned to the skybox of selected cameras or to a
+//! sampler property of materials of selected meshes.
+
+use crate::{
+    heightmap::make_image_file_filter,
+    inspector::editors::texture::{TextureEditorBuilder, TextureEditorMessage},
+    preview::PreviewPanel,
+    scene::{
+        commands::{
+            material::SetMaterialPropertyValueCommand, CommandGroup, SceneCommand,
+            SetPropertyCommand,
+        },
+        Selection,
+    },
+    EditorScene, GameEngine, Message,
+};
+use fyrox::{
+    asset::Resource,
+    core::{
+        algebra::{Matrix4, Vector2},
+        futures::executor::block_on,
+        pool::Handle,
+        sstorage::ImmutableString,
+    },
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBoxBuilder, TextCommitMode},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    material::PropertyValue,
+    resource::texture::{Texture, TextureData, TextureState},
+    scene::{
+        base::BaseBuilder,
+        camera::{Camera, SkyBox, SkyBoxBuilder},
+        mesh::{
+            surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder,
+        },
+    },
+    utils::log::Log,
+};
+use std::{path::Path, sync::mpsc::Sender};
+
+/// Names of the faces in the order of [`SkyBox::textures`].
+const FACE_NAMES: [&str; 6] = ["Left", "Right", "Top", "Bottom", "Front", "Back"];
+
+pub struct CubeMapWindow {
+    pub window: Handle<UiNode>,
+    preview: PreviewPanel,
+    face_editors: [Handle<UiNode>; 6],
+    faces: [Option<Texture>; 6],
+    /// Faces are changed and the preview must be updated once they're loaded.
+    need_preview_update: bool,
+    load_cross: Handle<UiNode>,
+    clear: Handle<UiNode>,
+    cross_selector: Handle<UiNode>,
+    property_name_box: Handle<UiNode>,
+    property_name: String,
+    assign_to_skybox: Handle<UiNode>,
+    assign_to_material: Handle<UiNode>,
+}
+
+impl CubeMapWindow {
+    pub fn new(engine: &mut GameEngine) -> Self {
+        let mut preview = PreviewPanel::new(engine, 300, 300);
+
+        let graph = &mut engine.scenes[preview.scene()].graph;
+        let sphere = MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_sphere(30, 30, 1.0, &Matrix4::identity()),
+            ))
+            .build()])
+            .build(graph);
+        preview.set_model(sphere, engine);
+
+        let resource_manager = engine.resource_manager.clone();
+        let ctx = &mut engine.user_interface.build_ctx();
+
+        let mut face_editors = [Handle::NONE; 6];
+        let mut faces_grid = WidgetBuilder::new();
+        for (index, name) in FACE_NAMES.iter().enumerate() {
+            let (column, row) = (index % 3, (index / 3) * 2);
+            faces_grid = faces_grid.with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(row)
+                        .on_column(column)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_text(*name)
+                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                .build(ctx),
+            );
+            face_editors[index] = TextureEditorBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row + 1)
+                    .on_column(column)
+                    .with_min_size(Vector2::new(0.0, 17.0)),
+            )
+            .build(ctx, resource_manager.clone());
+            faces_grid = faces_grid.with_child(face_editors[index]);
+        }
+        let faces_grid = GridBuilder::new(faces_grid.on_row(0))
+            .add_row(Row::strict(20.0))
+            .add_row(Row::strict(70.0))
+            .add_row(Row::strict(20.0))
+            .add_row(Row::strict(70.0))
+            .add_column(Column::stretch())
+            .add_column(Column::stretch())
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        let make_button = |ctx: &mut BuildContext, text: &str, width: f32| {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(width)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(text)
+            .build(ctx)
+        };
+
+        let load_cross = make_button(ctx, "Load Cross...", 100.0);
+        let clear = make_button(ctx, "Clear", 60.0);
+        let assign_to_skybox = make_button(ctx, "Assign To Skybox", 120.0);
+        let assign_to_material = make_button(ctx, "Assign To Material", 120.0);
+
+        let property_name = "reflectionTexture".to_owned();
+        let property_name_box;
+        let preview_panel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(620.0))
+            .open(false)
+            .with_title(WindowTitle::text("Cube Map"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(faces_grid)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child(load_cross)
+                                    .with_child(clear),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            preview_panel =
+                                BorderBuilder::new(WidgetBuilder::new().on_row(2)).build(ctx);
+                            preview_panel
+                        })
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Material Property")
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        property_name_box = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Name of a sampler property of materials of \
+                                                    selected meshes. Custom shaders can use the \
+                                                    cube map for reflections.",
+                                                )),
+                                        )
+                                        .with_text(&property_name)
+                                        .with_text_commit_mode(TextCommitMode::Immediate)
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx);
+                                        property_name_box
+                                    }),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::strict(110.0))
+                            .add_column(Column::stretch())
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(4)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child(assign_to_skybox)
+                                    .with_child(assign_to_material),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::strict(25.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        ctx.link(preview.root, preview_panel);
+
+        let cross_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::text("Select Cross Image"))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Open)
+        .with_path("./")
+        .with_filter(make_image_file_filter())
+        .build(ctx);
+
+        Self {
+            window,
+            preview,
+            face_editors,
+            faces: Default::default(),
+            need_preview_update: false,
+            load_cross,
+            clear,
+            cross_selector,
+            property_name_box,
+            property_name,
+            assign_to_skybox,
+            assign_to_material,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn set_faces(&mut self, faces: [Option<Texture>; 6], ui: &UserInterface) {
+        for (editor, face) in self.face_editors.iter().zip(faces.iter()) {
+            ui.send_message(TextureEditorMessage::texture(
+                *editor,
+                MessageDirection::ToWidget,
+                face.clone(),
+            ));
+        }
+        self.faces = faces;
+        self.need_preview_update = true;
+    }
+
+    fn load_cross(&mut self, path: &Path, engine: &GameEngine) {
+        let result = block_on(engine.resource_manager.request_texture(path))
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|cross| {
+                TextureData::cross_faces(&cross.data_ref(), true).map_err(|e| e.to_string())
+            });
+
+        match result {
+            Ok(cross_faces) => {
+                let mut faces: [Option<Texture>; 6] = Default::default();
+                for (face, data) in faces.iter_mut().zip(cross_faces) {
+                    *face = Some(Texture::from(Resource::new(TextureState::Ok(data))));
+                }
+                self.set_faces(faces, &engine.user_interface);
+            }
+            Err(e) => Log::err(format!(
+                "Unable to load cube map from {}! Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    fn make_skybox(&self) -> Option<SkyBox> {
+        if self.faces.iter().all(|face| face.is_none()) {
+            Log::warn("Cube map has no faces.");
+            return None;
+        }
+
+        match (SkyBoxBuilder {
+            left: self.faces[0].clone(),
+            right: self.faces[1].clone(),
+            top: self.faces[2].clone(),
+            bottom: self.faces[3].clone(),
+            front: self.faces[4].clone(),
+            back: self.faces[5].clone(),
+        })
+        .build()
+        {
+            Ok(skybox) => Some(skybox),
+            Err(e) => {
+                Log::err(format!("Unable to make a cube map! Reason: {:?}", e));
+                None
+            }
+        }
+    }
+
+    fn make_assign_to_skybox_command(
+        &self,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+    ) -> Option<SceneCommand> {
+        let selection = match &editor_scene.selection {
+            Selection::Graph(selection) => selection,
+            _ => return None,
+        };
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let cameras = selection
+            .nodes()
+            .iter()
+            .cloned()
+            .filter(|&handle| graph[handle].cast::<Camera>().is_some())
+            .collect::<Vec<_>>();
+        if cameras.is_empty() {
+            Log::warn("Select cameras to assign the skybox to.");
+            return None;
+        }
+
+        let skybox = self.make_skybox()?;
+        let commands = cameras
+            .into_iter()
+            .map(|camera| {
+                SceneCommand::new(SetPropertyCommand::new(
+                    camera,
+                    "sky_box".to_owned(),
+                    Box::new(Some(skybox.clone())),
+                ))
+            })
+            .collect::<Vec<_>>();
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+
+    fn make_assign_to_material_command(
+        &self,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+    ) -> Option<SceneCommand> {
+        let selection = match &editor_scene.selection {
+            Selection::Graph(selection) => selection,
+            _ => return None,
+        };
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let name = ImmutableString::new(&self.property_name);
+
+        let mut materials = Vec::new();
+        for &handle in selection.nodes() {
+            if let Some(mesh) = graph[handle].cast::<Mesh>() {
+                for surface in mesh.surfaces() {
+                    let material = surface.material();
+                    let fallback = match material.lock().property_ref(&name) {
+                        Some(PropertyValue::Sampler { fallback, .. }) => *fallback,
+                        _ => continue,
+                    };
+                    if !materials.iter().any(|(m, _)| m == material) {
+                        materials.push((material.clone(), fallback));
+                    }
+                }
+            }
+        }
+        if materials.is_empty() {
+            Log::warn(format!(
+                "Materials of selected meshes have no {} sampler property.",
+                self.property_name
+            ));
+            return None;
+        }
+
+        // A copy of the cube map with embedded content, so it is saved with the materials.
+        let cubemap = {
+            let skybox = self.make_skybox()?;
+            let cubemap = skybox.cubemap_ref()?.data_ref();
+            Texture::from_bytes(
+                cubemap.kind(),
+                cubemap.pixel_kind(),
+                cubemap.data().to_vec(),
+                true,
+            )?
+        };
+
+        let commands = materials
+            .into_iter()
+            .map(|(material, fallback)| {
+                SceneCommand::new(SetMaterialPropertyValueCommand::new(
+                    material,
+                    name.clone(),
+                    PropertyValue::Sampler {
+                        value: Some(cubemap.clone()),
+                        fallback,
+                    },
+                ))
+            })
+            .collect::<Vec<_>>();
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        sender: &Sender<Message>,
+    ) {
+        self.preview.handle_message(message, engine);
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            let command = if message.destination() == self.load_cross {
+                engine
+                    .user_interface
+                    .send_message(WindowMessage::open_modal(
+                        self.cross_selector,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                None
+            } else if message.destination() == self.clear {
+                self.set_faces(Default::default(), &engine.user_interface);
+                None
+            } else if message.destination() == self.assign_to_skybox {
+                self.make_assign_to_skybox_command(editor_scene, engine)
+            } else if message.destination() == self.assign_to_material {
+                self.make_assign_to_material_command(editor_scene, engine)
+            } else {
+                None
+            };
+            if let Some(command) = command {
+                sender.send(Message::DoSceneCommand(command)).unwrap();
+            }
+        } else if let Some(TextureEditorMessage::Texture(texture)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(index) = self
+                    .face_editors
+                    .iter()
+                    .position(|editor| *editor == message.destination())
+                {
+                    self.faces[index] = texture.clone();
+                    self.need_preview_update = true;
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.property_name_box {
+                self.property_name = text.clone();
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.cross_selector {
+                self.load_cross(path, engine);
+            }
+        }
+    }
+
+    pub fn update(&mut self, engine: &mut GameEngine) {
+        self.preview.update(engine);
+
+        // Faces dropped from the asset browser could still be loading.
+        if self.need_preview_update && self.faces.iter().flatten().all(|face| !face.is_loading()) {
+            self.need_preview_update = false;
+            let skybox = if self.faces.iter().all(|face| face.is_none()) {
+                None
+            } else {
+                self.make_skybox()
+            };
+            self.preview.set_skybox(skybox, engine);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    define_command_stack, send_sync_message, utils::create_file_selector, MessageBoxButtons,
-    MessageBoxMessage, MSG_SYNC_FLAG,
+    define_command_stack, inspector::editors::curve::CurveFieldMessage, send_sync_message,
+    utils::create_file_selector, MessageBoxButtons, MessageBoxMessage, MSG_SYNC_FLAG,
 };
 use fyrox::{
     asset::{Resource, ResourceData, ResourceState},
@@ -31,7 +31,12 @@ use std::{fmt::Debug, path::PathBuf};
 #[derive(Debug)]
 pub struct CurveEditorContext {}
 
-define_command_stack!(CurveCommand, CurveCommandStack, CurveEditorContext);
+define_command_stack!(
+    CurveCommand,
+    CurveCommandStack,
+    CurveEditorContext,
+    CurveCommandTransaction
+);
 
 #[derive(Debug)]
 struct ModifyCurveCommand {
@@ -90,6 +95,9 @@ pub struct CurveEditorWindow {
     cancel_message_box: Handle<UiNode>,
     modified: bool,
     backup: Curve,
+    /// Inspector field that opened the editor. When set, the edited curve is sent back to
+    /// the field on confirmation instead of being saved to a file.
+    field: Handle<UiNode>,
 }
 
 impl CurveEditorWindow {
@@ -276,6 +284,7 @@ impl CurveEditorWindow {
             modified: false,
             backup: Default::default(),
             cancel_message_box,
+            field: Default::default(),
         }
     }
 
@@ -296,6 +305,17 @@ impl CurveEditorWindow {
         ));
     }
 
+    pub fn open_for_field(&mut self, curve: Curve, field: Handle<UiNode>, ui: &UserInterface) {
+        let mut state = CurveResourceState::default();
+        state.curve = curve;
+
+        self.path = Default::default();
+        self.set_curve(CurveResource(Resource::new(ResourceState::Ok(state))), ui);
+        self.field = field;
+
+        self.open(ui);
+    }
+
     fn sync_to_model(&mut self, ui: &UserInterface) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
             send_sync_message(
@@ -358,6 +378,7 @@ impl CurveEditorWindow {
     }
 
     fn clear(&mut self, ui: &UserInterface) {
+        self.field = Handle::NONE;
         self.path = Default::default();
         self.backup = Default::default();
         self.command_stack.clear(CurveEditorContext {});
@@ -403,7 +424,9 @@ impl CurveEditorWindow {
 
         if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.cancel {
-                if self.modified && self.curve_resource.is_some() {
+                if self.field.is_some() {
+                    self.close(ui);
+                } else if self.modified && self.curve_resource.is_some() {
                     ui.send_message(MessageBoxMessage::open(
                         self.cancel_message_box,
                         MessageDirection::ToWidget,
@@ -414,7 +437,18 @@ impl CurveEditorWindow {
                     self.close(ui);
                 }
             } else if message.destination() == self.ok {
-                if self.modified && self.curve_resource.is_some() {
+                if self.field.is_some() {
+                    if let Some(curve_resource) = self.curve_resource.as_ref() {
+                        if self.modified {
+                            ui.send_message(CurveFieldMessage::curve(
+                                self.field,
+                                MessageDirection::ToWidget,
+                                curve_resource.data_ref().curve.clone(),
+                            ));
+                        }
+                    }
+                    self.close(ui);
+                } else if self.modified && self.curve_resource.is_some() {
                     if self.path == PathBuf::default() {
                         ui.send_message(MessageBoxMessage::open(
                             self.save_changes_message_box,
@@ -0,0 +1,437 @@
+//! Generation of terrains from grayscale height map images.
+
+use crate::{
+    create_terrain_layer_material, inspector::editors::make_property_editors_container,
+    scene::commands::graph::AddNodeCommand, EditorScene, Message, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{algebra::Vector2, pool::Handle, reflect::prelude::*},
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::inspectable::InspectablePropertyEditorDefinition, InspectorBuilder,
+            InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    resource::texture::{CompressionOptions, Texture, TextureKind, TexturePixelKind},
+    scene::{
+        base::BaseBuilder,
+        node::Node,
+        terrain::{LayerDefinition, Terrain, TerrainBuilder},
+    },
+    utils::log::Log,
+};
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::mpsc::Sender,
+};
+
+#[derive(Clone, Debug, Reflect)]
+pub struct HeightMapImportOptions {
+    #[reflect(
+        description = "Size of a pixel of the height map in meters. Defines width and length of \
+        the terrain.",
+        min_value = 0.001
+    )]
+    pub meters_per_pixel: f32,
+    #[reflect(description = "Height of the terrain at black pixels of the height map.")]
+    pub min_height: f32,
+    #[reflect(description = "Height of the terrain at white pixels of the height map.")]
+    pub max_height: f32,
+    #[reflect(
+        description = "Amount of height map points of the terrain per meter.",
+        min_value = 0.01
+    )]
+    pub resolution: f32,
+    #[reflect(
+        description = "Approximate size of a chunk in meters.",
+        min_value = 1.0
+    )]
+    pub chunk_size: f32,
+}
+
+impl Default for HeightMapImportOptions {
+    fn default() -> Self {
+        Self {
+            meters_per_pixel: 1.0,
+            min_height: 0.0,
+            max_height: 50.0,
+            resolution: 1.0,
+            chunk_size: 64.0,
+        }
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<f32> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_ne_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32)
+}
+
+fn read_f32(data: &[u8], offset: usize) -> Option<f32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u8(data: &[u8], offset: usize) -> Option<f32> {
+    data.get(offset).map(|v| *v as f32 / u8::MAX as f32)
+}
+
+// Returns brightness of a pixel in [0; 1] range (floating point images are taken as is). Color
+// images are converted to grayscale by averaging of the color channels.
+fn pixel_value(data: &[u8], pixel_kind: TexturePixelKind, index: usize) -> Option<f32> {
+    let average = |values: [Option<f32>; 3]| -> Option<f32> {
+        Some((values[0]? + values[1]? + values[2]?) / 3.0)
+    };
+
+    match pixel_kind {
+        TexturePixelKind::R8 | TexturePixelKind::Luminance8 => read_u8(data, index),
+        TexturePixelKind::RG8 | TexturePixelKind::LuminanceAlpha8 => read_u8(data, index * 2),
+        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 => average([
+            read_u8(data, index * 3),
+            read_u8(data, index * 3 + 1),
+            read_u8(data, index * 3 + 2),
+        ]),
+        TexturePixelKind::RGBA8 | TexturePixelKind::BGRA8 => average([
+            read_u8(data, index * 4),
+            read_u8(data, index * 4 + 1),
+            read_u8(data, index * 4 + 2),
+        ]),
+        TexturePixelKind::R16 | TexturePixelKind::Luminance16 => read_u16(data, index * 2),
+        TexturePixelKind::RG16 => read_u16(data, index * 4),
+        TexturePixelKind::RGB16 => average([
+            read_u16(data, index * 6),
+            read_u16(data, index * 6 + 2),
+            read_u16(data, index * 6 + 4),
+        ]),
+        TexturePixelKind::RGBA16 => average([
+            read_u16(data, index * 8),
+            read_u16(data, index * 8 + 2),
+            read_u16(data, index * 8 + 4),
+        ]),
+        TexturePixelKind::RGB32F => average([
+            read_f32(data, index * 12),
+            read_f32(data, index * 12 + 4),
+            read_f32(data, index * 12 + 8),
+        ]),
+        TexturePixelKind::RGBA32F => average([
+            read_f32(data, index * 16),
+            read_f32(data, index * 16 + 4),
+            read_f32(data, index * 16 + 8),
+        ]),
+        // Compressed formats cannot be sampled directly.
+        _ => None,
+    }
+}
+
+struct HeightMapImage {
+    width: u32,
+    height: u32,
+    values: Vec<f32>,
+}
+
+impl HeightMapImage {
+    fn load(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+        let texture = Texture::load_from_memory(&bytes, CompressionOptions::NoCompression, false)
+            .map_err(|e| format!("{:?}", e))?;
+        let data = texture.data_ref();
+
+        let (width, height) = match data.kind() {
+            TextureKind::Rectangle { width, height } => (width, height),
+            _ => return Err("Height map must be a 2D image!".to_owned()),
+        };
+
+        let pixels = data.first_mip_level_data();
+        let values = (0..(width * height) as usize)
+            .map(|i| pixel_value(pixels, data.pixel_kind(), i))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| format!("Unsupported pixel format {:?}!", data.pixel_kind()))?;
+
+        Ok(Self {
+            width,
+            height,
+            values,
+        })
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> f32 {
+        self.values[(y.min(self.height - 1) * self.width + x.min(self.width - 1)) as usize]
+    }
+
+    // Bilinear sampling, `uv` is in [0; 1] range.
+    fn sample(&self, uv: Vector2<f32>) -> f32 {
+        let x = uv.x.clamp(0.0, 1.0) * (self.width - 1) as f32;
+        let y = uv.y.clamp(0.0, 1.0) * (self.height - 1) as f32;
+        let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+        let (kx, ky) = (x.fract(), y.fract());
+
+        let top = self.pixel(x0, y0) * (1.0 - kx) + self.pixel(x0 + 1, y0) * kx;
+        let bottom = self.pixel(x0, y0 + 1) * (1.0 - kx) + self.pixel(x0 + 1, y0 + 1) * kx;
+        top * (1.0 - ky) + bottom * ky
+    }
+}
+
+/// Creates a terrain node with heights sampled from the height map image. Size of the terrain is
+/// defined by the size of the image, so non-square images produce non-square terrains.
+pub fn make_terrain_from_height_map(
+    path: &Path,
+    options: &HeightMapImportOptions,
+) -> Result<Node, String> {
+    let image = HeightMapImage::load(path)?;
+
+    let meters_per_pixel = options.meters_per_pixel.max(0.001);
+    let width = (image.width.max(2) - 1) as f32 * meters_per_pixel;
+    let length = (image.height.max(2) - 1) as f32 * meters_per_pixel;
+    let chunk_size = options.chunk_size.max(1.0);
+    let width_chunks = (width / chunk_size).ceil() as usize;
+    let length_chunks = (length / chunk_size).ceil() as usize;
+    let chunk_width = width / width_chunks as f32;
+    let chunk_length = length / length_chunks as f32;
+    // Every chunk must have at least two points on each side.
+    let resolution = options.resolution.max(2.0 / chunk_width.min(chunk_length));
+
+    let mut node = TerrainBuilder::new(
+        BaseBuilder::new().with_name(
+            path.file_stem()
+                .map_or_else(|| "Terrain".to_owned(), |s| s.to_string_lossy().to_string()),
+        ),
+    )
+    .with_width(width)
+    .with_length(length)
+    .with_width_chunks(width_chunks)
+    .with_length_chunks(length_chunks)
+    .with_height_map_resolution(resolution)
+    .with_layers(vec![LayerDefinition {
+        material: create_terrain_layer_material(),
+        mask_property_name: "maskTexture".to_owned(),
+    }])
+    .build_node();
+
+    let terrain = node.cast_mut::<Terrain>().unwrap();
+    for chunk in terrain.chunks_mut() {
+        let origin = chunk.local_position();
+        let width_points = chunk.width_point_count();
+        let length_points = chunk.length_point_count();
+
+        let mut heightmap = Vec::with_capacity((width_points * length_points) as usize);
+        for z in 0..length_points {
+            let kz = z as f32 / (length_points - 1) as f32;
+            for x in 0..width_points {
+                let kx = x as f32 / (width_points - 1) as f32;
+                let uv = Vector2::new(
+                    (origin.x + kx * chunk_width) / width,
+                    (origin.y + kz * chunk_length) / length,
+                );
+                let value = image.sample(uv);
+                heightmap
+                    .push(options.min_height + value * (options.max_height - options.min_height));
+            }
+        }
+        chunk.set_heightmap(heightmap);
+    }
+
+    Ok(node)
+}
+
+pub fn make_image_file_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            matches!(
+                ext.to_string_lossy().to_lowercase().as_ref(),
+                "png" | "jpg" | "jpeg" | "bmp" | "tga" | "tif" | "tiff"
+            )
+        } else {
+            p.is_dir()
+        }
+    })
+}
+
+pub struct HeightMapImportWindow {
+    pub window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    import: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    file_selector: Handle<UiNode>,
+    options: HeightMapImportOptions,
+}
+
+impl HeightMapImportWindow {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let options = HeightMapImportOptions::default();
+
+        let container = make_property_editors_container(sender);
+        container.insert(InspectablePropertyEditorDefinition::<HeightMapImportOptions>::new());
+
+        let context = InspectorContext::from_object(
+            &options,
+            ctx,
+            Rc::new(container),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+        );
+
+        let inspector;
+        let import;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(220.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Terrain From Height Map"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                    .with_context(context)
+                                    .build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        import = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Import...")
+                                        .build(ctx);
+                                        import
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        let file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::text("Select Height Map"))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Open)
+        .with_path("./")
+        .with_filter(make_image_file_filter())
+        .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            import,
+            cancel,
+            file_selector,
+            options,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn import(
+        &self,
+        path: PathBuf,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &Sender<Message>,
+    ) {
+        match make_terrain_from_height_map(&path, &self.options) {
+            Ok(terrain) => {
+                let root = engine.scenes[editor_scene.scene].graph.get_root();
+                sender
+                    .send(Message::do_scene_command(AddNodeCommand::new(
+                        terrain, root,
+                    )))
+                    .unwrap();
+            }
+            Err(e) => Log::err(format!(
+                "Unable to create terrain from {} height map! Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &Sender<Message>,
+    ) {
+        let ui = &engine.user_interface;
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.import {
+                ui.send_message(WindowMessage::open_modal(
+                    self.file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                Log::verify(
+                    PropertyAction::from_field_kind(&property_changed.value)
+                        .apply(&property_changed.path(), &mut self.options),
+                );
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.file_selector {
+                self.import(path.clone(), editor_scene, engine, sender);
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use crate::{Message, MessageDirection};
+use fyrox::{
+    core::{
+        algebra::Vector2, color::Color, curve::Curve, math::lerpf, parking_lot::Mutex, pool::Handle,
+    },
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        define_constructor,
+        draw::{CommandTexture, Draw, DrawingContext},
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            FieldKind, InspectorError, PropertyChanged,
+        },
+        message::{MouseButton, UiMessage},
+        utils::make_simple_tooltip,
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, Thickness, UiNode, UserInterface,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+/// Amount of samples used to draw the curve shape in the preview.
+const PREVIEW_SAMPLES: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurvePreviewMessage {
+    Curve(Curve),
+}
+
+impl CurvePreviewMessage {
+    define_constructor!(CurvePreviewMessage:Curve => fn curve(Curve), layout: false);
+}
+
+/// A small widget that draws the shape of a curve, scaled to fit its bounds.
+#[derive(Clone, Debug)]
+pub struct CurvePreview {
+    widget: Widget,
+    curve: Curve,
+}
+
+impl Deref for CurvePreview {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for CurvePreview {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl Control for CurvePreview {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let bounds = self.screen_bounds();
+
+        ctx.push_rect_filled(&bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let keys = self.curve.keys();
+        let (first, last) = match (keys.first(), keys.last()) {
+            (Some(first), Some(last)) => (first.location(), last.location()),
+            _ => return,
+        };
+
+        let values = (0..PREVIEW_SAMPLES)
+            .map(|i| {
+                let t = i as f32 / (PREVIEW_SAMPLES - 1) as f32;
+                (t, self.curve.value_at(lerpf(first, last, t)))
+            })
+            .collect::<Vec<_>>();
+
+        let (min, max) = values
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), (_, value)| {
+                (min.min(*value), max.max(*value))
+            });
+        let range = max - min;
+
+        // Keep the line off the edges of the preview, so flat segments stay visible.
+        let padding = 2.0;
+        let height = (bounds.h() - 2.0 * padding).max(0.0);
+
+        let to_screen = |(t, value): (f32, f32)| {
+            let k = if range > f32::EPSILON {
+                (value - min) / range
+            } else {
+                0.5
+            };
+            Vector2::new(
+                bounds.x() + t * bounds.w(),
+                bounds.y() + padding + (1.0 - k) * height,
+            )
+        };
+
+        for pair in values.windows(2) {
+            ctx.push_line(to_screen(pair[0]), to_screen(pair[1]), 1.0);
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            self.foreground(),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(CurvePreviewMessage::Curve(curve)) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.curve = curve.clone();
+            }
+        }
+    }
+}
+
+pub struct CurvePreviewBuilder {
+    widget_builder: WidgetBuilder,
+    curve: Curve,
+}
+
+impl CurvePreviewBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            curve: Default::default(),
+        }
+    }
+
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let preview = CurvePreview {
+            widget: self.widget_builder.build(),
+            curve: self.curve,
+        };
+
+        ctx.add_node(UiNode::new(preview))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveFieldMessage {
+    Curve(Curve),
+}
+
+impl CurveFieldMessage {
+    define_constructor!(CurveFieldMessage:Curve => fn curve(Curve), layout: false);
+}
+
+#[derive(Clone)]
+pub struct CurveFieldEditor {
+    widget: Widget,
+    sender: Sender<Message>,
+    preview: Handle<UiNode>,
+    edit: Handle<UiNode>,
+    curve: Curve,
+}
+
+impl Debug for CurveFieldEditor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CurveFieldEditor")
+    }
+}
+
+impl Deref for CurveFieldEditor {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for CurveFieldEditor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl CurveFieldEditor {
+    fn open_editor(&self) {
+        self.sender
+            .send(Message::OpenCurveEditor {
+                curve: self.curve.clone(),
+                target: self.handle,
+            })
+            .unwrap();
+    }
+}
+
+impl Control for CurveFieldEditor {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.edit {
+                self.open_editor();
+            }
+        } else if let Some(WidgetMessage::MouseDown { button, .. }) = message.data() {
+            if *button == MouseButton::Left && message.destination() == self.preview {
+                self.open_editor();
+                message.set_handled(true);
+            }
+        } else if let Some(CurveFieldMessage::Curve(curve)) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+                && &self.curve != curve
+            {
+                self.curve = curve.clone();
+
+                ui.send_message(CurvePreviewMessage::curve(
+                    self.preview,
+                    MessageDirection::ToWidget,
+                    self.curve.clone(),
+                ));
+
+                ui.send_message(message.reverse());
+            }
+        }
+    }
+}
+
+pub struct CurveFieldEditorBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl CurveFieldEditorBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(
+        self,
+        ctx: &mut BuildContext,
+        sender: Sender<Message>,
+        curve: Curve,
+    ) -> Handle<UiNode> {
+        let preview;
+        let edit;
+
+        let editor = CurveFieldEditor {
+            widget: self
+                .widget_builder
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(
+                                BorderBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_foreground(Brush::Solid(Color::opaque(80, 80, 80)))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Click to edit the curve",
+                                        ))
+                                        .with_child({
+                                            preview = CurvePreviewBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_background(Brush::Solid(Color::opaque(
+                                                        30, 30, 30,
+                                                    )))
+                                                    .with_foreground(Brush::Solid(Color::opaque(
+                                                        0, 162, 232,
+                                                    ))),
+                                            )
+                                            .with_curve(curve.clone())
+                                            .build(ctx);
+                                            preview
+                                        }),
+                                )
+                                .with_stroke_thickness(Thickness::uniform(1.0))
+                                .build(ctx),
+                            )
+                            .with_child({
+                                edit = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(1)
+                                        .with_width(40.0)
+                                        .with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Edit...")
+                                .build(ctx);
+                                edit
+                            }),
+                    )
+                    .add_row(Row::strict(50.0))
+                    .add_column(Column::stretch())
+                    .add_column(Column::auto())
+                    .build(ctx),
+                )
+                .build(),
+            sender,
+            preview,
+            edit,
+            curve,
+        };
+
+        ctx.add_node(UiNode::new(editor))
+    }
+}
+
+#[derive(Debug)]
+pub struct CurvePropertyEditorDefinition {
+    pub sender: Mutex<Sender<Message>>,
+}
+
+impl PropertyEditorDefinition for CurvePropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<Curve>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<Curve>()?;
+        Ok(PropertyEditorInstance::Simple {
+            editor: CurveFieldEditorBuilder::new(WidgetBuilder::new()).build(
+                ctx.build_context,
+                self.sender.lock().clone(),
+                value.clone(),
+            ),
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<Curve>()?;
+        Ok(Some(CurveFieldMessage::curve(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.clone(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(CurveFieldMessage::Curve(value)) = ctx.message.data() {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(value.clone()),
+                });
+            }
+        }
+        None
+    }
+}
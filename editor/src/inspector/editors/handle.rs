@@ -47,6 +47,7 @@ pub struct HandlePropertyEditor {
     text: Handle<UiNode>,
     locate: Handle<UiNode>,
     select: Handle<UiNode>,
+    pick: Handle<UiNode>,
     value: Handle<Node>,
     sender: Sender<Message>,
 }
@@ -60,6 +61,7 @@ impl Clone for HandlePropertyEditor {
             sender: self.sender.clone(),
             locate: self.locate,
             select: self.select,
+            pick: self.pick,
         }
     }
 }
@@ -168,6 +170,12 @@ impl Control for HandlePropertyEditor {
                         handle: self.value.into(),
                     })
                     .unwrap();
+            } else if message.destination == self.pick {
+                self.sender
+                    .send(Message::PickNodeHandle {
+                        view: self.handle(),
+                    })
+                    .unwrap();
             }
         }
     }
@@ -197,6 +205,7 @@ impl HandlePropertyEditorBuilder {
         let text;
         let locate;
         let select;
+        let pick;
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -233,12 +242,29 @@ impl HandlePropertyEditorBuilder {
                     .with_text("*")
                     .build(ctx);
                     select
+                })
+                .with_child({
+                    pick = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Pick Object - click a node in the Scene Viewer or in the World \
+                                Viewer to assign it here",
+                            ))
+                            .with_width(20.0)
+                            .with_height(20.0)
+                            .on_column(3),
+                    )
+                    .with_text("+")
+                    .build(ctx);
+                    pick
                 }),
         )
         .add_row(Row::stretch())
         .add_column(Column::stretch())
         .add_column(Column::auto())
         .add_column(Column::auto())
+        .add_column(Column::auto())
         .build(ctx);
 
         let editor = HandlePropertyEditor {
@@ -256,6 +282,7 @@ impl HandlePropertyEditorBuilder {
             sender: self.sender,
             locate,
             select,
+            pick,
         };
 
         ctx.add_node(UiNode::new(editor))
@@ -0,0 +1,276 @@
+use crate::{inspector::EditorEnvironment, settings::units::LengthDisplay};
+use fyrox::{
+    core::algebra::Vector3,
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            FieldKind, InspectorEnvironment, InspectorError, PropertyChanged,
+        },
+        message::{MessageDirection, UiMessage},
+        text::TextBuilder,
+        vec::vec3::{Vec3EditorBuilder, Vec3EditorMessage},
+        widget::WidgetBuilder,
+        Thickness, VerticalAlignment,
+    },
+    scene::transform::Transform,
+};
+use std::{any::TypeId, rc::Rc};
+
+/// Returns `true` if the property is a position in space, such properties are shown in display
+/// units.
+fn is_length_property(owner_type_id: TypeId, name: &str) -> bool {
+    owner_type_id == TypeId::of::<Transform>()
+        && matches!(
+            name,
+            "local_position"
+                | "rotation_offset"
+                | "rotation_pivot"
+                | "scaling_offset"
+                | "scaling_pivot"
+        )
+}
+
+fn length_display(
+    environment: &Option<Rc<dyn InspectorEnvironment>>,
+    owner_type_id: TypeId,
+    name: &str,
+) -> Option<LengthDisplay> {
+    if is_length_property(owner_type_id, name) {
+        EditorEnvironment::try_get_from(environment).and_then(|e| e.length_display)
+    } else {
+        None
+    }
+}
+
+/// Editor of `Vector3<f32>` properties that shows positions in the units from the editor
+/// settings. Values are converted back to scene units when edited, so it is purely a display
+/// transform. All other vectors are edited as is.
+#[derive(Debug)]
+pub struct LengthVec3PropertyEditorDefinition;
+
+impl PropertyEditorDefinition for LengthVec3PropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<Vector3<f32>>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = *ctx.property_info.cast_value::<Vector3<f32>>()?;
+
+        match length_display(
+            &ctx.environment,
+            ctx.property_info.owner_type_id,
+            ctx.property_info.name,
+        ) {
+            Some(display) => {
+                let editor = Vec3EditorBuilder::new(WidgetBuilder::new().on_column(0))
+                    .with_value(value.scale(display.scale))
+                    .build(ctx.build_context);
+
+                let container = GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_child(editor)
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_column(1)
+                                    .with_margin(Thickness::left(4.0)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text(display.suffix)
+                            .build(ctx.build_context),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .add_column(Column::strict(24.0))
+                .build(ctx.build_context);
+
+                Ok(PropertyEditorInstance::Custom { container, editor })
+            }
+            None => Ok(PropertyEditorInstance::Simple {
+                editor: Vec3EditorBuilder::new(
+                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                )
+                .with_value(value)
+                .build(ctx.build_context),
+            }),
+        }
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = *ctx.property_info.cast_value::<Vector3<f32>>()?;
+        let scale = length_display(
+            &ctx.environment,
+            ctx.property_info.owner_type_id,
+            ctx.property_info.name,
+        )
+        .map_or(1.0, |d| d.scale);
+
+        Ok(Some(Vec3EditorMessage::value(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.scale(scale),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(Vec3EditorMessage::Value(value)) =
+                ctx.message.data::<Vec3EditorMessage<f32>>()
+            {
+                let scale = length_display(&ctx.environment, ctx.owner_type_id, ctx.name)
+                    .map_or(1.0, |d| d.scale);
+
+                return Some(PropertyChanged {
+                    owner_type_id: ctx.owner_type_id,
+                    name: ctx.name.to_string(),
+                    value: FieldKind::object(value.unscale(scale)),
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        inspector::{editors::length::LengthVec3PropertyEditorDefinition, EditorEnvironment},
+        settings::units::LengthDisplay,
+    };
+    use fyrox::{
+        core::{
+            algebra::{Vector2, Vector3},
+            reflect::Reflect,
+            variable::InheritableVariable,
+        },
+        engine::resource_manager::ResourceManager,
+        gui::{
+            inspector::{
+                editors::{
+                    PropertyEditorBuildContext, PropertyEditorDefinitionContainer,
+                    PropertyEditorInstance, PropertyEditorMessageContext,
+                    PropertyEditorTranslationContext,
+                },
+                FieldKind, InspectorEnvironment,
+            },
+            message::{MessageDirection, UiMessage},
+            vec::vec3::Vec3EditorMessage,
+            UserInterface,
+        },
+        scene::transform::{Transform, TransformBuilder},
+    };
+    use std::{any::TypeId, rc::Rc, sync::mpsc::channel};
+
+    fn vec3_value(message: &UiMessage) -> Vector3<f32> {
+        match message.data::<Vec3EditorMessage<f32>>() {
+            Some(Vec3EditorMessage::Value(value)) => *value,
+            _ => panic!("not a value of vec3 editor"),
+        }
+    }
+
+    #[test]
+    fn test_inheritable_position_round_trip() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let container = PropertyEditorDefinitionContainer::new();
+        container.insert(LengthVec3PropertyEditorDefinition);
+        let container = Rc::new(container);
+
+        let environment: Option<Rc<dyn InspectorEnvironment>> = Some(Rc::new(EditorEnvironment {
+            resource_manager: ResourceManager::new(Default::default()),
+            serialization_context: Default::default(),
+            available_animations: Default::default(),
+            sender: channel().0,
+            length_display: Some(LengthDisplay {
+                scale: 100.0,
+                suffix: "cm",
+            }),
+        }));
+
+        // Position is an inheritable variable, so it is edited through the inheritable wrapper.
+        let definition = container
+            .definitions()
+            .get(&TypeId::of::<InheritableVariable<Vector3<f32>>>())
+            .cloned()
+            .unwrap();
+
+        let mut transform = TransformBuilder::new()
+            .with_local_position(Vector3::new(0.05, 0.0, 0.0))
+            .build();
+
+        let instance = {
+            let fields = transform.fields_info();
+            let field = fields.iter().find(|f| f.name == "local_position").unwrap();
+            match definition
+                .create_instance(PropertyEditorBuildContext {
+                    build_context: &mut ui.build_ctx(),
+                    property_info: field,
+                    environment: environment.clone(),
+                    definition_container: container.clone(),
+                    sync_flag: 0,
+                    layer_index: 0,
+                    generate_property_string_values: false,
+                })
+                .unwrap()
+            {
+                PropertyEditorInstance::Simple { editor } => editor,
+                PropertyEditorInstance::Custom { editor, .. } => editor,
+            }
+        };
+
+        let displayed_value = |transform: &Transform, ui: &mut UserInterface| {
+            let fields = transform.fields_info();
+            let field = fields.iter().find(|f| f.name == "local_position").unwrap();
+            let message = definition
+                .create_message(PropertyEditorMessageContext {
+                    sync_flag: 0,
+                    instance,
+                    ui,
+                    property_info: field,
+                    definition_container: container.clone(),
+                    layer_index: 0,
+                    environment: environment.clone(),
+                    generate_property_string_values: false,
+                })
+                .unwrap()
+                .unwrap();
+            vec3_value(&message)
+        };
+
+        assert!(
+            (displayed_value(&transform, &mut ui) - Vector3::new(5.0, 0.0, 0.0)).norm() < 1.0e-4
+        );
+
+        // Edit the displayed value and apply it like the inspector does.
+        let edited = Vector3::new(6.0, 0.0, 0.0);
+        let changed = definition
+            .translate_message(PropertyEditorTranslationContext {
+                environment: environment.clone(),
+                name: "local_position",
+                owner_type_id: TypeId::of::<Transform>(),
+                message: &Vec3EditorMessage::value(instance, MessageDirection::FromWidget, edited),
+                definition_container: container.clone(),
+            })
+            .unwrap();
+        let new_position = match changed.value {
+            FieldKind::Object(value) => value.cast_clone::<Vector3<f32>>().unwrap(),
+            _ => panic!("position must be changed by value"),
+        };
+        assert!((new_position - Vector3::new(0.06, 0.0, 0.0)).norm() < 1.0e-6);
+
+        transform.set_position(new_position);
+        assert!((displayed_value(&transform, &mut ui) - edited).norm() < 1.0e-4);
+    }
+}
@@ -4,7 +4,9 @@ use crate::{
             AnimationContainerPropertyEditorDefinition, AnimationPropertyEditorDefinition,
             MachinePropertyEditorDefinition,
         },
+        curve::CurvePropertyEditorDefinition,
         handle::NodeHandlePropertyEditorDefinition,
+        length::LengthVec3PropertyEditorDefinition,
         material::MaterialPropertyEditorDefinition,
         resource::ResourceFieldPropertyEditorDefinition,
         script::ScriptPropertyEditorDefinition,
@@ -24,6 +26,7 @@ use fyrox::{
         AnimationContainer,
     },
     core::{
+        curve::Curve,
         futures::executor::block_on,
         parking_lot::Mutex,
         pool::{ErasedHandle, Handle},
@@ -86,12 +89,15 @@ use fyrox::{
         },
         terrain::Layer,
         transform::Transform,
+        trigger::TriggerShape,
     },
 };
 use std::{rc::Rc, sync::mpsc::Sender};
 
 pub mod animation;
+pub mod curve;
 pub mod handle;
+pub mod length;
 pub mod material;
 pub mod resource;
 pub mod script;
@@ -124,6 +130,7 @@ pub fn make_property_editors_container(
     let container = PropertyEditorDefinitionContainer::new();
 
     container.insert(TexturePropertyEditorDefinition);
+    container.insert(LengthVec3PropertyEditorDefinition);
     container.insert(InheritablePropertyEditorDefinition::<Option<Texture>>::new());
     container.insert(InheritablePropertyEditorDefinition::<Handle<Node>>::new());
 
@@ -132,6 +139,12 @@ pub fn make_property_editors_container(
     });
     container.insert(InheritablePropertyEditorDefinition::<SharedMaterial>::new());
 
+    container.insert(CurvePropertyEditorDefinition {
+        sender: Mutex::new(sender.clone()),
+    });
+    container.insert(InheritablePropertyEditorDefinition::<Curve>::new());
+    container.register_inheritable_vec_collection::<Curve>();
+
     container.register_inheritable_vec_collection::<Handle<Node>>();
     container.insert(NodeHandlePropertyEditorDefinition::new(sender));
     container.register_inheritable_inspectable::<NodeHandle>();
@@ -260,6 +273,7 @@ pub fn make_property_editors_container(
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
+    container.register_inheritable_enum::<TriggerShape, _>();
 
     container.insert(ScriptPropertyEditorDefinition {});
     container.insert(BitFieldPropertyEditorDefinition::<BitMask>::new());
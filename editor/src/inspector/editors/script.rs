@@ -1,13 +1,19 @@
 use crate::{
-    gui::make_dropdown_list_option, inspector::EditorEnvironment, send_sync_message,
-    DropdownListBuilder, MSG_SYNC_FLAG,
+    gui::make_dropdown_list_option, inspector::EditorEnvironment, localization::tr,
+    send_sync_message, DropdownListBuilder, MSG_SYNC_FLAG,
 };
 use fyrox::{
-    core::{pool::Handle, uuid::Uuid},
+    core::{
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{Visit, Visitor},
+    },
     engine::SerializationContext,
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
         define_constructor,
         dropdown_list::{DropdownList, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
                 PropertyEditorBuildContext, PropertyEditorDefinition,
@@ -18,8 +24,11 @@ use fyrox::{
             InspectorEnvironment, InspectorError, InspectorMessage, PropertyChanged,
         },
         message::{MessageDirection, UiMessage},
-        widget::{Widget, WidgetBuilder},
-        BuildContext, Control, UiNode, UserInterface,
+        messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
+        utils::make_simple_tooltip,
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Control, Thickness, UiNode, UserInterface,
     },
     script::Script,
 };
@@ -35,11 +44,14 @@ use std::{
 pub enum ScriptPropertyEditorMessage {
     Value(Option<Uuid>),
     PropertyChanged(PropertyChanged),
+    /// Removes current script, asks for confirmation first if the script has modified fields.
+    Remove,
 }
 
 impl ScriptPropertyEditorMessage {
     define_constructor!(ScriptPropertyEditorMessage:Value => fn value(Option<Uuid>), layout: false);
     define_constructor!(ScriptPropertyEditorMessage:PropertyChanged => fn property_changed(PropertyChanged), layout: false);
+    define_constructor!(ScriptPropertyEditorMessage:Remove => fn remove(), layout: false);
 }
 
 #[derive(Clone, Debug)]
@@ -47,8 +59,11 @@ pub struct ScriptPropertyEditor {
     widget: Widget,
     inspector: Handle<UiNode>,
     variant_selector: Handle<UiNode>,
+    remove: Handle<UiNode>,
     selected_script_uuid: Option<Uuid>,
     need_context_update: Cell<bool>,
+    has_modified_fields: Cell<bool>,
+    remove_confirmation: Cell<Handle<UiNode>>,
 }
 
 impl Deref for ScriptPropertyEditor {
@@ -84,8 +99,19 @@ impl Control for ScriptPropertyEditor {
             {
                 self.selected_script_uuid = *id;
                 self.need_context_update.set(true);
+                ui.send_message(WidgetMessage::enabled(
+                    self.remove,
+                    MessageDirection::ToWidget,
+                    id.is_some(),
+                ));
                 ui.send_message(message.reverse());
             }
+        } else if let Some(ScriptPropertyEditorMessage::Remove) = message.data() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.try_remove(ui);
+            }
         } else if let Some(InspectorMessage::PropertyChanged(property_changed)) =
             message.data::<InspectorMessage>()
         {
@@ -117,16 +143,110 @@ impl Control for ScriptPropertyEditor {
                     .user_data_ref::<Uuid>()
                     .expect("Must be script UUID");
 
-                ui.send_message(ScriptPropertyEditorMessage::value(
+                if new_selected_script_uuid.is_nil() {
+                    // `<No Script>` item is selected.
+                    ui.send_message(ScriptPropertyEditorMessage::remove(
+                        self.handle(),
+                        MessageDirection::ToWidget,
+                    ));
+                } else {
+                    ui.send_message(ScriptPropertyEditorMessage::value(
+                        self.handle(),
+                        MessageDirection::ToWidget,
+                        Some(new_selected_script_uuid),
+                    ));
+                }
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            // Header of the editor is not a child of the editor, so its messages are previewed.
+            if message.destination() == self.remove {
+                ui.send_message(ScriptPropertyEditorMessage::remove(
                     self.handle(),
                     MessageDirection::ToWidget,
-                    Some(new_selected_script_uuid),
+                ));
+            }
+        } else if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() == self.remove_confirmation.get()
+                && self.remove_confirmation.get().is_some()
+            {
+                if *result == MessageBoxResult::Yes {
+                    ui.send_message(ScriptPropertyEditorMessage::value(
+                        self.handle(),
+                        MessageDirection::ToWidget,
+                        None,
+                    ));
+                } else {
+                    self.sync_selector(ui);
+                }
+
+                ui.send_message(WidgetMessage::remove(
+                    self.remove_confirmation.replace(Handle::NONE),
+                    MessageDirection::ToWidget,
                 ));
             }
         }
     }
 }
 
+impl ScriptPropertyEditor {
+    fn try_remove(&self, ui: &mut UserInterface) {
+        if self.selected_script_uuid.is_none() {
+            return;
+        }
+
+        if self.has_modified_fields.get() {
+            if self.remove_confirmation.get().is_none() {
+                let message_box = MessageBoxBuilder::new(
+                    WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                        .can_close(false)
+                        .can_minimize(false)
+                        .open(false)
+                        .with_title(WindowTitle::text(tr("Remove Script"))),
+                )
+                .with_text(&tr(
+                    "The script has modified properties, they will be lost. Do you want to remove it?",
+                ))
+                .with_buttons(MessageBoxButtons::YesNo)
+                .build(&mut ui.build_ctx());
+
+                ui.send_message(MessageBoxMessage::open(
+                    message_box,
+                    MessageDirection::ToWidget,
+                    None,
+                    None,
+                ));
+
+                self.remove_confirmation.set(message_box);
+            }
+        } else {
+            ui.send_message(ScriptPropertyEditorMessage::value(
+                self.handle(),
+                MessageDirection::ToWidget,
+                None,
+            ));
+        }
+    }
+
+    /// Selects the item of current script in the selector, it is needed when removal was cancelled.
+    fn sync_selector(&self, ui: &UserInterface) {
+        let selected_uuid = self.selected_script_uuid.unwrap_or_default();
+
+        let index = ui
+            .node(self.variant_selector)
+            .cast::<DropdownList>()
+            .expect("Must be DropdownList")
+            .items()
+            .iter()
+            .position(|item| ui.node(*item).user_data_ref::<Uuid>() == Some(&selected_uuid));
+
+        ui.send_message(DropdownListMessage::selection(
+            self.variant_selector,
+            MessageDirection::ToWidget,
+            index,
+        ));
+    }
+}
+
 pub struct ScriptPropertyEditorBuilder {
     widget_builder: WidgetBuilder,
 }
@@ -139,6 +259,7 @@ impl ScriptPropertyEditorBuilder {
     pub fn build(
         self,
         variant_selector: Handle<UiNode>,
+        remove: Handle<UiNode>,
         script_uuid: Option<Uuid>,
         environment: Option<Rc<dyn InspectorEnvironment>>,
         sync_flag: u64,
@@ -172,8 +293,11 @@ impl ScriptPropertyEditorBuilder {
                 .build(),
             selected_script_uuid: script_uuid,
             variant_selector,
+            remove,
             inspector,
             need_context_update: Cell::new(false),
+            has_modified_fields: Cell::new(false),
+            remove_confirmation: Cell::new(Handle::NONE),
         }))
     }
 }
@@ -240,6 +364,26 @@ fn fetch_script_definitions(
     editor_environment.map(|e| create_items(e.serialization_context.clone(), &mut ui.build_ctx()))
 }
 
+fn script_data(script: &Script) -> Option<Vec<u8>> {
+    let mut script = script.clone();
+    let mut visitor = Visitor::new();
+    script.deref_mut().visit("Data", &mut visitor).ok()?;
+    visitor.save_binary_to_vec().ok()
+}
+
+/// Checks whether any field of the script differs from the field of a freshly created instance
+/// of the same script type.
+fn has_modified_fields(script: &Script, serialization_context: &SerializationContext) -> bool {
+    match serialization_context
+        .script_constructors
+        .try_create(&script.id())
+    {
+        Some(default_script) => script_data(script) != script_data(&default_script),
+        // Unknown script type, better to be safe.
+        None => true,
+    }
+}
+
 #[derive(Debug)]
 pub struct ScriptPropertyEditorDefinition {}
 
@@ -266,15 +410,36 @@ impl PropertyEditorDefinition for ScriptPropertyEditorDefinition {
             .with_items(items)
             .build(ctx.build_context);
 
+        let remove = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_margin(Thickness::left(1.0))
+                .with_enabled(value.is_some())
+                .with_tooltip(make_simple_tooltip(ctx.build_context, &tr("Remove Script"))),
+        )
+        .with_text("-")
+        .build(ctx.build_context);
+
+        let header = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(variant_selector)
+                .with_child(remove),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(20.0))
+        .build(ctx.build_context);
+
         let editor;
         let container = make_expander_container(
             ctx.layer_index,
             ctx.property_info.display_name,
             ctx.property_info.description,
-            variant_selector,
+            header,
             {
                 editor = ScriptPropertyEditorBuilder::new(WidgetBuilder::new()).build(
                     variant_selector,
+                    remove,
                     value.as_ref().map(|s| s.id()),
                     ctx.environment.clone(),
                     ctx.sync_flag,
@@ -344,6 +509,12 @@ impl PropertyEditorDefinition for ScriptPropertyEditorDefinition {
             }
         }
 
+        instance_ref
+            .has_modified_fields
+            .set(value.as_ref().map_or(false, |script| {
+                has_modified_fields(script, &editor_environment.serialization_context)
+            }));
+
         if instance_ref.selected_script_uuid != value.as_ref().map(|s| s.id())
             || instance_ref.need_context_update.get()
         {
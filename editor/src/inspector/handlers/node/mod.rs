@@ -1,15 +1,20 @@
-use crate::scene::commands::RevertSceneNodePropertyCommand;
 use crate::{
+    interaction::transform_locks::TransformLocks,
     scene::commands::{
         make_set_node_property_command,
         terrain::{AddTerrainLayerCommand, DeleteTerrainLayerCommand},
+        ResetSceneNodePropertyCommand, RevertSceneNodePropertyCommand, SetPropertyCommand,
     },
     SceneCommand,
 };
 use fyrox::{
-    core::pool::Handle,
-    gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
-    scene::{node::Node, terrain::Terrain},
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+        reflect::Reflect,
+    },
+    gui::inspector::{CollectionChanged, FieldKind, PropertyAction, PropertyChanged},
+    scene::{base::Base, node::Node, terrain::Terrain, transform::Transform},
 };
 use std::any::TypeId;
 
@@ -21,6 +26,7 @@ impl SceneNodePropertyChangedHandler {
         args: &PropertyChanged,
         handle: Handle<Node>,
         node: &mut Node,
+        locks: &TransformLocks,
     ) -> Option<SceneCommand> {
         // Terrain is special and have its own commands for specific properties.
         if args.path() == Terrain::LAYERS && args.owner_type_id == TypeId::of::<Terrain>() {
@@ -36,10 +42,56 @@ impl SceneNodePropertyChangedHandler {
                 },
                 _ => None,
             }
+        } else if args.name == Base::LOCAL_TRANSFORM {
+            self.try_get_locked_transform_command(args, handle, node, locks)
         } else {
             None
         }
     }
+
+    /// Keeps locked axes of local transform intact, when its position, rotation or scale is
+    /// edited.
+    fn try_get_locked_transform_command(
+        &self,
+        args: &PropertyChanged,
+        handle: Handle<Node>,
+        node: &Node,
+        locks: &TransformLocks,
+    ) -> Option<SceneCommand> {
+        let inner = match args.value {
+            FieldKind::Inspectable(ref inner)
+                if inner.owner_type_id == TypeId::of::<Transform>() =>
+            {
+                inner
+            }
+            _ => return None,
+        };
+        let value = match inner.value {
+            FieldKind::Object(ref value) => value,
+            _ => return None,
+        };
+
+        let transform = node.local_transform();
+        let locked_value: Box<dyn Reflect> = match inner.name.as_str() {
+            Transform::LOCAL_POSITION => Box::new(
+                locks.lock_position(**transform.position(), value.cast_clone::<Vector3<f32>>()?),
+            ),
+            Transform::LOCAL_ROTATION => Box::new(locks.lock_rotation(
+                **transform.rotation(),
+                value.cast_clone::<UnitQuaternion<f32>>()?,
+            )),
+            Transform::LOCAL_SCALE => {
+                Box::new(locks.lock_scale(**transform.scale(), value.cast_clone::<Vector3<f32>>()?))
+            }
+            _ => return None,
+        };
+
+        Some(SceneCommand::new(SetPropertyCommand::new(
+            handle,
+            args.path(),
+            locked_value,
+        )))
+    }
 }
 
 impl SceneNodePropertyChangedHandler {
@@ -48,13 +100,25 @@ impl SceneNodePropertyChangedHandler {
         args: &PropertyChanged,
         handle: Handle<Node>,
         node: &mut Node,
+        locks: &TransformLocks,
     ) -> SceneCommand {
-        self.try_get_command(args, handle, node).unwrap_or_else(|| {
-            if args.is_inheritable() {
-                SceneCommand::new(RevertSceneNodePropertyCommand::new(args.path(), handle))
-            } else {
-                make_set_node_property_command(handle, args).unwrap()
-            }
-        })
+        self.try_get_command(args, handle, node, locks)
+            .unwrap_or_else(|| {
+                if args.is_inheritable() {
+                    let is_reset = matches!(
+                        PropertyAction::from_field_kind(&args.value),
+                        PropertyAction::ResetToDefault
+                    );
+
+                    // Default value of a prefab instance is the value from its prefab.
+                    if is_reset && node.resource().is_none() {
+                        SceneCommand::new(ResetSceneNodePropertyCommand::new(args.path(), handle))
+                    } else {
+                        SceneCommand::new(RevertSceneNodePropertyCommand::new(args.path(), handle))
+                    }
+                } else {
+                    make_set_node_property_command(handle, args).unwrap()
+                }
+            })
     }
 }
@@ -14,24 +14,35 @@ use crate::{
             sound_context::handle_sound_context_property_changed,
         },
     },
-    scene::{commands::effect::make_set_effect_property_command, EditorScene, Selection},
+    scene::{
+        commands::effect::make_set_effect_property_command, sidecar::node_path, EditorScene,
+        Selection,
+    },
     send_sync_message,
+    settings::{units::LengthDisplay, Settings},
     utils::window_content,
     Brush, CommandGroup, GameEngine, Message, Mode, WidgetMessage, WrapMode, MSG_SYNC_FLAG,
 };
 use fyrox::{
     animation::Animation,
-    core::{color::Color, pool::Handle, reflect::prelude::*},
+    core::{color::Color, pool::Handle, reflect::prelude::*, uuid::Uuid},
     engine::{resource_manager::ResourceManager, SerializationContext},
+    fxhash::FxHashMap,
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
         grid::{Column, GridBuilder, Row},
         inspector::{
-            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
-            InspectorEnvironment, InspectorMessage,
+            editors::{
+                inherit::{InheritablePropertyEditor, InheritablePropertyEditorMessage},
+                PropertyEditorDefinitionContainer,
+            },
+            InspectorBuilder, InspectorContext, InspectorEnvironment, InspectorMessage,
         },
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
         text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        utils::make_simple_tooltip,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
         BuildContext, Thickness, UiNode, UserInterface,
@@ -39,6 +50,7 @@ use fyrox::{
     scene::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         graph::Graph,
+        node::Node,
     },
     utils::log::{Log, MessageKind},
 };
@@ -63,6 +75,8 @@ pub struct EditorEnvironment {
     /// is `AnimationBlendingStateMachine`. The list is filled using ABSM's animation player.
     pub available_animations: Vec<AnimationDefinition>,
     pub sender: Sender<Message>,
+    /// Units of length fields, `None` means that the fields are shown in scene units.
+    pub length_display: Option<LengthDisplay>,
 }
 
 impl EditorEnvironment {
@@ -92,6 +106,15 @@ pub struct Inspector {
     node_property_changed_handler: SceneNodePropertyChangedHandler,
     warning_text: Handle<UiNode>,
     type_name_text: Handle<UiNode>,
+    node_info: Handle<UiNode>,
+    node_info_text: Handle<UiNode>,
+    copy_handle: Handle<UiNode>,
+    /// Node which handle and path are shown, with the last shown text.
+    shown_node: Option<(Handle<Node>, String)>,
+    /// Units of length fields of the current context.
+    length_display: Option<LengthDisplay>,
+    /// Default instances of node types, used to tell whether node properties have default values.
+    default_nodes: FxHashMap<Uuid, Option<Node>>,
 }
 
 #[macro_export]
@@ -133,6 +156,122 @@ macro_rules! handle_property_changed {
     }
 }
 
+/// Walks over the properties of the given object and highlights every inheritable property
+/// whose value differs from the respective property of the prefab. If there is no prefab, all
+/// the highlights will be removed. Also, if there is a default object, every inheritable property
+/// is told whether it has the default value or not, so the reset buttons will be updated.
+fn sync_prefab_overrides(
+    context: &InspectorContext,
+    object: &dyn Reflect,
+    prefab: Option<&dyn Reflect>,
+    default: Option<&dyn Reflect>,
+    ui: &UserInterface,
+) {
+    for (field, info) in object.fields().into_iter().zip(object.fields_info()) {
+        let entry = match context.find_property_editor(info.name) {
+            Some(entry) => entry,
+            None => continue,
+        };
+
+        let mut editor = entry.property_editor;
+        let mut value = field;
+        let mut prefab_value = prefab.and_then(|p| p.field(info.name));
+        let mut default_value = default.and_then(|d| d.field(info.name));
+
+        if let Some(inheritable) = field.as_inheritable_variable() {
+            let prefab_inheritable = prefab_value.and_then(|p| p.as_inheritable_variable());
+            let default_inheritable = default_value.and_then(|d| d.as_inheritable_variable());
+
+            if let Some(inheritable_editor) = ui.node(editor).cast::<InheritablePropertyEditor>() {
+                send_sync_message(
+                    ui,
+                    InheritablePropertyEditorMessage::modified(
+                        editor,
+                        MessageDirection::ToWidget,
+                        prefab_inheritable.map_or(false, |p| !inheritable.value_equals(p)),
+                    ),
+                );
+
+                if let Some(default_inheritable) = default_inheritable {
+                    send_sync_message(
+                        ui,
+                        InheritablePropertyEditorMessage::is_default(
+                            editor,
+                            MessageDirection::ToWidget,
+                            inheritable.value_equals(default_inheritable),
+                        ),
+                    );
+                }
+
+                editor = inheritable_editor.inner_editor();
+            }
+
+            value = inheritable.inner_value_ref();
+            prefab_value = prefab_inheritable.map(|p| p.inner_value_ref());
+            default_value = default_inheritable.map(|d| d.inner_value_ref());
+        }
+
+        // Inner properties could also be overridden, check them too.
+        if let Some(inner_inspector) = ui.node(editor).cast::<fyrox::gui::inspector::Inspector>() {
+            sync_prefab_overrides(
+                inner_inspector.context(),
+                value,
+                prefab_value,
+                default_value,
+                ui,
+            );
+        }
+    }
+}
+
+/// Highlights properties of the node that were overridden in comparison with the prefab the node
+/// was instantiated from. Nodes that are not prefab instances do not have such markers at all.
+/// Default values of the properties are taken from the prefab for instances, or from a default
+/// node of the same type otherwise. Default nodes are constructed once per type and then reused.
+fn sync_node_prefab_overrides(
+    context: &InspectorContext,
+    node: &Node,
+    serialization_context: &SerializationContext,
+    default_nodes: &mut FxHashMap<Uuid, Option<Node>>,
+    ui: &UserInterface,
+) {
+    if let Some(resource) = node.resource() {
+        let data = resource.data_ref();
+        if let Some(prefab_node) = data
+            .get_scene()
+            .graph
+            .try_get(node.original_handle_in_resource())
+        {
+            sync_prefab_overrides(
+                context,
+                node.as_reflect(),
+                Some(prefab_node.as_reflect()),
+                Some(prefab_node.as_reflect()),
+                ui,
+            );
+            return;
+        }
+    }
+
+    let default_node = default_nodes.entry(node.id()).or_insert_with(|| {
+        serialization_context
+            .node_constructors
+            .try_create(&node.id())
+    });
+
+    sync_prefab_overrides(
+        context,
+        node.as_reflect(),
+        None,
+        default_node.as_ref().map(|n| n.as_reflect()),
+        ui,
+    );
+}
+
+fn describe_node(graph: &Graph, handle: Handle<Node>) -> String {
+    format!("Handle: {}  Path: {}", handle, node_path(graph, handle))
+}
+
 impl Inspector {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let property_editors = Rc::new(make_property_editors_container(sender));
@@ -143,8 +282,11 @@ impl Inspector {
 
         let warning_text;
         let type_name_text;
+        let node_info;
+        let node_info_text;
+        let copy_handle;
         let inspector;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("Inspector"))
             .with_title(WindowTitle::text("Inspector"))
             .with_content(
                 GridBuilder::new(
@@ -172,8 +314,44 @@ impl Inspector {
                             .build(ctx);
                             type_name_text
                         })
+                        .with_child({
+                            node_info = GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_visibility(false)
+                                    .on_row(2)
+                                    .with_child({
+                                        node_info_text = TextBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::left(4.0))
+                                                .on_column(0),
+                                        )
+                                        .with_editable(false)
+                                        .build(ctx);
+                                        node_info_text
+                                    })
+                                    .with_child({
+                                        copy_handle = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Copy the handle of the node to the clipboard",
+                                                ))
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .on_column(1),
+                                        )
+                                        .with_text("Copy Handle")
+                                        .build(ctx);
+                                        copy_handle
+                                    }),
+                            )
+                            .add_row(Row::strict(22.0))
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .build(ctx);
+                            node_info
+                        })
                         .with_child(
-                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(2))
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(3))
                                 .with_content({
                                     inspector =
                                         InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
@@ -184,6 +362,7 @@ impl Inspector {
                 )
                 .add_row(Row::auto())
                 .add_row(Row::auto())
+                .add_row(Row::auto())
                 .add_row(Row::stretch())
                 .add_column(Column::stretch())
                 .build(ctx),
@@ -198,7 +377,56 @@ impl Inspector {
             node_property_changed_handler: SceneNodePropertyChangedHandler,
             warning_text,
             type_name_text,
+            node_info,
+            node_info_text,
+            copy_handle,
+            shown_node: None,
+            length_display: None,
+            default_nodes: Default::default(),
+        }
+    }
+
+    /// Shows handle and path of the first selected node, the info is hidden for other kinds of
+    /// selection. The path depends on names of the node and its ancestors, so it must be synced
+    /// after every change of the scene.
+    fn sync_node_info(&mut self, selection: &Selection, graph: &Graph, ui: &UserInterface) {
+        let shown_node = match selection {
+            Selection::Graph(selection) => selection
+                .nodes()
+                .first()
+                .filter(|handle| graph.is_valid_handle(**handle))
+                .map(|&handle| (handle, describe_node(graph, handle))),
+            _ => None,
+        };
+
+        self.set_shown_node(shown_node, ui);
+    }
+
+    fn set_shown_node(&mut self, shown_node: Option<(Handle<Node>, String)>, ui: &UserInterface) {
+        if shown_node == self.shown_node {
+            return;
         }
+
+        send_sync_message(
+            ui,
+            WidgetMessage::visibility(
+                self.node_info,
+                MessageDirection::ToWidget,
+                shown_node.is_some(),
+            ),
+        );
+        if let Some((_, text)) = shown_node.as_ref() {
+            send_sync_message(
+                ui,
+                TextMessage::text(
+                    self.node_info_text,
+                    MessageDirection::ToWidget,
+                    text.clone(),
+                ),
+            );
+        }
+
+        self.shown_node = shown_node;
     }
 
     fn sync_to(&mut self, obj: &dyn Reflect, ui: &mut UserInterface) {
@@ -222,6 +450,12 @@ impl Inspector {
     pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
         let scene = &engine.scenes[editor_scene.scene];
 
+        self.sync_node_info(
+            &editor_scene.selection,
+            &scene.graph,
+            &engine.user_interface,
+        );
+
         if self.needs_sync {
             if editor_scene.selection.is_single_selection() {
                 let obj: Option<&dyn Reflect> = match &editor_scene.selection {
@@ -299,6 +533,24 @@ impl Inspector {
                 if let Some(obj) = obj {
                     self.sync_to(obj, &mut engine.user_interface);
                 }
+
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    if let Some(node) = scene.graph.try_get(selection.nodes()[0]) {
+                        let ui = &engine.user_interface;
+                        let context = ui
+                            .node(self.inspector)
+                            .cast::<fyrox::gui::inspector::Inspector>()
+                            .unwrap()
+                            .context();
+                        sync_node_prefab_overrides(
+                            context,
+                            node,
+                            &engine.serialization_context,
+                            &mut self.default_nodes,
+                            ui,
+                        );
+                    }
+                }
             }
         } else {
             self.needs_sync = true;
@@ -314,10 +566,13 @@ impl Inspector {
         graph: &Graph,
         selection: &Selection,
         sender: &Sender<Message>,
+        length_display: Option<LengthDisplay>,
     ) {
+        self.length_display = length_display;
+
         let environment = Rc::new(EditorEnvironment {
             resource_manager,
-            serialization_context,
+            serialization_context: serialization_context.clone(),
             available_animations: if let Selection::Absm(absm_selection) = selection {
                 if let Some(animation_player) = graph
                     .try_get(absm_selection.absm_node_handle)
@@ -340,6 +595,7 @@ impl Inspector {
                 Default::default()
             },
             sender: sender.clone(),
+            length_display,
         });
 
         let context = InspectorContext::from_object(
@@ -352,8 +608,26 @@ impl Inspector {
             true,
         );
 
+        if let Selection::Graph(graph_selection) = selection {
+            if let Some(node) = graph_selection
+                .nodes()
+                .first()
+                .and_then(|&first| graph.try_get(first))
+            {
+                sync_node_prefab_overrides(
+                    &context,
+                    node,
+                    &serialization_context,
+                    &mut self.default_nodes,
+                    ui,
+                );
+            }
+        }
+
         self.needs_sync = false;
 
+        self.sync_node_info(selection, graph, ui);
+
         ui.send_message(InspectorMessage::context(
             self.inspector,
             MessageDirection::ToWidget,
@@ -376,8 +650,17 @@ impl Inspector {
         editor_scene: &EditorScene,
         engine: &mut GameEngine,
         sender: &Sender<Message>,
+        settings: &Settings,
     ) {
-        if let Message::SelectionChanged { .. } = message {
+        let length_display = settings.units.length_display();
+
+        let needs_new_context = match message {
+            Message::SelectionChanged { .. } => true,
+            Message::ForceSync => length_display != self.length_display,
+            _ => false,
+        };
+
+        if needs_new_context {
             let scene = &engine.scenes[editor_scene.scene];
 
             engine
@@ -469,6 +752,7 @@ impl Inspector {
                         &scene.graph,
                         &editor_scene.selection,
                         sender,
+                        length_display,
                     )
                 }
             } else {
@@ -477,7 +761,8 @@ impl Inspector {
         }
     }
 
-    pub fn clear(&self, ui: &UserInterface) {
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.set_shown_node(None, ui);
         ui.send_message(InspectorMessage::context(
             self.inspector,
             MessageDirection::ToWidget,
@@ -502,6 +787,16 @@ impl Inspector {
     ) {
         let scene = &mut engine.scenes[editor_scene.scene];
 
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.copy_handle {
+                if let Some((handle, _)) = self.shown_node.as_ref() {
+                    if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
+                        Log::verify(clipboard.set_contents(handle.to_string()));
+                    }
+                }
+            }
+        }
+
         if message.destination() == self.inspector
             && message.direction() == MessageDirection::FromWidget
         {
@@ -518,6 +813,7 @@ impl Inspector {
                                     args,
                                     node_handle,
                                     &mut scene.graph[node_handle],
+                                    &editor_scene.transform_locks,
                                 ))
                             } else {
                                 None
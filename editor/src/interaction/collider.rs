@@ -0,0 +1,911 @@
+//! Collider editing mode. Visualizes the shape of the collider of the selected node as a
+//! translucent mesh and allows changing its dimensions and offset by dragging handles.
+
+use crate::{
+    camera::PickingOptions,
+    interaction::{calculate_gizmo_distance_scaling, make_pick_selection, InteractionMode},
+    make_color_material,
+    scene::{
+        commands::{
+            graph::{decompose_matrix, AddNodeCommand, MoveNodeCommand, SetColliderShapeCommand},
+            ChangeSelectionCommand,
+        },
+        EditorScene, Selection,
+    },
+    set_mesh_diffuse_color,
+    settings::Settings,
+    GameEngine, Message,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{plane::Plane, ray::Ray},
+        pool::Handle,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    material::SharedMaterial,
+    scene::{
+        base::BaseBuilder,
+        camera::Camera,
+        collider::{Collider, ColliderBuilder, ColliderShape, CuboidShape},
+        graph::Graph,
+        mesh::{
+            surface::{Surface, SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder, RenderPath,
+        },
+        node::Node,
+        transform::TransformBuilder,
+    },
+};
+use std::sync::mpsc::Sender;
+
+const SHAPE_COLOR: Color = Color::from_rgba(0, 200, 255, 60);
+const HANDLE_COLOR: Color = Color::opaque(0, 200, 255);
+const ACTIVE_HANDLE_COLOR: Color = Color::opaque(255, 255, 0);
+const OFFSET_HANDLE_COLOR: Color = Color::opaque(255, 140, 0);
+const HANDLE_SIZE: f32 = 0.08;
+const MIN_EXTENT: f32 = 0.001;
+
+/// A parameter of a collider that is changed by a handle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum ShapeParameter {
+    /// Local position of the collider.
+    Offset,
+    /// Half extent of a cuboid along an axis with given index.
+    HalfExtent(usize),
+    Radius,
+    HalfHeight,
+    CapsuleBegin,
+    CapsuleEnd,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ShapeHandle {
+    node: Handle<Node>,
+    parameter: ShapeParameter,
+    /// Origin of a line (in local space of the collider) along which the handle moves.
+    origin: Vector3<f32>,
+    /// Direction of the line, it always points outwards.
+    axis: Vector3<f32>,
+    /// Current distance from the origin along the axis.
+    value: f32,
+}
+
+impl ShapeHandle {
+    fn new(
+        parameter: ShapeParameter,
+        origin: Vector3<f32>,
+        axis: Vector3<f32>,
+        value: f32,
+    ) -> Self {
+        Self {
+            node: Handle::NONE,
+            parameter,
+            origin,
+            axis,
+            value,
+        }
+    }
+
+    fn local_position(&self) -> Vector3<f32> {
+        self.origin + self.axis.scale(self.value)
+    }
+
+    fn apply(&self, shape: &mut ColliderShape, value: f32) {
+        let value = value.max(MIN_EXTENT);
+        let point = self.origin + self.axis.scale(value);
+        match (shape, self.parameter) {
+            (ColliderShape::Cuboid(cuboid), ShapeParameter::HalfExtent(axis)) => {
+                cuboid.half_extents[axis] = value;
+            }
+            (ColliderShape::Ball(ball), ShapeParameter::Radius) => ball.radius = value,
+            (ColliderShape::Cylinder(cylinder), ShapeParameter::Radius) => cylinder.radius = value,
+            (ColliderShape::Cylinder(cylinder), ShapeParameter::HalfHeight) => {
+                cylinder.half_height = value
+            }
+            (ColliderShape::Cone(cone), ShapeParameter::Radius) => cone.radius = value,
+            (ColliderShape::Cone(cone), ShapeParameter::HalfHeight) => cone.half_height = value,
+            (ColliderShape::Capsule(capsule), ShapeParameter::Radius) => capsule.radius = value,
+            (ColliderShape::Capsule(capsule), ShapeParameter::CapsuleBegin) => {
+                capsule.begin = point
+            }
+            (ColliderShape::Capsule(capsule), ShapeParameter::CapsuleEnd) => capsule.end = point,
+            _ => (),
+        }
+    }
+}
+
+fn perpendicular(v: Vector3<f32>) -> Vector3<f32> {
+    let other = if v.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    v.cross(&other)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z)
+}
+
+/// Creates a set of handles for given shape. Every shape has at least the offset handle.
+fn make_shape_handles(shape: &ColliderShape) -> Vec<ShapeHandle> {
+    let mut handles = vec![ShapeHandle::new(
+        ShapeParameter::Offset,
+        Vector3::default(),
+        Vector3::default(),
+        0.0,
+    )];
+
+    let mut add_symmetric = |parameter, axis: Vector3<f32>, value| {
+        for sign in [1.0, -1.0] {
+            handles.push(ShapeHandle::new(
+                parameter,
+                Vector3::default(),
+                axis.scale(sign),
+                value,
+            ));
+        }
+    };
+
+    match shape {
+        ColliderShape::Cuboid(cuboid) => {
+            for (i, axis) in [Vector3::x(), Vector3::y(), Vector3::z()]
+                .iter()
+                .enumerate()
+            {
+                add_symmetric(ShapeParameter::HalfExtent(i), *axis, cuboid.half_extents[i]);
+            }
+        }
+        ColliderShape::Ball(ball) => {
+            for axis in [Vector3::x(), Vector3::y(), Vector3::z()] {
+                add_symmetric(ShapeParameter::Radius, axis, ball.radius);
+            }
+        }
+        ColliderShape::Cylinder(cylinder) => {
+            add_symmetric(
+                ShapeParameter::HalfHeight,
+                Vector3::y(),
+                cylinder.half_height,
+            );
+            add_symmetric(ShapeParameter::Radius, Vector3::x(), cylinder.radius);
+            add_symmetric(ShapeParameter::Radius, Vector3::z(), cylinder.radius);
+        }
+        ColliderShape::Cone(cone) => {
+            add_symmetric(ShapeParameter::HalfHeight, Vector3::y(), cone.half_height);
+            add_symmetric(ShapeParameter::Radius, Vector3::x(), cone.radius);
+            add_symmetric(ShapeParameter::Radius, Vector3::z(), cone.radius);
+        }
+        ColliderShape::Capsule(capsule) => {
+            let center = (capsule.begin + capsule.end).scale(0.5);
+            let axis = (capsule.end - capsule.begin)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::y);
+            let half_length = (capsule.end - center).norm();
+            handles.push(ShapeHandle::new(
+                ShapeParameter::CapsuleBegin,
+                center,
+                -axis,
+                half_length,
+            ));
+            handles.push(ShapeHandle::new(
+                ShapeParameter::CapsuleEnd,
+                center,
+                axis,
+                half_length,
+            ));
+            for sign in [1.0, -1.0] {
+                handles.push(ShapeHandle::new(
+                    ShapeParameter::Radius,
+                    center,
+                    perpendicular(axis).scale(sign),
+                    capsule.radius,
+                ));
+            }
+        }
+        // Other shapes are defined by geometry of other nodes (or by a set of points), so there
+        // is nothing to resize here.
+        _ => (),
+    }
+
+    handles
+}
+
+/// Creates a set of surfaces that matches given shape in local space of a collider. Shapes that
+/// are built from geometry of other nodes have no visualization, they are shown by the physics
+/// debug drawing.
+fn make_shape_surfaces(shape: &ColliderShape, material: &SharedMaterial) -> Vec<Surface> {
+    const SIDES: usize = 24;
+
+    let data = match shape {
+        ColliderShape::Cuboid(cuboid) => vec![SurfaceData::make_cube(
+            Matrix4::new_nonuniform_scaling(&cuboid.half_extents.scale(2.0)),
+        )],
+        ColliderShape::Ball(ball) => vec![SurfaceData::make_sphere(
+            SIDES,
+            SIDES,
+            ball.radius,
+            &Matrix4::identity(),
+        )],
+        ColliderShape::Cylinder(cylinder) => vec![SurfaceData::make_cylinder(
+            SIDES,
+            cylinder.radius,
+            2.0 * cylinder.half_height,
+            true,
+            &Matrix4::new_translation(&Vector3::new(0.0, -cylinder.half_height, 0.0)),
+        )],
+        ColliderShape::Cone(cone) => vec![SurfaceData::make_cone(
+            SIDES,
+            cone.radius,
+            2.0 * cone.half_height,
+            &Matrix4::new_translation(&Vector3::new(0.0, -cone.half_height, 0.0)),
+        )],
+        ColliderShape::Capsule(capsule) => {
+            let axis = capsule.end - capsule.begin;
+            let rotation =
+                UnitQuaternion::rotation_between(&Vector3::y(), &axis).unwrap_or_else(|| {
+                    if axis.y < 0.0 {
+                        UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::PI)
+                    } else {
+                        UnitQuaternion::identity()
+                    }
+                });
+            vec![
+                SurfaceData::make_cylinder(
+                    SIDES,
+                    capsule.radius,
+                    axis.norm(),
+                    false,
+                    &(Matrix4::new_translation(&capsule.begin) * rotation.to_homogeneous()),
+                ),
+                SurfaceData::make_sphere(
+                    SIDES,
+                    SIDES,
+                    capsule.radius,
+                    &Matrix4::new_translation(&capsule.begin),
+                ),
+                SurfaceData::make_sphere(
+                    SIDES,
+                    SIDES,
+                    capsule.radius,
+                    &Matrix4::new_translation(&capsule.end),
+                ),
+            ]
+        }
+        _ => vec![],
+    };
+
+    data.into_iter()
+        .map(|data| {
+            SurfaceBuilder::new(SurfaceSharedData::new(data))
+                .with_material(material.clone())
+                .build()
+        })
+        .collect()
+}
+
+/// Returns a collider that is edited for current selection: either the selected collider itself
+/// or the first collider among children of the selected node.
+fn selected_collider(selection: &Selection, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            let node = graph.try_get(handle)?;
+            if node.cast::<Collider>().is_some() {
+                return Some(handle);
+            }
+            return node
+                .children()
+                .iter()
+                .cloned()
+                .find(|&child| graph[child].cast::<Collider>().is_some());
+        }
+    }
+    None
+}
+
+/// Creates a box collider that encloses local bounds of the node (if it has any).
+fn make_fitting_collider(node: &Node) -> Node {
+    let bounds = node.local_bounding_box();
+    let half_extents = bounds.half_extents();
+
+    let (half_extents, position) = if half_extents
+        .iter()
+        .all(|extent| extent.is_finite() && *extent > MIN_EXTENT)
+    {
+        (half_extents, bounds.center())
+    } else {
+        (CuboidShape::default().half_extents, Vector3::default())
+    };
+
+    ColliderBuilder::new(
+        BaseBuilder::new()
+            .with_name("Collider")
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+    )
+    .with_shape(ColliderShape::Cuboid(CuboidShape { half_extents }))
+    .build_node()
+}
+
+/// Returns distance along the line (`origin`, `direction`) to the point of the line that is
+/// closest to the ray.
+fn closest_line_parameter(ray: &Ray, origin: Vector3<f32>, direction: Vector3<f32>) -> Option<f32> {
+    let w = origin - ray.origin;
+    let a = direction.dot(&direction);
+    let b = direction.dot(&ray.dir);
+    let c = ray.dir.dot(&ray.dir);
+    let d = direction.dot(&w);
+    let e = ray.dir.dot(&w);
+    let denominator = a * c - b * b;
+    if denominator.abs() <= f32::EPSILON {
+        // Line is parallel to the ray.
+        None
+    } else {
+        Some((b * e - c * d) / denominator)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PanelState {
+    NoSelection,
+    NoCollider,
+    Collider(String),
+}
+
+struct ColliderPanel {
+    window: Handle<UiNode>,
+    status: Handle<UiNode>,
+    show_shape: Handle<UiNode>,
+    add_collider: Handle<UiNode>,
+    state: Option<PanelState>,
+}
+
+impl ColliderPanel {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let status;
+        let show_shape;
+        let add_collider;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(130.0))
+            .can_close(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            status = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_column(0)
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            status
+                        })
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Show Shape")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        show_shape = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .checked(Some(true))
+                                        .build(ctx);
+                                        show_shape
+                                    }),
+                            )
+                            .add_row(Row::stretch())
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .build(ctx),
+                        )
+                        .with_child({
+                            add_collider = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_enabled(false)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Add Collider")
+                            .build(ctx);
+                            add_collider
+                        }),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Collider"))
+            .build(ctx);
+
+        Self {
+            window,
+            status,
+            show_shape,
+            add_collider,
+            state: None,
+        }
+    }
+
+    fn set_state(&mut self, state: PanelState, ui: &UserInterface) {
+        if self.state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let text = match state {
+            PanelState::NoSelection => "Select a node to edit its collider.".to_string(),
+            PanelState::NoCollider => {
+                "Selected node has no collider. Add one to edit its shape.".to_string()
+            }
+            PanelState::Collider(ref name) => format!(
+                "Editing {}. Drag the handles to change its size or offset.",
+                name
+            ),
+        };
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.add_collider,
+            MessageDirection::ToWidget,
+            state == PanelState::NoCollider,
+        ));
+
+        self.state = Some(state);
+    }
+}
+
+struct DragContext {
+    collider: Handle<Node>,
+    handle: ShapeHandle,
+    initial_shape: ColliderShape,
+    initial_position: Vector3<f32>,
+    /// Plane along which the offset handle moves, and a point where the plane was hit initially.
+    plane: Option<(Plane, Vector3<f32>)>,
+}
+
+pub struct ColliderInteractionMode {
+    message_sender: Sender<Message>,
+    shape_gizmo: Handle<Node>,
+    shape_material: SharedMaterial,
+    /// A shape the visualization was built for, it is used to rebuild the visualization only
+    /// when the shape has changed.
+    visualized_shape: Option<ColliderShape>,
+    handles: Vec<ShapeHandle>,
+    drag: Option<DragContext>,
+    show_shape: bool,
+    panel: ColliderPanel,
+}
+
+impl ColliderInteractionMode {
+    pub fn new(
+        editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        message_sender: Sender<Message>,
+    ) -> Self {
+        let panel = ColliderPanel::new(&mut engine.user_interface.build_ctx());
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let shape_gizmo = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_cast_shadows(false)
+                .with_name("ColliderShape")
+                .with_visibility(false),
+        )
+        .with_render_path(RenderPath::Forward)
+        .build(graph);
+        graph.link_nodes(shape_gizmo, editor_scene.editor_objects_root);
+
+        Self {
+            message_sender,
+            shape_gizmo,
+            shape_material: make_color_material(SHAPE_COLOR),
+            visualized_shape: None,
+            handles: Default::default(),
+            drag: None,
+            show_shape: true,
+            panel,
+        }
+    }
+
+    fn destroy_handles(&mut self, graph: &mut Graph) {
+        for handle in self.handles.drain(..) {
+            graph.remove_node(handle.node);
+        }
+    }
+
+    fn hide(&mut self, graph: &mut Graph) {
+        self.destroy_handles(graph);
+        graph[self.shape_gizmo].set_visibility(false);
+    }
+
+    fn sync_handles(
+        &mut self,
+        graph: &mut Graph,
+        shape: &ColliderShape,
+        editor_objects_root: Handle<Node>,
+    ) {
+        let mut new_handles = make_shape_handles(shape);
+
+        let same_layout = new_handles.len() == self.handles.len()
+            && new_handles
+                .iter()
+                .zip(self.handles.iter())
+                .all(|(a, b)| a.parameter == b.parameter);
+
+        if same_layout {
+            for (new_handle, old_handle) in new_handles.iter_mut().zip(self.handles.iter()) {
+                new_handle.node = old_handle.node;
+            }
+        } else {
+            self.destroy_handles(graph);
+
+            for handle in new_handles.iter_mut() {
+                let color = if handle.parameter == ShapeParameter::Offset {
+                    OFFSET_HANDLE_COLOR
+                } else {
+                    HANDLE_COLOR
+                };
+                handle.node = MeshBuilder::new(
+                    BaseBuilder::new()
+                        .with_cast_shadows(false)
+                        .with_name("ColliderHandle"),
+                )
+                .with_render_path(RenderPath::Forward)
+                .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                    SurfaceData::make_cube(Matrix4::new_scaling(HANDLE_SIZE)),
+                ))
+                .with_material(make_color_material(color))
+                .build()])
+                .build(graph);
+                graph.link_nodes(handle.node, editor_objects_root);
+            }
+        }
+
+        self.handles = new_handles;
+    }
+
+    fn set_handle_active(&self, graph: &mut Graph, handle: &ShapeHandle, active: bool) {
+        let color = if active {
+            ACTIVE_HANDLE_COLOR
+        } else if handle.parameter == ShapeParameter::Offset {
+            OFFSET_HANDLE_COLOR
+        } else {
+            HANDLE_COLOR
+        };
+        if let Some(mesh) = graph
+            .try_get_mut(handle.node)
+            .and_then(|node| node.cast_mut::<Mesh>())
+        {
+            set_mesh_diffuse_color(mesh, color);
+        }
+    }
+}
+
+impl InteractionMode for ColliderInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        let collider = match selected_collider(&editor_scene.selection, graph) {
+            Some(collider) => collider,
+            None => return,
+        };
+
+        let handle_nodes = self.handles.iter().map(|h| h.node).collect::<Vec<_>>();
+        let picked = editor_scene
+            .camera_controller
+            .pick(PickingOptions {
+                cursor_pos: mouse_pos,
+                graph,
+                editor_objects_root: editor_scene.editor_objects_root,
+                screen_size: frame_size,
+                editor_only: true,
+                filter: |handle, _| handle_nodes.contains(&handle),
+                ignore_back_faces: settings.selection.ignore_back_faces,
+                use_picking_loop: false,
+                only_meshes: false,
+            })
+            .and_then(|result| self.handles.iter().find(|h| h.node == result.node))
+            .cloned();
+
+        if let Some(handle) = picked {
+            let plane = match graph[editor_scene.camera_controller.camera].cast::<Camera>() {
+                Some(camera) if handle.parameter == ShapeParameter::Offset => {
+                    let ray = camera.make_ray(mouse_pos, frame_size);
+                    Plane::from_normal_and_point(
+                        &camera.look_vector(),
+                        &graph[collider].global_position(),
+                    )
+                    .and_then(|plane| ray.plane_intersection_point(&plane).map(|hit| (plane, hit)))
+                }
+                _ => None,
+            };
+
+            let node = &graph[collider];
+            self.drag = Some(DragContext {
+                collider,
+                handle,
+                initial_shape: node.cast::<Collider>().unwrap().shape().clone(),
+                initial_position: **node.local_transform().position(),
+                plane,
+            });
+
+            self.set_handle_active(graph, &handle, true);
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        if let Some(drag) = self.drag.take() {
+            let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+            self.set_handle_active(graph, &drag.handle, false);
+
+            if drag.handle.parameter == ShapeParameter::Offset {
+                let new_position = **graph[drag.collider].local_transform().position();
+                if new_position != drag.initial_position {
+                    self.message_sender
+                        .send(Message::do_scene_command(MoveNodeCommand::new(
+                            drag.collider,
+                            drag.initial_position,
+                            new_position,
+                        )))
+                        .unwrap();
+                }
+            } else if let Some(collider) = graph[drag.collider].cast_mut::<Collider>() {
+                if collider.shape() != &drag.initial_shape {
+                    // Restore the initial shape, so the command will remember it for undo.
+                    let new_shape = collider.set_shape(drag.initial_shape);
+                    self.message_sender
+                        .send(Message::do_scene_command(SetColliderShapeCommand::new(
+                            drag.collider,
+                            new_shape,
+                        )))
+                        .unwrap();
+                }
+            }
+        } else {
+            let new_selection =
+                make_pick_selection(editor_scene, engine, mouse_pos, frame_size, settings);
+
+            if new_selection != editor_scene.selection {
+                self.message_sender
+                    .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                        new_selection,
+                        editor_scene.selection.clone(),
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        camera: Handle<Node>,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        let drag = match self.drag.as_ref() {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let ray = match graph[camera].cast::<Camera>() {
+            Some(camera) => camera.make_ray(mouse_position, frame_size),
+            None => return,
+        };
+
+        if drag.handle.parameter == ShapeParameter::Offset {
+            if let Some((plane, start)) = drag.plane {
+                if let Some(hit) = ray.plane_intersection_point(&plane) {
+                    let parent = graph[drag.collider].parent();
+                    let parent_inv = graph
+                        .try_get(parent)
+                        .and_then(|parent| parent.global_transform().try_inverse())
+                        .unwrap_or_else(Matrix4::identity);
+                    let delta = parent_inv.transform_vector(&(hit - start));
+                    graph[drag.collider]
+                        .local_transform_mut()
+                        .set_position(drag.initial_position + delta);
+                }
+            }
+        } else {
+            let transform = graph[drag.collider].global_transform();
+            let origin = transform
+                .transform_point(&Point3::from(drag.handle.origin))
+                .coords;
+            let axis = transform.transform_vector(&drag.handle.axis);
+            let axis_length = axis.norm();
+            if axis_length > f32::EPSILON {
+                if let Some(distance) = closest_line_parameter(&ray, origin, axis / axis_length) {
+                    let mut shape = drag.initial_shape.clone();
+                    drag.handle.apply(&mut shape, distance / axis_length);
+                    if let Some(collider) = graph[drag.collider].cast_mut::<Collider>() {
+                        collider.set_shape(shape);
+                    }
+                }
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        camera: Handle<Node>,
+        engine: &mut GameEngine,
+        _settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        let collider = selected_collider(&editor_scene.selection, graph).and_then(|handle| {
+            graph[handle]
+                .cast::<Collider>()
+                .map(|c| (handle, c.shape().clone(), c.global_transform()))
+        });
+
+        let (collider, shape, transform) = match collider {
+            Some(collider) => collider,
+            None => {
+                self.hide(graph);
+                let state = if matches!(&editor_scene.selection,
+                    Selection::Graph(selection) if selection.is_single_selection())
+                {
+                    PanelState::NoCollider
+                } else {
+                    PanelState::NoSelection
+                };
+                self.panel.set_state(state, &engine.user_interface);
+                return;
+            }
+        };
+
+        self.panel.set_state(
+            PanelState::Collider(graph[collider].name_owned()),
+            &engine.user_interface,
+        );
+
+        // Visualization.
+        if self.visualized_shape.as_ref() != Some(&shape) {
+            let surfaces = make_shape_surfaces(&shape, &self.shape_material);
+            if let Some(mesh) = graph[self.shape_gizmo].cast_mut::<Mesh>() {
+                mesh.set_surfaces(surfaces);
+            }
+            self.visualized_shape = Some(shape.clone());
+        }
+        let shape_gizmo = &mut graph[self.shape_gizmo];
+        shape_gizmo.set_visibility(self.show_shape);
+        *shape_gizmo.local_transform_mut() = decompose_matrix(&transform).0;
+
+        // Handles.
+        self.sync_handles(graph, &shape, editor_scene.editor_objects_root);
+        for handle in self.handles.iter() {
+            let position = transform
+                .transform_point(&Point3::from(handle.local_position()))
+                .coords;
+            let scale = calculate_gizmo_distance_scaling(graph, camera, handle.node);
+            graph[handle.node]
+                .local_transform_mut()
+                .set_position(position)
+                .set_scale(scale);
+        }
+    }
+
+    fn activate(&mut self, _editor_scene: &EditorScene, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.panel.window,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        // Cancel unfinished dragging.
+        if let Some(drag) = self.drag.take() {
+            if let Some(collider) = graph
+                .try_get_mut(drag.collider)
+                .and_then(|node| node.cast_mut::<Collider>())
+            {
+                collider.set_shape(drag.initial_shape);
+            }
+            if let Some(node) = graph.try_get_mut(drag.collider) {
+                node.local_transform_mut()
+                    .set_position(drag.initial_position);
+            }
+        }
+
+        self.hide(graph);
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.panel.add_collider {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    if selection.is_single_selection() {
+                        let parent = selection.nodes()[0];
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        if let Some(node) = graph.try_get(parent) {
+                            self.message_sender
+                                .send(Message::do_scene_command(AddNodeCommand::new(
+                                    make_fitting_collider(node),
+                                    parent,
+                                )))
+                                .unwrap();
+                        }
+                    }
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.panel.show_shape
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.show_shape = *value;
+            }
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    interaction::plane::PlaneKind,
+    interaction::{pivot::PivotMode, plane::PlaneKind},
     make_color_material,
     scene::{EditorScene, Selection},
     set_mesh_diffuse_color, GameEngine,
@@ -334,10 +334,20 @@ impl MoveGizmo {
         Vector3::default()
     }
 
-    pub fn sync_transform(&self, scene: &mut Scene, selection: &Selection, scale: Vector3<f32>) {
+    pub fn sync_transform(
+        &self,
+        scene: &mut Scene,
+        selection: &Selection,
+        scale: Vector3<f32>,
+        pivot: PivotMode,
+        cursor: Option<Vector3<f32>>,
+    ) {
         let graph = &mut scene.graph;
         if let Selection::Graph(selection) = selection {
-            if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+            if let (Some((rotation, _)), Some(position)) = (
+                selection.global_rotation_position(graph),
+                pivot.position(selection, graph, cursor),
+            ) {
                 let node = &mut graph[self.origin];
                 node.set_visibility(true);
                 node.local_transform_mut()
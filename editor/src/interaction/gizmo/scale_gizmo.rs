@@ -1,5 +1,5 @@
 use crate::{
-    make_color_material, scene::EditorScene, set_mesh_diffuse_color,
+    interaction::pivot::PivotMode, make_color_material, scene::EditorScene, set_mesh_diffuse_color,
     world::graph::selection::GraphSelection, GameEngine,
 };
 use fyrox::{
@@ -281,8 +281,13 @@ impl ScaleGizmo {
         graph: &mut Graph,
         selection: &GraphSelection,
         scale: Vector3<f32>,
+        pivot: PivotMode,
+        cursor: Option<Vector3<f32>>,
     ) {
-        if let Some((rotation, position)) = selection.global_rotation_position(graph) {
+        if let (Some((rotation, _)), Some(position)) = (
+            selection.global_rotation_position(graph),
+            pivot.position(selection, graph, cursor),
+        ) {
             let node = &mut graph[self.origin];
             node.set_visibility(true);
             node.local_transform_mut()
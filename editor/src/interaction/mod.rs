@@ -1,23 +1,34 @@
-use crate::{scene::EditorScene, settings::Settings, GameEngine};
+use crate::{
+    camera::PickingOptions,
+    scene::{EditorScene, Selection},
+    settings::{keys::KeyBindings, Settings},
+    world::graph::selection::GraphSelection,
+    GameEngine,
+};
 use fyrox::scene::camera::Projection;
 use fyrox::{
     core::{
         algebra::{Vector2, Vector3},
         pool::Handle,
     },
-    gui::message::{KeyCode, UiMessage},
+    gui::message::{KeyCode, KeyboardModifiers, UiMessage},
     scene::{graph::Graph, node::Node},
 };
 use std::any::Any;
 
+pub mod collider;
 pub mod gizmo;
 pub mod move_mode;
 pub mod navmesh;
+pub mod pivot;
 pub mod plane;
 pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
 pub mod terrain;
+pub mod transform_locks;
+pub mod vertex_paint;
+pub mod waypoint;
 
 pub trait BaseInteractionMode {
     fn as_any(&self) -> &dyn Any;
@@ -85,6 +96,7 @@ pub trait InteractionMode: BaseInteractionMode {
         _key: KeyCode,
         _editor_scene: &mut EditorScene,
         _engine: &mut GameEngine,
+        _key_bindings: &KeyBindings,
     ) -> bool {
         false
     }
@@ -96,6 +108,7 @@ pub trait InteractionMode: BaseInteractionMode {
         _key: KeyCode,
         _editor_scene: &mut EditorScene,
         _engine: &mut GameEngine,
+        _key_bindings: &KeyBindings,
     ) -> bool {
         false
     }
@@ -133,6 +146,66 @@ fn distance_scale_factor(fov: f32) -> f32 {
     fov.tan() * 0.1
 }
 
+/// Combines picked nodes with current selection the same way as the world viewer does: Shift adds
+/// the nodes to the selection, Ctrl toggles each of them, otherwise the nodes replace the selection.
+pub fn combine_selection(
+    current: &Selection,
+    picked: &[Handle<Node>],
+    modifiers: KeyboardModifiers,
+) -> Selection {
+    let mut selection = match current {
+        Selection::Graph(selection) if modifiers.shift || modifiers.control => selection.clone(),
+        _ => GraphSelection::default(),
+    };
+
+    for &node in picked {
+        if modifiers.control || !selection.contains(node) {
+            selection.insert_or_exclude(node);
+        }
+    }
+
+    Selection::Graph(selection)
+}
+
+/// Picks a node under the cursor and makes a new selection with it. The node is combined with
+/// current selection depending on pressed modifiers, see [`combine_selection`]. Repeated picks at
+/// the same position cycle through all the nodes under the cursor, so occluded nodes can be
+/// selected too. If a handle property editor waits for a node to be picked, the node is assigned
+/// to it and current selection is returned as is.
+pub fn make_pick_selection(
+    editor_scene: &mut EditorScene,
+    engine: &GameEngine,
+    mouse_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    settings: &Settings,
+) -> Selection {
+    let picked = editor_scene
+        .camera_controller
+        .pick(PickingOptions {
+            cursor_pos: mouse_pos,
+            graph: &engine.scenes[editor_scene.scene].graph,
+            editor_objects_root: editor_scene.editor_objects_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |_, _| true,
+            ignore_back_faces: settings.selection.ignore_back_faces,
+            use_picking_loop: true,
+            only_meshes: false,
+        })
+        .map(|result| result.node);
+
+    if editor_scene.try_pick_node_handle(picked.unwrap_or_default(), &engine.user_interface) {
+        return editor_scene.selection.clone();
+    }
+
+    // A click on empty space with a modifier keeps current selection as is.
+    combine_selection(
+        &editor_scene.selection,
+        &picked.into_iter().collect::<Vec<_>>(),
+        engine.user_interface.keyboard_modifiers(),
+    )
+}
+
 /// Helper enum to be able to access interaction modes in array directly.
 #[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
 #[repr(usize)]
@@ -143,4 +216,7 @@ pub enum InteractionModeKind {
     Rotate = 3,
     Navmesh = 4,
     Terrain = 5,
+    Collider = 6,
+    VertexPaint = 7,
+    Waypoint = 8,
 }
@@ -1,27 +1,37 @@
 use crate::{
     camera::{CameraController, PickingOptions},
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, plane::PlaneKind,
-        InteractionMode,
+        calculate_gizmo_distance_scaling, gizmo::move_gizmo::MoveGizmo, make_pick_selection,
+        plane::PlaneKind, transform_locks::TransformLocks, InteractionMode,
     },
+    make_color_material,
+    repeat::RepeatableAction,
     scene::{
         commands::{graph::MoveNodeCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
         EditorScene, Selection,
     },
-    settings::Settings,
+    settings::{keys::KeyBindings, Settings},
     world::graph::selection::GraphSelection,
     GameEngine, Message,
 };
 use fyrox::{
     core::{
         algebra::{Matrix4, Point3, Vector2, Vector3},
+        color::Color,
         math::plane::Plane,
         pool::Handle,
     },
     fxhash::FxHashSet,
+    gui::message::KeyCode,
     scene::{
+        base::BaseBuilder,
         camera::{Camera, Projection},
         graph::Graph,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder, RenderPath,
+        },
         node::Node,
         Scene,
     },
@@ -44,6 +54,39 @@ struct MoveContext {
     gizmo_local_transform: Matrix4<f32>,
 }
 
+impl Entry {
+    /// Returns offset of the node in world space from its initial position.
+    fn world_offset(&self, local_position: Vector3<f32>) -> Vector3<f32> {
+        self.initial_parent_inv_global_transform
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity)
+            .transform_vector(&(local_position - self.initial_local_position))
+    }
+
+    /// Snaps new local position to a grid in the view plane of an axis-aligned orthographic
+    /// view. Snapping is done in world space, so the grid is aligned with the view plane
+    /// regardless of transform of the parent.
+    fn try_snap_to_view_plane_grid(
+        &self,
+        local_position: Vector3<f32>,
+        settings: &Settings,
+        view_axis: usize,
+    ) -> Option<Vector3<f32>> {
+        let parent_global_transform = self.initial_parent_inv_global_transform.try_inverse()?;
+        let world_position = parent_global_transform
+            .transform_point(&Point3::from(local_position))
+            .coords;
+        let snapped = settings
+            .move_mode_settings
+            .try_snap_to_view_plane_grid(world_position, view_axis)?;
+        Some(
+            self.initial_parent_inv_global_transform
+                .transform_point(&Point3::from(snapped))
+                .coords,
+        )
+    }
+}
+
 impl MoveContext {
     pub fn from_filler<F>(
         scene: &Scene,
@@ -179,6 +222,8 @@ impl MoveContext {
             .flat_map(|node| graph.traverse_handle_iter(node))
             .collect::<FxHashSet<Handle<Node>>>();
 
+        let view_axis = editor_scene.camera_controller.orthographic_view_axis(graph);
+
         let new_position = if let Some(result) =
             editor_scene.camera_controller.pick(PickingOptions {
                 cursor_pos: mouse_position,
@@ -194,11 +239,15 @@ impl MoveContext {
             }) {
             Some(result.position)
         } else {
-            // In case of empty space, check intersection with oXZ plane (3D) or oXY (2D).
+            // In case of empty space, check intersection with oXZ plane (3D), the view plane of an
+            // axis-aligned orthographic view or oXY (2D).
             if let Some(camera) = graph[editor_scene.camera_controller.camera].cast::<Camera>() {
-                let normal = match camera.projection() {
-                    Projection::Perspective(_) => Vector3::new(0.0, 1.0, 0.0),
-                    Projection::Orthographic(_) => Vector3::new(0.0, 0.0, 1.0),
+                let normal = match (camera.projection(), view_axis) {
+                    (Projection::Perspective(_), _) => Vector3::new(0.0, 1.0, 0.0),
+                    (Projection::Orthographic(_), Some(axis)) => {
+                        Vector3::from_fn(|i, _| if i == axis { 1.0 } else { 0.0 })
+                    }
+                    (Projection::Orthographic(_), None) => Vector3::new(0.0, 0.0, 1.0),
                 };
 
                 let plane =
@@ -217,7 +266,12 @@ impl MoveContext {
                 let n2 = entry
                     .initial_parent_inv_global_transform
                     .transform_point(&(Point3::from(new_position)));
-                entry.new_local_position = Vector3::new(n2.x, n2.y, n2.z);
+                let new_local_position = Vector3::new(n2.x, n2.y, n2.z);
+                entry.new_local_position = view_axis
+                    .and_then(|axis| {
+                        entry.try_snap_to_view_plane_grid(new_local_position, settings, axis)
+                    })
+                    .unwrap_or(new_local_position);
             }
         }
     }
@@ -240,24 +294,110 @@ impl MoveContext {
             )
             .map(|p| self.plane_kind.project_point(p))
         {
+            // Orthographic views snap to a grid in the view plane, all other views snap each
+            // local axis.
+            let view_axis = camera_controller.orthographic_view_axis(graph);
+
             for entry in self.objects.iter_mut() {
-                entry.new_local_position = settings.move_mode_settings.try_snap_vector_to_grid(
-                    entry.initial_local_position
-                        + entry.initial_parent_inv_global_transform.transform_vector(
-                            &self.gizmo_local_transform.transform_vector(
-                                &(picked_position_gizmo_space + entry.initial_offset_gizmo_space),
-                            ),
+                let new_local_position = entry.initial_local_position
+                    + entry.initial_parent_inv_global_transform.transform_vector(
+                        &self.gizmo_local_transform.transform_vector(
+                            &(picked_position_gizmo_space + entry.initial_offset_gizmo_space),
                         ),
-                );
+                    );
+                entry.new_local_position = view_axis
+                    .and_then(|axis| {
+                        entry.try_snap_to_view_plane_grid(new_local_position, settings, axis)
+                    })
+                    .unwrap_or_else(|| {
+                        settings
+                            .move_mode_settings
+                            .try_snap_vector_to_grid(new_local_position)
+                    });
+            }
+        }
+    }
+}
+
+/// Creates a command that moves selected nodes (selected descendants of other selected nodes are
+/// ignored) by the given offset in world space, transform locks are respected.
+pub fn make_move_selection_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    offset: Vector3<f32>,
+    locks: &TransformLocks,
+) -> SceneCommand {
+    let commands = selection
+        .root_nodes(graph)
+        .into_iter()
+        .map(|node_handle| {
+            let node = &graph[node_handle];
+            let parent_inv_transform = graph
+                .try_get(node.parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_else(Matrix4::identity);
+            let position = **node.local_transform().position();
+            SceneCommand::new(MoveNodeCommand::new(
+                node_handle,
+                position,
+                locks.lock_position(
+                    position,
+                    position + parent_inv_transform.transform_vector(&offset),
+                ),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    SceneCommand::new(CommandGroup::from(commands))
+}
+
+/// Returns a point of the node that is closest to given point (in world space). Candidates are
+/// the pivot of the node and vertices of its surfaces (if it is a mesh).
+fn closest_snap_point(node: &Node, point: Vector3<f32>) -> Vector3<f32> {
+    let mut closest = node.global_position();
+    let mut closest_distance = closest.metric_distance(&point);
+
+    if let Some(mesh) = node.cast::<Mesh>() {
+        let transform = mesh.global_transform();
+
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.lock();
+
+            for vertex in data.vertex_buffer.iter() {
+                if let Ok(position) = vertex.read_3_f32(VertexAttributeUsage::Position) {
+                    let position = transform.transform_point(&Point3::from(position)).coords;
+                    let distance = position.metric_distance(&point);
+                    if distance < closest_distance {
+                        closest_distance = distance;
+                        closest = position;
+                    }
+                }
             }
         }
     }
+
+    closest
+}
+
+/// State of "snap point to point" action. The action starts when the snap key is pressed, first
+/// click picks a point of selected objects, second click picks a point of any other object and
+/// moves the selection so the points coincide.
+#[derive(Default)]
+struct PointSnap {
+    source: Option<Vector3<f32>>,
 }
 
 pub struct MoveInteractionMode {
     move_context: Option<MoveContext>,
     move_gizmo: MoveGizmo,
     message_sender: Sender<Message>,
+    point_snap: Option<PointSnap>,
+    /// A marker that shows the picked source point of point snapping.
+    snap_marker: Handle<Node>,
+    /// Mouse button was pressed when point snapping was active, so its release must not change
+    /// the selection.
+    snap_click: bool,
 }
 
 impl MoveInteractionMode {
@@ -266,10 +406,119 @@ impl MoveInteractionMode {
         engine: &mut GameEngine,
         message_sender: Sender<Message>,
     ) -> Self {
+        let move_gizmo = MoveGizmo::new(editor_scene, engine);
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let snap_marker = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_cast_shadows(false)
+                .with_name("SnapMarker")
+                .with_visibility(false),
+        )
+        .with_render_path(RenderPath::Forward)
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_sphere(8, 8, 0.05, &Matrix4::identity()),
+        ))
+        .with_material(make_color_material(Color::opaque(255, 0, 255)))
+        .build()])
+        .build(graph);
+        graph.link_nodes(snap_marker, editor_scene.editor_objects_root);
+
         Self {
             move_context: None,
-            move_gizmo: MoveGizmo::new(editor_scene, engine),
+            move_gizmo,
             message_sender,
+            point_snap: None,
+            snap_marker,
+            snap_click: false,
+        }
+    }
+
+    fn stop_point_snap(&mut self, graph: &mut Graph) {
+        self.point_snap = None;
+        graph[self.snap_marker].set_visibility(false);
+    }
+
+    fn pick_snap_point(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let selection = match &editor_scene.selection {
+            Selection::Graph(selection) if !selection.is_empty() => selection,
+            _ => return,
+        };
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        let moved_nodes = selection
+            .nodes()
+            .iter()
+            .flat_map(|&node| graph.traverse_handle_iter(node))
+            .collect::<FxHashSet<Handle<Node>>>();
+
+        let source = self.point_snap.as_ref().and_then(|snap| snap.source);
+
+        // Source point must belong to the selected objects, the destination point - to any
+        // other object.
+        let picked = editor_scene
+            .camera_controller
+            .pick(PickingOptions {
+                cursor_pos: mouse_pos,
+                graph,
+                editor_objects_root: editor_scene.editor_objects_root,
+                screen_size: frame_size,
+                editor_only: false,
+                filter: |handle, _| moved_nodes.contains(&handle) == source.is_none(),
+                ignore_back_faces: settings.selection.ignore_back_faces,
+                use_picking_loop: false,
+                only_meshes: false,
+            })
+            .map(|result| closest_snap_point(&graph[result.node], result.position));
+
+        let point = match picked {
+            Some(point) => point,
+            None => return,
+        };
+
+        match source {
+            None => {
+                if let Some(snap) = self.point_snap.as_mut() {
+                    snap.source = Some(point);
+                }
+                let marker = &mut graph[self.snap_marker];
+                marker.local_transform_mut().set_position(point);
+                marker.set_visibility(true);
+            }
+            Some(source) => {
+                let offset = point - source;
+                let command = make_move_selection_command(
+                    selection,
+                    graph,
+                    offset,
+                    &editor_scene.transform_locks,
+                );
+
+                self.message_sender
+                    .send(Message::DoRepeatableSceneCommand {
+                        command,
+                        action: RepeatableAction::after_move(
+                            editor_scene.last_action.as_ref(),
+                            offset,
+                        ),
+                    })
+                    .unwrap();
+
+                // Keep the action active, so the next pair of points can be picked while the key
+                // is held.
+                if let Some(snap) = self.point_snap.as_mut() {
+                    snap.source = None;
+                }
+                graph[self.snap_marker].set_visibility(false);
+            }
         }
     }
 }
@@ -283,6 +532,12 @@ impl InteractionMode for MoveInteractionMode {
         frame_size: Vector2<f32>,
         settings: &Settings,
     ) {
+        if self.point_snap.is_some() {
+            self.snap_click = true;
+            self.pick_snap_point(editor_scene, engine, mouse_pos, frame_size, settings);
+            return;
+        }
+
         let scene = &mut engine.scenes[editor_scene.scene];
         let graph = &mut scene.graph;
 
@@ -298,7 +553,7 @@ impl InteractionMode for MoveInteractionMode {
                 handle != camera && handle != camera_pivot && handle != self.move_gizmo.origin
             },
             ignore_back_faces: settings.selection.ignore_back_faces,
-            use_picking_loop: true,
+            use_picking_loop: false,
             only_meshes: false,
         }) {
             if let Some(plane_kind) = self.move_gizmo.handle_pick(result.node, graph) {
@@ -325,6 +580,10 @@ impl InteractionMode for MoveInteractionMode {
         frame_size: Vector2<f32>,
         settings: &Settings,
     ) {
+        if std::mem::take(&mut self.snap_click) {
+            return;
+        }
+
         let scene = &mut engine.scenes[editor_scene.scene];
 
         self.move_gizmo.reset_state(&mut scene.graph);
@@ -356,38 +615,30 @@ impl InteractionMode for MoveInteractionMode {
                         .collect::<Vec<_>>(),
                 );
 
+                // All nodes are moved by the same offset in world space, so any of them can be used
+                // to get it.
+                let offset = move_context
+                    .objects
+                    .first()
+                    .map(|entry| {
+                        entry.world_offset(**scene.graph[entry.node].local_transform().position())
+                    })
+                    .unwrap_or_default();
+
                 // Commit changes.
                 self.message_sender
-                    .send(Message::DoSceneCommand(SceneCommand::new(commands)))
+                    .send(Message::DoRepeatableSceneCommand {
+                        command: SceneCommand::new(commands),
+                        action: RepeatableAction::after_move(
+                            editor_scene.last_action.as_ref(),
+                            offset,
+                        ),
+                    })
                     .unwrap();
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph: &scene.graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection =
+                make_pick_selection(editor_scene, engine, mouse_pos, frame_size, settings);
 
             if new_selection != editor_scene.selection {
                 self.message_sender
@@ -416,10 +667,11 @@ impl InteractionMode for MoveInteractionMode {
 
             move_context.update(graph, editor_scene, settings, mouse_position, frame_size);
 
+            let locks = editor_scene.transform_locks;
             for entry in move_context.objects.iter() {
-                scene.graph[entry.node]
-                    .local_transform_mut()
-                    .set_position(entry.new_local_position);
+                scene.graph[entry.node].local_transform_mut().set_position(
+                    locks.lock_position(entry.initial_local_position, entry.new_local_position),
+                );
             }
         }
     }
@@ -436,15 +688,66 @@ impl InteractionMode for MoveInteractionMode {
         if !editor_scene.selection.is_empty() {
             let scale = calculate_gizmo_distance_scaling(graph, camera, self.move_gizmo.origin);
             self.move_gizmo.set_visible(graph, true);
-            self.move_gizmo
-                .sync_transform(scene, &editor_scene.selection, scale);
+            self.move_gizmo.sync_transform(
+                scene,
+                &editor_scene.selection,
+                scale,
+                editor_scene.pivot_mode,
+                editor_scene.cursor,
+            );
         } else {
             self.move_gizmo.set_visible(graph, false);
         }
+
+        let graph = &mut scene.graph;
+        if graph[self.snap_marker].visibility() {
+            let scale = calculate_gizmo_distance_scaling(graph, camera, self.snap_marker);
+            graph[self.snap_marker]
+                .local_transform_mut()
+                .set_scale(scale);
+        }
     }
 
     fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
         let graph = &mut engine.scenes[editor_scene.scene].graph;
         self.move_gizmo.set_visible(graph, false);
+        self.stop_point_snap(graph);
+        self.snap_click = false;
+    }
+
+    fn on_key_down(
+        &mut self,
+        key: KeyCode,
+        _editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        key_bindings: &KeyBindings,
+    ) -> bool {
+        // The key must not steal shortcuts with modifiers, for example Ctrl+V must still paste.
+        if key_bindings.snap_to_point == key
+            && engine.user_interface.keyboard_modifiers().is_none()
+            && self.move_context.is_none()
+        {
+            // Key repeat must not reset already picked source point.
+            self.point_snap.get_or_insert_with(Default::default);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn on_key_up(
+        &mut self,
+        key: KeyCode,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        key_bindings: &KeyBindings,
+    ) -> bool {
+        // Release is consumed only if snapping was started by the key press.
+        if key_bindings.snap_to_point == key && self.point_snap.is_some() {
+            self.stop_point_snap(&mut engine.scenes[editor_scene.scene].graph);
+            true
+        } else {
+            false
+        }
     }
 }
@@ -22,7 +22,7 @@ use crate::{
         EditorScene, Selection,
     },
     send_sync_message,
-    settings::Settings,
+    settings::{keys::KeyBindings, Settings},
     GameEngine, Message, Mode, MSG_SYNC_FLAG,
 };
 use fyrox::gui::UserInterface;
@@ -70,7 +70,7 @@ impl NavmeshPanel {
         let remove;
         let navmeshes;
         let connect;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
             .with_title(WindowTitle::text("Navmesh"))
             .with_content(
                 GridBuilder::new(
@@ -389,7 +389,7 @@ impl InteractionMode for EditNavmeshMode {
                         handle != camera && handle != camera_pivot && handle != gizmo_origin
                     },
                     ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
+                    use_picking_loop: false,
                     only_meshes: false,
                 })
                 .map(|r| r.node)
@@ -672,6 +672,7 @@ impl InteractionMode for EditNavmeshMode {
         key: KeyCode,
         editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
+        _key_bindings: &KeyBindings,
     ) -> bool {
         match key {
             KeyCode::Delete => {
@@ -0,0 +1,160 @@
+//! Pivot of rotation and scaling of selected nodes. With individual origins every node is rotated
+//! and scaled around its own origin, with other modes root nodes of the selection are also moved
+//! around a common point, so the selection is transformed as a whole. Like transform locks, the
+//! pivot mode is an editor state that is kept for the whole session.
+
+use crate::{
+    interaction::transform_locks::TransformLocks,
+    scene::commands::{graph::MoveNodeCommand, SceneCommand},
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        pool::Handle,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PivotMode {
+    IndividualOrigins,
+    MedianPoint,
+    ActiveNode,
+    /// The 3D cursor, or the world origin if the cursor is not placed.
+    Cursor,
+}
+
+impl Default for PivotMode {
+    fn default() -> Self {
+        Self::IndividualOrigins
+    }
+}
+
+impl PivotMode {
+    pub const ALL: [Self; 4] = [
+        Self::IndividualOrigins,
+        Self::MedianPoint,
+        Self::ActiveNode,
+        Self::Cursor,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::IndividualOrigins => "Individual Origins",
+            Self::MedianPoint => "Median Point",
+            Self::ActiveNode => "Active Node",
+            Self::Cursor => "3D Cursor",
+        }
+    }
+
+    /// Returns the next mode, the last one is followed by the first one.
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|m| *m == self)
+            .unwrap_or_default();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Returns position of the pivot in world space. Gizmos are placed at this position, with
+    /// individual origins they're placed at the median point of the selection.
+    pub fn position(
+        self,
+        selection: &GraphSelection,
+        graph: &Graph,
+        cursor: Option<Vector3<f32>>,
+    ) -> Option<Vector3<f32>> {
+        let (_, median) = selection.global_rotation_position(graph)?;
+        match self {
+            Self::IndividualOrigins | Self::MedianPoint => Some(median),
+            Self::ActiveNode => selection
+                .active()
+                .or_else(|| selection.nodes().first().cloned())
+                .map(|active| graph[active].global_position()),
+            Self::Cursor => Some(cursor.unwrap_or_default()),
+        }
+    }
+}
+
+struct PivotNode {
+    handle: Handle<Node>,
+    initial_local_position: Vector3<f32>,
+    initial_global_position: Vector3<f32>,
+}
+
+/// Moves root nodes of the selection around the pivot while the selection is rotated or scaled
+/// by a gizmo. Positions are always calculated from the initial ones, so errors do not accumulate
+/// during the interaction.
+pub struct PivotTransform {
+    pivot: Vector3<f32>,
+    nodes: Vec<PivotNode>,
+}
+
+impl PivotTransform {
+    /// Starts the transformation, returns `None` if nodes must stay in place (individual origins).
+    pub fn begin(
+        mode: PivotMode,
+        selection: &GraphSelection,
+        graph: &Graph,
+        cursor: Option<Vector3<f32>>,
+    ) -> Option<Self> {
+        if mode == PivotMode::IndividualOrigins {
+            return None;
+        }
+
+        Some(Self {
+            pivot: mode.position(selection, graph, cursor)?,
+            nodes: selection
+                .root_nodes(graph)
+                .into_iter()
+                .map(|handle| PivotNode {
+                    handle,
+                    initial_local_position: **graph[handle].local_transform().position(),
+                    initial_global_position: graph[handle].global_position(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Places root nodes at their initial offsets from the pivot transformed by the function.
+    /// Offsets are in world space.
+    pub fn apply<F>(&self, graph: &mut Graph, locks: &TransformLocks, transform: F)
+    where
+        F: Fn(Vector3<f32>) -> Vector3<f32>,
+    {
+        for node in self.nodes.iter() {
+            let new_global_position =
+                self.pivot + transform(node.initial_global_position - self.pivot);
+            let parent_inv_transform = graph
+                .try_get(graph[node.handle].parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_else(Matrix4::identity);
+            let new_local_position = parent_inv_transform
+                .transform_point(&Point3::from(new_global_position))
+                .coords;
+            graph[node.handle]
+                .local_transform_mut()
+                .set_position(locks.lock_position(node.initial_local_position, new_local_position));
+        }
+    }
+
+    /// Creates commands that move root nodes from their initial positions to the current ones.
+    pub fn make_commands(&self, graph: &Graph) -> Vec<SceneCommand> {
+        self.nodes
+            .iter()
+            .filter_map(|node| {
+                let position = **graph[node.handle].local_transform().position();
+                if position != node.initial_local_position {
+                    Some(SceneCommand::new(MoveNodeCommand::new(
+                        node.handle,
+                        node.initial_local_position,
+                        position,
+                    )))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
@@ -1,14 +1,14 @@
 use crate::camera::PickingOptions;
 use crate::{
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::rotate_gizmo::RotationGizmo, InteractionMode,
+        calculate_gizmo_distance_scaling, gizmo::rotate_gizmo::RotationGizmo, make_pick_selection,
+        pivot::PivotTransform, InteractionMode,
     },
     scene::{
         commands::{graph::RotateNodeCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
         EditorScene, Selection,
     },
     settings::Settings,
-    world::graph::selection::GraphSelection,
     GameEngine, Message,
 };
 use fyrox::core::math::round_to_step;
@@ -23,6 +23,10 @@ use std::sync::mpsc::Sender;
 
 pub struct RotateInteractionMode {
     initial_rotations: Vec<UnitQuaternion<f32>>,
+    /// Moves the selection around the common pivot, if there's one.
+    pivot_transform: Option<PivotTransform>,
+    /// Rotation of the selection around the pivot (in world space) since the interaction began.
+    pivot_rotation: UnitQuaternion<f32>,
     rotation_gizmo: RotationGizmo,
     interacting: bool,
     message_sender: Sender<Message>,
@@ -36,6 +40,8 @@ impl RotateInteractionMode {
     ) -> Self {
         Self {
             initial_rotations: Default::default(),
+            pivot_transform: None,
+            pivot_rotation: Default::default(),
             rotation_gizmo: RotationGizmo::new(editor_scene, engine),
             interacting: false,
             message_sender,
@@ -67,7 +73,7 @@ impl InteractionMode for RotateInteractionMode {
                 handle != camera && handle != camera_pivot && handle != self.rotation_gizmo.origin
             },
             ignore_back_faces: settings.selection.ignore_back_faces,
-            use_picking_loop: true,
+            use_picking_loop: false,
             only_meshes: false,
         }) {
             if self
@@ -78,6 +84,13 @@ impl InteractionMode for RotateInteractionMode {
                 if let Selection::Graph(selection) = &editor_scene.selection {
                     self.interacting = true;
                     self.initial_rotations = selection.local_rotations(graph);
+                    self.pivot_transform = PivotTransform::begin(
+                        editor_scene.pivot_mode,
+                        selection,
+                        graph,
+                        editor_scene.cursor,
+                    );
+                    self.pivot_rotation = Default::default();
                 }
             }
         }
@@ -100,7 +113,12 @@ impl InteractionMode for RotateInteractionMode {
                 if !selection.is_empty() {
                     self.interacting = false;
                     let current_rotation = selection.local_rotations(graph);
-                    if current_rotation != self.initial_rotations {
+                    let pivot_commands = self
+                        .pivot_transform
+                        .take()
+                        .map(|pivot_transform| pivot_transform.make_commands(graph))
+                        .unwrap_or_default();
+                    if current_rotation != self.initial_rotations || !pivot_commands.is_empty() {
                         let commands = CommandGroup::from(
                             selection
                                 .nodes()
@@ -113,6 +131,7 @@ impl InteractionMode for RotateInteractionMode {
                                         new_rotation,
                                     ))
                                 })
+                                .chain(pivot_commands)
                                 .collect::<Vec<SceneCommand>>(),
                         );
                         // Commit changes.
@@ -123,32 +142,8 @@ impl InteractionMode for RotateInteractionMode {
                 }
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection =
+                make_pick_selection(editor_scene, engine, mouse_pos, frame_size, settings);
 
             if new_selection != editor_scene.selection {
                 self.message_sender
@@ -201,7 +196,24 @@ impl InteractionMode for RotateInteractionMode {
                             settings.rotate_mode_settings.z_snap_step.to_radians(),
                         );
                     }
-                    transform.set_rotation(UnitQuaternion::from_euler_angles(roll, pitch, yaw));
+                    transform.set_rotation(editor_scene.transform_locks.lock_rotation(
+                        rotation,
+                        UnitQuaternion::from_euler_angles(roll, pitch, yaw),
+                    ));
+                }
+
+                if let Some(pivot_transform) = self.pivot_transform.as_ref() {
+                    let graph = &mut engine.scenes[editor_scene.scene].graph;
+                    // The delta is around an axis of the gizmo.
+                    let gizmo_rotation = graph.global_rotation(self.rotation_gizmo.origin);
+                    self.pivot_rotation = gizmo_rotation
+                        * rotation_delta
+                        * gizmo_rotation.inverse()
+                        * self.pivot_rotation;
+                    let pivot_rotation = self.pivot_rotation;
+                    pivot_transform.apply(graph, &editor_scene.transform_locks, |offset| {
+                        pivot_rotation * offset
+                    });
                 }
             }
         }
@@ -219,7 +231,13 @@ impl InteractionMode for RotateInteractionMode {
             if !editor_scene.selection.is_empty() {
                 let scale =
                     calculate_gizmo_distance_scaling(graph, camera, self.rotation_gizmo.origin);
-                self.rotation_gizmo.sync_transform(graph, selection, scale);
+                self.rotation_gizmo.sync_transform(
+                    graph,
+                    selection,
+                    scale,
+                    editor_scene.pivot_mode,
+                    editor_scene.cursor,
+                );
                 self.rotation_gizmo.set_visible(graph, true);
             } else {
                 self.rotation_gizmo.set_visible(graph, false);
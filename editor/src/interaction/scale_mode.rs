@@ -3,10 +3,14 @@ use crate::scene::commands::SceneCommand;
 use crate::world::graph::selection::GraphSelection;
 use crate::{
     interaction::{
-        calculate_gizmo_distance_scaling, gizmo::scale_gizmo::ScaleGizmo, InteractionMode,
+        calculate_gizmo_distance_scaling, gizmo::scale_gizmo::ScaleGizmo, make_pick_selection,
+        pivot::PivotTransform, InteractionMode,
     },
     scene::{
-        commands::{graph::ScaleNodeCommand, ChangeSelectionCommand, CommandGroup},
+        commands::{
+            graph::{ResizeTriggerVolumeCommand, ScaleNodeCommand},
+            ChangeSelectionCommand, CommandGroup,
+        },
         EditorScene, Selection,
     },
     settings::Settings,
@@ -14,15 +18,37 @@ use crate::{
 };
 use fyrox::{
     core::{
-        algebra::{Vector2, Vector3},
+        algebra::{UnitQuaternion, Vector2, Vector3},
         pool::Handle,
     },
-    scene::node::Node,
+    scene::{
+        graph::Graph,
+        node::Node,
+        trigger::{TriggerShape, TriggerVolume},
+    },
 };
 use std::sync::mpsc::Sender;
 
+/// Collects shapes of selected trigger volumes. Trigger volumes are resized by changing their
+/// shape instead of their scale.
+fn trigger_shapes(selection: &GraphSelection, graph: &Graph) -> Vec<Option<TriggerShape>> {
+    selection
+        .nodes()
+        .iter()
+        .map(|&node| graph[node].cast::<TriggerVolume>().map(|v| v.shape()))
+        .collect()
+}
+
 pub struct ScaleInteractionMode {
     initial_scales: Vec<Vector3<f32>>,
+    initial_shapes: Vec<Option<TriggerShape>>,
+    /// Moves the selection relative to the common pivot, if there's one.
+    pivot_transform: Option<PivotTransform>,
+    /// Scale of the selection relative to the pivot since the interaction began, it is along the
+    /// axes of the gizmo.
+    pivot_scale: Vector3<f32>,
+    /// Rotation of the gizmo at the beginning of the interaction.
+    pivot_rotation: UnitQuaternion<f32>,
     scale_gizmo: ScaleGizmo,
     interacting: bool,
     message_sender: Sender<Message>,
@@ -36,6 +62,10 @@ impl ScaleInteractionMode {
     ) -> Self {
         Self {
             initial_scales: Default::default(),
+            initial_shapes: Default::default(),
+            pivot_transform: None,
+            pivot_scale: Vector3::repeat(1.0),
+            pivot_rotation: Default::default(),
             scale_gizmo: ScaleGizmo::new(editor_scene, engine),
             interacting: false,
             message_sender,
@@ -66,7 +96,7 @@ impl InteractionMode for ScaleInteractionMode {
                 editor_only: true,
                 filter: |handle, _| handle != camera && handle != camera_pivot,
                 ignore_back_faces: settings.selection.ignore_back_faces,
-                use_picking_loop: true,
+                use_picking_loop: false,
                 only_meshes: false,
             }) {
                 if self
@@ -76,6 +106,15 @@ impl InteractionMode for ScaleInteractionMode {
                     let graph = &mut engine.scenes[editor_scene.scene].graph;
                     self.interacting = true;
                     self.initial_scales = selection.local_scales(graph);
+                    self.initial_shapes = trigger_shapes(selection, graph);
+                    self.pivot_transform = PivotTransform::begin(
+                        editor_scene.pivot_mode,
+                        selection,
+                        graph,
+                        editor_scene.cursor,
+                    );
+                    self.pivot_scale = Vector3::repeat(1.0);
+                    self.pivot_rotation = graph.global_rotation(self.scale_gizmo.origin);
                 }
             }
         }
@@ -98,18 +137,35 @@ impl InteractionMode for ScaleInteractionMode {
                 if !selection.is_empty() {
                     self.interacting = false;
                     let current_scales = selection.local_scales(graph);
-                    if current_scales != self.initial_scales {
+                    let current_shapes = trigger_shapes(selection, graph);
+                    let pivot_commands = self
+                        .pivot_transform
+                        .take()
+                        .map(|pivot_transform| pivot_transform.make_commands(graph))
+                        .unwrap_or_default();
+                    if current_scales != self.initial_scales
+                        || current_shapes != self.initial_shapes
+                        || !pivot_commands.is_empty()
+                    {
                         // Commit changes.
                         let commands = CommandGroup::from(
                             selection
                                 .nodes()
                                 .iter()
                                 .zip(self.initial_scales.iter().zip(current_scales.iter()))
-                                .map(|(&node, (&old_scale, &new_scale))| {
-                                    SceneCommand::new(ScaleNodeCommand::new(
-                                        node, old_scale, new_scale,
-                                    ))
+                                .zip(self.initial_shapes.iter().zip(current_shapes.iter()))
+                                .map(|((&node, (&old_scale, &new_scale)), shapes)| {
+                                    if let (Some(old_shape), Some(new_shape)) = shapes {
+                                        SceneCommand::new(ResizeTriggerVolumeCommand::new(
+                                            node, *old_shape, *new_shape,
+                                        ))
+                                    } else {
+                                        SceneCommand::new(ScaleNodeCommand::new(
+                                            node, old_scale, new_scale,
+                                        ))
+                                    }
                                 })
+                                .chain(pivot_commands)
                                 .collect::<Vec<_>>(),
                         );
                         self.message_sender
@@ -119,32 +175,8 @@ impl InteractionMode for ScaleInteractionMode {
                 }
             }
         } else {
-            let new_selection = editor_scene
-                .camera_controller
-                .pick(PickingOptions {
-                    cursor_pos: mouse_pos,
-                    graph,
-                    editor_objects_root: editor_scene.editor_objects_root,
-                    screen_size: frame_size,
-                    editor_only: false,
-                    filter: |_, _| true,
-                    ignore_back_faces: settings.selection.ignore_back_faces,
-                    use_picking_loop: true,
-                    only_meshes: false,
-                })
-                .map(|result| {
-                    if let (Selection::Graph(selection), true) = (
-                        &editor_scene.selection,
-                        engine.user_interface.keyboard_modifiers().control,
-                    ) {
-                        let mut selection = selection.clone();
-                        selection.insert_or_exclude(result.node);
-                        Selection::Graph(selection)
-                    } else {
-                        Selection::Graph(GraphSelection::single_or_empty(result.node))
-                    }
-                })
-                .unwrap_or_else(|| Selection::Graph(GraphSelection::default()));
+            let new_selection =
+                make_pick_selection(editor_scene, engine, mouse_pos, frame_size, settings);
 
             if new_selection != editor_scene.selection {
                 self.message_sender
@@ -177,15 +209,35 @@ impl InteractionMode for ScaleInteractionMode {
                     engine,
                     frame_size,
                 );
+                // Locked axes are not scaled at all.
+                let scale_delta = editor_scene
+                    .transform_locks
+                    .lock_scale(Vector3::default(), scale_delta);
                 for &node in selection.nodes().iter() {
-                    let transform =
-                        engine.scenes[editor_scene.scene].graph[node].local_transform_mut();
+                    let node = &mut engine.scenes[editor_scene.scene].graph[node];
+                    if let Some(volume) = node.cast_mut::<TriggerVolume>() {
+                        let shape = volume.shape().scaled(Vector3::repeat(1.0) + scale_delta);
+                        volume.set_shape(shape);
+                        continue;
+                    }
+                    let transform = node.local_transform_mut();
                     let initial_scale = transform.scale();
                     let sx = (initial_scale.x * (1.0 + scale_delta.x)).max(std::f32::EPSILON);
                     let sy = (initial_scale.y * (1.0 + scale_delta.y)).max(std::f32::EPSILON);
                     let sz = (initial_scale.z * (1.0 + scale_delta.z)).max(std::f32::EPSILON);
                     transform.set_scale(Vector3::new(sx, sy, sz));
                 }
+
+                if let Some(pivot_transform) = self.pivot_transform.as_ref() {
+                    self.pivot_scale
+                        .component_mul_assign(&(Vector3::repeat(1.0) + scale_delta));
+                    let (rotation, scale) = (self.pivot_rotation, self.pivot_scale);
+                    pivot_transform.apply(
+                        &mut engine.scenes[editor_scene.scene].graph,
+                        &editor_scene.transform_locks,
+                        |offset| rotation * (rotation.inverse() * offset).component_mul(&scale),
+                    );
+                }
             }
         }
     }
@@ -202,7 +254,13 @@ impl InteractionMode for ScaleInteractionMode {
             if !editor_scene.selection.is_empty() {
                 let scale =
                     calculate_gizmo_distance_scaling(graph, camera, self.scale_gizmo.origin);
-                self.scale_gizmo.sync_transform(graph, selection, scale);
+                self.scale_gizmo.sync_transform(
+                    graph,
+                    selection,
+                    scale,
+                    editor_scene.pivot_mode,
+                    editor_scene.cursor,
+                );
                 self.scale_gizmo.set_visible(graph, true);
             } else {
                 self.scale_gizmo.set_visible(graph, false);
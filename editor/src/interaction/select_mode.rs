@@ -1,8 +1,7 @@
 use crate::{
-    interaction::InteractionMode,
-    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
+    interaction::{combine_selection, make_pick_selection, InteractionMode},
+    scene::{commands::ChangeSelectionCommand, EditorScene},
     settings::Settings,
-    world::graph::selection::GraphSelection,
     GameEngine, Message,
 };
 use fyrox::{
@@ -12,6 +11,10 @@ use fyrox::{
 };
 use std::sync::mpsc::Sender;
 
+// Maximum distance (in pixels) between press and release positions of the mouse to treat them as
+// a click.
+const CLICK_DISTANCE: f32 = 3.0;
+
 pub struct SelectInteractionMode {
     preview: Handle<UiNode>,
     selection_frame: Handle<UiNode>,
@@ -73,10 +76,36 @@ impl InteractionMode for SelectInteractionMode {
         &mut self,
         editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
-        _mouse_pos: Vector2<f32>,
+        mouse_pos: Vector2<f32>,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
+        engine
+            .user_interface
+            .send_message(WidgetMessage::visibility(
+                self.selection_frame,
+                MessageDirection::ToWidget,
+                false,
+            ));
+
+        // A click without dragging selects a node under the cursor, repeated clicks cycle through
+        // all overlapping nodes.
+        if mouse_pos.metric_distance(&self.click_pos) < CLICK_DISTANCE {
+            let new_selection =
+                make_pick_selection(editor_scene, engine, mouse_pos, frame_size, settings);
+
+            if new_selection != editor_scene.selection {
+                self.message_sender
+                    .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                        new_selection,
+                        editor_scene.selection.clone(),
+                    )))
+                    .unwrap();
+            }
+
+            return;
+        }
+
         let scene = &engine.scenes[editor_scene.scene];
         let camera = scene.graph[editor_scene.camera_controller.camera].as_camera();
         let preview_screen_bounds = engine.user_interface.node(self.preview).screen_bounds();
@@ -87,7 +116,7 @@ impl InteractionMode for SelectInteractionMode {
         let relative_bounds = frame_screen_bounds.translate(-preview_screen_bounds.position);
         self.stack.clear();
         self.stack.push(scene.graph.get_root());
-        let mut graph_selection = GraphSelection::default();
+        let mut picked = Vec::new();
         while let Some(handle) = self.stack.pop() {
             let node = &scene.graph[handle];
             if handle == editor_scene.editor_objects_root {
@@ -105,7 +134,7 @@ impl InteractionMode for SelectInteractionMode {
                 .filter_map(|&p| camera.project(p + node.global_position(), frame_size))
             {
                 if relative_bounds.contains(screen_corner) {
-                    graph_selection.insert_or_exclude(handle);
+                    picked.push(handle);
                     break;
                 }
             }
@@ -113,7 +142,12 @@ impl InteractionMode for SelectInteractionMode {
             self.stack.extend_from_slice(node.children());
         }
 
-        let new_selection = Selection::Graph(graph_selection);
+        // Frame selection obeys the same modifiers as a click: Shift adds, Ctrl toggles.
+        let new_selection = combine_selection(
+            &editor_scene.selection,
+            &picked,
+            engine.user_interface.keyboard_modifiers(),
+        );
 
         if new_selection != editor_scene.selection {
             self.message_sender
@@ -123,13 +157,6 @@ impl InteractionMode for SelectInteractionMode {
                 )))
                 .unwrap();
         }
-        engine
-            .user_interface
-            .send_message(WidgetMessage::visibility(
-                self.selection_frame,
-                MessageDirection::ToWidget,
-                false,
-            ));
     }
 
     fn on_mouse_move(
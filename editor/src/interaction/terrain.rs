@@ -218,10 +218,16 @@ impl InteractionMode for TerrainInteractionMode {
         editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
         if let Selection::Graph(selection) = &editor_scene.selection {
             if selection.is_single_selection() {
+                let pressure = if settings.brush.use_pen_pressure {
+                    engine.user_interface.pointer_pressure()
+                } else {
+                    1.0
+                };
+
                 let graph = &mut engine.scenes[editor_scene.scene].graph;
                 let handle = selection.nodes()[0];
 
@@ -246,11 +252,13 @@ impl InteractionMode for TerrainInteractionMode {
                                     if engine.user_interface.keyboard_modifiers().shift {
                                         *amount *= -1.0;
                                     }
+                                    *amount *= pressure;
                                 }
                                 BrushMode::DrawOnMask { alpha, .. } => {
                                     if engine.user_interface.keyboard_modifiers().shift {
                                         *alpha = -1.0;
                                     }
+                                    *alpha *= pressure;
                                 }
                             }
 
@@ -0,0 +1,85 @@
+//! Per-axis locks of local transform. Locked axes are kept intact when a node is moved, rotated or
+//! scaled by a gizmo or in the inspector. Locks are an editor state, they're kept for the whole
+//! session and are not saved anywhere.
+
+use fyrox::core::algebra::{UnitQuaternion, Vector3};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransformLockKind {
+    Position,
+    Rotation,
+    Scale,
+}
+
+impl TransformLockKind {
+    pub const ALL: [Self; 3] = [Self::Position, Self::Rotation, Self::Scale];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Position => "Position",
+            Self::Rotation => "Rotation",
+            Self::Scale => "Scale",
+        }
+    }
+}
+
+/// Lock flags for X, Y and Z axes of each part of local transform. Rotation axes are axes of
+/// Euler angles.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct TransformLocks {
+    pub position: [bool; 3],
+    pub rotation: [bool; 3],
+    pub scale: [bool; 3],
+}
+
+fn lock_vector(locks: &[bool; 3], old: Vector3<f32>, new: Vector3<f32>) -> Vector3<f32> {
+    Vector3::from_fn(|i, _| if locks[i] { old[i] } else { new[i] })
+}
+
+impl TransformLocks {
+    pub fn axes(&self, kind: TransformLockKind) -> &[bool; 3] {
+        match kind {
+            TransformLockKind::Position => &self.position,
+            TransformLockKind::Rotation => &self.rotation,
+            TransformLockKind::Scale => &self.scale,
+        }
+    }
+
+    pub fn axes_mut(&mut self, kind: TransformLockKind) -> &mut [bool; 3] {
+        match kind {
+            TransformLockKind::Position => &mut self.position,
+            TransformLockKind::Rotation => &mut self.rotation,
+            TransformLockKind::Scale => &mut self.scale,
+        }
+    }
+
+    /// Returns new position with locked axes taken from the old one.
+    pub fn lock_position(&self, old: Vector3<f32>, new: Vector3<f32>) -> Vector3<f32> {
+        lock_vector(&self.position, old, new)
+    }
+
+    /// Returns new scale with locked axes taken from the old one.
+    pub fn lock_scale(&self, old: Vector3<f32>, new: Vector3<f32>) -> Vector3<f32> {
+        lock_vector(&self.scale, old, new)
+    }
+
+    /// Returns new rotation with locked Euler angles taken from the old one.
+    pub fn lock_rotation(
+        &self,
+        old: UnitQuaternion<f32>,
+        new: UnitQuaternion<f32>,
+    ) -> UnitQuaternion<f32> {
+        if !self.rotation.contains(&true) {
+            return new;
+        }
+
+        let (old_x, old_y, old_z) = old.euler_angles();
+        let (new_x, new_y, new_z) = new.euler_angles();
+        let angles = lock_vector(
+            &self.rotation,
+            Vector3::new(old_x, old_y, old_z),
+            Vector3::new(new_x, new_y, new_z),
+        );
+        UnitQuaternion::from_euler_angles(angles.x, angles.y, angles.z)
+    }
+}
@@ -0,0 +1,514 @@
+//! Vertex paint mode. Allows painting colors of vertices of the selected mesh with a spherical
+//! brush. Surfaces without vertex colors get white colors on first stroke, surfaces with the
+//! standard material are switched to the standard vertex color material, so the painted colors
+//! are visible right away.
+
+use crate::{
+    camera::PickingOptions,
+    interaction::InteractionMode,
+    make_color_material,
+    scene::{commands::mesh::SetMeshSurfacesCommand, EditorScene, Selection},
+    set_mesh_diffuse_color,
+    settings::Settings,
+    GameEngine, Message, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3, Vector4},
+        color::Color,
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    gui::{
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
+            InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, UiNode,
+    },
+    material::{shader::Shader, CullMode, Material, SharedMaterial},
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        mesh::{
+            buffer::{
+                VertexAttributeDataType, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexReadTrait, VertexWriteTrait,
+            },
+            surface::{Surface, SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder, RenderPath,
+        },
+        node::Node,
+    },
+    utils::log::Log,
+};
+use std::{rc::Rc, sync::mpsc::Sender};
+
+/// Location of vertex colors in the standard vertex color shader.
+const COLOR_SHADER_LOCATION: u8 = 7;
+
+#[derive(Clone, Debug, Reflect)]
+pub struct VertexPaintBrush {
+    #[reflect(description = "Color to paint with. Hold Shift to paint with white color instead.")]
+    pub color: Color,
+    #[reflect(
+        description = "Radius of the brush in meters. Vertices inside the sphere of this radius \
+        around the cursor are painted.",
+        min_value = 0.001
+    )]
+    pub radius: f32,
+    #[reflect(
+        description = "How much color is applied on every move of the brush.",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    pub strength: f32,
+    #[reflect(
+        description = "Part of the radius at which strength fades out to zero. Zero makes the \
+        brush hard, one makes the strength fall off from the center to the edge.",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    pub falloff: f32,
+}
+
+impl Default for VertexPaintBrush {
+    fn default() -> Self {
+        Self {
+            color: Color::RED,
+            radius: 0.5,
+            strength: 0.2,
+            falloff: 0.5,
+        }
+    }
+}
+
+impl VertexPaintBrush {
+    /// Returns amount of color applied to a vertex at given distance from the center of the brush.
+    fn weight(&self, distance: f32) -> f32 {
+        if distance >= self.radius {
+            return 0.0;
+        }
+        let t = distance / self.radius;
+        let edge = 1.0 - self.falloff;
+        let fade = if t <= edge {
+            1.0
+        } else {
+            (1.0 - t) / self.falloff
+        };
+        self.strength * fade
+    }
+}
+
+/// Shaders are compared by paths, because built-in shaders of loaded scenes may be separate
+/// instances.
+fn is_same_shader(a: &Shader, b: &Shader) -> bool {
+    a == b || a.state().path() == b.state().path()
+}
+
+/// Creates a copy of the standard material with the same properties, but with the shader that
+/// uses vertex colors. Returns `None` if the material already uses vertex colors or if it has a
+/// custom shader.
+fn make_vertex_color_material(material: &SharedMaterial) -> Option<SharedMaterial> {
+    let material = material.lock();
+    let shader = material.shader();
+
+    let two_sides = if is_same_shader(shader, &Shader::standard()) {
+        false
+    } else if is_same_shader(shader, &Shader::standard_twosides()) {
+        true
+    } else {
+        return None;
+    };
+
+    let mut new_material = Material::standard_vertex_color();
+    for (name, value) in material.properties() {
+        Log::verify(new_material.set_property(name, value.clone()));
+    }
+    new_material.set_cull_mode(if two_sides {
+        CullMode::None
+    } else {
+        material.cull_mode()
+    });
+
+    Some(SharedMaterial::new(new_material))
+}
+
+/// Prepares a surface for painting. Surface data is always copied, this way the old surface is
+/// kept intact for undo and other meshes that share the data are not affected.
+pub fn make_paintable_surface(surface: &Surface) -> Surface {
+    let mut surface = surface.clone();
+
+    let data = surface.data().deep_clone();
+    {
+        let mut data = data.lock();
+        if !data
+            .vertex_buffer
+            .has_attribute(VertexAttributeUsage::Color)
+        {
+            Log::verify(data.vertex_buffer.modify().add_attribute(
+                VertexAttributeDescriptor {
+                    usage: VertexAttributeUsage::Color,
+                    data_type: VertexAttributeDataType::U8,
+                    size: 4,
+                    divisor: 0,
+                    shader_location: COLOR_SHADER_LOCATION,
+                },
+                Vector4::<u8>::repeat(255),
+            ));
+        }
+    }
+    surface.set_data(data);
+
+    if let Some(material) = make_vertex_color_material(&surface.material()) {
+        surface.set_material(material);
+    } else if !is_same_shader(
+        surface.material().lock().shader(),
+        &Shader::standard_vertex_color(),
+    ) {
+        Log::warn(
+            "A surface of the mesh uses a custom shader, painted vertex colors are visible only \
+            if the shader uses them.",
+        );
+    }
+
+    surface
+}
+
+fn paint(
+    surfaces: &[Surface],
+    transform: &Matrix4<f32>,
+    center: Vector3<f32>,
+    color: Color,
+    brush: &VertexPaintBrush,
+) {
+    let color = Vector4::new(color.r, color.g, color.b, color.a).cast::<f32>();
+
+    for surface in surfaces {
+        let data = surface.data();
+        let mut data = data.lock();
+        let mut vertex_buffer = data.vertex_buffer.modify();
+        for mut view in vertex_buffer.iter_mut() {
+            if let (Ok(position), Ok(old_color)) = (
+                view.read_3_f32(VertexAttributeUsage::Position),
+                view.read_4_u8(VertexAttributeUsage::Color),
+            ) {
+                let position = transform.transform_point(&Point3::from(position)).coords;
+                let weight = brush.weight(position.metric_distance(&center));
+                if weight > 0.0 {
+                    let old_color = old_color.cast::<f32>();
+                    let new_color = old_color.lerp(&color, weight);
+                    let _ = view.write_4_u8(
+                        VertexAttributeUsage::Color,
+                        new_color.map(|c| c.round().clamp(0.0, 255.0) as u8),
+                    );
+                }
+            }
+        }
+    }
+}
+
+struct Stroke {
+    mesh: Handle<Node>,
+    old_surfaces: Vec<Surface>,
+    painted: bool,
+}
+
+pub struct VertexPaintInteractionMode {
+    brush: VertexPaintBrush,
+    brush_gizmo: Handle<Node>,
+    panel: VertexPaintPanel,
+    stroke: Option<Stroke>,
+    message_sender: Sender<Message>,
+}
+
+impl VertexPaintInteractionMode {
+    pub fn new(
+        editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        message_sender: Sender<Message>,
+    ) -> Self {
+        let brush = VertexPaintBrush::default();
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let brush_gizmo = MeshBuilder::new(
+            BaseBuilder::new()
+                .with_cast_shadows(false)
+                .with_name("VertexPaintBrush")
+                .with_visibility(false),
+        )
+        .with_render_path(RenderPath::Forward)
+        .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+            SurfaceData::make_sphere(16, 16, 1.0, &Matrix4::identity()),
+        ))
+        .with_material(make_color_material(gizmo_color(&brush)))
+        .build()])
+        .build(graph);
+        graph.link_nodes(brush_gizmo, editor_scene.editor_objects_root);
+
+        let panel = VertexPaintPanel::new(&mut engine.user_interface.build_ctx(), &brush);
+
+        Self {
+            brush,
+            brush_gizmo,
+            panel,
+            stroke: None,
+            message_sender,
+        }
+    }
+
+    /// Moves the brush to the point of the selected mesh under the cursor and paints there if a
+    /// stroke is active.
+    fn apply_brush(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        let mesh_handle = match selected_mesh(&editor_scene.selection, graph) {
+            Some(mesh_handle) => mesh_handle,
+            None => {
+                graph[self.brush_gizmo].set_visibility(false);
+                return;
+            }
+        };
+
+        let result = editor_scene.camera_controller.pick(PickingOptions {
+            cursor_pos: mouse_position,
+            graph,
+            editor_objects_root: editor_scene.editor_objects_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |handle, _| handle == mesh_handle,
+            ignore_back_faces: settings.selection.ignore_back_faces,
+            use_picking_loop: false,
+            only_meshes: true,
+        });
+
+        let result = match result {
+            Some(result) => result,
+            None => {
+                graph[self.brush_gizmo].set_visibility(false);
+                return;
+            }
+        };
+
+        let gizmo = &mut graph[self.brush_gizmo];
+        gizmo.set_visibility(true);
+        gizmo
+            .local_transform_mut()
+            .set_position(result.position)
+            .set_scale(Vector3::repeat(self.brush.radius));
+
+        let stroke = match self.stroke.as_mut() {
+            Some(stroke) if stroke.mesh == mesh_handle => stroke,
+            _ => return,
+        };
+
+        let ui = &engine.user_interface;
+        let mut brush = self.brush.clone();
+        if settings.brush.use_pen_pressure {
+            brush.strength *= ui.pointer_pressure();
+        }
+        let color = if ui.keyboard_modifiers().shift {
+            Color::WHITE
+        } else {
+            brush.color
+        };
+
+        let mesh = &engine.scenes[editor_scene.scene].graph[mesh_handle];
+        if let Some(mesh) = mesh.cast::<Mesh>() {
+            paint(
+                mesh.surfaces(),
+                &mesh.global_transform(),
+                result.position,
+                color,
+                &brush,
+            );
+            stroke.painted = true;
+        }
+    }
+
+    fn end_stroke(&mut self, graph: &mut Graph) {
+        if let Some(stroke) = self.stroke.take() {
+            if let Some(mesh) = graph[stroke.mesh].cast_mut::<Mesh>() {
+                // Put old surfaces back, the command will set painted ones again, this way the
+                // stroke can be undone. Clicks that missed the mesh leave it as is.
+                let new_surfaces = mesh.set_surfaces(stroke.old_surfaces);
+                if stroke.painted {
+                    self.message_sender
+                        .send(Message::do_scene_command(SetMeshSurfacesCommand::new(
+                            stroke.mesh,
+                            new_surfaces,
+                        )))
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
+
+fn selected_mesh(selection: &Selection, graph: &Graph) -> Option<Handle<Node>> {
+    if let Selection::Graph(selection) = selection {
+        if selection.is_single_selection() {
+            let handle = selection.nodes()[0];
+            if graph[handle].cast::<Mesh>().is_some() {
+                return Some(handle);
+            }
+        }
+    }
+    None
+}
+
+fn gizmo_color(brush: &VertexPaintBrush) -> Color {
+    Color::from_rgba(brush.color.r, brush.color.g, brush.color.b, 60)
+}
+
+impl InteractionMode for VertexPaintInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        if let Some(mesh_handle) = selected_mesh(&editor_scene.selection, graph) {
+            let mesh = graph[mesh_handle].as_mesh_mut();
+            let old_surfaces = mesh.surfaces().to_vec();
+            mesh.set_surfaces(old_surfaces.iter().map(make_paintable_surface).collect());
+
+            self.stroke = Some(Stroke {
+                mesh: mesh_handle,
+                old_surfaces,
+                painted: false,
+            });
+
+            self.apply_brush(editor_scene, engine, mouse_pos, frame_size, settings);
+        }
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+        self.end_stroke(&mut engine.scenes[editor_scene.scene].graph);
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        mouse_position: Vector2<f32>,
+        _camera: Handle<Node>,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        self.apply_brush(editor_scene, engine, mouse_position, frame_size, settings);
+    }
+
+    fn activate(&mut self, _editor_scene: &EditorScene, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.panel.window,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        self.end_stroke(graph);
+        graph[self.brush_gizmo].set_visibility(false);
+
+        engine.user_interface.send_message(WindowMessage::close(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+    ) {
+        if self.panel.handle_ui_message(message, &mut self.brush) {
+            let graph = &mut engine.scenes[editor_scene.scene].graph;
+            set_mesh_diffuse_color(
+                graph[self.brush_gizmo].as_mesh_mut(),
+                gizmo_color(&self.brush),
+            );
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
+
+struct VertexPaintPanel {
+    window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+}
+
+impl VertexPaintPanel {
+    fn new(ctx: &mut BuildContext, brush: &VertexPaintBrush) -> Self {
+        let context = InspectorContext::from_object(
+            brush,
+            ctx,
+            Rc::new(PropertyEditorDefinitionContainer::new()),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+        );
+
+        let inspector;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(150.0))
+            .can_close(false)
+            .with_content({
+                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                    .with_context(context)
+                    .build(ctx);
+                inspector
+            })
+            .open(false)
+            .with_title(WindowTitle::text("Vertex Paint"))
+            .build(ctx);
+
+        Self { window, inspector }
+    }
+
+    /// Returns `true` if the brush was changed.
+    fn handle_ui_message(&self, message: &UiMessage, brush: &mut VertexPaintBrush) -> bool {
+        if message.destination() == self.inspector
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+                Log::verify(
+                    PropertyAction::from_field_kind(&property_changed.value)
+                        .apply(&property_changed.path(), brush),
+                );
+                return true;
+            }
+        }
+        false
+    }
+}
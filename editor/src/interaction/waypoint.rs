@@ -0,0 +1,448 @@
+//! Waypoint mode. Allows placing waypoints of paths (for example patrol paths of bots) by clicking
+//! on surfaces in the viewport. A path is a regular node with the [`WAYPOINT_PATH_TAG`] tag, its
+//! children are waypoints and the order of the children is the order of the waypoints. This way
+//! paths are saved with the scene and can be found by the tag in game code.
+
+use crate::{
+    camera::PickingOptions,
+    interaction::InteractionMode,
+    scene::{
+        commands::{
+            graph::{AddModelCommand, LinkNodesCommand, SetChildrenOrderCommand},
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    settings::Settings,
+    world::graph::selection::GraphSelection,
+    GameEngine, Message,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        color::Color,
+        math::plane::Plane,
+        pool::Handle,
+    },
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode,
+    },
+    scene::{
+        base::BaseBuilder,
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+    },
+};
+use std::sync::mpsc::Sender;
+
+/// Tag of nodes that are waypoint paths.
+pub const WAYPOINT_PATH_TAG: &str = "WaypointPath";
+
+const PATH_COLOR: Color = Color::opaque(0, 170, 255);
+const SELECTED_PATH_COLOR: Color = Color::opaque(255, 200, 0);
+const WAYPOINT_RADIUS: f32 = 0.1;
+
+pub fn is_waypoint_path(node: &Node) -> bool {
+    node.tag() == WAYPOINT_PATH_TAG
+}
+
+/// Returns the selected path and the selected waypoint of it, if a waypoint is selected.
+fn selected_path(
+    selection: &Selection,
+    graph: &Graph,
+) -> Option<(Handle<Node>, Option<Handle<Node>>)> {
+    if let Selection::Graph(selection) = selection {
+        let handle = selection.active()?;
+        let node = graph.try_get(handle)?;
+        if is_waypoint_path(node) {
+            return Some((handle, None));
+        }
+        let parent = node.parent();
+        if graph.try_get(parent).map_or(false, is_waypoint_path) {
+            return Some((parent, Some(handle)));
+        }
+    }
+    None
+}
+
+fn draw_arrow(
+    drawing_context: &mut SceneDrawingContext,
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+    color: Color,
+) {
+    drawing_context.add_line(Line { begin, end, color });
+
+    let length = begin.metric_distance(&end);
+    let direction = match (end - begin).try_normalize(f32::EPSILON) {
+        Some(direction) => direction,
+        None => return,
+    };
+    let side = direction
+        .cross(&Vector3::y())
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::x);
+
+    // The head is in the middle of the segment, this way it is not hidden by the waypoints.
+    let size = (length * 0.25).min(0.5);
+    let tip = (begin + end).scale(0.5) + direction.scale(size * 0.5);
+    let back = tip - direction.scale(size);
+    for wing in [back + side.scale(size * 0.5), back - side.scale(size * 0.5)] {
+        drawing_context.add_line(Line {
+            begin: tip,
+            end: wing,
+            color,
+        });
+    }
+}
+
+fn draw_path(
+    drawing_context: &mut SceneDrawingContext,
+    graph: &Graph,
+    path: Handle<Node>,
+    color: Color,
+) {
+    let points = graph[path]
+        .children()
+        .iter()
+        .map(|&waypoint| graph[waypoint].global_position())
+        .collect::<Vec<_>>();
+
+    for &point in points.iter() {
+        drawing_context.draw_sphere(point, 8, 8, WAYPOINT_RADIUS, color);
+    }
+
+    for segment in points.windows(2) {
+        draw_arrow(drawing_context, segment[0], segment[1], color);
+    }
+}
+
+pub struct WaypointInteractionMode {
+    panel: WaypointPanel,
+    message_sender: Sender<Message>,
+}
+
+impl WaypointInteractionMode {
+    pub fn new(
+        _editor_scene: &EditorScene,
+        engine: &mut GameEngine,
+        message_sender: Sender<Message>,
+    ) -> Self {
+        Self {
+            panel: WaypointPanel::new(&mut engine.user_interface.build_ctx()),
+            message_sender,
+        }
+    }
+
+    /// Adds a waypoint at the point of a surface under the cursor. The waypoint is inserted after
+    /// the selected waypoint or appended to the selected path, a new path is created if there's
+    /// no selected path.
+    fn place_waypoint(
+        &self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let selected = selected_path(&editor_scene.selection, graph);
+
+        let position = match editor_scene.camera_controller.pick(PickingOptions {
+            cursor_pos: mouse_pos,
+            graph,
+            editor_objects_root: editor_scene.editor_objects_root,
+            screen_size: frame_size,
+            editor_only: false,
+            filter: |_, _| true,
+            ignore_back_faces: settings.selection.ignore_back_faces,
+            use_picking_loop: false,
+            only_meshes: true,
+        }) {
+            Some(result) => result.position,
+            None => {
+                // Nothing under the cursor, use horizontal plane at the height of the path.
+                let height = selected
+                    .map(|(path, _)| graph[path].global_position().y)
+                    .unwrap_or_default();
+                let plane =
+                    Plane::from_normal_and_point(&Vector3::y(), &Vector3::new(0.0, height, 0.0))
+                        .unwrap();
+                match editor_scene.camera_controller.pick_on_plane(
+                    plane,
+                    graph,
+                    mouse_pos,
+                    frame_size,
+                    Matrix4::identity(),
+                ) {
+                    Some(position) => position,
+                    None => return,
+                }
+            }
+        };
+
+        let mut commands = Vec::new();
+
+        let waypoint = if let Some((path, selected_waypoint)) = selected {
+            let local_position = graph[path]
+                .global_transform()
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::from(position))
+                .coords;
+            let waypoint = PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_name("Waypoint")
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(local_position)
+                            .build(),
+                    ),
+            )
+            .build(graph);
+
+            let mut order = graph[path].children().to_vec();
+            let index = selected_waypoint
+                .and_then(|selected| order.iter().position(|&w| w == selected))
+                .map_or(order.len(), |index| index + 1);
+            order.insert(index, waypoint);
+
+            // Extract the waypoint from the scene, only commands allowed to modify the scene.
+            let sub_graph = graph.take_reserve_sub_graph(waypoint);
+            commands.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+            commands.push(SceneCommand::new(LinkNodesCommand::new(waypoint, path)));
+            commands.push(SceneCommand::new(SetChildrenOrderCommand::new(path, order)));
+
+            waypoint
+        } else {
+            let waypoint = PivotBuilder::new(BaseBuilder::new().with_name("Waypoint")).build(graph);
+            let path = PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_name("WaypointPath")
+                    .with_tag(WAYPOINT_PATH_TAG.to_owned())
+                    .with_children(&[waypoint])
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    ),
+            )
+            .build(graph);
+
+            let sub_graph = graph.take_reserve_sub_graph(path);
+            commands.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+
+            waypoint
+        };
+
+        // Select the new waypoint, so the next one is placed after it.
+        commands.push(SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::single_or_empty(waypoint)),
+            editor_scene.selection.clone(),
+        )));
+
+        self.message_sender
+            .send(Message::do_scene_command(CommandGroup::from(commands)))
+            .unwrap();
+    }
+
+    /// Changes order of waypoints of the selected path with the given function, the function
+    /// receives the waypoints and the index of the selected waypoint, if any.
+    fn reorder_waypoints<F>(&self, editor_scene: &EditorScene, engine: &GameEngine, reorder: F)
+    where
+        F: FnOnce(&mut Vec<Handle<Node>>, Option<usize>),
+    {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        if let Some((path, selected_waypoint)) = selected_path(&editor_scene.selection, graph) {
+            let old_order = graph[path].children().to_vec();
+            let mut order = old_order.clone();
+            let index = selected_waypoint.and_then(|w| order.iter().position(|&c| c == w));
+            reorder(&mut order, index);
+            if order != old_order {
+                self.message_sender
+                    .send(Message::do_scene_command(SetChildrenOrderCommand::new(
+                        path, order,
+                    )))
+                    .unwrap();
+            }
+        }
+    }
+}
+
+impl InteractionMode for WaypointInteractionMode {
+    fn on_left_mouse_button_down(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        self.place_waypoint(editor_scene, engine, mouse_pos, frame_size, settings);
+    }
+
+    fn on_left_mouse_button_up(
+        &mut self,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut GameEngine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn on_mouse_move(
+        &mut self,
+        _mouse_offset: Vector2<f32>,
+        _mouse_position: Vector2<f32>,
+        _camera: Handle<Node>,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut GameEngine,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        _camera: Handle<Node>,
+        engine: &mut GameEngine,
+        _settings: &Settings,
+    ) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+        let selected = selected_path(&editor_scene.selection, &scene.graph).map(|(path, _)| path);
+
+        for (handle, node) in scene.graph.pair_iter() {
+            if is_waypoint_path(node) {
+                let color = if Some(handle) == selected {
+                    SELECTED_PATH_COLOR
+                } else {
+                    PATH_COLOR
+                };
+                draw_path(&mut scene.drawing_context, &scene.graph, handle, color);
+            }
+        }
+    }
+
+    fn activate(&mut self, _editor_scene: &EditorScene, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.panel.window,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    fn deactivate(&mut self, _editor_scene: &EditorScene, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WindowMessage::close(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.panel.new_path {
+                // Next waypoint starts a new path when there's no selected path.
+                if selected_path(
+                    &editor_scene.selection,
+                    &engine.scenes[editor_scene.scene].graph,
+                )
+                .is_some()
+                {
+                    self.message_sender
+                        .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                            Selection::None,
+                            editor_scene.selection.clone(),
+                        )))
+                        .unwrap();
+                }
+            } else if message.destination() == self.panel.move_earlier {
+                self.reorder_waypoints(editor_scene, engine, |order, index| {
+                    if let Some(index) = index.filter(|&i| i > 0) {
+                        order.swap(index, index - 1);
+                    }
+                });
+            } else if message.destination() == self.panel.move_later {
+                self.reorder_waypoints(editor_scene, engine, |order, index| {
+                    if let Some(index) = index.filter(|&i| i + 1 < order.len()) {
+                        order.swap(index, index + 1);
+                    }
+                });
+            } else if message.destination() == self.panel.reverse {
+                self.reorder_waypoints(editor_scene, engine, |order, _| order.reverse());
+            }
+        }
+    }
+
+    fn on_drop(&mut self, engine: &mut GameEngine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.panel.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
+
+struct WaypointPanel {
+    window: Handle<UiNode>,
+    new_path: Handle<UiNode>,
+    move_earlier: Handle<UiNode>,
+    move_later: Handle<UiNode>,
+    reverse: Handle<UiNode>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_height(24.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl WaypointPanel {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let new_path = make_button(ctx, "New Path");
+        let move_earlier = make_button(ctx, "Move Waypoint Earlier");
+        let move_later = make_button(ctx, "Move Waypoint Later");
+        let reverse = make_button(ctx, "Reverse Path");
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(200.0).with_height(140.0))
+            .can_close(false)
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(new_path)
+                        .with_child(move_earlier)
+                        .with_child(move_later)
+                        .with_child(reverse),
+                )
+                .build(ctx),
+            )
+            .open(false)
+            .with_title(WindowTitle::text("Waypoints"))
+            .build(ctx);
+
+        Self {
+            window,
+            new_path,
+            move_earlier,
+            move_later,
+            reverse,
+        }
+    }
+}
@@ -18,21 +18,33 @@ mod build;
 mod camera;
 mod command;
 mod configurator;
+mod copy_properties;
+mod cubemap;
 mod curve_editor;
 mod gui;
+mod heightmap;
 mod inspector;
 mod interaction;
 mod light;
+mod localization;
 mod log;
 mod material;
 mod menu;
 mod overlay;
 mod particle;
+mod paste_transform;
 mod preview;
+mod profiler;
+mod repeat;
+mod scale;
 mod scene;
+mod scene_loading;
 mod scene_viewer;
 mod settings;
+mod shortcuts;
+mod teleport;
 mod utils;
+mod vertex_ao;
 mod world;
 
 use crate::{
@@ -43,55 +55,87 @@ use crate::{
     build::BuildWindow,
     command::{panel::CommandStackViewer, Command, CommandStack},
     configurator::Configurator,
+    cubemap::CubeMapWindow,
     curve_editor::CurveEditorWindow,
+    heightmap::HeightMapImportWindow,
     inspector::{editors::handle::HandlePropertyEditorMessage, Inspector},
     interaction::{
+        collider::ColliderInteractionMode,
+        make_pick_selection,
         move_mode::MoveInteractionMode,
         navmesh::{EditNavmeshMode, NavmeshPanel},
+        pivot::PivotMode,
         rotate_mode::RotateInteractionMode,
         scale_mode::ScaleInteractionMode,
         select_mode::SelectInteractionMode,
         terrain::TerrainInteractionMode,
+        vertex_paint::VertexPaintInteractionMode,
+        waypoint::WaypointInteractionMode,
         InteractionMode, InteractionModeKind,
     },
     light::LightPanel,
+    localization::{load_language, tr},
     log::LogPanel,
     material::MaterialEditor,
     menu::{Menu, MenuContext, Panels},
     overlay::OverlayRenderPass,
     particle::ParticleSystemPreviewControlPanel,
+    paste_transform::PasteTransformWindow,
+    profiler::ProfilerPanel,
+    repeat::{repeat_last_action, RepeatableAction},
+    scale::NormalizeScaleWindow,
     scene::{
         commands::{
-            graph::AddModelCommand, make_delete_selection_command, mesh::SetMeshTextureCommand,
-            ChangeSelectionCommand, CommandGroup, PasteCommand, SceneCommand, SceneContext,
+            graph::{
+                make_clear_parent_command, make_parent_to_last_selected_command, AddModelCommand,
+                DeleteSubGraphCommand, LinkNodesCommand,
+            },
+            make_delete_selection_command,
+            mesh::SetMeshTextureCommand,
+            ChangeSelectionCommand, CommandGroup, DuplicationMode, PasteCommand, SceneCommand,
+            SceneContext,
         },
         is_scene_needs_to_be_saved,
+        reimport::reimport_models,
         settings::SceneSettingsWindow,
-        EditorScene, Selection,
+        sidecar::SceneSidecar,
+        EditorScene, Selection, ViewportRenderMode,
     },
+    scene_loading::SceneLoadingWindow,
     scene_viewer::SceneViewer,
     settings::{camera::SceneCameraSettings, Settings},
-    utils::path_fixer::PathFixer,
+    shortcuts::ShortcutsWindow,
+    teleport::TeleportCameraWindow,
+    utils::{path_fixer::PathFixer, script_relinker::ScriptRelinker},
+    vertex_ao::VertexAoBakeWindow,
     world::{graph::selection::GraphSelection, WorldViewer},
 };
 use fyrox::{
     core::{
         algebra::{Matrix3, Vector2},
         color::Color,
+        curve::Curve,
         futures::executor::block_on,
+        make_relative_path,
         pool::{ErasedHandle, Handle},
         scope_profile,
         sstorage::ImmutableString,
         visitor::Visitor,
     },
     dpi::LogicalSize,
-    engine::{resource_manager::ResourceManager, Engine, EngineInitParams, SerializationContext},
+    engine::{
+        executor::ExecutorCommand, resource_manager::ResourceManager, Engine, EngineInitParams,
+        SerializationContext,
+    },
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     fxhash::FxHashMap,
     gui::{
         brush::Brush,
-        dock::{DockingManagerBuilder, TileBuilder, TileContent},
+        dock::{
+            DockingManager, DockingManagerBuilder, DockingManagerLayoutDescriptor,
+            DockingManagerMessage, TileBuilder, TileContent, TileContentDescriptor,
+        },
         draw,
         dropdown_list::DropdownListBuilder,
         file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
@@ -107,13 +151,16 @@ use fyrox::{
     },
     material::{shader::Shader, Material, PropertyValue, SharedMaterial},
     plugin::PluginConstructor,
-    resource::texture::{CompressionOptions, Texture, TextureKind},
+    resource::{
+        model::Model,
+        texture::{CompressionOptions, Texture, TextureKind},
+    },
     scene::{
         camera::{Camera, Projection},
         mesh::Mesh,
         node::Node,
         particle_system::ParticleSystem,
-        Scene, SceneLoader,
+        Scene,
     },
     utils::{
         into_gui_texture,
@@ -125,7 +172,7 @@ use fyrox::{
 use std::{
     any::TypeId,
     cell::RefCell,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Write},
     path::{Path, PathBuf},
     process::Stdio,
     rc::Rc,
@@ -137,7 +184,6 @@ use std::{
     time::{Duration, Instant},
 };
 
-pub const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
 pub const MSG_SYNC_FLAG: u64 = 1;
 
 pub fn send_sync_message(ui: &UserInterface, mut msg: UiMessage) {
@@ -207,9 +253,19 @@ pub enum BuildProfile {
 #[derive(Debug)]
 pub enum Message {
     DoSceneCommand(SceneCommand),
+    /// Does the command and records the action it was made by as the last repeatable action, see
+    /// [`RepeatableAction`].
+    DoRepeatableSceneCommand {
+        command: SceneCommand,
+        action: RepeatableAction,
+    },
     UndoSceneCommand,
     RedoSceneCommand,
     ClearSceneCommandStack,
+    /// Starts a named transaction on the scene command stack, every scene command until the
+    /// matching [`Message::EndTransaction`] will be merged in a single undo step.
+    BeginTransaction(String),
+    EndTransaction,
     SelectionChanged {
         old_selection: Selection,
     },
@@ -228,6 +284,10 @@ pub enum Message {
     OpenAnimationEditor,
     OpenAbsmEditor,
     OpenMaterialEditor(SharedMaterial),
+    OpenCurveEditor {
+        curve: Curve,
+        target: Handle<UiNode>,
+    },
     ShowInAssetBrowser(PathBuf),
     SetWorldViewerFilter(String),
     LocateObject {
@@ -239,25 +299,72 @@ pub enum Message {
         handle: ErasedHandle,
     },
     SetEditorCameraProjection(Projection),
+    SetEditorCameraFollow(bool),
+    SetViewportRenderMode(ViewportRenderMode),
+    /// Sets the point around which selected nodes are rotated and scaled, see [`PivotMode`].
+    SetPivotMode(PivotMode),
+    /// Enables or disables preview of post-processing of the scene in the viewport, see
+    /// [`EditorScene::sync_post_processing`].
+    SetPostProcessingPreview(bool),
+    /// Hides or shows nodes in the editor only, see [`EditorScene::hidden_nodes`].
+    SetNodesHidden {
+        nodes: Vec<Handle<Node>>,
+        hidden: bool,
+    },
+    /// Freezes or unfreezes nodes in play mode, see [`EditorScene::frozen_nodes`].
+    SetNodesFrozen {
+        nodes: Vec<Handle<Node>>,
+        frozen: bool,
+    },
     SwitchToPlayMode,
     SwitchToEditMode,
     SwitchMode,
+    /// Pauses or resumes updates of scripts and plugins of the running game.
+    SetLogicPaused(bool),
+    /// Performs a single update of scripts and plugins of the running game while they're paused.
+    StepLogic,
     OpenLoadSceneDialog,
     OpenSaveSceneDialog,
     OpenSaveSceneConfirmationDialog(SaveSceneConfirmationDialogAction),
     SetBuildProfile(BuildProfile),
     SaveSelectionAsPrefab(PathBuf),
+    MakePrefab(PathBuf),
+    InstantiateAtSelection {
+        path: PathBuf,
+        placement: InstancePlacement,
+    },
+    ExportSelection(PathBuf),
     SyncNodeHandleName {
         view: Handle<UiNode>,
         handle: Handle<Node>,
     },
+    /// Makes the next click on a node in the scene viewer or in the world viewer assign the node
+    /// to the given handle property editor instead of selecting it.
+    PickNodeHandle {
+        view: Handle<UiNode>,
+    },
     ForceSync,
+    ResetLayout,
+    /// Hides every panel and the main menu leaving only the scene viewer, or restores them back.
+    ToggleImmersiveMode,
+    /// Opens a window with all keyboard shortcuts, or closes it if it is already opened.
+    ToggleShortcuts,
+    /// Reloads the models and syncs their instances in the current scene.
+    ReimportModels(Vec<Model>),
 }
 
 impl Message {
     pub fn do_scene_command<C: Command>(cmd: C) -> Self {
         Self::DoSceneCommand(SceneCommand::new(cmd))
     }
+
+    pub fn begin_transaction<S: AsRef<str>>(name: S) -> Self {
+        Self::BeginTransaction(name.as_ref().to_owned())
+    }
+
+    pub fn end_transaction() -> Self {
+        Self::EndTransaction
+    }
 }
 
 pub fn make_scene_file_filter() -> Filter {
@@ -273,7 +380,7 @@ pub fn make_scene_file_filter() -> Filter {
 pub fn make_save_file_selector(ctx: &mut BuildContext) -> Handle<UiNode> {
     FileSelectorBuilder::new(
         WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
-            .with_title(WindowTitle::Text("Save Scene As".into()))
+            .with_title(WindowTitle::Text(tr("Save Scene As")))
             .open(false),
     )
     .with_mode(FileBrowserMode::Save {
@@ -304,6 +411,9 @@ impl Mode {
 pub struct GameLoopData {
     clock: Instant,
     lag: f32,
+    /// Whether the previous frame dropped update steps, it is used to warn only once per
+    /// overload.
+    overloaded: bool,
 }
 
 pub struct StartupData {
@@ -315,6 +425,18 @@ pub struct StartupData {
     pub scene: PathBuf,
 }
 
+/// Defines where instances of a model are put, when the model is instantiated at every selected
+/// node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InstancePlacement {
+    /// Instances are siblings of the selected nodes and have the same local transform.
+    Sibling,
+    /// Instances are children of the selected nodes.
+    Child,
+    /// Same as `Sibling`, but the selected nodes are deleted.
+    Replace,
+}
+
 #[derive(Debug)]
 pub enum SaveSceneConfirmationDialogAction {
     /// Do nothing.
@@ -341,9 +463,11 @@ impl SaveSceneConfirmationDialog {
                 .can_close(false)
                 .can_minimize(false)
                 .open(false)
-                .with_title(WindowTitle::Text("Unsaved changes".to_owned())),
+                .with_title(WindowTitle::Text(tr("Unsaved changes"))),
         )
-        .with_text("There are unsaved changes. Do you wish to save them before continue?")
+        .with_text(&tr(
+            "There are unsaved changes. Do you wish to save them before continue?",
+        ))
         .with_buttons(MessageBoxButtons::YesNoCancel)
         .build(ctx);
 
@@ -454,41 +578,66 @@ impl SaveSceneConfirmationDialog {
     }
 }
 
+/// State of the edit mode that is put aside while the game is played.
+struct EditModeBackup {
+    command_stack: CommandStack,
+    has_unsaved_changes: bool,
+}
+
 pub struct Editor {
     game_loop_data: GameLoopData,
     engine: Engine,
     scene: Option<EditorScene>,
     command_stack: CommandStack,
+    // Play mode has its own command stack, so changes made in it can be discarded on return to the
+    // edit mode without touching the edit mode history.
+    edit_mode_backup: Option<EditModeBackup>,
     message_sender: Sender<Message>,
     message_receiver: Receiver<Message>,
     interaction_modes: Vec<Box<dyn InteractionMode>>,
     current_interaction_mode: Option<InteractionModeKind>,
     world_viewer: WorldViewer,
     root_grid: Handle<UiNode>,
+    docking_manager: Handle<UiNode>,
+    /// Layout of panels that is built in the code, it is used to reset customized layout.
+    default_layout: DockingManagerLayoutDescriptor,
+    /// Layout of panels before entering immersive mode, it is restored when the mode is turned off.
+    immersive_layout: Option<DockingManagerLayoutDescriptor>,
     scene_viewer: SceneViewer,
     asset_browser: AssetBrowser,
     exit_message_box: Handle<UiNode>,
     save_file_selector: Handle<UiNode>,
     save_scene_dialog: SaveSceneConfirmationDialog,
     light_panel: LightPanel,
+    profiler_panel: ProfilerPanel,
+    shortcuts: ShortcutsWindow,
     menu: Menu,
     exit: bool,
     configurator: Configurator,
     log: LogPanel,
     command_stack_viewer: CommandStackViewer,
     validation_message_box: Handle<UiNode>,
+    delete_confirmation_message_box: Handle<UiNode>,
     navmesh_panel: NavmeshPanel,
     settings: Settings,
     path_fixer: PathFixer,
+    script_relinker: ScriptRelinker,
     material_editor: MaterialEditor,
+    cube_map: CubeMapWindow,
     pub inspector: Inspector,
     curve_editor: CurveEditorWindow,
     audio_panel: AudioPanel,
     absm_editor: AbsmEditor,
     mode: Mode,
     build_window: BuildWindow,
+    scene_loading_window: SceneLoadingWindow,
     build_profile: BuildProfile,
     scene_settings: SceneSettingsWindow,
+    heightmap_import: HeightMapImportWindow,
+    normalize_scale: NormalizeScaleWindow,
+    paste_transform: PasteTransformWindow,
+    vertex_ao_bake: VertexAoBakeWindow,
+    teleport_camera: TeleportCameraWindow,
     animation_editor: AnimationEditor,
     particle_system_control_panel: ParticleSystemPreviewControlPanel,
     overlay_pass: Rc<RefCell<OverlayRenderPass>>,
@@ -549,11 +698,6 @@ impl Editor {
             .unwrap(),
         );
 
-        let configurator = Configurator::new(
-            message_sender.clone(),
-            &mut engine.user_interface.build_ctx(),
-        );
-
         let mut settings = Settings::default();
 
         match Settings::load() {
@@ -582,6 +726,19 @@ impl Editor {
             }
         }
 
+        // Language must be loaded before any UI is created.
+        if let Err(e) = load_language(&settings.general.language) {
+            println!(
+                "Failed to load {} language, fallback to default. Reason: {:?}",
+                settings.general.language, e
+            )
+        }
+
+        let configurator = Configurator::new(
+            message_sender.clone(),
+            &mut engine.user_interface.build_ctx(),
+        );
+
         let scene_viewer = SceneViewer::new(&mut engine, message_sender.clone());
         let asset_browser = AssetBrowser::new(&mut engine);
         let menu = Menu::new(&mut engine, message_sender.clone(), &settings);
@@ -589,6 +746,8 @@ impl Editor {
         let audio_panel = AudioPanel::new(&mut engine);
 
         let ctx = &mut engine.user_interface.build_ctx();
+        let profiler_panel = ProfilerPanel::new(ctx);
+        let shortcuts = ShortcutsWindow::new(ctx);
         let navmesh_panel = NavmeshPanel::new(ctx, message_sender.clone());
         let world_outliner = WorldViewer::new(ctx, message_sender.clone(), &settings);
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
@@ -598,126 +757,123 @@ impl Editor {
         let absm_editor = AbsmEditor::new(ctx, message_sender.clone());
         let particle_system_control_panel = ParticleSystemPreviewControlPanel::new(ctx);
 
+        let docking_manager =
+            DockingManagerBuilder::new(WidgetBuilder::new().on_row(1).with_child({
+                TileBuilder::new(WidgetBuilder::new())
+                    .with_content(TileContent::VerticalTiles {
+                        splitter: 0.75,
+                        tiles: [
+                            TileBuilder::new(WidgetBuilder::new())
+                                .with_content(TileContent::HorizontalTiles {
+                                    splitter: 0.25,
+                                    tiles: [
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::Window(
+                                                world_outliner.window,
+                                            ))
+                                            .build(ctx),
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::HorizontalTiles {
+                                                splitter: 0.66,
+                                                tiles: [
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(TileContent::Window(
+                                                            scene_viewer.window(),
+                                                        ))
+                                                        .build(ctx),
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(TileContent::Window(
+                                                            inspector.window,
+                                                        ))
+                                                        .build(ctx),
+                                                ],
+                                            })
+                                            .build(ctx),
+                                    ],
+                                })
+                                .build(ctx),
+                            TileBuilder::new(WidgetBuilder::new())
+                                .with_content(TileContent::HorizontalTiles {
+                                    splitter: 0.66,
+                                    tiles: [
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::HorizontalTiles {
+                                                splitter: 0.80,
+                                                tiles: [
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(TileContent::Window(
+                                                            asset_browser.window,
+                                                        ))
+                                                        .build(ctx),
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(TileContent::Window(
+                                                            command_stack_viewer.window,
+                                                        ))
+                                                        .build(ctx),
+                                                ],
+                                            })
+                                            .build(ctx),
+                                        TileBuilder::new(WidgetBuilder::new())
+                                            .with_content(TileContent::HorizontalTiles {
+                                                splitter: 0.5,
+                                                tiles: [
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(TileContent::Window(
+                                                            log.window,
+                                                        ))
+                                                        .build(ctx),
+                                                    TileBuilder::new(WidgetBuilder::new())
+                                                        .with_content(
+                                                            TileContent::HorizontalTiles {
+                                                                splitter: 0.5,
+                                                                tiles: [
+                                                                    TileBuilder::new(
+                                                                        WidgetBuilder::new(),
+                                                                    )
+                                                                    .with_content(
+                                                                        TileContent::Window(
+                                                                            navmesh_panel.window,
+                                                                        ),
+                                                                    )
+                                                                    .build(ctx),
+                                                                    TileBuilder::new(
+                                                                        WidgetBuilder::new(),
+                                                                    )
+                                                                    .with_content(
+                                                                        TileContent::Window(
+                                                                            audio_panel.window,
+                                                                        ),
+                                                                    )
+                                                                    .build(ctx),
+                                                                ],
+                                                            },
+                                                        )
+                                                        .build(ctx),
+                                                ],
+                                            })
+                                            .build(ctx),
+                                    ],
+                                })
+                                .build(ctx),
+                        ],
+                    })
+                    .build(ctx)
+            }))
+            .with_floating_windows(vec![
+                animation_editor.window,
+                absm_editor.window,
+                particle_system_control_panel.window,
+            ])
+            .build(ctx);
+
         let root_grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_width(logical_size.width)
                 .with_height(logical_size.height)
                 .with_child(menu.menu)
-                .with_child(
-                    DockingManagerBuilder::new(WidgetBuilder::new().on_row(1).with_child({
-                        TileBuilder::new(WidgetBuilder::new())
-                            .with_content(TileContent::VerticalTiles {
-                                splitter: 0.75,
-                                tiles: [
-                                    TileBuilder::new(WidgetBuilder::new())
-                                        .with_content(TileContent::HorizontalTiles {
-                                            splitter: 0.25,
-                                            tiles: [
-                                                TileBuilder::new(WidgetBuilder::new())
-                                                    .with_content(TileContent::Window(
-                                                        world_outliner.window,
-                                                    ))
-                                                    .build(ctx),
-                                                TileBuilder::new(WidgetBuilder::new())
-                                                    .with_content(TileContent::HorizontalTiles {
-                                                        splitter: 0.66,
-                                                        tiles: [
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(TileContent::Window(
-                                                                    scene_viewer.window(),
-                                                                ))
-                                                                .build(ctx),
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(TileContent::Window(
-                                                                    inspector.window,
-                                                                ))
-                                                                .build(ctx),
-                                                        ],
-                                                    })
-                                                    .build(ctx),
-                                            ],
-                                        })
-                                        .build(ctx),
-                                    TileBuilder::new(WidgetBuilder::new())
-                                        .with_content(TileContent::HorizontalTiles {
-                                            splitter: 0.66,
-                                            tiles: [
-                                                TileBuilder::new(WidgetBuilder::new())
-                                                    .with_content(TileContent::HorizontalTiles {
-                                                        splitter: 0.80,
-                                                        tiles: [
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(TileContent::Window(
-                                                                    asset_browser.window,
-                                                                ))
-                                                                .build(ctx),
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(TileContent::Window(
-                                                                    command_stack_viewer.window,
-                                                                ))
-                                                                .build(ctx),
-                                                        ],
-                                                    })
-                                                    .build(ctx),
-                                                TileBuilder::new(WidgetBuilder::new())
-                                                    .with_content(TileContent::HorizontalTiles {
-                                                        splitter: 0.5,
-                                                        tiles: [
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(TileContent::Window(
-                                                                    log.window,
-                                                                ))
-                                                                .build(ctx),
-                                                            TileBuilder::new(WidgetBuilder::new())
-                                                                .with_content(
-                                                                    TileContent::HorizontalTiles {
-                                                                        splitter: 0.5,
-                                                                        tiles: [
-                                                                            TileBuilder::new(
-                                                                                WidgetBuilder::new(
-                                                                                ),
-                                                                            )
-                                                                            .with_content(
-                                                                                TileContent::Window(
-                                                                                    navmesh_panel
-                                                                                        .window,
-                                                                                ),
-                                                                            )
-                                                                            .build(ctx),
-                                                                            TileBuilder::new(
-                                                                                WidgetBuilder::new(
-                                                                                ),
-                                                                            )
-                                                                            .with_content(
-                                                                                TileContent::Window(
-                                                                                    audio_panel
-                                                                                        .window,
-                                                                                ),
-                                                                            )
-                                                                            .build(ctx),
-                                                                        ],
-                                                                    },
-                                                                )
-                                                                .build(ctx),
-                                                        ],
-                                                    })
-                                                    .build(ctx),
-                                            ],
-                                        })
-                                        .build(ctx),
-                                ],
-                            })
-                            .build(ctx)
-                    }))
-                    .with_floating_windows(vec![
-                        animation_editor.window,
-                        absm_editor.window,
-                        particle_system_control_panel.window,
-                    ])
-                    .build(ctx),
-                ),
+                .with_child(docking_manager),
         )
-        .add_row(Row::strict(25.0))
+        .add_row(Row::auto())
         .add_row(Row::stretch())
         .add_column(Column::stretch())
         .build(ctx);
@@ -729,9 +885,11 @@ impl Editor {
                 .can_close(false)
                 .can_minimize(false)
                 .open(false)
-                .with_title(WindowTitle::Text("Unsaved changes".to_owned())),
+                .with_title(WindowTitle::Text(tr("Unsaved changes"))),
         )
-        .with_text("There are unsaved changes. Do you wish to save them before exit?")
+        .with_text(&tr(
+            "There are unsaved changes. Do you wish to save them before exit?",
+        ))
         .with_buttons(MessageBoxButtons::YesNoCancel)
         .build(ctx);
 
@@ -740,12 +898,23 @@ impl Editor {
                 .can_close(false)
                 .can_minimize(false)
                 .open(false)
-                .with_title(WindowTitle::Text("Validation failed!".to_owned())),
+                .with_title(WindowTitle::Text(tr("Validation failed!"))),
         )
         .with_buttons(MessageBoxButtons::Ok)
         .build(ctx);
 
+        let delete_confirmation_message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                .can_close(false)
+                .can_minimize(false)
+                .open(false)
+                .with_title(WindowTitle::Text(tr("Delete Selection"))),
+        )
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(ctx);
+
         let path_fixer = PathFixer::new(ctx);
+        let script_relinker = ScriptRelinker::new(ctx);
 
         let curve_editor = CurveEditorWindow::new(ctx);
 
@@ -753,10 +922,41 @@ impl Editor {
 
         let build_window = BuildWindow::new(ctx);
 
+        let scene_loading_window = SceneLoadingWindow::new(ctx);
+
         let scene_settings = SceneSettingsWindow::new(ctx, message_sender.clone());
 
+        let heightmap_import = HeightMapImportWindow::new(ctx, message_sender.clone());
+
+        let normalize_scale = NormalizeScaleWindow::new(ctx, message_sender.clone());
+
+        let paste_transform = PasteTransformWindow::new(ctx, message_sender.clone());
+
+        let vertex_ao_bake = VertexAoBakeWindow::new(ctx, message_sender.clone());
+
+        let teleport_camera = TeleportCameraWindow::new(ctx);
+
         let material_editor = MaterialEditor::new(&mut engine);
 
+        let cube_map = CubeMapWindow::new(&mut engine);
+
+        let default_layout = engine
+            .user_interface
+            .node(docking_manager)
+            .cast::<DockingManager>()
+            .unwrap()
+            .layout(&engine.user_interface);
+        if let Some(layout) = settings.windows.layout.clone() {
+            // Panels that no longer exist are skipped when the layout is applied.
+            engine
+                .user_interface
+                .send_message(DockingManagerMessage::layout(
+                    docking_manager,
+                    MessageDirection::ToWidget,
+                    layout,
+                ));
+        }
+
         let mut editor = Self {
             animation_editor,
             engine,
@@ -764,12 +964,16 @@ impl Editor {
             scene_viewer,
             scene: None,
             command_stack: CommandStack::new(false),
+            edit_mode_backup: None,
             message_sender,
             message_receiver,
             interaction_modes: Default::default(),
             current_interaction_mode: None,
             world_viewer: world_outliner,
             root_grid,
+            docking_manager,
+            default_layout,
+            immersive_layout: None,
             menu,
             exit: false,
             asset_browser,
@@ -778,11 +982,16 @@ impl Editor {
             configurator,
             log,
             light_panel,
+            profiler_panel,
+            shortcuts,
             command_stack_viewer,
             validation_message_box,
+            delete_confirmation_message_box,
             settings,
             path_fixer,
+            script_relinker,
             material_editor,
+            cube_map,
             inspector,
             curve_editor,
             audio_panel,
@@ -791,11 +1000,18 @@ impl Editor {
             game_loop_data: GameLoopData {
                 clock: Instant::now(),
                 lag: 0.0,
+                overloaded: false,
             },
             absm_editor,
             build_window,
+            scene_loading_window,
             build_profile: BuildProfile::Debug,
             scene_settings,
+            heightmap_import,
+            normalize_scale,
+            paste_transform,
+            vertex_ao_bake,
+            teleport_camera,
             particle_system_control_panel,
             overlay_pass,
         };
@@ -852,6 +1068,8 @@ impl Editor {
             }
         }
 
+        Log::verify(load_language(&self.settings.general.language));
+
         self.menu
             .file_menu
             .update_recent_files_list(&mut self.engine.user_interface, &self.settings);
@@ -873,6 +1091,7 @@ impl Editor {
 
     fn set_scene(&mut self, mut scene: Scene, path: Option<PathBuf>) {
         // Discard previous scene.
+        self.save_scene_sidecar();
         if let Some(previous_editor_scene) = self.scene.as_ref() {
             self.engine.scenes.remove(previous_editor_scene.scene);
         }
@@ -889,8 +1108,42 @@ impl Editor {
         self.scene_viewer
             .set_render_target(&self.engine.user_interface, scene.render_target.clone());
 
-        let editor_scene =
-            EditorScene::from_native_scene(scene, &mut self.engine, path.clone(), &self.settings);
+        let sidecar = path
+            .as_ref()
+            .filter(|path| SceneSidecar::path(path).exists())
+            .and_then(|path| match SceneSidecar::load(path) {
+                Ok(sidecar) => Some(sidecar),
+                Err(e) => {
+                    Log::warn(format!("Unable to load editor data of the scene: {}", e));
+                    None
+                }
+            });
+
+        let mut editor_scene = EditorScene::from_native_scene(
+            scene,
+            &mut self.engine,
+            path.clone(),
+            sidecar
+                .as_ref()
+                .filter(|_| self.settings.camera.remember_scene_camera)
+                .and_then(|sidecar| sidecar.camera.as_ref()),
+        );
+
+        editor_scene.transform_locks = self.scene_viewer.transform_locks();
+        editor_scene.pivot_mode = self.scene_viewer.pivot_mode();
+
+        // Restore selection from the previous session, it is set directly, so it won't be undone.
+        if let Some(sidecar) = sidecar.as_ref() {
+            let graph = &self.engine.scenes[editor_scene.scene].graph;
+            if let Some(selection) = sidecar.restore_selection(graph) {
+                editor_scene.selection = selection;
+                self.message_sender
+                    .send(Message::SelectionChanged {
+                        old_selection: Selection::None,
+                    })
+                    .unwrap();
+            }
+        }
 
         self.interaction_modes = vec![
             Box::new(SelectInteractionMode::new(
@@ -923,9 +1176,25 @@ impl Editor {
                 &mut self.engine,
                 self.message_sender.clone(),
             )),
+            Box::new(ColliderInteractionMode::new(
+                &editor_scene,
+                &mut self.engine,
+                self.message_sender.clone(),
+            )),
+            Box::new(VertexPaintInteractionMode::new(
+                &editor_scene,
+                &mut self.engine,
+                self.message_sender.clone(),
+            )),
+            Box::new(WaypointInteractionMode::new(
+                &editor_scene,
+                &mut self.engine,
+                self.message_sender.clone(),
+            )),
         ];
 
         self.command_stack = CommandStack::new(false);
+        self.edit_mode_backup = None;
         self.scene = Some(editor_scene);
 
         self.set_interaction_mode(Some(InteractionModeKind::Move));
@@ -940,17 +1209,20 @@ impl Editor {
             }
         }
 
-        self.scene_viewer.set_title(
-            &self.engine.user_interface,
-            format!(
-                "Scene Preview - {}",
-                path.map_or("Unnamed Scene".to_string(), |p| p
-                    .to_string_lossy()
-                    .to_string())
-            ),
-        );
+        if let Some(editor_scene) = self.scene.as_ref() {
+            let graph = &self.engine.scenes[editor_scene.scene].graph;
+            self.scene_viewer.sync_camera_projection(
+                &self.engine.user_interface,
+                matches!(
+                    graph[editor_scene.camera_controller.camera]
+                        .as_camera()
+                        .projection(),
+                    Projection::Orthographic(_)
+                ),
+            );
+        }
         self.scene_viewer
-            .reset_camera_projection(&self.engine.user_interface);
+            .reset_render_mode(&self.engine.user_interface);
         self.engine.renderer.flush();
     }
 
@@ -1019,6 +1291,26 @@ impl Editor {
                 sender
                     .send(Message::SetInteractionMode(InteractionModeKind::Terrain))
                     .unwrap();
+            } else if hot_key == key_bindings.enable_collider_mode {
+                if self.mode.is_edit() {
+                    sender
+                        .send(Message::SetInteractionMode(InteractionModeKind::Collider))
+                        .unwrap();
+                }
+            } else if hot_key == key_bindings.enable_vertex_paint_mode {
+                sender
+                    .send(Message::SetInteractionMode(
+                        InteractionModeKind::VertexPaint,
+                    ))
+                    .unwrap();
+            } else if hot_key == key_bindings.enable_waypoint_mode {
+                sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Waypoint))
+                    .unwrap();
+            } else if hot_key == key_bindings.toggle_immersive_mode {
+                sender.send(Message::ToggleImmersiveMode).unwrap();
+            } else if hot_key == key_bindings.show_shortcuts {
+                sender.send(Message::ToggleShortcuts).unwrap();
             } else if hot_key == key_bindings.load_scene {
                 sender.send(Message::OpenLoadSceneDialog).unwrap();
             } else if hot_key == key_bindings.save_scene {
@@ -1064,12 +1356,100 @@ impl Editor {
                 sender.send(Message::CloseScene).unwrap();
             } else if hot_key == key_bindings.remove_selection {
                 if let Some(editor_scene) = self.scene.as_mut() {
-                    if !editor_scene.selection.is_empty() {
-                        if let Selection::Graph(_) = editor_scene.selection {
+                    if let Selection::Graph(selection) = &editor_scene.selection {
+                        // Graph's root is non-deletable, so do not count it.
+                        let root = engine.scenes[editor_scene.scene].graph.get_root();
+                        let count = selection.nodes.iter().filter(|&&n| n != root).count();
+
+                        let settings = &self.settings.selection;
+                        if count > 0 {
+                            if settings.confirm_deletion
+                                && count > settings.deletion_confirmation_threshold
+                            {
+                                engine.user_interface.send_message(MessageBoxMessage::open(
+                                    self.delete_confirmation_message_box,
+                                    MessageDirection::ToWidget,
+                                    None,
+                                    Some(
+                                        tr("Do you really want to delete {} selected nodes?")
+                                            .replace("{}", &count.to_string()),
+                                    ),
+                                ));
+                            } else {
+                                sender
+                                    .send(Message::DoSceneCommand(make_delete_selection_command(
+                                        editor_scene,
+                                        engine,
+                                    )))
+                                    .unwrap();
+                            }
+                        }
+                    }
+                }
+            } else if hot_key == key_bindings.toggle_enabled {
+                if let Some(editor_scene) = self.scene.as_ref() {
+                    RepeatableAction::ToggleEnabled.perform(editor_scene, engine, sender);
+                }
+            } else if hot_key == key_bindings.duplicate_selection
+                || hot_key == key_bindings.duplicate_selection_linked
+            {
+                if let Some(editor_scene) = self.scene.as_ref() {
+                    let mode = if hot_key == key_bindings.duplicate_selection {
+                        DuplicationMode::Independent
+                    } else {
+                        DuplicationMode::Linked
+                    };
+                    RepeatableAction::duplicate(mode).perform(editor_scene, engine, sender);
+                }
+            } else if hot_key == key_bindings.cycle_pivot_mode {
+                sender
+                    .send(Message::SetPivotMode(self.scene_viewer.pivot_mode().next()))
+                    .unwrap();
+            } else if hot_key == key_bindings.clear_parent
+                || hot_key == key_bindings.parent_to_last_selected
+            {
+                if let Some(editor_scene) = self.scene.as_ref() {
+                    if let Selection::Graph(selection) = &editor_scene.selection {
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        let command = if hot_key == key_bindings.clear_parent {
+                            make_clear_parent_command(selection, graph)
+                        } else {
+                            make_parent_to_last_selected_command(selection, graph)
+                        };
+                        if let Some(command) = command {
+                            sender.send(Message::DoSceneCommand(command)).unwrap();
+                        }
+                    }
+                }
+            } else if hot_key == key_bindings.repeat_last_action {
+                if let Some(editor_scene) = self.scene.as_ref() {
+                    repeat_last_action(editor_scene, engine, sender);
+                }
+            } else if hot_key == key_bindings.frame_scene {
+                if let Some(editor_scene) = self.scene.as_mut() {
+                    editor_scene.camera_controller.frame_scene(
+                        &engine.scenes[editor_scene.scene].graph,
+                        editor_scene.editor_objects_root,
+                    );
+                }
+            } else if hot_key == key_bindings.cycle_selection {
+                if let Some(editor_scene) = self.scene.as_mut() {
+                    let screen_bounds = self.scene_viewer.frame_bounds(&engine.user_interface);
+                    let cursor_pos = engine.user_interface.cursor_position();
+                    if screen_bounds.contains(cursor_pos) {
+                        let new_selection = make_pick_selection(
+                            editor_scene,
+                            engine,
+                            cursor_pos - screen_bounds.position,
+                            screen_bounds.size,
+                            &self.settings,
+                        );
+
+                        if new_selection != editor_scene.selection {
                             sender
-                                .send(Message::DoSceneCommand(make_delete_selection_command(
-                                    editor_scene,
-                                    engine,
+                                .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                                    new_selection,
+                                    editor_scene.selection.clone(),
                                 )))
                                 .unwrap();
                         }
@@ -1105,16 +1485,24 @@ impl Editor {
                     world_outliner_window: self.world_viewer.window,
                     asset_window: self.asset_browser.window,
                     light_panel: self.light_panel.window,
+                    profiler_panel: self.profiler_panel.window,
                     log_panel: self.log.window,
                     navmesh_panel: self.navmesh_panel.window,
                     audio_panel: self.audio_panel.window,
                     configurator_window: self.configurator.window,
                     path_fixer: self.path_fixer.window,
+                    script_relinker: self.script_relinker.window,
+                    teleport_camera: self.teleport_camera.window,
                     curve_editor: &self.curve_editor,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
                     scene_settings: &self.scene_settings,
+                    heightmap_import: &self.heightmap_import,
+                    normalize_scale: &self.normalize_scale,
+                    paste_transform: &self.paste_transform,
+                    vertex_ao_bake: self.vertex_ao_bake.window,
                     animation_editor: &self.animation_editor,
+                    cube_map: &self.cube_map,
                 },
                 settings: &mut self.settings,
             },
@@ -1126,6 +1514,8 @@ impl Editor {
         self.asset_browser
             .handle_ui_message(message, engine, self.message_sender.clone());
         self.command_stack_viewer.handle_ui_message(message);
+        self.shortcuts
+            .handle_ui_message(message, &engine.user_interface);
         self.curve_editor.handle_ui_message(message, engine);
         self.path_fixer.handle_ui_message(
             message,
@@ -1133,6 +1523,11 @@ impl Editor {
             engine.serialization_context.clone(),
             engine.resource_manager.clone(),
         );
+        self.script_relinker.handle_ui_message(
+            message,
+            &mut engine.user_interface,
+            engine.serialization_context.clone(),
+        );
         self.scene_viewer.handle_ui_message(
             message,
             engine,
@@ -1152,13 +1547,40 @@ impl Editor {
         if let Some(editor_scene) = self.scene.as_mut() {
             self.particle_system_control_panel
                 .handle_ui_message(message, editor_scene, engine);
-            self.absm_editor
-                .handle_ui_message(message, engine, &self.message_sender, editor_scene);
+            self.absm_editor.handle_ui_message(
+                message,
+                engine,
+                &self.message_sender,
+                editor_scene,
+                &self.settings.key_bindings,
+            );
             self.audio_panel
                 .handle_ui_message(message, editor_scene, &self.message_sender, engine);
 
             self.scene_settings
-                .handle_ui_message(message, &self.message_sender);
+                .handle_ui_message(message, &self.message_sender, engine);
+            self.heightmap_import.handle_ui_message(
+                message,
+                editor_scene,
+                engine,
+                &self.message_sender,
+            );
+            self.normalize_scale.handle_ui_message(
+                message,
+                editor_scene,
+                engine,
+                &self.message_sender,
+            );
+            self.paste_transform.handle_ui_message(
+                message,
+                editor_scene,
+                engine,
+                &self.message_sender,
+            );
+            self.vertex_ao_bake
+                .handle_ui_message(message, editor_scene, engine);
+            self.teleport_camera
+                .handle_ui_message(message, editor_scene, engine);
 
             self.navmesh_panel.handle_message(
                 message,
@@ -1195,6 +1617,9 @@ impl Editor {
             self.material_editor
                 .handle_ui_message(message, engine, &self.message_sender);
 
+            self.cube_map
+                .handle_ui_message(message, editor_scene, engine, &self.message_sender);
+
             if let Some(MessageBoxMessage::Close(result)) = message.data::<MessageBoxMessage>() {
                 if message.destination() == self.exit_message_box {
                     match result {
@@ -1226,6 +1651,15 @@ impl Editor {
                         }
                         _ => {}
                     }
+                } else if message.destination() == self.delete_confirmation_message_box
+                    && *result == MessageBoxResult::Yes
+                {
+                    self.message_sender
+                        .send(Message::DoSceneCommand(make_delete_selection_command(
+                            editor_scene,
+                            engine,
+                        )))
+                        .unwrap();
                 }
             } else if let Some(FileSelectorMessage::Commit(path)) =
                 message.data::<FileSelectorMessage>()
@@ -1245,6 +1679,13 @@ impl Editor {
     }
 
     fn set_play_mode(&mut self) {
+        // Light solo is an editing tool, the game must be played with all the lights.
+        if let Some(editor_scene) = self.scene.as_mut() {
+            editor_scene.clear_light_solo(&mut self.engine.scenes[editor_scene.scene].graph);
+            self.light_panel
+                .sync_to_model(editor_scene, &self.engine.user_interface);
+        }
+
         if let Some(scene) = self.scene.as_ref() {
             if let Some(path) = scene.path.as_ref().cloned() {
                 self.save_current_scene(path.clone());
@@ -1253,6 +1694,7 @@ impl Editor {
 
                 process
                     .stdout(Stdio::piped())
+                    .stdin(Stdio::piped())
                     .arg("run")
                     .arg("--package")
                     .arg("executor");
@@ -1263,6 +1705,21 @@ impl Editor {
 
                 process.arg("--").arg("--override-scene").arg(path);
 
+                // Saving needs mutable access to the editor, so the scene is borrowed again.
+                let frozen_nodes: Vec<String> = self
+                    .scene
+                    .as_ref()
+                    .map(|s| {
+                        s.saved_frozen_nodes()
+                            .iter()
+                            .map(|h| h.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_else(Vec::new);
+                if !frozen_nodes.is_empty() {
+                    process.arg("--frozen-nodes").arg(frozen_nodes.join(","));
+                }
+
                 match process.spawn() {
                     Ok(mut process) => {
                         let active = Arc::new(AtomicBool::new(true));
@@ -1292,6 +1749,14 @@ impl Editor {
         }
     }
 
+    fn send_executor_command(&mut self, command: ExecutorCommand) {
+        if let Mode::Play { process, .. } = &mut self.mode {
+            if let Some(stdin) = process.stdin.as_mut() {
+                Log::verify(writeln!(stdin, "{}", command.as_str()));
+            }
+        }
+    }
+
     fn set_build_mode(&mut self) {
         if let Mode::Edit = self.mode {
             if let Some(scene) = self.scene.as_ref() {
@@ -1332,6 +1797,11 @@ impl Editor {
     }
 
     fn set_editor_mode(&mut self) {
+        // Freezing is a debugging aid for a single play session.
+        if let (Mode::Play { .. }, Some(editor_scene)) = (&self.mode, self.scene.as_mut()) {
+            editor_scene.frozen_nodes.clear();
+        }
+
         if let Mode::Play { mut process, .. } | Mode::Build { mut process } =
             std::mem::replace(&mut self.mode, Mode::Edit)
         {
@@ -1341,7 +1811,93 @@ impl Editor {
         }
     }
 
+    /// Immersive mode hides the main menu and every panel except the scene viewer, which takes
+    /// the whole window. Panels are restored exactly as they were when the mode is turned off.
+    fn set_immersive_mode(&mut self, immersive: bool) {
+        if immersive == self.immersive_layout.is_some() {
+            return;
+        }
+
+        let ui = &self.engine.user_interface;
+
+        let layout = if immersive {
+            let docking_manager = match ui.node(self.docking_manager).cast::<DockingManager>() {
+                Some(docking_manager) => docking_manager,
+                None => return,
+            };
+            self.immersive_layout = Some(docking_manager.layout(ui));
+
+            // Every window that is not mentioned in a layout will be closed.
+            DockingManagerLayoutDescriptor {
+                root_tile: TileContentDescriptor::Window(
+                    ui.node(self.scene_viewer.window()).name().to_owned(),
+                ),
+                floating_windows: vec![],
+            }
+        } else {
+            match self.immersive_layout.take() {
+                Some(layout) => layout,
+                None => return,
+            }
+        };
+
+        ui.send_message(DockingManagerMessage::layout(
+            self.docking_manager,
+            MessageDirection::ToWidget,
+            layout,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.menu.menu,
+            MessageDirection::ToWidget,
+            !immersive,
+        ));
+    }
+
+    fn begin_play_mode_commands(&mut self) {
+        if self.edit_mode_backup.is_none() {
+            if let Some(editor_scene) = self.scene.as_ref() {
+                self.edit_mode_backup = Some(EditModeBackup {
+                    command_stack: std::mem::replace(
+                        &mut self.command_stack,
+                        CommandStack::new(false),
+                    ),
+                    has_unsaved_changes: editor_scene.has_unsaved_changes,
+                });
+            }
+        }
+    }
+
+    /// Reverts every change made in the play mode and restores the edit mode command stack, so the
+    /// scene and its undo history are exactly the same as they were before entering the play mode.
+    fn end_play_mode_commands(&mut self) {
+        if let Some(backup) = self.edit_mode_backup.take() {
+            let mut play_mode_command_stack =
+                std::mem::replace(&mut self.command_stack, backup.command_stack);
+
+            let engine = &mut self.engine;
+            if let Some(editor_scene) = self.scene.as_mut() {
+                play_mode_command_stack.revert_all(SceneContext {
+                    scene: &mut engine.scenes[editor_scene.scene],
+                    message_sender: self.message_sender.clone(),
+                    editor_scene,
+                    resource_manager: engine.resource_manager.clone(),
+                    serialization_context: engine.serialization_context.clone(),
+                });
+
+                editor_scene.has_unsaved_changes = backup.has_unsaved_changes;
+            }
+
+            self.sync_to_model();
+        }
+    }
+
     fn on_mode_changed(&mut self) {
+        match self.mode {
+            Mode::Play { .. } => self.begin_play_mode_commands(),
+            Mode::Edit => self.end_play_mode_commands(),
+            Mode::Build { .. } => (),
+        }
+
         let engine = &mut self.engine;
         let ui = &engine.user_interface;
         self.scene_viewer.on_mode_changed(ui, &self.mode);
@@ -1352,11 +1908,56 @@ impl Editor {
         self.audio_panel.on_mode_changed(ui, &self.mode);
         self.navmesh_panel.on_mode_changed(ui, &self.mode);
         self.menu.on_mode_changed(ui, &self.mode);
+
+        // Collider shapes are shown only in edit mode.
+        if !self.mode.is_edit()
+            && self.current_interaction_mode == Some(InteractionModeKind::Collider)
+        {
+            self.message_sender
+                .send(Message::SetInteractionMode(InteractionModeKind::Move))
+                .unwrap();
+        }
+    }
+
+    /// Updates titles of the main window and the scene viewer. Titles are prefixed with an
+    /// asterisk if there are unsaved changes in the current scene.
+    fn sync_title(&mut self) {
+        let unsaved_marker = if self.scene.as_ref().map_or(false, |s| s.has_unsaved_changes) {
+            "*"
+        } else {
+            ""
+        };
+
+        let scene_viewer_title = match self.scene.as_ref() {
+            Some(editor_scene) => format!(
+                "{}Scene Preview - {}",
+                unsaved_marker,
+                editor_scene
+                    .path
+                    .as_ref()
+                    .map_or("Unnamed Scene".to_string(), |p| p
+                        .to_string_lossy()
+                        .to_string())
+            ),
+            None => "Scene Preview".to_string(),
+        };
+        self.scene_viewer
+            .set_title(&self.engine.user_interface, scene_viewer_title);
+
+        if let Ok(working_directory) = std::env::current_dir() {
+            self.engine.get_window().set_title(&format!(
+                "{}Fyroxed: {}",
+                unsaved_marker,
+                working_directory.to_string_lossy()
+            ));
+        }
     }
 
     fn sync_to_model(&mut self) {
         scope_profile!();
 
+        self.sync_title();
+
         let engine = &mut self.engine;
 
         self.menu
@@ -1373,6 +1974,10 @@ impl Editor {
             self.material_editor
                 .sync_to_model(&mut engine.user_interface);
             self.audio_panel.sync_to_model(editor_scene, engine);
+            self.light_panel
+                .sync_to_model(editor_scene, &engine.user_interface);
+            self.normalize_scale.sync_to_model(editor_scene, engine);
+            self.paste_transform.sync_to_model(editor_scene, engine);
             self.command_stack_viewer.sync_to_model(
                 &mut self.command_stack,
                 &SceneContext {
@@ -1406,12 +2011,23 @@ impl Editor {
             if let TextureKind::Rectangle { width, height } =
                 scene.render_target.clone().unwrap().data_ref().kind()
             {
+                let camera_settings = &self.settings.camera;
+                let scale = if camera_settings.reduce_resolution_while_moving
+                    && editor_scene.camera_controller.is_moving()
+                {
+                    camera_settings.moving_resolution_scale.clamp(0.1, 1.0)
+                } else {
+                    1.0
+                };
+
+                // The frame stretches the render target to its bounds, so a smaller render target
+                // just makes the image blurrier.
                 let frame_size = self.scene_viewer.frame_bounds(&engine.user_interface).size;
-                if width != frame_size.x as u32 || height != frame_size.y as u32 {
-                    scene.render_target = Some(Texture::new_render_target(
-                        frame_size.x as u32,
-                        frame_size.y as u32,
-                    ));
+                let target_width = ((frame_size.x * scale) as u32).max(1);
+                let target_height = ((frame_size.y * scale) as u32).max(1);
+                if width != target_width || height != target_height {
+                    scene.render_target =
+                        Some(Texture::new_render_target(target_width, target_height));
                     self.scene_viewer
                         .set_render_target(&engine.user_interface, scene.render_target.clone());
                 }
@@ -1422,6 +2038,8 @@ impl Editor {
     fn do_scene_command(&mut self, command: SceneCommand) -> bool {
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.as_mut() {
+            self.command_stack
+                .set_max_size(self.settings.general.max_command_stack_size);
             self.command_stack.do_command(
                 command.into_inner(),
                 SceneContext {
@@ -1453,6 +2071,10 @@ impl Editor {
             });
 
             editor_scene.has_unsaved_changes = true;
+            // The selection could be changed without a message.
+            if let Some(last_action) = editor_scene.last_action.as_mut() {
+                last_action.stop_merging_moves();
+            }
 
             true
         } else {
@@ -1472,6 +2094,10 @@ impl Editor {
             });
 
             editor_scene.has_unsaved_changes = true;
+            // The selection could be changed without a message.
+            if let Some(last_action) = editor_scene.last_action.as_mut() {
+                last_action.stop_merging_moves();
+            }
 
             true
         } else {
@@ -1510,15 +2136,29 @@ impl Editor {
                     .update_recent_files_list(&mut engine.user_interface, &self.settings);
             }
 
-            match editor_scene.save(path.clone(), engine) {
+            // Invalid state machines are still saved, but the user should know about the problems.
+            let scene = &engine.scenes[editor_scene.scene];
+            let absm_issues = absm::validation::validate_scene(scene);
+            for issue in absm_issues.iter() {
+                Log::warn(format!(
+                    "{} ({}): {}",
+                    scene.graph[issue.absm_node_handle].name(),
+                    issue.absm_node_handle,
+                    issue
+                ));
+            }
+            self.absm_editor
+                .highlight_issues(&absm_issues, editor_scene, &engine.user_interface);
+
+            match editor_scene.save(path.clone(), self.settings.general.safe_save, engine) {
                 Ok(message) => {
-                    self.scene_viewer.set_title(
-                        &engine.user_interface,
-                        format!("Scene Preview - {}", path.display()),
-                    );
                     Log::info(message);
 
                     editor_scene.has_unsaved_changes = false;
+
+                    self.save_scene_sidecar();
+
+                    self.sync_title();
                 }
                 Err(message) => {
                     Log::err(message.clone());
@@ -1534,21 +2174,33 @@ impl Editor {
     }
 
     fn load_scene(&mut self, scene_path: PathBuf) {
-        let engine = &mut self.engine;
-        let result = {
-            block_on(SceneLoader::from_file(
-                &scene_path,
-                engine.serialization_context.clone(),
-            ))
-        };
-        match result {
-            Ok(loader) => {
-                let scene = block_on(loader.finish(engine.resource_manager.clone()));
+        if self.scene_loading_window.is_loading() {
+            Log::warn(format!(
+                "Unable to load {} scene, another scene is loading!",
+                scene_path.display()
+            ));
+            return;
+        }
 
-                self.set_scene(scene, Some(scene_path));
-            }
-            Err(e) => {
-                Log::err(e.to_string());
+        // Loading could take a lot of time, so it is done in a separate thread to keep the editor
+        // responsive. The scene will be set in `update` when it is loaded.
+        let engine = &self.engine;
+        self.scene_loading_window.load(
+            scene_path,
+            engine.serialization_context.clone(),
+            engine.resource_manager.clone(),
+            &engine.user_interface,
+        );
+    }
+
+    fn update_scene_loading(&mut self) {
+        if let Some(loaded_scene) = self
+            .scene_loading_window
+            .update(&self.engine.resource_manager, &self.engine.user_interface)
+        {
+            match loaded_scene.result {
+                Ok(scene) => self.set_scene(scene, Some(loaded_scene.path)),
+                Err(e) => Log::err(e),
             }
         }
     }
@@ -1569,18 +2221,36 @@ impl Editor {
         }
     }
 
+    /// Writes editor-only data of the current scene (selection, camera) next to the scene file.
+    fn save_scene_sidecar(&self) {
+        if let Some(editor_scene) = self.scene.as_ref() {
+            if let Some(path) = editor_scene.path.as_ref() {
+                let sidecar = SceneSidecar::from_editor_scene(
+                    editor_scene,
+                    &self.engine.scenes[editor_scene.scene].graph,
+                    self.settings.camera.remember_scene_camera,
+                );
+                if let Err(e) = sidecar.save(path) {
+                    Log::warn(format!("Unable to save editor data of the scene: {}", e));
+                }
+            }
+        }
+    }
+
     fn close_current_scene(&mut self) -> bool {
+        self.save_scene_sidecar();
+
         let engine = &mut self.engine;
         if let Some(editor_scene) = self.scene.take() {
             engine.scenes.remove(editor_scene.scene);
 
+            // Play mode commands refer to the removed scene, they cannot be reverted anymore.
+            self.edit_mode_backup = None;
+
             // Preview frame has scene frame texture assigned, it must be cleared explicitly,
             // otherwise it will show last rendered frame in preview which is not what we want.
             self.scene_viewer
                 .set_render_target(&engine.user_interface, None);
-            // Set default title scene
-            self.scene_viewer
-                .set_title(&engine.user_interface, "Scene Preview".to_string());
 
             true
         } else {
@@ -1606,11 +2276,9 @@ impl Editor {
         // We must re-read settings, because each project have its own unique settings.
         self.reload_settings();
 
-        let engine = &mut self.engine;
+        self.sync_title();
 
-        engine
-            .get_window()
-            .set_title(&format!("Fyroxed: {}", working_directory.to_string_lossy()));
+        let engine = &mut self.engine;
 
         match FileSystemWatcher::new(&working_directory, Duration::from_secs(1)) {
             Ok(watcher) => {
@@ -1691,6 +2359,15 @@ impl Editor {
 
     fn on_selection_changed(&mut self, old_selection: Selection) {
         if let Some(editor_scene) = self.scene.as_mut() {
+            // The inspector is rebuilt for the new selection, so the editor waiting for a pick is
+            // gone.
+            editor_scene.node_handle_picker = None;
+
+            // Moves of other nodes must not be merged with the last duplication.
+            if let Some(last_action) = editor_scene.last_action.as_mut() {
+                last_action.stop_merging_moves();
+            }
+
             let scene = &self.engine.scenes[editor_scene.scene];
             let node_overrides = editor_scene.graph_switches.node_overrides.as_mut().unwrap();
 
@@ -1771,12 +2448,20 @@ impl Editor {
             _ => {}
         }
 
+        self.update_scene_loading();
+
         self.log.update(&mut self.engine);
+        self.profiler_panel.update(&mut self.engine, dt);
         self.material_editor.update(&mut self.engine);
-        self.asset_browser.update(&mut self.engine);
+        self.cube_map.update(&mut self.engine);
+        self.asset_browser
+            .update(&mut self.engine, &self.settings.asset_browser, dt);
 
         if let Some(scene) = self.scene.as_ref() {
             self.animation_editor.update(scene, &self.engine);
+            self.vertex_ao_bake
+                .update(scene, &self.engine, &self.message_sender);
+            scene.sync_post_processing(&mut self.engine, &self.settings.graphics.quality);
         }
 
         self.overlay_pass.borrow_mut().pictogram_size = self.settings.debugging.pictogram_size;
@@ -1794,6 +2479,8 @@ impl Editor {
                 editor_messages_processed_count += 1;
                 self.path_fixer
                     .handle_message(&message, &self.engine.user_interface);
+                self.script_relinker
+                    .handle_message(&message, &self.engine.user_interface);
 
                 self.save_scene_dialog
                     .handle_message(&message, &self.message_sender);
@@ -1810,6 +2497,7 @@ impl Editor {
                         editor_scene,
                         &mut self.engine,
                         &self.message_sender,
+                        &self.settings,
                     );
                 }
 
@@ -1824,8 +2512,20 @@ impl Editor {
 
                 match message {
                     Message::DoSceneCommand(command) => {
+                        // Any change except selection makes the repeat a no-op.
+                        if !command.is_selection_change() {
+                            if let Some(editor_scene) = self.scene.as_mut() {
+                                editor_scene.last_action = None;
+                            }
+                        }
                         needs_sync |= self.do_scene_command(command);
                     }
+                    Message::DoRepeatableSceneCommand { command, action } => {
+                        needs_sync |= self.do_scene_command(command);
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.last_action = Some(action);
+                        }
+                    }
                     Message::UndoSceneCommand => {
                         needs_sync |= self.undo_scene_command();
                     }
@@ -1835,15 +2535,19 @@ impl Editor {
                     Message::ClearSceneCommandStack => {
                         needs_sync |= self.clear_scene_command_stack();
                     }
+                    Message::BeginTransaction(name) => {
+                        self.command_stack.begin_transaction(&name);
+                    }
+                    Message::EndTransaction => {
+                        self.command_stack.end_transaction();
+                        needs_sync = true;
+                    }
                     Message::SelectionChanged { old_selection } => {
                         self.world_viewer.sync_selection = true;
                         self.on_selection_changed(old_selection);
                     }
                     Message::SaveScene(path) => self.save_current_scene(path),
-                    Message::LoadScene(scene_path) => {
-                        self.load_scene(scene_path);
-                        needs_sync = true;
-                    }
+                    Message::LoadScene(scene_path) => self.load_scene(scene_path),
                     Message::SetInteractionMode(mode_kind) => {
                         self.set_interaction_mode(Some(mode_kind))
                     }
@@ -1859,6 +2563,33 @@ impl Editor {
                         self.configure(working_directory);
                         needs_sync = true;
                     }
+                    Message::ReimportModels(models) => {
+                        if let Some(editor_scene) = self.scene.as_ref() {
+                            reimport_models(
+                                &models,
+                                &mut self.engine.scenes[editor_scene.scene],
+                                &self.engine.resource_manager,
+                            );
+                            needs_sync = true;
+                        }
+                    }
+                    Message::ResetLayout => {
+                        self.set_immersive_mode(false);
+                        self.engine
+                            .user_interface
+                            .send_message(DockingManagerMessage::layout(
+                                self.docking_manager,
+                                MessageDirection::ToWidget,
+                                self.default_layout.clone(),
+                            ));
+                    }
+                    Message::ToggleImmersiveMode => {
+                        self.set_immersive_mode(self.immersive_layout.is_none());
+                    }
+                    Message::ToggleShortcuts => {
+                        self.shortcuts
+                            .toggle(&self.settings.key_bindings, &mut self.engine.user_interface);
+                    }
                     Message::OpenSettings => {
                         self.menu.file_menu.settings.open(
                             &mut self.engine.user_interface,
@@ -1867,13 +2598,21 @@ impl Editor {
                         );
                     }
                     Message::OpenMaterialEditor(material) => self.open_material_editor(material),
+                    Message::OpenCurveEditor { curve, target } => {
+                        self.curve_editor
+                            .open_for_field(curve, target, &self.engine.user_interface)
+                    }
                     Message::ShowInAssetBrowser(path) => {
                         self.asset_browser
                             .locate_path(&self.engine.user_interface, path);
                     }
                     Message::SetWorldViewerFilter(filter) => {
+                        let graph = self
+                            .scene
+                            .as_ref()
+                            .map(|s| &self.engine.scenes[s.scene].graph);
                         self.world_viewer
-                            .set_filter(filter, &self.engine.user_interface);
+                            .set_filter(filter, graph, &self.engine.user_interface);
                     }
                     Message::LocateObject { type_id, handle } => self
                         .world_viewer
@@ -1889,12 +2628,74 @@ impl Editor {
                             );
                         }
                     }
+                    Message::SetEditorCameraFollow(follow) => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            let graph = &self.engine.scenes[editor_scene.scene].graph;
+                            let first_selected = match editor_scene.selection {
+                                Selection::Graph(ref selection) => {
+                                    selection.nodes().first().cloned()
+                                }
+                                _ => None,
+                            };
+                            match (follow, first_selected) {
+                                (true, Some(node)) => {
+                                    editor_scene.camera_controller.follow(graph, node)
+                                }
+                                _ => editor_scene.camera_controller.stop_following(),
+                            }
+                        }
+                        needs_sync = true;
+                    }
+                    Message::SetViewportRenderMode(render_mode) => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.set_render_mode(&mut self.engine, render_mode);
+                        }
+                    }
+                    Message::SetPivotMode(pivot_mode) => {
+                        self.scene_viewer
+                            .set_pivot_mode(&self.engine.user_interface, pivot_mode);
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.pivot_mode = pivot_mode;
+                        }
+                    }
+                    Message::SetPostProcessingPreview(preview) => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.post_processing_preview = preview;
+                        }
+                    }
+                    Message::SetNodesHidden { nodes, hidden } => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.set_nodes_hidden(
+                                &nodes,
+                                hidden,
+                                &mut self.engine.scenes[editor_scene.scene].graph,
+                            );
+                            needs_sync = true;
+                        }
+                    }
+                    Message::SetNodesFrozen { nodes, frozen } => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            for node in nodes {
+                                if frozen {
+                                    editor_scene.frozen_nodes.insert(node);
+                                } else {
+                                    editor_scene.frozen_nodes.remove(&node);
+                                }
+                            }
+                        }
+                    }
                     Message::SwitchMode => match self.mode {
                         Mode::Edit => self.set_build_mode(),
                         _ => self.set_editor_mode(),
                     },
                     Message::SwitchToPlayMode => self.set_play_mode(),
                     Message::SwitchToEditMode => self.set_editor_mode(),
+                    Message::SetLogicPaused(paused) => self.send_executor_command(if paused {
+                        ExecutorCommand::PauseLogic
+                    } else {
+                        ExecutorCommand::ResumeLogic
+                    }),
+                    Message::StepLogic => self.send_executor_command(ExecutorCommand::StepLogic),
                     Message::OpenLoadSceneDialog => {
                         self.menu
                             .open_load_file_selector(&mut self.engine.user_interface);
@@ -1913,6 +2714,15 @@ impl Editor {
                     Message::SaveSelectionAsPrefab(path) => {
                         self.try_save_selection_as_prefab(path);
                     }
+                    Message::MakePrefab(path) => {
+                        self.try_make_prefab(path);
+                    }
+                    Message::InstantiateAtSelection { path, placement } => {
+                        self.try_instantiate_at_selection(path, placement);
+                    }
+                    Message::ExportSelection(path) => {
+                        self.try_export_selection(path);
+                    }
                     Message::SyncNodeHandleName { view, handle } => {
                         if let Some(editor_scene) = self.scene.as_ref() {
                             let scene = &self.engine.scenes[editor_scene.scene];
@@ -1925,6 +2735,15 @@ impl Editor {
                             );
                         }
                     }
+                    Message::PickNodeHandle { view } => {
+                        if let Some(editor_scene) = self.scene.as_mut() {
+                            editor_scene.node_handle_picker = Some(view);
+                            Log::info(
+                                "Click a node in the Scene Viewer or in the World Viewer to \
+                                assign it to the property.",
+                            );
+                        }
+                    }
                     Message::ForceSync => {
                         needs_sync = true;
                     }
@@ -1949,30 +2768,16 @@ impl Editor {
         self.handle_resize();
 
         if let Some(editor_scene) = self.scene.as_mut() {
-            editor_scene.update(&mut self.engine, dt, &self.settings);
+            editor_scene.update(&mut self.engine, dt, &self.settings, self.mode.is_edit());
 
             self.absm_editor.update(editor_scene, &mut self.engine);
 
-            let scene = &self.engine.scenes[editor_scene.scene];
-
-            // Save camera current camera settings for current scene to be able to load them
-            // on next launch.
-            if let Some(path) = editor_scene.path.as_ref() {
-                let last_settings = SceneCameraSettings {
-                    position: editor_scene.camera_controller.position(&scene.graph),
-                    yaw: editor_scene.camera_controller.yaw,
-                    pitch: editor_scene.camera_controller.pitch,
-                };
-
-                if let Some(entry) = self.settings.camera.camera_settings.get_mut(path) {
-                    *entry = last_settings;
-                } else {
-                    self.settings
-                        .camera
-                        .camera_settings
-                        .insert(path.clone(), last_settings);
-                }
-            }
+            self.scene_viewer.update_node_labels(
+                editor_scene,
+                &mut self.engine,
+                &self.settings,
+                self.mode.is_edit(),
+            );
 
             if let Some(mode) = self.current_interaction_mode {
                 self.interaction_modes[mode as usize].update(
@@ -1986,9 +2791,246 @@ impl Editor {
     }
 
     fn try_save_selection_as_prefab(&self, path: PathBuf) {
+        self.save_selection_to_file(&path, "prefab", false);
+    }
+
+    fn try_export_selection(&self, path: PathBuf) {
+        self.save_selection_to_file(&path, "scene", true);
+    }
+
+    /// Saves selected nodes as a prefab and replaces them with a linked instance of the prefab in
+    /// one undoable step. The instance is attached to the parent of the selected nodes with the
+    /// identity transform, so every node keeps its world transform exactly.
+    fn try_make_prefab(&mut self, path: PathBuf) {
+        let editor_scene = match self.scene.as_ref() {
+            Some(editor_scene) => editor_scene,
+            None => {
+                Log::warn("Unable to make prefab, because there is no scene loaded!");
+                return;
+            }
+        };
+
+        let graph = &self.engine.scenes[editor_scene.scene].graph;
+        let root_nodes = if let Selection::Graph(ref graph_selection) = editor_scene.selection {
+            graph_selection
+                .root_nodes(graph)
+                .into_iter()
+                .filter(|&n| n != graph.get_root())
+                .collect::<Vec<_>>()
+        } else {
+            Log::warn("Unable to make prefab, because selection is not scene selection!");
+            return;
+        };
+
+        let parent = match root_nodes.first() {
+            Some(first) => graph[*first].parent(),
+            None => {
+                Log::warn("Unable to make prefab, because nothing is selected!");
+                return;
+            }
+        };
+        if root_nodes.iter().any(|&n| graph[n].parent() != parent) {
+            Log::warn(
+                "Unable to make prefab, because selected nodes must have the same parent! \
+                Otherwise world transform of the nodes cannot be preserved.",
+            );
+            return;
+        }
+
+        if !self.save_selection_to_file(&path, "prefab", false) {
+            return;
+        }
+
+        let relative_path = match make_relative_path(&path) {
+            Ok(relative_path) => relative_path,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to instantiate prefab {:?}, because it is not inside the working \
+                    directory! Reason: {:?}",
+                    path, e
+                ));
+                return;
+            }
+        };
+
+        let model = {
+            let mut state = self.engine.resource_manager.state();
+            let models = &mut state.containers_mut().models;
+            // The prefab could be overwritten, make sure the instance gets its new content.
+            match models.find(&relative_path).cloned() {
+                Some(model) => {
+                    models.reload_resource(model.clone());
+                    model
+                }
+                None => models.request(&relative_path),
+            }
+        };
+
+        let model = match block_on(model) {
+            Ok(model) => model,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to instantiate prefab {:?}! Reason: {:?}",
+                    relative_path, e
+                ));
+                return;
+            }
+        };
+
+        let scene = &mut self.engine.scenes[editor_scene.scene];
+        // Instance root is a copy of the root of the prefab's scene, which has identity transform,
+        // and the copies of the selected nodes keep their local transforms.
+        let instance = model.instantiate(scene);
+        // Extract the instance from the scene, only commands allowed to modify the scene.
+        let sub_graph = scene.graph.take_reserve_sub_graph(instance);
+
+        let mut group = vec![SceneCommand::new(ChangeSelectionCommand::new(
+            Default::default(),
+            editor_scene.selection.clone(),
+        ))];
+        group.extend(
+            root_nodes
+                .into_iter()
+                .map(|n| SceneCommand::new(DeleteSubGraphCommand::new(n))),
+        );
+        group.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+        group.push(SceneCommand::new(LinkNodesCommand::new(instance, parent)));
+        group.push(SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::single_or_empty(instance)),
+            Default::default(),
+        )));
+
+        self.message_sender
+            .send(Message::do_scene_command(CommandGroup::from(group)))
+            .unwrap();
+
+        Log::info(format!(
+            "Selection was replaced with an instance of {:?} prefab.",
+            relative_path
+        ));
+    }
+
+    fn try_instantiate_at_selection(&mut self, path: PathBuf, placement: InstancePlacement) {
+        let editor_scene = match self.scene.as_ref() {
+            Some(editor_scene) => editor_scene,
+            None => {
+                Log::warn("Unable to instantiate a model, because there is no scene loaded!");
+                return;
+            }
+        };
+
+        let graph = &self.engine.scenes[editor_scene.scene].graph;
+        let targets = if let Selection::Graph(ref graph_selection) = editor_scene.selection {
+            // Deletion of a node deletes its descendants with their instances, so only top-most
+            // nodes could be replaced.
+            let nodes = if placement == InstancePlacement::Replace {
+                graph_selection.root_nodes(graph)
+            } else {
+                graph_selection.nodes().to_vec()
+            };
+            nodes
+                .into_iter()
+                .filter(|&n| n != graph.get_root())
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        if targets.is_empty() {
+            Log::warn("Unable to instantiate a model, because no scene nodes are selected!");
+            return;
+        }
+
+        let relative_path = match make_relative_path(&path) {
+            Ok(relative_path) => relative_path,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to instantiate {:?}, because it is not inside the working directory! \
+                    Reason: {:?}",
+                    path, e
+                ));
+                return;
+            }
+        };
+
+        let model = match block_on(self.engine.resource_manager.request_model(&relative_path)) {
+            Ok(model) => model,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to instantiate {:?}! Reason: {:?}",
+                    relative_path, e
+                ));
+                return;
+            }
+        };
+
+        let scene = &mut self.engine.scenes[editor_scene.scene];
+
+        let mut group = vec![SceneCommand::new(ChangeSelectionCommand::new(
+            Default::default(),
+            editor_scene.selection.clone(),
+        ))];
+        let mut instances = Vec::with_capacity(targets.len());
+
+        for &target in targets.iter() {
+            let instance = model.instantiate(scene);
+
+            let parent = if placement == InstancePlacement::Child {
+                target
+            } else {
+                let target_ref = &scene.graph[target];
+                let position = **target_ref.local_transform().position();
+                let rotation = **target_ref.local_transform().rotation();
+                let scale = **target_ref.local_transform().scale();
+                scene.graph[instance]
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation)
+                    .set_scale(scale);
+                scene.graph[target].parent()
+            };
+
+            // Extract the instance from the scene, only commands allowed to modify the scene.
+            let sub_graph = scene.graph.take_reserve_sub_graph(instance);
+            group.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+            group.push(SceneCommand::new(LinkNodesCommand::new(instance, parent)));
+
+            instances.push(instance);
+        }
+
+        if placement == InstancePlacement::Replace {
+            group.extend(
+                targets
+                    .iter()
+                    .map(|&n| SceneCommand::new(DeleteSubGraphCommand::new(n))),
+            );
+        }
+
+        group.push(SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::from_list(instances)),
+            Default::default(),
+        )));
+
+        self.message_sender
+            .send(Message::do_scene_command(CommandGroup::from(group)))
+            .unwrap();
+
+        Log::info(format!(
+            "{} instances of {:?} were created.",
+            targets.len(),
+            relative_path
+        ));
+    }
+
+    /// Copies selected nodes (with all their descendants) to a new scene and saves it to the given
+    /// path. The current scene stays untouched. Returns `true` if the file was saved.
+    fn save_selection_to_file(&self, path: &Path, kind: &str, copy_scene_settings: bool) -> bool {
         if let Some(editor_scene) = self.scene.as_ref() {
             let source_scene = &self.engine.scenes[editor_scene.scene];
             let mut dest_scene = Scene::new();
+            if copy_scene_settings {
+                dest_scene.ambient_lighting_color = source_scene.ambient_lighting_color;
+            }
             if let Selection::Graph(ref graph_selection) = editor_scene.selection {
                 for root_node in graph_selection.root_nodes(&source_scene.graph) {
                     source_scene
@@ -1999,31 +3041,37 @@ impl Editor {
                 let mut visitor = Visitor::new();
                 match dest_scene.save("Scene", &mut visitor) {
                     Err(e) => Log::err(format!(
-                        "Failed to save selection as prefab! Reason: {:?}",
-                        e
+                        "Failed to save selection as {}! Reason: {:?}",
+                        kind, e
                     )),
                     Ok(_) => {
-                        if let Err(e) = visitor.save_binary(&path) {
+                        if let Err(e) = visitor.save_binary(path) {
                             Log::err(format!(
-                                "Failed to save selection as prefab! Reason: {:?}",
-                                e
+                                "Failed to save selection as {}! Reason: {:?}",
+                                kind, e
                             ));
                         } else {
                             Log::info(format!(
-                                "Selection was successfully saved as prefab to {:?}!",
-                                path
-                            ))
+                                "Selection was successfully saved as {} to {:?}!",
+                                kind, path
+                            ));
+                            return true;
                         }
                     }
                 }
             } else {
-                Log::warn(
-                    "Unable to selection to prefab, because selection is not scene selection!",
-                );
+                Log::warn(format!(
+                    "Unable to save selection as {}, because selection is not scene selection!",
+                    kind
+                ));
             }
         } else {
-            Log::warn("Unable to save selection to prefab, because there is no scene loaded!");
+            Log::warn(format!(
+                "Unable to save selection as {}, because there is no scene loaded!",
+                kind
+            ));
         }
+        false
     }
 
     pub fn add_game_plugin<P>(&mut self, plugin: P)
@@ -2128,7 +3176,16 @@ impl Editor {
                 }
             }
             Event::LoopDestroyed => {
+                // Do not save the layout of immersive mode, it is temporary.
+                self.settings.windows.layout = self.immersive_layout.clone().or_else(|| {
+                    self.engine
+                        .user_interface
+                        .node(self.docking_manager)
+                        .cast::<DockingManager>()
+                        .map(|docking_manager| docking_manager.layout(&self.engine.user_interface))
+                });
                 Log::verify(self.settings.save());
+                self.save_scene_sidecar();
             }
             _ => *control_flow = ControlFlow::Poll,
         });
@@ -2151,8 +3208,32 @@ fn update(editor: &mut Editor, control_flow: &mut ControlFlow) {
     editor.game_loop_data.clock = Instant::now();
     editor.game_loop_data.lag += elapsed;
 
-    while editor.game_loop_data.lag >= FIXED_TIMESTEP {
-        editor.game_loop_data.lag -= FIXED_TIMESTEP;
+    // Read the time step each frame, so changes in the settings are applied immediately.
+    let fixed_timestep = editor.settings.general.fixed_timestep();
+    let max_steps = editor.settings.general.max_catch_up_steps.max(1);
+
+    let mut steps = 0;
+    let mut overloaded = false;
+    while editor.game_loop_data.lag >= fixed_timestep {
+        // Spiral of death guard: if updates take longer than the time step, catching up would
+        // make every next frame longer. Drop the backlog (keeping the fraction of the step, so
+        // the update rate stays stable) and let the editor slow down instead.
+        if steps >= max_steps {
+            let dropped_steps = (editor.game_loop_data.lag / fixed_timestep) as u32;
+            if !editor.game_loop_data.overloaded {
+                Log::warn(format!(
+                    "Editor is overloaded, {} update step(s) ({:.2} s) were dropped.",
+                    dropped_steps,
+                    dropped_steps as f32 * fixed_timestep
+                ));
+            }
+            editor.game_loop_data.lag %= fixed_timestep;
+            overloaded = true;
+            break;
+        }
+
+        editor.game_loop_data.lag -= fixed_timestep;
+        steps += 1;
 
         let mut switches = FxHashMap::default();
         if let Some(scene) = editor.scene.as_ref() {
@@ -2160,22 +3241,19 @@ fn update(editor: &mut Editor, control_flow: &mut ControlFlow) {
         }
 
         editor.engine.pre_update(
-            FIXED_TIMESTEP,
+            fixed_timestep,
             control_flow,
             &mut editor.game_loop_data.lag,
             switches,
         );
 
-        editor.update(FIXED_TIMESTEP);
+        editor.update(fixed_timestep);
 
-        editor.engine.post_update(FIXED_TIMESTEP);
+        editor.engine.post_update(fixed_timestep);
 
         editor.post_update();
-
-        if editor.game_loop_data.lag >= 1.5 * FIXED_TIMESTEP {
-            break;
-        }
     }
+    editor.game_loop_data.overloaded = overloaded;
 
     let window = editor.engine.get_window();
     window.set_cursor_icon(translate_cursor_icon(editor.engine.user_interface.cursor()));
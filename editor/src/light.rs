@@ -1,24 +1,34 @@
-use crate::{scene::EditorScene, GameEngine};
+use crate::{
+    scene::{EditorScene, Selection},
+    send_sync_message, GameEngine,
+};
 use fyrox::{
     core::{pool::Handle, scope_profile},
     gui::{
         button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
-        Thickness, UiNode, VerticalAlignment,
+        Thickness, UiNode, UserInterface, VerticalAlignment,
     },
-    utils::lightmap::Lightmap,
+    scene::{light::BaseLight, mesh::Mesh, node::Node},
+    utils::{lightmap::Lightmap, log::Log, uvgen},
 };
+use std::collections::HashMap;
 
 pub struct LightPanel {
     pub window: Handle<UiNode>,
     nud_texels_per_unit: Handle<UiNode>,
     nud_spacing: Handle<UiNode>,
     generate: Handle<UiNode>,
+    solo: Handle<UiNode>,
+    generate_uvs: Handle<UiNode>,
+    uv_report: Handle<UiNode>,
     texels_per_unit: u32,
     spacing: f32,
 }
@@ -26,6 +36,9 @@ pub struct LightPanel {
 impl LightPanel {
     pub fn new(engine: &mut GameEngine) -> Self {
         let generate;
+        let solo;
+        let generate_uvs;
+        let uv_report;
         let nud_texels_per_unit;
         let nud_spacing;
         let ctx = &mut engine.user_interface.build_ctx();
@@ -95,6 +108,49 @@ impl LightPanel {
                             .with_text("Generate Lightmap")
                             .build(ctx);
                             generate
+                        })
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .on_column(0)
+                                    .with_vertical_alignment(VerticalAlignment::Center),
+                            )
+                            .with_text("Solo Selected")
+                            .build(ctx),
+                        )
+                        .with_child({
+                            solo = CheckBoxBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .checked(Some(false))
+                            .build(ctx);
+                            solo
+                        })
+                        .with_child({
+                            generate_uvs = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(4)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Generate Second UVs")
+                            .build(ctx);
+                            generate_uvs
+                        })
+                        .with_child({
+                            uv_report = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(5)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            uv_report
                         }),
                 )
                 .add_column(Column::strict(100.0))
@@ -102,6 +158,8 @@ impl LightPanel {
                 .add_row(Row::strict(25.0))
                 .add_row(Row::strict(25.0))
                 .add_row(Row::strict(25.0))
+                .add_row(Row::strict(25.0))
+                .add_row(Row::strict(25.0))
                 .add_row(Row::stretch())
                 .build(ctx),
             )
@@ -110,6 +168,9 @@ impl LightPanel {
         Self {
             window,
             generate,
+            solo,
+            generate_uvs,
+            uv_report,
             nud_texels_per_unit,
             texels_per_unit: 128,
             nud_spacing,
@@ -120,7 +181,7 @@ impl LightPanel {
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
-        editor_scene: &EditorScene,
+        editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
     ) {
         scope_profile!();
@@ -140,6 +201,13 @@ impl LightPanel {
                     .save("./", engine.resource_manager.clone())
                     .unwrap();
                 scene.set_lightmap(lightmap).unwrap();
+            } else if message.destination() == self.generate_uvs {
+                let report = self.generate_second_uvs(editor_scene, engine);
+                engine.user_interface.send_message(TextMessage::text(
+                    self.uv_report,
+                    MessageDirection::ToWidget,
+                    report,
+                ));
             }
         } else if let Some(&NumericUpDownMessage::Value(value)) =
             message.data::<NumericUpDownMessage<f32>>()
@@ -151,6 +219,124 @@ impl LightPanel {
                     self.spacing = value;
                 }
             }
+        } else if let Some(&CheckBoxMessage::Check(Some(value))) = message.data::<CheckBoxMessage>()
+        {
+            if message.destination() == self.solo
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let graph = &mut engine.scenes[editor_scene.scene].graph;
+                if value {
+                    let lights = if let Selection::Graph(selection) = &editor_scene.selection {
+                        selection
+                            .nodes()
+                            .iter()
+                            .filter(|&&n| {
+                                graph.try_get(n).map_or(false, |n| {
+                                    n.query_component_ref::<BaseLight>().is_some()
+                                })
+                            })
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    } else {
+                        Default::default()
+                    };
+
+                    if lights.is_empty() {
+                        Log::warn("Select at least one light to solo it!");
+                    }
+
+                    editor_scene.set_soloed_lights(&lights, graph);
+                } else {
+                    editor_scene.clear_light_solo(graph);
+                }
+
+                self.sync_to_model(editor_scene, &engine.user_interface);
+            }
+        }
+    }
+
+    /// Generates second texture coordinates for every surface of selected meshes (or of every
+    /// mesh in the scene, if there are no selected meshes) and returns a short report about
+    /// quality of generated UV maps.
+    fn generate_second_uvs(&self, editor_scene: &EditorScene, engine: &GameEngine) -> String {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        let is_mesh = |handle: Handle<Node>| {
+            graph
+                .try_get(handle)
+                .map_or(false, |node| node.cast::<Mesh>().is_some())
+        };
+
+        let mut meshes = if let Selection::Graph(selection) = &editor_scene.selection {
+            selection
+                .nodes()
+                .iter()
+                .cloned()
+                .filter(|&handle| is_mesh(handle))
+                .collect::<Vec<_>>()
+        } else {
+            Default::default()
+        };
+        if meshes.is_empty() {
+            meshes = graph
+                .pair_iter()
+                .map(|(handle, _)| handle)
+                .filter(|&handle| is_mesh(handle))
+                .collect();
+        }
+
+        // Surface data could be shared across multiple surfaces, generate UVs only once for it.
+        let mut unique_data_set = HashMap::new();
+        for handle in meshes {
+            for surface in graph[handle].cast::<Mesh>().unwrap().surfaces() {
+                let data = surface.data();
+                unique_data_set.entry(data.key()).or_insert(data);
+            }
+        }
+
+        if unique_data_set.is_empty() {
+            return "There are no meshes to generate UVs for.".to_string();
+        }
+
+        let mut count = 0;
+        let mut total_efficiency = 0.0;
+        let mut max_overlap = 0.0f32;
+        for data in unique_data_set.values() {
+            let mut data = data.lock();
+            let result = uvgen::generate_uvs(&mut data, self.spacing)
+                .and_then(|_| uvgen::analyze_uvs(&data));
+            match result {
+                Ok(stats) => {
+                    count += 1;
+                    total_efficiency += stats.packing_efficiency;
+                    max_overlap = max_overlap.max(stats.overlap);
+                }
+                Err(e) => Log::err(format!("Failed to generate second UVs. Reason: {:?}", e)),
+            }
+        }
+
+        if count == 0 {
+            return "Failed to generate UVs, see the log for details.".to_string();
         }
+
+        let report = format!(
+            "Generated UVs for {} surface(s).\nAverage packing efficiency: {:.1}%\nMax overlap: {:.1}%",
+            count,
+            100.0 * total_efficiency / count as f32,
+            100.0 * max_overlap
+        );
+        Log::info(report.replace('\n', " "));
+        report
+    }
+
+    pub fn sync_to_model(&self, editor_scene: &EditorScene, ui: &UserInterface) {
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.solo,
+                MessageDirection::ToWidget,
+                Some(!editor_scene.soloed_lights.is_empty()),
+            ),
+        );
     }
 }
@@ -0,0 +1,70 @@
+//! Lightweight localization of editor UI strings.
+//!
+//! Translations are stored in `lang/<language>.ron` files (relative to the working directory of
+//! the editor), each file is a simple map of string ids to translated strings:
+//!
+//! ```text
+//! {
+//!     "Save Scene As...": "Enregistrer la scène sous...",
+//!     "Unsaved changes": "Modifications non enregistrées",
+//! }
+//! ```
+//!
+//! Original English strings are used as ids, so any missing translation falls back to the
+//! English text.
+
+use fyrox::{core::parking_lot::RwLock, fxhash::FxHashMap};
+use std::{fs::File, path::PathBuf};
+
+/// Language that does not need a string table, since the ids are already in it.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+lazy_static! {
+    static ref STRING_TABLE: RwLock<FxHashMap<String, String>> = Default::default();
+}
+
+#[derive(Debug)]
+pub enum LocalizationError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl From<std::io::Error> for LocalizationError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for LocalizationError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::Ron(e)
+    }
+}
+
+pub fn language_file_path(language: &str) -> PathBuf {
+    PathBuf::from("lang").join(format!("{}.ron", language))
+}
+
+/// Replaces current string table with the table of the given language. Default language (or an
+/// empty string) just clears the table.
+pub fn load_language(language: &str) -> Result<(), LocalizationError> {
+    let table = if language.is_empty() || language == DEFAULT_LANGUAGE {
+        Default::default()
+    } else {
+        let file = File::open(language_file_path(language))?;
+        ron::de::from_reader(file)?
+    };
+
+    *STRING_TABLE.write() = table;
+
+    Ok(())
+}
+
+/// Returns translated string for the given id, or the id itself if there's no translation.
+pub fn tr(id: &str) -> String {
+    STRING_TABLE
+        .read()
+        .get(id)
+        .cloned()
+        .unwrap_or_else(|| id.to_owned())
+}
@@ -6,6 +6,7 @@ use fyrox::{
         button::{ButtonBuilder, ButtonMessage},
         copypasta::ClipboardProvider,
         dropdown_list::DropdownListMessage,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage},
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         list_view::{ListView, ListViewBuilder, ListViewMessage},
@@ -16,12 +17,17 @@ use fyrox::{
         stack_panel::StackPanelBuilder,
         text::{Text, TextBuilder},
         widget::WidgetBuilder,
-        window::{WindowBuilder, WindowTitle},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode,
     },
-    utils::log::{LogMessage, MessageKind},
+    utils::log::{Log, LogMessage, MessageKind},
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::Receiver,
 };
-use std::sync::mpsc::Receiver;
 
 struct ContextMenu {
     menu: Handle<UiNode>,
@@ -77,6 +83,10 @@ pub struct LogPanel {
     pub window: Handle<UiNode>,
     messages: Handle<UiNode>,
     clear: Handle<UiNode>,
+    save: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    // All received messages regardless of their severity, it is used to save the log to a file.
+    history: Vec<String>,
     receiver: Receiver<LogMessage>,
     severity: MessageKind,
     severity_list: Handle<UiNode>,
@@ -87,8 +97,9 @@ impl LogPanel {
     pub fn new(ctx: &mut BuildContext, message_receiver: Receiver<LogMessage>) -> Self {
         let messages;
         let clear;
+        let save;
         let severity_list;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("LogPanel"))
             .can_minimize(false)
             .with_title(WindowTitle::Text("Message Log".to_owned()))
             .with_content(
@@ -110,6 +121,16 @@ impl LogPanel {
                                         .build(ctx);
                                         clear
                                     })
+                                    .with_child({
+                                        save = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(120.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Save Log...")
+                                        .build(ctx);
+                                        save
+                                    })
                                     .with_child({
                                         severity_list = DropdownListBuilder::new(
                                             WidgetBuilder::new()
@@ -158,10 +179,24 @@ impl LogPanel {
 
         let context_menu = ContextMenu::new(ctx);
 
+        let save_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::text("Save Log As"))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("fyroxed.log"),
+        })
+        .with_path("./")
+        .build(ctx);
+
         Self {
             window,
             messages,
             clear,
+            save,
+            save_file_selector,
+            history: Default::default(),
             receiver: message_receiver,
             severity: MessageKind::Warning,
             severity_list,
@@ -174,11 +209,38 @@ impl LogPanel {
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.clear {
+                self.history.clear();
                 engine.user_interface.send_message(ListViewMessage::items(
                     self.messages,
                     MessageDirection::ToWidget,
                     vec![],
                 ));
+            } else if message.destination() == self.save {
+                engine
+                    .user_interface
+                    .send_message(WindowMessage::open_modal(
+                        self.save_file_selector,
+                        MessageDirection::ToWidget,
+                        true,
+                    ));
+                engine
+                    .user_interface
+                    .send_message(FileSelectorMessage::root(
+                        self.save_file_selector,
+                        MessageDirection::ToWidget,
+                        Some(std::env::current_dir().unwrap()),
+                    ));
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.save_file_selector {
+                match self.save(path) {
+                    Ok(_) => Log::info(format!("Log was saved to {}", path.display())),
+                    Err(e) => Log::err(format!(
+                        "Unable to save log to {}. Reason: {:?}",
+                        path.display(),
+                        e
+                    )),
+                }
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(idx))) =
             message.data::<DropdownListMessage>()
@@ -198,6 +260,22 @@ impl LogPanel {
         self.context_menu.handle_ui_message(message, engine);
     }
 
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        writeln!(file, "Fyroxed {}", env!("CARGO_PKG_VERSION"))?;
+        if let Ok(working_directory) = std::env::current_dir() {
+            writeln!(file, "Working directory: {}", working_directory.display())?;
+        }
+        writeln!(file)?;
+
+        for line in self.history.iter() {
+            writeln!(file, "{}", line)?;
+        }
+
+        file.flush()
+    }
+
     pub fn update(&mut self, engine: &mut GameEngine) {
         let mut count = engine
             .user_interface
@@ -209,6 +287,13 @@ impl LogPanel {
         let mut item_to_bring_into_view = Handle::NONE;
 
         while let Ok(msg) = self.receiver.try_recv() {
+            self.history.push(format!(
+                "[{:.2}s] {}{}",
+                msg.time.as_secs_f32(),
+                msg.kind.as_str(),
+                msg.content
+            ));
+
             if msg.kind < self.severity {
                 continue;
             }
@@ -2,7 +2,10 @@ use crate::{
     asset::item::AssetItem,
     gui::make_dropdown_list_option,
     preview::PreviewPanel,
-    scene::commands::material::{SetMaterialPropertyValueCommand, SetMaterialShaderCommand},
+    scene::commands::material::{
+        RelinkSurfaceMaterialCommand, SetMaterialCommand, SetMaterialCullModeCommand,
+        SetMaterialPropertyValueCommand, SetMaterialShaderCommand,
+    },
     send_sync_message, GameEngine, Message,
 };
 use fyrox::{
@@ -18,12 +21,14 @@ use fyrox::{
     engine::resource_manager::ResourceManager,
     gui::{
         border::BorderBuilder,
-        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        button::{ButtonBuilder, ButtonContent, ButtonMessage},
+        check_box::{CheckBox, CheckBoxBuilder, CheckBoxMessage},
         color::{ColorFieldBuilder, ColorFieldMessage},
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         grid::{Column, GridBuilder, Row},
         image::{Image, ImageBuilder, ImageMessage},
-        list_view::{ListViewBuilder, ListViewMessage},
+        list_view::{ListView, ListViewBuilder, ListViewMessage},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::{MessageDirection, UiMessage},
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
@@ -31,16 +36,17 @@ use fyrox::{
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
+        utils::make_simple_tooltip,
         vec::{
             vec2::{Vec2EditorBuilder, Vec2EditorMessage},
             vec3::{Vec3EditorBuilder, Vec3EditorMessage},
             vec4::{Vec4EditorBuilder, Vec4EditorMessage},
         },
         widget::{WidgetBuilder, WidgetMessage},
-        window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
-    material::{shader::Shader, Material, PropertyValue, SharedMaterial},
+    material::{shader::Shader, CullMode, Material, PropertyValue, SharedMaterial},
     resource::texture::TextureState,
     scene::{
         base::BaseBuilder,
@@ -48,10 +54,14 @@ use fyrox::{
             surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
             MeshBuilder,
         },
+        Scene,
     },
-    utils::into_gui_texture,
+    utils::{into_gui_texture, log::Log},
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
 };
-use std::sync::mpsc::Sender;
 
 struct TextureContextMenu {
     popup: Handle<UiNode>,
@@ -82,6 +92,87 @@ impl TextureContextMenu {
     }
 }
 
+/// Controls of shader time of the preview scene, they allow to see materials that are animated
+/// by `fyrox_time` built-in uniform. The time is a property of the preview scene only, so it does
+/// not affect the material itself.
+struct ShaderTimeControls {
+    play: Handle<UiNode>,
+    speed: Handle<UiNode>,
+    reset: Handle<UiNode>,
+    playing: bool,
+    time_scale: f32,
+}
+
+impl ShaderTimeControls {
+    fn new(preview: &PreviewPanel, engine: &mut GameEngine) -> Self {
+        engine.scenes[preview.scene()].shader_time_scale = 0.0;
+
+        let ctx = &mut engine.user_interface.build_ctx();
+
+        let play = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_width(50.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Play")
+        .build(ctx);
+        let speed = NumericUpDownBuilder::new(
+            WidgetBuilder::new()
+                .with_width(60.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(ctx, "Speed of shader time.")),
+        )
+        .with_value(1.0)
+        .with_min_value(0.0)
+        .with_step(0.1)
+        .build(ctx);
+        let reset = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(ctx, "Reset shader time to zero.")),
+        )
+        .with_text("Reset Time")
+        .build(ctx);
+
+        for control in [play, speed, reset] {
+            ctx.link(control, preview.tools_panel);
+        }
+
+        Self {
+            play,
+            speed,
+            reset,
+            playing: false,
+            time_scale: 1.0,
+        }
+    }
+
+    fn handle_ui_message(&mut self, message: &UiMessage, scene: &mut Scene, ui: &UserInterface) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.play {
+                self.playing = !self.playing;
+                ui.send_message(ButtonMessage::content(
+                    self.play,
+                    MessageDirection::ToWidget,
+                    ButtonContent::text(if self.playing { "Pause" } else { "Play" }),
+                ));
+            } else if message.destination() == self.reset {
+                scene.shader_time = 0.0;
+            }
+        } else if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.speed
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.time_scale = value;
+            }
+        }
+
+        scene.shader_time_scale = if self.playing { self.time_scale } else { 0.0 };
+    }
+}
+
 pub struct MaterialEditor {
     pub window: Handle<UiNode>,
     properties_panel: Handle<UiNode>,
@@ -90,7 +181,24 @@ pub struct MaterialEditor {
     material: Option<SharedMaterial>,
     available_shaders: Handle<UiNode>,
     shaders_list: Vec<Shader>,
+    cull_mode: Handle<UiNode>,
+    save_as_asset: Handle<UiNode>,
+    relink_surface: Handle<UiNode>,
+    load_from_asset: Handle<UiNode>,
+    save_file_selector: Handle<UiNode>,
+    load_file_selector: Handle<UiNode>,
     texture_context_menu: TextureContextMenu,
+    shader_time_controls: ShaderTimeControls,
+}
+
+fn make_material_file_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            ext.to_string_lossy().as_ref() == "material"
+        } else {
+            p.is_dir()
+        }
+    })
 }
 
 fn create_item_container(
@@ -192,15 +300,70 @@ fn create_vec4_view(ctx: &mut BuildContext, value: Vector4<f32>) -> Handle<UiNod
         .build(ctx)
 }
 
-fn sync_array<T, B>(
+fn sync_float_view(ui: &UserInterface, handle: Handle<UiNode>, value: f32) {
+    send_sync_message(
+        ui,
+        NumericUpDownMessage::value(handle, MessageDirection::ToWidget, value),
+    );
+}
+
+fn sync_int_view(ui: &UserInterface, handle: Handle<UiNode>, value: i32) {
+    sync_float_view(ui, handle, value as f32)
+}
+
+fn sync_uint_view(ui: &UserInterface, handle: Handle<UiNode>, value: u32) {
+    sync_float_view(ui, handle, value as f32)
+}
+
+fn sync_vec2_view(ui: &UserInterface, handle: Handle<UiNode>, value: Vector2<f32>) {
+    send_sync_message(
+        ui,
+        Vec2EditorMessage::value(handle, MessageDirection::ToWidget, value),
+    );
+}
+
+fn sync_vec3_view(ui: &UserInterface, handle: Handle<UiNode>, value: Vector3<f32>) {
+    send_sync_message(
+        ui,
+        Vec3EditorMessage::value(handle, MessageDirection::ToWidget, value),
+    );
+}
+
+fn sync_vec4_view(ui: &UserInterface, handle: Handle<UiNode>, value: Vector4<f32>) {
+    send_sync_message(
+        ui,
+        Vec4EditorMessage::value(handle, MessageDirection::ToWidget, value),
+    );
+}
+
+fn list_items(ui: &UserInterface, handle: Handle<UiNode>) -> Vec<Handle<UiNode>> {
+    ui.node(handle)
+        .cast::<ListView>()
+        .map(|list| list.items().to_vec())
+        .unwrap_or_default()
+}
+
+/// Syncs array view with the array. Views of elements are updated in-place if the size of the
+/// array hasn't changed, so an element that is being edited (dragged) keeps its view.
+fn sync_array<T, B, S>(
     ui: &mut UserInterface,
     handle: Handle<UiNode>,
     array: &[T],
     mut item_builder: B,
+    mut item_syncer: S,
 ) where
     T: Clone,
     B: FnMut(&mut BuildContext, T) -> Handle<UiNode>,
+    S: FnMut(&UserInterface, Handle<UiNode>, T),
 {
+    let items = list_items(ui, handle);
+    if items.len() == array.len() {
+        for (item, value) in items.into_iter().zip(array.iter()) {
+            item_syncer(ui, item, value.clone());
+        }
+        return;
+    }
+
     let ctx = &mut ui.build_ctx();
 
     let new_items = array.iter().map(|v| item_builder(ctx, v.clone())).collect();
@@ -211,16 +374,26 @@ fn sync_array<T, B>(
     );
 }
 
-fn sync_array_of_arrays<'a, T, I, B>(
+fn sync_array_of_arrays<'a, T, I, B, S>(
     ui: &mut UserInterface,
     handle: Handle<UiNode>,
     array: I,
     mut item_builder: B,
+    mut item_syncer: S,
 ) where
     T: 'a + Clone,
-    I: Iterator<Item = &'a [T]>,
+    I: Iterator<Item = &'a [T]> + Clone,
     B: FnMut(&mut BuildContext, T) -> Handle<UiNode>,
+    S: FnMut(&UserInterface, Handle<UiNode>, T),
 {
+    let items = list_items(ui, handle);
+    if items.len() == array.clone().count() {
+        for (item, value) in items.into_iter().zip(array) {
+            sync_array(ui, item, value, &mut item_builder, &mut item_syncer);
+        }
+        return;
+    }
+
     let ctx = &mut ui.build_ctx();
 
     let new_items = array
@@ -233,6 +406,78 @@ fn sync_array_of_arrays<'a, T, I, B>(
     );
 }
 
+/// A new value of an element of an array property (or of a matrix property).
+#[derive(Copy, Clone, Debug)]
+enum ArrayElementValue {
+    Number(f32),
+    Vector2(Vector2<f32>),
+    Vector3(Vector3<f32>),
+    Vector4(Vector4<f32>),
+}
+
+impl ArrayElementValue {
+    fn from_message(message: &UiMessage) -> Option<Self> {
+        if message.direction() != MessageDirection::FromWidget {
+            return None;
+        }
+
+        if let Some(NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            Some(Self::Number(*value))
+        } else if let Some(Vec2EditorMessage::Value(value)) =
+            message.data::<Vec2EditorMessage<f32>>()
+        {
+            Some(Self::Vector2(*value))
+        } else if let Some(Vec3EditorMessage::Value(value)) =
+            message.data::<Vec3EditorMessage<f32>>()
+        {
+            Some(Self::Vector3(*value))
+        } else if let Some(Vec4EditorMessage::Value(value)) =
+            message.data::<Vec4EditorMessage<f32>>()
+        {
+            Some(Self::Vector4(*value))
+        } else {
+            None
+        }
+    }
+}
+
+/// Makes a copy of the property value with one element replaced. `outer` is the index of a matrix
+/// in matrix arrays, `index` is the index of an element in an array (or in a matrix).
+fn with_array_element(
+    value: &PropertyValue,
+    outer: Option<usize>,
+    index: usize,
+    element: ArrayElementValue,
+) -> Option<PropertyValue> {
+    use ArrayElementValue as E;
+
+    let mut value = value.clone();
+    match (&mut value, outer, element) {
+        (PropertyValue::FloatArray(array), None, E::Number(n)) => *array.get_mut(index)? = n,
+        (PropertyValue::IntArray(array), None, E::Number(n)) => *array.get_mut(index)? = n as i32,
+        (PropertyValue::UIntArray(array), None, E::Number(n)) => *array.get_mut(index)? = n as u32,
+        (PropertyValue::Vector2Array(array), None, E::Vector2(v)) => *array.get_mut(index)? = v,
+        (PropertyValue::Vector3Array(array), None, E::Vector3(v)) => *array.get_mut(index)? = v,
+        (PropertyValue::Vector4Array(array), None, E::Vector4(v)) => *array.get_mut(index)? = v,
+        (PropertyValue::Matrix2(m), None, E::Number(n)) => *m.as_mut_slice().get_mut(index)? = n,
+        (PropertyValue::Matrix3(m), None, E::Number(n)) => *m.as_mut_slice().get_mut(index)? = n,
+        (PropertyValue::Matrix4(m), None, E::Number(n)) => *m.as_mut_slice().get_mut(index)? = n,
+        (PropertyValue::Matrix2Array(array), Some(outer), E::Number(n)) => {
+            *array.get_mut(outer)?.as_mut_slice().get_mut(index)? = n
+        }
+        (PropertyValue::Matrix3Array(array), Some(outer), E::Number(n)) => {
+            *array.get_mut(outer)?.as_mut_slice().get_mut(index)? = n
+        }
+        (PropertyValue::Matrix4Array(array), Some(outer), E::Number(n)) => {
+            *array.get_mut(outer)?.as_mut_slice().get_mut(index)? = n
+        }
+        _ => return None,
+    }
+    Some(value)
+}
+
 impl MaterialEditor {
     pub fn new(engine: &mut GameEngine) -> Self {
         let mut preview = PreviewPanel::new(engine, 300, 400);
@@ -246,11 +491,17 @@ impl MaterialEditor {
             .build(graph);
         preview.set_model(sphere, engine);
 
+        let shader_time_controls = ShaderTimeControls::new(&preview, engine);
+
         let ctx = &mut engine.user_interface.build_ctx();
 
         let panel;
         let properties_panel;
         let available_shaders;
+        let cull_mode;
+        let save_as_asset;
+        let relink_surface;
+        let load_from_asset;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0))
             .open(false)
             .with_title(WindowTitle::text("Material Editor"))
@@ -276,11 +527,94 @@ impl MaterialEditor {
                                         .with_close_on_selection(true)
                                         .build(ctx);
                                         available_shaders
-                                    }),
+                                    })
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new().on_row(1).on_column(0),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text("Cull Mode")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        cull_mode = DropdownListBuilder::new(
+                                            WidgetBuilder::new().on_row(1).on_column(1),
+                                        )
+                                        .with_items(
+                                            CullMode::ALL
+                                                .iter()
+                                                .map(|mode| {
+                                                    make_dropdown_list_option(
+                                                        ctx,
+                                                        &format!("{:?}", mode),
+                                                    )
+                                                })
+                                                .collect(),
+                                        )
+                                        .with_selected(0)
+                                        .with_close_on_selection(true)
+                                        .build(ctx);
+                                        cull_mode
+                                    })
+                                    .with_child({
+                                        relink_surface = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(2)
+                                                .on_column(0)
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Makes every surface that uses the edited \
+                                                    material use the saved asset instead.",
+                                                )),
+                                        )
+                                        .with_content(
+                                            TextBuilder::new(WidgetBuilder::new())
+                                                .with_vertical_text_alignment(
+                                                    VerticalAlignment::Center,
+                                                )
+                                                .with_text("Relink Surface On Save")
+                                                .build(ctx),
+                                        )
+                                        .checked(Some(false))
+                                        .build(ctx);
+                                        relink_surface
+                                    })
+                                    .with_child(
+                                        StackPanelBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(2)
+                                                .on_column(1)
+                                                .with_child({
+                                                    save_as_asset = ButtonBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_width(120.0)
+                                                            .with_margin(Thickness::uniform(1.0)),
+                                                    )
+                                                    .with_text("Save As Asset...")
+                                                    .build(ctx);
+                                                    save_as_asset
+                                                })
+                                                .with_child({
+                                                    load_from_asset = ButtonBuilder::new(
+                                                        WidgetBuilder::new()
+                                                            .with_width(120.0)
+                                                            .with_margin(Thickness::uniform(1.0)),
+                                                    )
+                                                    .with_text("Load From Asset...")
+                                                    .build(ctx);
+                                                    load_from_asset
+                                                }),
+                                        )
+                                        .with_orientation(Orientation::Horizontal)
+                                        .build(ctx),
+                                    ),
                             )
                             .add_column(Column::strict(150.0))
                             .add_column(Column::stretch())
                             .add_row(Row::strict(25.0))
+                            .add_row(Row::strict(25.0))
+                            .add_row(Row::strict(25.0))
                             .build(ctx),
                         )
                         .with_child(
@@ -298,7 +632,7 @@ impl MaterialEditor {
                             panel
                         }),
                 )
-                .add_row(Row::strict(26.0))
+                .add_row(Row::strict(76.0))
                 .add_row(Row::stretch())
                 .add_row(Row::strict(300.0))
                 .add_column(Column::stretch())
@@ -308,6 +642,28 @@ impl MaterialEditor {
 
         ctx.link(preview.root, panel);
 
+        let save_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::text("Save Material As"))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("unnamed.material"),
+        })
+        .with_path("./")
+        .with_filter(make_material_file_filter())
+        .build(ctx);
+
+        let load_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .with_title(WindowTitle::text("Load Material"))
+                .open(false),
+        )
+        .with_mode(FileBrowserMode::Open)
+        .with_path("./")
+        .with_filter(make_material_file_filter())
+        .build(ctx);
+
         let mut editor = Self {
             texture_context_menu: TextureContextMenu::new(ctx),
             window,
@@ -317,6 +673,13 @@ impl MaterialEditor {
             material: None,
             available_shaders,
             shaders_list: Default::default(),
+            cull_mode,
+            save_as_asset,
+            relink_surface,
+            load_from_asset,
+            save_file_selector,
+            load_file_selector,
+            shader_time_controls,
         };
 
         editor.sync_available_shaders_list(engine.resource_manager.clone());
@@ -521,7 +884,7 @@ impl MaterialEditor {
                         );
                     }
                     PropertyValue::FloatArray(value) => {
-                        sync_array(ui, item, value, create_float_view)
+                        sync_array(ui, item, value, create_float_view, sync_float_view)
                     }
                     PropertyValue::Int(value) => {
                         send_sync_message(
@@ -533,7 +896,9 @@ impl MaterialEditor {
                             ),
                         );
                     }
-                    PropertyValue::IntArray(value) => sync_array(ui, item, value, create_int_view),
+                    PropertyValue::IntArray(value) => {
+                        sync_array(ui, item, value, create_int_view, sync_int_view)
+                    }
                     PropertyValue::UInt(value) => {
                         send_sync_message(
                             ui,
@@ -545,55 +910,70 @@ impl MaterialEditor {
                         );
                     }
                     PropertyValue::UIntArray(value) => {
-                        sync_array(ui, item, value, create_uint_view)
+                        sync_array(ui, item, value, create_uint_view, sync_uint_view)
                     }
                     PropertyValue::Vector2(value) => send_sync_message(
                         ui,
                         Vec2EditorMessage::value(item, MessageDirection::ToWidget, *value),
                     ),
                     PropertyValue::Vector2Array(value) => {
-                        sync_array(ui, item, value, create_vec2_view)
+                        sync_array(ui, item, value, create_vec2_view, sync_vec2_view)
                     }
                     PropertyValue::Vector3(value) => send_sync_message(
                         ui,
                         Vec3EditorMessage::value(item, MessageDirection::ToWidget, *value),
                     ),
                     PropertyValue::Vector3Array(value) => {
-                        sync_array(ui, item, value, create_vec3_view)
+                        sync_array(ui, item, value, create_vec3_view, sync_vec3_view)
                     }
                     PropertyValue::Vector4(value) => send_sync_message(
                         ui,
                         Vec4EditorMessage::value(item, MessageDirection::ToWidget, *value),
                     ),
                     PropertyValue::Vector4Array(value) => {
-                        sync_array(ui, item, value, create_vec4_view)
-                    }
-                    PropertyValue::Matrix2(value) => {
-                        sync_array(ui, item, value.as_slice(), create_float_view)
+                        sync_array(ui, item, value, create_vec4_view, sync_vec4_view)
                     }
+                    PropertyValue::Matrix2(value) => sync_array(
+                        ui,
+                        item,
+                        value.as_slice(),
+                        create_float_view,
+                        sync_float_view,
+                    ),
                     PropertyValue::Matrix2Array(value) => sync_array_of_arrays(
                         ui,
                         item,
                         value.iter().map(|m| m.as_slice()),
                         create_float_view,
+                        sync_float_view,
+                    ),
+                    PropertyValue::Matrix3(value) => sync_array(
+                        ui,
+                        item,
+                        value.as_slice(),
+                        create_float_view,
+                        sync_float_view,
                     ),
-                    PropertyValue::Matrix3(value) => {
-                        sync_array(ui, item, value.as_slice(), create_float_view)
-                    }
                     PropertyValue::Matrix3Array(value) => sync_array_of_arrays(
                         ui,
                         item,
                         value.iter().map(|m| m.as_slice()),
                         create_float_view,
+                        sync_float_view,
+                    ),
+                    PropertyValue::Matrix4(value) => sync_array(
+                        ui,
+                        item,
+                        value.as_slice(),
+                        create_float_view,
+                        sync_float_view,
                     ),
-                    PropertyValue::Matrix4(value) => {
-                        sync_array(ui, item, value.as_slice(), create_float_view)
-                    }
                     PropertyValue::Matrix4Array(value) => sync_array_of_arrays(
                         ui,
                         item,
                         value.iter().map(|m| m.as_slice()),
                         create_float_view,
+                        sync_float_view,
                     ),
                     PropertyValue::Bool(value) => {
                         send_sync_message(
@@ -623,6 +1003,17 @@ impl MaterialEditor {
             }
 
             self.create_shaders_items(ui, &material);
+
+            send_sync_message(
+                ui,
+                DropdownListMessage::selection(
+                    self.cull_mode,
+                    MessageDirection::ToWidget,
+                    CullMode::ALL
+                        .iter()
+                        .position(|mode| *mode == material.cull_mode()),
+                ),
+            );
         } else {
             send_sync_message(
                 ui,
@@ -631,6 +1022,33 @@ impl MaterialEditor {
         }
     }
 
+    /// Loads the material back from the saved asset and makes every surface that uses the edited
+    /// material use the loaded one. The editor then continues to edit the loaded material.
+    fn relink_to_asset(
+        &mut self,
+        material: SharedMaterial,
+        path: &Path,
+        engine: &mut GameEngine,
+        sender: &Sender<Message>,
+    ) {
+        match block_on(Material::from_file(path, engine.resource_manager.clone())) {
+            Ok(loaded_material) => {
+                let loaded_material = SharedMaterial::new(loaded_material);
+                sender
+                    .send(Message::do_scene_command(
+                        RelinkSurfaceMaterialCommand::new(material, loaded_material.clone()),
+                    ))
+                    .unwrap();
+                self.set_material(Some(loaded_material), engine);
+            }
+            Err(e) => Log::err(format!(
+                "Unable to relink surface to {}. Reason: {:?}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
@@ -638,6 +1056,11 @@ impl MaterialEditor {
         sender: &Sender<Message>,
     ) {
         self.preview.handle_message(message, engine);
+        self.shader_time_controls.handle_ui_message(
+            message,
+            &mut engine.scenes[self.preview.scene()],
+            &engine.user_interface,
+        );
 
         if let Some(material) = self.material.clone() {
             if let Some(msg) = message.data::<DropdownListMessage>() {
@@ -659,6 +1082,88 @@ impl MaterialEditor {
                         }
                         _ => (),
                     }
+                } else if message.destination() == self.cull_mode
+                    && message.direction() == MessageDirection::FromWidget
+                {
+                    if let DropdownListMessage::SelectionChanged(Some(index)) = msg {
+                        let cull_mode = CullMode::ALL[*index];
+                        if material.lock().cull_mode() != cull_mode {
+                            sender
+                                .send(Message::do_scene_command(SetMaterialCullModeCommand::new(
+                                    material.clone(),
+                                    cull_mode,
+                                )))
+                                .unwrap();
+                        }
+                    }
+                }
+            } else if let Some(ButtonMessage::Click) = message.data() {
+                let file_selector = if message.destination() == self.save_as_asset {
+                    self.save_file_selector
+                } else if message.destination() == self.load_from_asset {
+                    self.load_file_selector
+                } else {
+                    Handle::NONE
+                };
+
+                if file_selector.is_some() {
+                    engine
+                        .user_interface
+                        .send_message(WindowMessage::open_modal(
+                            file_selector,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                    engine
+                        .user_interface
+                        .send_message(FileSelectorMessage::root(
+                            file_selector,
+                            MessageDirection::ToWidget,
+                            Some(std::env::current_dir().unwrap()),
+                        ));
+                }
+            } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+                if message.destination() == self.save_file_selector {
+                    let mut path = path.clone();
+                    if path.extension().is_none() {
+                        path.set_extension("material");
+                    }
+
+                    let saved = material.lock().save(&path);
+                    match saved {
+                        Ok(_) => {
+                            Log::info(format!("Material was saved to {}", path.display()));
+
+                            let relink = engine
+                                .user_interface
+                                .node(self.relink_surface)
+                                .cast::<CheckBox>()
+                                .map_or(false, |check_box| check_box.checked == Some(true));
+                            if relink {
+                                self.relink_to_asset(material.clone(), &path, engine, sender);
+                            }
+                        }
+                        Err(e) => Log::err(format!(
+                            "Unable to save material to {}. Reason: {:?}",
+                            path.display(),
+                            e
+                        )),
+                    }
+                } else if message.destination() == self.load_file_selector {
+                    match block_on(Material::from_file(path, engine.resource_manager.clone())) {
+                        Ok(loaded_material) => sender
+                            .send(Message::do_scene_command(SetMaterialCommand::new(
+                                material,
+                                loaded_material,
+                            )))
+                            .unwrap(),
+                        Err(e) => Log::err(format!(
+                            "Unable to load material from {}. Reason: {:?}",
+                            path.display(),
+                            e
+                        )),
+                    }
+                    return;
                 }
             } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) =
                 message.data::<PopupMessage>()
@@ -737,6 +1242,14 @@ impl MaterialEditor {
                     } else {
                         None
                     }
+                } else if let Some(CheckBoxMessage::Check(Some(value))) =
+                    message.data::<CheckBoxMessage>()
+                {
+                    if message.direction() == MessageDirection::FromWidget {
+                        Some(PropertyValue::Bool(*value))
+                    } else {
+                        None
+                    }
                 } else if let Some(WidgetMessage::Drop(handle)) = message.data::<WidgetMessage>() {
                     if let Some(asset_item) =
                         engine.user_interface.node(*handle).cast::<AssetItem>()
@@ -751,9 +1264,15 @@ impl MaterialEditor {
                                 texture.clone().map(into_gui_texture),
                             ));
 
+                            // Keep the fallback, only the texture is replaced.
+                            let fallback = match material.lock().property_ref(property_name) {
+                                Some(PropertyValue::Sampler { fallback, .. }) => *fallback,
+                                _ => Default::default(),
+                            };
+
                             Some(PropertyValue::Sampler {
                                 value: texture,
-                                fallback: Default::default(),
+                                fallback,
                             })
                         } else {
                             None
@@ -766,18 +1285,69 @@ impl MaterialEditor {
                 };
 
                 if let Some(property_value) = property_value {
-                    sender
-                        .send(Message::do_scene_command(
-                            SetMaterialPropertyValueCommand::new(
-                                material,
-                                property_name.clone(),
-                                property_value,
-                            ),
-                        ))
-                        .unwrap();
+                    self.set_property_value(&material, property_name, property_value, sender);
+                }
+            } else if let Some(element) = ArrayElementValue::from_message(message) {
+                if let Some((property_name, outer, index)) =
+                    self.find_array_element(message.destination(), &engine.user_interface)
+                {
+                    let property_value = material
+                        .lock()
+                        .property_ref(&property_name)
+                        .and_then(|value| with_array_element(value, outer, index, element));
+
+                    if let Some(property_value) = property_value {
+                        self.set_property_value(&material, &property_name, property_value, sender);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every change of a property goes through the command stack of the scene, so it can be
+    /// undone. Changes that do not modify the value are ignored to not pollute the stack.
+    fn set_property_value(
+        &self,
+        material: &SharedMaterial,
+        property_name: &ImmutableString,
+        property_value: PropertyValue,
+        sender: &Sender<Message>,
+    ) {
+        if material.lock().property_ref(property_name) == Some(&property_value) {
+            return;
+        }
+
+        sender
+            .send(Message::do_scene_command(
+                SetMaterialPropertyValueCommand::new(
+                    material.clone(),
+                    property_name.clone(),
+                    property_value,
+                ),
+            ))
+            .unwrap();
+    }
+
+    /// Searches for an array property that has the given view as a view of one of its elements.
+    /// Returns the name of the property, an index of the inner array (for arrays of matrices) and
+    /// an index of the element.
+    fn find_array_element(
+        &self,
+        view: Handle<UiNode>,
+        ui: &UserInterface,
+    ) -> Option<(ImmutableString, Option<usize>, usize)> {
+        for (name, &property_view) in self.properties.forward_map() {
+            let items = list_items(ui, property_view);
+            if let Some(index) = items.iter().position(|&item| item == view) {
+                return Some((name.clone(), None, index));
+            }
+            for (outer, &item) in items.iter().enumerate() {
+                if let Some(index) = list_items(ui, item).iter().position(|&i| i == view) {
+                    return Some((name.clone(), Some(outer), index));
                 }
             }
         }
+        None
     }
 
     pub fn update(&mut self, engine: &mut GameEngine) {
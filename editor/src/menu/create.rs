@@ -5,10 +5,13 @@ use crate::{
         physics::PhysicsMenu, physics2d::Physics2dMenu,
     },
     scene::commands::graph::AddNodeCommand,
-    Message, Mode,
+    HeightMapImportWindow, Message, Mode,
 };
 use fyrox::{
-    core::{algebra::Matrix4, pool::Handle},
+    core::{
+        algebra::{Matrix4, Vector3},
+        pool::Handle,
+    },
     gui::{
         menu::MenuItemMessage, message::MessageDirection, message::UiMessage,
         widget::WidgetMessage, BuildContext, UiNode, UserInterface,
@@ -34,6 +37,7 @@ use fyrox::{
         sound::{listener::ListenerBuilder, SoundBuilder},
         sprite::SpriteBuilder,
         terrain::{LayerDefinition, TerrainBuilder},
+        trigger::{TriggerShape, TriggerVolumeBuilder},
     },
 };
 use std::sync::mpsc::Sender;
@@ -41,27 +45,47 @@ use std::sync::mpsc::Sender;
 pub struct CreateEntityRootMenu {
     pub menu: Handle<UiNode>,
     pub sub_menus: CreateEntityMenu,
+    terrain_from_height_map: Handle<UiNode>,
 }
 
 impl CreateEntityRootMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
-        let (sub_menus, root_items) = CreateEntityMenu::new(ctx);
+        let (sub_menus, mut root_items) = CreateEntityMenu::new(ctx);
+
+        let terrain_from_height_map = create_menu_item("Terrain From Height Map...", vec![], ctx);
+        root_items.push(terrain_from_height_map);
 
         let menu = create_root_menu_item("Create", root_items, ctx);
 
-        Self { menu, sub_menus }
+        Self {
+            menu,
+            sub_menus,
+            terrain_from_height_map,
+        }
     }
 
+    /// Creates a selected node as a child of the parent. If the position (in local space of the
+    /// parent) is given, the node is placed there.
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
         sender: &Sender<Message>,
         parent: Handle<Node>,
+        position: Option<Vector3<f32>>,
+        heightmap_import: &HeightMapImportWindow,
+        ui: &UserInterface,
     ) {
-        if let Some(node) = self.sub_menus.handle_ui_message(message) {
+        if let Some(mut node) = self.sub_menus.handle_ui_message(message) {
+            if let Some(position) = position {
+                node.local_transform_mut().set_position(position);
+            }
             sender
                 .send(Message::do_scene_command(AddNodeCommand::new(node, parent)))
                 .unwrap();
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.terrain_from_height_map {
+                heightmap_import.open(ui);
+            }
         }
     }
 
@@ -82,6 +106,8 @@ pub struct CreateEntityMenu {
     create_cylinder: Handle<UiNode>,
     create_quad: Handle<UiNode>,
     create_decal: Handle<UiNode>,
+    create_box_trigger: Handle<UiNode>,
+    create_sphere_trigger: Handle<UiNode>,
     create_point_light: Handle<UiNode>,
     create_spot_light: Handle<UiNode>,
     create_directional_light: Handle<UiNode>,
@@ -110,6 +136,8 @@ impl CreateEntityMenu {
         let create_camera;
         let create_sprite;
         let create_decal;
+        let create_box_trigger;
+        let create_sphere_trigger;
         let create_particle_system;
         let create_terrain;
         let create_pivot;
@@ -208,6 +236,20 @@ impl CreateEntityMenu {
                 create_decal = create_menu_item("Decal", vec![], ctx);
                 create_decal
             },
+            create_menu_item(
+                "Trigger Volume",
+                vec![
+                    {
+                        create_box_trigger = create_menu_item("Box", vec![], ctx);
+                        create_box_trigger
+                    },
+                    {
+                        create_sphere_trigger = create_menu_item("Sphere", vec![], ctx);
+                        create_sphere_trigger
+                    },
+                ],
+                ctx,
+            ),
         ];
 
         (
@@ -228,6 +270,8 @@ impl CreateEntityMenu {
                 create_sound_source,
                 create_listener,
                 create_decal,
+                create_box_trigger,
+                create_sphere_trigger,
                 physics_menu,
                 physics2d_menu,
                 dim2_menu,
@@ -354,6 +398,22 @@ impl CreateEntityMenu {
                         )
                     } else if message.destination() == self.create_decal {
                         Some(DecalBuilder::new(BaseBuilder::new().with_name("Decal")).build_node())
+                    } else if message.destination() == self.create_box_trigger {
+                        Some(
+                            TriggerVolumeBuilder::new(BaseBuilder::new().with_name("BoxTrigger"))
+                                .with_shape(TriggerShape::Box {
+                                    half_extents: Vector3::new(0.5, 0.5, 0.5),
+                                })
+                                .build_node(),
+                        )
+                    } else if message.destination() == self.create_sphere_trigger {
+                        Some(
+                            TriggerVolumeBuilder::new(
+                                BaseBuilder::new().with_name("SphereTrigger"),
+                            )
+                            .with_shape(TriggerShape::Sphere { radius: 0.5 })
+                            .build_node(),
+                        )
                     } else if message.destination() == self.create_listener {
                         Some(
                             ListenerBuilder::new(BaseBuilder::new().with_name("Listener"))
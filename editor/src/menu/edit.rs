@@ -1,10 +1,29 @@
 use crate::{
-    menu::{create_menu_item_shortcut, create_root_menu_item},
-    scene::{commands::PasteCommand, EditorScene, Selection},
-    GameEngine, Message, Mode,
+    copy_properties::{copy_properties, make_paste_properties_from_clipboard_command},
+    menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
+    paste_transform::{CopiedTransform, PasteTransformOptions},
+    repeat::RepeatableAction,
+    scene::{
+        commands::{
+            graph::{
+                make_align_to_surface_command, make_clear_parent_command,
+                make_parent_to_last_selected_command, make_snap_to_grid_command,
+            },
+            mesh::{
+                make_combine_meshes_command, make_geometry_to_origin_command,
+                make_origin_to_geometry_command, make_split_mesh_command,
+                make_static_batch_command, make_unmerge_static_batch_command,
+            },
+            PasteCommand,
+        },
+        EditorScene, Selection,
+    },
+    settings::Settings,
+    GameEngine, Message, Mode, NormalizeScaleWindow, PasteTransformWindow,
 };
 use fyrox::gui::message::MessageDirection;
 use fyrox::gui::widget::WidgetMessage;
+use fyrox::gui::window::WindowMessage;
 use fyrox::gui::UserInterface;
 use fyrox::{
     core::pool::Handle,
@@ -18,6 +37,25 @@ pub struct EditMenu {
     redo: Handle<UiNode>,
     copy: Handle<UiNode>,
     paste: Handle<UiNode>,
+    copy_transform: Handle<UiNode>,
+    paste_transform: Handle<UiNode>,
+    paste_transform_special: Handle<UiNode>,
+    copy_properties: Handle<UiNode>,
+    paste_properties: Handle<UiNode>,
+    origin_to_geometry: Handle<UiNode>,
+    geometry_to_origin: Handle<UiNode>,
+    split_mesh: Handle<UiNode>,
+    combine_meshes: Handle<UiNode>,
+    make_static_batch: Handle<UiNode>,
+    unmerge_static_batch: Handle<UiNode>,
+    normalize_scale: Handle<UiNode>,
+    bake_vertex_ao: Handle<UiNode>,
+    snap_position: Handle<UiNode>,
+    snap_rotation: Handle<UiNode>,
+    snap_position_and_rotation: Handle<UiNode>,
+    align_to_surface: Handle<UiNode>,
+    clear_parent: Handle<UiNode>,
+    parent_to_last_selected: Handle<UiNode>,
 }
 
 impl EditMenu {
@@ -26,6 +64,25 @@ impl EditMenu {
         let undo;
         let copy;
         let paste;
+        let copy_transform;
+        let paste_transform;
+        let paste_transform_special;
+        let copy_properties;
+        let paste_properties;
+        let origin_to_geometry;
+        let geometry_to_origin;
+        let split_mesh;
+        let combine_meshes;
+        let make_static_batch;
+        let unmerge_static_batch;
+        let normalize_scale;
+        let bake_vertex_ao;
+        let snap_position;
+        let snap_rotation;
+        let snap_position_and_rotation;
+        let align_to_surface;
+        let clear_parent;
+        let parent_to_last_selected;
         let menu = create_root_menu_item(
             "Edit",
             vec![
@@ -45,6 +102,100 @@ impl EditMenu {
                     paste = create_menu_item_shortcut("Paste", "Ctrl+V", vec![], ctx);
                     paste
                 },
+                {
+                    copy_transform = create_menu_item("Copy Transform", vec![], ctx);
+                    copy_transform
+                },
+                {
+                    paste_transform = create_menu_item("Paste Transform", vec![], ctx);
+                    paste_transform
+                },
+                {
+                    paste_transform_special =
+                        create_menu_item("Paste Transform Special...", vec![], ctx);
+                    paste_transform_special
+                },
+                {
+                    copy_properties = create_menu_item("Copy Properties", vec![], ctx);
+                    copy_properties
+                },
+                {
+                    paste_properties = create_menu_item("Paste Properties", vec![], ctx);
+                    paste_properties
+                },
+                {
+                    origin_to_geometry = create_menu_item("Origin To Geometry", vec![], ctx);
+                    origin_to_geometry
+                },
+                {
+                    geometry_to_origin = create_menu_item("Geometry To Origin", vec![], ctx);
+                    geometry_to_origin
+                },
+                {
+                    split_mesh = create_menu_item("Split Mesh Surfaces", vec![], ctx);
+                    split_mesh
+                },
+                {
+                    combine_meshes = create_menu_item("Combine Meshes", vec![], ctx);
+                    combine_meshes
+                },
+                {
+                    make_static_batch = create_menu_item("Make Static Batch", vec![], ctx);
+                    make_static_batch
+                },
+                {
+                    unmerge_static_batch = create_menu_item("Unmerge Static Batch", vec![], ctx);
+                    unmerge_static_batch
+                },
+                {
+                    normalize_scale = create_menu_item("Normalize Scale...", vec![], ctx);
+                    normalize_scale
+                },
+                {
+                    bake_vertex_ao = create_menu_item("Bake Vertex AO...", vec![], ctx);
+                    bake_vertex_ao
+                },
+                create_menu_item(
+                    "Snap To Grid",
+                    vec![
+                        {
+                            snap_position = create_menu_item("Position", vec![], ctx);
+                            snap_position
+                        },
+                        {
+                            snap_rotation = create_menu_item("Rotation", vec![], ctx);
+                            snap_rotation
+                        },
+                        {
+                            snap_position_and_rotation =
+                                create_menu_item("Position And Rotation", vec![], ctx);
+                            snap_position_and_rotation
+                        },
+                    ],
+                    ctx,
+                ),
+                {
+                    align_to_surface = create_menu_item("Align To Surface", vec![], ctx);
+                    align_to_surface
+                },
+                {
+                    clear_parent = create_menu_item_shortcut(
+                        "Clear Parent (Keep Transform)",
+                        "Alt+P",
+                        vec![],
+                        ctx,
+                    );
+                    clear_parent
+                },
+                {
+                    parent_to_last_selected = create_menu_item_shortcut(
+                        "Parent To Last Selected (Keep Transform)",
+                        "Ctrl+P",
+                        vec![],
+                        ctx,
+                    );
+                    parent_to_last_selected
+                },
             ],
             ctx,
         );
@@ -55,6 +206,25 @@ impl EditMenu {
             redo,
             copy,
             paste,
+            copy_transform,
+            paste_transform,
+            paste_transform_special,
+            copy_properties,
+            paste_properties,
+            origin_to_geometry,
+            geometry_to_origin,
+            split_mesh,
+            combine_meshes,
+            make_static_batch,
+            unmerge_static_batch,
+            normalize_scale,
+            bake_vertex_ao,
+            snap_position,
+            snap_rotation,
+            snap_position_and_rotation,
+            align_to_surface,
+            clear_parent,
+            parent_to_last_selected,
         }
     }
 
@@ -64,6 +234,10 @@ impl EditMenu {
         sender: &Sender<Message>,
         editor_scene: &mut EditorScene,
         engine: &mut GameEngine,
+        settings: &Settings,
+        normalize_scale: &NormalizeScaleWindow,
+        paste_transform: &PasteTransformWindow,
+        vertex_ao_bake: Handle<UiNode>,
     ) {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.copy {
@@ -82,6 +256,105 @@ impl EditMenu {
                         )))
                         .unwrap();
                 }
+            } else if message.destination() == self.copy_transform {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(&first) = selection.nodes().first() {
+                        editor_scene.copied_transform =
+                            Some(CopiedTransform::from_node(graph, first));
+                    }
+                }
+            } else if message.destination() == self.paste_transform {
+                RepeatableAction::PasteTransform(PasteTransformOptions::default()).perform(
+                    editor_scene,
+                    engine,
+                    sender,
+                );
+            } else if message.destination() == self.paste_transform_special {
+                paste_transform.open(editor_scene, engine);
+            } else if message.destination() == self.copy_properties {
+                copy_properties(editor_scene, engine);
+            } else if message.destination() == self.paste_properties {
+                if let Some(command) =
+                    make_paste_properties_from_clipboard_command(editor_scene, engine)
+                {
+                    sender.send(Message::DoSceneCommand(command)).unwrap();
+                }
+            } else if message.destination() == self.origin_to_geometry
+                || message.destination() == self.geometry_to_origin
+                || message.destination() == self.split_mesh
+                || message.destination() == self.combine_meshes
+                || message.destination() == self.make_static_batch
+                || message.destination() == self.unmerge_static_batch
+            {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    let command = if message.destination() == self.origin_to_geometry {
+                        make_origin_to_geometry_command(selection, graph)
+                    } else if message.destination() == self.geometry_to_origin {
+                        make_geometry_to_origin_command(selection, graph)
+                    } else if message.destination() == self.split_mesh {
+                        make_split_mesh_command(selection, graph)
+                    } else if message.destination() == self.combine_meshes {
+                        make_combine_meshes_command(selection, graph)
+                    } else if message.destination() == self.make_static_batch {
+                        make_static_batch_command(selection, graph)
+                    } else {
+                        make_unmerge_static_batch_command(selection, graph)
+                    };
+                    if let Some(command) = command {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.normalize_scale {
+                normalize_scale.open(editor_scene, engine);
+            } else if message.destination() == self.bake_vertex_ao {
+                engine.user_interface.send_message(WindowMessage::open(
+                    vertex_ao_bake,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.snap_position
+                || message.destination() == self.snap_rotation
+                || message.destination() == self.snap_position_and_rotation
+            {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let snap_position = message.destination() != self.snap_rotation;
+                    let snap_rotation = message.destination() != self.snap_position;
+                    if let Some(command) = make_snap_to_grid_command(
+                        selection,
+                        &engine.scenes[editor_scene.scene].graph,
+                        Some(&settings.move_mode_settings).filter(|_| snap_position),
+                        Some(&settings.rotate_mode_settings).filter(|_| snap_rotation),
+                    ) {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.align_to_surface {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    if let Some(command) = make_align_to_surface_command(
+                        selection,
+                        &engine.scenes[editor_scene.scene].graph,
+                        editor_scene.editor_objects_root,
+                        settings.model.surface_alignment,
+                    ) {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.clear_parent
+                || message.destination() == self.parent_to_last_selected
+            {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    let command = if message.destination() == self.clear_parent {
+                        make_clear_parent_command(selection, graph)
+                    } else {
+                        make_parent_to_last_selected_command(selection, graph)
+                    };
+                    if let Some(command) = command {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
             } else if message.destination() == self.undo {
                 sender.send(Message::UndoSceneCommand).unwrap();
             } else if message.destination() == self.redo {
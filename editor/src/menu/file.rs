@@ -1,8 +1,10 @@
 use crate::{
+    localization::tr,
     make_save_file_selector, make_scene_file_filter,
     menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
     scene::{is_scene_needs_to_be_saved, EditorScene},
     settings::{recent::RecentFiles, Settings, SettingsWindow},
+    utils::show_in_file_explorer,
     GameEngine, Message, Mode, Panels, SaveSceneConfirmationDialogAction,
 };
 use fyrox::{
@@ -16,6 +18,7 @@ use fyrox::{
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
+    utils::log::Log,
 };
 use std::sync::mpsc::Sender;
 
@@ -36,6 +39,7 @@ pub struct FileMenu {
     pub recent_files_container: Handle<UiNode>,
     pub recent_files: Vec<Handle<UiNode>>,
     pub open_scene_settings: Handle<UiNode>,
+    show_in_file_explorer: Handle<UiNode>,
 }
 
 fn make_recent_files_items(
@@ -59,6 +63,7 @@ impl FileMenu {
         let open_settings;
         let open_scene_settings;
         let configure;
+        let show_in_file_explorer;
         let exit;
         let recent_files_container;
 
@@ -67,9 +72,11 @@ impl FileMenu {
         let configure_message = MessageBoxBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(150.0))
                 .open(false)
-                .with_title(WindowTitle::Text("Warning".to_owned())),
+                .with_title(WindowTitle::Text(tr("Warning"))),
         )
-        .with_text("Cannot reconfigure editor while scene is open! Close scene first and retry.")
+        .with_text(&tr(
+            "Cannot reconfigure editor while scene is open! Close scene first and retry.",
+        ))
         .with_buttons(MessageBoxButtons::Ok)
         .build(ctx);
 
@@ -99,6 +106,11 @@ impl FileMenu {
                     close_scene = create_menu_item_shortcut("Close Scene", "Ctrl+Q", vec![], ctx);
                     close_scene
                 },
+                {
+                    show_in_file_explorer =
+                        create_menu_item("Show Scene In File Explorer", vec![], ctx);
+                    show_in_file_explorer
+                },
                 {
                     open_settings = create_menu_item("Editor Settings...", vec![], ctx);
                     open_settings
@@ -129,7 +141,7 @@ impl FileMenu {
         let load_file_selector = FileSelectorBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
                 .open(false)
-                .with_title(WindowTitle::Text("Select a Scene To Load".into())),
+                .with_title(WindowTitle::Text(tr("Select a Scene To Load"))),
         )
         .with_filter(make_scene_file_filter())
         .build(ctx);
@@ -151,6 +163,7 @@ impl FileMenu {
             recent_files_container,
             recent_files,
             open_scene_settings,
+            show_in_file_explorer,
         }
     }
 
@@ -263,6 +276,11 @@ impl FileMenu {
                 } else {
                     sender.send(Message::CloseScene).unwrap();
                 }
+            } else if message.destination() == self.show_in_file_explorer {
+                match editor_scene.as_ref().and_then(|s| s.path.as_ref()) {
+                    Some(scene_path) => show_in_file_explorer(scene_path),
+                    None => Log::warn("The scene is not saved yet, so it has no file to show."),
+                }
             } else if message.destination() == self.exit {
                 sender.send(Message::Exit { force: false }).unwrap();
             } else if message.destination() == self.new_scene {
@@ -296,7 +314,7 @@ impl FileMenu {
                 self.settings
                     .open(&mut engine.user_interface, settings, sender);
             } else if message.destination() == self.open_scene_settings {
-                panels.scene_settings.open(&engine.user_interface);
+                panels.scene_settings.open(editor_scene.as_deref(), engine);
             } else if let Some(recent_file) = self
                 .recent_files
                 .iter()
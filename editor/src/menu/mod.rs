@@ -1,5 +1,7 @@
 use crate::{
     animation::AnimationEditor,
+    cubemap::CubeMapWindow,
+    localization::tr,
     menu::{
         create::CreateEntityRootMenu, edit::EditMenu, file::FileMenu, utils::UtilsMenu,
         view::ViewMenu,
@@ -7,7 +9,8 @@ use crate::{
     scene::EditorScene,
     send_sync_message,
     settings::Settings,
-    AbsmEditor, CurveEditorWindow, GameEngine, Message, Mode, SceneSettingsWindow,
+    AbsmEditor, CurveEditorWindow, GameEngine, HeightMapImportWindow, Message, Mode,
+    NormalizeScaleWindow, PasteTransformWindow, SceneSettingsWindow,
 };
 use fyrox::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
@@ -42,6 +45,7 @@ pub struct Menu {
 
 pub struct Panels<'b> {
     pub light_panel: Handle<UiNode>,
+    pub profiler_panel: Handle<UiNode>,
     pub log_panel: Handle<UiNode>,
     pub navmesh_panel: Handle<UiNode>,
     pub audio_panel: Handle<UiNode>,
@@ -51,10 +55,17 @@ pub struct Panels<'b> {
     pub asset_window: Handle<UiNode>,
     pub configurator_window: Handle<UiNode>,
     pub path_fixer: Handle<UiNode>,
+    pub script_relinker: Handle<UiNode>,
+    pub teleport_camera: Handle<UiNode>,
     pub curve_editor: &'b CurveEditorWindow,
     pub absm_editor: &'b AbsmEditor,
     pub scene_settings: &'b SceneSettingsWindow,
+    pub heightmap_import: &'b HeightMapImportWindow,
+    pub normalize_scale: &'b NormalizeScaleWindow,
+    pub paste_transform: &'b PasteTransformWindow,
+    pub vertex_ao_bake: Handle<UiNode>,
     pub animation_editor: &'b AnimationEditor,
+    pub cube_map: &'b CubeMapWindow,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -70,7 +81,7 @@ pub fn create_root_menu_item(
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
     MenuItemBuilder::new(WidgetBuilder::new().with_margin(Thickness::right(10.0)))
-        .with_content(MenuItemContent::text_no_arrow(text))
+        .with_content(MenuItemContent::text_no_arrow(&tr(text)))
         .with_items(items)
         .build(ctx)
 }
@@ -81,7 +92,7 @@ pub fn create_menu_item(
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
     MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
-        .with_content(MenuItemContent::text(text))
+        .with_content(MenuItemContent::text(&tr(text)))
         .with_items(items)
         .build(ctx)
 }
@@ -93,7 +104,7 @@ pub fn create_menu_item_shortcut(
     ctx: &mut BuildContext,
 ) -> Handle<UiNode> {
     MenuItemBuilder::new(WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)))
-        .with_content(MenuItemContent::text_with_shortcut(text, shortcut))
+        .with_content(MenuItemContent::text_with_shortcut(&tr(text), shortcut))
         .with_items(items)
         .build(ctx)
 }
@@ -111,7 +122,7 @@ impl Menu {
         let view_menu = ViewMenu::new(ctx);
         let utils_menu = UtilsMenu::new(ctx);
 
-        let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
+        let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0).with_height(25.0))
             .with_items(vec![
                 file_menu.menu,
                 edit_menu.menu,
@@ -164,13 +175,26 @@ impl Menu {
         scope_profile!();
 
         if let Some(scene) = ctx.editor_scene.as_mut() {
-            self.edit_menu
-                .handle_ui_message(message, &self.message_sender, scene, ctx.engine);
+            self.edit_menu.handle_ui_message(
+                message,
+                &self.message_sender,
+                scene,
+                ctx.engine,
+                ctx.settings,
+                ctx.panels.normalize_scale,
+                ctx.panels.paste_transform,
+                ctx.panels.vertex_ao_bake,
+            );
 
+            let graph = &ctx.engine.scenes[scene.scene].graph;
+            let root = graph.get_root();
             self.create_entity_menu.handle_ui_message(
                 message,
                 &self.message_sender,
-                ctx.engine.scenes[scene.scene].graph.get_root(),
+                root,
+                scene.cursor_local_position(graph, root),
+                ctx.panels.heightmap_import,
+                &ctx.engine.user_interface,
             );
         }
 
@@ -184,8 +208,12 @@ impl Menu {
             ctx.settings,
             &ctx.panels,
         );
-        self.view_menu
-            .handle_ui_message(message, &ctx.engine.user_interface, &ctx.panels);
+        self.view_menu.handle_ui_message(
+            message,
+            &ctx.engine.user_interface,
+            &ctx.panels,
+            &self.message_sender,
+        );
     }
 
     pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
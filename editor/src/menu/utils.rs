@@ -12,17 +12,21 @@ use fyrox::{
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
     open_path_fixer: Handle<UiNode>,
+    open_script_relinker: Handle<UiNode>,
     open_curve_editor: Handle<UiNode>,
     absm_editor: Handle<UiNode>,
     animation_editor: Handle<UiNode>,
+    cube_map: Handle<UiNode>,
 }
 
 impl UtilsMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let open_path_fixer;
+        let open_script_relinker;
         let open_curve_editor;
         let absm_editor;
         let animation_editor;
+        let cube_map;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -30,6 +34,10 @@ impl UtilsMenu {
                     open_path_fixer = create_menu_item("Path Fixer", vec![], ctx);
                     open_path_fixer
                 },
+                {
+                    open_script_relinker = create_menu_item("Script Relinker", vec![], ctx);
+                    open_script_relinker
+                },
                 {
                     open_curve_editor = create_menu_item("Curve Editor", vec![], ctx);
                     open_curve_editor
@@ -42,6 +50,10 @@ impl UtilsMenu {
                     animation_editor = create_menu_item("Animation Editor", vec![], ctx);
                     animation_editor
                 },
+                {
+                    cube_map = create_menu_item("Cube Map", vec![], ctx);
+                    cube_map
+                },
             ],
             ctx,
         );
@@ -49,9 +61,11 @@ impl UtilsMenu {
         Self {
             menu,
             open_path_fixer,
+            open_script_relinker,
             open_curve_editor,
             absm_editor,
             animation_editor,
+            cube_map,
         }
     }
 
@@ -63,12 +77,20 @@ impl UtilsMenu {
                     MessageDirection::ToWidget,
                     true,
                 ));
+            } else if message.destination() == self.open_script_relinker {
+                ui.send_message(WindowMessage::open_modal(
+                    panels.script_relinker,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
             } else if message.destination() == self.open_curve_editor {
                 panels.curve_editor.open(ui);
             } else if message.destination() == self.absm_editor {
                 panels.absm_editor.open(ui);
             } else if message.destination() == self.animation_editor {
                 panels.animation_editor.open(ui);
+            } else if message.destination() == self.cube_map {
+                panels.cube_map.open(ui);
             }
         }
     }
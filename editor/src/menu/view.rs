@@ -1,4 +1,7 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    Message,
+};
 use fyrox::{
     core::pool::Handle,
     gui::{
@@ -8,6 +11,7 @@ use fyrox::{
         BuildContext, UiNode, UserInterface,
     },
 };
+use std::sync::mpsc::Sender;
 
 pub struct ViewMenu {
     pub menu: Handle<UiNode>,
@@ -19,6 +23,11 @@ pub struct ViewMenu {
     nav_mesh: Handle<UiNode>,
     audio: Handle<UiNode>,
     command_stack: Handle<UiNode>,
+    profiler: Handle<UiNode>,
+    reset_layout: Handle<UiNode>,
+    immersive_mode: Handle<UiNode>,
+    shortcuts: Handle<UiNode>,
+    camera_coordinates: Handle<UiNode>,
 }
 
 fn switch_window_state(window: Handle<UiNode>, ui: &UserInterface, center: bool) {
@@ -40,6 +49,11 @@ impl ViewMenu {
         let nav_mesh;
         let audio;
         let command_stack;
+        let profiler;
+        let reset_layout;
+        let immersive_mode;
+        let shortcuts;
+        let camera_coordinates;
         let menu = create_root_menu_item(
             "View",
             vec![
@@ -75,6 +89,26 @@ impl ViewMenu {
                     command_stack = create_menu_item("Command Stack Panel", vec![], ctx);
                     command_stack
                 },
+                {
+                    profiler = create_menu_item("Profiler", vec![], ctx);
+                    profiler
+                },
+                {
+                    camera_coordinates = create_menu_item("Camera Coordinates", vec![], ctx);
+                    camera_coordinates
+                },
+                {
+                    reset_layout = create_menu_item("Reset Layout", vec![], ctx);
+                    reset_layout
+                },
+                {
+                    immersive_mode = create_menu_item("Immersive Mode", vec![], ctx);
+                    immersive_mode
+                },
+                {
+                    shortcuts = create_menu_item("Keyboard Shortcuts", vec![], ctx);
+                    shortcuts
+                },
             ],
             ctx,
         );
@@ -89,10 +123,21 @@ impl ViewMenu {
             nav_mesh,
             audio,
             command_stack,
+            profiler,
+            reset_layout,
+            immersive_mode,
+            shortcuts,
+            camera_coordinates,
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &UserInterface, panels: &Panels) {
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        panels: &Panels,
+        sender: &Sender<Message>,
+    ) {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.asset_browser {
                 switch_window_state(panels.asset_window, ui, false);
@@ -110,6 +155,16 @@ impl ViewMenu {
                 switch_window_state(panels.audio_panel, ui, false);
             } else if message.destination() == self.command_stack {
                 switch_window_state(panels.command_stack_panel, ui, false);
+            } else if message.destination() == self.profiler {
+                switch_window_state(panels.profiler_panel, ui, true);
+            } else if message.destination() == self.camera_coordinates {
+                switch_window_state(panels.teleport_camera, ui, true);
+            } else if message.destination() == self.reset_layout {
+                sender.send(Message::ResetLayout).unwrap();
+            } else if message.destination() == self.immersive_mode {
+                sender.send(Message::ToggleImmersiveMode).unwrap();
+            } else if message.destination() == self.shortcuts {
+                sender.send(Message::ToggleShortcuts).unwrap();
             }
         }
     }
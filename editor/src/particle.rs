@@ -28,7 +28,7 @@ impl ParticleSystemPreviewControlPanel {
         let play;
         let pause;
         let stop;
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("ParticleSystemPanel"))
             .open(false)
             .with_title(WindowTitle::text("Particle System"))
             .with_content(
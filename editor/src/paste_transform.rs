@@ -0,0 +1,526 @@
+//! Copying of a transform of a node and pasting it to other nodes. Paste Special window allows to
+//! choose which parts of the copied transform are pasted, in which space and how they're combined
+//! with the current values.
+
+use crate::{
+    inspector::editors::make_property_editors_container,
+    interaction::transform_locks::TransformLocks,
+    repeat::RepeatableAction,
+    scene::{
+        commands::{
+            graph::{decompose_matrix, MoveNodeCommand, RotateNodeCommand, ScaleNodeCommand},
+            CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+    Message, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+            },
+            InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        graph::Graph,
+        node::Node,
+        transform::{Transform, TransformBuilder},
+    },
+    utils::log::Log,
+};
+use std::{rc::Rc, sync::mpsc::Sender};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Maximum amount of nodes which resulting transforms are shown in the window.
+const MAX_DESCRIBED_NODES: usize = 8;
+const NO_SELECTION: &str = "Select one or more nodes to paste the transform to.";
+const NOTHING_COPIED: &str = "There is no copied transform, use Edit > Copy Transform first.";
+
+/// Position, rotation and scale of a node.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TransformValues {
+    pub position: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
+impl TransformValues {
+    fn from_transform(transform: &Transform) -> Self {
+        Self {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+        }
+    }
+
+    fn matrix(&self) -> Matrix4<f32> {
+        TransformBuilder::new()
+            .with_local_position(self.position)
+            .with_local_rotation(self.rotation)
+            .with_local_scale(self.scale)
+            .build()
+            .matrix()
+    }
+
+    fn describe(&self) -> String {
+        let (x, y, z) = self.rotation.euler_angles();
+        format!(
+            "position ({:.3}, {:.3}, {:.3}), rotation ({:.2}°, {:.2}°, {:.2}°), \
+            scale ({:.3}, {:.3}, {:.3})",
+            self.position.x,
+            self.position.y,
+            self.position.z,
+            x.to_degrees(),
+            y.to_degrees(),
+            z.to_degrees(),
+            self.scale.x,
+            self.scale.y,
+            self.scale.z
+        )
+    }
+}
+
+/// Transform of a node at the moment of copying, both in local and world space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CopiedTransform {
+    pub local: TransformValues,
+    pub world: TransformValues,
+}
+
+impl CopiedTransform {
+    pub fn from_node(graph: &Graph, handle: Handle<Node>) -> Self {
+        let node = &graph[handle];
+        Self {
+            local: TransformValues::from_transform(node.local_transform()),
+            world: TransformValues::from_transform(&decompose_matrix(&node.global_transform()).0),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum PasteSpace {
+    Local,
+    World,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum PasteMode {
+    Replace,
+    Add,
+}
+
+#[derive(Clone, Debug, Reflect)]
+pub struct PasteTransformOptions {
+    #[reflect(description = "Paste X coordinate of the position.")]
+    pub position_x: bool,
+    #[reflect(description = "Paste Y coordinate of the position.")]
+    pub position_y: bool,
+    #[reflect(description = "Paste Z coordinate of the position.")]
+    pub position_z: bool,
+    #[reflect(description = "Paste the rotation.")]
+    pub rotation: bool,
+    #[reflect(description = "Paste the scale.")]
+    pub scale: bool,
+    #[reflect(
+        description = "Space of the copied values. World values are converted to the local space \
+        of every target node, so the node ends up with the copied world position/rotation/scale."
+    )]
+    pub space: PasteSpace,
+    #[reflect(
+        description = "Replace current values or add the copied ones to them: the position is \
+        offset, the rotation is applied on top of the current one and the scale is multiplied."
+    )]
+    pub mode: PasteMode,
+}
+
+impl Default for PasteTransformOptions {
+    fn default() -> Self {
+        Self {
+            position_x: true,
+            position_y: true,
+            position_z: true,
+            rotation: true,
+            scale: true,
+            space: PasteSpace::Local,
+            mode: PasteMode::Replace,
+        }
+    }
+}
+
+impl PasteTransformOptions {
+    fn position_axes(&self) -> [bool; 3] {
+        [self.position_x, self.position_y, self.position_z]
+    }
+
+    fn combine(&self, current: &TransformValues, copied: &TransformValues) -> TransformValues {
+        let mut result = *current;
+
+        for (i, paste) in self.position_axes().iter().enumerate() {
+            if *paste {
+                result.position[i] = match self.mode {
+                    PasteMode::Replace => copied.position[i],
+                    PasteMode::Add => current.position[i] + copied.position[i],
+                };
+            }
+        }
+
+        if self.rotation {
+            result.rotation = match self.mode {
+                PasteMode::Replace => copied.rotation,
+                PasteMode::Add => copied.rotation * current.rotation,
+            };
+        }
+
+        if self.scale {
+            result.scale = match self.mode {
+                PasteMode::Replace => copied.scale,
+                PasteMode::Add => current.scale.component_mul(&copied.scale),
+            };
+        }
+
+        result
+    }
+}
+
+/// Calculates new local transform of a node after pasting, transform locks are respected.
+fn pasted_local_transform(
+    graph: &Graph,
+    handle: Handle<Node>,
+    copied: &CopiedTransform,
+    options: &PasteTransformOptions,
+    locks: &TransformLocks,
+) -> TransformValues {
+    let node = &graph[handle];
+    let current = TransformValues::from_transform(node.local_transform());
+
+    let mut new = match options.space {
+        PasteSpace::Local => options.combine(&current, &copied.local),
+        PasteSpace::World => {
+            let current_world =
+                TransformValues::from_transform(&decompose_matrix(&node.global_transform()).0);
+            let new_world = options.combine(&current_world, &copied.world);
+            let parent_inv_transform = graph
+                .try_get(node.parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_else(Matrix4::identity);
+            let new_local = TransformValues::from_transform(
+                &decompose_matrix(&(parent_inv_transform * new_world.matrix())).0,
+            );
+
+            // Decomposition is not exact, so parts that are not pasted are taken as is.
+            TransformValues {
+                position: if options.position_axes().contains(&true) {
+                    new_local.position
+                } else {
+                    current.position
+                },
+                rotation: if options.rotation {
+                    new_local.rotation
+                } else {
+                    current.rotation
+                },
+                scale: if options.scale {
+                    new_local.scale
+                } else {
+                    current.scale
+                },
+            }
+        }
+    };
+
+    new.position = locks.lock_position(current.position, new.position);
+    new.rotation = locks.lock_rotation(current.rotation, new.rotation);
+    new.scale = locks.lock_scale(current.scale, new.scale);
+    new
+}
+
+/// Creates a command that pastes the copied transform to every selected node (selected
+/// descendants of other selected nodes are ignored).
+pub fn make_paste_transform_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    copied: &CopiedTransform,
+    options: &PasteTransformOptions,
+    locks: &TransformLocks,
+) -> Option<SceneCommand> {
+    let mut commands = Vec::new();
+
+    for root in selection.root_nodes(graph) {
+        let current = TransformValues::from_transform(graph[root].local_transform());
+        let new = pasted_local_transform(graph, root, copied, options, locks);
+
+        if new.position != current.position {
+            commands.push(SceneCommand::new(MoveNodeCommand::new(
+                root,
+                current.position,
+                new.position,
+            )));
+        }
+        if new.rotation != current.rotation {
+            commands.push(SceneCommand::new(RotateNodeCommand::new(
+                root,
+                current.rotation,
+                new.rotation,
+            )));
+        }
+        if new.scale != current.scale {
+            commands.push(SceneCommand::new(ScaleNodeCommand::new(
+                root,
+                current.scale,
+                new.scale,
+            )));
+        }
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+fn describe_result(
+    selection: &GraphSelection,
+    graph: &Graph,
+    copied: &CopiedTransform,
+    options: &PasteTransformOptions,
+    locks: &TransformLocks,
+) -> String {
+    let root_nodes = selection.root_nodes(graph);
+    if root_nodes.is_empty() {
+        return NO_SELECTION.to_owned();
+    }
+
+    let mut text = format!(
+        "Copied ({:?} space): {}\n\nResult (local space):",
+        options.space,
+        match options.space {
+            PasteSpace::Local => copied.local.describe(),
+            PasteSpace::World => copied.world.describe(),
+        }
+    );
+    for &handle in root_nodes.iter().take(MAX_DESCRIBED_NODES) {
+        text += &format!(
+            "\n{}: {}",
+            graph[handle].name(),
+            pasted_local_transform(graph, handle, copied, options, locks).describe()
+        );
+    }
+    if root_nodes.len() > MAX_DESCRIBED_NODES {
+        text += &format!("\n...and {} more", root_nodes.len() - MAX_DESCRIBED_NODES);
+    }
+    text
+}
+
+pub struct PasteTransformWindow {
+    pub window: Handle<UiNode>,
+    preview: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    close: Handle<UiNode>,
+    options: PasteTransformOptions,
+}
+
+impl PasteTransformWindow {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let options = PasteTransformOptions::default();
+
+        let container = make_property_editors_container(sender);
+        container.insert(InspectablePropertyEditorDefinition::<PasteTransformOptions>::new());
+        container.insert(EnumPropertyEditorDefinition::<PasteSpace>::new());
+        container.insert(EnumPropertyEditorDefinition::<PasteMode>::new());
+
+        let context = InspectorContext::from_object(
+            &options,
+            ctx,
+            Rc::new(container),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+        );
+
+        let preview;
+        let inspector;
+        let apply;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(420.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Paste Transform Special"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                    .with_context(context)
+                                    .build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                preview = TextBuilder::new(WidgetBuilder::new())
+                                    .with_wrap(WrapMode::Word)
+                                    .build(ctx);
+                                preview
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        apply = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Apply")
+                                        .build(ctx);
+                                        apply
+                                    })
+                                    .with_child({
+                                        close = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Close")
+                                        .build(ctx);
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            preview,
+            inspector,
+            apply,
+            close,
+            options,
+        }
+    }
+
+    pub fn open(&self, editor_scene: &EditorScene, engine: &Engine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        self.sync_preview(editor_scene, engine, true);
+    }
+
+    fn sync_preview(&self, editor_scene: &EditorScene, engine: &Engine, force: bool) {
+        let ui = &engine.user_interface;
+
+        if !force && !ui.node(self.window).visibility() {
+            return;
+        }
+
+        let text = match (&editor_scene.selection, &editor_scene.copied_transform) {
+            (_, None) => NOTHING_COPIED.to_owned(),
+            (Selection::Graph(selection), Some(copied)) => describe_result(
+                selection,
+                &engine.scenes[editor_scene.scene].graph,
+                copied,
+                &self.options,
+                &editor_scene.transform_locks,
+            ),
+            _ => NO_SELECTION.to_owned(),
+        };
+
+        ui.send_message(TextMessage::text(
+            self.preview,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    /// Updates the preview, it must be called after every change of the scene, because the
+    /// selection and the nodes could change.
+    pub fn sync_to_model(&self, editor_scene: &EditorScene, engine: &Engine) {
+        self.sync_preview(editor_scene, engine, false);
+    }
+
+    fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &Sender<Message>,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.apply {
+                RepeatableAction::PasteTransform(self.options.clone()).perform(
+                    editor_scene,
+                    engine,
+                    sender,
+                );
+            } else if message.destination() == self.close {
+                self.close(&engine.user_interface);
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                Log::verify(
+                    PropertyAction::from_field_kind(&property_changed.value)
+                        .apply(&property_changed.path(), &mut self.options),
+                );
+                self.sync_preview(editor_scene, engine, false);
+            }
+        }
+    }
+}
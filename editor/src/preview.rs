@@ -19,7 +19,7 @@ use fyrox::{
     resource::texture::{Texture, TextureKind},
     scene::{
         base::BaseBuilder,
-        camera::{CameraBuilder, Projection},
+        camera::{CameraBuilder, Projection, SkyBox},
         debug::Line,
         light::{directional::DirectionalLightBuilder, BaseLightBuilder},
         mesh::Mesh,
@@ -371,6 +371,13 @@ impl PreviewPanel {
         self.fit_to_model(&mut engine.scenes[self.scene])
     }
 
+    /// Sets the skybox of the preview camera, the built-in one is used if it is `None`.
+    pub fn set_skybox(&self, skybox: Option<SkyBox>, engine: &mut GameEngine) {
+        engine.scenes[self.scene].graph[self.camera]
+            .as_camera_mut()
+            .set_skybox(Some(skybox.unwrap_or_else(built_in_skybox)));
+    }
+
     pub fn scene(&self) -> Handle<Scene> {
         self.scene
     }
@@ -0,0 +1,309 @@
+//! A live view of the data gathered by the built-in scoped profiler (see `scope_profile!`). The
+//! slowest scopes of the last second are shown as a table with a bar per scope, the bar shows the
+//! share of the frame time that was spent in the scope itself.
+
+use crate::GameEngine;
+use fyrox::{
+    core::{color::Color, pool::Handle, profiler, profiler::ScopeStatistics},
+    fxhash::FxHashMap,
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::MessageDirection,
+        scroll_viewer::ScrollViewerBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+
+/// Maximum amount of scopes in the table.
+const MAX_SCOPES: usize = 40;
+/// How often (in seconds) the table is updated.
+const UPDATE_INTERVAL: f32 = 1.0;
+const BAR_WIDTH: f32 = 100.0;
+
+pub struct ProfilerPanel {
+    pub window: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    scopes: Handle<UiNode>,
+    previous: Option<FxHashMap<(&'static str, u32), ScopeStatistics>>,
+    elapsed: f32,
+    frames: u32,
+}
+
+/// Statistics of a scope for the last update interval, per frame.
+struct ScopeRow {
+    name: String,
+    calls: f64,
+    total_time: f64,
+    self_time: f64,
+}
+
+fn scope_name(statistics: &ScopeStatistics) -> String {
+    // Function names of scopes are taken from a nested `scope` function.
+    let name = statistics
+        .function_name
+        .strip_suffix("::scope")
+        .unwrap_or(statistics.function_name);
+    format!("{}:{}", name, statistics.line)
+}
+
+fn make_header(ctx: &mut BuildContext) -> Handle<UiNode> {
+    let mut builder = WidgetBuilder::new().with_margin(Thickness::uniform(1.0));
+    for (column, (text, tooltip)) in [
+        ("Scope", "Name of the function and line of the scope"),
+        ("Self", "Share of the frame time spent in the scope itself"),
+        ("Self, ms", "Time per frame spent in the scope itself"),
+        (
+            "Total, ms",
+            "Time per frame spent in the scope, including nested scopes",
+        ),
+        ("Calls", "How many times per frame the scope was entered"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        builder = builder.with_child(
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .on_column(column)
+                    .with_tooltip(make_simple_tooltip(ctx, tooltip))
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(text)
+            .build(ctx),
+        );
+    }
+    add_columns(GridBuilder::new(builder)).build(ctx)
+}
+
+fn add_columns(grid: GridBuilder) -> GridBuilder {
+    grid.add_row(Row::strict(20.0))
+        .add_column(Column::stretch())
+        .add_column(Column::strict(BAR_WIDTH + 4.0))
+        .add_column(Column::strict(70.0))
+        .add_column(Column::strict(70.0))
+        .add_column(Column::strict(60.0))
+}
+
+fn make_row(row: &ScopeRow, frame_time: f64, ctx: &mut BuildContext) -> Handle<UiNode> {
+    let share = if frame_time > 0.0 {
+        (row.self_time / frame_time).min(1.0)
+    } else {
+        0.0
+    };
+
+    let make_text = |ctx: &mut BuildContext, column: usize, text: String| {
+        TextBuilder::new(
+            WidgetBuilder::new()
+                .on_column(column)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_text(text)
+        .build(ctx)
+    };
+
+    let name = make_text(ctx, 0, row.name.clone());
+    let self_time = make_text(ctx, 2, format!("{:.3}", row.self_time * 1000.0));
+    let total_time = make_text(ctx, 3, format!("{:.3}", row.total_time * 1000.0));
+    let calls = make_text(ctx, 4, format!("{:.1}", row.calls));
+
+    let bar = BorderBuilder::new(
+        WidgetBuilder::new()
+            .on_column(1)
+            .with_margin(Thickness::uniform(2.0))
+            .with_background(Brush::Solid(Color::opaque(40, 40, 40)))
+            .with_child(
+                BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(BAR_WIDTH * share as f32)
+                        .with_horizontal_alignment(HorizontalAlignment::Left)
+                        .with_background(Brush::Solid(Color::opaque(
+                            (80.0 + 175.0 * share) as u8,
+                            (200.0 - 120.0 * share) as u8,
+                            60,
+                        ))),
+                )
+                .build(ctx),
+            ),
+    )
+    .build(ctx);
+
+    add_columns(GridBuilder::new(
+        WidgetBuilder::new()
+            .with_child(name)
+            .with_child(bar)
+            .with_child(self_time)
+            .with_child(total_time)
+            .with_child(calls),
+    ))
+    .build(ctx)
+}
+
+impl ProfilerPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let summary;
+        let scopes;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(600.0).with_height(400.0))
+            .with_title(WindowTitle::text("Profiler"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            summary = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .with_text(if profiler::is_enabled() {
+                                "Gathering data..."
+                            } else {
+                                "The profiler is disabled, build the editor with \
+                                `enable_profiler` feature to gather the data."
+                            })
+                            .build(ctx);
+                            summary
+                        })
+                        .with_child({
+                            let header = make_header(ctx);
+                            ctx[header].set_row(1);
+                            header
+                        })
+                        .with_child({
+                            scopes = ListViewBuilder::new(WidgetBuilder::new().on_row(2))
+                                .with_scroll_viewer(
+                                    ScrollViewerBuilder::new(WidgetBuilder::new())
+                                        .with_horizontal_scroll_allowed(true)
+                                        .build(ctx),
+                                )
+                                .build(ctx);
+                            scopes
+                        }),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            summary,
+            scopes,
+            previous: None,
+            elapsed: 0.0,
+            frames: 0,
+        }
+    }
+
+    pub fn update(&mut self, engine: &mut GameEngine, dt: f32) {
+        let ui = &mut engine.user_interface;
+
+        // Snapshots are taken only while the panel is open, the data is quite big.
+        if !profiler::is_enabled() || !ui.node(self.window).visibility() {
+            self.previous = None;
+            return;
+        }
+
+        self.elapsed += dt;
+        self.frames += 1;
+
+        if self.previous.is_some() && self.elapsed < UPDATE_INTERVAL {
+            return;
+        }
+
+        let current = profiler::scope_statistics()
+            .into_iter()
+            .map(|s| ((s.function_name, s.line), s))
+            .collect::<FxHashMap<_, _>>();
+
+        if let Some(previous) = self.previous.as_ref() {
+            self.sync(previous, &current, ui);
+        }
+
+        self.previous = Some(current);
+        self.elapsed = 0.0;
+        self.frames = 0;
+    }
+
+    fn sync(
+        &self,
+        previous: &FxHashMap<(&'static str, u32), ScopeStatistics>,
+        current: &FxHashMap<(&'static str, u32), ScopeStatistics>,
+        ui: &mut UserInterface,
+    ) {
+        let frames = self.frames.max(1) as f64;
+        let frame_time = self.elapsed as f64 / frames;
+
+        let mut rows = current
+            .iter()
+            .filter_map(|(key, statistics)| {
+                let (count, total_time, self_time) = match previous.get(key) {
+                    Some(prev) => (
+                        statistics.count - prev.count,
+                        statistics.total_time - prev.total_time,
+                        statistics.self_time - prev.self_time,
+                    ),
+                    None => (
+                        statistics.count,
+                        statistics.total_time,
+                        statistics.self_time,
+                    ),
+                };
+
+                if count == 0 {
+                    None
+                } else {
+                    Some(ScopeRow {
+                        name: scope_name(statistics),
+                        calls: count as f64 / frames,
+                        total_time: total_time / frames,
+                        self_time: self_time / frames,
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|a, b| {
+            b.self_time
+                .partial_cmp(&a.self_time)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        rows.truncate(MAX_SCOPES);
+
+        let ctx = &mut ui.build_ctx();
+        let items = rows
+            .iter()
+            .map(|row| make_row(row, frame_time, ctx))
+            .collect::<Vec<_>>();
+
+        ui.send_message(ListViewMessage::items(
+            self.scopes,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            format!(
+                "Frame time: {:.2} ms ({} frames in the last {:.1} s). Showing {} slowest scopes.",
+                frame_time * 1000.0,
+                self.frames,
+                self.elapsed,
+                rows.len()
+            ),
+        ));
+    }
+}
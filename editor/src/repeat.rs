@@ -0,0 +1,130 @@
+//! Repeating of the last action with the current selection, for example duplicating the selection
+//! once more or moving it by the same offset again. Only actions that make sense in a different
+//! context are repeatable, doing any other change of the scene makes the repeat a no-op until the
+//! next repeatable action. Selection changes do not affect the last action, so it can be repeated
+//! for different nodes.
+
+use crate::{
+    interaction::move_mode::make_move_selection_command,
+    paste_transform::{make_paste_transform_command, PasteTransformOptions},
+    scene::{
+        commands::{
+            graph::make_toggle_enabled_command, make_duplicate_selection_command, DuplicationMode,
+            SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    Message,
+};
+use fyrox::{core::algebra::Vector3, engine::Engine};
+use std::sync::mpsc::Sender;
+
+#[derive(Clone, Debug)]
+pub enum RepeatableAction {
+    /// Duplication of selected nodes, duplicates are moved by the offset (in world space) from
+    /// their originals.
+    Duplicate {
+        mode: DuplicationMode,
+        offset: Vector3<f32>,
+        /// Moves of the duplicates that are done right after the duplication are added to the
+        /// offset, so "duplicate and move" is repeated as a whole. It stops once the selection is
+        /// changed or the action is repeated.
+        merge_moves: bool,
+    },
+    /// Moving of selected nodes by the offset in world space.
+    Move {
+        offset: Vector3<f32>,
+    },
+    ToggleEnabled,
+    PasteTransform(PasteTransformOptions),
+}
+
+impl RepeatableAction {
+    /// Creates a new duplication action.
+    pub fn duplicate(mode: DuplicationMode) -> Self {
+        Self::Duplicate {
+            mode,
+            offset: Vector3::default(),
+            merge_moves: true,
+        }
+    }
+
+    /// Returns an action that should be recorded after moving of selected nodes by the given
+    /// offset in world space.
+    pub fn after_move(last_action: Option<&RepeatableAction>, offset: Vector3<f32>) -> Self {
+        match last_action {
+            Some(Self::Duplicate {
+                mode,
+                offset: duplication_offset,
+                merge_moves: true,
+            }) => Self::Duplicate {
+                mode: *mode,
+                offset: duplication_offset + offset,
+                merge_moves: true,
+            },
+            _ => Self::Move { offset },
+        }
+    }
+
+    pub fn stop_merging_moves(&mut self) {
+        if let Self::Duplicate { merge_moves, .. } = self {
+            *merge_moves = false;
+        }
+    }
+
+    /// Creates a command that performs the action with the current selection. Returns `None` if
+    /// the action cannot be done with it.
+    pub fn make_command(
+        &self,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) -> Option<SceneCommand> {
+        let selection = match &editor_scene.selection {
+            Selection::Graph(selection) if !selection.is_empty() => selection,
+            _ => return None,
+        };
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        match self {
+            Self::Duplicate { mode, offset, .. } => {
+                make_duplicate_selection_command(selection, graph, *mode, *offset)
+            }
+            Self::Move { offset } => Some(make_move_selection_command(
+                selection,
+                graph,
+                *offset,
+                &editor_scene.transform_locks,
+            )),
+            Self::ToggleEnabled => make_toggle_enabled_command(selection, graph),
+            Self::PasteTransform(options) => make_paste_transform_command(
+                selection,
+                graph,
+                editor_scene.copied_transform.as_ref()?,
+                options,
+                &editor_scene.transform_locks,
+            ),
+        }
+    }
+
+    /// Performs the action with the current selection and records it as the last action.
+    pub fn perform(self, editor_scene: &EditorScene, engine: &Engine, sender: &Sender<Message>) {
+        if let Some(command) = self.make_command(editor_scene, engine) {
+            sender
+                .send(Message::DoRepeatableSceneCommand {
+                    command,
+                    action: self,
+                })
+                .unwrap();
+        }
+    }
+}
+
+/// Performs the last repeatable action of the scene once more, does nothing if there's no such
+/// action.
+pub fn repeat_last_action(editor_scene: &EditorScene, engine: &Engine, sender: &Sender<Message>) {
+    if let Some(action) = editor_scene.last_action.as_ref() {
+        let mut action = action.clone();
+        action.stop_merging_moves();
+        action.perform(editor_scene, engine, sender);
+    }
+}
@@ -0,0 +1,447 @@
+//! Normalization of scale of nodes, it is mostly used to bring imported models made in different
+//! units to the units of the scene. The scale is defined by a known real-world size of a node.
+
+use crate::{
+    inspector::editors::make_property_editors_container,
+    scene::{
+        commands::{
+            graph::{MoveNodeCommand, ScaleNodeCommand},
+            mesh::SetMeshSurfacesDataCommand,
+            CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+    Message, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::Vector3, math::aabb::AxisAlignedBoundingBox, pool::Handle, reflect::prelude::*,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::{
+                enumeration::EnumPropertyEditorDefinition,
+                inspectable::InspectablePropertyEditorDefinition,
+            },
+            InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        graph::Graph,
+        mesh::{buffer::VertexAttributeUsage, Mesh},
+        node::Node,
+    },
+    utils::log::Log,
+};
+use std::{rc::Rc, sync::mpsc::Sender};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Maximum amount of nodes which sizes are shown in the window.
+const MAX_DESCRIBED_NODES: usize = 8;
+const NO_SELECTION: &str = "Select one or more nodes to normalize their scale.";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum ReferenceAxis {
+    Largest,
+    X,
+    Y,
+    Z,
+}
+
+impl ReferenceAxis {
+    fn size(self, size: Vector3<f32>) -> f32 {
+        match self {
+            ReferenceAxis::Largest => size.max(),
+            ReferenceAxis::X => size.x,
+            ReferenceAxis::Y => size.y,
+            ReferenceAxis::Z => size.z,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Reflect)]
+pub struct NormalizeScaleOptions {
+    #[reflect(description = "Dimension of the bounding box that is compared with the size.")]
+    pub axis: ReferenceAxis,
+    #[reflect(
+        description = "Real-world size of every selected node along the axis in scene units.",
+        min_value = 0.0001
+    )]
+    pub size: f32,
+    #[reflect(
+        description = "Apply the scale to the geometry of meshes and positions of descendants \
+        instead of the scale of the node. Sizes of other nodes (lights, colliders, etc.) are not \
+        changed. Hierarchies with skinned meshes are always scaled as a whole."
+    )]
+    pub bake: bool,
+}
+
+impl Default for NormalizeScaleOptions {
+    fn default() -> Self {
+        Self {
+            axis: ReferenceAxis::Largest,
+            size: 1.0,
+            bake: false,
+        }
+    }
+}
+
+/// Returns world-space bounding box of a node and its descendants, nodes without bounds are
+/// ignored. `None` means that there is nothing to measure.
+fn hierarchy_bounds(graph: &Graph, root: Handle<Node>) -> Option<AxisAlignedBoundingBox> {
+    let mut bounding_box = AxisAlignedBoundingBox::default();
+    let mut is_empty = true;
+
+    for node in graph.traverse_iter(root) {
+        let local_bounding_box = node.local_bounding_box();
+        if local_bounding_box.min.x <= local_bounding_box.max.x
+            && local_bounding_box.min.y <= local_bounding_box.max.y
+            && local_bounding_box.min.z <= local_bounding_box.max.z
+        {
+            bounding_box.add_box(node.world_bounding_box());
+            is_empty = false;
+        }
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(bounding_box)
+    }
+}
+
+fn has_skinned_meshes(graph: &Graph, root: Handle<Node>) -> bool {
+    graph.traverse_iter(root).any(|node| {
+        node.cast::<Mesh>().map_or(false, |mesh| {
+            mesh.surfaces().iter().any(|s| !s.bones().is_empty())
+        })
+    })
+}
+
+fn make_bake_commands(graph: &Graph, root: Handle<Node>, factor: f32) -> Vec<SceneCommand> {
+    let mut commands = Vec::new();
+
+    for handle in graph.traverse_handle_iter(root) {
+        let node = &graph[handle];
+
+        // A uniform scale commutes with rotations, so it is enough to scale positions of the
+        // descendants and the geometry, the root stays in place.
+        if handle != root {
+            let old_position = **node.local_transform().position();
+            commands.push(SceneCommand::new(MoveNodeCommand::new(
+                handle,
+                old_position,
+                old_position.scale(factor),
+            )));
+        }
+
+        if let Some(mesh) = node.cast::<Mesh>() {
+            let data = mesh
+                .surfaces()
+                .iter()
+                .map(|surface| {
+                    let data = surface.data().deep_clone();
+                    {
+                        let mut data = data.lock();
+                        let mut vertex_buffer = data.vertex_buffer.modify();
+                        for mut view in vertex_buffer.iter_mut() {
+                            if let Ok(position) = view.read_3_f32(VertexAttributeUsage::Position) {
+                                let _ = view.write_3_f32(
+                                    VertexAttributeUsage::Position,
+                                    position.scale(factor),
+                                );
+                            }
+                        }
+                    }
+                    data
+                })
+                .collect();
+            commands.push(SceneCommand::new(SetMeshSurfacesDataCommand::new(
+                handle, data,
+            )));
+        }
+    }
+
+    commands
+}
+
+/// Creates a command that uniformly scales every selected node (selected descendants of other
+/// selected nodes are ignored) so its bounding box has the given size along the given axis.
+pub fn make_normalize_scale_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    options: &NormalizeScaleOptions,
+) -> Option<SceneCommand> {
+    let mut commands = Vec::new();
+
+    for root in selection.root_nodes(graph) {
+        let node = &graph[root];
+
+        let current_size = match hierarchy_bounds(graph, root) {
+            Some(bounds) => options.axis.size(bounds.max - bounds.min),
+            None => {
+                Log::warn(format!(
+                    "Unable to normalize scale of {}, it has no geometry!",
+                    node.name()
+                ));
+                continue;
+            }
+        };
+
+        if current_size <= f32::EPSILON {
+            Log::warn(format!(
+                "Unable to normalize scale of {}, its size along the {:?} axis is zero!",
+                node.name(),
+                options.axis
+            ));
+            continue;
+        }
+
+        let factor = options.size.max(0.0001) / current_size;
+
+        if options.bake && !has_skinned_meshes(graph, root) {
+            commands.extend(make_bake_commands(graph, root, factor));
+        } else {
+            if options.bake {
+                Log::warn(format!(
+                    "{} has skinned meshes, their geometry is bound to bones and cannot be baked. \
+                    The scale of the node is changed instead.",
+                    node.name()
+                ));
+            }
+
+            let old_scale = **node.local_transform().scale();
+            commands.push(SceneCommand::new(ScaleNodeCommand::new(
+                root,
+                old_scale,
+                old_scale.scale(factor),
+            )));
+        }
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+fn describe_sizes(selection: &GraphSelection, graph: &Graph) -> String {
+    let root_nodes = selection.root_nodes(graph);
+    if root_nodes.is_empty() {
+        return NO_SELECTION.to_owned();
+    }
+
+    let mut text = String::from("Current size (world space):");
+    for &handle in root_nodes.iter().take(MAX_DESCRIBED_NODES) {
+        let name = graph[handle].name();
+        match hierarchy_bounds(graph, handle) {
+            Some(bounds) => {
+                let size = bounds.max - bounds.min;
+                text += &format!("\n{}: {:.3} x {:.3} x {:.3}", name, size.x, size.y, size.z);
+            }
+            None => text += &format!("\n{}: no geometry", name),
+        }
+    }
+    if root_nodes.len() > MAX_DESCRIBED_NODES {
+        text += &format!("\n...and {} more", root_nodes.len() - MAX_DESCRIBED_NODES);
+    }
+    text
+}
+
+pub struct NormalizeScaleWindow {
+    pub window: Handle<UiNode>,
+    sizes: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    apply: Handle<UiNode>,
+    close: Handle<UiNode>,
+    options: NormalizeScaleOptions,
+}
+
+impl NormalizeScaleWindow {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let options = NormalizeScaleOptions::default();
+
+        let container = make_property_editors_container(sender);
+        container.insert(InspectablePropertyEditorDefinition::<NormalizeScaleOptions>::new());
+        container.insert(EnumPropertyEditorDefinition::<ReferenceAxis>::new());
+
+        let context = InspectorContext::from_object(
+            &options,
+            ctx,
+            Rc::new(container),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+        );
+
+        let sizes;
+        let inspector;
+        let apply;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(280.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Normalize Scale"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            sizes = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            sizes
+                        })
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                    .with_context(context)
+                                    .build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        apply = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Apply")
+                                        .build(ctx);
+                                        apply
+                                    })
+                                    .with_child({
+                                        close = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Close")
+                                        .build(ctx);
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            sizes,
+            inspector,
+            apply,
+            close,
+            options,
+        }
+    }
+
+    pub fn open(&self, editor_scene: &EditorScene, engine: &Engine) {
+        engine.user_interface.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        self.sync_sizes(editor_scene, engine, true);
+    }
+
+    fn sync_sizes(&self, editor_scene: &EditorScene, engine: &Engine, force: bool) {
+        let ui = &engine.user_interface;
+
+        if !force && !ui.node(self.window).visibility() {
+            return;
+        }
+
+        let text = match &editor_scene.selection {
+            Selection::Graph(selection) => {
+                describe_sizes(selection, &engine.scenes[editor_scene.scene].graph)
+            }
+            _ => NO_SELECTION.to_owned(),
+        };
+
+        ui.send_message(TextMessage::text(
+            self.sizes,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    /// Updates sizes of the selected nodes, it must be called after every change of the scene,
+    /// because the selection and the nodes could change.
+    pub fn sync_to_model(&self, editor_scene: &EditorScene, engine: &Engine) {
+        self.sync_sizes(editor_scene, engine, false);
+    }
+
+    fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &Sender<Message>,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.apply {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    if let Some(command) = make_normalize_scale_command(
+                        selection,
+                        &engine.scenes[editor_scene.scene].graph,
+                        &self.options,
+                    ) {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.close {
+                self.close(&engine.user_interface);
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                Log::verify(
+                    PropertyAction::from_field_kind(&property_changed.value)
+                        .apply(&property_changed.path(), &mut self.options),
+                );
+            }
+        }
+    }
+}
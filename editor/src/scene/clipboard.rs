@@ -49,6 +49,15 @@ fn deep_clone_nodes(
     result
 }
 
+/// Creates copies of the given nodes with all their descendants in the same graph. Copies are
+/// attached to the root of the graph.
+pub fn duplicate_nodes(root_nodes: &[Handle<Node>], graph: &mut Graph) -> DeepCloneResult {
+    // A graph cannot copy nodes into itself, so copy them through a temporary graph.
+    let mut temp_graph = Graph::new();
+    let temp_result = deep_clone_nodes(root_nodes, graph, &mut temp_graph);
+    deep_clone_nodes(&temp_result.root_nodes, &temp_graph, graph)
+}
+
 impl Clipboard {
     pub fn fill_from_selection(
         &mut self,
@@ -1,14 +1,30 @@
-use crate::{command::Command, scene::commands::SceneContext};
+use crate::{
+    camera::surface_normal_below,
+    command::Command,
+    scene::commands::{CommandGroup, SceneCommand, SceneContext},
+    settings::{
+        move_mode::MoveInteractionModeSettings, rotate_mode::RotateInteractionModeSettings,
+    },
+    world::graph::selection::GraphSelection,
+};
 use fyrox::{
     core::{
-        algebra::{UnitQuaternion, Vector3},
+        algebra::{Matrix4, Unit, UnitQuaternion, Vector3},
+        fxhash::FxHashSet,
+        math::{round_to_step, Matrix4Ext},
         pool::{Handle, Ticket},
     },
     scene::{
         base::Base,
+        collider::{Collider, ColliderShape},
         graph::{Graph, SubGraph},
+        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight, BaseLight},
         node::Node,
+        pivot::Pivot,
+        transform::{Transform, TransformBuilder},
+        trigger::{TriggerShape, TriggerVolume},
     },
+    utils::log::Log,
 };
 
 #[derive(Debug)]
@@ -148,6 +164,208 @@ impl Command for RotateNodeCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct ResizeTriggerVolumeCommand {
+    node: Handle<Node>,
+    old_shape: TriggerShape,
+    new_shape: TriggerShape,
+}
+
+impl ResizeTriggerVolumeCommand {
+    pub fn new(node: Handle<Node>, old_shape: TriggerShape, new_shape: TriggerShape) -> Self {
+        Self {
+            node,
+            old_shape,
+            new_shape,
+        }
+    }
+
+    fn swap(&mut self) -> TriggerShape {
+        let shape = self.new_shape;
+        std::mem::swap(&mut self.new_shape, &mut self.old_shape);
+        shape
+    }
+
+    fn set_shape(&self, graph: &mut Graph, shape: TriggerShape) {
+        if let Some(volume) = graph[self.node].cast_mut::<TriggerVolume>() {
+            volume.set_shape(shape);
+        }
+    }
+}
+
+impl Command for ResizeTriggerVolumeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Resize Trigger Volume".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let shape = self.swap();
+        self.set_shape(&mut context.scene.graph, shape);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let shape = self.swap();
+        self.set_shape(&mut context.scene.graph, shape);
+    }
+}
+
+#[derive(Debug)]
+pub struct SetColliderShapeCommand {
+    node: Handle<Node>,
+    shape: ColliderShape,
+}
+
+impl SetColliderShapeCommand {
+    pub fn new(node: Handle<Node>, shape: ColliderShape) -> Self {
+        Self { node, shape }
+    }
+
+    fn swap(&mut self, graph: &mut Graph) {
+        if let Some(collider) = graph[self.node].cast_mut::<Collider>() {
+            let old_shape = collider.set_shape(self.shape.clone());
+            self.shape = old_shape;
+        }
+    }
+}
+
+impl Command for SetColliderShapeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Collider Shape".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+}
+
+/// Turns editor-only hidden state of the nodes into their actual visibility, so they will be
+/// saved as invisible.
+#[derive(Debug)]
+pub struct BakeNodesVisibilityCommand {
+    nodes: Vec<Handle<Node>>,
+}
+
+impl BakeNodesVisibilityCommand {
+    pub fn new(nodes: Vec<Handle<Node>>) -> Self {
+        Self { nodes }
+    }
+}
+
+impl Command for BakeNodesVisibilityCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Bake Visibility".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        // Hidden nodes are already invisible, so just forget about them.
+        for node in self.nodes.iter() {
+            context.editor_scene.hidden_nodes.remove(node);
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        context
+            .editor_scene
+            .hidden_nodes
+            .extend(self.nodes.iter().cloned());
+    }
+}
+
+/// Sets visibility of the nodes.
+#[derive(Debug)]
+pub struct SetNodesVisibilityCommand {
+    nodes: Vec<(Handle<Node>, bool)>,
+}
+
+impl SetNodesVisibilityCommand {
+    pub fn new(nodes: Vec<Handle<Node>>, visibility: bool) -> Self {
+        Self {
+            nodes: nodes.into_iter().map(|n| (n, visibility)).collect(),
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        for (node, visibility) in self.nodes.iter_mut() {
+            *visibility = context.scene.graph[*node].set_visibility(*visibility);
+        }
+    }
+}
+
+impl Command for SetNodesVisibilityCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Visibility".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+/// Sets enabled state of the nodes. Disabled nodes (and their descendants) are not updated,
+/// rendered and do not run scripts.
+#[derive(Debug)]
+pub struct SetNodesEnabledCommand {
+    nodes: Vec<(Handle<Node>, bool)>,
+}
+
+impl SetNodesEnabledCommand {
+    pub fn new(nodes: Vec<Handle<Node>>, enabled: bool) -> Self {
+        Self {
+            nodes: nodes.into_iter().map(|n| (n, enabled)).collect(),
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        for (node, enabled) in self.nodes.iter_mut() {
+            let node = &mut context.scene.graph[*node];
+            let old = node.is_enabled();
+            node.set_enabled(*enabled);
+            *enabled = old;
+        }
+    }
+}
+
+impl Command for SetNodesEnabledCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Enabled".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+/// Creates a command that toggles enabled state of selected nodes. All nodes get the same state:
+/// they're disabled if the first selected node is enabled and vice versa. The root of the graph
+/// cannot be disabled.
+pub fn make_toggle_enabled_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let nodes = selection
+        .nodes()
+        .iter()
+        .filter(|&&n| n != graph.get_root() && graph.is_valid_handle(n))
+        .cloned()
+        .collect::<Vec<_>>();
+    let enabled = !graph[*nodes.first()?].is_enabled();
+    Some(SceneCommand::new(SetNodesEnabledCommand::new(
+        nodes, enabled,
+    )))
+}
+
 #[derive(Debug)]
 pub struct LinkNodesCommand {
     child: Handle<Node>,
@@ -180,6 +398,80 @@ impl Command for LinkNodesCommand {
     }
 }
 
+/// Relinks the children to the end of the list of children of the parent in the given order.
+fn set_children_order(graph: &mut Graph, parent: Handle<Node>, order: &[Handle<Node>]) {
+    for &child in order {
+        graph.link_nodes(child, parent);
+    }
+}
+
+/// Changes order of children of a node, for example to change order of points of a path. The order
+/// must contain every child of the node.
+#[derive(Debug)]
+pub struct SetChildrenOrderCommand {
+    parent: Handle<Node>,
+    order: Vec<Handle<Node>>,
+}
+
+impl SetChildrenOrderCommand {
+    pub fn new(parent: Handle<Node>, order: Vec<Handle<Node>>) -> Self {
+        Self { parent, order }
+    }
+
+    fn swap(&mut self, graph: &mut Graph) {
+        let old_order = graph[self.parent].children().to_vec();
+        set_children_order(graph, self.parent, &self.order);
+        self.order = old_order;
+    }
+}
+
+impl Command for SetChildrenOrderCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Children Order".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(&mut context.scene.graph);
+    }
+}
+
+#[derive(Debug)]
+pub struct SetNodeTransformCommand {
+    handle: Handle<Node>,
+    transform: Transform,
+}
+
+impl SetNodeTransformCommand {
+    pub fn new(handle: Handle<Node>, transform: Transform) -> Self {
+        Self { handle, transform }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        std::mem::swap(
+            context.scene.graph[self.handle].local_transform_mut(),
+            &mut self.transform,
+        );
+    }
+}
+
+impl Command for SetNodeTransformCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Node Transform".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
 #[derive(Debug)]
 pub struct DeleteNodeCommand {
     handle: Handle<Node>,
@@ -263,6 +555,7 @@ pub struct DeleteSubGraphCommand {
     sub_graph_root: Handle<Node>,
     sub_graph: Option<SubGraph>,
     parent: Handle<Node>,
+    index: usize,
 }
 
 impl DeleteSubGraphCommand {
@@ -271,6 +564,7 @@ impl DeleteSubGraphCommand {
             sub_graph_root,
             sub_graph: None,
             parent: Handle::NONE,
+            index: 0,
         }
     }
 }
@@ -281,7 +575,13 @@ impl Command for DeleteSubGraphCommand {
     }
 
     fn execute(&mut self, context: &mut SceneContext) {
-        self.parent = context.scene.graph[self.sub_graph_root].parent();
+        let graph = &context.scene.graph;
+        self.parent = graph[self.sub_graph_root].parent();
+        self.index = graph[self.parent]
+            .children()
+            .iter()
+            .position(|&c| c == self.sub_graph_root)
+            .unwrap_or_default();
         self.sub_graph = Some(
             context
                 .scene
@@ -295,10 +595,15 @@ impl Command for DeleteSubGraphCommand {
             .scene
             .graph
             .put_sub_graph_back(self.sub_graph.take().unwrap());
-        context
-            .scene
-            .graph
-            .link_nodes(self.sub_graph_root, self.parent);
+        let graph = &mut context.scene.graph;
+        graph.link_nodes(self.sub_graph_root, self.parent);
+
+        // Put the root back to its place among the children by moving the children that were
+        // after it to the end, order of children matters for paths and for the world viewer.
+        let children = graph[self.parent].children();
+        let next_siblings =
+            children[self.index.min(children.len() - 1)..children.len() - 1].to_vec();
+        set_children_order(graph, self.parent, &next_siblings);
     }
 
     fn finalize(&mut self, context: &mut SceneContext) {
@@ -403,3 +708,468 @@ impl Command for ReplaceNodeCommand {
         self.swap(context);
     }
 }
+
+/// Type of a light source, used as a target of light conversion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LightKind {
+    Point,
+    Spot,
+    Directional,
+}
+
+impl LightKind {
+    fn of(node: &Node) -> Option<Self> {
+        if node.cast::<PointLight>().is_some() {
+            Some(Self::Point)
+        } else if node.cast::<SpotLight>().is_some() {
+            Some(Self::Spot)
+        } else if node.cast::<DirectionalLight>().is_some() {
+            Some(Self::Directional)
+        } else {
+            None
+        }
+    }
+}
+
+/// Creates a command that converts selected light sources to lights of the given kind. Color,
+/// intensity, shadows and scattering are carried over, point and spot lights also share their
+/// range and shadow bias. Everything else gets default values of the new type. Name, transform
+/// and hierarchy of nodes are kept intact. Returns `None` if there's nothing to convert.
+pub fn make_convert_lights_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    kind: LightKind,
+) -> Option<SceneCommand> {
+    let mut commands = Vec::new();
+
+    for &handle in selection.nodes() {
+        let node = match graph.try_get(handle) {
+            Some(node) if LightKind::of(node).map_or(false, |k| k != kind) => node,
+            _ => continue,
+        };
+
+        let base_light = match node.query_component_ref::<BaseLight>() {
+            Some(base_light) => base_light.clone(),
+            None => continue,
+        };
+
+        let (range, shadow_bias) = if let Some(point) = node.cast::<PointLight>() {
+            (Some(point.radius()), Some(point.shadow_bias()))
+        } else if let Some(spot) = node.cast::<SpotLight>() {
+            (Some(spot.distance()), Some(spot.shadow_bias()))
+        } else {
+            (None, None)
+        };
+
+        let replacement = match kind {
+            LightKind::Point => {
+                let mut point = PointLight::default();
+                *point.base_light_mut() = base_light;
+                if let Some(range) = range {
+                    point.set_radius(range);
+                }
+                if let Some(shadow_bias) = shadow_bias {
+                    point.set_shadow_bias(shadow_bias);
+                }
+                Node::new(point)
+            }
+            LightKind::Spot => {
+                let mut spot = SpotLight::default();
+                *spot.base_light_mut() = base_light;
+                if let Some(range) = range {
+                    spot.set_distance(range);
+                }
+                if let Some(shadow_bias) = shadow_bias {
+                    spot.set_shadow_bias(shadow_bias);
+                }
+                Node::new(spot)
+            }
+            LightKind::Directional => Node::new(DirectionalLight::from(base_light)),
+        };
+
+        commands.push(SceneCommand::new(ReplaceNodeCommand {
+            handle,
+            node: replacement,
+        }));
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+fn snap_to_step(value: f32, step: f32) -> f32 {
+    // Zero step means that snapping is disabled for an axis.
+    if step > 0.0 {
+        round_to_step(value, step)
+    } else {
+        value
+    }
+}
+
+/// Creates a command that rounds local positions and/or rotations of selected nodes to the grid
+/// increments from the settings of move and rotate interaction modes. Snapping is applied even if
+/// it is disabled for interaction modes. Returns `None` if nothing will be changed.
+pub fn make_snap_to_grid_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    move_settings: Option<&MoveInteractionModeSettings>,
+    rotate_settings: Option<&RotateInteractionModeSettings>,
+) -> Option<SceneCommand> {
+    let mut group = CommandGroup::from(Vec::new());
+    let mut is_empty = true;
+
+    for &node_handle in selection.nodes() {
+        let transform = graph[node_handle].local_transform();
+
+        if let Some(settings) = move_settings {
+            let old_position = **transform.position();
+            let new_position = Vector3::new(
+                snap_to_step(old_position.x, settings.x_snap_step),
+                snap_to_step(old_position.y, settings.y_snap_step),
+                snap_to_step(old_position.z, settings.z_snap_step),
+            );
+            if new_position.metric_distance(&old_position) > f32::EPSILON {
+                group.push(SceneCommand::new(MoveNodeCommand::new(
+                    node_handle,
+                    old_position,
+                    new_position,
+                )));
+                is_empty = false;
+            }
+        }
+
+        if let Some(settings) = rotate_settings {
+            let old_rotation = **transform.rotation();
+            // Same axes mapping as in the rotate interaction mode.
+            let (roll, pitch, yaw) = old_rotation.euler_angles();
+            let new_rotation = UnitQuaternion::from_euler_angles(
+                snap_to_step(roll, settings.z_snap_step.to_radians()),
+                snap_to_step(pitch, settings.x_snap_step.to_radians()),
+                snap_to_step(yaw, settings.y_snap_step.to_radians()),
+            );
+            // Conversion to Euler angles and back is not exact, so ignore tiny differences.
+            if new_rotation.angle_to(&old_rotation) > 1.0e-4 {
+                group.push(SceneCommand::new(RotateNodeCommand::new(
+                    node_handle,
+                    old_rotation,
+                    new_rotation,
+                )));
+                is_empty = false;
+            }
+        }
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(SceneCommand::new(group))
+    }
+}
+
+/// Height above a node from which a ray is cast down to find a surface the node stands on.
+pub const SURFACE_PROBE_HEIGHT: f32 = 1.0;
+
+/// Rotates the given global rotation so its up axis points along the surface normal. `alignment`
+/// blends target up axis between world up (0.0) and the surface normal (1.0), partial alignment
+/// helps to avoid extreme tilts on steep slopes. Rotation around the up axis is preserved.
+pub fn align_rotation_to_normal(
+    rotation: UnitQuaternion<f32>,
+    normal: Vector3<f32>,
+    alignment: f32,
+) -> UnitQuaternion<f32> {
+    let target_up =
+        Vector3::y_axis().slerp(&Unit::new_normalize(normal), alignment.clamp(0.0, 1.0));
+    let current_up = rotation * Vector3::y_axis();
+    UnitQuaternion::rotation_between_axis(&current_up, &target_up)
+        .unwrap_or_else(UnitQuaternion::identity)
+        * rotation
+}
+
+/// Creates a command that rotates selected nodes so their up axes are aligned with normals of
+/// surfaces under them. Surfaces are searched by a ray cast down from above of every node, the
+/// selected nodes (with descendants) are ignored. Returns `None` if nothing will be changed.
+pub fn make_align_to_surface_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    editor_objects_root: Handle<Node>,
+    alignment: f32,
+) -> Option<SceneCommand> {
+    let root_nodes = selection.root_nodes(graph);
+
+    let ignored = root_nodes
+        .iter()
+        .flat_map(|&root| graph.traverse_handle_iter(root))
+        .collect::<FxHashSet<_>>();
+
+    let mut group = CommandGroup::from(Vec::new());
+    let mut is_empty = true;
+
+    for node_handle in root_nodes {
+        let node = &graph[node_handle];
+
+        let origin = node.global_position() + Vector3::new(0.0, SURFACE_PROBE_HEIGHT, 0.0);
+        let normal = match surface_normal_below(graph, origin, editor_objects_root, |h, _| {
+            !ignored.contains(&h)
+        }) {
+            Some(normal) => normal,
+            None => continue,
+        };
+
+        let global_rotation = graph.global_rotation(node_handle);
+        let new_global_rotation = align_rotation_to_normal(global_rotation, normal, alignment);
+
+        // Convert the rotation back to the local space of the node.
+        let parent_rotation = if node.parent().is_some() {
+            graph.global_rotation(node.parent())
+        } else {
+            UnitQuaternion::identity()
+        };
+        let old_rotation = **node.local_transform().rotation();
+        let new_rotation = parent_rotation.inverse() * new_global_rotation;
+
+        if new_rotation.angle_to(&old_rotation) > 1.0e-4 {
+            group.push(SceneCommand::new(RotateNodeCommand::new(
+                node_handle,
+                old_rotation,
+                new_rotation,
+            )));
+            is_empty = false;
+        }
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(SceneCommand::new(group))
+    }
+}
+
+/// Defines where descendants of a node are moved by [`make_flatten_hierarchy_command`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FlattenTarget {
+    /// Descendants become direct children of the node itself.
+    Node,
+    /// Descendants become siblings of the node.
+    Parent,
+}
+
+// Decomposes the matrix into a transform with translation, rotation and scale only. Shear (that
+// appears when a rotated node has non-uniformly scaled ancestor) cannot be represented, so the
+// second value tells whether the decomposition is exact.
+pub fn decompose_matrix(matrix: &Matrix4<f32>) -> (Transform, bool) {
+    let basis = matrix.basis();
+    let mut scale = Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    );
+    if basis.determinant() < 0.0 {
+        scale.x = -scale.x;
+    }
+    let mut rotation_matrix = basis;
+    for i in 0..3 {
+        if scale[i].abs() > f32::EPSILON {
+            rotation_matrix.set_column(i, &(basis.column(i) / scale[i]));
+        }
+    }
+    let rotation = UnitQuaternion::from_matrix(&rotation_matrix);
+
+    let transform = TransformBuilder::new()
+        .with_local_position(matrix.position())
+        .with_local_rotation(rotation)
+        .with_local_scale(scale)
+        .build();
+
+    let is_exact = transform
+        .matrix()
+        .iter()
+        .zip(matrix.iter())
+        .all(|(a, b)| (a - b).abs() <= 1.0e-3 * b.abs().max(1.0));
+
+    (transform, is_exact)
+}
+
+/// Creates a command that moves every descendant of selected nodes directly under the nodes (or
+/// under their parents) keeping world transforms of the descendants intact. Optionally, pivots
+/// that were in the middle of the hierarchy (and thus became empty) are deleted. Only the transforms
+/// and the hierarchy are changed, so shared data (like surfaces of meshes) is left untouched.
+pub fn make_flatten_hierarchy_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    target: FlattenTarget,
+    delete_empty_pivots: bool,
+) -> Option<SceneCommand> {
+    let mut links = Vec::new();
+    let mut transforms = Vec::new();
+    let mut deletions = Vec::new();
+    let mut sheared = Vec::new();
+
+    for &root in selection.root_nodes(graph).iter() {
+        let new_parent = match target {
+            FlattenTarget::Node => root,
+            FlattenTarget::Parent => graph[root].parent(),
+        };
+        if new_parent.is_none() {
+            continue;
+        }
+
+        let parent_inv_transform = graph[new_parent]
+            .global_transform()
+            .try_inverse()
+            .unwrap_or_else(Matrix4::identity);
+
+        let mut intermediates = Vec::new();
+        if target == FlattenTarget::Parent {
+            intermediates.push(root);
+        }
+
+        let mut stack = graph[root].children().to_vec();
+        while let Some(handle) = stack.pop() {
+            let node = &graph[handle];
+            stack.extend_from_slice(node.children());
+
+            if !node.children().is_empty() {
+                intermediates.push(handle);
+            }
+
+            if node.parent() == new_parent {
+                continue;
+            }
+
+            links.push(SceneCommand::new(LinkNodesCommand::new(handle, new_parent)));
+
+            let (transform, is_exact) =
+                decompose_matrix(&(parent_inv_transform * node.global_transform()));
+            if !is_exact {
+                sheared.push(node.name().to_owned());
+            }
+            transforms.push(SceneCommand::new(SetNodeTransformCommand::new(
+                handle, transform,
+            )));
+        }
+
+        if delete_empty_pivots {
+            for handle in intermediates {
+                let node = &graph[handle];
+                // Nodes of prefab instances will be restored on next resolve, keep them.
+                if node.cast::<Pivot>().is_some() && node.resource().is_none() {
+                    deletions.push(SceneCommand::new(DeleteSubGraphCommand::new(handle)));
+                }
+            }
+        }
+    }
+
+    if !sheared.is_empty() {
+        Log::warn(format!(
+            "Transforms of the following nodes have shear, it cannot be preserved and the nodes may \
+            look different: {}",
+            sheared.join(", ")
+        ));
+    }
+
+    if links.is_empty() && deletions.is_empty() {
+        return None;
+    }
+
+    // Nodes must be detached from pivots before the pivots could be deleted.
+    let mut group = CommandGroup::from(links);
+    for command in transforms.into_iter().chain(deletions) {
+        group.push(command);
+    }
+    Some(SceneCommand::new(group))
+}
+
+/// Creates a command that links given nodes to a new parent keeping their world transforms
+/// intact, so the nodes do not move. Nodes that are already children of the parent are skipped,
+/// as well as nodes that have the parent among their descendants (linking them would create a
+/// cycle). Returns `None` if nothing will be changed.
+pub fn make_link_keeping_transform_command(
+    nodes: &[Handle<Node>],
+    new_parent: Handle<Node>,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let parent_inv_transform = graph[new_parent]
+        .global_transform()
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity);
+
+    let mut links = Vec::new();
+    let mut transforms = Vec::new();
+    let mut sheared = Vec::new();
+
+    for &handle in nodes {
+        let node = &graph[handle];
+        if handle == new_parent || node.parent() == new_parent {
+            continue;
+        }
+
+        // Make sure we won't create any loops - the node must not have the parent in its
+        // descendants.
+        let mut p = new_parent;
+        while p.is_some() && p != handle {
+            p = graph[p].parent();
+        }
+        if p == handle {
+            continue;
+        }
+
+        links.push(SceneCommand::new(LinkNodesCommand::new(handle, new_parent)));
+
+        let (transform, is_exact) =
+            decompose_matrix(&(parent_inv_transform * node.global_transform()));
+        if !is_exact {
+            sheared.push(node.name().to_owned());
+        }
+        transforms.push(SceneCommand::new(SetNodeTransformCommand::new(
+            handle, transform,
+        )));
+    }
+
+    if !sheared.is_empty() {
+        Log::warn(format!(
+            "Transforms of the following nodes have shear, it cannot be preserved and the nodes may \
+            look different: {}",
+            sheared.join(", ")
+        ));
+    }
+
+    if links.is_empty() {
+        return None;
+    }
+
+    let mut group = CommandGroup::from(links);
+    for command in transforms {
+        group.push(command);
+    }
+    Some(SceneCommand::new(group))
+}
+
+/// Creates a command that links selected nodes directly to the root of the scene keeping their
+/// world transforms, the keyboard equivalent of dragging the nodes to the root in the world viewer.
+pub fn make_clear_parent_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    make_link_keeping_transform_command(&selection.root_nodes(graph), graph.get_root(), graph)
+}
+
+/// Creates a command that links selected nodes to the node that was selected last keeping their
+/// world transforms. Returns `None` if less than two nodes are selected.
+pub fn make_parent_to_last_selected_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    if !selection.is_multi_selection() {
+        return None;
+    }
+    let parent = *selection.nodes().last()?;
+    let children = selection
+        .root_nodes(graph)
+        .into_iter()
+        .filter(|&handle| handle != parent)
+        .collect::<Vec<_>>();
+    make_link_keeping_transform_command(&children, parent, graph)
+}
@@ -1,7 +1,8 @@
 use crate::{command::Command, scene::commands::SceneContext};
 use fyrox::{
-    core::sstorage::ImmutableString,
-    material::{shader::Shader, Material, PropertyValue, SharedMaterial},
+    core::{pool::Handle, sstorage::ImmutableString},
+    material::{shader::Shader, CullMode, Material, PropertyValue, SharedMaterial},
+    scene::{mesh::Mesh, node::Node},
 };
 
 #[derive(Debug)]
@@ -45,6 +46,39 @@ impl Command for SetMaterialPropertyValueCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct SetMaterialCullModeCommand {
+    material: SharedMaterial,
+    cull_mode: CullMode,
+}
+
+impl SetMaterialCullModeCommand {
+    pub fn new(material: SharedMaterial, cull_mode: CullMode) -> Self {
+        Self {
+            material,
+            cull_mode,
+        }
+    }
+
+    fn swap(&mut self) {
+        self.cull_mode = self.material.lock().set_cull_mode(self.cull_mode);
+    }
+}
+
+impl Command for SetMaterialCullModeCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Set Material Cull Mode".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut SceneContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut SceneContext) {
+        self.swap();
+    }
+}
+
 #[derive(Debug)]
 enum SetMaterialShaderCommandState {
     Undefined,
@@ -75,10 +109,12 @@ impl SetMaterialShaderCommand {
             SetMaterialShaderCommandState::NonExecuted { new_shader } => {
                 let mut material = self.material.lock();
 
-                let old_material = std::mem::replace(
-                    &mut *material,
-                    Material::from_shader(new_shader, Some(context.resource_manager.clone())),
-                );
+                let mut new_material =
+                    Material::from_shader(new_shader, Some(context.resource_manager.clone()));
+                // Cull mode does not depend on shader, so keep it.
+                new_material.set_cull_mode(material.cull_mode());
+
+                let old_material = std::mem::replace(&mut *material, new_material);
 
                 self.state = SetMaterialShaderCommandState::Executed { old_material };
             }
@@ -113,3 +149,80 @@ impl Command for SetMaterialShaderCommand {
         self.swap(ctx);
     }
 }
+
+/// Replaces the entire content of a material (shader, properties, etc.) with the given one.
+#[derive(Debug)]
+pub struct SetMaterialCommand {
+    material: SharedMaterial,
+    value: Material,
+}
+
+impl SetMaterialCommand {
+    pub fn new(material: SharedMaterial, value: Material) -> Self {
+        Self { material, value }
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut *self.material.lock(), &mut self.value);
+    }
+}
+
+impl Command for SetMaterialCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Set Material".to_owned()
+    }
+
+    fn execute(&mut self, _: &mut SceneContext) {
+        self.swap();
+    }
+
+    fn revert(&mut self, _: &mut SceneContext) {
+        self.swap();
+    }
+}
+
+/// Replaces a material with another one on every surface in the scene that uses it.
+#[derive(Debug)]
+pub struct RelinkSurfaceMaterialCommand {
+    old_material: SharedMaterial,
+    new_material: SharedMaterial,
+    surfaces: Vec<(Handle<Node>, usize)>,
+}
+
+impl RelinkSurfaceMaterialCommand {
+    pub fn new(old_material: SharedMaterial, new_material: SharedMaterial) -> Self {
+        Self {
+            old_material,
+            new_material,
+            surfaces: Default::default(),
+        }
+    }
+}
+
+impl Command for RelinkSurfaceMaterialCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Relink Surface Material".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.surfaces.clear();
+        for (handle, node) in context.scene.graph.pair_iter_mut() {
+            if let Some(mesh) = node.cast_mut::<Mesh>() {
+                for (index, surface) in mesh.surfaces_mut().iter_mut().enumerate() {
+                    if surface.material().key() == self.old_material.key() {
+                        surface.set_material(self.new_material.clone());
+                        self.surfaces.push((handle, index));
+                    }
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        for &(handle, index) in self.surfaces.iter() {
+            if let Some(mesh) = context.scene.graph[handle].cast_mut::<Mesh>() {
+                mesh.surfaces_mut()[index].set_material(self.old_material.clone());
+            }
+        }
+    }
+}
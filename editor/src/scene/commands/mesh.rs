@@ -1,9 +1,41 @@
-use crate::{command::Command, scene::commands::SceneContext};
+use crate::{
+    command::Command,
+    scene::{
+        commands::{
+            graph::{
+                decompose_matrix, AddNodeCommand, DeleteSubGraphCommand, LinkNodesCommand,
+                MoveNodeCommand, SetNodeTransformCommand, SetNodesVisibilityCommand,
+            },
+            ChangeSelectionCommand, CommandGroup, SceneCommand, SceneContext,
+        },
+        Selection,
+    },
+    world::graph::selection::GraphSelection,
+};
 use fyrox::{
-    core::{pool::Handle, sstorage::ImmutableString},
-    material::{shader::SamplerFallback, PropertyValue},
+    core::{
+        algebra::{Matrix4, Vector3},
+        math::TriangleDefinition,
+        pool::Handle,
+        sstorage::ImmutableString,
+    },
+    fxhash::FxHashSet,
+    material::{shader::SamplerFallback, PropertyValue, SharedMaterial},
     resource::texture::Texture,
-    scene::{mesh::Mesh, node::Node},
+    scene::{
+        base::{BaseBuilder, Property, PropertyValue as NodePropertyValue},
+        graph::Graph,
+        mesh::{
+            buffer::{
+                TriangleBuffer, VertexAttribute, VertexAttributeDescriptor, VertexAttributeUsage,
+                VertexBuffer,
+            },
+            surface::{Surface, SurfaceBuilder, SurfaceData, SurfaceSharedData},
+            Mesh, MeshBuilder,
+        },
+        node::{Node, NodeTrait},
+    },
+    utils::log::Log,
 };
 
 #[derive(Debug)]
@@ -105,3 +137,614 @@ impl Command for SetMeshTextureCommand {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct SetMeshSurfacesDataCommand {
+    node: Handle<Node>,
+    data: Vec<SurfaceSharedData>,
+}
+
+impl SetMeshSurfacesDataCommand {
+    pub fn new(node: Handle<Node>, data: Vec<SurfaceSharedData>) -> Self {
+        Self { node, data }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let mesh: &mut Mesh = context.scene.graph[self.node].as_mesh_mut();
+        assert_eq!(mesh.surfaces().len(), self.data.len());
+        for (surface, data) in mesh.surfaces_mut().iter_mut().zip(self.data.iter_mut()) {
+            *data = surface.set_data(data.clone());
+        }
+    }
+}
+
+impl Command for SetMeshSurfacesDataCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Mesh Surfaces Data".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+/// Replaces all surfaces of a mesh. It is used when both data and materials of surfaces are
+/// changed at once, for example when vertex colors are painted.
+#[derive(Debug)]
+pub struct SetMeshSurfacesCommand {
+    node: Handle<Node>,
+    surfaces: Vec<Surface>,
+}
+
+impl SetMeshSurfacesCommand {
+    pub fn new(node: Handle<Node>, surfaces: Vec<Surface>) -> Self {
+        Self { node, surfaces }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let mesh: &mut Mesh = context.scene.graph[self.node].as_mesh_mut();
+        self.surfaces = mesh.set_surfaces(std::mem::take(&mut self.surfaces));
+    }
+}
+
+impl Command for SetMeshSurfacesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Mesh Surfaces".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+fn translate_positions(data: &mut SurfaceData, offset: Vector3<f32>) {
+    let mut vertex_buffer = data.vertex_buffer.modify();
+    for mut view in vertex_buffer.iter_mut() {
+        if let Ok(position) = view.read_3_f32(VertexAttributeUsage::Position) {
+            let _ = view.write_3_f32(VertexAttributeUsage::Position, position + offset);
+        }
+    }
+}
+
+/// Returns local-space center of the geometry of a mesh and copies of its surfaces data with
+/// the geometry moved so the center is at the origin. Skinned meshes are ignored, because their
+/// vertices are tied to bind poses of the bones.
+fn centered_surfaces_data(mesh: &Mesh) -> Option<(Vector3<f32>, Vec<SurfaceSharedData>)> {
+    if mesh.surfaces().is_empty() || mesh.surfaces().iter().any(|s| !s.bones().is_empty()) {
+        return None;
+    }
+
+    let bounds = mesh.local_bounding_box();
+    if bounds.min.x > bounds.max.x {
+        // Empty geometry.
+        return None;
+    }
+    let center = bounds.center();
+
+    // Data is cloned even if it is used by a single surface, this way the old data is kept
+    // intact and undo is just a matter of putting it back.
+    let data = mesh
+        .surfaces()
+        .iter()
+        .map(|surface| {
+            let data = surface.data().deep_clone();
+            translate_positions(&mut data.lock(), -center);
+            data
+        })
+        .collect();
+
+    Some((center, data))
+}
+
+/// Creates a command that moves pivots of selected meshes to the center of their geometry,
+/// the meshes (and their children) stay in the same place in the world.
+pub fn make_origin_to_geometry_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let mut group = CommandGroup::from(Vec::new());
+    let mut is_empty = true;
+
+    for &node_handle in selection.nodes() {
+        let node = &graph[node_handle];
+        if let Some((center, data)) = node.cast::<Mesh>().and_then(centered_surfaces_data) {
+            let local_transform = node.local_transform();
+            let old_position = **local_transform.position();
+            let new_position = old_position + local_transform.matrix().transform_vector(&center);
+
+            group.push(SceneCommand::new(SetMeshSurfacesDataCommand::new(
+                node_handle,
+                data,
+            )));
+            group.push(SceneCommand::new(MoveNodeCommand::new(
+                node_handle,
+                old_position,
+                new_position,
+            )));
+
+            for &child in node.children() {
+                let old_position = **graph[child].local_transform().position();
+                group.push(SceneCommand::new(MoveNodeCommand::new(
+                    child,
+                    old_position,
+                    old_position - center,
+                )));
+            }
+
+            is_empty = false;
+        }
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(SceneCommand::new(group))
+    }
+}
+
+/// Creates a command that moves geometry of selected meshes so the center of the geometry is at
+/// the pivot of each mesh.
+pub fn make_geometry_to_origin_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let commands = selection
+        .nodes()
+        .iter()
+        .filter_map(|&node_handle| {
+            graph[node_handle]
+                .cast::<Mesh>()
+                .and_then(centered_surfaces_data)
+                .map(|(_, data)| {
+                    SceneCommand::new(SetMeshSurfacesDataCommand::new(node_handle, data))
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+/// Creates a command that moves every surface of selected meshes to a separate child mesh, one
+/// mesh per surface. Selected meshes stay in the graph without surfaces, so their transforms,
+/// children and scripts are kept. Surfaces are moved as is, the data and materials are not copied,
+/// so the visual result is the same and the data is still shared with other meshes that use it.
+pub fn make_split_mesh_command(selection: &GraphSelection, graph: &Graph) -> Option<SceneCommand> {
+    let mut commands = Vec::new();
+
+    for &node_handle in selection.nodes() {
+        let mesh = match graph.try_get(node_handle).and_then(|n| n.cast::<Mesh>()) {
+            Some(mesh) if mesh.surfaces().len() > 1 => mesh,
+            _ => continue,
+        };
+
+        for (i, surface) in mesh.surfaces().iter().enumerate() {
+            // Parts are linked to the mesh with identity transform, so they stay in place.
+            let part = MeshBuilder::new(
+                BaseBuilder::new()
+                    .with_name(format!("{}_{}", mesh.name(), i))
+                    .with_cast_shadows(mesh.cast_shadows()),
+            )
+            .with_surfaces(vec![surface.clone()])
+            .with_render_path(mesh.render_path())
+            .with_decal_layer_index(mesh.decal_layer_index())
+            .build_node();
+            commands.push(SceneCommand::new(AddNodeCommand::new(part, node_handle)));
+        }
+
+        commands.push(SceneCommand::new(SetMeshSurfacesCommand::new(
+            node_handle,
+            Vec::new(),
+        )));
+    }
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(CommandGroup::from(commands)))
+    }
+}
+
+fn is_ancestor(graph: &Graph, ancestor: Handle<Node>, node: Handle<Node>) -> bool {
+    let mut current = graph[node].parent();
+    while current.is_some() {
+        if current == ancestor {
+            return true;
+        }
+        current = graph[current].parent();
+    }
+    false
+}
+
+/// Creates a command that merges surfaces of selected meshes into the first selected mesh. The
+/// geometry of other meshes is copied and moved to the local space of the first mesh, so the
+/// visual result is the same, materials are shared. The other meshes are deleted, their children
+/// are linked to the first mesh keeping their world transforms. Skinned meshes, parts of prefab
+/// instances and ancestors of the first mesh are skipped.
+pub fn make_combine_meshes_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let meshes = selection
+        .nodes()
+        .iter()
+        .cloned()
+        .filter(|h| graph.try_get(*h).and_then(|n| n.cast::<Mesh>()).is_some())
+        .collect::<Vec<_>>();
+    let (&target, others) = meshes.split_first()?;
+
+    let target_inv_transform = graph[target]
+        .global_transform()
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity);
+
+    let mut surfaces = graph[target].as_mesh().surfaces().to_vec();
+    let mut combined = FxHashSet::default();
+    let mut skipped = Vec::new();
+
+    for &handle in others {
+        let node = &graph[handle];
+        let mesh = node.as_mesh();
+
+        if mesh.surfaces().iter().any(|s| !s.bones().is_empty())
+            || (node.resource().is_some() && !node.is_resource_instance_root())
+            || is_ancestor(graph, handle, target)
+        {
+            skipped.push(node.name().to_owned());
+            continue;
+        }
+
+        let transform = target_inv_transform * node.global_transform();
+        let new_surfaces = mesh
+            .surfaces()
+            .iter()
+            .map(|surface| {
+                let data = surface.data().deep_clone();
+                let result = data.lock().transform_geometry(&transform);
+                result.map(|_| {
+                    SurfaceBuilder::new(data)
+                        .with_material(surface.material().clone())
+                        .build()
+                })
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        match new_surfaces {
+            Ok(new_surfaces) => {
+                surfaces.extend(new_surfaces);
+                combined.insert(handle);
+            }
+            Err(_) => skipped.push(node.name().to_owned()),
+        }
+    }
+
+    if !skipped.is_empty() {
+        Log::warn(format!(
+            "The following meshes cannot be combined and were skipped: {}",
+            skipped.join(", ")
+        ));
+    }
+
+    if combined.is_empty() {
+        return None;
+    }
+
+    let mut commands = vec![
+        // Combined meshes will be deleted, so they must not stay selected.
+        SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::single_or_empty(target)),
+            Selection::Graph(selection.clone()),
+        )),
+        SceneCommand::new(SetMeshSurfacesCommand::new(target, surfaces)),
+    ];
+    let mut deletions = Vec::new();
+
+    for &handle in combined.iter() {
+        for &child in graph[handle].children() {
+            if combined.contains(&child) {
+                continue;
+            }
+            commands.push(SceneCommand::new(LinkNodesCommand::new(child, target)));
+            let (transform, _) =
+                decompose_matrix(&(target_inv_transform * graph[child].global_transform()));
+            commands.push(SceneCommand::new(SetNodeTransformCommand::new(
+                child, transform,
+            )));
+        }
+
+        // Meshes nested in other combined meshes are deleted together with them.
+        if !combined
+            .iter()
+            .any(|&other| other != handle && is_ancestor(graph, other, handle))
+        {
+            deletions.push(SceneCommand::new(DeleteSubGraphCommand::new(handle)));
+        }
+    }
+
+    // Children must be detached before their parents are deleted.
+    commands.extend(deletions);
+
+    Some(SceneCommand::new(CommandGroup::from(commands)))
+}
+
+/// Tag of meshes created by [`make_static_batch_command`].
+pub const STATIC_BATCH_TAG: &str = "StaticBatch";
+/// Name of the properties of a static batch that keep handles of its source meshes, so the batch
+/// can be unmerged even after the scene is saved and loaded.
+const STATIC_BATCH_SOURCE_PROPERTY: &str = "StaticBatchSource";
+
+fn same_layout(a: &[VertexAttribute], b: &[VertexAttribute]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.usage == b.usage
+                && a.data_type == b.data_type
+                && a.size == b.size
+                && a.divisor == b.divisor
+                && a.shader_location == b.shader_location
+        })
+}
+
+/// Geometry of all batched surfaces with the same material and vertex layout.
+struct BatchPart {
+    material: SharedMaterial,
+    layout: Vec<VertexAttribute>,
+    vertices: Vec<u8>,
+    vertex_count: u32,
+    triangles: Vec<TriangleDefinition>,
+}
+
+impl BatchPart {
+    fn append(&mut self, data: &SurfaceData) {
+        let offset = self.vertex_count;
+        self.vertices
+            .extend_from_slice(data.vertex_buffer.raw_data());
+        self.vertex_count += data.vertex_buffer.vertex_count();
+        self.triangles.extend(
+            data.geometry_buffer
+                .iter()
+                .map(|t| TriangleDefinition([t[0] + offset, t[1] + offset, t[2] + offset])),
+        );
+    }
+
+    fn into_surface(self) -> Option<Surface> {
+        let layout = self
+            .layout
+            .iter()
+            .map(|a| VertexAttributeDescriptor {
+                usage: a.usage,
+                data_type: a.data_type,
+                size: a.size,
+                divisor: a.divisor,
+                shader_location: a.shader_location,
+            })
+            .collect::<Vec<_>>();
+        let vertex_buffer =
+            VertexBuffer::new(self.vertex_count as usize, &layout, self.vertices).ok()?;
+        let data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(self.triangles), true);
+        Some(
+            SurfaceBuilder::new(SurfaceSharedData::new(data))
+                .with_material(self.material)
+                .build(),
+        )
+    }
+}
+
+/// Checks whether a mesh can be a part of a static batch: it must be visible, not skinned and not
+/// moved by physics.
+fn is_static_mesh(graph: &Graph, handle: Handle<Node>) -> bool {
+    let node = &graph[handle];
+    let mesh = match node.cast::<Mesh>() {
+        Some(mesh) => mesh,
+        None => return false,
+    };
+
+    if !node.global_visibility()
+        || node.tag() == STATIC_BATCH_TAG
+        || mesh.surfaces().iter().any(|s| !s.bones().is_empty())
+    {
+        return false;
+    }
+
+    let mut current = node.parent();
+    while let Some(parent) = graph.try_get(current) {
+        if parent.is_rigid_body() || parent.is_rigid_body2d() {
+            return false;
+        }
+        current = parent.parent();
+    }
+
+    true
+}
+
+/// Creates a command that merges selected meshes and their descendant meshes into a single mesh,
+/// one surface per material, to reduce the amount of draw calls for many small static props.
+/// World transforms are baked into the vertices of the batch, so it is added to the root with
+/// identity transform. Source meshes are hidden and kept in the scene, see
+/// [`make_unmerge_static_batch_command`]. Meshes with children that are not batched (lights,
+/// sounds, etc.) are skipped, because hiding them would hide the children too.
+pub fn make_static_batch_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let mut candidates = Vec::new();
+    let mut visited = FxHashSet::default();
+    for &root in selection.nodes() {
+        for handle in graph.traverse_handle_iter(root) {
+            if visited.insert(handle) {
+                candidates.push(handle);
+            }
+        }
+    }
+
+    let static_meshes = candidates
+        .iter()
+        .cloned()
+        .filter(|&h| is_static_mesh(graph, h))
+        .collect::<FxHashSet<_>>();
+
+    let mut sources = Vec::new();
+    let mut parts: Vec<BatchPart> = Vec::new();
+    let mut skipped = 0;
+
+    for &handle in candidates.iter() {
+        let node = &graph[handle];
+        if node.cast::<Mesh>().is_none() {
+            continue;
+        }
+
+        if !static_meshes.contains(&handle)
+            || !graph
+                .traverse_handle_iter(handle)
+                .all(|h| static_meshes.contains(&h))
+        {
+            skipped += 1;
+            continue;
+        }
+
+        let transformed = node
+            .as_mesh()
+            .surfaces()
+            .iter()
+            .map(|surface| {
+                let data = surface.data().deep_clone();
+                let result = data.lock().transform_geometry(&node.global_transform());
+                result.map(|_| (surface.material().clone(), data))
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        let transformed = match transformed {
+            Ok(transformed) => transformed,
+            Err(_) => {
+                skipped += 1;
+                continue;
+            }
+        };
+
+        for (material, data) in transformed {
+            let data = data.lock();
+            let layout = data.vertex_buffer.layout();
+            let part = match parts
+                .iter_mut()
+                .position(|p| p.material.key() == material.key() && same_layout(&p.layout, layout))
+            {
+                Some(index) => &mut parts[index],
+                None => {
+                    parts.push(BatchPart {
+                        material,
+                        layout: layout.to_vec(),
+                        vertices: Vec::new(),
+                        vertex_count: 0,
+                        triangles: Vec::new(),
+                    });
+                    parts.last_mut().unwrap()
+                }
+            };
+            part.append(&data);
+        }
+
+        sources.push(handle);
+    }
+
+    if skipped > 0 {
+        Log::warn(format!(
+            "{} meshes cannot be batched (skinned, moved by physics, hidden or with other \
+            children) and were skipped.",
+            skipped
+        ));
+    }
+
+    if sources.is_empty() {
+        return None;
+    }
+
+    let surfaces = parts
+        .into_iter()
+        .map(BatchPart::into_surface)
+        .collect::<Option<Vec<_>>>()?;
+
+    Log::info(format!(
+        "{} meshes were merged into a static batch with {} surfaces.",
+        sources.len(),
+        surfaces.len()
+    ));
+
+    let mut batch = MeshBuilder::new(
+        BaseBuilder::new()
+            .with_name("StaticBatch")
+            .with_tag(STATIC_BATCH_TAG.to_owned()),
+    )
+    .with_surfaces(surfaces)
+    .build_node();
+    batch.set_properties(
+        sources
+            .iter()
+            .map(|&source| Property {
+                name: STATIC_BATCH_SOURCE_PROPERTY.to_owned(),
+                value: NodePropertyValue::NodeHandle(source),
+            })
+            .collect(),
+    );
+
+    Some(SceneCommand::new(CommandGroup::from(vec![
+        SceneCommand::new(AddNodeCommand::new(batch, graph.get_root())),
+        SceneCommand::new(SetNodesVisibilityCommand::new(sources, false)),
+    ])))
+}
+
+/// Creates a command that deletes selected static batches and shows their source meshes again.
+pub fn make_unmerge_static_batch_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    let mut batches = Vec::new();
+    let mut sources = Vec::new();
+
+    for &handle in selection.nodes() {
+        if let Some(node) = graph
+            .try_get(handle)
+            .filter(|n| n.tag() == STATIC_BATCH_TAG)
+        {
+            sources.extend(
+                node.find_properties_ref(STATIC_BATCH_SOURCE_PROPERTY)
+                    .filter_map(|p| match p.value {
+                        NodePropertyValue::NodeHandle(source) if graph.is_valid_handle(source) => {
+                            Some(source)
+                        }
+                        _ => None,
+                    }),
+            );
+            batches.push(handle);
+        }
+    }
+
+    if batches.is_empty() {
+        return None;
+    }
+
+    let mut commands = vec![
+        // Batches will be deleted, so their sources are selected instead.
+        SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Graph(GraphSelection::from_list(sources.clone())),
+            Selection::Graph(selection.clone()),
+        )),
+        SceneCommand::new(SetNodesVisibilityCommand::new(sources, true)),
+    ];
+    commands.extend(
+        batches
+            .into_iter()
+            .map(|batch| SceneCommand::new(DeleteSubGraphCommand::new(batch))),
+    );
+
+    Some(SceneCommand::new(CommandGroup::from(commands)))
+}
@@ -2,18 +2,25 @@ use crate::{
     command::{universal::set_entity_field, Command},
     define_universal_commands,
     scene::{
-        clipboard::DeepCloneResult, commands::graph::DeleteSubGraphCommand, EditorScene,
-        GraphSelection, Selection,
+        clipboard::{duplicate_nodes, DeepCloneResult},
+        commands::graph::DeleteSubGraphCommand,
+        EditorScene, GraphSelection, Selection,
     },
     GameEngine, Message,
 };
 use fyrox::{
     core::{
+        algebra::{Matrix4, Vector3},
         pool::Handle,
         reflect::{Reflect, ResolvePath},
     },
     engine::{resource_manager::ResourceManager, SerializationContext},
-    scene::{graph::SubGraph, node::Node, Scene},
+    scene::{
+        graph::{Graph, SubGraph},
+        mesh::Mesh,
+        node::Node,
+        Scene,
+    },
     utils::log::Log,
 };
 use std::{
@@ -26,6 +33,7 @@ pub mod graph;
 pub mod material;
 pub mod mesh;
 pub mod navmesh;
+pub mod prefab;
 pub mod sound_context;
 pub mod terrain;
 
@@ -205,6 +213,10 @@ impl Command for ChangeSelectionCommand {
         self.cached_name.clone()
     }
 
+    fn is_selection_change(&self) -> bool {
+        true
+    }
+
     fn execute(&mut self, context: &mut SceneContext) {
         let old_selection = self.old_selection.clone();
         let new_selection = self.swap();
@@ -344,6 +356,186 @@ impl Command for PasteCommand {
     }
 }
 
+/// Defines how duplicated meshes refer to surface data and materials of their originals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuplicationMode {
+    /// Duplicates get their own copies of surface data and materials.
+    Independent,
+    /// Duplicates share surface data and materials with their originals, so editing one of them
+    /// affects all the others. This saves memory when there are many identical objects.
+    Linked,
+}
+
+#[derive(Debug)]
+pub struct DuplicateNodesCommand {
+    nodes: Vec<Handle<Node>>,
+    mode: DuplicationMode,
+    offset: Vector3<f32>,
+    state: PasteCommandState,
+}
+
+impl DuplicateNodesCommand {
+    pub fn new(nodes: Vec<Handle<Node>>, mode: DuplicationMode) -> Self {
+        Self {
+            nodes,
+            mode,
+            offset: Vector3::default(),
+            state: PasteCommandState::NonExecuted,
+        }
+    }
+
+    /// Sets an offset in world space, duplicates are moved by it from their originals.
+    pub fn with_offset(mut self, offset: Vector3<f32>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    // Duplicates are placed next to their originals.
+    fn link_duplicates(&self, duplicates: &[Handle<Node>], graph: &mut Graph) {
+        for (&original, &duplicate) in self.nodes.iter().zip(duplicates) {
+            let parent = graph[original].parent();
+            graph.link_nodes(duplicate, parent);
+        }
+    }
+
+    fn offset_duplicates(&self, duplicates: &[Handle<Node>], graph: &mut Graph) {
+        for &duplicate in duplicates {
+            let parent_inv_transform = graph
+                .try_get(graph[duplicate].parent())
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .unwrap_or_else(Matrix4::identity);
+            let offset = parent_inv_transform.transform_vector(&self.offset);
+            let transform = graph[duplicate].local_transform_mut();
+            let position = **transform.position();
+            transform.set_position(position + offset);
+        }
+    }
+}
+
+fn make_unique_surfaces(root: Handle<Node>, graph: &mut Graph) {
+    let descendants = graph.traverse_handle_iter(root).collect::<Vec<_>>();
+    for handle in descendants {
+        if let Some(mesh) = graph[handle].cast_mut::<Mesh>() {
+            for surface in mesh.surfaces_mut() {
+                let data = surface.data().deep_clone();
+                surface.set_data(data);
+                let material = surface.material().deep_copy();
+                surface.set_material(material);
+            }
+        }
+    }
+}
+
+impl Command for DuplicateNodesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        match self.mode {
+            DuplicationMode::Independent => "Duplicate".to_owned(),
+            DuplicationMode::Linked => "Duplicate Linked".to_owned(),
+        }
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let (paste_result, mut selection, first_execution) =
+            match std::mem::replace(&mut self.state, PasteCommandState::Undefined) {
+                PasteCommandState::NonExecuted => {
+                    let paste_result = duplicate_nodes(&self.nodes, &mut context.scene.graph);
+
+                    if self.mode == DuplicationMode::Independent {
+                        for &root in paste_result.root_nodes.iter() {
+                            make_unique_surfaces(root, &mut context.scene.graph);
+                        }
+                    }
+
+                    let selection = Selection::Graph(GraphSelection::from_list(
+                        paste_result.root_nodes.clone(),
+                    ));
+
+                    (paste_result, selection, true)
+                }
+                PasteCommandState::Reverted {
+                    subgraphs,
+                    selection,
+                } => {
+                    let mut paste_result = DeepCloneResult::default();
+                    for subgraph in subgraphs {
+                        paste_result
+                            .root_nodes
+                            .push(context.scene.graph.put_sub_graph_back(subgraph));
+                    }
+
+                    (paste_result, selection, false)
+                }
+                _ => unreachable!(),
+            };
+
+        self.link_duplicates(&paste_result.root_nodes, &mut context.scene.graph);
+
+        // Sub-graphs keep their transform when they're reverted, so the offset is applied once.
+        if first_execution {
+            self.offset_duplicates(&paste_result.root_nodes, &mut context.scene.graph);
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut selection);
+        self.state = PasteCommandState::Executed {
+            paste_result,
+            last_selection: selection,
+        };
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let PasteCommandState::Executed {
+            paste_result,
+            mut last_selection,
+        } = std::mem::replace(&mut self.state, PasteCommandState::Undefined)
+        {
+            let mut subgraphs = Vec::new();
+            for root_node in paste_result.root_nodes {
+                subgraphs.push(context.scene.graph.take_reserve_sub_graph(root_node));
+            }
+
+            std::mem::swap(&mut context.editor_scene.selection, &mut last_selection);
+
+            self.state = PasteCommandState::Reverted {
+                subgraphs,
+                selection: last_selection,
+            };
+        }
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        if let PasteCommandState::Reverted { subgraphs, .. } =
+            std::mem::replace(&mut self.state, PasteCommandState::Undefined)
+        {
+            for subgraph in subgraphs {
+                context.scene.graph.forget_sub_graph(subgraph);
+            }
+        }
+    }
+}
+
+/// Creates a command that duplicates selected nodes with all their descendants, duplicates are
+/// moved by the given offset in world space. Returns `None` if there's nothing to duplicate, the
+/// root of the graph cannot be duplicated.
+pub fn make_duplicate_selection_command(
+    selection: &GraphSelection,
+    graph: &Graph,
+    mode: DuplicationMode,
+    offset: Vector3<f32>,
+) -> Option<SceneCommand> {
+    let nodes = selection
+        .root_nodes(graph)
+        .into_iter()
+        .filter(|&n| n != graph.get_root())
+        .collect::<Vec<_>>();
+    if nodes.is_empty() {
+        None
+    } else {
+        Some(SceneCommand::new(
+            DuplicateNodesCommand::new(nodes, mode).with_offset(offset),
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct RevertSceneNodePropertyCommand {
     path: String,
@@ -463,6 +655,83 @@ impl Command for RevertSceneNodePropertyCommand {
     }
 }
 
+/// Sets a property of a node to the value of the same property of a freshly constructed node of
+/// the same type.
+#[derive(Debug)]
+pub struct ResetSceneNodePropertyCommand {
+    path: String,
+    handle: Handle<Node>,
+    value: Option<Box<dyn Reflect>>,
+}
+
+impl ResetSceneNodePropertyCommand {
+    pub fn new(path: String, handle: Handle<Node>) -> Self {
+        Self {
+            path,
+            handle,
+            value: None,
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let node = &mut context.scene.graph[self.handle];
+
+        let value = match self.value.take() {
+            Some(value) => value,
+            None => {
+                let default_value = context
+                    .serialization_context
+                    .node_constructors
+                    .try_create(&node.id())
+                    .and_then(|default_node| {
+                        default_node
+                            .as_reflect()
+                            .resolve_path(&self.path)
+                            .ok()
+                            .and_then(|field| field.as_inheritable_variable())
+                            .map(|field| field.clone_value_box())
+                    });
+
+                match default_value {
+                    Some(default_value) => default_value,
+                    None => {
+                        Log::err(format!(
+                            "Failed to reset property {}. Reason: unable to get default value!",
+                            self.path
+                        ));
+                        return;
+                    }
+                }
+            }
+        };
+
+        match set_entity_field(node.as_reflect_mut(), &self.path, value) {
+            Ok(old_value) => self.value = Some(old_value),
+            Err(value) => {
+                self.value = Some(value);
+                Log::err(format!(
+                    "Failed to reset property {}. Reason: no such property!",
+                    self.path
+                ))
+            }
+        }
+    }
+}
+
+impl Command for ResetSceneNodePropertyCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        format!("Reset {} Property", self.path)
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
 define_universal_commands!(
     make_set_node_property_command,
     Command,
@@ -0,0 +1,198 @@
+use crate::{
+    command::{universal::set_entity_field, Command},
+    scene::commands::{CommandGroup, SceneCommand, SceneContext},
+};
+use fyrox::{
+    core::{
+        pool::Handle,
+        reflect::{Reflect, ResolvePath},
+    },
+    fxhash::FxHashMap,
+    resource::model::Model,
+    scene::{
+        graph::{map::NodeHandleMap, Graph},
+        node::Node,
+    },
+    utils::log::Log,
+};
+
+/// Sets a value of an inheritable property of an instance, but keeps the property non-modified
+/// so it continues to inherit its value from the prefab.
+#[derive(Debug)]
+pub struct InheritPropertyCommand {
+    handle: Handle<Node>,
+    path: String,
+    value: Option<Box<dyn Reflect>>,
+}
+
+impl InheritPropertyCommand {
+    pub fn new(handle: Handle<Node>, path: String, value: Box<dyn Reflect>) -> Self {
+        Self {
+            handle,
+            path,
+            value: Some(value),
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        let node = context.scene.graph[self.handle].as_reflect_mut();
+        match set_entity_field(node, &self.path, self.value.take().unwrap()) {
+            Ok(old_value) => {
+                self.value = Some(old_value);
+
+                // Setting a value marks it as modified, which is not what we want here.
+                if let Some(inheritable) = node
+                    .resolve_path_mut(&self.path)
+                    .ok()
+                    .and_then(|field| field.as_inheritable_variable_mut())
+                {
+                    inheritable.reset_modified_flag();
+                }
+            }
+            Err(value) => {
+                self.value = Some(value);
+                Log::err(format!("Failed to set property {}!", self.path))
+            }
+        }
+    }
+}
+
+impl Command for InheritPropertyCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        format!("Inherit {} Property", self.path)
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
+
+fn join_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+// Walks over the instance and its original simultaneously and collects values of every non-modified
+// inheritable property that differs from the respective property of the original. Modified
+// properties are local overrides, they're left untouched together with everything inside them.
+fn collect_changed_properties(
+    instance: &dyn Reflect,
+    original: &dyn Reflect,
+    path: &str,
+    changes: &mut Vec<(String, Box<dyn Reflect>)>,
+) {
+    if (*instance).type_id() != (*original).type_id() {
+        return;
+    }
+
+    if let (Some(instance), Some(original)) = (
+        instance.as_inheritable_variable(),
+        original.as_inheritable_variable(),
+    ) {
+        if !instance.is_modified() && !instance.value_equals(original) {
+            changes.push((path.to_owned(), original.clone_value_box()));
+        }
+    } else if let (Some(instance), Some(original)) = (instance.as_array(), original.as_array()) {
+        if instance.reflect_len() == original.reflect_len() {
+            for i in 0..instance.reflect_len() {
+                // Sparse arrays (like Pool) could have empty entries.
+                if let (Some(instance_item), Some(original_item)) =
+                    (instance.reflect_index(i), original.reflect_index(i))
+                {
+                    let item_path = format!("{}[{}]", path, i);
+                    collect_changed_properties(instance_item, original_item, &item_path, changes);
+                }
+            }
+        }
+    } else {
+        for field_info in instance.fields_info() {
+            if let (Some(instance_field), Some(original_field)) = (
+                instance.field(field_info.name),
+                original.field(field_info.name),
+            ) {
+                collect_changed_properties(
+                    instance_field,
+                    original_field,
+                    &join_path(path, field_info.name),
+                    changes,
+                );
+            }
+        }
+    }
+}
+
+fn instance_root(graph: &Graph, mut handle: Handle<Node>, resource: &Model) -> Handle<Node> {
+    while let Some(parent) = graph.try_get(graph[handle].parent()) {
+        if parent.resource().as_ref() != Some(resource) {
+            break;
+        }
+        handle = graph[handle].parent();
+    }
+    handle
+}
+
+/// Creates a command that updates every non-modified property of every instance of the given
+/// prefabs in the graph with respective values from the prefabs. Properties that were modified in
+/// an instance are local overrides and they're kept as is.
+pub fn make_propagate_prefab_properties_command(
+    prefabs: &[Model],
+    graph: &Graph,
+) -> Option<SceneCommand> {
+    // Each instance has its own mapping of handles of prefab nodes to handles of instance nodes, it
+    // is needed to correctly inherit properties with handles.
+    let mut mappings = FxHashMap::<Handle<Node>, FxHashMap<Handle<Node>, Handle<Node>>>::default();
+    let mut instance_nodes = Vec::new();
+    for (handle, node) in graph.pair_iter() {
+        if let Some(resource) = node.resource().filter(|r| prefabs.contains(r)) {
+            let root = instance_root(graph, handle, &resource);
+            mappings
+                .entry(root)
+                .or_default()
+                .insert(node.original_handle_in_resource(), handle);
+            instance_nodes.push((handle, root, resource));
+        }
+    }
+
+    let mappings = mappings
+        .into_iter()
+        .map(|(root, map)| (root, NodeHandleMap::from(map)))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut group = CommandGroup::from(Vec::new());
+    let mut is_empty = true;
+
+    for (handle, root, resource) in instance_nodes {
+        let node = &graph[handle];
+        let resource_data = resource.data_ref();
+        let mut changes = Vec::new();
+        if let Some(original) = resource_data
+            .get_scene()
+            .graph
+            .try_get(node.original_handle_in_resource())
+        {
+            collect_changed_properties(node.as_reflect(), original.as_reflect(), "", &mut changes);
+        }
+
+        for (path, mut value) in changes {
+            mappings[&root].remap_entity_handles(&mut *value, node.name());
+
+            group.push(SceneCommand::new(InheritPropertyCommand::new(
+                handle, path, value,
+            )));
+            is_empty = false;
+        }
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(SceneCommand::new(group))
+    }
+}
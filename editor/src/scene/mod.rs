@@ -2,13 +2,21 @@ use crate::{
     absm::selection::AbsmSelection,
     animation::selection::AnimationSelection,
     audio::EffectSelection,
-    camera::CameraController,
-    interaction::navmesh::{
-        data_model::{Navmesh, NavmeshContainer, NavmeshTriangle, NavmeshVertex},
-        selection::NavmeshSelection,
+    camera::{CameraController, NEUTRAL_EXPOSURE},
+    inspector::editors::handle::HandlePropertyEditorMessage,
+    interaction::{
+        navmesh::{
+            data_model::{Navmesh, NavmeshContainer, NavmeshTriangle, NavmeshVertex},
+            selection::NavmeshSelection,
+        },
+        pivot::PivotMode,
+        transform_locks::TransformLocks,
     },
+    paste_transform::CopiedTransform,
+    repeat::RepeatableAction,
     scene::clipboard::Clipboard,
-    settings::debugging::DebuggingSettings,
+    settings::{camera::SceneCameraSettings, debugging::DebuggingSettings},
+    utils::write_file,
     world::graph::selection::GraphSelection,
     GameEngine, Settings,
 };
@@ -18,30 +26,39 @@ use fyrox::{
         color::Color,
         math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Matrix4Ext, TriangleDefinition},
         pool::Handle,
+        reflect::Reflect,
+        variable::InheritableVariable,
         visitor::Visitor,
     },
     engine::Engine,
+    fxhash::{FxHashMap, FxHashSet},
+    gui::{message::MessageDirection, UiNode, UserInterface},
+    renderer::QualitySettings,
     scene::{
         base::BaseBuilder,
         camera::Camera,
         debug::{Line, SceneDrawingContext},
         graph::{Graph, GraphUpdateSwitches},
-        light::{point::PointLight, spot::SpotLight},
+        light::{point::PointLight, spot::SpotLight, BaseLight},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
         },
         node::Node,
         pivot::PivotBuilder,
-        Scene,
+        trigger::{TriggerShape, TriggerVolume},
+        Scene, SceneRenderMode,
     },
+    utils::log::Log,
 };
 use std::{collections::HashMap, fmt::Write, path::PathBuf};
 
 pub mod clipboard;
 pub mod property;
+pub mod reimport;
 pub mod selector;
 pub mod settings;
+pub mod sidecar;
 
 #[macro_use]
 pub mod commands;
@@ -54,10 +71,95 @@ pub struct EditorScene {
     pub editor_objects_root: Handle<Node>,
     pub selection: Selection,
     pub clipboard: Clipboard,
+    /// Transform copied by Edit > Copy Transform.
+    pub copied_transform: Option<CopiedTransform>,
     pub camera_controller: CameraController,
     pub navmeshes: NavmeshContainer,
     pub preview_camera: Handle<Node>,
     pub graph_switches: GraphUpdateSwitches,
+    pub render_mode: ViewportRenderMode,
+    /// Nodes that are hidden in the editor only. Such nodes are invisible while editing, but
+    /// they're saved as visible.
+    pub hidden_nodes: FxHashSet<Handle<Node>>,
+    /// Lights that are soloed in the editor, every other light is muted while there's at least
+    /// one soloed light.
+    pub soloed_lights: Vec<Handle<Node>>,
+    /// Lights that are muted because of the solo, with their original intensity. Like hidden
+    /// nodes, such lights are saved with their original intensity.
+    pub muted_lights: FxHashMap<Handle<Node>, f32>,
+    /// Node under the mouse cursor in the scene viewer, it is highlighted to show what will be
+    /// selected by a click.
+    pub hovered_node: Handle<Node>,
+    /// Axes of local transform that cannot be changed by gizmos or in the inspector.
+    pub transform_locks: TransformLocks,
+    /// Point around which multiple selected nodes are rotated and scaled by gizmos.
+    pub pivot_mode: PivotMode,
+    /// Handle property editor that waits for a node to be picked, see
+    /// [`Self::try_pick_node_handle`].
+    pub node_handle_picker: Option<Handle<UiNode>>,
+    /// Last action that can be repeated with the current selection, see [`RepeatableAction`].
+    pub last_action: Option<RepeatableAction>,
+    /// Apply post-processing settings of the scene's camera (exposure, color grading) and bloom
+    /// to the viewport, instead of neutral settings. See [`Self::sync_post_processing`].
+    pub post_processing_preview: bool,
+    /// Nodes that keep their editor transform in play mode, so the rest of the game can be
+    /// debugged around them. This is editor-only state, it is not saved to the scene and it is
+    /// cleared when play mode ends.
+    pub frozen_nodes: FxHashSet<Handle<Node>>,
+    /// Handles of the frozen nodes in the last saved scene. They differ from the handles in the
+    /// editor, because editor objects are not saved.
+    saved_frozen_nodes: Vec<Handle<Node>>,
+    /// 3D cursor in world space, it is placed by Shift + right click in the scene viewer. New
+    /// nodes are created at the cursor and it can be used as a pivot, see [`PivotMode::Cursor`].
+    /// This is editor-only state, it is not saved to the scene.
+    pub cursor: Option<Vector3<f32>>,
+}
+
+/// Defines how the scene is shown in the scene viewer. This is editor-only view state, it does
+/// not modify the scene (materials, lights, etc.) in any way.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ViewportRenderMode {
+    Lit,
+    Unlit,
+    Wireframe,
+    /// Lit scene with wireframe drawn on top of it.
+    ShadedWireframe,
+    Normals,
+}
+
+impl Default for ViewportRenderMode {
+    fn default() -> Self {
+        Self::Lit
+    }
+}
+
+impl ViewportRenderMode {
+    pub const ALL: [ViewportRenderMode; 5] = [
+        ViewportRenderMode::Lit,
+        ViewportRenderMode::Unlit,
+        ViewportRenderMode::Wireframe,
+        ViewportRenderMode::ShadedWireframe,
+        ViewportRenderMode::Normals,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ViewportRenderMode::Lit => "Lit",
+            ViewportRenderMode::Unlit => "Unlit",
+            ViewportRenderMode::Wireframe => "Wireframe",
+            ViewportRenderMode::ShadedWireframe => "Shaded Wireframe",
+            ViewportRenderMode::Normals => "Normals",
+        }
+    }
+
+    pub fn scene_render_mode(self) -> SceneRenderMode {
+        match self {
+            ViewportRenderMode::Lit | ViewportRenderMode::ShadedWireframe => SceneRenderMode::Lit,
+            ViewportRenderMode::Unlit => SceneRenderMode::Unlit,
+            ViewportRenderMode::Wireframe => SceneRenderMode::Wireframe,
+            ViewportRenderMode::Normals => SceneRenderMode::Normals,
+        }
+    }
 }
 
 pub fn is_scene_needs_to_be_saved(editor_scene: Option<&EditorScene>) -> bool {
@@ -66,20 +168,75 @@ pub fn is_scene_needs_to_be_saved(editor_scene: Option<&EditorScene>) -> bool {
         .map_or(false, |s| s.has_unsaved_changes || s.path.is_none())
 }
 
+/// Calculates world-space bounding box of every node of the scene except editor objects. Nodes
+/// without bounds are taken into account by their positions. Mesh bounds are calculated from
+/// vertices if `accurate_meshes` is set, this is slow for big meshes. Returns `None` if the scene
+/// has no nodes.
+pub fn calculate_scene_bounding_box(
+    graph: &Graph,
+    editor_objects_root: Handle<Node>,
+    accurate_meshes: bool,
+) -> Option<AxisAlignedBoundingBox> {
+    let mut bounding_box = AxisAlignedBoundingBox::default();
+    let mut is_empty = true;
+
+    let mut stack = graph[graph.get_root()].children().to_vec();
+    while let Some(handle) = stack.pop() {
+        if handle == editor_objects_root {
+            continue;
+        }
+
+        let node = &graph[handle];
+        match node.cast::<Mesh>() {
+            Some(mesh) if accurate_meshes => {
+                bounding_box.add_box(mesh.accurate_world_bounding_box(graph))
+            }
+            _ => {
+                let local_bounding_box = node.local_bounding_box();
+                // Most of the nodes do not have any bounds, take their position instead.
+                if local_bounding_box.min.x <= local_bounding_box.max.x
+                    && local_bounding_box.min.y <= local_bounding_box.max.y
+                    && local_bounding_box.min.z <= local_bounding_box.max.z
+                {
+                    bounding_box.add_box(node.world_bounding_box());
+                } else {
+                    bounding_box.add_point(node.global_position());
+                }
+            }
+        }
+        is_empty = false;
+
+        stack.extend_from_slice(node.children());
+    }
+
+    if is_empty {
+        None
+    } else {
+        Some(bounding_box)
+    }
+}
+
+// Changes intensity of the light without raising the modified flag of the property, unlike
+// `BaseLight::set_intensity`.
+fn set_light_intensity_silent(light: &mut BaseLight, intensity: f32) -> f32 {
+    match light
+        .field_mut("intensity")
+        .and_then(|field| field.downcast_mut::<InheritableVariable<f32>>())
+    {
+        Some(variable) => variable.set_value_silent(intensity),
+        None => light.set_intensity(intensity),
+    }
+}
+
 impl EditorScene {
     pub fn from_native_scene(
         mut scene: Scene,
         engine: &mut Engine,
         path: Option<PathBuf>,
-        settings: &Settings,
+        camera_settings: Option<&SceneCameraSettings>,
     ) -> Self {
         let root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
-        let camera_controller = CameraController::new(
-            &mut scene.graph,
-            root,
-            path.as_ref()
-                .and_then(|p| settings.camera.camera_settings.get(p)),
-        );
+        let camera_controller = CameraController::new(&mut scene.graph, root, camera_settings);
 
         // Freeze physics simulation in while editing scene by setting time step to zero.
         scene.graph.physics.integration_parameters.dt = Some(0.0);
@@ -116,6 +273,7 @@ impl EditorScene {
             scene: engine.scenes.add(scene),
             selection: Default::default(),
             clipboard: Default::default(),
+            copied_transform: None,
             has_unsaved_changes: false,
             preview_camera: Default::default(),
             graph_switches: GraphUpdateSwitches {
@@ -125,14 +283,165 @@ impl EditorScene {
                 // Update only editor's camera.
                 node_overrides: Some(Default::default()),
             },
+            render_mode: Default::default(),
+            hidden_nodes: Default::default(),
+            soloed_lights: Default::default(),
+            muted_lights: Default::default(),
+            hovered_node: Default::default(),
+            transform_locks: Default::default(),
+            pivot_mode: Default::default(),
+            node_handle_picker: None,
+            last_action: None,
+            post_processing_preview: false,
+            frozen_nodes: Default::default(),
+            saved_frozen_nodes: Default::default(),
+            cursor: None,
+        }
+    }
+
+    /// Hides or shows the given nodes in the editor only. Nodes that are hidden by their own
+    /// visibility are not affected.
+    pub fn set_nodes_hidden(&mut self, nodes: &[Handle<Node>], hidden: bool, graph: &mut Graph) {
+        for &node_handle in nodes {
+            if let Some(node) = graph.try_get_mut(node_handle) {
+                if hidden {
+                    if node.visibility() {
+                        node.set_visibility(false);
+                        self.hidden_nodes.insert(node_handle);
+                    }
+                } else if self.hidden_nodes.remove(&node_handle) {
+                    node.set_visibility(true);
+                }
+            }
+        }
+    }
+
+    /// Mutes every light except the given ones. Lights are muted by setting their intensity to
+    /// zero, so nodes attached to them are not affected. Intensity is changed without marking it
+    /// modified, so the solo does not create property overrides in prefab instances.
+    pub fn set_soloed_lights(&mut self, lights: &[Handle<Node>], graph: &mut Graph) {
+        self.clear_light_solo(graph);
+
+        if lights.is_empty() {
+            return;
+        }
+
+        let editor_nodes = graph
+            .traverse_handle_iter(self.editor_objects_root)
+            .collect::<FxHashSet<_>>();
+
+        for (handle, node) in graph.pair_iter_mut() {
+            if lights.contains(&handle) || editor_nodes.contains(&handle) {
+                continue;
+            }
+
+            if let Some(light) = node.query_component_mut::<BaseLight>() {
+                self.muted_lights
+                    .insert(handle, set_light_intensity_silent(light, 0.0));
+            }
         }
+
+        self.soloed_lights = lights.to_vec();
     }
 
-    pub fn make_purified_scene(&self, engine: &mut GameEngine) -> Scene {
+    /// Restores intensity of the lights muted by the solo. Intensity is not restored if it was
+    /// changed while the light was muted.
+    pub fn clear_light_solo(&mut self, graph: &mut Graph) {
+        for (handle, intensity) in self.muted_lights.drain() {
+            if let Some(light) = graph
+                .try_get_mut(handle)
+                .and_then(|n| n.query_component_mut::<BaseLight>())
+            {
+                if light.intensity() == 0.0 {
+                    set_light_intensity_silent(light, intensity);
+                }
+            }
+        }
+
+        self.soloed_lights.clear();
+    }
+
+    /// Assigns the node to the handle property editor that waits for picking, if any. Returns
+    /// `true` if the pick was consumed, in this case the selection must not be changed. A pick of
+    /// nothing (`Handle::NONE`) just cancels the picking.
+    pub fn try_pick_node_handle(&mut self, node: Handle<Node>, ui: &UserInterface) -> bool {
+        match self.node_handle_picker.take() {
+            Some(view) => {
+                if node.is_some() {
+                    ui.send_message(HandlePropertyEditorMessage::value(
+                        view,
+                        MessageDirection::ToWidget,
+                        node,
+                    ));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns position of the 3D cursor in local space of the given parent node, so a new child
+    /// of the node can be placed at the cursor. Returns `None` if the cursor is not placed.
+    pub fn cursor_local_position(
+        &self,
+        graph: &Graph,
+        parent: Handle<Node>,
+    ) -> Option<Vector3<f32>> {
+        let cursor = self.cursor?;
+        Some(
+            graph
+                .try_get(parent)
+                .and_then(|parent| parent.global_transform().try_inverse())
+                .map_or(cursor, |inv_transform| {
+                    inv_transform.transform_point(&Point3::from(cursor)).coords
+                }),
+        )
+    }
+
+    /// Returns handles of the frozen nodes in the last saved scene, see [`Self::frozen_nodes`].
+    pub fn saved_frozen_nodes(&self) -> &[Handle<Node>] {
+        &self.saved_frozen_nodes
+    }
+
+    pub fn make_purified_scene(&mut self, engine: &mut GameEngine) -> Scene {
         let scene = &mut engine.scenes[self.scene];
 
         let editor_root = self.editor_objects_root;
-        let (mut pure_scene, _) = scene.clone(&mut |node, _| node != editor_root);
+        let (mut pure_scene, old_new_map) = scene.clone(&mut |node, _| node != editor_root);
+
+        // Editor-only hidden state must not leak into the scene.
+        for &hidden in self.hidden_nodes.iter() {
+            let mut handle = hidden;
+            if old_new_map.try_map(&mut handle) {
+                pure_scene.graph[handle].set_visibility(true);
+            }
+        }
+
+        // Same for the muted lights.
+        for (&muted, &intensity) in self.muted_lights.iter() {
+            let mut handle = muted;
+            if old_new_map.try_map(&mut handle) {
+                if let Some(light) = pure_scene.graph[handle].query_component_mut::<BaseLight>() {
+                    if light.intensity() == 0.0 {
+                        light.set_intensity(intensity);
+                    }
+                }
+            }
+        }
+
+        // Frozen nodes are passed to the game by their handles in the saved scene.
+        self.saved_frozen_nodes = self
+            .frozen_nodes
+            .iter()
+            .filter_map(|&frozen| {
+                let mut handle = frozen;
+                if old_new_map.try_map(&mut handle) {
+                    Some(handle)
+                } else {
+                    None
+                }
+            })
+            .collect();
 
         pure_scene.navmeshes.clear();
 
@@ -170,7 +479,12 @@ impl EditorScene {
         pure_scene
     }
 
-    pub fn save(&mut self, path: PathBuf, engine: &mut GameEngine) -> Result<String, String> {
+    pub fn save(
+        &mut self,
+        path: PathBuf,
+        safe: bool,
+        engine: &mut GameEngine,
+    ) -> Result<String, String> {
         // Validate first.
         let valid = true;
         let mut reason = "Scene is not saved, because validation failed:\n".to_owned();
@@ -181,8 +495,11 @@ impl EditorScene {
             let mut pure_scene = self.make_purified_scene(engine);
 
             let mut visitor = Visitor::new();
-            pure_scene.save("Scene", &mut visitor).unwrap();
-            if let Err(e) = visitor.save_binary(&path) {
+            let data = pure_scene
+                .save("Scene", &mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+                .map_err(|e| format!("Failed to serialize scene! Reason: {}", e))?;
+            if let Err(e) = write_file(&path, &data, safe) {
                 Err(format!("Failed to save scene! Reason: {}", e))
             } else {
                 Ok(format!("Scene {} was successfully saved!", path.display()))
@@ -194,8 +511,14 @@ impl EditorScene {
         }
     }
 
-    pub fn update(&mut self, engine: &mut Engine, dt: f32, settings: &Settings) {
-        self.draw_auxiliary_geometry(engine, settings);
+    pub fn update(
+        &mut self,
+        engine: &mut Engine,
+        dt: f32,
+        settings: &Settings,
+        is_edit_mode: bool,
+    ) {
+        self.draw_auxiliary_geometry(engine, settings, is_edit_mode);
 
         let scene = &mut engine.scenes[self.scene];
 
@@ -213,23 +536,152 @@ impl EditorScene {
             .update(&mut scene.graph, &settings.camera, dt);
     }
 
-    pub fn draw_auxiliary_geometry(&mut self, engine: &mut Engine, settings: &Settings) {
+    /// Returns the camera that shows the scene in the game: the preview camera if there's one,
+    /// otherwise the first enabled camera of the scene.
+    fn find_scene_camera<'a>(&self, graph: &'a Graph) -> Option<&'a Camera> {
+        graph
+            .try_get_of_type::<Camera>(self.preview_camera)
+            .or_else(|| {
+                graph
+                    .pair_iter()
+                    .filter(|(handle, _)| *handle != self.camera_controller.camera)
+                    .find_map(|(_, node)| node.cast::<Camera>().filter(|c| c.is_enabled()))
+            })
+    }
+
+    /// Applies post-processing settings of the scene's camera to the editor camera and enables
+    /// bloom if [`Self::post_processing_preview`] is enabled, otherwise applies neutral settings
+    /// for accurate color work. Only the editor camera and the renderer are changed, the scene
+    /// itself is never modified. Must be called every frame to track changes of the scene's camera.
+    pub fn sync_post_processing(&self, engine: &mut Engine, quality: &QualitySettings) {
+        let graph = &mut engine.scenes[self.scene].graph;
+
+        let (exposure, color_grading_lut, color_grading_enabled) = if self.post_processing_preview {
+            self.find_scene_camera(graph)
+                .map_or((NEUTRAL_EXPOSURE, None, false), |camera| {
+                    (
+                        camera.exposure(),
+                        camera.color_grading_lut(),
+                        camera.color_grading_enabled(),
+                    )
+                })
+        } else {
+            (NEUTRAL_EXPOSURE, None, false)
+        };
+
+        let camera = graph[self.camera_controller.camera].as_camera_mut();
+        if camera.exposure() != exposure {
+            camera.set_exposure(exposure);
+        }
+        if camera.color_grading_lut_ref() != color_grading_lut.as_ref() {
+            camera.set_color_grading_lut(color_grading_lut);
+        }
+        if camera.color_grading_enabled() != color_grading_enabled {
+            camera.set_color_grading_enabled(color_grading_enabled);
+        }
+
+        let mut quality = *quality;
+        quality.use_bloom &= self.post_processing_preview;
+        if engine.renderer.get_quality_settings() != quality {
+            Log::verify(engine.renderer.set_quality_settings(&quality));
+        }
+    }
+
+    pub fn set_render_mode(&mut self, engine: &mut Engine, render_mode: ViewportRenderMode) {
+        self.render_mode = render_mode;
+        engine.scenes[self.scene].render_mode = render_mode.scene_render_mode();
+    }
+
+    pub fn draw_auxiliary_geometry(
+        &mut self,
+        engine: &mut Engine,
+        settings: &Settings,
+        is_edit_mode: bool,
+    ) {
         let debug_settings = &settings.debugging;
         let scene = &mut engine.scenes[self.scene];
 
         scene.drawing_context.clear_lines();
 
         if let Selection::Graph(selection) = &self.selection {
-            for &node in selection.nodes() {
-                let node = &scene.graph[node];
+            // The active node is distinguished only in multi-selection, a single selected node
+            // is obviously the active one.
+            let active = if selection.is_multi_selection() {
+                selection.active()
+            } else {
+                None
+            };
+
+            for &handle in selection.nodes() {
+                let node = &scene.graph[handle];
                 scene.drawing_context.draw_oob(
                     &node.local_bounding_box(),
                     node.global_transform(),
-                    Color::GREEN,
+                    if Some(handle) == active {
+                        settings.selection.active_selection_color
+                    } else {
+                        settings.selection.selection_color
+                    },
                 );
             }
         }
 
+        if let Some(node) = scene.graph.try_get(self.hovered_node) {
+            let is_selected = matches!(&self.selection,
+                Selection::Graph(selection) if selection.contains(self.hovered_node));
+            if settings.selection.hover_highlight && !is_selected && node.global_visibility() {
+                scene.drawing_context.draw_oob(
+                    &node.local_bounding_box(),
+                    node.global_transform(),
+                    Color::opaque(200, 200, 200),
+                );
+            }
+        }
+
+        // Frozen nodes are marked with a box and a cross inside it.
+        for &handle in self.frozen_nodes.iter() {
+            if let Some(node) = scene.graph.try_get(handle) {
+                let aabb = node.local_bounding_box();
+                let transform = node.global_transform();
+                let color = Color::opaque(80, 200, 255);
+                scene.drawing_context.draw_oob(&aabb, transform, color);
+                for (begin, end) in [
+                    (aabb.min, aabb.max),
+                    (
+                        Vector3::new(aabb.max.x, aabb.min.y, aabb.min.z),
+                        Vector3::new(aabb.min.x, aabb.max.y, aabb.max.z),
+                    ),
+                ] {
+                    scene.drawing_context.add_line(Line {
+                        begin: transform.transform_point(&Point3::from(begin)).coords,
+                        end: transform.transform_point(&Point3::from(end)).coords,
+                        color,
+                    });
+                }
+            }
+        }
+
+        // The cursor keeps the same size on screen regardless of the distance to it.
+        if let Some(cursor) = self.cursor {
+            let distance = (scene.graph[self.camera_controller.camera].global_position() - cursor)
+                .norm()
+                .max(0.1);
+            let radius = distance * 0.02;
+            let ctx = &mut scene.drawing_context;
+            ctx.draw_wire_sphere(cursor, radius, 16, Color::WHITE);
+            for (axis, color) in [
+                (Vector3::x(), Color::RED),
+                (Vector3::y(), Color::GREEN),
+                (Vector3::z(), Color::BLUE),
+            ] {
+                ctx.add_line(Line {
+                    begin: cursor - axis.scale(radius * 2.0),
+                    end: cursor + axis.scale(radius * 2.0),
+                    color,
+                });
+            }
+        }
+
         if debug_settings.show_physics {
             scene.graph.physics.draw(&mut scene.drawing_context);
             scene.graph.physics2d.draw(&mut scene.drawing_context);
@@ -241,6 +693,7 @@ impl EditorScene {
             ctx: &mut SceneDrawingContext,
             editor_scene: &EditorScene,
             settings: &DebuggingSettings,
+            is_edit_mode: bool,
         ) {
             // Ignore editor nodes.
             if node == editor_scene.editor_objects_root {
@@ -258,6 +711,36 @@ impl EditorScene {
             }
 
             if let Some(mesh) = node.cast::<Mesh>() {
+                if editor_scene.render_mode == ViewportRenderMode::ShadedWireframe {
+                    let transform = node.global_transform();
+
+                    for surface in mesh.surfaces() {
+                        let data = surface.data();
+                        let data = data.lock();
+                        let position = |i: u32| {
+                            data.vertex_buffer
+                                .get(i as usize)
+                                .and_then(|v| v.read_3_f32(VertexAttributeUsage::Position).ok())
+                                .map(|p| transform.transform_point(&Point3::from(p)).coords)
+                        };
+                        for triangle in data.geometry_buffer.iter() {
+                            if let (Some(a), Some(b), Some(c)) = (
+                                position(triangle[0]),
+                                position(triangle[1]),
+                                position(triangle[2]),
+                            ) {
+                                for (begin, end) in [(a, b), (b, c), (c, a)] {
+                                    ctx.add_line(Line {
+                                        begin,
+                                        end,
+                                        color: Color::opaque(20, 20, 20),
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+
                 if settings.show_tbn {
                     let transform = node.global_transform();
 
@@ -339,10 +822,26 @@ impl EditorScene {
                     Color::GREEN,
                     false,
                 );
+            } else if let Some(volume) = node
+                .query_component_ref::<TriggerVolume>()
+                .filter(|_| is_edit_mode)
+            {
+                let color = Color::from_rgba(0, 200, 255, 140);
+                match volume.shape() {
+                    TriggerShape::Box { .. } => ctx.draw_oob(
+                        &volume.shape().local_bounding_box(),
+                        volume.global_transform(),
+                        color,
+                    ),
+                    TriggerShape::Sphere { radius } => {
+                        let scale = volume.global_transform().basis().column(0).norm();
+                        ctx.draw_wire_sphere(volume.global_position(), radius * scale, 30, color)
+                    }
+                }
             }
 
             for &child in node.children() {
-                draw_recursively(child, graph, ctx, editor_scene, settings)
+                draw_recursively(child, graph, ctx, editor_scene, settings, is_edit_mode)
             }
         }
 
@@ -353,6 +852,7 @@ impl EditorScene {
             &mut scene.drawing_context,
             self,
             debug_settings,
+            is_edit_mode,
         );
 
         let selection = if let Selection::Navmesh(ref selection) = self.selection {
@@ -0,0 +1,97 @@
+//! Reimport of models that are instantiated in a scene. Instances are reconciled with new content
+//! of the models: nodes are matched with the nodes of the models, non-modified properties are
+//! inherited again and nodes that were added to the models are added to the instances. Modified
+//! properties are local overrides, they're kept as is.
+
+use fyrox::{
+    core::{futures::executor::block_on, reflect::Reflect},
+    engine::resource_manager::ResourceManager,
+    resource::model::Model,
+    scene::{mesh::Mesh, Scene},
+    utils::log::Log,
+};
+
+fn is_surfaces_modified(mesh: &Mesh) -> bool {
+    mesh.as_reflect()
+        .field("surfaces")
+        .and_then(|surfaces| surfaces.as_inheritable_variable())
+        .map_or(false, |surfaces| surfaces.is_modified())
+}
+
+/// Reloads the models from their source files and syncs every instance of them in the scene.
+/// Nodes of instances that cannot be matched with nodes of the models are left untouched and
+/// reported to the log.
+pub fn reimport_models(models: &[Model], scene: &mut Scene, resource_manager: &ResourceManager) {
+    for model in models {
+        resource_manager
+            .state()
+            .containers_mut()
+            .models
+            .reload_resource(model.clone());
+    }
+
+    let mut reimported = Vec::new();
+    for model in models {
+        match block_on(model.clone()) {
+            Ok(model) => reimported.push(model),
+            Err(e) => Log::err(format!(
+                "Unable to reimport {} model! Reason: {:?}",
+                model.state().path().display(),
+                e
+            )),
+        }
+    }
+
+    let mut unmatched = Vec::new();
+    let mut outdated_geometry = Vec::new();
+    for node in scene.graph.linear_iter_mut() {
+        let resource = match node.resource() {
+            Some(resource) if reimported.contains(&resource) => resource,
+            _ => continue,
+        };
+        let data = resource.data_ref();
+
+        match data.find_original_node(node) {
+            Some((_, original)) => {
+                // Surfaces of meshes with overridden materials are not inherited, so new geometry
+                // must be copied manually, materials are kept.
+                if let (Some(mesh), Some(original_mesh)) =
+                    (node.cast_mut::<Mesh>(), original.cast::<Mesh>())
+                {
+                    if is_surfaces_modified(mesh) {
+                        if mesh.surfaces().len() == original_mesh.surfaces().len() {
+                            for (surface, original_surface) in
+                                mesh.surfaces_mut().iter_mut().zip(original_mesh.surfaces())
+                            {
+                                surface.set_data(original_surface.data());
+                            }
+                        } else {
+                            outdated_geometry.push(mesh.name().to_owned());
+                        }
+                    }
+                }
+            }
+            None => unmatched.push(node.name().to_owned()),
+        }
+    }
+
+    scene.resolve();
+
+    if !unmatched.is_empty() {
+        Log::warn(format!(
+            "{} node(s) cannot be matched with nodes of reimported models and were left as is: {}",
+            unmatched.len(),
+            unmatched.join(", ")
+        ));
+    }
+
+    if !outdated_geometry.is_empty() {
+        Log::warn(format!(
+            "Surface count has changed for meshes with modified surfaces, their geometry was not \
+            updated: {}",
+            outdated_geometry.join(", ")
+        ));
+    }
+
+    Log::info(format!("{} model(s) were reimported.", reimported.len()));
+}
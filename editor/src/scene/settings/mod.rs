@@ -1,12 +1,15 @@
 use crate::{
     inspector::editors::make_property_editors_container,
-    scene::settings::command::make_set_scene_property_command, EditorScene, Message,
-    MessageDirection, MSG_SYNC_FLAG,
+    scene::{calculate_scene_bounding_box, settings::command::make_set_scene_property_command},
+    EditorScene, Message, MessageDirection, MSG_SYNC_FLAG,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{algebra::Vector3, math::aabb::AxisAlignedBoundingBox, pool::Handle},
     engine::Engine,
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        copypasta::ClipboardProvider,
+        grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
                 inspectable::InspectablePropertyEditorDefinition, PropertyEditorDefinitionContainer,
@@ -15,9 +18,10 @@ use fyrox::{
         },
         message::UiMessage,
         scroll_viewer::ScrollViewerBuilder,
+        text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, UiNode, UserInterface,
+        BuildContext, Thickness, UiNode, VerticalAlignment,
     },
     scene::{
         dim2,
@@ -26,8 +30,9 @@ use fyrox::{
             Graph,
         },
     },
+    utils::log::Log,
 };
-use std::{rc::Rc, sync::mpsc::Sender};
+use std::{cell::Cell, rc::Rc, sync::mpsc::Sender};
 
 mod command;
 
@@ -35,19 +40,87 @@ pub struct SceneSettingsWindow {
     pub window: Handle<UiNode>,
     inspector: Handle<UiNode>,
     property_definitions: Rc<PropertyEditorDefinitionContainer>,
+    bounds_text: Handle<UiNode>,
+    copy_bounds: Handle<UiNode>,
+    bounds: Cell<Option<AxisAlignedBoundingBox>>,
+}
+
+fn format_vector(v: Vector3<f32>) -> String {
+    format!("({:.3}, {:.3}, {:.3})", v.x, v.y, v.z)
+}
+
+fn describe_bounds(bounds: Option<AxisAlignedBoundingBox>) -> String {
+    match bounds {
+        Some(bounds) => {
+            let size = bounds.max - bounds.min;
+            format!(
+                "Size: {:.3} x {:.3} x {:.3}\nCenter: {}\nMin: {}\nMax: {}",
+                size.x,
+                size.y,
+                size.z,
+                format_vector(bounds.center()),
+                format_vector(bounds.min),
+                format_vector(bounds.max)
+            )
+        }
+        None => "The scene is empty.".to_owned(),
+    }
 }
 
 impl SceneSettingsWindow {
     pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
         let inspector;
+        let bounds_text;
+        let copy_bounds;
         let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(500.0))
             .with_content(
-                ScrollViewerBuilder::new(WidgetBuilder::new())
-                    .with_content({
-                        inspector = InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
-                        inspector
-                    })
-                    .build(ctx),
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(0))
+                                .with_content({
+                                    inspector =
+                                        InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
+                                    inspector
+                                })
+                                .build(ctx),
+                        )
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_child({
+                                        bounds_text = TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(2.0)),
+                                        )
+                                        .build(ctx);
+                                        bounds_text
+                                    })
+                                    .with_child({
+                                        copy_bounds = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_column(1)
+                                                .with_width(90.0)
+                                                .with_height(24.0)
+                                                .with_margin(Thickness::uniform(2.0))
+                                                .with_vertical_alignment(VerticalAlignment::Top),
+                                        )
+                                        .with_text("Copy Bounds")
+                                        .build(ctx);
+                                        copy_bounds
+                                    }),
+                            )
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .add_row(Row::auto())
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .build(ctx),
             )
             .open(false)
             .can_minimize(false)
@@ -67,18 +140,46 @@ impl SceneSettingsWindow {
             window,
             inspector,
             property_definitions: Rc::new(container),
+            bounds_text,
+            copy_bounds,
+            bounds: Cell::new(None),
+        }
+    }
+
+    pub fn open(&self, editor_scene: Option<&EditorScene>, engine: &Engine) {
+        engine
+            .user_interface
+            .send_message(WindowMessage::open_modal(
+                self.window,
+                MessageDirection::ToWidget,
+                true,
+            ));
+        if let Some(editor_scene) = editor_scene {
+            self.sync_bounds(editor_scene, engine);
         }
     }
 
-    pub fn open(&self, ui: &UserInterface) {
-        ui.send_message(WindowMessage::open_modal(
-            self.window,
+    /// Bounds of the scene are calculated only when they're visible, it is not cheap for big
+    /// scenes.
+    fn sync_bounds(&self, editor_scene: &EditorScene, engine: &Engine) {
+        let bounds = calculate_scene_bounding_box(
+            &engine.scenes[editor_scene.scene].graph,
+            editor_scene.editor_objects_root,
+            false,
+        );
+        self.bounds.set(bounds);
+        engine.user_interface.send_message(TextMessage::text(
+            self.bounds_text,
             MessageDirection::ToWidget,
-            true,
+            describe_bounds(bounds),
         ));
     }
 
     pub fn sync_to_model(&self, editor_scene: &EditorScene, engine: &mut Engine) {
+        if engine.user_interface.node(self.window).visibility() {
+            self.sync_bounds(editor_scene, engine);
+        }
+
         let ui = &mut engine.user_interface;
         let scene = &engine.scenes[editor_scene.scene];
 
@@ -99,13 +200,24 @@ impl SceneSettingsWindow {
         ));
     }
 
-    pub fn handle_ui_message(&self, message: &UiMessage, sender: &Sender<Message>) {
+    pub fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        sender: &Sender<Message>,
+        engine: &Engine,
+    ) {
         if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
             if message.destination() == self.inspector {
                 if let Some(command) = make_set_scene_property_command((), property_changed) {
                     sender.send(Message::DoSceneCommand(command)).unwrap();
                 }
             }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.copy_bounds {
+                if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
+                    Log::verify(clipboard.set_contents(describe_bounds(self.bounds.get())));
+                }
+            }
         }
     }
 }
@@ -0,0 +1,128 @@
+//! Editor-only data of a scene, that is stored in a separate file next to the scene file. It
+//! contains things that does not belong to the scene itself, but is nice to have restored when the
+//! scene is loaded again (for example - selection).
+
+use crate::{
+    scene::{EditorScene, Selection},
+    settings::camera::SceneCameraSettings,
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::pool::Handle,
+    scene::{graph::Graph, node::Node},
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// Separator of node names in node paths.
+const PATH_SEPARATOR: &str = "/";
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct SceneSidecar {
+    /// Paths of selected nodes, see [`node_path`].
+    #[serde(default)]
+    pub selection: Vec<String>,
+    /// State of the editor camera, it is `None` if the camera should not be restored.
+    #[serde(default)]
+    pub camera: Option<SceneCameraSettings>,
+}
+
+/// Returns a path of a node in the graph: names of all its ancestors (except the root) and the
+/// name of the node, separated by `/`.
+pub fn node_path(graph: &Graph, handle: Handle<Node>) -> String {
+    let mut names = Vec::new();
+    let mut current = handle;
+    while let Some(node) = graph.try_get(current) {
+        if current == graph.get_root() {
+            break;
+        }
+        names.push(node.name());
+        current = node.parent();
+    }
+    names.reverse();
+    names.join(PATH_SEPARATOR)
+}
+
+/// Searches a node by the path made by [`node_path`]. If there are multiple children with the same
+/// name, the first one is taken.
+fn find_by_path(graph: &Graph, path: &str) -> Handle<Node> {
+    let mut current = graph.get_root();
+    if path.is_empty() {
+        return current;
+    }
+    for name in path.split(PATH_SEPARATOR) {
+        match graph[current]
+            .children()
+            .iter()
+            .find(|c| graph[**c].name() == name)
+        {
+            Some(child) => current = *child,
+            None => return Handle::NONE,
+        }
+    }
+    current
+}
+
+impl SceneSidecar {
+    /// Returns a path of the sidecar file of a scene at the given path, for `level.rgs` it will be
+    /// `level.rgs.editor`.
+    pub fn path(scene_path: &Path) -> PathBuf {
+        let mut path = scene_path.as_os_str().to_owned();
+        path.push(".editor");
+        path.into()
+    }
+
+    pub fn from_editor_scene(editor_scene: &EditorScene, graph: &Graph, save_camera: bool) -> Self {
+        let selection = match &editor_scene.selection {
+            Selection::Graph(selection) => selection
+                .nodes()
+                .iter()
+                .filter(|h| graph.is_valid_handle(**h))
+                .map(|h| node_path(graph, *h))
+                .collect(),
+            _ => Default::default(),
+        };
+
+        let camera = if save_camera {
+            Some(editor_scene.camera_controller.scene_camera_settings(graph))
+        } else {
+            None
+        };
+
+        Self { selection, camera }
+    }
+
+    pub fn load(scene_path: &Path) -> Result<Self, String> {
+        let path = Self::path(scene_path);
+        let file = File::open(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        ron::de::from_reader(file).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, scene_path: &Path) -> Result<(), String> {
+        let path = Self::path(scene_path);
+        let file = File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        ron::ser::to_writer_pretty(file, self, PrettyConfig::default())
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Returns a selection made of nodes with the stored paths, nodes that no longer exist are
+    /// skipped. `None` is returned if no stored node was found.
+    pub fn restore_selection(&self, graph: &Graph) -> Option<Selection> {
+        let nodes = self
+            .selection
+            .iter()
+            .map(|path| find_by_path(graph, path))
+            .filter(|h| h.is_some())
+            .collect::<Vec<_>>();
+
+        if nodes.is_empty() {
+            None
+        } else {
+            Some(Selection::Graph(GraphSelection::from_list(nodes)))
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use fyrox::{
+    core::{futures::executor::block_on, pool::Handle},
+    engine::{resource_manager::ResourceManager, SerializationContext},
+    gui::{
+        grid::{Column, GridBuilder, Row},
+        message::MessageDirection,
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::{Scene, SceneLoader},
+};
+use std::{
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+};
+
+/// Result of a scene loading, path is the path of the scene that was requested to load.
+pub struct LoadedScene {
+    pub path: PathBuf,
+    pub result: Result<Scene, String>,
+}
+
+/// Modal window that is shown while a scene is loading in a background thread. It blocks the
+/// input to the editor and shows progress of loading of the resources that the scene depends on.
+pub struct SceneLoadingWindow {
+    window: Handle<UiNode>,
+    text: Handle<UiNode>,
+    progress_bar: Handle<UiNode>,
+    receiver: Option<Receiver<LoadedScene>>,
+}
+
+impl SceneLoadingWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let text;
+        let progress_bar;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(100.0))
+            .can_minimize(false)
+            .can_close(false)
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            text = TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            text
+                        })
+                        .with_child({
+                            progress_bar = ProgressBarBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            progress_bar
+                        }),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_title(WindowTitle::text("Loading Scene..."))
+            .build(ctx);
+
+        Self {
+            window,
+            text,
+            progress_bar,
+            receiver: None,
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Starts loading of a scene in a background thread. Use [`Self::update`] to fetch the result.
+    pub fn load(
+        &mut self,
+        path: PathBuf,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+        ui: &UserInterface,
+    ) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(TextMessage::text(
+            self.text,
+            MessageDirection::ToWidget,
+            format!("Loading {}...", path.display()),
+        ));
+        ui.send_message(ProgressBarMessage::progress(
+            self.progress_bar,
+            MessageDirection::ToWidget,
+            0.0,
+        ));
+
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+
+        std::thread::spawn(move || {
+            let result = match block_on(SceneLoader::from_file(&path, serialization_context)) {
+                Ok(loader) => Ok(block_on(loader.finish(resource_manager))),
+                Err(e) => Err(e.to_string()),
+            };
+
+            // The receiver could be dropped if the editor was closed in the middle of loading.
+            let _ = sender.send(LoadedScene { path, result });
+        });
+    }
+
+    /// Updates loading progress and returns loaded scene (or loading error) when loading is done.
+    pub fn update(
+        &mut self,
+        resource_manager: &ResourceManager,
+        ui: &UserInterface,
+    ) -> Option<LoadedScene> {
+        let loaded_scene = match self.receiver.as_ref()?.try_recv() {
+            Ok(loaded_scene) => loaded_scene,
+            Err(TryRecvError::Empty) => {
+                ui.send_message(ProgressBarMessage::progress(
+                    self.progress_bar,
+                    MessageDirection::ToWidget,
+                    resource_manager.state().loading_progress() as f32 / 100.0,
+                ));
+                return None;
+            }
+            Err(TryRecvError::Disconnected) => LoadedScene {
+                path: Default::default(),
+                result: Err("Scene loading thread has crashed!".to_owned()),
+            },
+        };
+
+        self.receiver = None;
+
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+
+        Some(loaded_scene)
+    }
+}
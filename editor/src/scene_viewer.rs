@@ -1,14 +1,18 @@
 use crate::{
-    camera::PickingOptions, gui::make_dropdown_list_option,
-    gui::make_dropdown_list_option_with_height, load_image, settings::keys::KeyBindings,
-    utils::enable_widget, AddModelCommand, AssetItem, AssetKind, BuildProfile,
-    ChangeSelectionCommand, CommandGroup, DropdownListBuilder, EditorScene, GameEngine,
-    GraphSelection, InteractionMode, InteractionModeKind, Message, Mode, SceneCommand, Selection,
-    SetMeshTextureCommand, Settings,
+    camera::surface_normal_below, camera::PickingOptions, gui::make_dropdown_list_option,
+    gui::make_dropdown_list_option_with_height, interaction::pivot::PivotMode,
+    interaction::transform_locks::TransformLockKind, interaction::transform_locks::TransformLocks,
+    load_image, scene::commands::graph::align_rotation_to_normal,
+    scene::commands::graph::AddNodeCommand, scene::commands::graph::SURFACE_PROBE_HEIGHT,
+    scene::ViewportRenderMode, send_sync_message, settings::debugging::NodeNameLabelFilter,
+    settings::keys::KeyBindings, utils::enable_widget, AddModelCommand, AssetItem, AssetKind,
+    BuildProfile, ChangeSelectionCommand, CommandGroup, DropdownListBuilder, EditorScene,
+    GameEngine, GraphSelection, InteractionMode, InteractionModeKind, Message, Mode, SceneCommand,
+    Selection, SetMeshTextureCommand, Settings, MSG_SYNC_FLAG,
 };
 use fyrox::{
     core::{
-        algebra::{Vector2, Vector3},
+        algebra::{UnitQuaternion, Vector2, Vector3},
         color::Color,
         make_relative_path,
         math::{plane::Plane, Rect},
@@ -21,32 +25,161 @@ use fyrox::{
         brush::{Brush, GradientPoint},
         button::{Button, ButtonBuilder, ButtonContent, ButtonMessage},
         canvas::CanvasBuilder,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         decorator::{DecoratorBuilder, DecoratorMessage},
         dropdown_list::DropdownListMessage,
         grid::{Column, GridBuilder, Row},
         image::{ImageBuilder, ImageMessage},
         message::{KeyCode, MessageDirection, MouseButton, UiMessage},
         stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
         utils::make_simple_tooltip,
         vec::vec3::{Vec3EditorBuilder, Vec3EditorMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
-        BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST, COLOR_DARKEST,
-        COLOR_LIGHTEST,
+        VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+        COLOR_DARKEST, COLOR_LIGHTEST,
     },
     resource::texture::{Texture, TextureState},
     scene::{
-        camera::{Camera, Projection},
+        base::BaseBuilder,
+        camera::{Camera, CameraBuilder, Projection},
+        graph::Graph,
+        light::{
+            directional::DirectionalLightBuilder, point::PointLightBuilder, spot::SpotLightBuilder,
+            BaseLightBuilder,
+        },
         node::Node,
+        pivot::PivotBuilder,
     },
     utils::into_gui_texture,
 };
-use std::sync::mpsc::Sender;
+use std::{cmp::Ordering, sync::mpsc::Sender};
+
+/// Minimal distance (in pixels) the cursor should travel to update the hovered node.
+const HOVER_PICK_DISTANCE: f32 = 3.0;
+
+struct NodeLabel {
+    text: Handle<UiNode>,
+    name: String,
+    visible: bool,
+}
 
 struct PreviewInstance {
     instance: Handle<Node>,
     nodes: FxHashSet<Handle<Node>>,
+    // Initial rotation of the instance, alignment to a surface is always applied on top of it.
+    rotation: UnitQuaternion<f32>,
+}
+
+/// Built-in node types that can be added from the toolbar of the scene viewer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum QuickCreateKind {
+    PointLight,
+    SpotLight,
+    DirectionalLight,
+    Camera,
+    Empty,
+}
+
+impl QuickCreateKind {
+    const ALL: [Self; 5] = [
+        Self::PointLight,
+        Self::SpotLight,
+        Self::DirectionalLight,
+        Self::Camera,
+        Self::Empty,
+    ];
+
+    fn button_text(self) -> &'static str {
+        match self {
+            Self::PointLight => "Point",
+            Self::SpotLight => "Spot",
+            Self::DirectionalLight => "Directional",
+            Self::Camera => "Camera",
+            Self::Empty => "Empty",
+        }
+    }
+
+    fn tooltip(self) -> &'static str {
+        match self {
+            Self::PointLight => "Point Light",
+            Self::SpotLight => "Spot Light",
+            Self::DirectionalLight => "Directional Light",
+            Self::Camera => "Camera",
+            Self::Empty => "Empty Pivot",
+        }
+    }
+
+    // Uses the same defaults as the "Create" menu.
+    fn make_node(self) -> Node {
+        match self {
+            Self::PointLight => PointLightBuilder::new(BaseLightBuilder::new(
+                BaseBuilder::new().with_name("PointLight"),
+            ))
+            .with_radius(10.0)
+            .build_node(),
+            Self::SpotLight => SpotLightBuilder::new(BaseLightBuilder::new(
+                BaseBuilder::new().with_name("SpotLight"),
+            ))
+            .with_distance(10.0)
+            .with_hotspot_cone_angle(45.0f32.to_radians())
+            .with_falloff_angle_delta(2.0f32.to_radians())
+            .build_node(),
+            Self::DirectionalLight => DirectionalLightBuilder::new(BaseLightBuilder::new(
+                BaseBuilder::new().with_name("DirectionalLight"),
+            ))
+            .build_node(),
+            Self::Camera => CameraBuilder::new(BaseBuilder::new().with_name("Camera")).build_node(),
+            Self::Empty => PivotBuilder::new(BaseBuilder::new().with_name("Pivot")).build_node(),
+        }
+    }
+}
+
+/// Returns a point in the scene under the given position in the frame. It is the closest
+/// intersection with scene objects, or, in case of empty space, an intersection with oXZ plane
+/// (3D) or oXY plane (2D).
+fn pick_placement_position<F>(
+    editor_scene: &mut EditorScene,
+    graph: &Graph,
+    rel_pos: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    settings: &Settings,
+    filter: F,
+) -> Option<Vector3<f32>>
+where
+    F: FnMut(Handle<Node>, &Node) -> bool,
+{
+    if let Some(result) = editor_scene.camera_controller.pick(PickingOptions {
+        cursor_pos: rel_pos,
+        graph,
+        editor_objects_root: editor_scene.editor_objects_root,
+        screen_size: frame_size,
+        editor_only: false,
+        filter,
+        ignore_back_faces: settings.selection.ignore_back_faces,
+        // We need info only about closest intersection.
+        use_picking_loop: false,
+        only_meshes: false,
+    }) {
+        Some(result.position)
+    } else {
+        let camera = graph[editor_scene.camera_controller.camera]
+            .query_component_ref::<Camera>()
+            .unwrap();
+
+        let normal = match camera.projection() {
+            Projection::Perspective(_) => Vector3::new(0.0, 1.0, 0.0),
+            Projection::Orthographic(_) => Vector3::new(0.0, 0.0, 1.0),
+        };
+
+        let plane = Plane::from_normal_and_point(&normal, &Default::default()).unwrap_or_default();
+
+        let ray = camera.make_ray(rel_pos, frame_size);
+
+        ray.plane_intersection_point(&plane)
+    }
 }
 
 pub struct SceneViewer {
@@ -54,6 +187,9 @@ pub struct SceneViewer {
     window: Handle<UiNode>,
     pub last_mouse_pos: Option<Vector2<f32>>,
     pub click_mouse_pos: Option<Vector2<f32>>,
+    /// Position of Shift + right click that places the 3D cursor, if the mouse button is held.
+    cursor_click_pos: Option<Vector2<f32>>,
+    hover_pick_pos: Option<Vector2<f32>>,
     selection_frame: Handle<UiNode>,
     // Side bar stuff
     select_mode: Handle<UiNode>,
@@ -62,14 +198,33 @@ pub struct SceneViewer {
     scale_mode: Handle<UiNode>,
     navmesh_mode: Handle<UiNode>,
     terrain_mode: Handle<UiNode>,
+    collider_mode: Handle<UiNode>,
+    vertex_paint_mode: Handle<UiNode>,
+    waypoint_mode: Handle<UiNode>,
     camera_projection: Handle<UiNode>,
+    render_mode: Handle<UiNode>,
+    follow_selection: Handle<UiNode>,
+    post_processing_preview: Handle<UiNode>,
     switch_mode: Handle<UiNode>,
+    pause_logic: Handle<UiNode>,
+    step_logic: Handle<UiNode>,
+    logic_paused: bool,
     build_profile: Handle<UiNode>,
     sender: Sender<Message>,
     interaction_mode_panel: Handle<UiNode>,
     contextual_actions: Handle<UiNode>,
     global_position_display: Handle<UiNode>,
     preview_instance: Option<PreviewInstance>,
+    overlay_canvas: Handle<UiNode>,
+    node_labels: Vec<NodeLabel>,
+    quick_create_panel: Handle<UiNode>,
+    quick_create_buttons: Vec<(Handle<UiNode>, QuickCreateKind)>,
+    /// Toggle buttons of transform locks, indexed by [`TransformLockKind`] and axis.
+    lock_buttons: [[Handle<UiNode>; 3]; 3],
+    transform_locks: TransformLocks,
+    pivot_mode_selector: Handle<UiNode>,
+    pivot_mode: PivotMode,
+    clear_cursor: Handle<UiNode>,
 }
 
 fn make_interaction_mode_button(
@@ -125,6 +280,27 @@ fn make_interaction_mode_button(
     .build(ctx)
 }
 
+fn make_transform_lock_button(ctx: &mut BuildContext, text: &str, tooltip: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(20.0)
+            .with_tooltip(make_simple_tooltip(ctx, tooltip))
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_back(
+        DecoratorBuilder::new(BorderBuilder::new(
+            WidgetBuilder::new().with_foreground(BRUSH_LIGHTEST),
+        ))
+        .with_normal_brush(BRUSH_LIGHT)
+        .with_hover_brush(BRUSH_LIGHTER)
+        .with_pressed_brush(BRUSH_LIGHTEST)
+        .with_selected_brush(BRUSH_BRIGHT_BLUE)
+        .build(ctx),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
 impl SceneViewer {
     pub fn new(engine: &mut GameEngine, sender: Sender<Message>) -> Self {
         let ctx = &mut engine.user_interface.build_ctx();
@@ -156,6 +332,21 @@ impl SceneViewer {
             "Edit Terrain\n\nTerrain edit mode allows you to modify selected \
         terrain.";
 
+        let collider_mode_tooltip =
+            "Edit Collider - Shortcut: [7]\n\nCollider edit mode shows the \
+        shape of the collider of selected node and allows you to change its size and offset by \
+        dragging the handles.";
+
+        let vertex_paint_mode_tooltip =
+            "Paint Vertex Colors - Shortcut: [8]\n\nVertex paint mode allows you to paint colors \
+        of vertices of selected mesh. Hold Shift to paint with white color. Brush options are \
+        in the Vertex Paint window.";
+
+        let waypoint_mode_tooltip =
+            "Place Waypoints - Shortcut: [9]\n\nWaypoint mode allows you to place waypoints of \
+        paths by clicking on surfaces. A new waypoint is added after the selected one, a new path \
+        is started if no path is selected. Waypoints can be reordered in the Waypoints window.";
+
         let frame;
         let select_mode;
         let move_mode;
@@ -163,9 +354,18 @@ impl SceneViewer {
         let scale_mode;
         let navmesh_mode;
         let terrain_mode;
+        let collider_mode;
+        let vertex_paint_mode;
+        let waypoint_mode;
         let selection_frame;
+        let overlay_canvas;
         let camera_projection;
+        let render_mode;
+        let follow_selection;
+        let post_processing_preview;
         let switch_mode;
+        let pause_logic;
+        let step_logic;
         let build_profile;
 
         let interaction_mode_panel = StackPanelBuilder::new(
@@ -226,6 +426,33 @@ impl SceneViewer {
                         false,
                     );
                     terrain_mode
+                })
+                .with_child({
+                    collider_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/collider.png"),
+                        collider_mode_tooltip,
+                        false,
+                    );
+                    collider_mode
+                })
+                .with_child({
+                    vertex_paint_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/vertex_paint.png"),
+                        vertex_paint_mode_tooltip,
+                        false,
+                    );
+                    vertex_paint_mode
+                })
+                .with_child({
+                    waypoint_mode = make_interaction_mode_button(
+                        ctx,
+                        include_bytes!("../resources/embed/waypoint.png"),
+                        waypoint_mode_tooltip,
+                        false,
+                    );
+                    waypoint_mode
                 }),
         )
         .build(ctx);
@@ -234,6 +461,66 @@ impl SceneViewer {
             WidgetBuilder::new()
                 .on_column(1)
                 .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_child({
+                    follow_selection = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Makes the camera follow the selected node, keeping the current \
+                                offset between them.",
+                            )),
+                    )
+                    .with_content(
+                        TextBuilder::new(WidgetBuilder::new())
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text("Follow Selection")
+                            .build(ctx),
+                    )
+                    .checked(Some(false))
+                    .build(ctx);
+                    follow_selection
+                })
+                .with_child({
+                    post_processing_preview = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Shows the viewport with exposure and color grading of the scene's \
+                                camera and with bloom, as it will look in the game. When disabled, \
+                                neutral settings are used for accurate color work. The scene itself \
+                                is not changed.",
+                            )),
+                    )
+                    .with_content(
+                        TextBuilder::new(WidgetBuilder::new())
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text("Post Effects")
+                            .build(ctx),
+                    )
+                    .checked(Some(false))
+                    .build(ctx);
+                    post_processing_preview
+                })
+                .with_child({
+                    render_mode = DropdownListBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(150.0),
+                    )
+                    .with_items(
+                        ViewportRenderMode::ALL
+                            .iter()
+                            .map(|mode| {
+                                make_dropdown_list_option_with_height(ctx, mode.name(), 22.0)
+                            })
+                            .collect(),
+                    )
+                    .with_selected(0)
+                    .build(ctx);
+                    render_mode
+                })
                 .with_child({
                     camera_projection = DropdownListBuilder::new(
                         WidgetBuilder::new()
@@ -252,8 +539,48 @@ impl SceneViewer {
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
+        let mut quick_create_buttons = Vec::new();
+        let mut quick_create_panel_builder = WidgetBuilder::new()
+            .with_horizontal_alignment(HorizontalAlignment::Left)
+            .with_child(
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness {
+                    left: 4.0,
+                    top: 1.0,
+                    right: 2.0,
+                    bottom: 1.0,
+                }))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text("Add:")
+                .build(ctx),
+            );
+        for kind in QuickCreateKind::ALL {
+            // Buttons are draggable, so a node could be dropped right into the scene.
+            let button = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_allow_drag(true)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        &format!(
+                            "{}\nClick to add at the 3D cursor (or at the center of the view if \
+                            the cursor is not placed), or drag and drop into the view to place \
+                            under the mouse cursor.",
+                            kind.tooltip()
+                        ),
+                    )),
+            )
+            .with_text(kind.button_text())
+            .build(ctx);
+            quick_create_buttons.push((button, kind));
+            quick_create_panel_builder = quick_create_panel_builder.with_child(button);
+        }
+        let quick_create_panel = StackPanelBuilder::new(quick_create_panel_builder)
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
         let top_ribbon = GridBuilder::new(
             WidgetBuilder::new()
+                .with_child(quick_create_panel)
                 .with_child({
                     StackPanelBuilder::new(
                         WidgetBuilder::new()
@@ -268,6 +595,38 @@ impl SceneViewer {
                                 .build(ctx);
                                 switch_mode
                             })
+                            .with_child({
+                                pause_logic = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_width(60.0)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Pauses or resumes scripts and plugins of the running \
+                                            game, the game is still rendered while paused.",
+                                        )),
+                                )
+                                .with_text("Pause")
+                                .build(ctx);
+                                pause_logic
+                            })
+                            .with_child({
+                                step_logic = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_width(60.0)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Updates paused scripts and plugins once, using a \
+                                            single fixed time step.",
+                                        )),
+                                )
+                                .with_text("Step")
+                                .build(ctx);
+                                step_logic
+                            })
                             .with_child({
                                 build_profile = DropdownListBuilder::new(
                                     WidgetBuilder::new()
@@ -293,11 +652,86 @@ impl SceneViewer {
         .add_row(Row::stretch())
         .build(ctx);
 
+        let mut lock_buttons = [[Handle::NONE; 3]; 3];
+        let mut lock_widgets = Vec::new();
+        for (kind_index, kind) in TransformLockKind::ALL.iter().enumerate() {
+            lock_widgets.push(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::left(4.0))
+                        .with_vertical_alignment(VerticalAlignment::Center),
+                )
+                .with_text(kind.name())
+                .build(ctx),
+            );
+            for (axis, axis_name) in ["X", "Y", "Z"].iter().enumerate() {
+                let button = make_transform_lock_button(
+                    ctx,
+                    axis_name,
+                    &format!("Lock {} Along {} Axis", kind.name(), axis_name),
+                );
+                lock_buttons[kind_index][axis] = button;
+                lock_widgets.push(button);
+            }
+        }
+
+        let pivot_mode_selector;
+        let clear_cursor;
         let global_position_display;
         let bottom_toolbar = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_horizontal_alignment(HorizontalAlignment::Right)
                 .with_margin(Thickness::uniform(1.0))
+                .with_child(
+                    TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::left(4.0))
+                            .with_vertical_alignment(VerticalAlignment::Center),
+                    )
+                    .with_text("Pivot")
+                    .build(ctx),
+                )
+                .with_child({
+                    pivot_mode_selector = DropdownListBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(130.0)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Point around which multiple selected nodes are rotated and \
+                                scaled. With individual origins each node is transformed around \
+                                its own origin, with other modes the selection is transformed as a \
+                                whole.",
+                            )),
+                    )
+                    .with_items(
+                        PivotMode::ALL
+                            .iter()
+                            .map(|mode| {
+                                make_dropdown_list_option_with_height(ctx, mode.name(), 22.0)
+                            })
+                            .collect(),
+                    )
+                    .with_selected(0)
+                    .build(ctx);
+                    pivot_mode_selector
+                })
+                .with_child({
+                    clear_cursor = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Removes the 3D cursor. The cursor is placed by Shift + right \
+                                click in the scene, new nodes are created at the cursor and it \
+                                can be used as a pivot.",
+                            )),
+                    )
+                    .with_text("Clear Cursor")
+                    .build(ctx);
+                    clear_cursor
+                })
+                .with_children(lock_widgets)
                 .with_child({
                     global_position_display = Vec3EditorBuilder::<f32>::new(
                         WidgetBuilder::new()
@@ -316,7 +750,7 @@ impl SceneViewer {
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("SceneViewer"))
             .can_close(false)
             .can_minimize(false)
             .with_content(
@@ -339,8 +773,8 @@ impl SceneViewer {
                                         .build(ctx);
                                         frame
                                     })
-                                    .with_child(
-                                        CanvasBuilder::new(
+                                    .with_child({
+                                        overlay_canvas = CanvasBuilder::new(
                                             WidgetBuilder::new().on_column(1).with_child({
                                                 selection_frame = BorderBuilder::new(
                                                     WidgetBuilder::new()
@@ -357,8 +791,9 @@ impl SceneViewer {
                                                 selection_frame
                                             }),
                                         )
-                                        .build(ctx),
-                                    )
+                                        .build(ctx);
+                                        overlay_canvas
+                                    })
                                     .with_child(interaction_mode_panel),
                             )
                             .add_row(Row::stretch())
@@ -389,14 +824,34 @@ impl SceneViewer {
             select_mode,
             navmesh_mode,
             terrain_mode,
+            collider_mode,
+            vertex_paint_mode,
+            waypoint_mode,
             camera_projection,
+            render_mode,
+            follow_selection,
+            post_processing_preview,
             click_mouse_pos: None,
+            cursor_click_pos: None,
+            hover_pick_pos: None,
             switch_mode,
+            pause_logic,
+            step_logic,
+            logic_paused: false,
             interaction_mode_panel,
             contextual_actions,
             global_position_display,
             build_profile,
             preview_instance: None,
+            overlay_canvas,
+            node_labels: Default::default(),
+            quick_create_panel,
+            quick_create_buttons,
+            lock_buttons,
+            transform_locks: Default::default(),
+            pivot_mode_selector,
+            pivot_mode: Default::default(),
+            clear_cursor,
         }
     }
 }
@@ -414,6 +869,63 @@ impl SceneViewer {
         self.selection_frame
     }
 
+    /// Returns current transform locks, they're kept for the whole session and should be passed
+    /// to every new scene.
+    pub fn transform_locks(&self) -> TransformLocks {
+        self.transform_locks
+    }
+
+    /// Returns current pivot mode, it is kept for the whole session and should be passed to
+    /// every new scene.
+    pub fn pivot_mode(&self) -> PivotMode {
+        self.pivot_mode
+    }
+
+    /// Remembers the pivot mode and shows it in the selector.
+    pub fn set_pivot_mode(&mut self, ui: &UserInterface, mode: PivotMode) {
+        self.pivot_mode = mode;
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.pivot_mode_selector,
+                MessageDirection::ToWidget,
+                PivotMode::ALL.iter().position(|m| *m == mode),
+            ),
+        );
+    }
+
+    fn toggle_transform_lock(
+        &mut self,
+        button: Handle<UiNode>,
+        ui: &UserInterface,
+        editor_scene: Option<&mut EditorScene>,
+    ) -> bool {
+        for (kind, buttons) in TransformLockKind::ALL.iter().zip(self.lock_buttons.iter()) {
+            if let Some(axis) = buttons.iter().position(|b| *b == button) {
+                let axes = self.transform_locks.axes_mut(*kind);
+                axes[axis] = !axes[axis];
+
+                let decorator = ui
+                    .node(button)
+                    .query_component::<Button>()
+                    .unwrap()
+                    .decorator;
+                ui.send_message(DecoratorMessage::select(
+                    decorator,
+                    MessageDirection::ToWidget,
+                    self.transform_locks.axes(*kind)[axis],
+                ));
+
+                if let Some(editor_scene) = editor_scene {
+                    editor_scene.transform_locks = self.transform_locks;
+                }
+
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn handle_message(&mut self, message: &Message, engine: &mut Engine) {
         if let Message::SetInteractionMode(mode) = message {
             let active_button = match mode {
@@ -423,6 +935,9 @@ impl SceneViewer {
                 InteractionModeKind::Rotate => self.rotate_mode,
                 InteractionModeKind::Navmesh => self.navmesh_mode,
                 InteractionModeKind::Terrain => self.terrain_mode,
+                InteractionModeKind::Collider => self.collider_mode,
+                InteractionModeKind::VertexPaint => self.vertex_paint_mode,
+                InteractionModeKind::Waypoint => self.waypoint_mode,
             };
 
             for mode_button in [
@@ -432,6 +947,9 @@ impl SceneViewer {
                 self.rotate_mode,
                 self.navmesh_mode,
                 self.terrain_mode,
+                self.collider_mode,
+                self.vertex_paint_mode,
+                self.waypoint_mode,
             ] {
                 let decorator = engine
                     .user_interface
@@ -453,7 +971,7 @@ impl SceneViewer {
         &mut self,
         message: &mut UiMessage,
         engine: &mut Engine,
-        editor_scene: Option<&mut EditorScene>,
+        mut editor_scene: Option<&mut EditorScene>,
         interaction_mode: Option<&mut Box<dyn InteractionMode>>,
         settings: &Settings,
         mode: &Mode,
@@ -485,8 +1003,43 @@ impl SceneViewer {
                 self.sender
                     .send(Message::SetInteractionMode(InteractionModeKind::Terrain))
                     .unwrap();
+            } else if message.destination() == self.collider_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Collider))
+                    .unwrap();
+            } else if message.destination() == self.vertex_paint_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(
+                        InteractionModeKind::VertexPaint,
+                    ))
+                    .unwrap();
+            } else if message.destination() == self.waypoint_mode {
+                self.sender
+                    .send(Message::SetInteractionMode(InteractionModeKind::Waypoint))
+                    .unwrap();
             } else if message.destination() == self.switch_mode {
                 self.sender.send(Message::SwitchMode).unwrap();
+            } else if message.destination() == self.pause_logic {
+                self.set_logic_paused(ui, !self.logic_paused);
+                self.sender
+                    .send(Message::SetLogicPaused(self.logic_paused))
+                    .unwrap();
+            } else if message.destination() == self.step_logic {
+                self.sender.send(Message::StepLogic).unwrap();
+            } else if message.destination() == self.clear_cursor {
+                if let Some(editor_scene) = editor_scene.as_deref_mut() {
+                    editor_scene.cursor = None;
+                }
+            } else if self.toggle_transform_lock(
+                message.destination(),
+                ui,
+                editor_scene.as_deref_mut(),
+            ) {
+                // Handled.
+            } else if let Some(kind) = self.quick_create_kind(message.destination()) {
+                if let Some(editor_scene) = editor_scene.as_deref_mut() {
+                    self.quick_create(kind, editor_scene, engine, settings);
+                }
             }
         } else if let Some(WidgetMessage::MouseDown { button, .. }) =
             message.data::<WidgetMessage>()
@@ -496,9 +1049,24 @@ impl SceneViewer {
             {
                 self.sender.send(Message::OpenSettings).unwrap();
             }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.follow_selection
+                && message.direction == MessageDirection::FromWidget
+            {
+                self.sender
+                    .send(Message::SetEditorCameraFollow(*value))
+                    .unwrap();
+            } else if message.destination() == self.post_processing_preview
+                && message.direction == MessageDirection::FromWidget
+            {
+                self.sender
+                    .send(Message::SetPostProcessingPreview(*value))
+                    .unwrap();
+            }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.direction == MessageDirection::FromWidget {
-                if message.destination() == self.camera_projection {
+                if message.destination() == self.camera_projection && message.flags != MSG_SYNC_FLAG
+                {
                     if *index == 0 {
                         self.sender
                             .send(Message::SetEditorCameraProjection(Projection::Perspective(
@@ -512,6 +1080,20 @@ impl SceneViewer {
                             ))
                             .unwrap()
                     }
+                } else if message.destination() == self.pivot_mode_selector
+                    && message.flags != MSG_SYNC_FLAG
+                {
+                    if let Some(pivot_mode) = PivotMode::ALL.get(*index) {
+                        self.sender
+                            .send(Message::SetPivotMode(*pivot_mode))
+                            .unwrap();
+                    }
+                } else if message.destination() == self.render_mode {
+                    if let Some(render_mode) = ViewportRenderMode::ALL.get(*index) {
+                        self.sender
+                            .send(Message::SetViewportRenderMode(*render_mode))
+                            .unwrap();
+                    }
                 } else if message.destination() == self.build_profile {
                     if *index == 0 {
                         self.sender
@@ -578,6 +1160,9 @@ impl SceneViewer {
                         }
                     }
                     WidgetMessage::MouseLeave => {
+                        editor_scene.hovered_node = Handle::NONE;
+                        self.hover_pick_pos = None;
+
                         if let Some(preview) = self.preview_instance.take() {
                             let scene = &mut engine.scenes[editor_scene.scene];
 
@@ -616,11 +1201,29 @@ impl SceneViewer {
                                                     .traverse_handle_iter(instance)
                                                     .collect::<FxHashSet<Handle<Node>>>();
 
-                                                self.preview_instance =
-                                                    Some(PreviewInstance { instance, nodes });
+                                                let rotation = **scene.graph[instance]
+                                                    .local_transform()
+                                                    .rotation();
+
+                                                self.preview_instance = Some(PreviewInstance {
+                                                    instance,
+                                                    nodes,
+                                                    rotation,
+                                                });
                                             }
                                         }
                                     }
+                                } else if let Some(kind) = self.quick_create_kind(handle) {
+                                    let scene = &mut engine.scenes[editor_scene.scene];
+                                    let instance = scene.graph.add_node(kind.make_node());
+                                    let nodes = std::iter::once(instance).collect();
+                                    let rotation =
+                                        **scene.graph[instance].local_transform().rotation();
+                                    self.preview_instance = Some(PreviewInstance {
+                                        instance,
+                                        nodes,
+                                        rotation,
+                                    });
                                 }
                             }
                             Some(preview) => {
@@ -630,46 +1233,46 @@ impl SceneViewer {
                                 let rel_pos = cursor_pos - screen_bounds.position;
                                 let graph = &mut engine.scenes[editor_scene.scene].graph;
 
-                                let position = if let Some(result) =
-                                    editor_scene.camera_controller.pick(PickingOptions {
-                                        cursor_pos: rel_pos,
-                                        graph,
-                                        editor_objects_root: editor_scene.editor_objects_root,
-                                        screen_size: frame_size,
-                                        editor_only: false,
-                                        filter: |handle, _| !preview.nodes.contains(&handle),
-                                        ignore_back_faces: settings.selection.ignore_back_faces,
-                                        // We need info only about closest intersection.
-                                        use_picking_loop: false,
-                                        only_meshes: false,
-                                    }) {
-                                    Some(result.position)
-                                } else {
-                                    // In case of empty space, check intersection with oXZ plane (3D) or oXY (2D).
-                                    let camera = graph[editor_scene.camera_controller.camera]
-                                        .query_component_ref::<Camera>()
-                                        .unwrap();
-
-                                    let normal = match camera.projection() {
-                                        Projection::Perspective(_) => Vector3::new(0.0, 1.0, 0.0),
-                                        Projection::Orthographic(_) => Vector3::new(0.0, 0.0, 1.0),
-                                    };
-
-                                    let plane =
-                                        Plane::from_normal_and_point(&normal, &Default::default())
-                                            .unwrap_or_default();
-
-                                    let ray = camera.make_ray(rel_pos, frame_size);
-
-                                    ray.plane_intersection_point(&plane)
-                                };
+                                let position = pick_placement_position(
+                                    editor_scene,
+                                    graph,
+                                    rel_pos,
+                                    frame_size,
+                                    settings,
+                                    |handle, _| !preview.nodes.contains(&handle),
+                                );
 
                                 if let Some(position) = position {
-                                    graph[preview.instance].local_transform_mut().set_position(
-                                        settings
-                                            .move_mode_settings
-                                            .try_snap_vector_to_grid(position),
-                                    );
+                                    let position = settings
+                                        .move_mode_settings
+                                        .try_snap_vector_to_grid(position);
+
+                                    graph[preview.instance]
+                                        .local_transform_mut()
+                                        .set_position(position);
+
+                                    if settings.model.align_to_surface {
+                                        // Preview instance is a child of the root, so its local
+                                        // rotation is the global one.
+                                        let rotation = surface_normal_below(
+                                            graph,
+                                            position + Vector3::new(0.0, SURFACE_PROBE_HEIGHT, 0.0),
+                                            editor_scene.editor_objects_root,
+                                            |handle, _| !preview.nodes.contains(&handle),
+                                        )
+                                        .map(|normal| {
+                                            align_rotation_to_normal(
+                                                preview.rotation,
+                                                normal,
+                                                settings.model.surface_alignment,
+                                            )
+                                        })
+                                        .unwrap_or(preview.rotation);
+
+                                        graph[preview.instance]
+                                            .local_transform_mut()
+                                            .set_rotation(rotation);
+                                    }
                                 }
                             }
                         }
@@ -684,6 +1287,21 @@ impl SceneViewer {
     }
 
     pub fn sync_to_model(&self, editor_scene: &EditorScene, engine: &Engine) {
+        let scene = &engine.scenes[editor_scene.scene];
+        let following = scene
+            .graph
+            .is_valid_handle(editor_scene.camera_controller.followed_node());
+        engine.user_interface.send_message(CheckBoxMessage::checked(
+            self.follow_selection,
+            MessageDirection::ToWidget,
+            Some(following),
+        ));
+        engine.user_interface.send_message(CheckBoxMessage::checked(
+            self.post_processing_preview,
+            MessageDirection::ToWidget,
+            Some(editor_scene.post_processing_preview),
+        ));
+
         if let Selection::Graph(ref selection) = editor_scene.selection {
             let scene = &engine.scenes[editor_scene.scene];
             if let Some((_, position)) = selection.global_rotation_position(&scene.graph) {
@@ -696,16 +1314,78 @@ impl SceneViewer {
         }
     }
 
-    pub fn on_mode_changed(&self, ui: &UserInterface, mode: &Mode) {
+    fn quick_create_kind(&self, handle: Handle<UiNode>) -> Option<QuickCreateKind> {
+        self.quick_create_buttons
+            .iter()
+            .find(|(button, _)| *button == handle)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Adds a new node at the center of the view.
+    fn quick_create(
+        &self,
+        kind: QuickCreateKind,
+        editor_scene: &mut EditorScene,
+        engine: &Engine,
+        settings: &Settings,
+    ) {
+        let frame_size = self.frame_bounds(&engine.user_interface).size;
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        // The cursor is already snapped to the grid when it is placed.
+        let position = match editor_scene.cursor {
+            Some(cursor) => cursor,
+            None => settings.move_mode_settings.try_snap_vector_to_grid(
+                pick_placement_position(
+                    editor_scene,
+                    graph,
+                    frame_size.scale(0.5),
+                    frame_size,
+                    settings,
+                    |_, _| true,
+                )
+                .unwrap_or_default(),
+            ),
+        };
+
+        let mut node = kind.make_node();
+        node.local_transform_mut().set_position(position);
+        self.sender
+            .send(Message::do_scene_command(AddNodeCommand::new(
+                node,
+                graph.get_root(),
+            )))
+            .unwrap();
+    }
+
+    fn set_logic_paused(&mut self, ui: &UserInterface, paused: bool) {
+        self.logic_paused = paused;
+        ui.send_message(ButtonMessage::content(
+            self.pause_logic,
+            MessageDirection::ToWidget,
+            ButtonContent::text(if paused { "Resume" } else { "Pause" }),
+        ));
+        enable_widget(self.step_logic, paused, ui);
+    }
+
+    pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
         let enabled = mode.is_edit();
         ui.send_message(ButtonMessage::content(
             self.switch_mode,
             MessageDirection::ToWidget,
             ButtonContent::text(if enabled { "Play" } else { "Stop" }),
         ));
-        for widget in [self.interaction_mode_panel, self.contextual_actions] {
+        for widget in [
+            self.interaction_mode_panel,
+            self.contextual_actions,
+            self.quick_create_panel,
+        ] {
             enable_widget(widget, enabled, ui);
         }
+
+        // Every game starts unpaused.
+        self.set_logic_paused(ui, false);
+        enable_widget(self.pause_logic, matches!(mode, Mode::Play { .. }), ui);
     }
 
     pub fn set_render_target(&self, ui: &UserInterface, render_target: Option<Texture>) {
@@ -724,15 +1404,165 @@ impl SceneViewer {
         ));
     }
 
-    pub fn reset_camera_projection(&self, ui: &UserInterface) {
-        // Default camera projection is Perspective.
+    /// Syncs the projection selector with the actual projection of the editor camera, the camera
+    /// itself is not changed.
+    pub fn sync_camera_projection(&self, ui: &UserInterface, orthographic: bool) {
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.camera_projection,
+                MessageDirection::ToWidget,
+                Some(if orthographic { 1 } else { 0 }),
+            ),
+        );
+    }
+
+    pub fn reset_render_mode(&self, ui: &UserInterface) {
         ui.send_message(DropdownListMessage::selection(
-            self.camera_projection,
+            self.render_mode,
             MessageDirection::ToWidget,
             Some(0),
         ));
     }
 
+    fn set_node_label(
+        &mut self,
+        index: usize,
+        name: &str,
+        ui: &mut UserInterface,
+    ) -> Handle<UiNode> {
+        if index == self.node_labels.len() {
+            let ctx = &mut ui.build_ctx();
+            let text = TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_hit_test_visibility(false)
+                    .with_foreground(Brush::Solid(Color::WHITE)),
+            )
+            .with_shadow(true)
+            .build(ctx);
+            ctx.link(text, self.overlay_canvas);
+            self.node_labels.push(NodeLabel {
+                text,
+                name: Default::default(),
+                visible: true,
+            });
+        }
+
+        let label = &mut self.node_labels[index];
+        if label.name != name {
+            label.name = name.to_owned();
+            ui.send_message(TextMessage::text(
+                label.text,
+                MessageDirection::ToWidget,
+                label.name.clone(),
+            ));
+        }
+        if !label.visible {
+            label.visible = true;
+            ui.send_message(WidgetMessage::visibility(
+                label.text,
+                MessageDirection::ToWidget,
+                true,
+            ));
+        }
+        label.text
+    }
+
+    /// Updates floating labels with names of nodes over the frame. Labels are shown in edit mode
+    /// only, closest nodes get labels first and labels fade out with distance from the camera.
+    pub fn update_node_labels(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        settings: &Settings,
+        is_edit_mode: bool,
+    ) {
+        let debugging = &settings.debugging;
+
+        let mut used_count = 0;
+        if debugging.show_node_names && is_edit_mode {
+            let graph = &engine.scenes[editor_scene.scene].graph;
+            let camera = graph[editor_scene.camera_controller.camera].as_camera();
+            let camera_position = camera.global_position();
+            let frame_size = self.frame_bounds(&engine.user_interface).size;
+            let fade_distance = debugging.node_name_fade_distance.max(f32::EPSILON);
+
+            let editor_nodes = graph
+                .traverse_handle_iter(editor_scene.editor_objects_root)
+                .collect::<FxHashSet<_>>();
+
+            let mut candidates = graph
+                .pair_iter()
+                .filter(|(handle, node)| {
+                    *handle != graph.get_root()
+                        && !editor_nodes.contains(handle)
+                        && !node.name().is_empty()
+                        && match debugging.node_name_filter {
+                            NodeNameLabelFilter::All => true,
+                            NodeNameLabelFilter::LightsAndCameras => {
+                                node.is_directional_light()
+                                    || node.is_spot_light()
+                                    || node.is_point_light()
+                                    || node.is_camera()
+                            }
+                            NodeNameLabelFilter::Selected => matches!(
+                                &editor_scene.selection,
+                                Selection::Graph(selection) if selection.contains(*handle)
+                            ),
+                        }
+                })
+                .filter_map(|(_, node)| {
+                    let position = node.global_position();
+                    let distance = position.metric_distance(&camera_position);
+                    if distance < fade_distance {
+                        Some((node, position, distance))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            candidates.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+
+            for (node, position, distance) in candidates {
+                if used_count >= debugging.max_node_name_labels {
+                    break;
+                }
+
+                if let Some(screen_position) = camera.project(position, frame_size) {
+                    let text =
+                        self.set_node_label(used_count, node.name(), &mut engine.user_interface);
+                    engine
+                        .user_interface
+                        .send_message(WidgetMessage::desired_position(
+                            text,
+                            MessageDirection::ToWidget,
+                            screen_position,
+                        ));
+                    engine.user_interface.send_message(WidgetMessage::opacity(
+                        text,
+                        MessageDirection::ToWidget,
+                        Some(1.0 - distance / fade_distance),
+                    ));
+                    used_count += 1;
+                }
+            }
+        }
+
+        for label in self.node_labels.iter_mut().skip(used_count) {
+            if label.visible {
+                label.visible = false;
+                engine
+                    .user_interface
+                    .send_message(WidgetMessage::visibility(
+                        label.text,
+                        MessageDirection::ToWidget,
+                        false,
+                    ));
+            }
+        }
+    }
+
     pub fn frame_bounds(&self, ui: &UserInterface) -> Rect<f32> {
         ui.node(self.frame).screen_bounds()
     }
@@ -751,7 +1581,7 @@ impl SceneViewer {
         }
 
         if let Some(interaction_mode) = active_interaction_mode {
-            if interaction_mode.on_key_up(key, editor_scene, engine) {
+            if interaction_mode.on_key_up(key, editor_scene, engine, key_bindings) {
                 return true;
             }
         }
@@ -776,7 +1606,7 @@ impl SceneViewer {
         }
 
         if let Some(interaction_mode) = active_interaction_mode {
-            if interaction_mode.on_key_down(key, editor_scene, engine) {
+            if interaction_mode.on_key_down(key, editor_scene, engine, key_bindings) {
                 return true;
             }
         }
@@ -813,9 +1643,51 @@ impl SceneViewer {
             );
         }
 
+        self.update_hovered_node(rel_pos, screen_bounds.size, editor_scene, engine, settings);
+
         self.last_mouse_pos = Some(pos);
     }
 
+    fn update_hovered_node(
+        &mut self,
+        rel_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        editor_scene: &mut EditorScene,
+        engine: &Engine,
+        settings: &Settings,
+    ) {
+        // Picking is quite expensive, so do it only when the cursor has moved far enough.
+        if self
+            .hover_pick_pos
+            .map_or(false, |p| p.metric_distance(&rel_pos) < HOVER_PICK_DISTANCE)
+        {
+            return;
+        }
+        self.hover_pick_pos = Some(rel_pos);
+
+        // Do not highlight anything while dragging.
+        editor_scene.hovered_node =
+            if settings.selection.hover_highlight && self.click_mouse_pos.is_none() {
+                editor_scene
+                    .camera_controller
+                    .pick(PickingOptions {
+                        cursor_pos: rel_pos,
+                        graph: &engine.scenes[editor_scene.scene].graph,
+                        editor_objects_root: editor_scene.editor_objects_root,
+                        screen_size: frame_size,
+                        editor_only: false,
+                        filter: |_, _| true,
+                        ignore_back_faces: settings.selection.ignore_back_faces,
+                        use_picking_loop: false,
+                        only_meshes: false,
+                    })
+                    .map(|result| result.node)
+                    .unwrap_or_default()
+            } else {
+                Handle::NONE
+            };
+    }
+
     fn on_mouse_up(
         &mut self,
         button: MouseButton,
@@ -841,6 +1713,29 @@ impl SceneViewer {
                     settings,
                 );
             }
+        } else if button == MouseButton::Right {
+            // Placing the cursor must not interfere with rotation of the camera, so the cursor is
+            // placed only if the mouse wasn't dragged.
+            if let Some(click_pos) = self.cursor_click_pos.take() {
+                let rel_pos = pos - screen_bounds.position;
+                if (rel_pos - click_pos).norm() < 4.0 {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(position) = pick_placement_position(
+                        editor_scene,
+                        graph,
+                        rel_pos,
+                        screen_bounds.size,
+                        settings,
+                        |_, _| true,
+                    ) {
+                        editor_scene.cursor = Some(
+                            settings
+                                .move_mode_settings
+                                .try_snap_vector_to_grid(position),
+                        );
+                    }
+                }
+            }
         }
 
         editor_scene.camera_controller.on_mouse_button_up(button);
@@ -873,6 +1768,8 @@ impl SceneViewer {
                     settings,
                 );
             }
+        } else if button == MouseButton::Right && engine.user_interface.keyboard_modifiers().shift {
+            self.cursor_click_pos = Some(pos - screen_bounds.position);
         }
 
         editor_scene.camera_controller.on_mouse_button_down(button);
@@ -892,6 +1789,29 @@ impl SceneViewer {
         let screen_bounds = self.frame_bounds(&engine.user_interface);
         let frame_size = screen_bounds.size;
 
+        if let Some(kind) = self.quick_create_kind(handle) {
+            if let Some(preview) = self.preview_instance.take() {
+                // Preview is not a part of the scene, only commands allowed to modify the scene.
+                let graph = &mut engine.scenes[editor_scene.scene].graph;
+                let transform = graph[preview.instance].local_transform();
+                let position = **transform.position();
+                let rotation = **transform.rotation();
+                graph.remove_node(preview.instance);
+
+                let mut node = kind.make_node();
+                node.local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation);
+                self.sender
+                    .send(Message::do_scene_command(AddNodeCommand::new(
+                        node,
+                        graph.get_root(),
+                    )))
+                    .unwrap();
+            }
+            return;
+        }
+
         if let Some(item) = engine.user_interface.node(handle).cast::<AssetItem>() {
             // Make sure all resources loaded with relative paths only.
             // This will make scenes portable.
@@ -0,0 +1,36 @@
+use fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+pub struct AssetBrowserSettings {
+    #[reflect(
+        description = "Play a short preview of a sound when the mouse cursor hovers over it in the asset browser."
+    )]
+    pub sound_preview_on_hover: bool,
+
+    #[reflect(
+        description = "Volume of sound previews.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub sound_preview_volume: f32,
+
+    #[reflect(
+        description = "Max duration (in seconds) of sound previews, longer sounds will be stopped after this time.",
+        min_value = 0.5,
+        max_value = 60.0,
+        step = 0.5
+    )]
+    pub sound_preview_duration: f32,
+}
+
+impl Default for AssetBrowserSettings {
+    fn default() -> Self {
+        Self {
+            sound_preview_on_hover: true,
+            sound_preview_volume: 0.5,
+            sound_preview_duration: 5.0,
+        }
+    }
+}
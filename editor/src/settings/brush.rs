@@ -0,0 +1,11 @@
+use fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug, Reflect, Eq)]
+pub struct BrushSettings {
+    #[reflect(
+        description = "Scale strength of terrain and vertex paint brushes with pen pressure of a \
+        drawing tablet. Devices without pressure sensor always have full strength."
+    )]
+    pub use_pen_pressure: bool,
+}
@@ -1,13 +1,20 @@
 use crate::camera;
-use fyrox::core::{algebra::Vector3, reflect::prelude::*};
+use fyrox::{
+    core::{algebra::Vector3, reflect::prelude::*},
+    scene::camera::OrthographicProjection,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
 
+/// Editor camera state of a scene, it is stored in the sidecar file of the scene.
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(default)]
 pub struct SceneCameraSettings {
     pub position: Vector3<f32>,
     pub yaw: f32,
     pub pitch: f32,
+    pub orthographic: bool,
+    /// Vertical size of the orthographic projection, it is ignored for perspective projection.
+    pub vertical_size: f32,
 }
 
 impl Default for SceneCameraSettings {
@@ -16,6 +23,8 @@ impl Default for SceneCameraSettings {
             position: Vector3::new(0.0, 1.0, camera::DEFAULT_Z_OFFSET),
             yaw: 0.0,
             pitch: 0.0,
+            orthographic: false,
+            vertical_size: OrthographicProjection::default().vertical_size,
         }
     }
 }
@@ -25,8 +34,35 @@ pub struct CameraSettings {
     pub speed: f32,
     pub invert_dragging: bool,
     pub drag_speed: f32,
-    #[reflect(hidden)]
-    pub camera_settings: HashMap<PathBuf, SceneCameraSettings>,
+    #[serde(default)]
+    #[reflect(
+        description = "Render the scene viewport at reduced resolution while the camera is moving. \
+        Makes navigation smoother in heavy scenes, full resolution is restored when the camera stops."
+    )]
+    pub reduce_resolution_while_moving: bool,
+    #[serde(default = "default_moving_resolution_scale")]
+    #[reflect(
+        description = "Fraction of the viewport resolution that is used while the camera is moving.",
+        min_value = 0.1,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub moving_resolution_scale: f32,
+    #[serde(default = "default_remember_scene_camera")]
+    #[reflect(
+        description = "Save position, orientation and projection of the editor camera for each scene \
+        and restore them when the scene is loaded. If disabled, the camera always starts at the \
+        default view."
+    )]
+    pub remember_scene_camera: bool,
+}
+
+fn default_moving_resolution_scale() -> f32 {
+    0.5
+}
+
+fn default_remember_scene_camera() -> bool {
+    true
 }
 
 impl Default for CameraSettings {
@@ -35,7 +71,9 @@ impl Default for CameraSettings {
             speed: 10.0,
             invert_dragging: false,
             drag_speed: 0.01,
-            camera_settings: Default::default(),
+            reduce_resolution_while_moving: false,
+            moving_resolution_scale: default_moving_resolution_scale(),
+            remember_scene_camera: default_remember_scene_camera(),
         }
     }
 }
@@ -1,5 +1,35 @@
 use fyrox::core::reflect::prelude::*;
 use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines which nodes get floating name labels in the scene viewport.
+#[derive(
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+    Debug,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum NodeNameLabelFilter {
+    /// Every node of the scene.
+    All,
+    /// Lights and cameras only.
+    LightsAndCameras,
+    /// Selected nodes only.
+    Selected,
+}
+
+impl Default for NodeNameLabelFilter {
+    fn default() -> Self {
+        Self::All
+    }
+}
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct DebuggingSettings {
@@ -9,6 +39,29 @@ pub struct DebuggingSettings {
     #[reflect(description = "Size of pictograms in meters. It is used for objects like lights.")]
     #[serde(default)]
     pub pictogram_size: f32,
+    #[reflect(description = "Show names of nodes as floating labels in the scene viewport.")]
+    #[serde(default)]
+    pub show_node_names: bool,
+    #[reflect(description = "Defines which nodes get name labels.")]
+    #[serde(default)]
+    pub node_name_filter: NodeNameLabelFilter,
+    #[reflect(
+        description = "Distance (in meters) from the camera at which name labels fade out completely.",
+        min_value = 0.0
+    )]
+    #[serde(default = "default_node_name_fade_distance")]
+    pub node_name_fade_distance: f32,
+    #[reflect(description = "Maximum amount of name labels, the closest nodes get labels first.")]
+    #[serde(default = "default_max_node_name_labels")]
+    pub max_node_name_labels: usize,
+}
+
+fn default_node_name_fade_distance() -> f32 {
+    50.0
+}
+
+fn default_max_node_name_labels() -> usize {
+    64
 }
 
 impl Default for DebuggingSettings {
@@ -18,6 +71,10 @@ impl Default for DebuggingSettings {
             show_bounds: true,
             show_tbn: false,
             pictogram_size: 0.33,
+            show_node_names: false,
+            node_name_filter: Default::default(),
+            node_name_fade_distance: default_node_name_fade_distance(),
+            max_node_name_labels: default_max_node_name_labels(),
         }
     }
 }
@@ -0,0 +1,75 @@
+use crate::localization::DEFAULT_LANGUAGE;
+use fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Eq)]
+pub struct GeneralSettings {
+    #[reflect(
+        description = "Language of the editor UI, translations are loaded from `lang/<language>.ron` \
+        file. Most of the UI will be translated only after restart of the editor."
+    )]
+    pub language: String,
+
+    #[serde(default = "default_safe_save")]
+    #[reflect(
+        description = "Write scenes to a temporary file first and then replace the original one, \
+        so a crash in the middle of saving can't corrupt it. Previous version of the scene is kept \
+        near it with `.bak` suffix."
+    )]
+    pub safe_save: bool,
+
+    #[serde(default = "default_update_rate")]
+    #[reflect(
+        description = "How many times per second the editor (and the game in play mode) is updated. \
+        Every update uses fixed time step of `1 / update_rate` seconds."
+    )]
+    pub update_rate: u32,
+
+    #[serde(default = "default_max_catch_up_steps")]
+    #[reflect(
+        description = "Maximum amount of updates per frame, that are used to catch up with real \
+        time when a frame took too long. If there is more time left, it is dropped: the editor \
+        (and the game in play mode) slows down instead of trying to simulate the whole backlog, \
+        which would make the next frame even longer."
+    )]
+    pub max_catch_up_steps: u32,
+
+    #[serde(default)]
+    #[reflect(
+        description = "Maximum amount of entries in the undo history of a scene, 0 means unlimited. \
+        When the limit is exceeded, the oldest commands are dropped and can't be undone anymore. \
+        Limit the history to reduce memory usage in long sessions."
+    )]
+    pub max_command_stack_size: usize,
+}
+
+fn default_safe_save() -> bool {
+    true
+}
+
+fn default_update_rate() -> u32 {
+    60
+}
+
+fn default_max_catch_up_steps() -> u32 {
+    5
+}
+
+impl GeneralSettings {
+    /// Returns time step (in seconds) of the fixed update loop.
+    pub fn fixed_timestep(&self) -> f32 {
+        1.0 / self.update_rate.max(1) as f32
+    }
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        Self {
+            language: DEFAULT_LANGUAGE.to_owned(),
+            safe_save: default_safe_save(),
+            update_rate: default_update_rate(),
+            max_catch_up_steps: default_max_catch_up_steps(),
+            max_command_stack_size: 0,
+        }
+    }
+}
@@ -17,6 +17,13 @@ pub struct KeyBindings {
     pub move_down: KeyBinding,
     pub speed_up: KeyBinding,
     pub slow_down: KeyBinding,
+    #[serde(default = "default_snap_to_point")]
+    #[reflect(
+        description = "Hold in move mode, click a point of selected objects and then a point of \
+        another object to move the selection so the points coincide. Nearest vertices and pivots \
+        are used as points."
+    )]
+    pub snap_to_point: KeyBinding,
 
     pub undo: HotKey,
     pub redo: HotKey,
@@ -26,6 +33,24 @@ pub struct KeyBindings {
     pub enable_scale_mode: HotKey,
     pub enable_navmesh_mode: HotKey,
     pub enable_terrain_mode: HotKey,
+    #[serde(default = "default_enable_collider_mode")]
+    #[reflect(description = "Enables collider editing mode, it is available only in edit mode.")]
+    pub enable_collider_mode: HotKey,
+    #[serde(default = "default_enable_vertex_paint_mode")]
+    #[reflect(description = "Enables painting of vertex colors of the selected mesh.")]
+    pub enable_vertex_paint_mode: HotKey,
+    #[serde(default = "default_enable_waypoint_mode")]
+    #[reflect(description = "Enables placing of waypoints of paths by clicking on surfaces.")]
+    pub enable_waypoint_mode: HotKey,
+    #[serde(default = "default_toggle_immersive_mode")]
+    #[reflect(
+        description = "Hides the main menu and every panel except the scene viewer, pressing it \
+        again restores the panels."
+    )]
+    pub toggle_immersive_mode: HotKey,
+    #[serde(default = "default_show_shortcuts")]
+    #[reflect(description = "Shows a window with all keyboard shortcuts.")]
+    pub show_shortcuts: HotKey,
     pub save_scene: HotKey,
     pub load_scene: HotKey,
     pub copy_selection: HotKey,
@@ -33,6 +58,118 @@ pub struct KeyBindings {
     pub new_scene: HotKey,
     pub close_scene: HotKey,
     pub remove_selection: HotKey,
+    #[serde(default = "default_cycle_selection")]
+    #[reflect(
+        description = "Selects next node under the mouse cursor in the scene viewer, repeated \
+        presses cycle through all overlapping nodes."
+    )]
+    pub cycle_selection: HotKey,
+    #[serde(default = "default_toggle_enabled")]
+    #[reflect(description = "Enables or disables selected nodes.")]
+    pub toggle_enabled: HotKey,
+    #[serde(default = "default_frame_scene")]
+    #[reflect(
+        description = "Moves the camera so the whole scene fits into the view. The camera returns \
+        to the default view if the scene is empty."
+    )]
+    pub frame_scene: HotKey,
+    #[serde(default = "default_duplicate_selection")]
+    #[reflect(
+        description = "Duplicates selected nodes, meshes of the duplicates get their own copies of \
+        geometry and materials."
+    )]
+    pub duplicate_selection: HotKey,
+    #[serde(default = "default_duplicate_selection_linked")]
+    #[reflect(
+        description = "Duplicates selected nodes, meshes of the duplicates share geometry and \
+        materials with the originals."
+    )]
+    pub duplicate_selection_linked: HotKey,
+    #[serde(default = "default_repeat_last_action")]
+    #[reflect(
+        description = "Repeats the last repeatable action (duplicate, move by gizmo, toggle \
+        enabled, paste transform) with the current selection. A duplication followed by a move of \
+        the duplicates is repeated as a whole."
+    )]
+    pub repeat_last_action: HotKey,
+    #[serde(default = "default_cycle_pivot_mode")]
+    #[reflect(
+        description = "Switches the point around which multiple selected nodes are rotated and \
+        scaled: individual origins, median point, active node or 3D cursor."
+    )]
+    pub cycle_pivot_mode: HotKey,
+    #[serde(default = "default_clear_parent")]
+    #[reflect(
+        description = "Links selected nodes directly to the root of the scene, world transforms of \
+        the nodes are kept so they do not move."
+    )]
+    pub clear_parent: HotKey,
+    #[serde(default = "default_parent_to_last_selected")]
+    #[reflect(
+        description = "Links selected nodes to the node that was selected last, world transforms of \
+        the nodes are kept so they do not move."
+    )]
+    pub parent_to_last_selected: HotKey,
+}
+
+fn default_snap_to_point() -> KeyBinding {
+    KeyBinding::from_key_code(KeyCode::V)
+}
+
+fn default_enable_collider_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key7)
+}
+
+fn default_enable_vertex_paint_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key8)
+}
+
+fn default_enable_waypoint_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::Key9)
+}
+
+fn default_toggle_immersive_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::F11)
+}
+
+fn default_show_shortcuts() -> HotKey {
+    HotKey::from_key_code(KeyCode::F1)
+}
+
+fn default_cycle_selection() -> HotKey {
+    HotKey::from_key_code(KeyCode::Tab)
+}
+
+fn default_toggle_enabled() -> HotKey {
+    HotKey::ctrl_key(KeyCode::E)
+}
+
+fn default_frame_scene() -> HotKey {
+    HotKey::from_key_code(KeyCode::Home)
+}
+
+fn default_duplicate_selection() -> HotKey {
+    HotKey::ctrl_key(KeyCode::D)
+}
+
+fn default_duplicate_selection_linked() -> HotKey {
+    HotKey::alt_key(KeyCode::D)
+}
+
+fn default_repeat_last_action() -> HotKey {
+    HotKey::shift_key(KeyCode::R)
+}
+
+fn default_cycle_pivot_mode() -> HotKey {
+    HotKey::from_key_code(KeyCode::Period)
+}
+
+fn default_clear_parent() -> HotKey {
+    HotKey::alt_key(KeyCode::P)
+}
+
+fn default_parent_to_last_selected() -> HotKey {
+    HotKey::ctrl_key(KeyCode::P)
 }
 
 impl Default for KeyBindings {
@@ -46,6 +183,7 @@ impl Default for KeyBindings {
             move_down: KeyBinding::from_key_code(KeyCode::E),
             speed_up: KeyBinding::from_key_code(KeyCode::LControl),
             slow_down: KeyBinding::from_key_code(KeyCode::LShift),
+            snap_to_point: default_snap_to_point(),
 
             undo: HotKey::ctrl_key(KeyCode::Z),
             redo: HotKey::ctrl_key(KeyCode::Y),
@@ -55,6 +193,11 @@ impl Default for KeyBindings {
             enable_scale_mode: HotKey::from_key_code(KeyCode::Key4),
             enable_navmesh_mode: HotKey::from_key_code(KeyCode::Key5),
             enable_terrain_mode: HotKey::from_key_code(KeyCode::Key6),
+            enable_collider_mode: default_enable_collider_mode(),
+            enable_vertex_paint_mode: default_enable_vertex_paint_mode(),
+            enable_waypoint_mode: default_enable_waypoint_mode(),
+            toggle_immersive_mode: default_toggle_immersive_mode(),
+            show_shortcuts: default_show_shortcuts(),
             save_scene: HotKey::ctrl_key(KeyCode::S),
             load_scene: HotKey::ctrl_key(KeyCode::L),
             copy_selection: HotKey::ctrl_key(KeyCode::C),
@@ -62,6 +205,15 @@ impl Default for KeyBindings {
             new_scene: HotKey::ctrl_key(KeyCode::N),
             close_scene: HotKey::ctrl_key(KeyCode::Q),
             remove_selection: HotKey::from_key_code(KeyCode::Delete),
+            cycle_selection: default_cycle_selection(),
+            toggle_enabled: default_toggle_enabled(),
+            frame_scene: default_frame_scene(),
+            duplicate_selection: default_duplicate_selection(),
+            duplicate_selection_linked: default_duplicate_selection_linked(),
+            repeat_last_action: default_repeat_last_action(),
+            cycle_pivot_mode: default_cycle_pivot_mode(),
+            clear_parent: default_clear_parent(),
+            parent_to_last_selected: default_parent_to_last_selected(),
         }
     }
 }
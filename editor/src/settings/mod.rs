@@ -1,10 +1,23 @@
 use crate::{
     inspector::editors::make_property_editors_container,
+    localization::load_language,
     settings::{
-        camera::CameraSettings, debugging::DebuggingSettings, graphics::GraphicsSettings,
-        keys::KeyBindings, model::ModelSettings, move_mode::MoveInteractionModeSettings,
-        navmesh::NavmeshSettings, recent::RecentFiles, rotate_mode::RotateInteractionModeSettings,
+        asset_browser::AssetBrowserSettings,
+        brush::BrushSettings,
+        camera::CameraSettings,
+        debugging::DebuggingSettings,
+        debugging::NodeNameLabelFilter,
+        general::GeneralSettings,
+        graphics::GraphicsSettings,
+        keys::KeyBindings,
+        model::ModelSettings,
+        move_mode::MoveInteractionModeSettings,
+        navmesh::NavmeshSettings,
+        recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings,
         selection::SelectionSettings,
+        units::{LengthUnit, UnitsSettings},
+        windows::WindowsSettings,
     },
     GameEngine, Message, MSG_SYNC_FLAG,
 };
@@ -36,8 +49,11 @@ use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 use std::{fs::File, path::PathBuf, rc::Rc, sync::mpsc::Sender};
 
+pub mod asset_browser;
+pub mod brush;
 pub mod camera;
 pub mod debugging;
+pub mod general;
 pub mod graphics;
 pub mod keys;
 pub mod model;
@@ -46,6 +62,8 @@ pub mod navmesh;
 pub mod recent;
 pub mod rotate_mode;
 pub mod selection;
+pub mod units;
+pub mod windows;
 
 pub struct SettingsWindow {
     window: Handle<UiNode>,
@@ -56,6 +74,8 @@ pub struct SettingsWindow {
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug, Reflect)]
 pub struct Settings {
+    #[serde(default)]
+    pub general: GeneralSettings,
     pub selection: SelectionSettings,
     pub graphics: GraphicsSettings,
     pub debugging: DebuggingSettings,
@@ -64,9 +84,18 @@ pub struct Settings {
     pub model: ModelSettings,
     pub camera: CameraSettings,
     pub navmesh: NavmeshSettings,
+    #[serde(default)]
+    pub brush: BrushSettings,
+    #[serde(default)]
+    pub asset_browser: AssetBrowserSettings,
+    #[serde(default)]
+    pub units: UnitsSettings,
     pub key_bindings: KeyBindings,
     #[reflect(hidden)]
     pub recent: RecentFiles,
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub windows: WindowsSettings,
 }
 
 #[derive(Debug)]
@@ -117,10 +146,12 @@ impl Settings {
     ) -> Rc<PropertyEditorDefinitionContainer> {
         let container = make_property_editors_container(sender);
 
+        container.insert(InspectablePropertyEditorDefinition::<GeneralSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<NodeNameLabelFilter>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CameraSettings>::new());
@@ -132,6 +163,10 @@ impl Settings {
         >::new());
         container.insert(InspectablePropertyEditorDefinition::<ModelSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<NavmeshSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<BrushSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<AssetBrowserSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<UnitsSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<LengthUnit>::new());
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
         container.insert(HotKeyPropertyEditorDefinition);
         container.insert(KeyBindingPropertyEditorDefinition);
@@ -286,6 +321,23 @@ impl SettingsWindow {
                 }
             }
 
+            if settings.general.language != old_settings.general.language {
+                match load_language(&settings.general.language) {
+                    Ok(_) => Log::info(
+                        "Language was changed, restart the editor to apply it to the whole UI.",
+                    ),
+                    Err(e) => Log::err(format!(
+                        "Unable to load {} language! Reason: {:?}",
+                        settings.general.language, e
+                    )),
+                }
+            }
+
+            // Length fields of the inspector must be re-created to show the new units.
+            if settings.units != old_settings.units {
+                sender.send(Message::ForceSync).unwrap();
+            }
+
             // Save config
             match settings.save() {
                 Ok(_) => {
@@ -8,12 +8,33 @@ pub struct ModelSettings {
         Useful when you have lots of huge models and don't want to rescale them manually."
     )]
     pub instantiation_scale: Vector3<f32>,
+    #[serde(default)]
+    #[reflect(
+        description = "Rotate dropped models and quick-created nodes so their up axis is aligned \
+        with the normal of the surface under them."
+    )]
+    pub align_to_surface: bool,
+    #[serde(default = "default_surface_alignment")]
+    #[reflect(
+        description = "How much the up axis is aligned with the surface normal: 0 - keep world up, \
+        1 - fully align with the normal. Lower values help to avoid extreme tilts on steep slopes.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub surface_alignment: f32,
+}
+
+fn default_surface_alignment() -> f32 {
+    1.0
 }
 
 impl Default for ModelSettings {
     fn default() -> Self {
         Self {
             instantiation_scale: Vector3::new(1.0, 1.0, 1.0),
+            align_to_surface: false,
+            surface_alignment: default_surface_alignment(),
         }
     }
 }
@@ -7,6 +7,17 @@ pub struct MoveInteractionModeSettings {
     pub x_snap_step: f32,
     pub y_snap_step: f32,
     pub z_snap_step: f32,
+    #[serde(default = "default_view_plane_snapping")]
+    #[reflect(
+        description = "When the editor camera is orthographic and looks along one of the world \
+        axes, snap the dragged position to a 2D grid that lies in the view plane (for example XZ \
+        in a top view). The position is snapped in world space and the depth axis is left intact."
+    )]
+    pub view_plane_snapping: bool,
+}
+
+fn default_view_plane_snapping() -> bool {
+    true
 }
 
 impl Default for MoveInteractionModeSettings {
@@ -16,6 +27,7 @@ impl Default for MoveInteractionModeSettings {
             x_snap_step: 0.05,
             y_snap_step: 0.05,
             z_snap_step: 0.05,
+            view_plane_snapping: default_view_plane_snapping(),
         }
     }
 }
@@ -32,4 +44,26 @@ impl MoveInteractionModeSettings {
             v
         }
     }
+
+    /// Snaps a world space position to a grid that lies in the plane perpendicular to the given
+    /// view axis (0 - X, 1 - Y, 2 - Z), the coordinate along the view axis is kept as is. Returns
+    /// `None` if view plane snapping is not enabled.
+    pub fn try_snap_to_view_plane_grid(
+        &self,
+        v: Vector3<f32>,
+        view_axis: usize,
+    ) -> Option<Vector3<f32>> {
+        if self.grid_snapping && self.view_plane_snapping {
+            let steps = [self.x_snap_step, self.y_snap_step, self.z_snap_step];
+            Some(Vector3::from_fn(|i, _| {
+                if i == view_axis {
+                    v[i]
+                } else {
+                    math::round_to_step(v[i], steps[i])
+                }
+            }))
+        } else {
+            None
+        }
+    }
 }
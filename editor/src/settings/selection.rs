@@ -1,19 +1,84 @@
-use fyrox::core::reflect::prelude::*;
+use fyrox::core::{color::Color, reflect::prelude::*};
 use serde::{Deserialize, Serialize};
 
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Color")]
+struct ColorDef {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Eq)]
 pub struct SelectionSettings {
     pub ignore_back_faces: bool,
 
+    #[serde(default = "default_hover_highlight")]
+    #[reflect(
+        description = "Highlight a node under the mouse cursor in the scene viewer, so it is clear \
+        what will be selected by a click."
+    )]
+    pub hover_highlight: bool,
+
+    #[serde(default = "default_confirm_deletion")]
+    #[reflect(
+        description = "Ask for confirmation when deleting a large selection using the keyboard."
+    )]
+    pub confirm_deletion: bool,
+
+    #[serde(default = "default_deletion_confirmation_threshold")]
+    #[reflect(
+        description = "Deletion of more than this amount of nodes at once requires a confirmation. \
+        Zero means that a confirmation will be asked for any selection."
+    )]
+    pub deletion_confirmation_threshold: usize,
+
+    #[serde(default = "default_selection_color", with = "ColorDef")]
+    #[reflect(description = "Color of bounds of selected nodes in the scene viewer.")]
+    pub selection_color: Color,
+
+    #[serde(default = "default_active_selection_color", with = "ColorDef")]
+    #[reflect(
+        description = "Color of bounds of the active node of a multi-selection, which is the node \
+        shown in the inspector."
+    )]
+    pub active_selection_color: Color,
+
     // Hidden because there's a separate switch in world viewer for this.
     #[reflect(hidden)]
     pub track_selection: bool,
 }
 
+fn default_hover_highlight() -> bool {
+    true
+}
+
+fn default_confirm_deletion() -> bool {
+    true
+}
+
+fn default_deletion_confirmation_threshold() -> usize {
+    50
+}
+
+fn default_selection_color() -> Color {
+    Color::GREEN
+}
+
+fn default_active_selection_color() -> Color {
+    Color::opaque(255, 200, 0)
+}
+
 impl Default for SelectionSettings {
     fn default() -> Self {
         Self {
             ignore_back_faces: false,
+            hover_highlight: default_hover_highlight(),
+            confirm_deletion: default_confirm_deletion(),
+            deletion_confirmation_threshold: default_deletion_confirmation_threshold(),
+            selection_color: default_selection_color(),
+            active_selection_color: default_active_selection_color(),
             track_selection: true,
         }
     }
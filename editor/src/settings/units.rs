@@ -0,0 +1,89 @@
+use fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+#[derive(
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+    Debug,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum LengthUnit {
+    Meters,
+    Centimeters,
+    Millimeters,
+    Feet,
+    Inches,
+}
+
+impl Default for LengthUnit {
+    fn default() -> Self {
+        Self::Meters
+    }
+}
+
+impl LengthUnit {
+    /// Returns length of the unit in meters.
+    pub fn meters(self) -> f32 {
+        match self {
+            LengthUnit::Meters => 1.0,
+            LengthUnit::Centimeters => 0.01,
+            LengthUnit::Millimeters => 0.001,
+            LengthUnit::Feet => 0.3048,
+            LengthUnit::Inches => 0.0254,
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            LengthUnit::Meters => "m",
+            LengthUnit::Centimeters => "cm",
+            LengthUnit::Millimeters => "mm",
+            LengthUnit::Feet => "ft",
+            LengthUnit::Inches => "in",
+        }
+    }
+}
+
+/// Defines how length values are shown in the inspector: a value in scene units is multiplied
+/// by `scale` for display and divided by it when edited.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct LengthDisplay {
+    pub scale: f32,
+    pub suffix: &'static str,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect, Default)]
+pub struct UnitsSettings {
+    #[reflect(
+        description = "Show positions in the inspector in display units with a unit suffix. \
+        Values in the scene are always stored in scene units."
+    )]
+    pub enabled: bool,
+
+    #[reflect(description = "What a single unit of the scene is, usually it is a meter.")]
+    pub scene_unit: LengthUnit,
+
+    #[reflect(description = "Units of positions in the inspector.")]
+    pub display_unit: LengthUnit,
+}
+
+impl UnitsSettings {
+    pub fn length_display(&self) -> Option<LengthDisplay> {
+        if self.enabled {
+            Some(LengthDisplay {
+                scale: self.scene_unit.meters() / self.display_unit.meters(),
+                suffix: self.display_unit.suffix(),
+            })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use fyrox::gui::dock::DockingManagerLayoutDescriptor;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Default)]
+pub struct WindowsSettings {
+    /// Arrangement of docked and floating panels, it is saved on exit and restored on startup.
+    /// `None` means that the default layout is used.
+    pub layout: Option<DockingManagerLayoutDescriptor>,
+}
@@ -0,0 +1,243 @@
+//! A window with all keyboard shortcuts of the editor. The list is built from the key bindings in
+//! the settings every time the window is opened, so it always shows the current bindings.
+
+use crate::settings::keys::KeyBindings;
+use fyrox::{
+    core::{color::Color, pool::Handle, reflect::prelude::*},
+    gui::{
+        brush::Brush,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        key::{HotKey, KeyBinding},
+        message::{KeyCode, MessageDirection, UiMessage},
+        scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+
+const CATEGORIES: [&str; 6] = [
+    "Camera",
+    "Interaction Modes",
+    "Editing",
+    "Scene",
+    "View",
+    "Other",
+];
+
+/// Returns a category of a key binding by its name, new bindings should be added here, otherwise
+/// they will be shown in "Other" category.
+fn category(name: &str) -> &'static str {
+    match name {
+        "move_forward" | "move_back" | "move_left" | "move_right" | "move_up" | "move_down"
+        | "speed_up" | "slow_down" | "frame_scene" => "Camera",
+        "snap_to_point" => "Interaction Modes",
+        "undo"
+        | "redo"
+        | "copy_selection"
+        | "paste"
+        | "remove_selection"
+        | "cycle_selection"
+        | "toggle_enabled"
+        | "duplicate_selection"
+        | "duplicate_selection_linked"
+        | "repeat_last_action"
+        | "cycle_pivot_mode"
+        | "clear_parent"
+        | "parent_to_last_selected" => "Editing",
+        "save_scene" | "load_scene" | "new_scene" | "close_scene" => "Scene",
+        "toggle_immersive_mode" | "show_shortcuts" => "View",
+        _ if name.starts_with("enable_") && name.ends_with("_mode") => "Interaction Modes",
+        _ => "Other",
+    }
+}
+
+struct Shortcut {
+    name: &'static str,
+    description: &'static str,
+    keys: String,
+}
+
+fn collect_shortcuts(key_bindings: &KeyBindings) -> Vec<(&'static str, Vec<Shortcut>)> {
+    let mut groups = CATEGORIES
+        .iter()
+        .map(|category| (*category, Vec::new()))
+        .collect::<Vec<_>>();
+
+    for field in key_bindings.fields_info() {
+        let keys = if let Ok(hot_key) = field.cast_value::<HotKey>() {
+            hot_key.to_string()
+        } else if let Ok(key_binding) = field.cast_value::<KeyBinding>() {
+            key_binding.to_string()
+        } else {
+            continue;
+        };
+
+        let category = category(field.name);
+        if let Some((_, shortcuts)) = groups.iter_mut().find(|(c, _)| *c == category) {
+            shortcuts.push(Shortcut {
+                name: field.display_name,
+                description: field.description,
+                keys,
+            });
+        }
+    }
+
+    groups.retain(|(_, shortcuts)| !shortcuts.is_empty());
+    groups
+}
+
+fn make_category(category: &str, shortcuts: &[Shortcut], ctx: &mut BuildContext) -> Handle<UiNode> {
+    let mut grid = WidgetBuilder::new();
+    for (row, shortcut) in shortcuts.iter().enumerate() {
+        let mut name = WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0));
+        if !shortcut.description.is_empty() {
+            name = name.with_tooltip(make_simple_tooltip(ctx, shortcut.description));
+        }
+
+        grid = grid
+            .with_child(
+                TextBuilder::new(name)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_text(shortcut.name)
+                    .build(ctx),
+            )
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(row)
+                        .on_column(1)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_foreground(Brush::Solid(Color::opaque(220, 180, 90))),
+                )
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(&shortcut.keys)
+                .build(ctx),
+            );
+    }
+
+    let grid = GridBuilder::new(grid.with_margin(Thickness::left(10.0)))
+        .add_rows(shortcuts.iter().map(|_| Row::strict(20.0)).collect())
+        .add_column(Column::stretch())
+        .add_column(Column::strict(140.0))
+        .build(ctx);
+
+    StackPanelBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::bottom(6.0))
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_foreground(Brush::Solid(Color::opaque(140, 190, 255))),
+                )
+                .with_text(category)
+                .build(ctx),
+            )
+            .with_child(grid),
+    )
+    .build(ctx)
+}
+
+pub struct ShortcutsWindow {
+    pub window: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+}
+
+impl ShortcutsWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let scroll_viewer;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(420.0).with_height(500.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Keyboard Shortcuts"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .with_text(
+                                "Shortcuts can be changed in the settings. Press Escape to close \
+                                this window.",
+                            )
+                            .build(ctx),
+                        )
+                        .with_child({
+                            scroll_viewer = ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            scroll_viewer
+                        }),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            scroll_viewer,
+        }
+    }
+
+    pub fn open(&self, key_bindings: &KeyBindings, ui: &mut UserInterface) {
+        let ctx = &mut ui.build_ctx();
+        let categories = collect_shortcuts(key_bindings)
+            .iter()
+            .map(|(category, shortcuts)| make_category(category, shortcuts, ctx))
+            .collect::<Vec<_>>();
+        let content =
+            StackPanelBuilder::new(WidgetBuilder::new().with_children(categories)).build(ctx);
+
+        ui.send_message(ScrollViewerMessage::content(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            content,
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn close(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    pub fn toggle(&self, key_bindings: &KeyBindings, ui: &mut UserInterface) {
+        if ui.node(self.window).visibility() {
+            self.close(ui);
+        } else {
+            self.open(key_bindings, ui);
+        }
+    }
+
+    pub fn handle_ui_message(&self, message: &UiMessage, ui: &UserInterface) {
+        if let Some(WidgetMessage::KeyDown(KeyCode::Escape)) = message.data() {
+            if ui.node(self.window).visibility() {
+                self.close(ui);
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! A small window that allows to type exact position and look direction of the editor camera and
+//! move the camera there. It is useful to inspect objects at known coordinates.
+
+use crate::{scene::EditorScene, GameEngine};
+use fyrox::{
+    core::{algebra::Vector3, color::Color, pool::Handle},
+    gui::{
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        vec::vec3::{Vec3EditorBuilder, Vec3EditorMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+};
+
+pub struct TeleportCameraWindow {
+    pub window: Handle<UiNode>,
+    position_editor: Handle<UiNode>,
+    look_editor: Handle<UiNode>,
+    status: Handle<UiNode>,
+    go: Handle<UiNode>,
+    go_to_origin: Handle<UiNode>,
+    from_camera: Handle<UiNode>,
+    close: Handle<UiNode>,
+    position: Vector3<f32>,
+    look_direction: Vector3<f32>,
+}
+
+fn make_label(ctx: &mut BuildContext, text: &str, row: usize) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .on_column(0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_vertical_text_alignment(VerticalAlignment::Center)
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+impl TeleportCameraWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let position_editor;
+        let look_editor;
+        let status;
+        let go;
+        let go_to_origin;
+        let from_camera;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(160.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Camera Coordinates"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child(make_label(ctx, "Position", 0))
+                                    .with_child({
+                                        position_editor = Vec3EditorBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(0)
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .build(ctx);
+                                        position_editor
+                                    })
+                                    .with_child(make_label(ctx, "Look Direction", 1))
+                                    .with_child({
+                                        look_editor = Vec3EditorBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(1)
+                                                .on_column(1)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_value(Vector3::z())
+                                        .build(ctx);
+                                        look_editor
+                                    }),
+                            )
+                            .add_row(Row::strict(24.0))
+                            .add_row(Row::strict(24.0))
+                            .add_column(Column::strict(100.0))
+                            .add_column(Column::stretch())
+                            .build(ctx),
+                        )
+                        .with_child({
+                            status = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_foreground(Brush::Solid(Color::RED)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            status
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        go_to_origin = make_button(ctx, "Go To Origin");
+                                        go_to_origin
+                                    })
+                                    .with_child({
+                                        from_camera = make_button(ctx, "From Camera");
+                                        from_camera
+                                    })
+                                    .with_child({
+                                        go = make_button(ctx, "Go");
+                                        go
+                                    })
+                                    .with_child({
+                                        close = make_button(ctx, "Close");
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(26.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            position_editor,
+            look_editor,
+            status,
+            go,
+            go_to_origin,
+            from_camera,
+            close,
+            position: Default::default(),
+            look_direction: Vector3::z(),
+        }
+    }
+
+    fn set_status(&self, ui: &UserInterface, text: &str) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text.to_owned(),
+        ));
+    }
+
+    fn sync_to_camera(&mut self, editor_scene: &EditorScene, engine: &GameEngine) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        self.position = **graph[editor_scene.camera_controller.pivot]
+            .local_transform()
+            .position();
+        self.look_direction = editor_scene.camera_controller.look_direction();
+
+        let ui = &engine.user_interface;
+        ui.send_message(Vec3EditorMessage::value(
+            self.position_editor,
+            MessageDirection::ToWidget,
+            self.position,
+        ));
+        ui.send_message(Vec3EditorMessage::value(
+            self.look_editor,
+            MessageDirection::ToWidget,
+            self.look_direction,
+        ));
+        self.set_status(ui, "");
+    }
+
+    fn teleport(
+        &mut self,
+        position: Vector3<f32>,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+    ) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        if editor_scene
+            .camera_controller
+            .teleport(graph, position, self.look_direction)
+        {
+            // Show normalized direction, so it is clear where the camera looks at.
+            self.sync_to_camera(editor_scene, engine);
+        } else {
+            self.set_status(
+                &engine.user_interface,
+                "Invalid coordinates: look direction must have non-zero length.",
+            );
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut GameEngine,
+    ) {
+        if let Some(WindowMessage::Open { .. }) = message.data() {
+            if message.destination() == self.window {
+                self.sync_to_camera(editor_scene, engine);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.go {
+                self.teleport(self.position, editor_scene, engine);
+            } else if message.destination() == self.go_to_origin {
+                self.teleport(Vector3::default(), editor_scene, engine);
+            } else if message.destination() == self.from_camera {
+                self.sync_to_camera(editor_scene, engine);
+            } else if message.destination() == self.close {
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(&Vec3EditorMessage::Value(value)) =
+            message.data::<Vec3EditorMessage<f32>>()
+        {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.position_editor {
+                    self.position = value;
+                } else if message.destination() == self.look_editor {
+                    self.look_direction = value;
+                }
+            }
+        }
+    }
+}
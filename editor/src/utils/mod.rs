@@ -9,9 +9,18 @@ use fyrox::{
     },
     resource::texture::{CompressionOptions, Texture},
     scene::camera::{SkyBox, SkyBoxBuilder},
+    utils::log::Log,
+};
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
 };
 
 pub mod path_fixer;
+pub mod script_relinker;
 
 pub fn is_slice_equal_permutation<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     if a.is_empty() && !b.is_empty() {
@@ -146,3 +155,98 @@ where
 
     apply_filter_recursive(root, ui, &filter);
 }
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Writes the data to the file at the given path. In safe mode the data is written to a temporary
+/// file first which then replaces the original file, so a crash in the middle of writing can't
+/// corrupt it. Previous version of the file is kept near it with `.bak` suffix.
+pub fn write_file(path: &Path, data: &[u8], safe: bool) -> io::Result<()> {
+    if !safe {
+        return fs::write(path, data);
+    }
+
+    let temp_path = path_with_suffix(path, ".tmp");
+    let result = File::create(&temp_path).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    if path.exists() {
+        fs::copy(path, path_with_suffix(path, ".bak"))?;
+    }
+
+    fs::rename(&temp_path, path)
+}
+
+fn execute_command(command: &mut Command) {
+    match command.spawn() {
+        Ok(mut process) => Log::verify(process.wait()),
+        Err(err) => Log::err(format!(
+            "Failed to run the file explorer. Reason: {:?}",
+            err
+        )),
+    }
+}
+
+fn canonicalize_existing(path: &Path) -> Option<PathBuf> {
+    match path.canonicalize() {
+        Ok(canonical_path) => Some(canonical_path),
+        Err(err) => {
+            Log::warn(format!(
+                "Unable to show {} in the file explorer. Reason: {}",
+                path.display(),
+                err
+            ));
+            None
+        }
+    }
+}
+
+/// Opens the system file explorer at the folder that contains the given file, and selects the file
+/// if the file explorer supports it. Logs a warning if the path does not exist.
+pub fn show_in_file_explorer(path: &Path) {
+    let path = match canonicalize_existing(path) {
+        Some(path) => path,
+        None => return,
+    };
+
+    if cfg!(target_os = "windows") {
+        execute_command(Command::new("explorer").arg("/select,").arg(&path))
+    } else if cfg!(target_os = "macos") {
+        execute_command(Command::new("open").arg("-R").arg(&path))
+    } else {
+        // There is no common way to select a file on Linux, so just open the folder.
+        let folder = if path.is_dir() {
+            path.as_path()
+        } else {
+            path.parent().unwrap_or(&path)
+        };
+        execute_command(Command::new("xdg-open").arg(folder))
+    }
+}
+
+/// Opens the given file (or folder) with the default application of the OS. Logs a warning if the
+/// path does not exist.
+pub fn open_in_file_explorer(path: &Path) {
+    let path = match canonicalize_existing(path) {
+        Some(path) => path,
+        None => return,
+    };
+
+    if cfg!(target_os = "windows") {
+        execute_command(Command::new("explorer").arg(&path))
+    } else if cfg!(target_os = "macos") {
+        execute_command(Command::new("open").arg(&path))
+    } else {
+        execute_command(Command::new("xdg-open").arg(&path))
+    }
+}
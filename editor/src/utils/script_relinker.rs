@@ -0,0 +1,556 @@
+//! Special utility that allows you to fix references to script types that no longer exist. It is
+//! useful if you've renamed (or changed UUID of) a script type, but a scene still refers to the
+//! old one. This is the same as [`super::path_fixer::PathFixer`], but for script types.
+//!
+//! The scene is edited as raw serialized data, because scripts of unknown types cannot be loaded
+//! at all. Only top-level fields of script data are matched: fields that do not exist in the new
+//! type are dropped, missing fields are taken from the default instance of the new type.
+
+use crate::{gui::make_dropdown_list_option, make_scene_file_filter, Message};
+use fyrox::{
+    core::{
+        color::Color,
+        futures::executor::block_on,
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{Field, FieldKind, Node as VisitorNode, Visit, Visitor},
+    },
+    engine::SerializationContext,
+    gui::{
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileSelectorBuilder, FileSelectorMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    utils::log::Log,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+struct BrokenScript {
+    type_uuid: Uuid,
+    /// Script regions of all nodes that use the script type.
+    regions: Vec<Handle<VisitorNode>>,
+    selector: Handle<UiNode>,
+    target: Option<Uuid>,
+}
+
+pub struct ScriptRelinker {
+    pub window: Handle<UiNode>,
+    scene_selector: Handle<UiNode>,
+    scene_path: Handle<UiNode>,
+    scripts_list: Handle<UiNode>,
+    load_scene: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    scene_path_value: PathBuf,
+    visitor: Option<Visitor>,
+    broken_scripts: Vec<BrokenScript>,
+    /// Currently registered script types, in the same order as in selectors (after `<Keep>`).
+    registered_scripts: Vec<(Uuid, String)>,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(100.0)
+            .with_margin(Thickness::uniform(1.0)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn find_child(visitor: &Visitor, parent: Handle<VisitorNode>, name: &str) -> Handle<VisitorNode> {
+    visitor
+        .node(parent)
+        .and_then(|node| {
+            node.children()
+                .iter()
+                .find(|child| visitor.node(**child).map_or(false, |c| c.name() == name))
+                .cloned()
+        })
+        .unwrap_or_default()
+}
+
+fn script_type_uuid(node: &VisitorNode) -> Option<Uuid> {
+    node.fields()
+        .iter()
+        .find(|field| field.name() == "TypeUuid")
+        .and_then(|field| match field.kind() {
+            FieldKind::Uuid(uuid) => Some(*uuid),
+            _ => None,
+        })
+}
+
+/// Collects regions of scripts, see `visit_opt_script` in the engine for the data layout.
+fn collect_script_regions(
+    visitor: &Visitor,
+    handle: Handle<VisitorNode>,
+    regions: &mut Vec<(Handle<VisitorNode>, Uuid)>,
+) {
+    if let Some(node) = visitor.node(handle) {
+        if node.name() == "Script" {
+            if let Some(uuid) = script_type_uuid(node).filter(|uuid| !uuid.is_nil()) {
+                regions.push((handle, uuid));
+            }
+        }
+        for &child in node.children() {
+            collect_script_regions(visitor, child, regions);
+        }
+    }
+}
+
+/// Returns a region with the data of a script instance. Scripts saved in the old format have no
+/// `Data` region, their data is stored directly in `ScriptData`.
+fn script_data_region(visitor: &Visitor, script_data: Handle<VisitorNode>) -> Handle<VisitorNode> {
+    let data = find_child(visitor, script_data, "Data");
+    if data.is_some() {
+        data
+    } else {
+        script_data
+    }
+}
+
+/// Makes data in the `data` region match the data in `default_data`: fields and regions that do
+/// not exist in the defaults are removed, missing ones are copied from the defaults.
+fn merge_script_data(
+    visitor: &mut Visitor,
+    data: Handle<VisitorNode>,
+    defaults: &Visitor,
+    default_data: Handle<VisitorNode>,
+    type_name: &str,
+) {
+    let default_node = defaults.node(default_data).unwrap();
+
+    let node = visitor.node_mut(data).unwrap();
+    node.fields_mut().retain(|field| {
+        let exists = default_node
+            .fields()
+            .iter()
+            .any(|f| f.name() == field.name());
+        if !exists {
+            Log::warn(format!(
+                "Field {} does not exist in {} script and was dropped.",
+                field.name(),
+                type_name
+            ));
+        }
+        exists
+    });
+    for default_field in default_node.fields() {
+        if !node
+            .fields()
+            .iter()
+            .any(|f| f.name() == default_field.name())
+        {
+            Log::info(format!(
+                "Field {} of {} script is missing, default value is used.",
+                default_field.name(),
+                type_name
+            ));
+            node.fields_mut().push(default_field.clone());
+        }
+    }
+
+    for child in node.children().to_vec() {
+        let name = visitor.node(child).unwrap().name().to_owned();
+        if find_child(defaults, default_data, &name).is_none() {
+            Log::warn(format!(
+                "Field {} does not exist in {} script and was dropped.",
+                name, type_name
+            ));
+            visitor.remove_node(child);
+        }
+    }
+    for &default_child in default_node.children() {
+        let name = defaults.node(default_child).unwrap().name();
+        if find_child(visitor, data, name).is_none() {
+            Log::info(format!(
+                "Field {} of {} script is missing, default value is used.",
+                name, type_name
+            ));
+            visitor.copy_node_from(data, defaults, default_child);
+        }
+    }
+}
+
+fn relink_script(
+    visitor: &mut Visitor,
+    script: Handle<VisitorNode>,
+    new_type_uuid: Uuid,
+    type_name: &str,
+    defaults: &Visitor,
+) {
+    let node = visitor.node_mut(script).unwrap();
+    for field in node.fields_mut().iter_mut() {
+        if field.name() == "TypeUuid" {
+            *field = Field::new("TypeUuid", FieldKind::Uuid(new_type_uuid));
+        }
+    }
+
+    let default_script_data = find_child(defaults, defaults.root(), "ScriptData");
+    let script_data = find_child(visitor, script, "ScriptData");
+    if script_data.is_none() {
+        visitor.copy_node_from(script, defaults, default_script_data);
+    } else {
+        let data = script_data_region(visitor, script_data);
+        let default_data = script_data_region(defaults, default_script_data);
+        merge_script_data(visitor, data, defaults, default_data, type_name);
+    }
+}
+
+impl ScriptRelinker {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let scene_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select a scene to relink scripts")),
+        )
+        .with_filter(make_scene_file_filter())
+        .build(ctx);
+
+        let scene_path;
+        let scripts_list;
+        let load_scene;
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(400.0))
+            .with_title(WindowTitle::text("Script Relinker"))
+            .open(false)
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            scene_path = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Scene: No scene loaded!")
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                            scene_path
+                        })
+                        .with_child({
+                            scripts_list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            scripts_list
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .with_child({
+                                        load_scene = make_button(ctx, "Load Scene...");
+                                        load_scene
+                                    })
+                                    .with_child({
+                                        ok = make_button(ctx, "Relink");
+                                        ok
+                                    })
+                                    .with_child({
+                                        cancel = make_button(ctx, "Cancel");
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(28.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            scene_selector,
+            scene_path,
+            scripts_list,
+            load_scene,
+            ok,
+            cancel,
+            scene_path_value: Default::default(),
+            visitor: None,
+            broken_scripts: Default::default(),
+            registered_scripts: Default::default(),
+        }
+    }
+
+    fn set_status(&self, ui: &UserInterface, text: String) {
+        ui.send_message(TextMessage::text(
+            self.scene_path,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+
+    fn clear(&mut self, ui: &UserInterface) {
+        self.visitor = None;
+        self.broken_scripts.clear();
+        ui.send_message(ListViewMessage::items(
+            self.scripts_list,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+    }
+
+    fn load_scene(
+        &mut self,
+        path: &Path,
+        ui: &mut UserInterface,
+        serialization_context: &SerializationContext,
+    ) {
+        self.clear(ui);
+
+        let visitor = match block_on(Visitor::load_binary(path)) {
+            Ok(visitor) => visitor,
+            Err(e) => {
+                self.set_status(
+                    ui,
+                    format!("Failed to load a scene {}\nReason: {}", path.display(), e),
+                );
+                return;
+            }
+        };
+
+        self.registered_scripts = serialization_context
+            .script_constructors
+            .map()
+            .iter()
+            .map(|(uuid, constructor)| (*uuid, constructor.name.clone()))
+            .collect();
+
+        let mut regions = Vec::new();
+        collect_script_regions(&visitor, visitor.root(), &mut regions);
+        for (region, type_uuid) in regions {
+            if self
+                .registered_scripts
+                .iter()
+                .any(|(uuid, _)| *uuid == type_uuid)
+            {
+                continue;
+            }
+            match self
+                .broken_scripts
+                .iter_mut()
+                .find(|s| s.type_uuid == type_uuid)
+            {
+                Some(broken_script) => broken_script.regions.push(region),
+                None => self.broken_scripts.push(BrokenScript {
+                    type_uuid,
+                    regions: vec![region],
+                    selector: Default::default(),
+                    target: None,
+                }),
+            }
+        }
+
+        let ctx = &mut ui.build_ctx();
+        let items = self
+            .broken_scripts
+            .iter_mut()
+            .map(|broken_script| {
+                broken_script.selector = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .on_column(1)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .with_items(
+                    std::iter::once("<Keep>")
+                        .chain(
+                            self.registered_scripts
+                                .iter()
+                                .map(|(_, name)| name.as_str()),
+                        )
+                        .map(|name| make_dropdown_list_option(ctx, name))
+                        .collect(),
+                )
+                .with_selected(0)
+                .build(ctx);
+
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_height(24.0)
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_foreground(Brush::Solid(Color::RED)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text(format!(
+                                "{} ({} node(s))",
+                                broken_script.type_uuid,
+                                broken_script.regions.len()
+                            ))
+                            .build(ctx),
+                        )
+                        .with_child(broken_script.selector),
+                )
+                .add_column(Column::stretch())
+                .add_column(Column::strict(180.0))
+                .add_row(Row::stretch())
+                .build(ctx)
+            })
+            .collect::<Vec<_>>();
+
+        ui.send_message(ListViewMessage::items(
+            self.scripts_list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        self.set_status(
+            ui,
+            format!(
+                "Scene: {}\n{} unknown script type(s) found.",
+                path.display(),
+                self.broken_scripts.len()
+            ),
+        );
+        self.visitor = Some(visitor);
+        self.scene_path_value = path.to_owned();
+    }
+
+    fn relink(&mut self, ui: &UserInterface, serialization_context: &SerializationContext) {
+        let mut visitor = match self.visitor.take() {
+            Some(visitor) => visitor,
+            None => return,
+        };
+
+        for broken_script in self.broken_scripts.iter() {
+            let target = match broken_script.target {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let type_name = self
+                .registered_scripts
+                .iter()
+                .find(|(uuid, _)| *uuid == target)
+                .map(|(_, name)| name.as_str())
+                .unwrap_or_default();
+
+            // Default instance of the new type defines which fields should be kept.
+            let mut defaults = Visitor::new();
+            match serialization_context
+                .script_constructors
+                .try_create(&target)
+                .map(|mut script| script.visit("ScriptData", &mut defaults))
+            {
+                Some(Ok(_)) => (),
+                _ => {
+                    Log::err(format!(
+                        "Unable to create an instance of {} script!",
+                        type_name
+                    ));
+                    continue;
+                }
+            }
+
+            for &region in broken_script.regions.iter() {
+                relink_script(&mut visitor, region, target, type_name, &defaults);
+            }
+
+            Log::info(format!(
+                "Script type {} was relinked to {} in {} node(s).",
+                broken_script.type_uuid,
+                type_name,
+                broken_script.regions.len()
+            ));
+        }
+
+        match visitor.save_binary(&self.scene_path_value) {
+            Ok(_) => Log::info(format!(
+                "Scene {} was saved with relinked scripts.",
+                self.scene_path_value.display()
+            )),
+            Err(e) => Log::err(format!(
+                "Unable to save a scene {}. Reason: {:?}",
+                self.scene_path_value.display(),
+                e
+            )),
+        }
+
+        self.clear(ui);
+        self.set_status(ui, "Scene: No scene loaded!".to_owned());
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        serialization_context: Arc<SerializationContext>,
+    ) {
+        if let Some(FileSelectorMessage::Commit(path)) = message.data::<FileSelectorMessage>() {
+            if message.destination() == self.scene_selector {
+                self.load_scene(path, ui, &serialization_context);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.load_scene {
+                ui.send_message(WindowMessage::open_modal(
+                    self.scene_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.ok {
+                self.relink(ui, &serialization_context);
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                self.clear(ui);
+                self.set_status(ui, "Scene: No scene loaded!".to_owned());
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(broken_script) = self
+                    .broken_scripts
+                    .iter_mut()
+                    .find(|s| s.selector == message.destination())
+                {
+                    broken_script.target = index
+                        .checked_sub(1)
+                        .and_then(|i| self.registered_scripts.get(i))
+                        .map(|(uuid, _)| *uuid);
+                }
+            }
+        }
+    }
+
+    pub fn handle_message(&mut self, message: &Message, ui: &UserInterface) {
+        if let Message::Configure { working_directory } = message {
+            ui.send_message(FileSelectorMessage::root(
+                self.scene_selector,
+                MessageDirection::ToWidget,
+                Some(working_directory.to_owned()),
+            ));
+        }
+    }
+}
@@ -0,0 +1,602 @@
+//! Baking of ambient occlusion to vertex colors. It is a lightweight alternative to lightmaps: for
+//! every vertex of selected meshes a number of rays is cast over the hemisphere around the vertex
+//! normal using physics of the scene, the amount of rays that hit something defines how dark the
+//! vertex is. Baking is spread over multiple frames, so the editor stays responsive.
+
+use crate::{
+    inspector::editors::make_property_editors_container,
+    interaction::vertex_paint::make_paintable_surface,
+    scene::{
+        commands::{mesh::SetMeshSurfacesCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    Message, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix3, Matrix4, Point3, Vector3, Vector4},
+        arrayvec::ArrayVec,
+        math::Matrix4Ext,
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        inspector::{
+            editors::inspectable::InspectablePropertyEditorDefinition, InspectorBuilder,
+            InspectorContext, InspectorMessage, PropertyAction,
+        },
+        message::{MessageDirection, UiMessage},
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        collider::InteractionGroups,
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph,
+        },
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait, VertexWriteTrait},
+            surface::Surface,
+            Mesh,
+        },
+        node::Node,
+        Scene,
+    },
+    utils::log::Log,
+};
+use std::{
+    f32::consts::PI,
+    rc::Rc,
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+/// Amount of time per frame that is spent on baking.
+const TIME_PER_FRAME: Duration = Duration::from_millis(15);
+const DESCRIPTION: &str = "Bakes ambient occlusion of the selected meshes to their vertex colors. \
+    Only objects with colliders occlude vertices, add trimesh colliders to the geometry that \
+    should cast occlusion.";
+
+#[derive(Clone, Debug, Reflect)]
+pub struct VertexAoOptions {
+    #[reflect(
+        description = "Amount of rays cast from every vertex. More samples give smoother \
+        result, but baking takes longer.",
+        min_value = 1.0,
+        max_value = 1024.0
+    )]
+    pub samples: u32,
+    #[reflect(
+        description = "Maximum distance in scene units at which geometry occludes a vertex.",
+        min_value = 0.001
+    )]
+    pub max_distance: f32,
+    #[reflect(
+        description = "Offset of ray origins along vertex normals, it prevents a surface from \
+        occluding itself.",
+        min_value = 0.0
+    )]
+    pub bias: f32,
+    #[reflect(
+        description = "Darkness of fully occluded vertices. Zero has no effect, one makes them \
+        black.",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    pub strength: f32,
+    #[reflect(
+        description = "Multiply existing vertex colors by the occlusion instead of replacing \
+        them with grayscale values."
+    )]
+    pub multiply: bool,
+}
+
+impl Default for VertexAoOptions {
+    fn default() -> Self {
+        Self {
+            samples: 64,
+            max_distance: 1.0,
+            bias: 0.01,
+            strength: 1.0,
+            multiply: false,
+        }
+    }
+}
+
+fn radical_inverse(mut bits: u32) -> f32 {
+    bits = bits.reverse_bits();
+    bits as f32 * 2.3283064e-10
+}
+
+/// Returns cosine-weighted directions over the hemisphere around Z axis. Hammersley sequence is
+/// used instead of random numbers, so the result of baking is stable.
+fn hemisphere_directions(count: u32) -> Vec<Vector3<f32>> {
+    (0..count)
+        .map(|i| {
+            let u = (i as f32 + 0.5) / count as f32;
+            let phi = 2.0 * PI * radical_inverse(i);
+            let r = u.sqrt();
+            Vector3::new(r * phi.cos(), r * phi.sin(), (1.0 - u).sqrt())
+        })
+        .collect()
+}
+
+/// Returns a rotation that maps Z axis to the given normal.
+fn tangent_space(normal: Vector3<f32>) -> Matrix3<f32> {
+    let helper = if normal.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let tangent = helper.cross(&normal).normalize();
+    let binormal = normal.cross(&tangent);
+    Matrix3::from_columns(&[tangent, binormal, normal])
+}
+
+struct MeshBake {
+    mesh: Handle<Node>,
+    surfaces: Vec<Surface>,
+    transform: Matrix4<f32>,
+    normal_matrix: Matrix3<f32>,
+    surface: usize,
+    vertex: usize,
+}
+
+impl MeshBake {
+    fn new(mesh_handle: Handle<Node>, mesh: &Mesh) -> Self {
+        let transform = mesh.global_transform();
+        let basis = transform.basis();
+        Self {
+            mesh: mesh_handle,
+            surfaces: mesh.surfaces().iter().map(make_paintable_surface).collect(),
+            transform,
+            normal_matrix: basis
+                .try_inverse()
+                .map(|inv| inv.transpose())
+                .unwrap_or(basis),
+            surface: 0,
+            vertex: 0,
+        }
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.surfaces
+            .iter()
+            .map(|s| s.data().lock().vertex_buffer.vertex_count() as usize)
+            .sum()
+    }
+
+    /// Bakes vertices until the deadline, returns amount of baked vertices.
+    fn bake(
+        &mut self,
+        graph: &Graph,
+        directions: &[Vector3<f32>],
+        options: &VertexAoOptions,
+        deadline: Instant,
+    ) -> usize {
+        let mut baked = 0;
+
+        while let Some(surface) = self.surfaces.get(self.surface) {
+            let data = surface.data();
+            let mut data = data.lock();
+            let mut vertex_buffer = data.vertex_buffer.modify();
+
+            while self.vertex < vertex_buffer.vertex_count() as usize {
+                if Instant::now() >= deadline {
+                    return baked;
+                }
+
+                let mut view = vertex_buffer.get_mut(self.vertex).unwrap();
+                if let (Ok(position), Ok(normal), Ok(color)) = (
+                    view.read_3_f32(VertexAttributeUsage::Position),
+                    view.read_3_f32(VertexAttributeUsage::Normal),
+                    view.read_4_u8(VertexAttributeUsage::Color),
+                ) {
+                    if let Some(normal) = (self.normal_matrix * normal).try_normalize(f32::EPSILON)
+                    {
+                        let position = self.transform.transform_point(&Point3::from(position));
+                        let ambient = 1.0
+                            - options.strength
+                                * occlusion(graph, position, normal, directions, options);
+                        let base = if options.multiply {
+                            color.xyz().cast::<f32>()
+                        } else {
+                            Vector3::repeat(255.0)
+                        };
+                        let rgb = (base * ambient).map(|c| c.round().clamp(0.0, 255.0) as u8);
+                        let _ = view.write_4_u8(
+                            VertexAttributeUsage::Color,
+                            Vector4::new(rgb.x, rgb.y, rgb.z, color.w),
+                        );
+                    }
+                }
+
+                self.vertex += 1;
+                baked += 1;
+            }
+
+            self.surface += 1;
+            self.vertex = 0;
+        }
+
+        baked
+    }
+
+    fn is_done(&self) -> bool {
+        self.surface >= self.surfaces.len()
+    }
+}
+
+/// Returns a fraction of the rays from the point that hit a collider.
+fn occlusion(
+    graph: &Graph,
+    position: Point3<f32>,
+    normal: Vector3<f32>,
+    directions: &[Vector3<f32>],
+    options: &VertexAoOptions,
+) -> f32 {
+    let rotation = tangent_space(normal);
+    let origin = position + normal.scale(options.bias);
+    let mut intersections = ArrayVec::<Intersection, 1>::new();
+    let mut hits = 0;
+
+    for direction in directions {
+        graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin: origin,
+                ray_direction: rotation * direction,
+                max_len: options.max_distance,
+                groups: InteractionGroups::default(),
+                sort_results: false,
+            },
+            &mut intersections,
+        );
+        if !intersections.is_empty() {
+            hits += 1;
+        }
+    }
+
+    hits as f32 / directions.len() as f32
+}
+
+struct BakeJob {
+    scene: Handle<Scene>,
+    meshes: Vec<MeshBake>,
+    current: usize,
+    directions: Vec<Vector3<f32>>,
+    total_vertices: usize,
+    baked_vertices: usize,
+    started: Instant,
+}
+
+pub struct VertexAoBakeWindow {
+    pub window: Handle<UiNode>,
+    inspector: Handle<UiNode>,
+    progress_bar: Handle<UiNode>,
+    status: Handle<UiNode>,
+    bake: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    close: Handle<UiNode>,
+    options: VertexAoOptions,
+    job: Option<BakeJob>,
+}
+
+impl VertexAoBakeWindow {
+    pub fn new(ctx: &mut BuildContext, sender: Sender<Message>) -> Self {
+        let options = VertexAoOptions::default();
+
+        let container = make_property_editors_container(sender);
+        container.insert(InspectablePropertyEditorDefinition::<VertexAoOptions>::new());
+
+        let context = InspectorContext::from_object(
+            &options,
+            ctx,
+            Rc::new(container),
+            None,
+            MSG_SYNC_FLAG,
+            0,
+            true,
+        );
+
+        let inspector;
+        let progress_bar;
+        let status;
+        let bake;
+        let cancel;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(320.0))
+            .open(false)
+            .can_minimize(false)
+            .with_title(WindowTitle::text("Bake Vertex Ambient Occlusion"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .with_text(DESCRIPTION)
+                            .build(ctx),
+                        )
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                inspector = InspectorBuilder::new(WidgetBuilder::new())
+                                    .with_context(context)
+                                    .build(ctx);
+                                inspector
+                            })
+                            .build(ctx),
+                        )
+                        .with_child({
+                            status = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            status
+                        })
+                        .with_child({
+                            progress_bar = ProgressBarBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(3)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            progress_bar
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(4)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        bake = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Bake")
+                                        .build(ctx);
+                                        bake
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(false)
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    })
+                                    .with_child({
+                                        close = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Close")
+                                        .build(ctx);
+                                        close
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::auto())
+                .add_row(Row::strict(20.0))
+                .add_row(Row::strict(25.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            inspector,
+            progress_bar,
+            status,
+            bake,
+            cancel,
+            close,
+            options,
+            job: None,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn set_status(&self, ui: &UserInterface, text: String, progress: f32) {
+        ui.send_message(TextMessage::text(
+            self.status,
+            MessageDirection::ToWidget,
+            text,
+        ));
+        ui.send_message(ProgressBarMessage::progress(
+            self.progress_bar,
+            MessageDirection::ToWidget,
+            progress,
+        ));
+    }
+
+    fn set_baking(&self, ui: &UserInterface, baking: bool) {
+        ui.send_message(WidgetMessage::enabled(
+            self.bake,
+            MessageDirection::ToWidget,
+            !baking,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.cancel,
+            MessageDirection::ToWidget,
+            baking,
+        ));
+    }
+
+    fn start(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        let ui = &engine.user_interface;
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        let meshes = match &editor_scene.selection {
+            Selection::Graph(selection) => selection
+                .nodes()
+                .iter()
+                .filter_map(|&handle| {
+                    graph
+                        .try_get(handle)
+                        .and_then(|node| node.cast::<Mesh>())
+                        .map(|mesh| MeshBake::new(handle, mesh))
+                })
+                .collect::<Vec<_>>(),
+            _ => Default::default(),
+        };
+
+        if meshes.is_empty() {
+            self.set_status(ui, "Select one or more meshes to bake.".to_owned(), 0.0);
+            return;
+        }
+
+        self.job = Some(BakeJob {
+            scene: editor_scene.scene,
+            total_vertices: meshes.iter().map(|m| m.vertex_count()).sum(),
+            meshes,
+            current: 0,
+            directions: hemisphere_directions(self.options.samples.max(1)),
+            baked_vertices: 0,
+            started: Instant::now(),
+        });
+        self.set_baking(ui, true);
+    }
+
+    fn cancel(&mut self, ui: &UserInterface) {
+        if self.job.take().is_some() {
+            self.set_baking(ui, false);
+            self.set_status(ui, "Baking was cancelled.".to_owned(), 0.0);
+        }
+    }
+
+    /// Continues baking, it must be called every frame. When every mesh is baked, a command that
+    /// sets the baked surfaces is sent, so the whole bake is a single undo step.
+    pub fn update(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &Sender<Message>,
+    ) {
+        let ui = &engine.user_interface;
+
+        let job = match self.job.as_mut() {
+            Some(job) => job,
+            None => return,
+        };
+
+        if job.scene != editor_scene.scene {
+            self.cancel(ui);
+            return;
+        }
+
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let deadline = Instant::now() + TIME_PER_FRAME;
+        while let Some(mesh) = job.meshes.get_mut(job.current) {
+            job.baked_vertices += mesh.bake(graph, &job.directions, &self.options, deadline);
+            if !mesh.is_done() {
+                break;
+            }
+            job.current += 1;
+        }
+
+        if job.current < job.meshes.len() {
+            let text = format!(
+                "Baking: {}/{} vertices",
+                job.baked_vertices, job.total_vertices
+            );
+            let progress = job.baked_vertices as f32 / job.total_vertices.max(1) as f32;
+            self.set_status(ui, text, progress);
+            return;
+        }
+
+        let job = self.job.take().unwrap();
+        let commands = job
+            .meshes
+            .into_iter()
+            .filter(|bake| {
+                graph
+                    .try_get(bake.mesh)
+                    .map_or(false, |n| n.cast::<Mesh>().is_some())
+            })
+            .map(|bake| SceneCommand::new(SetMeshSurfacesCommand::new(bake.mesh, bake.surfaces)))
+            .collect::<Vec<_>>();
+        if !commands.is_empty() {
+            sender
+                .send(Message::do_scene_command(CommandGroup::from(commands)))
+                .unwrap();
+        }
+
+        self.set_baking(ui, false);
+        self.set_status(
+            ui,
+            format!(
+                "Baked {} vertices in {:.1} s",
+                job.total_vertices,
+                job.started.elapsed().as_secs_f32()
+            ),
+            1.0,
+        );
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.bake {
+                self.start(editor_scene, engine);
+            } else if message.destination() == self.cancel {
+                self.cancel(&engine.user_interface);
+            } else if message.destination() == self.close {
+                self.cancel(&engine.user_interface);
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
+            if message.destination() == self.inspector {
+                Log::verify(
+                    PropertyAction::from_field_kind(&property_changed.value)
+                        .apply(&property_changed.path(), &mut self.options),
+                );
+            }
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use crate::load_image;
 use crate::utils::make_node_name;
 use fyrox::{
-    core::{algebra::Vector2, pool::Handle},
+    core::{algebra::Vector2, color::Color, pool::Handle},
     gui::{
         brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
         define_constructor,
         draw::{DrawingContext, SharedTexture},
         grid::{Column, GridBuilder, Row},
-        image::ImageBuilder,
+        image::{ImageBuilder, ImageMessage},
         message::{MessageDirection, OsEvent, UiMessage},
         text::{TextBuilder, TextMessage},
         tree::{Tree, TreeBuilder},
@@ -24,15 +25,60 @@ use std::{
     sync::mpsc::Sender,
 };
 
+/// Visibility state of an entity shown by the "eye" icon of an item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SceneItemVisibility {
+    Visible,
+    Hidden,
+    /// The entity itself is visible, but one of its ancestors is hidden.
+    HiddenByParent,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SceneItemMessage {
     Name(String),
     Validate(Result<(), String>),
+    Visibility(SceneItemVisibility),
+    /// Whether the entity is enabled or not (including the state of its ancestors), disabled
+    /// items are dimmed.
+    Enabled(bool),
+    /// Whether the entity matches the filter of the world viewer or not. Items that don't match,
+    /// but shown because of their matching descendants, are dimmed.
+    FilterMatch(bool),
+    /// Sent by an item when its "eye" icon was clicked. `recursive` is true if the whole subtree
+    /// should be toggled (Alt+Click).
+    ToggleVisibility {
+        recursive: bool,
+    },
 }
 
 impl SceneItemMessage {
     define_constructor!(SceneItemMessage:Name => fn name(String), layout: false);
     define_constructor!(SceneItemMessage:Validate => fn validate(Result<(), String>), layout: false);
+    define_constructor!(SceneItemMessage:Visibility => fn visibility(SceneItemVisibility), layout: false);
+    define_constructor!(SceneItemMessage:Enabled => fn enabled(bool), layout: false);
+    define_constructor!(SceneItemMessage:FilterMatch => fn filter_match(bool), layout: false);
+    define_constructor!(SceneItemMessage:ToggleVisibility => fn toggle_visibility(recursive: bool), layout: false);
+}
+
+fn visibility_icon(visibility: SceneItemVisibility) -> Option<SharedTexture> {
+    match visibility {
+        SceneItemVisibility::Visible | SceneItemVisibility::HiddenByParent => {
+            load_image(include_bytes!("../../../resources/embed/visible.png"))
+        }
+        SceneItemVisibility::Hidden => {
+            load_image(include_bytes!("../../../resources/embed/invisible.png"))
+        }
+    }
+}
+
+fn visibility_icon_brush(visibility: SceneItemVisibility) -> Brush {
+    match visibility {
+        SceneItemVisibility::Visible | SceneItemVisibility::Hidden => {
+            Brush::Solid(Color::opaque(180, 180, 180))
+        }
+        SceneItemVisibility::HiddenByParent => Brush::Solid(Color::opaque(80, 80, 80)),
+    }
 }
 
 pub struct SceneItem<T> {
@@ -43,12 +89,41 @@ pub struct SceneItem<T> {
     pub entity_handle: Handle<T>,
     // Can be unassigned if there's no warning.
     pub warning_icon: Handle<UiNode>,
+    visibility_button: Handle<UiNode>,
+    visibility_icon: Handle<UiNode>,
+    visibility: SceneItemVisibility,
+    enabled: bool,
+    filter_match: bool,
 }
 
 impl<T> SceneItem<T> {
     pub fn name(&self) -> &str {
         &self.name_value
     }
+
+    pub fn visibility(&self) -> SceneItemVisibility {
+        self.visibility
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_filter_match(&self) -> bool {
+        self.filter_match
+    }
+
+    fn sync_opacity(&self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::opacity(
+            self.grid,
+            MessageDirection::ToWidget,
+            if self.enabled && self.filter_match {
+                None
+            } else {
+                Some(0.4)
+            },
+        ));
+    }
 }
 
 impl<T> Clone for SceneItem<T> {
@@ -60,6 +135,11 @@ impl<T> Clone for SceneItem<T> {
             grid: self.grid,
             entity_handle: self.entity_handle,
             warning_icon: self.warning_icon,
+            visibility_button: self.visibility_button,
+            visibility_icon: self.visibility_icon,
+            visibility: self.visibility,
+            enabled: self.enabled,
+            filter_match: self.filter_match,
         }
     }
 }
@@ -98,6 +178,8 @@ impl<T: 'static> Control for SceneItem<T> {
     fn resolve(&mut self, node_map: &NodeHandleMapping) {
         self.tree.resolve(node_map);
         node_map.resolve(&mut self.text_name);
+        node_map.resolve(&mut self.visibility_button);
+        node_map.resolve(&mut self.visibility_icon);
     }
 
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
@@ -147,7 +229,7 @@ impl<T: 'static> Control for SceneItem<T> {
                                 .with_tooltip(make_simple_tooltip(&mut ui.build_ctx(), msg))
                                 .with_margin(Thickness::uniform(1.0))
                                 .on_row(0)
-                                .on_column(2),
+                                .on_column(3),
                         )
                         .with_opt_texture(load_image(include_bytes!(
                             "../../../resources/embed/warning.png"
@@ -162,6 +244,39 @@ impl<T: 'static> Control for SceneItem<T> {
                     }
                 }
             }
+        } else if let Some(&SceneItemMessage::Visibility(visibility)) = message.data() {
+            if message.destination() == self.handle() && self.visibility != visibility {
+                self.visibility = visibility;
+
+                ui.send_message(ImageMessage::texture(
+                    self.visibility_icon,
+                    MessageDirection::ToWidget,
+                    visibility_icon(visibility),
+                ));
+                ui.send_message(WidgetMessage::background(
+                    self.visibility_icon,
+                    MessageDirection::ToWidget,
+                    visibility_icon_brush(visibility),
+                ));
+            }
+        } else if let Some(&SceneItemMessage::Enabled(enabled)) = message.data() {
+            if message.destination() == self.handle() && self.enabled != enabled {
+                self.enabled = enabled;
+                self.sync_opacity(ui);
+            }
+        } else if let Some(&SceneItemMessage::FilterMatch(filter_match)) = message.data() {
+            if message.destination() == self.handle() && self.filter_match != filter_match {
+                self.filter_match = filter_match;
+                self.sync_opacity(ui);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.visibility_button {
+                ui.send_message(SceneItemMessage::toggle_visibility(
+                    self.handle(),
+                    MessageDirection::FromWidget,
+                    ui.keyboard_modifiers().alt,
+                ));
+            }
         }
     }
 
@@ -220,14 +335,42 @@ impl<T: 'static> SceneItemBuilder<T> {
 
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let text_name;
+        let visibility_button;
+        let visibility_icon;
         let content = GridBuilder::new(
             WidgetBuilder::new()
+                .with_child({
+                    visibility_button = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Show/Hide In Editor\nAlt+Click - Toggle Whole Subtree",
+                            ))
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(0),
+                    )
+                    .with_content({
+                        visibility_icon = ImageBuilder::new(
+                            WidgetBuilder::new()
+                                .with_background(visibility_icon_brush(
+                                    SceneItemVisibility::Visible,
+                                ))
+                                .with_width(14.0)
+                                .with_height(14.0),
+                        )
+                        .with_opt_texture(visibility_icon(SceneItemVisibility::Visible))
+                        .build(ctx);
+                        visibility_icon
+                    })
+                    .build(ctx);
+                    visibility_button
+                })
                 .with_child(
                     ImageBuilder::new(
                         WidgetBuilder::new()
                             .with_width(16.0)
                             .with_height(16.0)
-                            .on_column(0)
+                            .on_column(1)
                             .with_margin(Thickness::uniform(1.0)),
                     )
                     .with_opt_texture(self.icon)
@@ -241,7 +384,7 @@ impl<T: 'static> SceneItemBuilder<T> {
                                     .unwrap_or(Brush::Solid(fyrox::gui::COLOR_FOREGROUND)),
                             )
                             .with_margin(Thickness::uniform(1.0))
-                            .on_column(1)
+                            .on_column(2)
                             .with_vertical_alignment(VerticalAlignment::Center),
                     )
                     .with_text(format!(
@@ -256,6 +399,7 @@ impl<T: 'static> SceneItemBuilder<T> {
         )
         .add_row(Row::stretch())
         .add_column(Column::auto())
+        .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_column(Column::auto())
         .build(ctx);
@@ -269,6 +413,11 @@ impl<T: 'static> SceneItemBuilder<T> {
             text_name,
             grid: content,
             warning_icon: Default::default(),
+            visibility_button,
+            visibility_icon,
+            visibility: SceneItemVisibility::Visible,
+            enabled: true,
+            filter_match: true,
         };
 
         ctx.add_node(UiNode::new(item))
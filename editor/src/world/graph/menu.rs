@@ -1,31 +1,54 @@
 use crate::{
     make_save_file_selector,
     menu::{create::CreateEntityMenu, create_menu_item, create_menu_item_shortcut},
+    repeat::RepeatableAction,
     scene::{
         commands::{
-            graph::{AddNodeCommand, ReplaceNodeCommand},
+            graph::{
+                make_convert_lights_command, make_flatten_hierarchy_command, AddNodeCommand,
+                BakeNodesVisibilityCommand, FlattenTarget, LightKind, ReplaceNodeCommand,
+            },
             make_delete_selection_command,
+            prefab::make_propagate_prefab_properties_command,
+            ChangeSelectionCommand, DuplicationMode,
         },
         EditorScene, Selection,
     },
-    world::graph::item::SceneItem,
-    GameEngine, Message, MessageDirection, PasteCommand,
+    world::graph::{item::SceneItem, selection::SimilarNodesScope},
+    GameEngine, InstancePlacement, Message, MessageDirection, PasteCommand,
 };
 use fyrox::{
     core::{algebra::Vector2, pool::Handle, scope_profile},
     gui::{
-        file_browser::FileSelectorMessage,
+        file_browser::{FileSelectorBuilder, FileSelectorMessage, Filter},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
         message::UiMessage,
         popup::{Placement, PopupBuilder, PopupMessage},
         stack_panel::StackPanelBuilder,
         widget::{WidgetBuilder, WidgetMessage},
-        window::WindowMessage,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode,
     },
-    scene::node::Node,
+    scene::{light::BaseLight, node::Node},
 };
-use std::sync::mpsc::Sender;
+use std::{path::Path, sync::mpsc::Sender};
+
+fn open_save_dialog(engine: &GameEngine, dialog: Handle<UiNode>) {
+    engine
+        .user_interface
+        .send_message(WindowMessage::open_modal(
+            dialog,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    engine
+        .user_interface
+        .send_message(FileSelectorMessage::root(
+            dialog,
+            MessageDirection::ToWidget,
+            Some(std::env::current_dir().unwrap()),
+        ));
+}
 
 pub struct ItemContextMenu {
     pub menu: Handle<UiNode>,
@@ -38,7 +61,36 @@ pub struct ItemContextMenu {
     preview_camera: Handle<UiNode>,
     save_as_prefab: Handle<UiNode>,
     save_as_prefab_dialog: Handle<UiNode>,
+    make_prefab: Handle<UiNode>,
+    make_prefab_dialog: Handle<UiNode>,
+    instantiate_as_siblings: Handle<UiNode>,
+    instantiate_as_children: Handle<UiNode>,
+    instantiate_replacing: Handle<UiNode>,
+    instantiate_dialog: Handle<UiNode>,
+    /// Placement of the instances, it is chosen before the model is selected in the dialog.
+    instance_placement: InstancePlacement,
+    export_selection: Handle<UiNode>,
+    export_selection_dialog: Handle<UiNode>,
     paste: Handle<UiNode>,
+    duplicate_independent: Handle<UiNode>,
+    duplicate_linked: Handle<UiNode>,
+    bake_visibility: Handle<UiNode>,
+    toggle_enabled: Handle<UiNode>,
+    toggle_frozen: Handle<UiNode>,
+    propagate_prefab_properties: Handle<UiNode>,
+    reimport_models: Handle<UiNode>,
+    flatten_into_node: Handle<UiNode>,
+    flatten_into_parent: Handle<UiNode>,
+    flatten_into_node_delete_pivots: Handle<UiNode>,
+    flatten_into_parent_delete_pivots: Handle<UiNode>,
+    select_type_in_scene: Handle<UiNode>,
+    select_type_in_subtree: Handle<UiNode>,
+    select_similar_in_scene: Handle<UiNode>,
+    select_similar_in_subtree: Handle<UiNode>,
+    convert_light_menu: Handle<UiNode>,
+    convert_to_point_light: Handle<UiNode>,
+    convert_to_spot_light: Handle<UiNode>,
+    convert_to_directional_light: Handle<UiNode>,
 }
 
 impl ItemContextMenu {
@@ -46,7 +98,31 @@ impl ItemContextMenu {
         let delete_selection;
         let copy_selection;
         let save_as_prefab;
+        let make_prefab;
+        let instantiate_as_siblings;
+        let instantiate_as_children;
+        let instantiate_replacing;
+        let export_selection;
         let paste;
+        let duplicate_independent;
+        let duplicate_linked;
+        let bake_visibility;
+        let toggle_enabled;
+        let toggle_frozen;
+        let propagate_prefab_properties;
+        let reimport_models;
+        let flatten_into_node;
+        let flatten_into_parent;
+        let flatten_into_node_delete_pivots;
+        let flatten_into_parent_delete_pivots;
+        let select_type_in_scene;
+        let select_type_in_subtree;
+        let select_similar_in_scene;
+        let select_similar_in_subtree;
+        let convert_light_menu;
+        let convert_to_point_light;
+        let convert_to_spot_light;
+        let convert_to_directional_light;
 
         let (create_entity_menu, create_entity_menu_root_items) = CreateEntityMenu::new(ctx);
         let (replace_with_menu, replace_with_menu_root_items) = CreateEntityMenu::new(ctx);
@@ -70,10 +146,169 @@ impl ItemContextMenu {
                             paste = create_menu_item("Paste As Child", vec![], ctx);
                             paste
                         })
+                        .with_child(create_menu_item(
+                            "Duplicate",
+                            vec![
+                                {
+                                    duplicate_independent = create_menu_item_shortcut(
+                                        "Independent",
+                                        "Ctrl+D",
+                                        vec![],
+                                        ctx,
+                                    );
+                                    duplicate_independent
+                                },
+                                {
+                                    duplicate_linked =
+                                        create_menu_item_shortcut("Linked", "Alt+D", vec![], ctx);
+                                    duplicate_linked
+                                },
+                            ],
+                            ctx,
+                        ))
                         .with_child({
                             save_as_prefab = create_menu_item("Save As Prefab...", vec![], ctx);
                             save_as_prefab
                         })
+                        .with_child({
+                            make_prefab = create_menu_item("Make Prefab...", vec![], ctx);
+                            make_prefab
+                        })
+                        .with_child(create_menu_item(
+                            "Instantiate Model At Selected",
+                            vec![
+                                {
+                                    instantiate_as_siblings =
+                                        create_menu_item("As Siblings...", vec![], ctx);
+                                    instantiate_as_siblings
+                                },
+                                {
+                                    instantiate_as_children =
+                                        create_menu_item("As Children...", vec![], ctx);
+                                    instantiate_as_children
+                                },
+                                {
+                                    instantiate_replacing =
+                                        create_menu_item("Replacing Nodes...", vec![], ctx);
+                                    instantiate_replacing
+                                },
+                            ],
+                            ctx,
+                        ))
+                        .with_child({
+                            export_selection = create_menu_item("Export Selection...", vec![], ctx);
+                            export_selection
+                        })
+                        .with_child({
+                            bake_visibility = create_menu_item("Bake Visibility", vec![], ctx);
+                            bake_visibility
+                        })
+                        .with_child({
+                            toggle_enabled =
+                                create_menu_item_shortcut("Toggle Enabled", "Ctrl+E", vec![], ctx);
+                            toggle_enabled
+                        })
+                        .with_child({
+                            toggle_frozen =
+                                create_menu_item("Toggle Frozen In Play Mode", vec![], ctx);
+                            toggle_frozen
+                        })
+                        .with_child({
+                            propagate_prefab_properties =
+                                create_menu_item("Propagate Prefab Changes", vec![], ctx);
+                            propagate_prefab_properties
+                        })
+                        .with_child({
+                            reimport_models = create_menu_item("Reimport Model", vec![], ctx);
+                            reimport_models
+                        })
+                        .with_child(create_menu_item(
+                            "Select All Of Type",
+                            vec![
+                                {
+                                    select_type_in_scene =
+                                        create_menu_item("In Scene", vec![], ctx);
+                                    select_type_in_scene
+                                },
+                                {
+                                    select_type_in_subtree =
+                                        create_menu_item("In Parent Subtree", vec![], ctx);
+                                    select_type_in_subtree
+                                },
+                            ],
+                            ctx,
+                        ))
+                        .with_child(create_menu_item(
+                            "Select Similar",
+                            vec![
+                                {
+                                    select_similar_in_scene =
+                                        create_menu_item("In Scene", vec![], ctx);
+                                    select_similar_in_scene
+                                },
+                                {
+                                    select_similar_in_subtree =
+                                        create_menu_item("In Parent Subtree", vec![], ctx);
+                                    select_similar_in_subtree
+                                },
+                            ],
+                            ctx,
+                        ))
+                        .with_child(create_menu_item(
+                            "Flatten Hierarchy",
+                            vec![
+                                {
+                                    flatten_into_node = create_menu_item("Into Node", vec![], ctx);
+                                    flatten_into_node
+                                },
+                                {
+                                    flatten_into_parent =
+                                        create_menu_item("Into Parent", vec![], ctx);
+                                    flatten_into_parent
+                                },
+                                {
+                                    flatten_into_node_delete_pivots = create_menu_item(
+                                        "Into Node, Delete Empty Pivots",
+                                        vec![],
+                                        ctx,
+                                    );
+                                    flatten_into_node_delete_pivots
+                                },
+                                {
+                                    flatten_into_parent_delete_pivots = create_menu_item(
+                                        "Into Parent, Delete Empty Pivots",
+                                        vec![],
+                                        ctx,
+                                    );
+                                    flatten_into_parent_delete_pivots
+                                },
+                            ],
+                            ctx,
+                        ))
+                        .with_child({
+                            convert_light_menu = create_menu_item(
+                                "Convert Light To",
+                                vec![
+                                    {
+                                        convert_to_point_light =
+                                            create_menu_item("Point Light", vec![], ctx);
+                                        convert_to_point_light
+                                    },
+                                    {
+                                        convert_to_spot_light =
+                                            create_menu_item("Spot Light", vec![], ctx);
+                                        convert_to_spot_light
+                                    },
+                                    {
+                                        convert_to_directional_light =
+                                            create_menu_item("Directional Light", vec![], ctx);
+                                        convert_to_directional_light
+                                    },
+                                ],
+                                ctx,
+                            );
+                            convert_light_menu
+                        })
                         .with_child(
                             MenuItemBuilder::new(
                                 WidgetBuilder::new().with_min_size(Vector2::new(120.0, 22.0)),
@@ -107,6 +342,21 @@ impl ItemContextMenu {
 
         // TODO: Not sure if this is the right place for this dialog.
         let save_as_prefab_dialog = make_save_file_selector(ctx);
+        let make_prefab_dialog = make_save_file_selector(ctx);
+        let export_selection_dialog = make_save_file_selector(ctx);
+        let instantiate_dialog = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select a Model To Instantiate")),
+        )
+        .with_filter(Filter::new(|p: &Path| {
+            if let Some(ext) = p.extension() {
+                matches!(ext.to_string_lossy().as_ref(), "fbx" | "rgs")
+            } else {
+                p.is_dir()
+            }
+        }))
+        .build(ctx);
 
         Self {
             create_entity_menu,
@@ -117,8 +367,103 @@ impl ItemContextMenu {
             preview_camera,
             save_as_prefab,
             save_as_prefab_dialog,
+            make_prefab,
+            make_prefab_dialog,
+            instantiate_as_siblings,
+            instantiate_as_children,
+            instantiate_replacing,
+            instantiate_dialog,
+            instance_placement: InstancePlacement::Sibling,
+            export_selection,
+            export_selection_dialog,
             replace_with_menu,
             paste,
+            duplicate_independent,
+            duplicate_linked,
+            bake_visibility,
+            toggle_enabled,
+            toggle_frozen,
+            propagate_prefab_properties,
+            reimport_models,
+            flatten_into_node,
+            flatten_into_parent,
+            flatten_into_node_delete_pivots,
+            flatten_into_parent_delete_pivots,
+            select_type_in_scene,
+            select_type_in_subtree,
+            select_similar_in_scene,
+            select_similar_in_subtree,
+            convert_light_menu,
+            convert_to_point_light,
+            convert_to_spot_light,
+            convert_to_directional_light,
+        }
+    }
+
+    fn flatten_params(&self, message: &UiMessage) -> Option<(FlattenTarget, bool)> {
+        let destination = message.destination();
+        if destination == self.flatten_into_node {
+            Some((FlattenTarget::Node, false))
+        } else if destination == self.flatten_into_parent {
+            Some((FlattenTarget::Parent, false))
+        } else if destination == self.flatten_into_node_delete_pivots {
+            Some((FlattenTarget::Node, true))
+        } else if destination == self.flatten_into_parent_delete_pivots {
+            Some((FlattenTarget::Parent, true))
+        } else {
+            None
+        }
+    }
+
+    fn select_similar_params(&self, message: &UiMessage) -> Option<(SimilarNodesScope, bool)> {
+        let destination = message.destination();
+        if destination == self.select_type_in_scene {
+            Some((SimilarNodesScope::Scene, false))
+        } else if destination == self.select_type_in_subtree {
+            Some((SimilarNodesScope::ParentSubtree, false))
+        } else if destination == self.select_similar_in_scene {
+            Some((SimilarNodesScope::Scene, true))
+        } else if destination == self.select_similar_in_subtree {
+            Some((SimilarNodesScope::ParentSubtree, true))
+        } else {
+            None
+        }
+    }
+
+    fn duplication_mode(&self, message: &UiMessage) -> Option<DuplicationMode> {
+        let destination = message.destination();
+        if destination == self.duplicate_independent {
+            Some(DuplicationMode::Independent)
+        } else if destination == self.duplicate_linked {
+            Some(DuplicationMode::Linked)
+        } else {
+            None
+        }
+    }
+
+    fn instance_placement(&self, message: &UiMessage) -> Option<InstancePlacement> {
+        let destination = message.destination();
+        if destination == self.instantiate_as_siblings {
+            Some(InstancePlacement::Sibling)
+        } else if destination == self.instantiate_as_children {
+            Some(InstancePlacement::Child)
+        } else if destination == self.instantiate_replacing {
+            Some(InstancePlacement::Replace)
+        } else {
+            None
+        }
+    }
+
+    fn convert_light_params(&self, message: &UiMessage) -> Option<LightKind> {
+        let destination = message.destination();
+        if destination == self.convert_to_point_light {
+            Some(LightKind::Point)
+        } else if destination == self.convert_to_spot_light {
+            Some(LightKind::Spot)
+        } else if destination == self.convert_to_directional_light {
+            Some(LightKind::Directional)
+        } else {
+            None
         }
     }
 
@@ -133,7 +478,11 @@ impl ItemContextMenu {
 
         if let Selection::Graph(graph_selection) = &editor_scene.selection {
             if let Some(first) = graph_selection.nodes().first() {
-                if let Some(node) = self.create_entity_menu.handle_ui_message(message) {
+                if let Some(mut node) = self.create_entity_menu.handle_ui_message(message) {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    if let Some(position) = editor_scene.cursor_local_position(graph, *first) {
+                        node.local_transform_mut().set_position(position);
+                    }
                     sender
                         .send(Message::do_scene_command(AddNodeCommand::new(node, *first)))
                         .unwrap();
@@ -175,6 +524,8 @@ impl ItemContextMenu {
                         }
                     }
                 }
+            } else if let Some(mode) = self.duplication_mode(message) {
+                RepeatableAction::duplicate(mode).perform(editor_scene, engine, sender);
             } else if message.destination() == self.preview_camera {
                 let new_preview_camera = engine
                     .user_interface
@@ -187,21 +538,111 @@ impl ItemContextMenu {
                 } else {
                     editor_scene.preview_camera = new_preview_camera
                 }
-            } else if message.destination() == self.save_as_prefab {
-                engine
-                    .user_interface
-                    .send_message(WindowMessage::open_modal(
-                        self.save_as_prefab_dialog,
-                        MessageDirection::ToWidget,
-                        true,
-                    ));
-                engine
-                    .user_interface
-                    .send_message(FileSelectorMessage::root(
-                        self.save_as_prefab_dialog,
-                        MessageDirection::ToWidget,
-                        Some(std::env::current_dir().unwrap()),
+            } else if message.destination() == self.bake_visibility {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let nodes = graph_selection
+                        .nodes()
+                        .iter()
+                        .filter(|n| editor_scene.hidden_nodes.contains(*n))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if !nodes.is_empty() {
+                        sender
+                            .send(Message::do_scene_command(BakeNodesVisibilityCommand::new(
+                                nodes,
+                            )))
+                            .unwrap();
+                    }
+                }
+            } else if message.destination() == self.toggle_enabled {
+                RepeatableAction::ToggleEnabled.perform(editor_scene, engine, sender);
+            } else if message.destination() == self.toggle_frozen {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let nodes = graph_selection.nodes().to_vec();
+                    // Mixed selection is frozen as a whole.
+                    let frozen = !nodes.iter().all(|n| editor_scene.frozen_nodes.contains(n));
+                    sender
+                        .send(Message::SetNodesFrozen { nodes, frozen })
+                        .unwrap();
+                }
+            } else if message.destination() == self.propagate_prefab_properties {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    let mut prefabs = Vec::new();
+                    for &node in graph_selection.nodes() {
+                        if let Some(resource) = graph[node].resource() {
+                            if !prefabs.contains(&resource) {
+                                prefabs.push(resource);
+                            }
+                        }
+                    }
+                    if let Some(command) = make_propagate_prefab_properties_command(&prefabs, graph)
+                    {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.reimport_models {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    let mut models = Vec::new();
+                    for &node in graph_selection.nodes() {
+                        if let Some(resource) = graph[node].resource() {
+                            if !models.contains(&resource) {
+                                models.push(resource);
+                            }
+                        }
+                    }
+                    if !models.is_empty() {
+                        sender.send(Message::ReimportModels(models)).unwrap();
+                    }
+                }
+            } else if let Some((target, delete_empty_pivots)) = self.flatten_params(message) {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    if let Some(command) = make_flatten_hierarchy_command(
+                        graph_selection,
+                        &engine.scenes[editor_scene.scene].graph,
+                        target,
+                        delete_empty_pivots,
+                    ) {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if let Some((scope, match_materials)) = self.select_similar_params(message) {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    let new_selection = Selection::Graph(graph_selection.similar(
+                        &engine.scenes[editor_scene.scene].graph,
+                        scope,
+                        match_materials,
+                        editor_scene.editor_objects_root,
                     ));
+                    if new_selection != editor_scene.selection {
+                        sender
+                            .send(Message::do_scene_command(ChangeSelectionCommand::new(
+                                new_selection,
+                                editor_scene.selection.clone(),
+                            )))
+                            .unwrap();
+                    }
+                }
+            } else if let Some(kind) = self.convert_light_params(message) {
+                if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                    if let Some(command) = make_convert_lights_command(
+                        graph_selection,
+                        &engine.scenes[editor_scene.scene].graph,
+                        kind,
+                    ) {
+                        sender.send(Message::DoSceneCommand(command)).unwrap();
+                    }
+                }
+            } else if message.destination() == self.save_as_prefab {
+                open_save_dialog(engine, self.save_as_prefab_dialog);
+            } else if message.destination() == self.make_prefab {
+                open_save_dialog(engine, self.make_prefab_dialog);
+            } else if message.destination() == self.export_selection {
+                open_save_dialog(engine, self.export_selection_dialog);
+            } else if let Some(placement) = self.instance_placement(message) {
+                self.instance_placement = placement;
+                open_save_dialog(engine, self.instantiate_dialog);
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == self.menu {
@@ -228,6 +669,46 @@ impl ItemContextMenu {
                     is_camera,
                 ));
 
+                // Propagation and reimport make sense only for instances of prefabs.
+                let has_instances =
+                    if let Selection::Graph(graph_selection) = &editor_scene.selection {
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+                        graph_selection
+                            .nodes()
+                            .iter()
+                            .any(|n| graph.try_get(*n).map_or(false, |n| n.resource().is_some()))
+                    } else {
+                        false
+                    };
+                engine.user_interface.send_message(WidgetMessage::enabled(
+                    self.propagate_prefab_properties,
+                    MessageDirection::ToWidget,
+                    has_instances,
+                ));
+                engine.user_interface.send_message(WidgetMessage::enabled(
+                    self.reimport_models,
+                    MessageDirection::ToWidget,
+                    has_instances,
+                ));
+
+                // Light conversion makes sense only if there's at least one light selected.
+                let has_lights = if let Selection::Graph(graph_selection) = &editor_scene.selection
+                {
+                    let graph = &engine.scenes[editor_scene.scene].graph;
+                    graph_selection.nodes().iter().any(|n| {
+                        graph
+                            .try_get(*n)
+                            .map_or(false, |n| n.query_component_ref::<BaseLight>().is_some())
+                    })
+                } else {
+                    false
+                };
+                engine.user_interface.send_message(WidgetMessage::enabled(
+                    self.convert_light_menu,
+                    MessageDirection::ToWidget,
+                    has_lights,
+                ));
+
                 // Check if there's something to paste and deactivate "Paste" if nothing.
                 engine.user_interface.send_message(WidgetMessage::enabled(
                     self.paste,
@@ -240,6 +721,17 @@ impl ItemContextMenu {
                 sender
                     .send(Message::SaveSelectionAsPrefab(path.clone()))
                     .unwrap();
+            } else if message.destination() == self.make_prefab_dialog {
+                sender.send(Message::MakePrefab(path.clone())).unwrap();
+            } else if message.destination() == self.export_selection_dialog {
+                sender.send(Message::ExportSelection(path.clone())).unwrap();
+            } else if message.destination() == self.instantiate_dialog {
+                sender
+                    .send(Message::InstantiateAtSelection {
+                        path: path.clone(),
+                        placement: self.instance_placement,
+                    })
+                    .unwrap();
             }
         }
     }
@@ -2,9 +2,29 @@ use crate::utils;
 use fyrox::{
     asset::core::algebra::Vector3,
     core::{algebra::UnitQuaternion, math::Matrix4Ext, pool::Handle},
-    scene::{graph::Graph, node::Node},
+    fxhash::FxHashSet,
+    material::SharedMaterial,
+    scene::{graph::Graph, mesh::Mesh, node::Node},
 };
 
+/// Defines where [`GraphSelection::similar`] searches for nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SimilarNodesScope {
+    /// Whole scene.
+    Scene,
+    /// Hierarchies of the parents of selected nodes.
+    ParentSubtree,
+}
+
+fn materials(node: &Node) -> Option<Vec<SharedMaterial>> {
+    node.cast::<Mesh>().map(|mesh| {
+        mesh.surfaces()
+            .iter()
+            .map(|surface| surface.material().clone())
+            .collect()
+    })
+}
+
 #[derive(Debug, Default, Clone, Eq)]
 pub struct GraphSelection {
     pub nodes: Vec<Handle<Node>>,
@@ -51,6 +71,11 @@ impl GraphSelection {
         &self.nodes
     }
 
+    /// Returns the node that is shown in the inspector, it is the first selected node.
+    pub fn active(&self) -> Option<Handle<Node>> {
+        self.nodes.first().cloned()
+    }
+
     pub fn is_multi_selection(&self) -> bool {
         self.nodes.len() > 1
     }
@@ -194,4 +219,76 @@ impl GraphSelection {
         }
         scales
     }
+
+    /// Creates a new selection with every node that has the same type as any of the selected
+    /// nodes. If `match_materials` is set, meshes must also have the same materials. Nodes in the
+    /// hierarchy of `excluded_root` are never selected.
+    pub fn similar(
+        &self,
+        graph: &Graph,
+        scope: SimilarNodesScope,
+        match_materials: bool,
+        excluded_root: Handle<Node>,
+    ) -> GraphSelection {
+        let samples = self
+            .nodes
+            .iter()
+            .filter_map(|&handle| graph.try_get(handle))
+            .map(|node| {
+                (
+                    node.id(),
+                    if match_materials {
+                        materials(node)
+                    } else {
+                        None
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let roots = match scope {
+            SimilarNodesScope::Scene => vec![graph.get_root()],
+            SimilarNodesScope::ParentSubtree => {
+                let mut roots = Vec::new();
+                for handle in self.root_nodes(graph) {
+                    let parent = graph[handle].parent();
+                    let root = if parent.is_some() { parent } else { handle };
+                    if !roots.contains(&root) {
+                        roots.push(root);
+                    }
+                }
+                roots
+            }
+        };
+
+        let excluded = graph
+            .try_get(excluded_root)
+            .map(|_| graph.traverse_handle_iter(excluded_root).collect())
+            .unwrap_or_else(FxHashSet::default);
+
+        let mut visited = FxHashSet::default();
+        let mut nodes = Vec::new();
+        for root in roots {
+            for handle in graph.traverse_handle_iter(root) {
+                if handle == graph.get_root()
+                    || excluded.contains(&handle)
+                    || !visited.insert(handle)
+                {
+                    continue;
+                }
+
+                let node = &graph[handle];
+                let id = node.id();
+                let is_similar = samples.iter().any(|(sample_id, sample_materials)| {
+                    *sample_id == id
+                        && (sample_materials.is_none() || *sample_materials == materials(node))
+                });
+                if is_similar {
+                    nodes.push(handle);
+                }
+            }
+        }
+
+        GraphSelection::from_list(nodes)
+    }
 }
@@ -2,18 +2,22 @@ use crate::{
     gui::make_image_button_with_tooltip,
     load_image,
     scene::{
-        commands::{graph::LinkNodesCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
+        commands::{
+            graph::{LinkNodesCommand, SetNodesVisibilityCommand},
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
         EditorScene, Selection,
     },
     send_sync_message,
     utils::window_content,
     world::{
         graph::{
-            item::{SceneItem, SceneItemBuilder, SceneItemMessage},
+            item::{SceneItem, SceneItemBuilder, SceneItemMessage, SceneItemVisibility},
             menu::ItemContextMenu,
             selection::GraphSelection,
         },
         search::SearchBar,
+        type_filter::{TypeFilter, TypeFilterSelector},
     },
     GameEngine, Message, Mode, Settings,
 };
@@ -24,6 +28,7 @@ use fyrox::{
         scope_profile,
     },
     engine::Engine,
+    fxhash::FxHashSet,
     gui::{
         border::BorderBuilder,
         brush::Brush,
@@ -34,25 +39,38 @@ use fyrox::{
         message::{MessageDirection, UiMessage},
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         stack_panel::StackPanelBuilder,
-        text::TextBuilder,
+        text::{TextBuilder, TextMessage},
         tree::{
             Tree, TreeBuilder, TreeExpansionStrategy, TreeMessage, TreeRoot, TreeRootBuilder,
             TreeRootMessage,
         },
         ttf::{FontBuilder, SharedFont},
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
         wrap_panel::WrapPanelBuilder,
         BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
         BRUSH_BRIGHT_BLUE, BRUSH_PRIMARY,
     },
-    scene::{graph::Graph, node::Node, Scene},
+    scene::{
+        graph::Graph,
+        mesh::Mesh,
+        node::{Node, NodeTrait},
+        Scene,
+    },
     utils::log::Log,
 };
-use std::{any::TypeId, cmp::Ordering, collections::HashMap, sync::mpsc::Sender};
+use std::{
+    any::TypeId,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    sync::mpsc::Sender,
+};
 
 pub mod graph;
 pub mod search;
+pub mod type_filter;
 
 pub struct WorldViewer {
     pub window: Handle<UiNode>,
@@ -62,6 +80,8 @@ pub struct WorldViewer {
     track_selection: Handle<UiNode>,
     search_bar: SearchBar,
     filter: String,
+    type_filter_selector: TypeFilterSelector,
+    type_filter: Option<TypeFilter>,
     stack: Vec<(Handle<UiNode>, Handle<Node>)>,
     /// Hack. Due to delayed execution of UI code we can't sync immediately after we
     /// did sync_to_model, instead we defer selection syncing to post_update() - at
@@ -75,7 +95,13 @@ pub struct WorldViewer {
     scroll_view: Handle<UiNode>,
     item_context_menu: ItemContextMenu,
     node_to_view_map: HashMap<Handle<Node>, Handle<UiNode>>,
+    /// Nodes which items are expanded. Items are rebuilt when nodes are moved in the hierarchy,
+    /// the set allows to restore their expansion, so the tree does not jump around after edits.
+    expanded_nodes: HashSet<Handle<Node>>,
     small_font: SharedFont,
+    // Shared by all the items, its text is updated when an item is hovered.
+    item_tooltip: Rc<Handle<UiNode>>,
+    item_tooltip_text: Handle<UiNode>,
 }
 
 fn make_graph_node_item(
@@ -83,6 +109,8 @@ fn make_graph_node_item(
     handle: Handle<Node>,
     ctx: &mut BuildContext,
     context_menu: Handle<UiNode>,
+    tooltip: Rc<Handle<UiNode>>,
+    expanded: bool,
 ) -> Handle<UiNode> {
     let icon = if node.is_point_light() || node.is_directional_light() || node.is_spot_light() {
         load_image(include_bytes!("../../resources/embed/light.png"))
@@ -98,16 +126,20 @@ fn make_graph_node_item(
         load_image(include_bytes!("../../resources/embed/cube.png"))
     };
 
-    SceneItemBuilder::new(TreeBuilder::new(
-        WidgetBuilder::new()
-            .with_margin(Thickness {
-                left: 1.0,
-                top: 1.0,
-                right: 0.0,
-                bottom: 0.0,
-            })
-            .with_context_menu(context_menu),
-    ))
+    SceneItemBuilder::new(
+        TreeBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness {
+                    left: 1.0,
+                    top: 1.0,
+                    right: 0.0,
+                    bottom: 0.0,
+                })
+                .with_context_menu(context_menu)
+                .with_tooltip(tooltip),
+        )
+        .with_expanded(expanded),
+    )
     .with_text_brush(if node.resource().is_some() {
         Brush::Solid(Color::opaque(160, 160, 200))
     } else {
@@ -119,6 +151,71 @@ fn make_graph_node_item(
     .build(ctx)
 }
 
+fn node_statistics(graph: &Graph, node_handle: Handle<Node>) -> String {
+    let node = &graph[node_handle];
+
+    if let Some(mesh) = node.cast::<Mesh>() {
+        let mut vertices = 0;
+        let mut triangles = 0;
+        let mut materials = FxHashSet::default();
+        for surface in mesh.surfaces() {
+            let data = surface.data();
+            let data = data.lock();
+            vertices += data.vertex_buffer.vertex_count() as usize;
+            triangles += data.geometry_buffer.len();
+            materials.insert(surface.material().key());
+        }
+
+        let size = if vertices > 0 {
+            mesh.local_bounding_box().half_extents() * 2.0
+        } else {
+            Default::default()
+        };
+
+        let mut statistics = format!(
+            "Vertices: {}\nTriangles: {}\nSurfaces: {}\nMaterials: {}\nSize: {:.3} x {:.3} x {:.3}",
+            vertices,
+            triangles,
+            mesh.surfaces().len(),
+            materials.len(),
+            size.x,
+            size.y,
+            size.z
+        );
+
+        // Linked duplicates (and instances of the same model) share surface data.
+        let keys = mesh
+            .surfaces()
+            .iter()
+            .map(|s| s.data().key())
+            .collect::<FxHashSet<_>>();
+        let linked = graph
+            .pair_iter()
+            .filter(|(handle, other)| {
+                *handle != node_handle
+                    && other.cast::<Mesh>().map_or(false, |other| {
+                        other
+                            .surfaces()
+                            .iter()
+                            .any(|s| keys.contains(&s.data().key()))
+                    })
+            })
+            .count();
+        if linked > 0 {
+            statistics += &format!("\nLinked With: {} mesh(es)", linked);
+        }
+
+        statistics
+    } else {
+        let type_name = node.as_reflect().type_name();
+        format!(
+            "Type: {}\nChildren: {}",
+            type_name.rsplit("::").next().unwrap_or(type_name),
+            node.children().len()
+        )
+    }
+}
+
 fn tree_node(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<Node> {
     if let Some(item) = ui.node(tree).cast::<SceneItem<Node>>() {
         return item.entity_handle;
@@ -126,6 +223,35 @@ fn tree_node(ui: &UserInterface, tree: Handle<UiNode>) -> Handle<Node> {
     unreachable!()
 }
 
+// Checks the node and its ancestors, because a disabled node disables its whole subtree.
+fn is_node_enabled(graph: &Graph, node_handle: Handle<Node>) -> bool {
+    let mut handle = node_handle;
+    while let Some(node) = graph.try_get(handle) {
+        if !node.is_enabled() {
+            return false;
+        }
+        handle = node.parent();
+    }
+    true
+}
+
+fn node_visibility(graph: &Graph, node_handle: Handle<Node>) -> SceneItemVisibility {
+    let node = &graph[node_handle];
+    if !node.visibility() {
+        return SceneItemVisibility::Hidden;
+    }
+
+    let mut parent = node.parent();
+    while let Some(parent_ref) = graph.try_get(parent) {
+        if !parent_ref.visibility() {
+            return SceneItemVisibility::HiddenByParent;
+        }
+        parent = parent_ref.parent();
+    }
+
+    SceneItemVisibility::Visible
+}
+
 fn colorize(handle: Handle<UiNode>, ui: &UserInterface, index: &mut usize) {
     let node = ui.node(handle);
 
@@ -186,8 +312,9 @@ impl WorldViewer {
         let scroll_view;
         let track_selection;
         let search_bar = SearchBar::new(ctx);
+        let type_filter_selector = TypeFilterSelector::new(ctx);
         let graph_folder = make_folder(ctx, "Scene Graph");
-        let window = WindowBuilder::new(WidgetBuilder::new())
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("WorldViewer"))
             .can_minimize(false)
             .with_title(WindowTitle::text("World Viewer"))
             .with_content(
@@ -230,7 +357,7 @@ impl WorldViewer {
                                             load_image(include_bytes!(
                                                 "../../resources/embed/locate.png"
                                             )),
-                                            "Locate Selection",
+                                            "Locate Selection - expand the tree to the selected node and scroll to it",
                                         );
                                         locate_selection
                                     })
@@ -256,10 +383,11 @@ impl WorldViewer {
                             .build(ctx),
                         )
                         .with_child(search_bar.container)
+                        .with_child(type_filter_selector.dropdown)
                         .with_child({
                             node_path = WrapPanelBuilder::new(
                                 WidgetBuilder::new()
-                                    .on_row(3)
+                                    .on_row(4)
                                     .with_vertical_alignment(VerticalAlignment::Top),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -267,7 +395,7 @@ impl WorldViewer {
                             node_path
                         })
                         .with_child({
-                            scroll_view = ScrollViewerBuilder::new(WidgetBuilder::new().on_row(2))
+                            scroll_view = ScrollViewerBuilder::new(WidgetBuilder::new().on_row(3))
                                 .with_content({
                                     tree_root = TreeRootBuilder::new(WidgetBuilder::new())
                                         .with_items(vec![graph_folder])
@@ -281,6 +409,7 @@ impl WorldViewer {
                 .add_column(Column::stretch())
                 .add_row(Row::strict(24.0))
                 .add_row(Row::strict(20.0))
+                .add_row(Row::strict(21.0))
                 .add_row(Row::stretch())
                 .add_row(Row::auto())
                 .build(ctx),
@@ -289,6 +418,9 @@ impl WorldViewer {
 
         let item_context_menu = ItemContextMenu::new(ctx);
 
+        let item_tooltip = make_simple_tooltip(ctx, "");
+        let item_tooltip_text = ctx[*item_tooltip].children()[0];
+
         Self {
             search_bar,
             track_selection,
@@ -306,14 +438,29 @@ impl WorldViewer {
             scroll_view,
             item_context_menu,
             node_to_view_map: Default::default(),
+            expanded_nodes: Default::default(),
             filter: Default::default(),
+            type_filter_selector,
+            type_filter: None,
             small_font,
+            item_tooltip,
+            item_tooltip_text,
         }
     }
 
     pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut GameEngine) {
         scope_profile!();
 
+        // Scripts of game plugins could be registered (or unregistered) after the world viewer
+        // was created.
+        if self.type_filter_selector.sync(
+            self.type_filter,
+            &engine.serialization_context,
+            &mut engine.user_interface,
+        ) {
+            self.type_filter = None;
+        }
+
         let scene = &mut engine.scenes[editor_scene.scene];
         let graph = &mut scene.graph;
         let ui = &mut engine.user_interface;
@@ -480,6 +627,8 @@ impl WorldViewer {
                                     child_handle,
                                     &mut ui.build_ctx(),
                                     self.item_context_menu.menu,
+                                    self.item_tooltip.clone(),
+                                    self.expanded_nodes.contains(&child_handle),
                                 );
                                 send_sync_message(
                                     ui,
@@ -502,6 +651,8 @@ impl WorldViewer {
                         node_handle,
                         &mut ui.build_ctx(),
                         self.item_context_menu.menu,
+                        self.item_tooltip.clone(),
+                        self.expanded_nodes.contains(&node_handle),
                     );
                     send_sync_message(
                         ui,
@@ -537,6 +688,26 @@ impl WorldViewer {
                         );
                     }
 
+                    let visibility = node_visibility(graph, item.entity_handle);
+                    if item.visibility() != visibility {
+                        send_sync_message(
+                            ui,
+                            SceneItemMessage::visibility(
+                                handle,
+                                MessageDirection::ToWidget,
+                                visibility,
+                            ),
+                        );
+                    }
+
+                    let enabled = is_node_enabled(graph, item.entity_handle);
+                    if item.is_enabled() != enabled {
+                        send_sync_message(
+                            ui,
+                            SceneItemMessage::enabled(handle, MessageDirection::ToWidget, enabled),
+                        );
+                    }
+
                     stack.extend_from_slice(&item.tree.items);
                 }
             } else if let Some(root) = ui_node.cast::<TreeRoot>() {
@@ -549,6 +720,11 @@ impl WorldViewer {
 
         self.colorize(ui);
 
+        // Newly added items must obey the filters too.
+        if self.is_filter_active() {
+            self.apply_filter(ui, graph);
+        }
+
         self.node_to_view_map
             .retain(|k, v| graph.is_valid_handle(*k) && ui.try_get_node(*v).is_some());
     }
@@ -558,36 +734,99 @@ impl WorldViewer {
         colorize(self.tree_root, ui, &mut index);
     }
 
-    fn apply_filter(&self, ui: &UserInterface) {
-        fn apply_filter_recursive(node: Handle<UiNode>, filter: &str, ui: &UserInterface) -> bool {
+    fn is_filter_active(&self) -> bool {
+        !self.filter.is_empty() || self.type_filter.is_some()
+    }
+
+    /// Shows only the items that match both the text filter and the type filter. Ancestors of
+    /// matching items stay visible, but dimmed, so the hierarchy can still be navigated.
+    fn apply_filter(&self, ui: &UserInterface, graph: &Graph) {
+        fn apply_filter_recursive(
+            node: Handle<UiNode>,
+            filter: &str,
+            type_filter: Option<TypeFilter>,
+            ui: &UserInterface,
+            graph: &Graph,
+        ) -> bool {
             let node_ref = ui.node(node);
 
             let mut is_any_match = false;
             for &child in node_ref.children() {
-                is_any_match |= apply_filter_recursive(child, filter, ui)
+                is_any_match |= apply_filter_recursive(child, filter, type_filter, ui, graph)
             }
 
-            let name = node_ref.cast::<SceneItem<Node>>().map(|i| i.name());
+            if let Some(item) = node_ref.cast::<SceneItem<Node>>() {
+                let is_match = item.name().to_lowercase().contains(filter)
+                    && type_filter.map_or(true, |type_filter| {
+                        graph
+                            .try_get(item.entity_handle)
+                            .map_or(false, |node| type_filter.matches(node))
+                    });
 
-            if let Some(name) = name {
-                is_any_match |= name.to_lowercase().contains(filter);
+                is_any_match |= is_match;
 
                 ui.send_message(WidgetMessage::visibility(
                     node,
                     MessageDirection::ToWidget,
                     is_any_match,
                 ));
+
+                if item.is_filter_match() != is_match {
+                    ui.send_message(SceneItemMessage::filter_match(
+                        node,
+                        MessageDirection::ToWidget,
+                        is_match,
+                    ));
+                }
             }
 
             is_any_match
         }
 
-        apply_filter_recursive(self.tree_root, &self.filter.to_lowercase(), ui);
+        apply_filter_recursive(
+            self.tree_root,
+            &self.filter.to_lowercase(),
+            self.type_filter,
+            ui,
+            graph,
+        );
     }
 
-    pub fn set_filter(&mut self, filter: String, ui: &UserInterface) {
+    pub fn set_filter(&mut self, filter: String, graph: Option<&Graph>, ui: &UserInterface) {
         self.filter = filter;
-        self.apply_filter(ui)
+        if let Some(graph) = graph {
+            self.apply_filter(ui, graph)
+        }
+    }
+
+    fn sync_item_tooltip(
+        &self,
+        hovered: Handle<UiNode>,
+        editor_scene: &EditorScene,
+        engine: &GameEngine,
+    ) {
+        let ui = &engine.user_interface;
+
+        if ui.try_get_node(hovered).is_none() {
+            return;
+        }
+
+        // Statistics is calculated only when an item is hovered, because it could be quite heavy
+        // for large meshes.
+        let item = ui.find_by_criteria_up(hovered, |n| n.cast::<SceneItem<Node>>().is_some());
+        if let Some(item) = ui
+            .try_get_node(item)
+            .and_then(|n| n.cast::<SceneItem<Node>>())
+        {
+            let graph = &engine.scenes[editor_scene.scene].graph;
+            if graph.is_valid_handle(item.entity_handle) {
+                ui.send_message(TextMessage::text(
+                    self.item_tooltip_text,
+                    MessageDirection::ToWidget,
+                    node_statistics(graph, item.entity_handle),
+                ));
+            }
+        }
     }
 
     pub fn handle_ui_message(
@@ -603,6 +842,29 @@ impl WorldViewer {
             .handle_ui_message(message, editor_scene, engine, &self.sender);
         self.search_bar
             .handle_ui_message(message, &engine.user_interface, &self.sender);
+        if let Some(type_filter) = self.type_filter_selector.handle_ui_message(message) {
+            self.type_filter = type_filter;
+            self.apply_filter(
+                &engine.user_interface,
+                &engine.scenes[editor_scene.scene].graph,
+            );
+        }
+
+        if let Some(&TreeMessage::Expand { expand, .. }) = message.data::<TreeMessage>() {
+            if message.direction() == MessageDirection::ToWidget {
+                if let Some(item) = engine
+                    .user_interface
+                    .try_get_node(message.destination())
+                    .and_then(|n| n.cast::<SceneItem<Node>>())
+                {
+                    if expand {
+                        self.expanded_nodes.insert(item.entity_handle);
+                    } else {
+                        self.expanded_nodes.remove(&item.entity_handle);
+                    }
+                }
+            }
+        }
 
         if let Some(TreeRootMessage::Selected(selection)) = message.data::<TreeRootMessage>() {
             if message.destination() == self.tree_root
@@ -610,8 +872,20 @@ impl WorldViewer {
             {
                 self.handle_selection(selection, editor_scene, engine);
             }
+        } else if let Some(&SceneItemMessage::ToggleVisibility { recursive }) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(item) = engine
+                    .user_interface
+                    .try_get_node(message.destination())
+                    .and_then(|n| n.cast::<SceneItem<Node>>())
+                {
+                    self.toggle_visibility(item.entity_handle, recursive, editor_scene, engine);
+                }
+            }
         } else if let Some(&WidgetMessage::Drop(node)) = message.data::<WidgetMessage>() {
             self.handle_drop(engine, editor_scene, message.destination(), node);
+        } else if let Some(WidgetMessage::MouseEnter) = message.data::<WidgetMessage>() {
+            self.sync_item_tooltip(message.destination(), editor_scene, engine);
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if let Some(&view) = self.breadcrumbs.get(&message.destination()) {
                 if let Some(graph_node) = engine
@@ -692,11 +966,23 @@ impl WorldViewer {
     }
 
     fn handle_selection(
-        &self,
+        &mut self,
         selection: &[Handle<UiNode>],
-        editor_scene: &EditorScene,
+        editor_scene: &mut EditorScene,
         engine: &Engine,
     ) {
+        if editor_scene.node_handle_picker.is_some() {
+            let picked = selection
+                .last()
+                .and_then(|item| engine.user_interface.node(*item).cast::<SceneItem<Node>>())
+                .map(|item| item.entity_handle)
+                .unwrap_or_default();
+            editor_scene.try_pick_node_handle(picked, &engine.user_interface);
+            // The tree has changed its selection already, bring it back.
+            self.sync_selection = true;
+            return;
+        }
+
         let mut new_selection = Selection::None;
         for selected_item in selection {
             let selected_item_ref = engine.user_interface.node(*selected_item);
@@ -728,6 +1014,55 @@ impl WorldViewer {
         }
     }
 
+    fn toggle_visibility(
+        &self,
+        node_handle: Handle<Node>,
+        recursive: bool,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        let mut nodes = Vec::new();
+        let mut stack = vec![node_handle];
+        while let Some(handle) = stack.pop() {
+            // Root cannot be hidden, otherwise editor objects will be hidden too.
+            if handle != graph.get_root() {
+                nodes.push(handle);
+            }
+            if recursive {
+                stack.extend(
+                    graph[handle]
+                        .children()
+                        .iter()
+                        .filter(|&&c| c != editor_scene.editor_objects_root),
+                );
+            }
+        }
+
+        let hidden = graph[node_handle].visibility();
+        if !hidden {
+            // Nodes that are invisible by their own visibility (not in the editor only) are
+            // shown using regular undoable commands, because it modifies the scene.
+            let invisible = nodes
+                .iter()
+                .filter(|n| !graph[**n].visibility() && !editor_scene.hidden_nodes.contains(*n))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !invisible.is_empty() {
+                self.sender
+                    .send(Message::do_scene_command(SetNodesVisibilityCommand::new(
+                        invisible, true,
+                    )))
+                    .unwrap();
+            }
+        }
+
+        self.sender
+            .send(Message::SetNodesHidden { nodes, hidden })
+            .unwrap();
+    }
+
     /// `target` - is a node at which `dropped` was dropped.
     /// `dropped` - is a node which was dropped at `target`.
     fn handle_drop(
@@ -820,6 +1155,7 @@ impl WorldViewer {
 
     pub fn clear(&mut self, ui: &UserInterface) {
         self.node_to_view_map.clear();
+        self.expanded_nodes.clear();
         self.clear_breadcrumbs(ui);
         ui.send_message(TreeMessage::set_items(
             self.graph_folder,
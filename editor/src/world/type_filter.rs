@@ -0,0 +1,190 @@
+use crate::{gui::make_dropdown_list_option, send_sync_message};
+use fyrox::{
+    core::{pool::Handle, uuid::Uuid},
+    engine::SerializationContext,
+    gui::{
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        message::{MessageDirection, UiMessage},
+        widget::WidgetBuilder,
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        camera::Camera,
+        collider::Collider,
+        decal::Decal,
+        dim2,
+        joint::Joint,
+        light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
+        mesh::Mesh,
+        node::{Node, TypeUuidProvider},
+        particle_system::ParticleSystem,
+        pivot::Pivot,
+        rigidbody::RigidBody,
+        sound::{listener::Listener, Sound},
+        sprite::Sprite,
+        terrain::Terrain,
+        trigger::TriggerVolume,
+    },
+};
+
+/// Restricts the world viewer to the nodes of a given type or to the nodes with a given script.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TypeFilter {
+    Node(Uuid),
+    Script(Uuid),
+}
+
+impl TypeFilter {
+    pub fn matches(&self, node: &Node) -> bool {
+        match self {
+            TypeFilter::Node(type_uuid) => node.id() == *type_uuid,
+            TypeFilter::Script(type_uuid) => node.script().map_or(false, |s| s.id() == *type_uuid),
+        }
+    }
+}
+
+fn node_types() -> Vec<(&'static str, Uuid)> {
+    vec![
+        ("Pivot", Pivot::type_uuid()),
+        ("Mesh", Mesh::type_uuid()),
+        ("Camera", Camera::type_uuid()),
+        ("Point Light", PointLight::type_uuid()),
+        ("Spot Light", SpotLight::type_uuid()),
+        ("Directional Light", DirectionalLight::type_uuid()),
+        ("Particle System", ParticleSystem::type_uuid()),
+        ("Sprite", Sprite::type_uuid()),
+        ("Decal", Decal::type_uuid()),
+        ("Terrain", Terrain::type_uuid()),
+        ("Sound", Sound::type_uuid()),
+        ("Listener", Listener::type_uuid()),
+        ("Rigid Body", RigidBody::type_uuid()),
+        ("Collider", Collider::type_uuid()),
+        ("Joint", Joint::type_uuid()),
+        ("Trigger Volume", TriggerVolume::type_uuid()),
+        ("Rectangle (2D)", dim2::rectangle::Rectangle::type_uuid()),
+        ("Rigid Body (2D)", dim2::rigidbody::RigidBody::type_uuid()),
+        ("Collider (2D)", dim2::collider::Collider::type_uuid()),
+        ("Joint (2D)", dim2::joint::Joint::type_uuid()),
+        ("Animation Player", AnimationPlayer::type_uuid()),
+        (
+            "Animation Blending State Machine",
+            AnimationBlendingStateMachine::type_uuid(),
+        ),
+    ]
+}
+
+/// A drop-down list with built-in node types and registered scripts. Scripts can be registered
+/// at any time (for example when a game plugin is reloaded), so the list is synced with the
+/// serialization context.
+pub struct TypeFilterSelector {
+    pub dropdown: Handle<UiNode>,
+    // The first item is always "Any Type".
+    filters: Vec<Option<TypeFilter>>,
+    script_types: Vec<Uuid>,
+}
+
+impl TypeFilterSelector {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let filters = Self::make_filters(&[]);
+        let items = Self::make_items(&[], ctx);
+        let dropdown = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_margin(Thickness::top(1.0)),
+        )
+        .with_items(items)
+        .with_selected(0)
+        .build(ctx);
+
+        Self {
+            dropdown,
+            filters,
+            script_types: Default::default(),
+        }
+    }
+
+    fn make_filters(scripts: &[(Uuid, String)]) -> Vec<Option<TypeFilter>> {
+        std::iter::once(None)
+            .chain(
+                node_types()
+                    .into_iter()
+                    .map(|(_, type_uuid)| Some(TypeFilter::Node(type_uuid))),
+            )
+            .chain(
+                scripts
+                    .iter()
+                    .map(|(type_uuid, _)| Some(TypeFilter::Script(*type_uuid))),
+            )
+            .collect()
+    }
+
+    fn make_items(scripts: &[(Uuid, String)], ctx: &mut BuildContext) -> Vec<Handle<UiNode>> {
+        std::iter::once("Any Type".to_owned())
+            .chain(
+                node_types()
+                    .into_iter()
+                    .map(|(name, _)| format!("Node: {}", name)),
+            )
+            .chain(scripts.iter().map(|(_, name)| format!("Script: {}", name)))
+            .map(|name| make_dropdown_list_option(ctx, &name))
+            .collect()
+    }
+
+    /// Rebuilds the list if the set of registered scripts has changed. Returns `true` if the
+    /// current filter was reset, because its script is no longer registered.
+    pub fn sync(
+        &mut self,
+        current: Option<TypeFilter>,
+        serialization_context: &SerializationContext,
+        ui: &mut UserInterface,
+    ) -> bool {
+        let scripts = serialization_context
+            .script_constructors
+            .map()
+            .iter()
+            .map(|(type_uuid, constructor)| (*type_uuid, constructor.name.clone()))
+            .collect::<Vec<_>>();
+
+        if scripts
+            .iter()
+            .map(|(type_uuid, _)| type_uuid)
+            .eq(self.script_types.iter())
+        {
+            return false;
+        }
+
+        self.script_types = scripts.iter().map(|(type_uuid, _)| *type_uuid).collect();
+        self.filters = Self::make_filters(&scripts);
+
+        let items = Self::make_items(&scripts, &mut ui.build_ctx());
+        send_sync_message(
+            ui,
+            DropdownListMessage::items(self.dropdown, MessageDirection::ToWidget, items),
+        );
+
+        let selected = self.filters.iter().position(|f| *f == current);
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.dropdown,
+                MessageDirection::ToWidget,
+                Some(selected.unwrap_or(0)),
+            ),
+        );
+
+        selected.is_none()
+    }
+
+    /// Returns a new filter if the user has selected one.
+    pub fn handle_ui_message(&self, message: &UiMessage) -> Option<Option<TypeFilter>> {
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.dropdown
+                && message.direction() == MessageDirection::FromWidget
+            {
+                return self.filters.get(*index).cloned();
+            }
+        }
+        None
+    }
+}
@@ -22,6 +22,7 @@ pub fn impl_reflect(ty_args: &args::TypeArgs) -> TokenStream2 {
             quote!(vec![]),
             None,
             quote!(vec![]),
+            None,
         );
     }
 
@@ -192,6 +193,7 @@ fn impl_reflect_struct(ty_args: &args::TypeArgs, field_args: &args::Fields) -> T
         fields_mut_body,
         set_field_body,
         metadata,
+        None,
     )
 }
 
@@ -338,6 +340,7 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
             quote!(vec![]),
             None,
             quote!(vec![]),
+            None,
         )
     } else {
         let field_body = quote! {
@@ -390,10 +393,55 @@ fn impl_reflect_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs
             fields_mut_body,
             None,
             fields_metadata_body,
+            Some(self::enum_variants_impl(variant_args)),
         )
     }
 }
 
+/// `Reflect::{variant_names, variant_index, variant_constructor}` for enums. Only variants
+/// without fields could be created by the variant constructor.
+fn enum_variants_impl(variant_args: &[args::VariantArgs]) -> TokenStream2 {
+    let names = variant_args.iter().map(|v| v.ident.to_string());
+
+    let index_arms = variant_args.iter().enumerate().map(|(i, v)| {
+        let ident = &v.ident;
+        match v.fields.style {
+            ast::Style::Struct => quote!(Self::#ident { .. } => #i),
+            ast::Style::Tuple => quote!(Self::#ident(..) => #i),
+            ast::Style::Unit => quote!(Self::#ident => #i),
+        }
+    });
+
+    let constructor_arms = variant_args
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| matches!(v.fields.style, ast::Style::Unit))
+        .map(|(i, v)| {
+            let ident = &v.ident;
+            quote!(#i => Some(Box::new(Self::#ident) as Box<dyn Reflect>))
+        });
+
+    quote! {
+        fn variant_names(&self) -> Option<&'static [&'static str]> {
+            Some(&[#(#names),*])
+        }
+
+        fn variant_index(&self) -> Option<usize> {
+            Some(match self {
+                #(#index_arms,)*
+            })
+        }
+
+        fn variant_constructor(&self) -> Option<fn(usize) -> Option<Box<dyn Reflect>>> {
+            let constructor: fn(usize) -> Option<Box<dyn Reflect>> = |index| match index {
+                #(#constructor_arms,)*
+                _ => None,
+            };
+            Some(constructor)
+        }
+    }
+}
+
 fn gen_impl(
     ty_args: &args::TypeArgs,
     field: TokenStream2,
@@ -402,6 +450,7 @@ fn gen_impl(
     fields_mut: TokenStream2,
     set_field: Option<TokenStream2>,
     metadata: TokenStream2,
+    variants: Option<TokenStream2>,
 ) -> TokenStream2 {
     let ty_ident = &ty_args.ident;
     let generics = ty_args.impl_generics();
@@ -479,6 +528,8 @@ fn gen_impl(
             #as_array_impl
 
             #as_list_impl
+
+            #variants
         }
     }
 }
@@ -281,6 +281,36 @@ fn reflect_fields_list_of_enum() {
     );
 }
 
+#[test]
+fn reflect_enum_variants() {
+    #[derive(Reflect, Debug, PartialEq)]
+    enum Foo {
+        Bar,
+        Baz(u32),
+        Qux { field: f32 },
+        Quux,
+    }
+
+    let value = Foo::Qux { field: 1.0 };
+
+    assert_eq!(
+        value.variant_names(),
+        Some(["Bar", "Baz", "Qux", "Quux"].as_slice())
+    );
+    assert_eq!(value.variant_index(), Some(2));
+    assert_eq!(Foo::Baz(1).variant_index(), Some(1));
+
+    let constructor = value.variant_constructor().unwrap();
+    assert_eq!(
+        constructor(3).and_then(|v| v.take::<Foo>().ok()),
+        Some(Foo::Quux)
+    );
+    assert!(constructor(1).is_none());
+    assert!(constructor(4).is_none());
+
+    assert!(Tuple(0, 0).variant_names().is_none());
+}
+
 fn default_prop() -> FieldInfo<'static> {
     FieldInfo {
         owner_type_id: TypeId::of::<()>(),
@@ -40,6 +40,42 @@ pub fn print_hot_path() -> Result<String, fmt::Error> {
     }
 }
 
+/// Returns `true` if the profiler gathers data, see the crate feature "enable_profiler".
+pub fn is_enabled() -> bool {
+    cfg!(feature = "enable_profiler")
+}
+
+/// Statistics of a single scope. Statistics of the same scope that was entered from different
+/// places (parent scopes) are merged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScopeStatistics {
+    /// Full name of the function that contains the scope.
+    pub function_name: &'static str,
+    /// Line of the scope in the source file.
+    pub line: u32,
+    /// How many times the scope was entered.
+    pub count: u64,
+    /// Total time (in seconds) spent in the scope, including nested scopes.
+    pub total_time: f64,
+    /// Time (in seconds) spent in the scope itself, excluding nested scopes.
+    pub self_time: f64,
+}
+
+/// Returns statistics of every scope that was entered since the start of profiling. The data is
+/// the same that is used by [`print`], it is cumulative, so take a difference of two snapshots to
+/// get statistics of a time interval. Returns an empty vector if the profiler is disabled.
+pub fn scope_statistics() -> Vec<ScopeStatistics> {
+    #[cfg(feature = "enable_profiler")]
+    {
+        PROFILER.lock().unwrap().scope_statistics()
+    }
+
+    #[cfg(not(feature = "enable_profiler"))]
+    {
+        Vec::new()
+    }
+}
+
 struct Sample {
     count: u64,
     time: f64,
@@ -124,6 +160,39 @@ impl Profiler {
         self.samples.get_mut(&scope).unwrap().collect(elapsed);
     }
 
+    fn scope_statistics(&self) -> Vec<ScopeStatistics> {
+        let mut statistics = FxHashMap::<(&'static str, u32), ScopeStatistics>::default();
+
+        for (scope_mark, sample) in self.samples.iter() {
+            if scope_mark == &ENTRY_SCOPE_MARK {
+                continue;
+            }
+
+            let children_time = sample
+                .children
+                .iter()
+                .filter_map(|child| self.samples.get(child))
+                .map(|child| child.time)
+                .sum::<f64>();
+
+            let entry = statistics
+                .entry((scope_mark.function_name, scope_mark.line))
+                .or_insert_with(|| ScopeStatistics {
+                    function_name: scope_mark.function_name,
+                    line: scope_mark.line,
+                    count: 0,
+                    total_time: 0.0,
+                    self_time: 0.0,
+                });
+
+            entry.count += sample.count;
+            entry.total_time += sample.time;
+            entry.self_time += (sample.time - children_time).max(0.0);
+        }
+
+        statistics.into_values().collect()
+    }
+
     fn print(&self, buffer: &mut String) -> fmt::Result {
         let full_time = (std::time::Instant::now() - self.start_time).as_secs_f64();
         self.recursive_print(buffer, &ENTRY_SCOPE_MARK, 0, full_time)?;
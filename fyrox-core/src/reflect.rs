@@ -128,6 +128,10 @@ impl<'a> PartialEq<Self> for FieldInfo<'a> {
     }
 }
 
+/// Creates a new value of an enum with the variant at the given index, or returns `None` if the
+/// variant can't be created without additional data (it has fields).
+pub type VariantConstructor = fn(usize) -> Option<Box<dyn Reflect>>;
+
 /// Trait for runtime reflection
 ///
 /// Derive macro is available.
@@ -216,6 +220,24 @@ pub trait Reflect: Any + Debug {
     fn as_inheritable_variable_mut(&mut self) -> Option<&mut dyn ReflectInheritableVariable> {
         None
     }
+
+    /// Returns names of all variants, if `self` is an enum. Derive macro implements it for every
+    /// enum.
+    fn variant_names(&self) -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// Returns index of the current variant (in [`Reflect::variant_names`] list), if `self` is an
+    /// enum.
+    fn variant_index(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns a function that creates new values of the enum by variant index, if `self` is an
+    /// enum. Only variants without fields could be created this way.
+    fn variant_constructor(&self) -> Option<VariantConstructor> {
+        None
+    }
 }
 
 /// [`Reflect`] sub trait for working with slices.
@@ -696,6 +718,18 @@ macro_rules! delegate_reflect {
         fn as_list_mut(&mut self) -> Option<&mut dyn ReflectList> {
             self.deref_mut().as_list_mut()
         }
+
+        fn variant_names(&self) -> Option<&'static [&'static str]> {
+            self.deref().variant_names()
+        }
+
+        fn variant_index(&self) -> Option<usize> {
+            self.deref().variant_index()
+        }
+
+        fn variant_constructor(&self) -> Option<VariantConstructor> {
+            self.deref().variant_constructor()
+        }
     };
 }
 
@@ -10,7 +10,7 @@ use std::{
 
 use crate::{
     delegate_reflect,
-    reflect::{blank_reflect, prelude::*, ReflectArray, ReflectList},
+    reflect::{blank_reflect, prelude::*, ReflectArray, ReflectList, VariantConstructor},
     uuid::Uuid,
 };
 use fyrox_core_derive::impl_reflect;
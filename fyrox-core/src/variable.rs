@@ -3,7 +3,9 @@
 //! For more info see [`InheritableVariable`]
 
 use crate::{
-    reflect::{prelude::*, ReflectArray, ReflectInheritableVariable, ReflectList},
+    reflect::{
+        prelude::*, ReflectArray, ReflectInheritableVariable, ReflectList, VariantConstructor,
+    },
     visitor::prelude::*,
 };
 use bitflags::bitflags;
@@ -324,6 +326,18 @@ where
         self.value.as_list_mut()
     }
 
+    fn variant_names(&self) -> Option<&'static [&'static str]> {
+        self.value.variant_names()
+    }
+
+    fn variant_index(&self) -> Option<usize> {
+        self.value.variant_index()
+    }
+
+    fn variant_constructor(&self) -> Option<VariantConstructor> {
+        self.value.variant_constructor()
+    }
+
     fn as_inheritable_variable(&self) -> Option<&dyn ReflectInheritableVariable> {
         Some(self)
     }
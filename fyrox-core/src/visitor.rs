@@ -44,6 +44,7 @@ use std::{
 };
 use uuid::Uuid;
 
+#[derive(Clone)]
 pub enum FieldKind {
     Bool(bool),
     U8(u8),
@@ -507,6 +508,7 @@ impl<'a> Visit for Data<'a> {
     }
 }
 
+#[derive(Clone)]
 pub struct Field {
     name: String,
     kind: FieldKind,
@@ -640,6 +642,14 @@ impl Field {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> &FieldKind {
+        &self.kind
+    }
+
     fn save(field: &Field, file: &mut dyn Write) -> VisitResult {
         fn write_vec_n<T, const N: usize>(
             file: &mut dyn Write,
@@ -1025,6 +1035,26 @@ impl Node {
             children: Vec::new(),
         }
     }
+
+    /// Returns name of the region.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns fields of the region.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Returns fields of the region, it could be used to change serialized data directly.
+    pub fn fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.fields
+    }
+
+    /// Returns handles of the nested regions.
+    pub fn children(&self) -> &[Handle<Node>] {
+        &self.children
+    }
 }
 
 impl Default for Node {
@@ -1077,6 +1107,39 @@ pub trait Visit {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult;
 }
 
+/// A type with versioned serialized representation. Every change of the format of such type must
+/// increase [`Versioned::VERSION`] and describe how to convert the data of the previous version in
+/// [`Versioned::migrate_from`], so files saved by older versions of the engine can still be loaded.
+///
+/// Typical `Visit` implementation of a versioned type looks like this:
+///
+/// ```rust,ignore
+/// fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+///     let mut region = visitor.enter_region(name)?;
+///     let version = region.visit_version::<Self>()?;
+///
+///     // Visit the fields, fields that were added in newer versions are optional.
+///
+///     if region.is_reading() && version < Self::VERSION {
+///         self.migrate_from(version)?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub trait Versioned {
+    /// Current version of the format.
+    const VERSION: u32;
+
+    /// Converts the data that was just read in an older format to the current one, for example
+    /// fills new fields with values that preserve old behaviour. `version` is always less than
+    /// [`Versioned::VERSION`], data that was saved before the versioning was introduced has
+    /// version `0`.
+    fn migrate_from(&mut self, _version: u32) -> VisitResult {
+        Ok(())
+    }
+}
+
 impl Default for Visitor {
     fn default() -> Self {
         Self::new()
@@ -1112,6 +1175,33 @@ impl Visitor {
         self.reading
     }
 
+    /// Writes the current version of the format of `T` in the current region, or reads the version
+    /// of the data. Data without version (saved before the versioning was introduced) has version
+    /// `0`. Data of newer versions cannot be read, because there is no way to migrate it back.
+    pub fn visit_version<T: Versioned>(&mut self) -> Result<u32, VisitError> {
+        let mut version = T::VERSION;
+
+        if self.reading {
+            match version.visit("Version", self) {
+                Ok(_) => {
+                    if version > T::VERSION {
+                        return Err(VisitError::User(format!(
+                            "Data version {} is newer than the supported version {}!",
+                            version,
+                            T::VERSION
+                        )));
+                    }
+                }
+                Err(VisitError::FieldDoesNotExist(_)) => version = 0,
+                Err(e) => return Err(e),
+            }
+        } else {
+            version.visit("Version", self)?;
+        }
+
+        Ok(version)
+    }
+
     fn current_node(&mut self) -> &mut Node {
         self.nodes.borrow_mut(self.current_node)
     }
@@ -1159,6 +1249,62 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Returns handle of the root region. The root region and [`Self::node`] allow to inspect (and
+    /// fix) serialized data without knowing its types, which is useful for tools that repair
+    /// broken files.
+    pub fn root(&self) -> Handle<Node> {
+        self.root
+    }
+
+    /// Returns a reference to the region with the given handle.
+    pub fn node(&self, handle: Handle<Node>) -> Option<&Node> {
+        self.nodes.try_borrow(handle)
+    }
+
+    /// Returns a mutable reference to the region with the given handle.
+    pub fn node_mut(&mut self, handle: Handle<Node>) -> Option<&mut Node> {
+        self.nodes.try_borrow_mut(handle)
+    }
+
+    /// Removes the region with all its nested regions. The root region cannot be removed.
+    pub fn remove_node(&mut self, handle: Handle<Node>) {
+        if handle == self.root || !self.nodes.is_valid_handle(handle) {
+            return;
+        }
+
+        let parent = self.nodes.borrow(handle).parent;
+        if let Some(parent) = self.nodes.try_borrow_mut(parent) {
+            parent.children.retain(|child| *child != handle);
+        }
+
+        let mut stack = vec![handle];
+        while let Some(handle) = stack.pop() {
+            stack.extend(self.nodes.free(handle).children);
+        }
+    }
+
+    /// Copies the region (with all its nested regions) from another visitor and attaches it to
+    /// the given parent region. Returns handle of the copy.
+    pub fn copy_node_from(
+        &mut self,
+        parent: Handle<Node>,
+        source: &Visitor,
+        source_handle: Handle<Node>,
+    ) -> Handle<Node> {
+        let source_node = source.nodes.borrow(source_handle);
+
+        let mut node = Node::new(&source_node.name, parent);
+        node.fields = source_node.fields.clone();
+        let handle = self.nodes.spawn(node);
+        self.nodes.borrow_mut(parent).children.push(handle);
+
+        for &child in source_node.children.iter() {
+            self.copy_node_from(handle, source, child);
+        }
+
+        handle
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
@@ -1803,7 +1949,7 @@ impl<T: Visit> Visit for Range<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{Data, Visit, VisitResult, Visitor};
+    use crate::visitor::{Data, Versioned, Visit, VisitResult, Visitor};
     use std::{fs::File, io::Write, path::Path, rc::Rc};
 
     #[derive(Visit, Default)]
@@ -1907,4 +2053,110 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[derive(Default)]
+    struct VersionedData {
+        value: u32,
+        // Added in version 1, data of version 0 must be treated as if it was `10`.
+        scale: u32,
+        migrated_from: Option<u32>,
+    }
+
+    impl Versioned for VersionedData {
+        const VERSION: u32 = 1;
+
+        fn migrate_from(&mut self, version: u32) -> VisitResult {
+            if version < 1 {
+                self.scale = 10;
+            }
+            self.migrated_from = Some(version);
+            Ok(())
+        }
+    }
+
+    impl Visit for VersionedData {
+        fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+            let mut region = visitor.enter_region(name)?;
+            let version = region.visit_version::<Self>()?;
+
+            self.value.visit("Value", &mut region)?;
+            if version >= 1 {
+                self.scale.visit("Scale", &mut region)?;
+            }
+
+            if region.is_reading() && version < Self::VERSION {
+                self.migrate_from(version)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn versioned_data_migration() {
+        // Data saved before the versioning was introduced.
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Data").unwrap();
+            let mut value = 5u32;
+            value.visit("Value", &mut region).unwrap();
+        }
+        visitor.reading = true;
+
+        let mut data = VersionedData::default();
+        data.visit("Data", &mut visitor).unwrap();
+        assert_eq!(data.value, 5);
+        assert_eq!(data.scale, 10);
+        assert_eq!(data.migrated_from, Some(0));
+
+        // Data of the current version must be read as is.
+        let mut visitor = Visitor::new();
+        let mut data = VersionedData {
+            value: 5,
+            scale: 3,
+            migrated_from: None,
+        };
+        data.visit("Data", &mut visitor).unwrap();
+        visitor.reading = true;
+
+        let mut data = VersionedData::default();
+        data.visit("Data", &mut visitor).unwrap();
+        assert_eq!(data.scale, 3);
+        assert_eq!(data.migrated_from, None);
+    }
+
+    #[test]
+    fn edit_regions() {
+        let mut visitor = Visitor::new();
+        Model { data: 1 }.visit("First", &mut visitor).unwrap();
+        Model { data: 2 }.visit("Second", &mut visitor).unwrap();
+
+        let mut other = Visitor::new();
+        Model { data: 3 }.visit("Third", &mut other).unwrap();
+
+        let root = visitor.root();
+        let first = visitor.node(root).unwrap().children()[0];
+        assert_eq!(visitor.node(first).unwrap().name(), "First");
+        assert_eq!(visitor.node(first).unwrap().fields()[0].name(), "Data");
+        visitor.remove_node(first);
+        assert!(visitor.node(first).is_none());
+
+        let third = other.node(other.root()).unwrap().children()[0];
+        visitor.copy_node_from(root, &other, third);
+
+        let names = visitor
+            .node(root)
+            .unwrap()
+            .children()
+            .iter()
+            .map(|c| visitor.node(*c).unwrap().name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Second", "Third"]);
+
+        visitor.reading = true;
+        let mut model = Model::default();
+        model.visit("Third", &mut visitor).unwrap();
+        assert_eq!(model.data, 3);
+        assert!(Model::default().visit("First", &mut visitor).is_err());
+    }
 }
@@ -16,6 +16,7 @@ use crate::{
     window::{Window, WindowMessage},
     BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
@@ -835,6 +836,156 @@ impl Tile {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum DockingManagerMessage {
+    /// Replaces current arrangement of tiles and floating windows with the given one.
+    Layout(DockingManagerLayoutDescriptor),
+}
+
+impl DockingManagerMessage {
+    define_constructor!(DockingManagerMessage:Layout => fn layout(DockingManagerLayoutDescriptor), layout: false);
+}
+
+/// Serializable description of a tile content. Windows are referenced by their names, so every
+/// window that should be restored must have a unique name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TileContentDescriptor {
+    Empty,
+    Window(String),
+    VerticalTiles {
+        splitter: f32,
+        tiles: [Box<TileContentDescriptor>; 2],
+    },
+    HorizontalTiles {
+        splitter: f32,
+        tiles: [Box<TileContentDescriptor>; 2],
+    },
+}
+
+impl TileContentDescriptor {
+    fn from_tile(tile: Handle<UiNode>, ui: &UserInterface) -> Self {
+        let content = match ui.try_get_node(tile).and_then(|n| n.cast::<Tile>()) {
+            Some(tile) => &tile.content,
+            None => return Self::Empty,
+        };
+
+        match *content {
+            TileContent::Empty => Self::Empty,
+            TileContent::Window(window) => match ui.try_get_node(window) {
+                Some(window) if !window.name().is_empty() => Self::Window(window.name().to_owned()),
+                _ => Self::Empty,
+            },
+            TileContent::VerticalTiles { splitter, tiles } => Self::VerticalTiles {
+                splitter,
+                tiles: [
+                    Box::new(Self::from_tile(tiles[0], ui)),
+                    Box::new(Self::from_tile(tiles[1], ui)),
+                ],
+            },
+            TileContent::HorizontalTiles { splitter, tiles } => Self::HorizontalTiles {
+                splitter,
+                tiles: [
+                    Box::new(Self::from_tile(tiles[0], ui)),
+                    Box::new(Self::from_tile(tiles[1], ui)),
+                ],
+            },
+        }
+    }
+
+    /// Builds tiles for the content. Windows that cannot be found are skipped and split tiles with
+    /// only one non-empty side collapse to that side, so `None` means that there's nothing to show.
+    fn build(
+        &self,
+        ui: &mut UserInterface,
+        used_windows: &mut Vec<Handle<UiNode>>,
+    ) -> Option<TileContent> {
+        match self {
+            TileContentDescriptor::Empty => None,
+            TileContentDescriptor::Window(name) => {
+                let window = find_window_by_name(ui, name);
+                if window.is_some() && !used_windows.contains(&window) {
+                    used_windows.push(window);
+                    Some(TileContent::Window(window))
+                } else {
+                    None
+                }
+            }
+            TileContentDescriptor::VerticalTiles { splitter, tiles }
+            | TileContentDescriptor::HorizontalTiles { splitter, tiles } => {
+                match (
+                    tiles[0].build(ui, used_windows),
+                    tiles[1].build(ui, used_windows),
+                ) {
+                    (Some(first), Some(second)) => {
+                        let tiles = [
+                            TileBuilder::new(WidgetBuilder::new())
+                                .with_content(first)
+                                .build(&mut ui.build_ctx()),
+                            TileBuilder::new(WidgetBuilder::new())
+                                .with_content(second)
+                                .build(&mut ui.build_ctx()),
+                        ];
+                        let splitter = splitter.clamp(0.0, 1.0);
+                        Some(if let TileContentDescriptor::VerticalTiles { .. } = self {
+                            TileContent::VerticalTiles { splitter, tiles }
+                        } else {
+                            TileContent::HorizontalTiles { splitter, tiles }
+                        })
+                    }
+                    (Some(content), None) | (None, Some(content)) => Some(content),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+}
+
+/// Serializable description of a floating (undocked) window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FloatingWindowDescriptor {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub is_open: bool,
+}
+
+/// Serializable description of the whole docking manager layout, it could be obtained by
+/// [`DockingManager::layout`] and applied back by [`DockingManagerMessage::Layout`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DockingManagerLayoutDescriptor {
+    pub root_tile: TileContentDescriptor,
+    pub floating_windows: Vec<FloatingWindowDescriptor>,
+}
+
+fn find_window_by_name(ui: &UserInterface, name: &str) -> Handle<UiNode> {
+    ui.nodes()
+        .pair_iter()
+        .find(|(_, node)| node.name() == name && node.cast::<Window>().is_some())
+        .map(|(handle, _)| handle)
+        .unwrap_or_default()
+}
+
+fn collect_docked_windows(
+    tile: Handle<UiNode>,
+    ui: &UserInterface,
+    windows: &mut Vec<Handle<UiNode>>,
+) {
+    if let Some(tile) = ui.try_get_node(tile).and_then(|n| n.cast::<Tile>()) {
+        match tile.content {
+            TileContent::Empty => {}
+            TileContent::Window(window) => windows.push(window),
+            TileContent::VerticalTiles { tiles, .. }
+            | TileContent::HorizontalTiles { tiles, .. } => {
+                for tile in tiles {
+                    collect_docked_windows(tile, ui, windows);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct DockingManager {
     pub widget: Widget,
@@ -858,6 +1009,15 @@ impl Control for DockingManager {
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
+
+        if let Some(DockingManagerMessage::Layout(layout)) = message.data::<DockingManagerMessage>()
+        {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.set_layout(ui, layout);
+            }
+        }
     }
 
     fn preview_message(&self, _ui: &UserInterface, message: &mut UiMessage) {
@@ -874,6 +1034,140 @@ impl Control for DockingManager {
     }
 }
 
+impl DockingManager {
+    fn root_tile(&self, ui: &UserInterface) -> Option<Handle<UiNode>> {
+        self.children()
+            .iter()
+            .cloned()
+            .find(|&child| ui.node(child).cast::<Tile>().is_some())
+    }
+
+    /// Returns serializable description of current arrangement of tiles and floating windows.
+    /// Only named windows are included in the description.
+    pub fn layout(&self, ui: &UserInterface) -> DockingManagerLayoutDescriptor {
+        DockingManagerLayoutDescriptor {
+            root_tile: self
+                .root_tile(ui)
+                .map(|tile| TileContentDescriptor::from_tile(tile, ui))
+                .unwrap_or(TileContentDescriptor::Empty),
+            floating_windows: self
+                .floating_windows
+                .borrow()
+                .iter()
+                .filter_map(|&window| {
+                    let window = ui.try_get_node(window)?;
+                    if window.name().is_empty() {
+                        return None;
+                    }
+                    let position = window.desired_local_position();
+                    Some(FloatingWindowDescriptor {
+                        name: window.name().to_owned(),
+                        x: position.x,
+                        y: position.y,
+                        width: window.width(),
+                        height: window.height(),
+                        is_open: window.visibility(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn set_layout(&mut self, ui: &mut UserInterface, layout: &DockingManagerLayoutDescriptor) {
+        // Detach every window from current tiles first, otherwise the windows will be destroyed
+        // together with the tiles.
+        let mut windows = self.floating_windows.borrow().clone();
+        let old_root_tile = self.root_tile(ui);
+        if let Some(old_root_tile) = old_root_tile {
+            collect_docked_windows(old_root_tile, ui, &mut windows);
+        }
+        let root = ui.root();
+        for &window in windows.iter() {
+            if ui.try_get_node(window).is_some() {
+                ui.build_ctx().link(window, root);
+            }
+        }
+        if let Some(old_root_tile) = old_root_tile {
+            ui.send_message(WidgetMessage::remove(
+                old_root_tile,
+                MessageDirection::ToWidget,
+            ));
+        }
+
+        let mut used_windows = Vec::new();
+        let content = layout
+            .root_tile
+            .build(ui, &mut used_windows)
+            .unwrap_or(TileContent::Empty);
+        let root_tile = TileBuilder::new(WidgetBuilder::new())
+            .with_content(content)
+            .build(&mut ui.build_ctx());
+        ui.send_message(WidgetMessage::link(
+            root_tile,
+            MessageDirection::ToWidget,
+            self.handle(),
+        ));
+        for &window in used_windows.iter() {
+            ui.send_message(WindowMessage::open(
+                window,
+                MessageDirection::ToWidget,
+                false,
+            ));
+        }
+
+        let mut floating_windows = Vec::new();
+        for descriptor in layout.floating_windows.iter() {
+            let window = find_window_by_name(ui, &descriptor.name);
+            if window.is_none() || used_windows.contains(&window) {
+                continue;
+            }
+            ui.send_message(WindowMessage::can_resize(
+                window,
+                MessageDirection::ToWidget,
+                true,
+            ));
+            ui.send_message(WidgetMessage::desired_position(
+                window,
+                MessageDirection::ToWidget,
+                Vector2::new(descriptor.x, descriptor.y),
+            ));
+            ui.send_message(WidgetMessage::width(
+                window,
+                MessageDirection::ToWidget,
+                descriptor.width,
+            ));
+            ui.send_message(WidgetMessage::height(
+                window,
+                MessageDirection::ToWidget,
+                descriptor.height,
+            ));
+            ui.send_message(if descriptor.is_open {
+                WindowMessage::open(window, MessageDirection::ToWidget, false)
+            } else {
+                WindowMessage::close(window, MessageDirection::ToWidget)
+            });
+            used_windows.push(window);
+            floating_windows.push(window);
+        }
+
+        // Windows that are not mentioned in the layout become closed floating windows, so they
+        // still can be opened later.
+        for window in windows {
+            if !used_windows.contains(&window) && ui.try_get_node(window).is_some() {
+                ui.send_message(WindowMessage::can_resize(
+                    window,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(WindowMessage::close(window, MessageDirection::ToWidget));
+                floating_windows.push(window);
+            }
+        }
+
+        *self.floating_windows.borrow_mut() = floating_windows;
+    }
+}
+
 pub struct DockingManagerBuilder {
     widget_builder: WidgetBuilder,
     floating_windows: Vec<Handle<UiNode>>,
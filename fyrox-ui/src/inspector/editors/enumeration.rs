@@ -1,6 +1,7 @@
 use crate::{
     border::BorderBuilder,
-    core::pool::Handle,
+    brush::Brush,
+    core::{color::Color, pool::Handle},
     decorator::DecoratorBuilder,
     define_constructor,
     dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
@@ -29,7 +30,7 @@ use std::{
 };
 use strum::VariantNames;
 
-const LOCAL_SYNC_FLAG: u64 = 0xFF;
+pub(crate) const LOCAL_SYNC_FLAG: u64 = 0xFF;
 
 pub trait InspectableEnum: Debug + Reflect + Clone + 'static {}
 
@@ -151,11 +152,14 @@ impl<T: InspectableEnum> Control for EnumPropertyEditor<T> {
             if let Some(DropdownListMessage::SelectionChanged(Some(index))) =
                 message.data::<DropdownListMessage>()
             {
-                ui.send_message(EnumPropertyEditorMessage::variant(
-                    self.handle,
-                    MessageDirection::ToWidget,
-                    *index,
-                ));
+                // Unknown value item can't be selected.
+                if *index < (self.definition.names_generator)().len() {
+                    ui.send_message(EnumPropertyEditorMessage::variant(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        *index,
+                    ));
+                }
             }
         }
     }
@@ -264,8 +268,51 @@ impl EnumPropertyEditorBuilder {
     }
 }
 
+/// Creates items for a variant selector of an enum property editor. Unknown value (the one that
+/// does not match any of the variants) is added as the last item, highlighted with red color.
+pub fn make_variant_selector_items(
+    ctx: &mut BuildContext,
+    names: Vec<String>,
+    unknown_value: Option<String>,
+) -> Vec<Handle<UiNode>> {
+    let make_item = |ctx: &mut BuildContext, name: String, foreground: Option<Brush>| {
+        let mut text_builder = WidgetBuilder::new();
+        if let Some(foreground) = foreground {
+            text_builder = text_builder.with_foreground(foreground);
+        }
+
+        DecoratorBuilder::new(BorderBuilder::new(
+            WidgetBuilder::new().with_child(
+                TextBuilder::new(text_builder)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                    .with_text(name)
+                    .build(ctx),
+            ),
+        ))
+        .build(ctx)
+    };
+
+    let mut items = names
+        .into_iter()
+        .map(|name| make_item(ctx, name, None))
+        .collect::<Vec<_>>();
+
+    if let Some(unknown_value) = unknown_value {
+        items.push(make_item(
+            ctx,
+            format!("Unknown: {}", unknown_value),
+            Some(Brush::Solid(Color::opaque(230, 60, 60))),
+        ));
+    }
+
+    items
+}
+
 pub struct EnumPropertyEditorDefinition<T: InspectableEnum> {
     pub variant_generator: fn(usize) -> T,
+    /// Returns index of the variant of a value. Index that is out of bounds of the names list
+    /// means that the value is unknown, such values are shown in the editor, but highlighted.
     pub index_generator: fn(&T) -> usize,
     pub names_generator: fn() -> Vec<String>,
 }
@@ -298,7 +345,7 @@ where
                 T::VARIANTS
                     .iter()
                     .position(|v| v == &in_var.as_ref())
-                    .unwrap()
+                    .unwrap_or(T::VARIANTS.len())
             },
             names_generator: || T::VARIANTS.iter().map(|v| v.to_string()).collect(),
         }
@@ -338,30 +385,21 @@ where
     ) -> Result<PropertyEditorInstance, InspectorError> {
         let value = ctx.property_info.cast_value::<T>()?;
         let names = (self.names_generator)();
+        let variant_count = names.len();
+        let variant_index = (self.index_generator)(value);
+        let unknown_value = (variant_index >= variant_count).then(|| format!("{:?}", value));
 
         let variant_selector = DropdownListBuilder::new(
             WidgetBuilder::new()
                 .on_column(1)
                 .with_margin(Thickness::uniform(1.0)),
         )
-        .with_selected((self.index_generator)(value))
-        .with_items(
-            names
-                .into_iter()
-                .map(|name| {
-                    DecoratorBuilder::new(BorderBuilder::new(
-                        WidgetBuilder::new().with_child(
-                            TextBuilder::new(WidgetBuilder::new())
-                                .with_vertical_text_alignment(VerticalAlignment::Center)
-                                .with_horizontal_text_alignment(HorizontalAlignment::Center)
-                                .with_text(name)
-                                .build(ctx.build_context),
-                        ),
-                    ))
-                    .build(ctx.build_context)
-                })
-                .collect::<Vec<_>>(),
-        )
+        .with_selected(variant_index.min(variant_count))
+        .with_items(make_variant_selector_items(
+            ctx.build_context,
+            names,
+            unknown_value,
+        ))
         .with_close_on_selection(true)
         .build(ctx.build_context);
 
@@ -405,7 +443,7 @@ where
             .cast::<DropdownList>()
             .expect("Must be a DropDownList");
 
-        let variant_index = (self.index_generator)(value);
+        let variant_index = (self.index_generator)(value).min((self.names_generator)().len());
         if Some(variant_index) != variant_selector_ref.selection() {
             let environment = ctx
                 .ui
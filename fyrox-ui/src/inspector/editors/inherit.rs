@@ -1,7 +1,11 @@
 //! Property editor for [`InheritableVariable`]. It acts like a proxy to inner property, but also
-//! adds special "revert" button that is used to revert value to its parent's value.
+//! adds special "revert" button that is used to revert value to its parent's value. Modified
+//! properties are also highlighted with a thin marker on the left side of the editor. Optional
+//! "reset" button is shown when a user of the editor tells whether the property has its default
+//! value (see [`InheritablePropertyEditorMessage::IsDefault`]).
 
 use crate::{
+    border::BorderBuilder,
     button::{ButtonBuilder, ButtonMessage},
     core::{pool::Handle, reflect::prelude::*, variable::InheritableVariable},
     define_constructor,
@@ -17,8 +21,8 @@ use crate::{
     message::UiMessage,
     utils::make_simple_tooltip,
     widget::WidgetBuilder,
-    BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, VerticalAlignment,
-    Widget, WidgetMessage,
+    BuildContext, Control, HorizontalAlignment, MessageDirection, Thickness, UiNode, UserInterface,
+    VerticalAlignment, Widget, WidgetMessage, BRUSH_BRIGHT_BLUE,
 };
 use fyrox_core::reflect::FieldValue;
 use std::{
@@ -32,20 +36,36 @@ use std::{
 pub enum InheritablePropertyEditorMessage {
     Revert,
     Modified(bool),
+    /// Emitted by the editor when the reset button is clicked.
+    ResetToDefault,
+    /// Tells the editor whether the property has its default value or not. The reset button is
+    /// hidden until this message is received, and it is disabled when the value is default.
+    IsDefault(bool),
 }
 
 impl InheritablePropertyEditorMessage {
     define_constructor!(InheritablePropertyEditorMessage:Revert => fn revert(), layout: false);
     define_constructor!(InheritablePropertyEditorMessage:Modified => fn modified(bool), layout: false);
+    define_constructor!(InheritablePropertyEditorMessage:ResetToDefault => fn reset_to_default(), layout: false);
+    define_constructor!(InheritablePropertyEditorMessage:IsDefault => fn is_default(bool), layout: false);
 }
 
 #[derive(Debug, Clone)]
 pub struct InheritablePropertyEditor {
     widget: Widget,
     revert: Handle<UiNode>,
+    reset: Handle<UiNode>,
+    modified_marker: Handle<UiNode>,
     inner_editor: Handle<UiNode>,
 }
 
+impl InheritablePropertyEditor {
+    /// Returns a handle of the wrapped property editor.
+    pub fn inner_editor(&self) -> Handle<UiNode> {
+        self.inner_editor
+    }
+}
+
 impl Deref for InheritablePropertyEditor {
     type Target = Widget;
 
@@ -78,15 +98,41 @@ impl Control for InheritablePropertyEditor {
                     self.handle,
                     MessageDirection::FromWidget,
                 ));
-            }
-        } else if let Some(InheritablePropertyEditorMessage::Modified(modified)) = message.data() {
-            if message.destination() == self.handle {
-                ui.send_message(WidgetMessage::visibility(
-                    self.revert,
-                    MessageDirection::ToWidget,
-                    *modified,
+            } else if message.destination() == self.reset {
+                ui.send_message(InheritablePropertyEditorMessage::reset_to_default(
+                    self.handle,
+                    MessageDirection::FromWidget,
                 ));
             }
+        } else if let Some(msg) = message.data::<InheritablePropertyEditorMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    InheritablePropertyEditorMessage::Modified(modified) => {
+                        for widget in [self.revert, self.modified_marker] {
+                            ui.send_message(WidgetMessage::visibility(
+                                widget,
+                                MessageDirection::ToWidget,
+                                *modified,
+                            ));
+                        }
+                    }
+                    InheritablePropertyEditorMessage::IsDefault(is_default) => {
+                        ui.send_message(WidgetMessage::visibility(
+                            self.reset,
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                        ui.send_message(WidgetMessage::enabled(
+                            self.reset,
+                            MessageDirection::ToWidget,
+                            !*is_default,
+                        ));
+                    }
+                    _ => (),
+                }
+            }
         }
 
         // Re-cast messages from inner editor as message from this editor.
@@ -132,29 +178,66 @@ impl InheritablePropertyEditorBuilder {
 
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let revert;
-        let grid = GridBuilder::new(WidgetBuilder::new().with_child(self.container).with_child({
-            revert = ButtonBuilder::new(
-                WidgetBuilder::new()
-                    .with_visibility(self.modified)
-                    .with_width(16.0)
-                    .with_height(16.0)
-                    .with_vertical_alignment(VerticalAlignment::Top)
-                    .with_tooltip(make_simple_tooltip(ctx, "Revert To Parent"))
-                    .with_margin(Thickness::uniform(1.0))
-                    .on_column(1),
-            )
-            .with_text("<")
-            .build(ctx);
-            revert
-        }))
+        let reset;
+        let modified_marker;
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(self.container)
+                .with_child({
+                    modified_marker = BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .with_visibility(self.modified)
+                            .with_width(2.0)
+                            .with_horizontal_alignment(HorizontalAlignment::Left)
+                            .with_background(BRUSH_BRIGHT_BLUE)
+                            .on_column(0),
+                    )
+                    .with_stroke_thickness(Thickness::zero())
+                    .build(ctx);
+                    modified_marker
+                })
+                .with_child({
+                    revert = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_visibility(self.modified)
+                            .with_width(16.0)
+                            .with_height(16.0)
+                            .with_vertical_alignment(VerticalAlignment::Top)
+                            .with_tooltip(make_simple_tooltip(ctx, "Revert To Parent"))
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(1),
+                    )
+                    .with_text("<")
+                    .build(ctx);
+                    revert
+                })
+                .with_child({
+                    reset = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_visibility(false)
+                            .with_width(16.0)
+                            .with_height(16.0)
+                            .with_vertical_alignment(VerticalAlignment::Top)
+                            .with_tooltip(make_simple_tooltip(ctx, "Reset To Default"))
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_column(2),
+                    )
+                    .with_text("R")
+                    .build(ctx);
+                    reset
+                }),
+        )
         .add_row(Row::auto())
         .add_column(Column::stretch())
         .add_column(Column::auto())
+        .add_column(Column::auto())
         .build(ctx);
 
         ctx.add_node(UiNode::new(InheritablePropertyEditor {
             widget: self.widget_builder.with_child(grid).build(),
             revert,
+            reset,
+            modified_marker,
             inner_editor: self.inner_editor,
         }))
     }
@@ -195,7 +278,9 @@ where
     let value = property_info.cast_value::<InheritableVariable<T>>()?;
 
     Ok(FieldInfo {
-        owner_type_id: TypeId::of::<T>(),
+        // Keep the owner of the variable, so the inner editor sees the same owner in every
+        // method, including `translate_message` that receives the owner from the inspector.
+        owner_type_id: property_info.owner_type_id,
         name: property_info.name,
         display_name: property_info.display_name,
         value: &**value,
@@ -306,12 +391,22 @@ where
     }
 
     fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
-        if let Some(InheritablePropertyEditorMessage::Revert) = ctx.message.data() {
-            return Some(PropertyChanged {
-                name: ctx.name.to_string(),
-                owner_type_id: ctx.owner_type_id,
-                value: FieldKind::Inheritable(InheritableAction::Revert),
-            });
+        if let Some(msg) = ctx.message.data::<InheritablePropertyEditorMessage>() {
+            let action = match msg {
+                InheritablePropertyEditorMessage::Revert => Some(InheritableAction::Revert),
+                InheritablePropertyEditorMessage::ResetToDefault => {
+                    Some(InheritableAction::ResetToDefault)
+                }
+                _ => None,
+            };
+
+            if let Some(action) = action {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::Inheritable(action),
+                });
+            }
         }
 
         // Try translate other messages using inner property editor.
@@ -56,6 +56,7 @@ pub mod numeric;
 pub mod quat;
 pub mod range;
 pub mod rect;
+pub mod reflect_enum;
 pub mod string;
 pub mod uuid;
 pub mod vec;
@@ -0,0 +1,400 @@
+//! Property editor for enums without a registered property editor definition. It uses enum
+//! reflection ([`Reflect::variant_names`], [`Reflect::variant_index`] and
+//! [`Reflect::variant_constructor`]), so any enum with derived `Reflect` trait gets a dropdown list
+//! of its variants in the inspector.
+
+use crate::{
+    core::{
+        pool::Handle,
+        reflect::{blank_reflect, prelude::*, VariantConstructor},
+    },
+    dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
+    inspector::{
+        editors::{
+            enumeration::{
+                make_variant_selector_items, EnumPropertyEditorMessage, LOCAL_SYNC_FLAG,
+            },
+            PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
+            PropertyEditorMessageContext, PropertyEditorTranslationContext,
+        },
+        make_expander_container, FieldKind, Inspector, InspectorBuilder, InspectorContext,
+        InspectorEnvironment, InspectorError, InspectorMessage, ObjectValue, PropertyChanged,
+        Value,
+    },
+    message::{MessageDirection, UiMessage},
+    widget::{Widget, WidgetBuilder},
+    Control, Thickness, UiNode, UserInterface,
+};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// A variant of an enum of unknown type, it is used to pass a new value of an enum property in
+/// [`FieldKind::Object`]. The actual value is created by the variant constructor when the value is
+/// converted to `Box<dyn Reflect>`.
+#[derive(Debug)]
+pub struct ReflectEnumVariant {
+    pub index: usize,
+    pub constructor: VariantConstructor,
+}
+
+impl Reflect for ReflectEnumVariant {
+    blank_reflect!();
+}
+
+impl Value for ReflectEnumVariant {
+    fn clone_box(&self) -> Box<dyn Value> {
+        Box::new(Self {
+            index: self.index,
+            constructor: self.constructor,
+        })
+    }
+
+    fn into_box_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        match (self.constructor)(self.index) {
+            Some(value) => value,
+            None => self,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReflectEnumPropertyEditor {
+    pub widget: Widget,
+    pub variant_selector: Handle<UiNode>,
+    pub inspector: Handle<UiNode>,
+    pub variant_count: usize,
+    pub variant_constructor: VariantConstructor,
+    pub definition_container: Rc<PropertyEditorDefinitionContainer>,
+    pub environment: Option<Rc<dyn InspectorEnvironment>>,
+    pub sync_flag: u64,
+    pub layer_index: usize,
+    pub generate_property_string_values: bool,
+}
+
+impl Debug for ReflectEnumPropertyEditor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "ReflectEnumPropertyEditor")
+    }
+}
+
+impl Deref for ReflectEnumPropertyEditor {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl DerefMut for ReflectEnumPropertyEditor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl Control for ReflectEnumPropertyEditor {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(EnumPropertyEditorMessage::Variant(variant)) =
+            message.data::<EnumPropertyEditorMessage>()
+        {
+            if message.destination() == self.handle {
+                if let Some(variant) = (self.variant_constructor)(*variant) {
+                    let ctx = InspectorContext::from_object(
+                        &*variant,
+                        &mut ui.build_ctx(),
+                        self.definition_container.clone(),
+                        self.environment.clone(),
+                        self.sync_flag,
+                        self.layer_index,
+                        self.generate_property_string_values,
+                    );
+
+                    ui.send_message(InspectorMessage::context(
+                        self.inspector,
+                        MessageDirection::ToWidget,
+                        ctx,
+                    ));
+                }
+            }
+        } else if let Some(InspectorMessage::PropertyChanged(property_changed)) =
+            message.data::<InspectorMessage>()
+        {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                ui.send_message(EnumPropertyEditorMessage::property_changed(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    property_changed.clone(),
+                ))
+            }
+        }
+    }
+
+    fn preview_message(&self, ui: &UserInterface, message: &mut UiMessage) {
+        if message.direction() == MessageDirection::FromWidget
+            && message.destination() == self.variant_selector
+            && message.flags != LOCAL_SYNC_FLAG
+        {
+            if let Some(DropdownListMessage::SelectionChanged(Some(index))) =
+                message.data::<DropdownListMessage>()
+            {
+                // Unknown value item can't be selected, as well as variants with fields, because
+                // there's no way to create them without knowing the actual type of the enum.
+                if *index < self.variant_count && (self.variant_constructor)(*index).is_some() {
+                    ui.send_message(EnumPropertyEditorMessage::variant(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        *index,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Property editor definition for enums, that is used by the inspector for every enum property
+/// that does not have a registered definition. Unlike other definitions, it can't work only with
+/// [`crate::core::reflect::FieldInfo`], because the type of the enum is unknown, so it is created
+/// by the inspector for every such property and requires a reference to the value of the property
+/// (see [`Self::create_instance_for`] and [`Self::create_message_for`]).
+///
+/// Only variants without fields could be selected in this editor, register
+/// [`super::enumeration::EnumPropertyEditorDefinition`] for the enum if you need to switch to
+/// variants with fields too.
+#[derive(Debug)]
+pub struct ReflectEnumPropertyEditorDefinition {
+    pub value_type_id: TypeId,
+    pub variant_constructor: VariantConstructor,
+}
+
+impl ReflectEnumPropertyEditorDefinition {
+    /// Creates a new definition for the given value, returns `None` if the value is not an enum.
+    pub fn new(value: &dyn Reflect) -> Option<Self> {
+        value.variant_constructor().map(|variant_constructor| Self {
+            value_type_id: value.as_any().type_id(),
+            variant_constructor,
+        })
+    }
+
+    fn variant_names(value: &dyn Reflect) -> &'static [&'static str] {
+        value.variant_names().unwrap_or_default()
+    }
+
+    /// Returns index of the current variant of the value, index of unknown value is equal to
+    /// the amount of variants (it points to the "unknown" item of the variant selector).
+    fn variant_index(value: &dyn Reflect) -> usize {
+        let variant_count = Self::variant_names(value).len();
+        value
+            .variant_index()
+            .map_or(variant_count, |index| index.min(variant_count))
+    }
+
+    pub fn create_instance_for(
+        &self,
+        ctx: PropertyEditorBuildContext,
+        value: &dyn Reflect,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let names = Self::variant_names(value);
+        let variant_index = Self::variant_index(value);
+        let unknown_value = (variant_index == names.len()).then(|| format!("{:?}", value));
+
+        let items = make_variant_selector_items(
+            ctx.build_context,
+            names.iter().map(|name| name.to_string()).collect(),
+            unknown_value,
+        );
+
+        for (index, item) in items.iter().enumerate().take(names.len()) {
+            if index != variant_index && (self.variant_constructor)(index).is_none() {
+                ctx.build_context[*item].set_enabled(false);
+            }
+        }
+
+        let variant_selector = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_selected(variant_index)
+        .with_items(items)
+        .with_close_on_selection(true)
+        .build(ctx.build_context);
+
+        let definition_container = ctx.definition_container.clone();
+
+        let context = InspectorContext::from_object(
+            value,
+            ctx.build_context,
+            definition_container.clone(),
+            ctx.environment.clone(),
+            ctx.sync_flag,
+            ctx.layer_index + 1,
+            ctx.generate_property_string_values,
+        );
+
+        let inspector = InspectorBuilder::new(WidgetBuilder::new())
+            .with_context(context)
+            .build(ctx.build_context);
+
+        let editor = ctx
+            .build_context
+            .add_node(UiNode::new(ReflectEnumPropertyEditor {
+                widget: WidgetBuilder::new()
+                    .with_preview_messages(true)
+                    .with_child(inspector)
+                    .build(),
+                variant_selector,
+                inspector,
+                variant_count: names.len(),
+                variant_constructor: self.variant_constructor,
+                definition_container,
+                environment: ctx.environment.clone(),
+                sync_flag: ctx.sync_flag,
+                layer_index: ctx.layer_index + 1,
+                generate_property_string_values: ctx.generate_property_string_values,
+            }));
+
+        let container = make_expander_container(
+            ctx.layer_index,
+            ctx.property_info.display_name,
+            ctx.property_info.description,
+            variant_selector,
+            editor,
+            ctx.build_context,
+        );
+
+        Ok(PropertyEditorInstance::Custom { container, editor })
+    }
+
+    pub fn create_message_for(
+        &self,
+        ctx: PropertyEditorMessageContext,
+        value: &dyn Reflect,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let instance_ref = ctx
+            .ui
+            .node(ctx.instance)
+            .cast::<ReflectEnumPropertyEditor>()
+            .expect("Must be ReflectEnumPropertyEditor!");
+
+        let variant_selector_ref = ctx
+            .ui
+            .node(instance_ref.variant_selector)
+            .cast::<DropdownList>()
+            .expect("Must be a DropDownList");
+
+        let variant_index = Self::variant_index(value);
+        if Some(variant_index) != variant_selector_ref.selection() {
+            let mut selection_message = DropdownListMessage::selection(
+                instance_ref.variant_selector,
+                MessageDirection::ToWidget,
+                Some(variant_index),
+            );
+            selection_message.flags = LOCAL_SYNC_FLAG;
+            ctx.ui.send_message(selection_message);
+
+            let inspector = instance_ref.inspector;
+
+            let context = InspectorContext::from_object(
+                value,
+                &mut ctx.ui.build_ctx(),
+                ctx.definition_container.clone(),
+                ctx.environment.clone(),
+                ctx.sync_flag,
+                ctx.layer_index + 1,
+                ctx.generate_property_string_values,
+            );
+
+            Ok(Some(InspectorMessage::context(
+                inspector,
+                MessageDirection::ToWidget,
+                context,
+            )))
+        } else {
+            let layer_index = ctx.layer_index;
+            let inspector_ctx = ctx
+                .ui
+                .node(instance_ref.inspector)
+                .cast::<Inspector>()
+                .expect("Must be Inspector!")
+                .context()
+                .clone();
+
+            if let Err(e) = inspector_ctx.sync(
+                value,
+                ctx.ui,
+                layer_index + 1,
+                ctx.generate_property_string_values,
+            ) {
+                Err(InspectorError::Group(e))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl PropertyEditorDefinition for ReflectEnumPropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        self.value_type_id
+    }
+
+    fn create_instance(
+        &self,
+        _ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        Err(InspectorError::Custom(
+            "Reflection-based enum editor requires a value, use create_instance_for".to_string(),
+        ))
+    }
+
+    fn create_message(
+        &self,
+        _ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        Err(InspectorError::Custom(
+            "Reflection-based enum editor requires a value, use create_message_for".to_string(),
+        ))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if let Some(msg) = ctx.message.data::<EnumPropertyEditorMessage>() {
+            return match msg {
+                EnumPropertyEditorMessage::PropertyChanged(property_changed) => {
+                    Some(PropertyChanged {
+                        name: ctx.name.to_string(),
+                        owner_type_id: ctx.owner_type_id,
+                        value: FieldKind::Inspectable(Box::new(property_changed.clone())),
+                    })
+                }
+                EnumPropertyEditorMessage::Variant(index) => Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::Object(ObjectValue {
+                        value: Box::new(ReflectEnumVariant {
+                            index: *index,
+                            constructor: self.variant_constructor,
+                        }),
+                    }),
+                }),
+            };
+        }
+
+        None
+    }
+}
@@ -11,8 +11,9 @@ use crate::{
     formatted_text::WrapMode,
     grid::{Column, GridBuilder, Row},
     inspector::editors::{
-        PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorDefinitionContainer,
-        PropertyEditorInstance, PropertyEditorMessageContext, PropertyEditorTranslationContext,
+        reflect_enum::ReflectEnumPropertyEditorDefinition, PropertyEditorBuildContext,
+        PropertyEditorDefinition, PropertyEditorDefinitionContainer, PropertyEditorInstance,
+        PropertyEditorMessageContext, PropertyEditorTranslationContext,
     },
     menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
     message::{MessageDirection, UiMessage},
@@ -57,6 +58,7 @@ impl CollectionChanged {
 #[derive(Debug, Clone)]
 pub enum InheritableAction {
     Revert,
+    ResetToDefault,
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +89,8 @@ pub enum PropertyAction {
     },
     /// Revert value to parent.
     Revert,
+    /// Reset value to the default value of the type of an entity.
+    ResetToDefault,
 }
 
 impl PropertyAction {
@@ -108,7 +112,8 @@ impl PropertyAction {
                 }
             },
             FieldKind::Inspectable(ref inspectable) => Self::from_field_kind(&inspectable.value),
-            FieldKind::Inheritable { .. } => Self::Revert,
+            FieldKind::Inheritable(InheritableAction::Revert) => Self::Revert,
+            FieldKind::Inheritable(InheritableAction::ResetToDefault) => Self::ResetToDefault,
         }
     }
 
@@ -158,6 +163,10 @@ impl PropertyAction {
                 // Unsupported due to lack of context (a reference to parent entity).
                 Err(Self::Revert)
             }
+            PropertyAction::ResetToDefault => {
+                // Unsupported due to lack of context (a default instance of the entity).
+                Err(Self::ResetToDefault)
+            }
         }
     }
 }
@@ -529,11 +538,24 @@ impl InspectorContext {
                     format!("{}\n\n{}", info.display_name, info.description)
                 };
 
-                if let Some(definition) = definition_container
+                let registered_definition = definition_container
                     .definitions()
                     .get(&info.value.type_id())
-                {
-                    match definition.create_instance(PropertyEditorBuildContext {
+                    .cloned();
+
+                // Enums without registered definition are edited using reflection.
+                let reflect_enum_definition = if registered_definition.is_none() {
+                    ReflectEnumPropertyEditorDefinition::new(*property).map(Rc::new)
+                } else {
+                    None
+                };
+
+                if let Some(definition) = registered_definition.or_else(|| {
+                    reflect_enum_definition
+                        .clone()
+                        .map(|d| d as Rc<dyn PropertyEditorDefinition>)
+                }) {
+                    let build_context = PropertyEditorBuildContext {
                         build_context: ctx,
                         property_info: info,
                         environment: environment.clone(),
@@ -541,7 +563,16 @@ impl InspectorContext {
                         sync_flag,
                         layer_index,
                         generate_property_string_values,
-                    }) {
+                    };
+
+                    let instance = match reflect_enum_definition {
+                        Some(ref reflect_enum_definition) => {
+                            reflect_enum_definition.create_instance_for(build_context, *property)
+                        }
+                        None => definition.create_instance(build_context),
+                    };
+
+                    match instance {
                         Ok(instance) => {
                             let (container, editor) = match instance {
                                 PropertyEditorInstance::Simple { editor } => (
@@ -650,33 +681,42 @@ impl InspectorContext {
     ) -> Result<(), Vec<InspectorError>> {
         let mut sync_errors = Vec::new();
 
-        for info in object.fields_info() {
-            if let Some(constructor) = self
+        for (property, info) in object.fields().into_iter().zip(object.fields_info()) {
+            let registered_definition = self
                 .property_definitions
                 .definitions()
                 .get(&info.value.type_id())
-            {
-                if let Some(property_editor) = self.find_property_editor(info.name) {
-                    let ctx = PropertyEditorMessageContext {
-                        sync_flag: self.sync_flag,
-                        instance: property_editor.property_editor,
-                        ui,
-                        property_info: &info,
-                        definition_container: self.property_definitions.clone(),
-                        layer_index,
-                        environment: self.environment.clone(),
-                        generate_property_string_values,
-                    };
+                .cloned();
+
+            if let Some(property_editor) = self.find_property_editor(info.name) {
+                let ctx = PropertyEditorMessageContext {
+                    sync_flag: self.sync_flag,
+                    instance: property_editor.property_editor,
+                    ui,
+                    property_info: &info,
+                    definition_container: self.property_definitions.clone(),
+                    layer_index,
+                    environment: self.environment.clone(),
+                    generate_property_string_values,
+                };
 
-                    match constructor.create_message(ctx) {
-                        Ok(message) => {
-                            if let Some(mut message) = message {
-                                message.flags = self.sync_flag;
-                                ui.send_message(message);
-                            }
+                let message = if let Some(definition) = registered_definition {
+                    definition.create_message(ctx)
+                } else if let Some(definition) = ReflectEnumPropertyEditorDefinition::new(property)
+                {
+                    definition.create_message_for(ctx, property)
+                } else {
+                    continue;
+                };
+
+                match message {
+                    Ok(message) => {
+                        if let Some(mut message) = message {
+                            message.flags = self.sync_flag;
+                            ui.send_message(message);
                         }
-                        Err(e) => sync_errors.push(e),
                     }
+                    Err(e) => sync_errors.push(e),
                 }
             }
         }
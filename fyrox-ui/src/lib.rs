@@ -611,6 +611,7 @@ pub struct UserInterface {
     drag_context: DragContext,
     mouse_state: MouseState,
     keyboard_modifiers: KeyboardModifiers,
+    pointer_pressure: f32,
     cursor_icon: CursorIcon,
     active_tooltip: Option<TooltipEntry>,
     preview_set: FxHashSet<Handle<UiNode>>,
@@ -731,6 +732,7 @@ impl UserInterface {
             drag_context: Default::default(),
             mouse_state: Default::default(),
             keyboard_modifiers: Default::default(),
+            pointer_pressure: 1.0,
             cursor_icon: Default::default(),
             active_tooltip: Default::default(),
             preview_set: Default::default(),
@@ -751,6 +753,12 @@ impl UserInterface {
         self.keyboard_modifiers
     }
 
+    /// Returns current pressure of a pen (or a finger) in `[0; 1]` range. It is always `1.0` for
+    /// devices without pressure sensor (mouse for example).
+    pub fn pointer_pressure(&self) -> f32 {
+        self.pointer_pressure
+    }
+
     pub fn build_ctx(&mut self) -> BuildContext<'_> {
         BuildContext { ui: self }
     }
@@ -2071,6 +2079,9 @@ impl UserInterface {
                 // TODO: Is message needed for focused node?
                 self.keyboard_modifiers = modifiers;
             }
+            &OsEvent::PointerPressure(pressure) => {
+                self.pointer_pressure = pressure.clamp(0.0, 1.0);
+            }
         }
 
         self.prev_picked_node = self.picked_node;
@@ -286,6 +286,9 @@ pub enum OsEvent {
     Character(char),
     KeyboardModifiers(KeyboardModifiers),
     MouseWheel(f32, f32),
+    /// Pressure of a pen (or a finger) in `[0; 1]` range. Devices without pressure sensor do not
+    /// send this event, pressure is considered to be `1.0` for them.
+    PointerPressure(f32),
 }
 
 #[derive(
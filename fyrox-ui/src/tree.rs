@@ -184,6 +184,12 @@ impl Control for Tree {
                                         } else {
                                             let node = ui.node(handle);
 
+                                            // Items of collapsed trees are hidden and must not be
+                                            // a part of the range.
+                                            if !node.visibility() {
+                                                return;
+                                            }
+
                                             if let Some(first) = selection.first() {
                                                 if *first == handle {
                                                     *first_position = Some(hierarchy.len());
@@ -229,7 +235,9 @@ impl Control for Tree {
                                             flat_hierarchy[this_position..=first_position].to_vec()
                                         })
                                     } else {
-                                        Some(vec![])
+                                        // There's nothing to start the range from, select this
+                                        // item only.
+                                        Some(vec![self.handle])
                                     }
                                 } else if !self.is_selected {
                                     Some(vec![self.handle()])
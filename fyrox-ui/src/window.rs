@@ -218,15 +218,18 @@ impl Control for Window {
         self.widget.handle_routed_message(ui, message);
 
         if let Some(msg) = message.data::<WidgetMessage>() {
+            // Any click on a window brings it to front, even if the window cannot be resized.
+            if let WidgetMessage::MouseDown { .. } = msg {
+                ui.send_message(WidgetMessage::topmost(
+                    self.handle(),
+                    MessageDirection::ToWidget,
+                ));
+            }
+
             // Grip interaction have higher priority than other actions.
             if self.can_resize {
                 match msg {
                     &WidgetMessage::MouseDown { pos, .. } => {
-                        ui.send_message(WidgetMessage::topmost(
-                            self.handle(),
-                            MessageDirection::ToWidget,
-                        ));
-
                         // Check grips.
                         for grip in self.grips.borrow_mut().iter_mut() {
                             let offset = self.screen_position();
@@ -273,32 +276,40 @@ impl Control for Window {
                                     GripKind::LeftBottomCorner => (-1.0, 0.0, 1.0, -1.0),
                                 };
 
-                                let new_pos = self.initial_position
-                                    + Vector2::new(delta.x * dx, delta.y * dy);
                                 let new_size =
                                     self.initial_size + Vector2::new(delta.x * dw, delta.y * dh);
 
-                                if new_size.x > self.min_width()
-                                    && new_size.x < self.max_width()
-                                    && new_size.y > self.min_height()
-                                    && new_size.y < self.max_height()
-                                {
-                                    ui.send_message(WidgetMessage::desired_position(
-                                        self.handle(),
-                                        MessageDirection::ToWidget,
-                                        ui.screen_to_root_canvas_space(new_pos),
-                                    ));
-                                    ui.send_message(WidgetMessage::width(
-                                        self.handle(),
-                                        MessageDirection::ToWidget,
-                                        new_size.x,
-                                    ));
-                                    ui.send_message(WidgetMessage::height(
-                                        self.handle(),
-                                        MessageDirection::ToWidget,
-                                        new_size.y,
-                                    ));
-                                }
+                                // Clamp the size instead of ignoring the movement, otherwise a
+                                // fast drag leaves the window bigger than its minimal size.
+                                let new_size = Vector2::new(
+                                    new_size.x.max(self.min_width()).min(self.max_width()),
+                                    new_size.y.max(self.min_height()).min(self.max_height()),
+                                );
+
+                                // Left and top grips move the window, so the opposite edges stay
+                                // in place.
+                                let size_change = self.initial_size - new_size;
+                                let new_pos = self.initial_position
+                                    + Vector2::new(
+                                        if dx != 0.0 { size_change.x } else { 0.0 },
+                                        if dy != 0.0 { size_change.y } else { 0.0 },
+                                    );
+
+                                ui.send_message(WidgetMessage::desired_position(
+                                    self.handle(),
+                                    MessageDirection::ToWidget,
+                                    ui.screen_to_root_canvas_space(new_pos),
+                                ));
+                                ui.send_message(WidgetMessage::width(
+                                    self.handle(),
+                                    MessageDirection::ToWidget,
+                                    new_size.x,
+                                ));
+                                ui.send_message(WidgetMessage::height(
+                                    self.handle(),
+                                    MessageDirection::ToWidget,
+                                    new_size.y,
+                                ));
 
                                 break;
                             }
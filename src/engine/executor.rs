@@ -1,12 +1,16 @@
 //! Executor is a small wrapper that manages plugins and scripts for your game.
 
 use crate::{
-    core::instant::Instant,
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        instant::Instant,
+        pool::Handle,
+    },
     engine::{resource_manager::ResourceManager, Engine, EngineInitParams, SerializationContext},
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     plugin::PluginConstructor,
-    scene::loader::AsyncSceneLoader,
+    scene::{graph::Graph, loader::AsyncSceneLoader, node::Node},
     utils::{
         log::{Log, MessageKind},
         translate_event,
@@ -15,8 +19,12 @@ use crate::{
 };
 use clap::Parser;
 use std::{
+    io::BufRead,
     ops::{Deref, DerefMut},
-    sync::Arc,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc,
+    },
 };
 
 #[derive(Parser, Debug)]
@@ -24,6 +32,102 @@ use std::{
 struct Args {
     #[clap(short, long, default_value = "")]
     override_scene: String,
+    /// Nodes of the overridden scene that keep their initial local transform, see
+    /// [`FrozenNode`]. Handles are written as `index:generation` and separated by commas.
+    #[clap(long, default_value = "")]
+    frozen_nodes: String,
+}
+
+fn parse_handles(text: &str) -> Vec<Handle<Node>> {
+    text.split(',')
+        .filter(|h| !h.trim().is_empty())
+        .filter_map(|h| {
+            let handle = h.trim().split_once(':').and_then(|(index, generation)| {
+                Some(Handle::new(index.parse().ok()?, generation.parse().ok()?))
+            });
+            if handle.is_none() {
+                Log::warn(format!("Invalid node handle {}", h));
+            }
+            handle
+        })
+        .collect()
+}
+
+/// A node that is frozen in place by the editor for debugging: its local transform is restored
+/// after every update, so scripts, animations and physics can't move it.
+struct FrozenNode {
+    handle: Handle<Node>,
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    scale: Vector3<f32>,
+}
+
+impl FrozenNode {
+    fn new(handle: Handle<Node>, graph: &Graph) -> Option<Self> {
+        let transform = graph.try_get(handle)?.local_transform();
+        Some(Self {
+            handle,
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+        })
+    }
+
+    fn restore(&self, graph: &mut Graph) {
+        if let Some(node) = graph.try_get_mut(self.handle) {
+            node.local_transform_mut()
+                .set_position(self.position)
+                .set_rotation(self.rotation)
+                .set_scale(self.scale);
+        }
+    }
+}
+
+/// A command that controls a game launched by the editor. Commands are passed through the standard
+/// input of the game process, one command per line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExecutorCommand {
+    /// Pauses updates of scripts and plugins, see [`Engine::set_logic_paused`].
+    PauseLogic,
+    /// Resumes updates of scripts and plugins.
+    ResumeLogic,
+    /// Performs a single update of paused scripts and plugins, see [`Engine::step_logic`].
+    StepLogic,
+}
+
+impl ExecutorCommand {
+    /// Returns a textual representation of the command.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PauseLogic => "pause_logic",
+            Self::ResumeLogic => "resume_logic",
+            Self::StepLogic => "step_logic",
+        }
+    }
+
+    /// Parses a command from its textual representation.
+    pub fn parse(command: &str) -> Option<Self> {
+        [Self::PauseLogic, Self::ResumeLogic, Self::StepLogic]
+            .into_iter()
+            .find(|c| c.as_str() == command.trim())
+    }
+}
+
+// Reads commands from the standard input in a separate thread.
+fn listen_commands() -> Receiver<ExecutorCommand> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().flatten() {
+            if let Some(command) = ExecutorCommand::parse(&line) {
+                if sender.send(command).is_err() {
+                    break;
+                }
+            } else {
+                Log::warn(format!("Unknown executor command {}", line));
+            }
+        }
+    });
+    receiver
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -117,6 +221,18 @@ impl Executor {
 
         let args = Args::parse();
 
+        // Overridden scene means that the game was launched by the editor, which controls the game
+        // through the standard input.
+        let frozen_handles = parse_handles(&args.frozen_nodes);
+        let mut frozen_nodes = Vec::new();
+        let mut override_scene = Handle::NONE;
+
+        let commands = if args.override_scene.is_empty() {
+            None
+        } else {
+            Some(listen_commands())
+        };
+
         if !args.override_scene.is_empty() {
             // Try to load specified scene in a separate thread.
             self.loader = Some(AsyncSceneLoader::begin_loading(
@@ -136,8 +252,14 @@ impl Executor {
         event_loop.run(move |event, _, control_flow| {
             if let Some(loader) = self.loader.as_ref() {
                 if let Some(result) = loader.fetch_result() {
-                    let override_scene = match result {
-                        Ok(scene) => engine.scenes.add(scene),
+                    override_scene = match result {
+                        Ok(scene) => {
+                            frozen_nodes = frozen_handles
+                                .iter()
+                                .filter_map(|&handle| FrozenNode::new(handle, &scene.graph))
+                                .collect();
+                            engine.scenes.add(scene)
+                        }
                         Err(e) => {
                             Log::err(e);
                             Default::default()
@@ -150,6 +272,16 @@ impl Executor {
                 }
             }
 
+            if let Some(commands) = commands.as_ref() {
+                while let Ok(command) = commands.try_recv() {
+                    match command {
+                        ExecutorCommand::PauseLogic => engine.set_logic_paused(true),
+                        ExecutorCommand::ResumeLogic => engine.set_logic_paused(false),
+                        ExecutorCommand::StepLogic => engine.step_logic(),
+                    }
+                }
+            }
+
             engine.handle_os_event_by_plugins(&event, fixed_time_step, control_flow, &mut lag);
 
             let scenes = engine
@@ -177,6 +309,16 @@ impl Executor {
                         lag -= fixed_time_step;
                     }
 
+                    if let Some(scene) = engine.scenes.try_get_mut(override_scene) {
+                        if !frozen_nodes.is_empty() {
+                            for frozen_node in frozen_nodes.iter() {
+                                frozen_node.restore(&mut scene.graph);
+                            }
+                            // Global transforms must be up to date before rendering.
+                            scene.graph.update_hierarchical_data();
+                        }
+                    }
+
                     engine.get_window().request_redraw();
                 }
                 Event::RedrawRequested(_) => {
@@ -112,6 +112,10 @@ pub struct Engine {
 
     plugins_enabled: bool,
 
+    // Scripts and plugins are not updated while the logic is paused, except single steps.
+    logic_paused: bool,
+    logic_step_requested: bool,
+
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
@@ -817,6 +821,8 @@ impl Engine {
             serialization_context: node_constructors,
             script_processor: Default::default(),
             plugins_enabled: false,
+            logic_paused: false,
+            logic_step_requested: false,
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
         })
@@ -915,8 +921,31 @@ impl Engine {
             );
         }
 
-        self.update_plugins(dt, control_flow, lag);
-        self.handle_scripts(dt);
+        if !self.logic_paused || std::mem::take(&mut self.logic_step_requested) {
+            self.update_plugins(dt, control_flow, lag);
+            self.handle_scripts(dt);
+        }
+    }
+
+    /// Pauses or resumes updates of scripts and plugins. Scenes are still updated and rendered
+    /// while the logic is paused, OS events are not passed to scripts and plugins. Could be used
+    /// to debug game logic, see also [`Self::step_logic`].
+    pub fn set_logic_paused(&mut self, paused: bool) {
+        self.logic_paused = paused;
+        self.logic_step_requested = false;
+    }
+
+    /// Returns `true` if updates of scripts and plugins are paused.
+    pub fn is_logic_paused(&self) -> bool {
+        self.logic_paused
+    }
+
+    /// Requests a single update of scripts and plugins while the logic is paused. The update
+    /// will be performed on the next engine update. Does nothing if the logic is not paused.
+    pub fn step_logic(&mut self) {
+        if self.logic_paused {
+            self.logic_step_requested = true;
+        }
     }
 
     /// Performs post update for the engine.
@@ -1007,7 +1036,7 @@ impl Engine {
         control_flow: &mut ControlFlow,
         lag: &mut f32,
     ) {
-        if self.plugins_enabled {
+        if self.plugins_enabled && !self.logic_paused {
             for plugin in self.plugins.iter_mut() {
                 plugin.on_os_event(
                     event,
@@ -1050,7 +1079,7 @@ impl Engine {
             .find(|s| s.handle == scene)
         {
             let scene = &mut self.scenes[scene];
-            if scene.enabled {
+            if scene.enabled && !self.logic_paused {
                 process_scripts(
                     scene,
                     &mut self.plugins,
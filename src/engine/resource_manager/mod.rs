@@ -30,12 +30,13 @@ use crate::{
     },
     utils::{log::Log, watcher::FileSystemWatcher},
 };
+use fxhash::FxHashSet;
 use fyrox_sound::buffer::SoundBufferResource;
 use std::{
     fmt::{Debug, Display, Formatter},
     future::Future,
     ops::Deref,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
@@ -160,6 +161,9 @@ impl ResourceWaitContext {
 pub struct ResourceManagerState {
     containers_storage: Option<ContainersStorage>,
     watcher: Option<FileSystemWatcher>,
+    watcher_paused: bool,
+    /// Paths of files that were changed while the watcher was paused.
+    pending_reloads: FxHashSet<PathBuf>,
 }
 
 /// See module docs.
@@ -407,6 +411,8 @@ impl ResourceManagerState {
         Self {
             containers_storage: None,
             watcher: None,
+            watcher_paused: false,
+            pending_reloads: Default::default(),
         }
     }
 
@@ -416,6 +422,41 @@ impl ResourceManagerState {
     /// for fast iterative development.
     pub fn set_watcher(&mut self, watcher: Option<FileSystemWatcher>) {
         self.watcher = watcher;
+        self.pending_reloads.clear();
+    }
+
+    /// Pauses or resumes reloading of changed resources. While the watcher is paused, changes are
+    /// only collected, every changed resource is reloaded once when the watcher is resumed. It is
+    /// useful to prevent a storm of reloads when lots of files are changed by an external tool.
+    pub fn set_watcher_paused(&mut self, paused: bool) {
+        if self.watcher_paused == paused {
+            return;
+        }
+
+        self.watcher_paused = paused;
+
+        if !paused {
+            let pending_reloads = std::mem::take(&mut self.pending_reloads);
+            if !pending_reloads.is_empty() {
+                Log::info(format!(
+                    "Resource watcher was resumed, reloading {} changed file(s)...",
+                    pending_reloads.len()
+                ));
+            }
+            for path in pending_reloads {
+                self.reload_resource_from_path(&path);
+            }
+        }
+    }
+
+    /// Returns `true` if reloading of changed resources is paused.
+    pub fn is_watcher_paused(&self) -> bool {
+        self.watcher_paused
+    }
+
+    /// Returns amount of changed files that will be reloaded when the watcher is resumed.
+    pub fn pending_reload_count(&self) -> usize {
+        self.pending_reloads.len()
     }
 
     /// Returns a reference to resource containers storage.
@@ -500,32 +541,48 @@ impl ResourceManagerState {
         containers.shaders.update(dt);
         containers.curves.update(dt);
 
-        if let Some(watcher) = self.watcher.as_ref() {
-            if let Some(evt) = watcher.try_get_event() {
-                if let notify::EventKind::Modify(_) = evt.kind {
-                    for path in evt.paths {
-                        if let Ok(relative_path) = make_relative_path(path) {
-                            let containers = self.containers_mut();
-                            for container in [
-                                &mut containers.textures as &mut dyn Container,
-                                &mut containers.models as &mut dyn Container,
-                                &mut containers.sound_buffers as &mut dyn Container,
-                                &mut containers.shaders as &mut dyn Container,
-                                &mut containers.curves as &mut dyn Container,
-                            ] {
-                                if container.try_reload_resource_from_path(&relative_path) {
-                                    Log::info(format!(
-                                        "File {} was changed, trying to reload a respective resource...",
-                                        relative_path.display()
-                                    ));
-
-                                    break;
-                                }
-                            }
-                        }
+        if self.watcher_paused {
+            // Collect every change, so each changed resource will be reloaded only once when
+            // the watcher is resumed.
+            if let Some(watcher) = self.watcher.as_ref() {
+                while let Some(evt) = watcher.try_get_event() {
+                    if let notify::EventKind::Modify(_) = evt.kind {
+                        self.pending_reloads.extend(
+                            evt.paths
+                                .into_iter()
+                                .filter_map(|path| make_relative_path(path).ok()),
+                        );
+                    }
+                }
+            }
+        } else if let Some(evt) = self.watcher.as_ref().and_then(|w| w.try_get_event()) {
+            if let notify::EventKind::Modify(_) = evt.kind {
+                for path in evt.paths {
+                    if let Ok(relative_path) = make_relative_path(path) {
+                        self.reload_resource_from_path(&relative_path);
                     }
                 }
             }
         }
     }
+
+    fn reload_resource_from_path(&mut self, relative_path: &Path) {
+        let containers = self.containers_mut();
+        for container in [
+            &mut containers.textures as &mut dyn Container,
+            &mut containers.models as &mut dyn Container,
+            &mut containers.sound_buffers as &mut dyn Container,
+            &mut containers.shaders as &mut dyn Container,
+            &mut containers.curves as &mut dyn Container,
+        ] {
+            if container.try_reload_resource_from_path(relative_path) {
+                Log::info(format!(
+                    "File {} was changed, trying to reload a respective resource...",
+                    relative_path.display()
+                ));
+
+                break;
+            }
+        }
+    }
 }
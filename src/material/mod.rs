@@ -15,13 +15,14 @@ use crate::{
     },
     engine::resource_manager::ResourceManager,
     material::shader::{PropertyKind, SamplerFallback, Shader},
-    renderer::framework::framebuffer::DrawParameters,
+    renderer::framework::framebuffer::{CullFace, DrawParameters},
     resource::texture::Texture,
 };
 use fxhash::FxHashMap;
 use fyrox_core::parking_lot::{Mutex, MutexGuard};
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::path::Path;
 use std::sync::Arc;
 
 pub mod shader;
@@ -32,7 +33,7 @@ pub mod shader;
 ///
 /// There is a limited set of possible types that can be passed to a shader, most of them are
 /// just simple data types.
-#[derive(Debug, Visit, Clone)]
+#[derive(Debug, Visit, Clone, PartialEq)]
 pub enum PropertyValue {
     /// Real number.
     Float(f32),
@@ -249,6 +250,93 @@ impl Default for PropertyValue {
     }
 }
 
+/// Defines which faces of surfaces will be culled (not rendered) when a material is used.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Visit)]
+pub enum CullMode {
+    /// Back faces are culled, this is the default mode.
+    Back,
+    /// Front faces are culled.
+    Front,
+    /// Nothing is culled, both sides of surfaces are rendered. Useful for foliage, thin panels, etc.
+    None,
+}
+
+impl Default for CullMode {
+    fn default() -> Self {
+        Self::Back
+    }
+}
+
+impl CullMode {
+    /// All possible cull modes.
+    pub const ALL: [CullMode; 3] = [CullMode::Back, CullMode::Front, CullMode::None];
+
+    /// Returns a cull face that should be used for a render pass with the given cull face. Render
+    /// passes that do not cull anything (for example in two-sided shaders) stay as is, otherwise the
+    /// mode overrides the face of the pass.
+    pub fn cull_face(self, pass_cull_face: Option<CullFace>) -> Option<CullFace> {
+        pass_cull_face.and(match self {
+            CullMode::Back => Some(CullFace::Back),
+            CullMode::Front => Some(CullFace::Front),
+            CullMode::None => None,
+        })
+    }
+
+    /// Returns draw parameters of a render pass with the cull mode applied.
+    pub fn apply(self, draw_parameters: &DrawParameters) -> DrawParameters {
+        DrawParameters {
+            cull_face: self.cull_face(draw_parameters.cull_face),
+            ..draw_parameters.clone()
+        }
+    }
+}
+
+// Names of the built-in standard shaders that have the alpha test.
+const STANDARD_SHADER_NAMES: [&str; 3] = [
+    "StandardShader",
+    "StandardTwoSidesShader",
+    "StandardVertexColorShader",
+];
+
+fn default_property_value(
+    kind: &PropertyKind,
+    resource_manager: &Option<ResourceManager>,
+) -> PropertyValue {
+    match kind {
+        PropertyKind::Float(value) => PropertyValue::Float(*value),
+        PropertyKind::Int(value) => PropertyValue::Int(*value),
+        PropertyKind::UInt(value) => PropertyValue::UInt(*value),
+        PropertyKind::Vector2(value) => PropertyValue::Vector2(*value),
+        PropertyKind::Vector3(value) => PropertyValue::Vector3(*value),
+        PropertyKind::Vector4(value) => PropertyValue::Vector4(*value),
+        PropertyKind::Color { r, g, b, a } => {
+            PropertyValue::Color(Color::from_rgba(*r, *g, *b, *a))
+        }
+        PropertyKind::Matrix2(value) => PropertyValue::Matrix2(*value),
+        PropertyKind::Matrix3(value) => PropertyValue::Matrix3(*value),
+        PropertyKind::Matrix4(value) => PropertyValue::Matrix4(*value),
+        PropertyKind::Bool(value) => PropertyValue::Bool(*value),
+        PropertyKind::Sampler {
+            default,
+            fallback: usage,
+        } => PropertyValue::Sampler {
+            value: default
+                .as_ref()
+                .and_then(|path| resource_manager.clone().map(|rm| rm.request_texture(path))),
+            fallback: *usage,
+        },
+        PropertyKind::FloatArray(value) => PropertyValue::FloatArray(value.clone()),
+        PropertyKind::IntArray(value) => PropertyValue::IntArray(value.clone()),
+        PropertyKind::UIntArray(value) => PropertyValue::UIntArray(value.clone()),
+        PropertyKind::Vector2Array(value) => PropertyValue::Vector2Array(value.clone()),
+        PropertyKind::Vector3Array(value) => PropertyValue::Vector3Array(value.clone()),
+        PropertyKind::Vector4Array(value) => PropertyValue::Vector4Array(value.clone()),
+        PropertyKind::Matrix2Array(value) => PropertyValue::Matrix2Array(value.clone()),
+        PropertyKind::Matrix3Array(value) => PropertyValue::Matrix3Array(value.clone()),
+        PropertyKind::Matrix4Array(value) => PropertyValue::Matrix4Array(value.clone()),
+    }
+}
+
 /// Material defines a set of values for a shader. Materials usually contains textures (diffuse,
 /// normal, height, emission, etc. maps), numerical values (floats, integers), vectors, booleans,
 /// matrices and arrays of each type, except textures. Each parameter can be changed in runtime
@@ -348,6 +436,8 @@ pub struct Material {
     shader: Shader,
     draw_parameters: DrawParameters,
     properties: FxHashMap<ImmutableString, PropertyValue>,
+    #[visit(optional)] // Backward compatibility.
+    cull_mode: CullMode,
 }
 
 /// A set of possible errors that can occur when working with materials.
@@ -428,6 +518,11 @@ impl Material {
         Self::from_shader(Shader::standard_terrain(), None)
     }
 
+    /// Creates new instance of standard material that multiplies diffuse color by vertex colors.
+    pub fn standard_vertex_color() -> Self {
+        Self::from_shader(Shader::standard_vertex_color(), None)
+    }
+
     /// Creates a new material instance with given shader. Each property will have default values
     /// defined in the shader.
     ///
@@ -465,40 +560,7 @@ impl Material {
 
         let mut property_values = FxHashMap::default();
         for property_definition in data.definition.properties.iter() {
-            let value = match &property_definition.kind {
-                PropertyKind::Float(value) => PropertyValue::Float(*value),
-                PropertyKind::Int(value) => PropertyValue::Int(*value),
-                PropertyKind::UInt(value) => PropertyValue::UInt(*value),
-                PropertyKind::Vector2(value) => PropertyValue::Vector2(*value),
-                PropertyKind::Vector3(value) => PropertyValue::Vector3(*value),
-                PropertyKind::Vector4(value) => PropertyValue::Vector4(*value),
-                PropertyKind::Color { r, g, b, a } => {
-                    PropertyValue::Color(Color::from_rgba(*r, *g, *b, *a))
-                }
-                PropertyKind::Matrix2(value) => PropertyValue::Matrix2(*value),
-                PropertyKind::Matrix3(value) => PropertyValue::Matrix3(*value),
-                PropertyKind::Matrix4(value) => PropertyValue::Matrix4(*value),
-                PropertyKind::Bool(value) => PropertyValue::Bool(*value),
-                PropertyKind::Sampler {
-                    default,
-                    fallback: usage,
-                } => PropertyValue::Sampler {
-                    value: default.as_ref().and_then(|path| {
-                        resource_manager.clone().map(|rm| rm.request_texture(path))
-                    }),
-                    fallback: *usage,
-                },
-                PropertyKind::FloatArray(value) => PropertyValue::FloatArray(value.clone()),
-                PropertyKind::IntArray(value) => PropertyValue::IntArray(value.clone()),
-                PropertyKind::UIntArray(value) => PropertyValue::UIntArray(value.clone()),
-                PropertyKind::Vector2Array(value) => PropertyValue::Vector2Array(value.clone()),
-                PropertyKind::Vector3Array(value) => PropertyValue::Vector3Array(value.clone()),
-                PropertyKind::Vector4Array(value) => PropertyValue::Vector4Array(value.clone()),
-                PropertyKind::Matrix2Array(value) => PropertyValue::Matrix2Array(value.clone()),
-                PropertyKind::Matrix3Array(value) => PropertyValue::Matrix3Array(value.clone()),
-                PropertyKind::Matrix4Array(value) => PropertyValue::Matrix4Array(value.clone()),
-            };
-
+            let value = default_property_value(&property_definition.kind, &resource_manager);
             property_values.insert(ImmutableString::new(&property_definition.name), value);
         }
 
@@ -508,10 +570,35 @@ impl Material {
             shader,
             draw_parameters: Default::default(),
             properties: property_values,
+            cull_mode: Default::default(),
         }
     }
 
     pub(crate) fn resolve(&mut self, resource_manager: ResourceManager) {
+        // Shader could get new properties after the material was saved, add them with default
+        // values so they could be used and edited.
+        if let ResourceState::Ok(shader_state) = &*self.shader.state() {
+            // Standard shaders did alpha test unconditionally before it became optional, keep it
+            // enabled for materials saved before that, so their cutouts do not become opaque.
+            let always_alpha_tested =
+                STANDARD_SHADER_NAMES.contains(&shader_state.definition.name.as_str());
+
+            for property_definition in shader_state.definition.properties.iter() {
+                let name = ImmutableString::new(&property_definition.name);
+                if !self.properties.contains_key(&name) {
+                    let value = if always_alpha_tested && property_definition.name == "alphaTest" {
+                        PropertyValue::Bool(true)
+                    } else {
+                        default_property_value(
+                            &property_definition.kind,
+                            &Some(resource_manager.clone()),
+                        )
+                    };
+                    self.properties.insert(name, value);
+                }
+            }
+        }
+
         for value in self.properties.values_mut() {
             if let PropertyValue::Sampler {
                 value: Some(texture),
@@ -682,6 +769,56 @@ impl Material {
     pub fn properties(&self) -> &FxHashMap<ImmutableString, PropertyValue> {
         &self.properties
     }
+
+    /// Sets new cull mode of the material and returns the old one. See [`CullMode`] docs for more
+    /// info.
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) -> CullMode {
+        std::mem::replace(&mut self.cull_mode, cull_mode)
+    }
+
+    /// Returns current cull mode of the material.
+    pub fn cull_mode(&self) -> CullMode {
+        self.cull_mode
+    }
+
+    /// Saves the material to a file, the file then can be loaded by [`Material::from_file`]. Shader
+    /// and textures are saved as references to their source files.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("Material", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads a material from a file, that was previously saved by [`Material::save`]. Shader and
+    /// textures of the material will be requested from the given resource manager.
+    pub async fn from_file<P: AsRef<Path>>(
+        path: P,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut material = Material::default();
+        material.visit("Material", &mut visitor)?;
+
+        let shader_path = material.shader.state().path().to_path_buf();
+        material.shader = match Shader::standard_shaders()
+            .into_iter()
+            .find(|shader| shader.state().path() == shader_path)
+        {
+            Some(standard_shader) => standard_shader,
+            None => {
+                let shader = resource_manager.request_shader(shader_path);
+                // Shader definition is needed to render the material correctly.
+                shader.clone().await.map_err(|e| {
+                    VisitError::User(format!("Unable to load material shader. Reason: {:?}", e))
+                })?;
+                shader
+            }
+        };
+
+        material.resolve(resource_manager);
+
+        Ok(material)
+    }
 }
 
 /// Shared material is a material instance that can be used across multiple objects. It is useful
@@ -32,6 +32,10 @@ pub const STANDARD_SHADER_SRC: &str = include_str!("standard/standard.shader");
 /// A source code of the standard two-sides shader.
 pub const STANDARD_TWOSIDES_SHADER_SRC: &str = include_str!("standard/standard-two-sides.shader");
 
+/// A source code of the standard shader that multiplies diffuse color by vertex colors.
+pub const STANDARD_VERTEX_COLOR_SHADER_SRC: &str =
+    include_str!("standard/standard-vertex-color.shader");
+
 /// A source code of the standard terrain shader.
 pub const STANDARD_TERRAIN_SHADER_SRC: &str = include_str!("standard/terrain.shader");
 
@@ -66,6 +70,9 @@ impl Visit for ShaderState {
                 self.definition = ShaderDefinition::from_str(STANDARD_TERRAIN_SHADER_SRC).unwrap();
             } else if self.path == Path::new("StandardTwoSides") {
                 self.definition = ShaderDefinition::from_str(STANDARD_TWOSIDES_SHADER_SRC).unwrap();
+            } else if self.path == Path::new("StandardVertexColor") {
+                self.definition =
+                    ShaderDefinition::from_str(STANDARD_VERTEX_COLOR_SHADER_SRC).unwrap();
             }
         }
 
@@ -469,6 +476,7 @@ define_new_resource!(
     /// | fyrox_cameraPosition       | `Vector3`       | Position of the camera.
     /// | fyrox_usePOM               | `bool`          | Whether to use parallax mapping or not.
     /// | fyrox_lightPosition        | `Vector3`       | Light position.
+    /// | fyrox_time                 | `float`         | Time of the scene in seconds, see `Scene::shader_time`.
     ///
     /// To use any of the variables, just define a uniform with appropriate name:
     ///
@@ -562,12 +570,20 @@ impl Shader {
         STANDARD_TWOSIDES.clone()
     }
 
+    /// Returns an instance of standard shader that multiplies diffuse color by vertex colors. It
+    /// has the same properties as the standard shader, but requires meshes to have vertex colors
+    /// (`VertexAttributeUsage::Color` attribute with four `u8` components at location 7).
+    pub fn standard_vertex_color() -> Self {
+        STANDARD_VERTEX_COLOR.clone()
+    }
+
     /// Returns a list of standard shader.
     pub fn standard_shaders() -> Vec<Shader> {
         vec![
             Self::standard(),
             Self::standard_terrain(),
             Self::standard_twosides(),
+            Self::standard_vertex_color(),
         ]
     }
 }
@@ -596,6 +612,12 @@ lazy_static! {
     )));
 }
 
+lazy_static! {
+    static ref STANDARD_VERTEX_COLOR: Shader = Shader(Resource::new(ResourceState::Ok(
+        ShaderState::from_str(STANDARD_VERTEX_COLOR_SHADER_SRC, "StandardVertexColor").unwrap(),
+    )));
+}
+
 #[cfg(test)]
 mod test {
     use crate::material::shader::{
@@ -51,6 +51,27 @@
             name: "diffuseColor",
             kind: Color(r: 255, g: 255, b: 255, a: 255),
         ),
+        (
+            name: "flipNormalGreen",
+            kind: Bool(false),
+        ),
+        (
+            // Enables alpha test (cutout): fragments with alpha less than the threshold are
+            // discarded, the rest are opaque. Useful for foliage, fences, etc. Transparent meshes
+            // (forward render path) use blending instead and are not affected.
+            name: "alphaTest",
+            kind: Bool(false),
+        ),
+        (
+            name: "alphaTestThreshold",
+            kind: Float(0.5),
+        ),
+        (
+            // Alpha test threshold of shadow passes, it is lower than the main one to keep
+            // shadows of thin cutouts (leaves, grass) solid.
+            name: "alphaTestShadowThreshold",
+            kind: Float(0.2),
+        ),
     ],
 
     passes: [
@@ -173,6 +194,9 @@
                 uniform uint layerIndex;
                 uniform vec3 emissionStrength;
                 uniform vec4 diffuseColor;
+                uniform bool flipNormalGreen;
+                uniform bool alphaTest;
+                uniform float alphaTestThreshold;
 
                 // Define uniforms with reserved names. Fyrox will automatically provide
                 // required data to these uniforms.
@@ -202,12 +226,16 @@
                     outColor = diffuseColor * texture(diffuseTexture, tc);
 
                     // Alpha test.
-                    if (outColor.a < 0.5) {
+                    if (alphaTest && outColor.a < alphaTestThreshold) {
                         discard;
                     }
                     outColor.a = 1.0;
 
                     vec4 n = normalize(texture(normalTexture, tc) * 2.0 - 1.0);
+                    // Normal maps for DirectX have inverted green channel.
+                    if (flipNormalGreen) {
+                        n.y = -n.y;
+                    }
                     outNormal = vec4(normalize(tangentSpace * n.xyz) * 0.5 + 0.5, 1.0);
 
                     outMaterial.x = texture(metallicTexture, tc).r;
@@ -371,12 +399,14 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool alphaTest;
+                uniform float alphaTestShadowThreshold;
 
                 in vec2 texCoord;
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (alphaTest && texture(diffuseTexture, texCoord).a < alphaTestShadowThreshold) discard;
                 }
                 "#,
         ),
@@ -442,12 +472,14 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool alphaTest;
+                uniform float alphaTestShadowThreshold;
 
                 in vec2 texCoord;
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (alphaTest && texture(diffuseTexture, texCoord).a < alphaTestShadowThreshold) discard;
                 }
                 "#,
         ),
@@ -516,6 +548,8 @@
             fragment_shader:
                 r#"
                 uniform sampler2D diffuseTexture;
+                uniform bool alphaTest;
+                uniform float alphaTestShadowThreshold;
 
                 uniform vec3 fyrox_lightPosition;
 
@@ -526,7 +560,7 @@
 
                 void main()
                 {
-                    if (texture(diffuseTexture, texCoord).a < 0.2) discard;
+                    if (alphaTest && texture(diffuseTexture, texCoord).a < alphaTestShadowThreshold) discard;
                     depth = length(fyrox_lightPosition - worldPosition);
                 }
                 "#,
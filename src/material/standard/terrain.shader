@@ -55,6 +55,10 @@
             name: "diffuseColor",
             kind: Color(r: 255, g: 255, b: 255, a: 255),
         ),
+        (
+            name: "flipNormalGreen",
+            kind: Bool(false),
+        ),
     ],
 
     passes: [
@@ -146,6 +150,7 @@
                 uniform vec3 emissionStrength;
                 uniform sampler2D maskTexture;
                 uniform vec4 diffuseColor;
+                uniform bool flipNormalGreen;
 
                 // Define uniforms with reserved names. Fyrox will automatically provide
                 // required data to these uniforms.
@@ -175,6 +180,10 @@
                     outColor = diffuseColor * texture(diffuseTexture, tc);
 
                     vec4 n = normalize(texture(normalTexture, tc) * 2.0 - 1.0);
+                    // Normal maps for DirectX have inverted green channel.
+                    if (flipNormalGreen) {
+                        n.y = -n.y;
+                    }
                     outNormal = vec4(normalize(tangentSpace * n.xyz) * 0.5 + 0.5, 1.0);
 
                     outMaterial.x = texture(metallicTexture, tc).r;
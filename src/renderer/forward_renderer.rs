@@ -37,6 +37,7 @@ pub(crate) struct ForwardRenderContext<'a, 'b> {
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub time: f32,
 }
 
 impl ForwardRenderer {
@@ -64,6 +65,7 @@ impl ForwardRenderer {
             white_dummy,
             normal_dummy,
             black_dummy,
+            time,
         } = args;
 
         let initial_view_projection = camera.view_projection_matrix();
@@ -80,6 +82,8 @@ impl ForwardRenderer {
                 .get(state, material.shader())
                 .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
             {
+                let draw_params = material.cull_mode().apply(&render_pass.draw_params);
+
                 for instance in batch.instances.iter() {
                     if camera.visibility_cache.is_visible(instance.owner) {
                         let view_projection = if instance.depth_offset != 0.0 {
@@ -95,7 +99,7 @@ impl ForwardRenderer {
                             state,
                             viewport,
                             &render_pass.program,
-                            &render_pass.draw_params,
+                            &draw_params,
                             |mut program_binding| {
                                 apply_material(MaterialContext {
                                     material: &material,
@@ -108,6 +112,7 @@ impl ForwardRenderer {
                                     camera_position: &camera.global_position(),
                                     use_pom: quality_settings.use_parallax_mapping,
                                     light_position: &Default::default(),
+                                    time,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
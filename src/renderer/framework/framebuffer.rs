@@ -114,6 +114,15 @@ unsafe fn set_attachment(state: &mut PipelineState, gl_attachment_kind: u32, tex
                 0,
             );
         }
+        GpuTextureKind::Array { .. } => {
+            state.gl.framebuffer_texture_layer(
+                glow::FRAMEBUFFER,
+                gl_attachment_kind,
+                Some(texture.id()),
+                0,
+                0,
+            );
+        }
     }
 }
 
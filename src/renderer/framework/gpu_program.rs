@@ -32,6 +32,7 @@ pub enum BuiltInUniform {
     CameraPosition,
     UsePOM,
     LightPosition,
+    Time,
     // Must be last.
     Count,
 }
@@ -431,6 +432,7 @@ fn fetch_built_in_uniform_locations(
         fetch_uniform_location(state, program, "fyrox_usePOM");
     locations[BuiltInUniform::LightPosition as usize] =
         fetch_uniform_location(state, program, "fyrox_lightPosition");
+    locations[BuiltInUniform::Time as usize] = fetch_uniform_location(state, program, "fyrox_time");
 
     locations
 }
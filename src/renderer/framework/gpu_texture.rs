@@ -27,6 +27,11 @@ pub enum GpuTextureKind {
         height: usize,
         depth: usize,
     },
+    Array {
+        width: usize,
+        height: usize,
+        layers: usize,
+    },
 }
 
 impl From<TextureKind> for GpuTextureKind {
@@ -52,6 +57,15 @@ impl From<TextureKind> for GpuTextureKind {
                 height: height as usize,
                 depth: depth as usize,
             },
+            TextureKind::Array {
+                width,
+                height,
+                layers,
+            } => GpuTextureKind::Array {
+                width: width as usize,
+                height: height as usize,
+                layers: layers as usize,
+            },
         }
     }
 }
@@ -63,6 +77,7 @@ impl GpuTextureKind {
             Self::Rectangle { .. } => glow::TEXTURE_2D,
             Self::Cube { .. } => glow::TEXTURE_CUBE_MAP,
             Self::Volume { .. } => glow::TEXTURE_3D,
+            Self::Array { .. } => glow::TEXTURE_2D_ARRAY,
         }
     }
 }
@@ -630,6 +645,21 @@ impl<'a> TextureBinding<'a> {
                         break 'mip_loop;
                     }
                 }
+                GpuTextureKind::Array {
+                    width,
+                    height,
+                    layers,
+                } => {
+                    if let (Some(width), Some(height)) = (
+                        width.checked_shr(mip as u32),
+                        height.checked_shr(mip as u32),
+                    ) {
+                        desired_byte_count +=
+                            layers * image_2d_size_bytes(pixel_kind, width, height);
+                    } else {
+                        break 'mip_loop;
+                    }
+                }
             };
         }
 
@@ -937,6 +967,55 @@ impl<'a> TextureBinding<'a> {
                                 );
                             }
 
+                            mip_byte_offset += size as usize;
+                        } else {
+                            // No need to add degenerated mips (0x1, 0x2, 4x0, etc).
+                            break 'mip_loop2;
+                        }
+                    }
+                    GpuTextureKind::Array {
+                        width,
+                        height,
+                        layers,
+                    } => {
+                        if let (Some(width), Some(height)) = (
+                            width.checked_shr(mip as u32),
+                            height.checked_shr(mip as u32),
+                        ) {
+                            // Every mip level contains all the layers one after another.
+                            let size =
+                                (layers * image_2d_size_bytes(pixel_kind, width, height)) as i32;
+                            let pixels = data.map(|data| {
+                                &data[mip_byte_offset..(mip_byte_offset + size as usize)]
+                            });
+
+                            if is_compressed {
+                                self.state.gl.compressed_tex_image_3d(
+                                    glow::TEXTURE_2D_ARRAY,
+                                    mip as i32,
+                                    internal_format as i32,
+                                    width as i32,
+                                    height as i32,
+                                    layers as i32,
+                                    0,
+                                    size,
+                                    pixels.ok_or(FrameworkError::EmptyTextureData)?,
+                                );
+                            } else {
+                                self.state.gl.tex_image_3d(
+                                    glow::TEXTURE_2D_ARRAY,
+                                    mip as i32,
+                                    internal_format as i32,
+                                    width as i32,
+                                    height as i32,
+                                    layers as i32,
+                                    0,
+                                    format,
+                                    type_,
+                                    pixels,
+                                );
+                            }
+
                             mip_byte_offset += size as usize;
                         } else {
                             // No need to add degenerated mips (0x1, 0x2, 4x0, etc).
@@ -965,6 +1044,9 @@ impl GpuTexture {
     /// In case of Cube texture, `bytes` should contain all 6 cube faces ordered like so,
     /// +X, -X, +Y, -Y, +Z, -Z. Cube mips must follow one after another.
     ///
+    /// In case of Array texture, every mip level should contain all the layers one after
+    /// another, mips must follow one after another.
+    ///
     /// Produced texture can be used as render target for framebuffer, in this case `data`
     /// parameter can be None.
     ///
@@ -168,6 +168,23 @@ impl Default for BlendFunc {
     }
 }
 
+#[derive(Copy, Clone, PartialOrd, PartialEq, Hash, Debug, Eq)]
+#[repr(u32)]
+pub enum PolygonFillMode {
+    /// Only vertices of polygons are rasterized.
+    Point = glow::POINT,
+    /// Only edges of polygons are rasterized.
+    Line = glow::LINE,
+    /// Polygons are fully rasterized, this is the default mode.
+    Fill = glow::FILL,
+}
+
+impl Default for PolygonFillMode {
+    fn default() -> Self {
+        Self::Fill
+    }
+}
+
 pub struct PipelineState {
     pub gl: glow::Context,
 
@@ -181,6 +198,7 @@ pub struct PipelineState {
     stencil_test: bool,
     cull_face: CullFace,
     culling: bool,
+    polygon_fill_mode: PolygonFillMode,
     stencil_mask: u32,
     clear_color: Color,
     clear_stencil: i32,
@@ -342,6 +360,7 @@ impl PipelineState {
             stencil_test: false,
             cull_face: CullFace::Back,
             culling: false,
+            polygon_fill_mode: Default::default(),
             stencil_mask: 0xFFFF_FFFF,
             clear_color: Color::from_rgba(0, 0, 0, 0),
             clear_stencil: 0,
@@ -480,6 +499,19 @@ impl PipelineState {
         }
     }
 
+    pub fn set_polygon_fill_mode(&mut self, polygon_fill_mode: PolygonFillMode) {
+        if self.polygon_fill_mode != polygon_fill_mode {
+            self.polygon_fill_mode = polygon_fill_mode;
+
+            // WebGL supports only `Fill` mode.
+            #[cfg(not(target_arch = "wasm32"))]
+            unsafe {
+                self.gl
+                    .polygon_mode(glow::FRONT_AND_BACK, self.polygon_fill_mode as u32)
+            }
+        }
+    }
+
     pub fn set_stencil_mask(&mut self, stencil_mask: u32) {
         if self.stencil_mask != stencil_mask {
             self.stencil_mask = stencil_mask;
@@ -67,6 +67,7 @@ pub(crate) struct GBufferRenderContext<'a, 'b> {
     pub black_dummy: Rc<RefCell<GpuTexture>>,
     pub use_parallax_mapping: bool,
     pub graph: &'b Graph,
+    pub time: f32,
 }
 
 impl GBuffer {
@@ -271,6 +272,7 @@ impl GBuffer {
             normal_dummy,
             black_dummy,
             graph,
+            time,
             ..
         } = args;
 
@@ -297,6 +299,8 @@ impl GBuffer {
                 .get(state, material.shader())
                 .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
             {
+                let draw_params = material.cull_mode().apply(&render_pass.draw_params);
+
                 for instance in batch.instances.iter() {
                     if camera.visibility_cache.is_visible(instance.owner) {
                         let apply_uniforms = |mut program_binding: GpuProgramBinding| {
@@ -319,6 +323,7 @@ impl GBuffer {
                                 camera_position: &camera.global_position(),
                                 use_pom: use_parallax_mapping,
                                 light_position: &Default::default(),
+                                time,
                                 normal_dummy: normal_dummy.clone(),
                                 white_dummy: white_dummy.clone(),
                                 black_dummy: black_dummy.clone(),
@@ -330,7 +335,7 @@ impl GBuffer {
                             state,
                             viewport,
                             &render_pass.program,
-                            &render_pass.draw_params,
+                            &draw_params,
                             apply_uniforms,
                         );
                     }
@@ -530,6 +530,7 @@ impl DeferredLightRenderer {
                         normal_dummy.clone(),
                         white_dummy.clone(),
                         black_dummy.clone(),
+                        scene.shader_time,
                     );
 
                     light_stats.spot_shadow_maps_rendered += 1;
@@ -548,6 +549,7 @@ impl DeferredLightRenderer {
                                 normal_dummy: normal_dummy.clone(),
                                 white_dummy: white_dummy.clone(),
                                 black_dummy: black_dummy.clone(),
+                                time: scene.shader_time,
                             });
 
                     light_stats.point_shadow_maps_rendered += 1;
@@ -565,6 +567,7 @@ impl DeferredLightRenderer {
                         normal_dummy: normal_dummy.clone(),
                         white_dummy: white_dummy.clone(),
                         black_dummy: black_dummy.clone(),
+                        time: scene.shader_time,
                     });
 
                     light_stats.csm_rendered += 1;
@@ -65,7 +65,7 @@ use crate::{
                 Coordinate, GpuTexture, GpuTextureKind, MagnificationFilter, MinificationFilter,
                 PixelKind, WrapMode,
             },
-            state::{PipelineState, PipelineStatistics},
+            state::{PipelineState, PipelineStatistics, PolygonFillMode},
         },
         fxaa::FxaaRenderer,
         gbuffer::{GBuffer, GBufferRenderContext},
@@ -77,7 +77,7 @@ use crate::{
         ui_renderer::{UiRenderContext, UiRenderer},
     },
     resource::texture::{Texture, TextureKind},
-    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer},
+    scene::{camera::Camera, mesh::surface::SurfaceData, Scene, SceneContainer, SceneRenderMode},
     utils::log::{Log, MessageKind},
 };
 use fxhash::FxHashMap;
@@ -912,6 +912,7 @@ pub(crate) struct MaterialContext<'a, 'b, 'c> {
     pub camera_position: &'a Vector3<f32>,
     pub use_pom: bool,
     pub light_position: &'a Vector3<f32>,
+    pub time: f32,
 
     // Fallback samplers.
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
@@ -948,6 +949,9 @@ pub(crate) fn apply_material(ctx: MaterialContext) {
         ctx.program_binding
             .set_vector3(location, ctx.light_position);
     }
+    if let Some(location) = &built_in_uniforms[BuiltInUniform::Time as usize] {
+        ctx.program_binding.set_f32(location, ctx.time);
+    }
 
     // Apply material properties.
     for (name, value) in ctx.material.properties() {
@@ -1497,6 +1501,10 @@ impl Renderer {
             {
                 let viewport = camera.viewport_pixels(frame_size);
 
+                if scene.render_mode == SceneRenderMode::Wireframe {
+                    state.set_polygon_fill_mode(PolygonFillMode::Line);
+                }
+
                 self.statistics += scene_associated_data.gbuffer.fill(GBufferRenderContext {
                     state,
                     camera,
@@ -1510,8 +1518,11 @@ impl Renderer {
                     white_dummy: self.white_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
                     graph,
+                    time: scene.shader_time,
                 });
 
+                state.set_polygon_fill_mode(PolygonFillMode::Fill);
+
                 scene_associated_data.copy_depth_stencil_to_scene_framebuffer(state);
 
                 scene_associated_data.hdr_scene_framebuffer.clear(
@@ -1595,6 +1606,7 @@ impl Renderer {
                     white_dummy: self.white_dummy.clone(),
                     normal_dummy: self.normal_dummy.clone(),
                     black_dummy: self.black_dummy.clone(),
+                    time: scene.shader_time,
                 });
 
                 for render_pass in self.scene_render_passes.iter() {
@@ -1648,6 +1660,28 @@ impl Renderer {
                     &mut self.texture_cache,
                 );
 
+                // Debug render modes replace the final frame with the contents of the G-Buffer.
+                let debug_texture = match scene.render_mode {
+                    SceneRenderMode::Lit => None,
+                    SceneRenderMode::Unlit | SceneRenderMode::Wireframe => {
+                        Some(scene_associated_data.gbuffer.diffuse_texture())
+                    }
+                    SceneRenderMode::Normals => {
+                        Some(scene_associated_data.gbuffer.normal_texture())
+                    }
+                };
+                if let Some(debug_texture) = debug_texture {
+                    let quad = &self.quad;
+                    self.statistics.geometry += blit_pixels(
+                        state,
+                        &mut scene_associated_data.ldr_scene_framebuffer,
+                        debug_texture,
+                        &self.flat_shader,
+                        viewport,
+                        quad,
+                    );
+                }
+
                 // Apply FXAA if needed.
                 if self.quality_settings.fxaa {
                     self.statistics.geometry += self.fxaa_renderer.render(
@@ -107,6 +107,7 @@ pub(crate) struct CsmRenderContext<'a, 'c> {
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub time: f32,
 }
 
 impl CsmRenderer {
@@ -155,6 +156,7 @@ impl CsmRenderer {
             normal_dummy,
             white_dummy,
             black_dummy,
+            time,
         } = ctx;
 
         let light_direction = -light
@@ -269,7 +271,7 @@ impl CsmRenderer {
                             viewport,
                             &render_pass.program,
                             &DrawParameters {
-                                cull_face: Some(CullFace::Back),
+                                cull_face: material.cull_mode().cull_face(Some(CullFace::Back)),
                                 color_write: ColorMask::all(false),
                                 depth_write: true,
                                 stencil_test: None,
@@ -289,6 +291,7 @@ impl CsmRenderer {
                                     camera_position: &camera.global_position(),
                                     use_pom: false,
                                     light_position: &Default::default(),
+                                    time,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
@@ -51,6 +51,7 @@ pub(crate) struct PointShadowMapRenderContext<'a> {
     pub normal_dummy: Rc<RefCell<GpuTexture>>,
     pub white_dummy: Rc<RefCell<GpuTexture>>,
     pub black_dummy: Rc<RefCell<GpuTexture>>,
+    pub time: f32,
 }
 
 impl PointShadowMapRenderer {
@@ -200,6 +201,7 @@ impl PointShadowMapRenderer {
             normal_dummy,
             white_dummy,
             black_dummy,
+            time,
         } = args;
 
         let framebuffer = &mut self.cascades[cascade];
@@ -237,6 +239,8 @@ impl PointShadowMapRenderer {
                     .get(state, material.shader())
                     .and_then(|shader_set| shader_set.render_passes.get(&self.render_pass_name))
                 {
+                    let draw_params = material.cull_mode().apply(&render_pass.draw_params);
+
                     for instance in batch.instances.iter() {
                         if should_cast_shadows(instance, &frustum) {
                             statistics += framebuffer.draw(
@@ -244,7 +248,7 @@ impl PointShadowMapRenderer {
                                 state,
                                 viewport,
                                 &render_pass.program,
-                                &render_pass.draw_params,
+                                &draw_params,
                                 |mut program_binding| {
                                     apply_material(MaterialContext {
                                         material: &material,
@@ -258,6 +262,7 @@ impl PointShadowMapRenderer {
                                         camera_position: &Default::default(),
                                         use_pom: false,
                                         light_position: &light_pos,
+                                        time,
                                         normal_dummy: normal_dummy.clone(),
                                         white_dummy: white_dummy.clone(),
                                         black_dummy: black_dummy.clone(),
@@ -127,6 +127,7 @@ impl SpotShadowMapRenderer {
         normal_dummy: Rc<RefCell<GpuTexture>>,
         white_dummy: Rc<RefCell<GpuTexture>>,
         black_dummy: Rc<RefCell<GpuTexture>>,
+        time: f32,
     ) -> RenderPassStatistics {
         scope_profile!();
 
@@ -156,7 +157,7 @@ impl SpotShadowMapRenderer {
                             viewport,
                             &render_pass.program,
                             &DrawParameters {
-                                cull_face: Some(CullFace::Back),
+                                cull_face: material.cull_mode().cull_face(Some(CullFace::Back)),
                                 color_write: ColorMask::all(false),
                                 depth_write: true,
                                 stencil_test: None,
@@ -176,6 +177,7 @@ impl SpotShadowMapRenderer {
                                     camera_position: &Default::default(),
                                     use_pom: false,
                                     light_position: &Default::default(),
+                                    time,
                                     normal_dummy: normal_dummy.clone(),
                                     white_dummy: white_dummy.clone(),
                                     black_dummy: black_dummy.clone(),
@@ -55,6 +55,7 @@ use crate::{
     utils::{
         log::{Log, MessageKind},
         raw_mesh::RawMeshBuilder,
+        uvgen,
     },
 };
 use fxhash::{FxHashMap, FxHashSet};
@@ -291,6 +292,30 @@ async fn create_surfaces(
                 )
             }
             for (name, texture_handle) in material.textures.iter() {
+                // Transparency maps are usually made of alpha channel of the diffuse texture,
+                // such materials are cutouts (foliage, fences, etc.) and should be alpha tested.
+                // Diffuse textures are alpha tested too, like it was always done by the standard
+                // shader before the alpha test became optional, it does nothing for opaque ones.
+                if name.contains("TransparentColor")
+                    || name.contains("TransparencyFactor")
+                    || name.contains("transparent_color")
+                    || name.contains("DiffuseColor")
+                    || name.contains("diffuse_color")
+                {
+                    if let Err(e) = surface.material().lock().set_property(
+                        &ImmutableString::new("alphaTest"),
+                        PropertyValue::Bool(true),
+                    ) {
+                        Log::writeln(
+                            MessageKind::Error,
+                            format!(
+                                "Failed to set alphaTest property for material. Reason: {:?}",
+                                e,
+                            ),
+                        )
+                    }
+                }
+
                 let texture = fbx_scene.get(*texture_handle).as_texture()?;
                 let path = texture.get_file_path();
                 if let Some(filename) = path.file_name() {
@@ -497,6 +522,21 @@ async fn convert_mesh(
             }
         }
 
+        // Skinned meshes cannot have baked lighting, so there is no need for the second channel.
+        if model_import_options.generate_lightmap_uvs && geom.deformers.is_empty() {
+            for surface in surfaces.iter_mut() {
+                if let Err(e) =
+                    uvgen::generate_uvs(&mut surface.data().lock(), uvgen::DEFAULT_SPACING)
+                {
+                    Log::warn(format!(
+                        "Failed to generate lightmap UVs for {} model. Reason: {:?}",
+                        model_path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
         for surface in surfaces {
             mesh_surfaces.push(surface);
         }
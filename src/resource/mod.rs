@@ -6,3 +6,4 @@ pub mod curve;
 pub mod fbx;
 pub mod model;
 pub mod texture;
+pub mod video;
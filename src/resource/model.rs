@@ -372,6 +372,12 @@ pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+
+    /// Whether to generate a second UV channel (used by lightmaps) for static meshes on import.
+    /// Generation is done by the simple box-mapping generator from [`crate::utils::uvgen`], which
+    /// might take some time on large meshes. Default is `false`.
+    #[serde(default)]
+    pub generate_lightmap_uvs: bool,
 }
 
 impl ImportOptions for ModelImportOptions {}
@@ -484,6 +490,26 @@ impl ModelData {
         self.scene.graph.find_by_name_from_root(name)
     }
 
+    /// Tries to find a node in the model from which the given instance node was instantiated. Depending
+    /// on the source format, nodes are matched either by their names (for example, for FBX) or by the
+    /// original handles (for native scenes).
+    pub fn find_original_node(&self, instance_node: &Node) -> Option<(Handle<Node>, &Node)> {
+        let graph = &self.scene.graph;
+        match self.mapping {
+            // For some models we can resolve it only by names of nodes, but this is not reliable way of
+            // doing this, because some editors allow nodes to have same names for objects, but here we'll
+            // assume that modellers will not create models with duplicated names and user of the engine
+            // reads log messages.
+            NodeMapping::UseNames => graph
+                .pair_iter()
+                .find(|(_, node)| node.name() == instance_node.name()),
+            NodeMapping::UseHandles => {
+                let original = instance_node.original_handle_in_resource();
+                graph.try_get(original).map(|node| (original, node))
+            }
+        }
+    }
+
     pub(crate) fn get_scene_mut(&mut self) -> &mut Scene {
         &mut self.scene
     }
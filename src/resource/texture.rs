@@ -26,7 +26,7 @@ use crate::{
         futures::io::Error,
         io::{self, FileLoadError},
         reflect::prelude::*,
-        visitor::{PodVecView, Visit, VisitError, VisitResult, Visitor},
+        visitor::{PodVecView, Versioned, Visit, VisitError, VisitResult, Visitor},
     },
     engine::resource_manager::options::ImportOptions,
 };
@@ -76,6 +76,16 @@ pub enum TextureKind {
         /// Depth of the volume.
         depth: u32,
     },
+    /// Array of 2D textures of the same size, in shaders it is available as `sampler2DArray`.
+    /// Mip levels reduce only width and height of the layers, amount of layers stays the same.
+    Array {
+        /// Width of a layer.
+        width: u32,
+        /// Height of a layer.
+        height: u32,
+        /// Amount of layers.
+        layers: u32,
+    },
 }
 
 impl Default for TextureKind {
@@ -96,6 +106,7 @@ impl Visit for TextureKind {
             TextureKind::Rectangle { .. } => 1,
             TextureKind::Cube { .. } => 2,
             TextureKind::Volume { .. } => 3,
+            TextureKind::Array { .. } => 4,
         };
         id.visit("Id", &mut region)?;
         if region.is_reading() {
@@ -114,6 +125,11 @@ impl Visit for TextureKind {
                     height: 0,
                     depth: 0,
                 },
+                4 => TextureKind::Array {
+                    width: 0,
+                    height: 0,
+                    layers: 0,
+                },
                 _ => {
                     return VisitResult::Err(VisitError::User(format!(
                         "Invalid texture kind {}!",
@@ -143,6 +159,15 @@ impl Visit for TextureKind {
                 height.visit("Height", &mut region)?;
                 depth.visit("Depth", &mut region)?;
             }
+            TextureKind::Array {
+                width,
+                height,
+                layers,
+            } => {
+                width.visit("Width", &mut region)?;
+                height.visit("Height", &mut region)?;
+                layers.visit("Layers", &mut region)?;
+            }
         }
 
         Ok(())
@@ -212,10 +237,31 @@ impl ResourceData for TextureData {
     }
 }
 
+/// Format history:
+///
+/// - `0` - data saved before the versioning was introduced.
+/// - `1` - the version is written, the rest of the format is the same as in `0`.
+impl Versioned for TextureData {
+    const VERSION: u32 = 1;
+
+    fn migrate_from(&mut self, version: u32) -> VisitResult {
+        match version {
+            // Version 1 only added the version field, nothing to convert.
+            0 => Ok(()),
+            _ => Err(VisitError::User(format!(
+                "Unable to migrate texture data of version {}!",
+                version
+            ))),
+        }
+    }
+}
+
 impl Visit for TextureData {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
 
+        let version = region.visit_version::<Self>()?;
+
         let mut kind = self.pixel_kind.id();
         kind.visit("KindId", &mut region)?;
         if region.is_reading() {
@@ -242,6 +288,10 @@ impl Visit for TextureData {
             bytes_view.visit("Data", &mut region)?;
         }
 
+        if region.is_reading() && version < Self::VERSION {
+            self.migrate_from(version)?;
+        }
+
         Ok(())
     }
 }
@@ -476,6 +526,48 @@ impl Texture {
             TextureData::from_bytes(kind, pixel_kind, bytes, serialize_content)?,
         ))))
     }
+
+    /// Creates new array texture from a set of rectangle textures, see
+    /// [`TextureData::from_layers`] for more info.
+    pub fn from_layers(
+        layers: &[TextureData],
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        Ok(Self(Resource::new(TextureState::Ok(
+            TextureData::from_layers(layers, serialize_content)?,
+        ))))
+    }
+
+    /// Creates new array texture from a strip of frames, see [`TextureData::from_strip`] for
+    /// more info.
+    pub fn from_strip(
+        strip: &TextureData,
+        layers: u32,
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        Ok(Self(Resource::new(TextureState::Ok(
+            TextureData::from_strip(strip, layers, serialize_content)?,
+        ))))
+    }
+
+    /// Creates new cube texture from six faces, see [`TextureData::from_cube_faces`] for more
+    /// info.
+    pub fn from_cube_faces(
+        faces: &[&TextureData],
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        Ok(Self(Resource::new(TextureState::Ok(
+            TextureData::from_cube_faces(faces, serialize_content)?,
+        ))))
+    }
+
+    /// Creates new cube texture from an image with faces in a cross layout, see
+    /// [`TextureData::from_cross`] for more info.
+    pub fn from_cross(cross: &TextureData, serialize_content: bool) -> Result<Self, TextureError> {
+        Ok(Self(Resource::new(TextureState::Ok(
+            TextureData::from_cross(cross, serialize_content)?,
+        ))))
+    }
 }
 
 /// The texture magnification function is used when the pixel being textured maps to an area
@@ -754,6 +846,31 @@ impl TexturePixelKind {
     fn id(self) -> u32 {
         self as u32
     }
+
+    /// Returns `true` if the pixel kind is one of block-compressed formats.
+    pub fn is_compressed(self) -> bool {
+        matches!(
+            self,
+            Self::DXT1RGB
+                | Self::DXT1RGBA
+                | Self::DXT3RGBA
+                | Self::DXT5RGBA
+                | Self::R8RGTC
+                | Self::RG8RGTC
+        )
+    }
+
+    /// Returns a pixel kind that stores the same channels without compression. Uncompressed
+    /// pixel kinds are returned as is.
+    pub fn uncompressed_equivalent(self) -> Self {
+        match self {
+            Self::DXT1RGB => Self::RGB8,
+            Self::DXT1RGBA | Self::DXT3RGBA | Self::DXT5RGBA => Self::RGBA8,
+            Self::R8RGTC => Self::R8,
+            Self::RG8RGTC => Self::RG8,
+            _ => self,
+        }
+    }
 }
 
 /// An error that may occur during texture operations.
@@ -767,6 +884,8 @@ pub enum TextureError {
     Image(image::ImageError),
     /// An error occurred during file loading.
     FileLoadError(FileLoadError),
+    /// Layers of an array texture are not compatible with each other.
+    IncompatibleLayers(String),
 }
 
 impl Display for TextureError {
@@ -784,6 +903,9 @@ impl Display for TextureError {
             TextureError::FileLoadError(v) => {
                 write!(f, "A file load error has occurred {v:?}")
             }
+            TextureError::IncompatibleLayers(v) => {
+                write!(f, "Incompatible texture layers: {v}")
+            }
         }
     }
 }
@@ -941,6 +1063,11 @@ fn bytes_in_first_mip(kind: TextureKind, pixel_kind: TexturePixelKind) -> u32 {
             height,
             depth,
         } => width * height * depth,
+        TextureKind::Array {
+            width,
+            height,
+            layers,
+        } => width * height * layers,
     };
     match pixel_kind {
         // Uncompressed formats.
@@ -988,11 +1115,63 @@ fn bytes_in_first_mip(kind: TextureKind, pixel_kind: TexturePixelKind) -> u32 {
                     height,
                     depth,
                 } => ceil_div_4(width) * ceil_div_4(height) * ceil_div_4(depth) * block_size,
+                TextureKind::Array {
+                    width,
+                    height,
+                    layers,
+                } => layers * ceil_div_4(width) * ceil_div_4(height) * block_size,
             }
         }
     }
 }
 
+/// Calculates amount of bytes that is needed to store a texture of given kind with given amount
+/// of mip levels. Every next mip level is two times smaller than the previous one on every axis,
+/// except the amount of layers of array textures.
+pub fn mip_chain_size_bytes(
+    kind: TextureKind,
+    pixel_kind: TexturePixelKind,
+    mip_count: u32,
+) -> usize {
+    (0..mip_count.max(1))
+        .map(|mip| {
+            let reduce = |size: u32| size.checked_shr(mip).unwrap_or_default().max(1);
+            let mip_kind = match kind {
+                TextureKind::Line { length } => TextureKind::Line {
+                    length: reduce(length),
+                },
+                TextureKind::Rectangle { width, height } => TextureKind::Rectangle {
+                    width: reduce(width),
+                    height: reduce(height),
+                },
+                TextureKind::Cube { width, height } => TextureKind::Cube {
+                    width: reduce(width),
+                    height: reduce(height),
+                },
+                TextureKind::Volume {
+                    width,
+                    height,
+                    depth,
+                } => TextureKind::Volume {
+                    width: reduce(width),
+                    height: reduce(height),
+                    depth: reduce(depth),
+                },
+                TextureKind::Array {
+                    width,
+                    height,
+                    layers,
+                } => TextureKind::Array {
+                    width: reduce(width),
+                    height: reduce(height),
+                    layers,
+                },
+            };
+            bytes_in_first_mip(mip_kind, pixel_kind) as usize
+        })
+        .sum()
+}
+
 impl TextureData {
     /// Tries to load a texture from given data in one of the following formats: PNG, BMP, TGA, JPG, DDS, GIF. Use
     /// this method if you want to load a texture from embedded data.
@@ -1234,6 +1413,321 @@ impl TextureData {
         }
     }
 
+    /// Creates new array texture from a set of rectangle textures. All the layers must have the
+    /// same size, pixel kind and amount of mip levels. Sampling parameters are taken from the
+    /// first layer.
+    pub fn from_layers(
+        layers: &[TextureData],
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        let first = layers.first().ok_or_else(|| {
+            TextureError::IncompatibleLayers("at least one layer is required".to_string())
+        })?;
+
+        let (width, height) = match first.kind {
+            TextureKind::Rectangle { width, height } => (width, height),
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        for (index, layer) in layers.iter().enumerate() {
+            let same_size = matches!(layer.kind, TextureKind::Rectangle { width: w, height: h }
+                if w == width && h == height);
+            if !same_size
+                || layer.pixel_kind != first.pixel_kind
+                || layer.mip_count != first.mip_count
+                || layer.bytes.len()
+                    != mip_chain_size_bytes(layer.kind, layer.pixel_kind, layer.mip_count)
+            {
+                return Err(TextureError::IncompatibleLayers(format!(
+                    "layer {} does not match the first layer ({}x{} {:?}, {} mip(s))",
+                    index, width, height, first.pixel_kind, first.mip_count
+                )));
+            }
+        }
+
+        // Every mip level of an array texture contains all the layers, so the data of the layers
+        // is interleaved mip by mip.
+        let mut bytes = Vec::with_capacity(layers.len() * first.bytes.len());
+        let mut layer_offset = 0;
+        for mip in 0..first.mip_count.max(1) {
+            let reduce = |size: u32| size.checked_shr(mip).unwrap_or_default().max(1);
+            let mip_size = bytes_in_first_mip(
+                TextureKind::Rectangle {
+                    width: reduce(width),
+                    height: reduce(height),
+                },
+                first.pixel_kind,
+            ) as usize;
+            for layer in layers {
+                bytes.extend_from_slice(&layer.bytes[layer_offset..(layer_offset + mip_size)]);
+            }
+            layer_offset += mip_size;
+        }
+
+        Ok(Self {
+            path: Default::default(),
+            kind: TextureKind::Array {
+                width,
+                height,
+                layers: layers.len() as u32,
+            },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            pixel_kind: first.pixel_kind,
+            minification_filter: first.minification_filter,
+            magnification_filter: first.magnification_filter,
+            s_wrap_mode: first.s_wrap_mode,
+            t_wrap_mode: first.t_wrap_mode,
+            mip_count: first.mip_count,
+            anisotropy: first.anisotropy,
+            serialize_content,
+            is_render_target: false,
+        })
+    }
+
+    /// Creates new array texture by splitting a strip of frames into layers. Frames of the strip
+    /// are placed side by side if the strip is wider than it is tall, and one under another
+    /// otherwise. Only the first mip level of uncompressed strips is used.
+    pub fn from_strip(
+        strip: &TextureData,
+        layers: u32,
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        let (width, height) = match strip.kind {
+            TextureKind::Rectangle { width, height } => (width, height),
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        if strip.pixel_kind.is_compressed() {
+            return Err(TextureError::UnsupportedFormat);
+        }
+
+        let horizontal = width > height;
+        let (frame_width, frame_height) = if horizontal {
+            (width / layers.max(1), height)
+        } else {
+            (width, height / layers.max(1))
+        };
+
+        if layers == 0
+            || frame_width == 0
+            || frame_height == 0
+            || frame_width * frame_height * layers != width * height
+        {
+            return Err(TextureError::IncompatibleLayers(format!(
+                "a strip of {}x{} pixels cannot be split into {} frame(s)",
+                width, height, layers
+            )));
+        }
+
+        let data = strip.first_mip_level_data();
+        let bytes = if horizontal {
+            let pixel_size = bytes_in_first_mip(
+                TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                },
+                strip.pixel_kind,
+            ) as usize;
+            let row_size = width as usize * pixel_size;
+            let frame_row_size = frame_width as usize * pixel_size;
+            let mut bytes = Vec::with_capacity(data.len());
+            for layer in 0..layers as usize {
+                for row in data.chunks_exact(row_size) {
+                    let begin = layer * frame_row_size;
+                    bytes.extend_from_slice(&row[begin..(begin + frame_row_size)]);
+                }
+            }
+            bytes
+        } else {
+            // Frames of a vertical strip are already stored one after another.
+            data.to_vec()
+        };
+
+        Ok(Self {
+            path: Default::default(),
+            kind: TextureKind::Array {
+                width: frame_width,
+                height: frame_height,
+                layers,
+            },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            pixel_kind: strip.pixel_kind,
+            minification_filter: strip.minification_filter,
+            magnification_filter: strip.magnification_filter,
+            s_wrap_mode: strip.s_wrap_mode,
+            t_wrap_mode: strip.t_wrap_mode,
+            mip_count: 1,
+            anisotropy: strip.anisotropy,
+            serialize_content,
+            is_render_target: false,
+        })
+    }
+
+    /// Creates new cube texture from six square rectangle textures of the same size and pixel
+    /// kind. Faces must be in the following order: left, right, top, bottom, front, back - the
+    /// same as in [`crate::scene::camera::SkyBox::textures`]. Only the first mip level of the
+    /// faces is used, sampling parameters are taken from the first face.
+    pub fn from_cube_faces(
+        faces: &[&TextureData],
+        serialize_content: bool,
+    ) -> Result<Self, TextureError> {
+        if faces.len() != 6 {
+            return Err(TextureError::IncompatibleLayers(format!(
+                "a cube texture requires 6 faces, {} given",
+                faces.len()
+            )));
+        }
+
+        let first = faces[0];
+        let size = match first.kind {
+            TextureKind::Rectangle { width, height } if width == height => width,
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        for (index, face) in faces.iter().enumerate() {
+            let same_size = matches!(face.kind, TextureKind::Rectangle { width, height }
+                if width == size && height == size);
+            if !same_size || face.pixel_kind != first.pixel_kind {
+                return Err(TextureError::IncompatibleLayers(format!(
+                    "face {} does not match the first face ({}x{} {:?})",
+                    index, size, size, first.pixel_kind
+                )));
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(6 * first.first_mip_level_data().len());
+        for face in faces {
+            bytes.extend_from_slice(face.first_mip_level_data());
+        }
+
+        Ok(Self {
+            path: Default::default(),
+            kind: TextureKind::Cube {
+                width: size,
+                height: size,
+            },
+            data_hash: data_hash(&bytes),
+            bytes: bytes.into(),
+            pixel_kind: first.pixel_kind,
+            minification_filter: first.minification_filter,
+            magnification_filter: first.magnification_filter,
+            s_wrap_mode: first.s_wrap_mode,
+            t_wrap_mode: first.t_wrap_mode,
+            mip_count: 1,
+            anisotropy: first.anisotropy,
+            serialize_content,
+            is_render_target: false,
+        })
+    }
+
+    /// Splits an image with faces of a cube in a cross layout into six faces, in the order
+    /// required by [`Self::from_cube_faces`]. A horizontal cross has four faces (left, front,
+    /// right, back) in the middle row, a vertical cross has three faces (left, front, right)
+    /// in the second row and the back face upside down in the last row. In both layouts the top
+    /// and the bottom faces are above and below the front face. Only uncompressed images are
+    /// supported.
+    pub fn cross_faces(
+        cross: &TextureData,
+        serialize_content: bool,
+    ) -> Result<Vec<TextureData>, TextureError> {
+        let (width, height) = match cross.kind {
+            TextureKind::Rectangle { width, height } => (width, height),
+            _ => return Err(TextureError::UnsupportedFormat),
+        };
+
+        if cross.pixel_kind.is_compressed() {
+            return Err(TextureError::UnsupportedFormat);
+        }
+
+        // Column and row of each face in the order of cube faces, the last value tells whether
+        // the face is upside down.
+        const HORIZONTAL_CROSS: [(usize, usize, bool); 6] = [
+            (0, 1, false),
+            (2, 1, false),
+            (1, 0, false),
+            (1, 2, false),
+            (1, 1, false),
+            (3, 1, false),
+        ];
+        const VERTICAL_CROSS: [(usize, usize, bool); 6] = [
+            (0, 1, false),
+            (2, 1, false),
+            (1, 0, false),
+            (1, 2, false),
+            (1, 1, false),
+            (1, 3, true),
+        ];
+
+        let (size, cells) = match (width * 3 == height * 4, width * 4 == height * 3) {
+            (true, _) if width > 0 => (width / 4, HORIZONTAL_CROSS),
+            (_, true) if width > 0 => (width / 3, VERTICAL_CROSS),
+            _ => {
+                return Err(TextureError::IncompatibleLayers(format!(
+                    "an image of {}x{} pixels is not a cross of 4x3 or 3x4 square faces",
+                    width, height
+                )))
+            }
+        };
+
+        let pixel_size = bytes_in_first_mip(
+            TextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            cross.pixel_kind,
+        ) as usize;
+        let (size, width) = (size as usize, width as usize);
+        let data = cross.first_mip_level_data();
+
+        Ok(cells
+            .iter()
+            .map(|&(column, row, upside_down)| {
+                let mut bytes = Vec::with_capacity(size * size * pixel_size);
+                for y in 0..size {
+                    let begin = ((row * size + y) * width + column * size) * pixel_size;
+                    bytes.extend_from_slice(&data[begin..(begin + size * pixel_size)]);
+                }
+                if upside_down {
+                    // Rotation by 180 degrees is the reversed order of pixels.
+                    bytes = bytes
+                        .chunks_exact(pixel_size)
+                        .rev()
+                        .flatten()
+                        .cloned()
+                        .collect();
+                }
+
+                Self {
+                    path: Default::default(),
+                    kind: TextureKind::Rectangle {
+                        width: size as u32,
+                        height: size as u32,
+                    },
+                    data_hash: data_hash(&bytes),
+                    bytes: bytes.into(),
+                    pixel_kind: cross.pixel_kind,
+                    minification_filter: cross.minification_filter,
+                    magnification_filter: cross.magnification_filter,
+                    s_wrap_mode: cross.s_wrap_mode,
+                    t_wrap_mode: cross.t_wrap_mode,
+                    mip_count: 1,
+                    anisotropy: cross.anisotropy,
+                    serialize_content,
+                    is_render_target: false,
+                }
+            })
+            .collect())
+    }
+
+    /// Creates new cube texture from an image with faces in a cross layout, see
+    /// [`Self::cross_faces`] for supported layouts.
+    pub fn from_cross(cross: &TextureData, serialize_content: bool) -> Result<Self, TextureError> {
+        let faces = Self::cross_faces(cross, false)?;
+        Self::from_cube_faces(&faces.iter().collect::<Vec<_>>(), serialize_content)
+    }
+
     /// Sets new minification filter. It is used when texture becomes smaller.
     pub fn set_minification_filter(&mut self, filter: TextureMinificationFilter) {
         self.minification_filter = filter;
@@ -1294,6 +1788,23 @@ impl TextureData {
         self.pixel_kind
     }
 
+    /// Returns estimated amount of memory (in bytes) that is needed to store the texture with
+    /// all its mip levels.
+    pub fn memory_usage(&self) -> usize {
+        mip_chain_size_bytes(self.kind, self.pixel_kind, self.mip_count)
+    }
+
+    /// Returns estimated amount of memory (in bytes) that would be needed to store the texture
+    /// with all its mip levels without compression. It is equal to [`Self::memory_usage`] for
+    /// uncompressed textures.
+    pub fn uncompressed_memory_usage(&self) -> usize {
+        mip_chain_size_bytes(
+            self.kind,
+            self.pixel_kind.uncompressed_equivalent(),
+            self.mip_count,
+        )
+    }
+
     /// Returns current data as immutable slice.
     pub fn data(&self) -> &[u8] {
         &self.bytes
@@ -1419,7 +1930,13 @@ impl<'a> TextureDataRefMut<'a> {
 
 #[cfg(test)]
 pub mod test {
-    use crate::resource::texture::{Texture, TextureKind, TexturePixelKind};
+    use crate::{
+        core::visitor::{Visit, Visitor},
+        resource::texture::{
+            bytes_in_first_mip, mip_chain_size_bytes, Texture, TextureData, TextureError,
+            TextureKind, TexturePixelKind,
+        },
+    };
 
     pub fn create_test_texture() -> Texture {
         Texture::from_bytes(
@@ -1433,4 +1950,234 @@ pub mod test {
         )
         .unwrap()
     }
+
+    #[test]
+    fn test_mip_chain_size() {
+        let kind = TextureKind::Rectangle {
+            width: 4,
+            height: 4,
+        };
+        assert_eq!(mip_chain_size_bytes(kind, TexturePixelKind::RGBA8, 1), 64);
+        assert_eq!(
+            mip_chain_size_bytes(kind, TexturePixelKind::RGBA8, 3),
+            64 + 16 + 4
+        );
+
+        // Every mip of a compressed texture takes at least one block.
+        let kind = TextureKind::Rectangle {
+            width: 8,
+            height: 8,
+        };
+        assert_eq!(
+            mip_chain_size_bytes(kind, TexturePixelKind::DXT1RGB, 4),
+            32 + 8 + 8 + 8
+        );
+    }
+
+    #[test]
+    fn test_array_kind_visit() {
+        let mut kind = TextureKind::Array {
+            width: 16,
+            height: 8,
+            layers: 5,
+        };
+        let mut visitor = Visitor::new();
+        kind.visit("Kind", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut loaded = TextureKind::default();
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        loaded.visit("Kind", &mut visitor).unwrap();
+
+        assert!(matches!(
+            loaded,
+            TextureKind::Array {
+                width: 16,
+                height: 8,
+                layers: 5
+            }
+        ));
+    }
+
+    #[test]
+    fn test_array_from_layers_and_strip() {
+        let layer = |value: u8| {
+            TextureData::from_bytes(
+                TextureKind::Rectangle {
+                    width: 2,
+                    height: 1,
+                },
+                TexturePixelKind::R8,
+                vec![value, value + 1],
+                false,
+            )
+            .unwrap()
+        };
+
+        let array = TextureData::from_layers(&[layer(0), layer(10), layer(20)], false).unwrap();
+        assert!(matches!(
+            array.kind(),
+            TextureKind::Array {
+                width: 2,
+                height: 1,
+                layers: 3
+            }
+        ));
+        assert_eq!(array.data(), &[0, 1, 10, 11, 20, 21]);
+
+        let mismatched = TextureData::from_bytes(
+            TextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            TexturePixelKind::R8,
+            vec![0],
+            false,
+        )
+        .unwrap();
+        assert!(TextureData::from_layers(&[layer(0), mismatched], false).is_err());
+
+        // Two 2x2 frames side by side.
+        let strip = TextureData::from_bytes(
+            TextureKind::Rectangle {
+                width: 4,
+                height: 2,
+            },
+            TexturePixelKind::R8,
+            vec![0, 1, 10, 11, 2, 3, 12, 13],
+            false,
+        )
+        .unwrap();
+        let array = TextureData::from_strip(&strip, 2, false).unwrap();
+        assert!(matches!(
+            array.kind(),
+            TextureKind::Array {
+                width: 2,
+                height: 2,
+                layers: 2
+            }
+        ));
+        assert_eq!(array.data(), &[0, 1, 2, 3, 10, 11, 12, 13]);
+        assert!(TextureData::from_strip(&strip, 3, false).is_err());
+    }
+
+    #[test]
+    fn test_cube_faces_mismatch() {
+        let face = |width: u32, height: u32, pixel_kind: TexturePixelKind| {
+            let kind = TextureKind::Rectangle { width, height };
+            TextureData::from_bytes(
+                kind,
+                pixel_kind,
+                vec![0; bytes_in_first_mip(kind, pixel_kind) as usize],
+                false,
+            )
+            .unwrap()
+        };
+
+        let square = face(2, 2, TexturePixelKind::R8);
+
+        // One of the faces has different size.
+        let smaller = face(1, 1, TexturePixelKind::R8);
+        let mut faces = vec![&square; 6];
+        faces[3] = &smaller;
+        assert!(matches!(
+            TextureData::from_cube_faces(&faces, false),
+            Err(TextureError::IncompatibleLayers(_))
+        ));
+
+        // One of the faces has different pixel kind.
+        let rgba = face(2, 2, TexturePixelKind::RGBA8);
+        let mut faces = vec![&square; 6];
+        faces[5] = &rgba;
+        assert!(matches!(
+            TextureData::from_cube_faces(&faces, false),
+            Err(TextureError::IncompatibleLayers(_))
+        ));
+
+        // Faces must be square.
+        let rectangle = face(2, 1, TexturePixelKind::R8);
+        assert!(matches!(
+            TextureData::from_cube_faces(&[&rectangle; 6], false),
+            Err(TextureError::UnsupportedFormat)
+        ));
+
+        // A cross must consist of square faces.
+        let cross = face(8, 4, TexturePixelKind::R8);
+        assert!(matches!(
+            TextureData::from_cross(&cross, false),
+            Err(TextureError::IncompatibleLayers(_))
+        ));
+    }
+
+    #[test]
+    fn test_cube_from_faces_and_cross() {
+        let face = |value: u8| {
+            TextureData::from_bytes(
+                TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                },
+                TexturePixelKind::R8,
+                vec![value],
+                false,
+            )
+            .unwrap()
+        };
+
+        let faces = (0..6).map(face).collect::<Vec<_>>();
+        let cube = TextureData::from_cube_faces(&faces.iter().collect::<Vec<_>>(), false).unwrap();
+        assert!(matches!(
+            cube.kind(),
+            TextureKind::Cube {
+                width: 1,
+                height: 1
+            }
+        ));
+        assert_eq!(cube.data(), &[0, 1, 2, 3, 4, 5]);
+        assert!(
+            TextureData::from_cube_faces(&faces.iter().take(5).collect::<Vec<_>>(), false).is_err()
+        );
+
+        // Horizontal cross of 1x1 faces: top; left, front, right, back; bottom.
+        let horizontal = TextureData::from_bytes(
+            TextureKind::Rectangle {
+                width: 4,
+                height: 3,
+            },
+            TexturePixelKind::R8,
+            vec![0, 3, 0, 0, 1, 5, 2, 6, 0, 4, 0, 0],
+            false,
+        )
+        .unwrap();
+        let cube = TextureData::from_cross(&horizontal, false).unwrap();
+        assert_eq!(cube.data(), &[1, 2, 3, 4, 5, 6]);
+
+        // Vertical cross of 2x2 faces, the back face is upside down.
+        let mut bytes = vec![0; 6 * 8];
+        for (column, row, value) in [(1, 0, 3), (0, 1, 1), (1, 1, 5), (2, 1, 2), (1, 2, 4)] {
+            for y in 0..2 {
+                for x in 0..2 {
+                    bytes[(row * 2 + y) * 6 + column * 2 + x] = value;
+                }
+            }
+        }
+        bytes[(3 * 2) * 6 + 2..(3 * 2) * 6 + 4].copy_from_slice(&[10, 11]);
+        bytes[(3 * 2 + 1) * 6 + 2..(3 * 2 + 1) * 6 + 4].copy_from_slice(&[12, 13]);
+        let vertical = TextureData::from_bytes(
+            TextureKind::Rectangle {
+                width: 6,
+                height: 8,
+            },
+            TexturePixelKind::R8,
+            bytes,
+            false,
+        )
+        .unwrap();
+        let faces = TextureData::cross_faces(&vertical, false).unwrap();
+        assert_eq!(faces[0].data(), &[1, 1, 1, 1]);
+        assert_eq!(faces[4].data(), &[5, 5, 5, 5]);
+        assert_eq!(faces[5].data(), &[13, 12, 11, 10]);
+
+        assert!(TextureData::from_cross(&face(0), false).is_err());
+    }
 }
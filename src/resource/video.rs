@@ -0,0 +1,383 @@
+//! Video is a sequence of frames that can be played on a texture. Frames are decoded from animated
+//! images (GIF or APNG), each frame is stored as RGBA8 pixels together with its duration.
+//!
+//! # Playing
+//!
+//! [`VideoPlayer`] owns a texture which content is replaced every time when a frame changes, so
+//! the texture can be assigned to any material (or a UI image) and it will show the video:
+//!
+//! ```no_run
+//! use fyrox::{
+//!     core::sstorage::ImmutableString,
+//!     material::{PropertyValue, SharedMaterial},
+//!     resource::{texture::SamplerFallback, video::{Video, VideoPlayer}},
+//! };
+//!
+//! async fn play_on_material(material: &SharedMaterial) -> VideoPlayer {
+//!     let video = Video::load_from_file("data/intro.gif").await.unwrap();
+//!     let player = VideoPlayer::new(video);
+//!
+//!     material
+//!         .lock()
+//!         .set_property(
+//!             &ImmutableString::new("diffuseTexture"),
+//!             PropertyValue::Sampler {
+//!                 value: Some(player.texture()),
+//!                 fallback: SamplerFallback::White,
+//!             },
+//!         )
+//!         .unwrap();
+//!
+//!     // Do not forget to call `player.update(dt)` every frame.
+//!     player
+//! }
+//! ```
+
+use crate::{
+    core::io,
+    resource::texture::{Texture, TextureError, TextureKind, TexturePixelKind},
+};
+use image::{
+    codecs::{gif::GifDecoder, png::PngDecoder},
+    AnimationDecoder, Frame, ImageFormat,
+};
+use std::{io::Cursor, path::Path, time::Duration};
+
+/// Frames with shorter duration are shown for this amount of seconds. Many animated images have
+/// zero delays, and viewers usually treat them as "show with some default speed".
+pub const MIN_FRAME_DURATION: f32 = 0.02;
+
+/// A single frame of a video.
+#[derive(Clone, Debug)]
+pub struct VideoFrame {
+    pixels: Vec<u8>,
+    duration: f32,
+}
+
+impl VideoFrame {
+    /// Returns pixels of the frame in RGBA8 format.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns amount of seconds the frame is shown.
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+/// A decoded video, see module docs for more info.
+#[derive(Clone, Debug)]
+pub struct Video {
+    width: u32,
+    height: u32,
+    frames: Vec<VideoFrame>,
+}
+
+impl Video {
+    /// Decodes a video from the given data. Supported formats are GIF and PNG (a non-animated PNG
+    /// will produce a video with one frame).
+    pub fn load_from_memory(data: &[u8]) -> Result<Self, TextureError> {
+        let frames = match image::guess_format(data).map_err(TextureError::Image)? {
+            ImageFormat::Gif => GifDecoder::new(Cursor::new(data))
+                .and_then(|decoder| decoder.into_frames().collect_frames()),
+            ImageFormat::Png => PngDecoder::new(Cursor::new(data)).and_then(|decoder| {
+                if decoder.is_apng() {
+                    decoder.apng().into_frames().collect_frames()
+                } else {
+                    image::load_from_memory_with_format(data, ImageFormat::Png)
+                        .map(|image| vec![Frame::new(image.into_rgba8())])
+                }
+            }),
+            _ => return Err(TextureError::UnsupportedFormat),
+        }
+        .map_err(TextureError::Image)?;
+
+        Self::from_frames(frames)
+    }
+
+    /// Loads and decodes a video from the given file, see [`Self::load_from_memory`] for more
+    /// info.
+    pub async fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, TextureError> {
+        let data = io::load_file(path)
+            .await
+            .map_err(TextureError::FileLoadError)?;
+        Self::load_from_memory(&data)
+    }
+
+    fn from_frames(frames: Vec<Frame>) -> Result<Self, TextureError> {
+        let (width, height) = frames
+            .first()
+            .map(|frame| frame.buffer().dimensions())
+            .ok_or(TextureError::UnsupportedFormat)?;
+
+        let frames = frames
+            .into_iter()
+            .map(|frame| {
+                // Decoders compose frames on the full canvas, so every frame must have the same
+                // size.
+                if frame.buffer().dimensions() != (width, height) {
+                    return Err(TextureError::UnsupportedFormat);
+                }
+                let duration = Duration::from(frame.delay()).as_secs_f32();
+                Ok(VideoFrame {
+                    duration: duration.max(MIN_FRAME_DURATION),
+                    pixels: frame.into_buffer().into_raw(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            width,
+            height,
+            frames,
+        })
+    }
+
+    /// Returns width of the video in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns height of the video in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns a slice with all frames of the video.
+    pub fn frames(&self) -> &[VideoFrame] {
+        &self.frames
+    }
+
+    /// Returns total duration of the video in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Creates a new texture with the content of the given frame. Could be useful to make a
+    /// thumbnail of the video.
+    pub fn frame_texture(&self, index: usize) -> Option<Texture> {
+        Texture::from_bytes(
+            TextureKind::Rectangle {
+                width: self.width,
+                height: self.height,
+            },
+            TexturePixelKind::RGBA8,
+            self.frames.get(index)?.pixels.clone(),
+            false,
+        )
+    }
+}
+
+/// Plays a video on a texture, see module docs for more info.
+#[derive(Debug)]
+pub struct VideoPlayer {
+    video: Video,
+    texture: Texture,
+    current_frame: usize,
+    frame_time: f32,
+    playing: bool,
+    looping: bool,
+    speed: f32,
+}
+
+impl VideoPlayer {
+    /// Creates new player that shows the first frame of the video. The player is looping and
+    /// starts playing immediately.
+    pub fn new(video: Video) -> Self {
+        let texture = video
+            .frame_texture(0)
+            .expect("Video always has at least one frame!");
+
+        Self {
+            video,
+            texture,
+            current_frame: 0,
+            frame_time: 0.0,
+            playing: true,
+            looping: true,
+            speed: 1.0,
+        }
+    }
+
+    /// Returns the texture the video is played on.
+    pub fn texture(&self) -> Texture {
+        self.texture.clone()
+    }
+
+    /// Returns the video.
+    pub fn video(&self) -> &Video {
+        &self.video
+    }
+
+    /// Resumes playing.
+    pub fn play(&mut self) {
+        // Start over if the video was played to the end.
+        if !self.looping && self.current_frame + 1 == self.video.frames.len() {
+            self.rewind();
+        }
+        self.playing = true;
+    }
+
+    /// Pauses playing, the texture keeps showing the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Returns `true` if the video is playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Sets whether the video should start over when the last frame was shown. Non-looping video
+    /// stops on its last frame.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Returns `true` if the video is looping.
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets playback speed multiplier, negative values are clamped to zero.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Returns playback speed multiplier.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Returns index of the frame that is currently shown.
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Shows the given frame, the index is clamped to the frame count.
+    pub fn set_current_frame(&mut self, index: usize) {
+        let index = index.min(self.video.frames.len() - 1);
+        self.frame_time = 0.0;
+        if index != self.current_frame {
+            self.current_frame = index;
+            self.upload_current_frame();
+        }
+    }
+
+    /// Shows the first frame.
+    pub fn rewind(&mut self) {
+        self.set_current_frame(0);
+    }
+
+    /// Advances the video by the given amount of seconds. Must be called every frame, the texture
+    /// is updated only when the frame changes.
+    pub fn update(&mut self, dt: f32) {
+        if !self.playing || self.video.frames.len() < 2 {
+            return;
+        }
+
+        let last_frame = self.current_frame;
+        self.frame_time += dt * self.speed;
+        loop {
+            let duration = self.video.frames[self.current_frame].duration;
+            if self.frame_time < duration {
+                break;
+            }
+            self.frame_time -= duration;
+
+            if self.current_frame + 1 < self.video.frames.len() {
+                self.current_frame += 1;
+            } else if self.looping {
+                // Skip whole loops at once, otherwise huge time steps would take forever.
+                self.frame_time %= self.video.duration();
+                self.current_frame = 0;
+            } else {
+                self.frame_time = 0.0;
+                self.playing = false;
+                break;
+            }
+        }
+
+        if self.current_frame != last_frame {
+            self.upload_current_frame();
+        }
+    }
+
+    fn upload_current_frame(&mut self) {
+        let mut data = self.texture.data_ref();
+        data.modify()
+            .data_mut()
+            .copy_from_slice(&self.video.frames[self.current_frame].pixels);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::resource::video::{Video, VideoPlayer};
+    use image::{codecs::gif::GifEncoder, Delay, Frame, Rgba, RgbaImage};
+
+    fn make_gif() -> Vec<u8> {
+        let mut data = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut data);
+            for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+                encoder
+                    .encode_frame(Frame::from_parts(
+                        RgbaImage::from_pixel(2, 2, Rgba(color)),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(100, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+        data
+    }
+
+    fn texture_pixels(player: &VideoPlayer) -> Vec<u8> {
+        player.texture().data_ref().data().to_vec()
+    }
+
+    #[test]
+    fn test_decode() {
+        let video = Video::load_from_memory(&make_gif()).unwrap();
+        assert_eq!(video.width(), 2);
+        assert_eq!(video.height(), 2);
+        assert_eq!(video.frames().len(), 2);
+        assert!((video.duration() - 0.2).abs() < 0.001);
+        assert_ne!(video.frames()[0].pixels(), video.frames()[1].pixels());
+    }
+
+    #[test]
+    fn test_playing() {
+        let mut player = VideoPlayer::new(Video::load_from_memory(&make_gif()).unwrap());
+        let frames = player.video().frames().to_vec();
+        assert_eq!(texture_pixels(&player), frames[0].pixels());
+
+        player.update(0.05);
+        assert_eq!(player.current_frame(), 0);
+
+        player.update(0.06);
+        assert_eq!(player.current_frame(), 1);
+        assert_eq!(texture_pixels(&player), frames[1].pixels());
+
+        // Looping.
+        player.update(0.1);
+        assert_eq!(player.current_frame(), 0);
+        assert_eq!(texture_pixels(&player), frames[0].pixels());
+
+        // Paused.
+        player.pause();
+        player.update(1.0);
+        assert_eq!(player.current_frame(), 0);
+
+        // Non-looping video stops on the last frame.
+        player.set_looping(false);
+        player.play();
+        player.update(10.0);
+        assert_eq!(player.current_frame(), 1);
+        assert!(!player.is_playing());
+        assert_eq!(texture_pixels(&player), frames[1].pixels());
+    }
+}
@@ -239,6 +239,8 @@ pub enum PropertyValue {
     F32(f32),
     /// A 64-bit floating point value.
     F64(f64),
+    /// A boolean value.
+    Bool(bool),
 }
 
 impl Default for PropertyValue {
@@ -25,6 +25,12 @@ pub struct NodeHandleMap {
     pub(crate) map: FxHashMap<Handle<Node>, Handle<Node>>,
 }
 
+impl From<FxHashMap<Handle<Node>, Handle<Node>>> for NodeHandleMap {
+    fn from(map: FxHashMap<Handle<Node>, Handle<Node>>) -> Self {
+        Self { map }
+    }
+}
+
 impl NodeHandleMap {
     /// Maps a handle to a handle of its origin, or sets it to [Handle::NONE] if there is no such node.
     /// It should be used when you are sure that respective origin exists.
@@ -102,6 +108,12 @@ impl NodeHandleMap {
         self.remap_handles_internal(node.as_reflect_mut(), &name);
     }
 
+    /// Same as [`Self::remap_handles`], but works with any reflectable entity (for example a value of
+    /// some property of a node). `owner_name` is used only for error messages.
+    pub fn remap_entity_handles(&self, entity: &mut dyn Reflect, owner_name: &str) {
+        self.remap_handles_internal(entity, owner_name);
+    }
+
     fn remap_handles_internal(&self, entity: &mut dyn Reflect, node_name: &str) {
         if let Some(handle) = entity.downcast_mut::<Handle<Node>>() {
             if handle.is_some() && !self.try_map(handle) {
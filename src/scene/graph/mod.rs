@@ -33,7 +33,7 @@ use crate::{
         variable::try_inherit_properties,
         visitor::{Visit, VisitResult, Visitor},
     },
-    resource::model::{Model, NodeMapping},
+    resource::model::Model,
     scene::{
         self,
         base::NodeScriptMessage,
@@ -717,36 +717,7 @@ impl Graph {
                 let model = model.state();
                 match *model {
                     ResourceState::Ok(ref data) => {
-                        let resource_graph = &data.get_scene().graph;
-
-                        let resource_node = match data.mapping {
-                            NodeMapping::UseNames => {
-                                // For some models we can resolve it only by names of nodes, but this is not
-                                // reliable way of doing this, because some editors allow nodes to have same
-                                // names for objects, but here we'll assume that modellers will not create
-                                // models with duplicated names and user of the engine reads log messages.
-                                resource_graph
-                                    .pair_iter()
-                                    .find_map(|(handle, resource_node)| {
-                                        if resource_node.name() == node.name() {
-                                            Some((resource_node, handle))
-                                        } else {
-                                            None
-                                        }
-                                    })
-                            }
-                            NodeMapping::UseHandles => {
-                                // Use original handle directly.
-                                resource_graph
-                                    .pool
-                                    .try_borrow(node.original_handle_in_resource)
-                                    .map(|resource_node| {
-                                        (resource_node, node.original_handle_in_resource)
-                                    })
-                            }
-                        };
-
-                        if let Some((resource_node, original)) = resource_node {
+                        if let Some((original, resource_node)) = data.find_original_node(node) {
                             node.original_handle_in_resource = original;
                             node.inv_bind_pose_transform = resource_node.inv_bind_pose_transform();
 
@@ -929,10 +900,33 @@ impl Graph {
         }
     }
 
+    /// Resets non-finite (NaN or infinite) properties of local transforms of every node to their
+    /// default values and logs every such node. A single node with broken transform would
+    /// otherwise spread NaNs over global transforms of its descendants, and break rendering and
+    /// physics. Returns amount of fixed nodes. It is called automatically when a scene is loaded.
+    pub fn reset_non_finite_transforms(&mut self) -> usize {
+        let mut count = 0;
+        for (handle, node) in self.pool.pair_iter_mut() {
+            let reset = node.local_transform_mut().reset_non_finite();
+            if !reset.is_empty() {
+                Log::warn(format!(
+                    "Node {} ({}) had non-finite values in its transform, following properties \
+                    were reset to defaults: {}",
+                    node.name(),
+                    handle,
+                    reset.join(", ")
+                ));
+                count += 1;
+            }
+        }
+        count
+    }
+
     pub(crate) fn resolve(&mut self) {
         Log::writeln(MessageKind::Information, "Resolving graph...");
 
         self.restore_dynamic_node_data();
+        self.reset_non_finite_transforms();
         self.update_hierarchical_data();
         self.restore_original_handles_and_inherit_properties();
         let instances = self.restore_integrity();
@@ -1450,9 +1444,15 @@ mod test {
     use crate::scene::base::BaseBuilder;
     use crate::scene::pivot::PivotBuilder;
     use crate::{
-        core::pool::Handle,
-        scene::{graph::Graph, node::Node, pivot::Pivot},
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+            visitor::{Visit, Visitor},
+        },
+        engine::SerializationContext,
+        scene::{graph::Graph, node::Node, pivot::Pivot, transform::TransformBuilder},
     };
+    use std::sync::Arc;
 
     #[test]
     fn graph_init_test() {
@@ -1520,4 +1520,60 @@ mod test {
         assert_eq!(result.0, a);
         assert_eq!(result.1, "A");
     }
+
+    #[test]
+    fn test_non_finite_transforms_are_reset_on_load() {
+        let mut graph = Graph::new();
+
+        let broken = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(f32::NAN, 1.0, 2.0))
+                    .with_local_scale(Vector3::new(1.0, f32::INFINITY, 1.0))
+                    .with_local_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 1.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        let child = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                    .build(),
+            ),
+        )
+        .build(&mut graph);
+        graph.link_nodes(child, broken);
+
+        let mut visitor = Visitor::new();
+        graph.visit("Graph", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        visitor.environment = Some(Arc::new(SerializationContext::new()));
+        let mut loaded = Graph::default();
+        loaded.visit("Graph", &mut visitor).unwrap();
+        loaded.resolve();
+
+        let transform = loaded[broken].local_transform();
+        assert!(transform.is_finite());
+        assert_eq!(**transform.position(), Vector3::default());
+        assert_eq!(**transform.scale(), Vector3::new(1.0, 1.0, 1.0));
+        // Finite properties are kept as is.
+        assert_eq!(
+            **transform.rotation(),
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 1.0)
+        );
+
+        assert_eq!(
+            **loaded[child].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(loaded[child]
+            .global_position()
+            .iter()
+            .all(|c| c.is_finite()));
+
+        assert_eq!(loaded.reset_non_finite_transforms(), 0);
+    }
 }
@@ -89,6 +89,8 @@ pub enum VertexAttributeUsage {
     BoneWeight = 11,
     /// Bone indices. Usually `Vector4<u8>`.
     BoneIndices = 12,
+    /// Vertex color. Usually `Vector4<u8>`, it is used by the standard vertex color shader.
+    Color = 13,
     /// Maximum amount of attribute kinds.
     Count,
 }
@@ -1089,6 +1089,13 @@ impl Surface {
         (*self.data).clone()
     }
 
+    /// Sets new data for the surface and returns the old one. Keep in mind that the data could be
+    /// shared with other surfaces, so if you need to modify the data of a single surface only,
+    /// use [`SurfaceSharedData::deep_clone`] first.
+    pub fn set_data(&mut self, data: SurfaceSharedData) -> SurfaceSharedData {
+        self.data.set_value_and_mark_modified(data)
+    }
+
     /// Returns current material of the surface.
     pub fn material(&self) -> &SharedMaterial {
         &self.material
@@ -25,6 +25,7 @@ pub mod sound;
 pub mod sprite;
 pub mod terrain;
 pub mod transform;
+pub mod trigger;
 pub mod visibility;
 
 use crate::scene::graph::GraphUpdateSwitches;
@@ -42,6 +43,7 @@ use crate::{
     material::{shader::SamplerFallback, PropertyValue},
     resource::texture::Texture,
     scene::{
+        base::Property,
         camera::Camera,
         debug::SceneDrawingContext,
         graph::{map::NodeHandleMap, Graph, GraphPerformanceStatistics},
@@ -140,6 +142,27 @@ impl IndexMut<Handle<Navmesh>> for NavMeshContainer {
     }
 }
 
+/// Defines how the renderer should show a scene. Every mode except [`SceneRenderMode::Lit`] is
+/// intended for debugging purposes only, materials of the scene remain untouched in any mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SceneRenderMode {
+    /// Full rendering with lighting, shadows and post effects. This is default mode.
+    Lit,
+    /// Lighting is ignored, only albedo of objects is shown.
+    Unlit,
+    /// Only edges of polygons are drawn. Does nothing on WebAssembly, because WebGL does not
+    /// support polygon rasterization modes other than fill.
+    Wireframe,
+    /// World-space normals of objects are shown as colors.
+    Normals,
+}
+
+impl Default for SceneRenderMode {
+    fn default() -> Self {
+        Self::Lit
+    }
+}
+
 /// See module docs.
 #[derive(Debug, Reflect)]
 pub struct Scene {
@@ -185,6 +208,26 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: bool,
+
+    /// Defines how the scene should be rendered, see [`SceneRenderMode`] docs for more info.
+    /// This is a view state, it is not serialized.
+    #[reflect(hidden)]
+    pub render_mode: SceneRenderMode,
+
+    /// Time in seconds that is passed to shaders as `fyrox_time` built-in uniform, it is advanced
+    /// on each update by `dt * shader_time_scale`. It can be used to animate materials (scrolling
+    /// UVs, pulsing emission, etc.). This is a view state, it is not serialized.
+    #[reflect(hidden)]
+    pub shader_time: f32,
+
+    /// Speed of shader time, `0.0` pauses material animations. Default is `1.0`.
+    #[reflect(hidden)]
+    pub shader_time_scale: f32,
+
+    /// A set of custom properties of the scene itself. It can be used to store arbitrary data,
+    /// that game code can read when the scene is loaded (for example, a name of a level, its
+    /// difficulty, etc.). Node handles in the properties are remapped when the scene is cloned.
+    pub properties: Vec<Property>,
 }
 
 impl Default for Scene {
@@ -198,6 +241,10 @@ impl Default for Scene {
             performance_statistics: Default::default(),
             ambient_lighting_color: Color::opaque(100, 100, 100),
             enabled: true,
+            render_mode: Default::default(),
+            shader_time: 0.0,
+            shader_time_scale: 1.0,
+            properties: Default::default(),
         }
     }
 }
@@ -350,9 +397,32 @@ impl Scene {
             performance_statistics: Default::default(),
             ambient_lighting_color: Color::opaque(100, 100, 100),
             enabled: true,
+            render_mode: Default::default(),
+            shader_time: 0.0,
+            shader_time_scale: 1.0,
+            properties: Default::default(),
         }
     }
 
+    /// Tries to find properties of the scene by the name. The method returns an iterator because
+    /// it possible to have multiple properties with the same name.
+    #[inline]
+    pub fn find_properties_ref<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+
+    /// Tries to find a first property of the scene with the given name.
+    #[inline]
+    pub fn find_first_property_ref(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    /// Sets a new set of properties of the scene, returns the old one.
+    #[inline]
+    pub fn set_properties(&mut self, properties: Vec<Property>) -> Vec<Property> {
+        std::mem::replace(&mut self.properties, properties)
+    }
+
     /// Synchronizes the state of the scene with external resources.
     pub fn resolve(&mut self) {
         Log::writeln(MessageKind::Information, "Starting resolve...");
@@ -492,6 +562,7 @@ impl Scene {
     /// no need to call it directly, engine automatically updates all available scenes.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
         self.graph.update(frame_size, dt, switches);
+        self.shader_time += dt * self.shader_time_scale;
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
     }
 
@@ -503,6 +574,9 @@ impl Scene {
     {
         let (graph, old_new_map) = self.graph.clone(filter);
 
+        let mut properties = self.properties.clone();
+        old_new_map.remap_entity_handles(&mut properties, "Scene");
+
         (
             Self {
                 graph,
@@ -515,6 +589,11 @@ impl Scene {
                 performance_statistics: Default::default(),
                 ambient_lighting_color: self.ambient_lighting_color,
                 enabled: self.enabled,
+                // Render mode is a view state and it is not copied as well.
+                render_mode: Default::default(),
+                shader_time: 0.0,
+                shader_time_scale: self.shader_time_scale,
+                properties,
             },
             old_new_map,
         )
@@ -529,6 +608,7 @@ impl Scene {
         self.ambient_lighting_color
             .visit("AmbientLightingColor", &mut region)?;
         self.enabled.visit("Enabled", &mut region)?;
+        let _ = self.properties.visit("Properties", &mut region);
 
         Ok(())
     }
@@ -657,3 +737,53 @@ impl IndexMut<Handle<Scene>> for SceneContainer {
         &mut self.pool[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::scene::{
+        base::{BaseBuilder, Property, PropertyValue},
+        pivot::PivotBuilder,
+        Scene,
+    };
+
+    #[test]
+    fn test_scene_properties() {
+        let mut scene = Scene::new();
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("A")).build(&mut scene.graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut scene.graph);
+
+        scene.set_properties(vec![
+            Property {
+                name: "Difficulty".to_owned(),
+                value: PropertyValue::U32(3),
+            },
+            Property {
+                name: "Target".to_owned(),
+                value: PropertyValue::NodeHandle(b),
+            },
+            Property {
+                name: "Boss".to_owned(),
+                value: PropertyValue::Bool(true),
+            },
+        ]);
+
+        assert_eq!(
+            scene.find_first_property_ref("Difficulty").unwrap().value,
+            PropertyValue::U32(3)
+        );
+        assert_eq!(scene.find_properties_ref("Boss").count(), 1);
+        assert!(scene.find_first_property_ref("Unknown").is_none());
+
+        // Skip a node, so the handle of the other one changes in the copy.
+        let (copy, _) = scene.clone(&mut |handle, _| handle != a);
+        let (new_b, _) = copy.graph.find_by_name_from_root("B").unwrap();
+        assert_eq!(
+            copy.find_first_property_ref("Target").unwrap().value,
+            PropertyValue::NodeHandle(new_b)
+        );
+        assert_eq!(
+            copy.find_first_property_ref("Boss").unwrap().value,
+            PropertyValue::Bool(true)
+        );
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
         sound::{listener::Listener, Sound},
         sprite::Sprite,
         terrain::Terrain,
+        trigger::TriggerVolume,
     },
 };
 use fxhash::FxHashMap;
@@ -53,6 +54,7 @@ impl NodeConstructorContainer {
         container.add::<scene::rigidbody::RigidBody>();
         container.add::<Sprite>();
         container.add::<Terrain>();
+        container.add::<TriggerVolume>();
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
 
@@ -138,6 +138,10 @@ impl Default for Transform {
     }
 }
 
+fn all_finite(values: &[f32]) -> bool {
+    values.iter().all(|v| v.is_finite())
+}
+
 fn build_post_rotation_matrix(post_rotation: UnitQuaternion<f32>) -> Matrix3<f32> {
     post_rotation
         .to_rotation_matrix()
@@ -399,6 +403,72 @@ impl Transform {
         self
     }
 
+    /// Returns `true` if every property of the transform has only finite values (no NaN or
+    /// infinity).
+    pub fn is_finite(&self) -> bool {
+        let vectors = [
+            &self.local_scale,
+            &self.local_position,
+            &self.rotation_offset,
+            &self.rotation_pivot,
+            &self.scaling_offset,
+            &self.scaling_pivot,
+        ];
+        let rotations = [
+            &self.local_rotation,
+            &self.pre_rotation,
+            &self.post_rotation,
+        ];
+
+        vectors.iter().all(|v| all_finite(v.as_slice()))
+            && rotations.iter().all(|r| all_finite(r.coords.as_slice()))
+    }
+
+    /// Resets every property of the transform that has non-finite values (NaN or infinity) to its
+    /// default value. Returns names of the properties that were reset.
+    pub fn reset_non_finite(&mut self) -> Vec<&'static str> {
+        let mut reset = Vec::new();
+
+        if !all_finite(self.local_position.as_slice()) {
+            self.set_position(Vector3::default());
+            reset.push(Self::LOCAL_POSITION);
+        }
+        if !all_finite(self.local_rotation.coords.as_slice()) {
+            self.set_rotation(UnitQuaternion::identity());
+            reset.push(Self::LOCAL_ROTATION);
+        }
+        if !all_finite(self.local_scale.as_slice()) {
+            self.set_scale(Vector3::new(1.0, 1.0, 1.0));
+            reset.push(Self::LOCAL_SCALE);
+        }
+        if !all_finite(self.pre_rotation.coords.as_slice()) {
+            self.set_pre_rotation(UnitQuaternion::identity());
+            reset.push(Self::PRE_ROTATION);
+        }
+        if !all_finite(self.post_rotation.coords.as_slice()) {
+            self.set_post_rotation(UnitQuaternion::identity());
+            reset.push(Self::POST_ROTATION);
+        }
+        if !all_finite(self.rotation_offset.as_slice()) {
+            self.set_rotation_offset(Vector3::default());
+            reset.push(Self::ROTATION_OFFSET);
+        }
+        if !all_finite(self.rotation_pivot.as_slice()) {
+            self.set_rotation_pivot(Vector3::default());
+            reset.push(Self::ROTATION_PIVOT);
+        }
+        if !all_finite(self.scaling_offset.as_slice()) {
+            self.set_scaling_offset(Vector3::default());
+            reset.push(Self::SCALING_OFFSET);
+        }
+        if !all_finite(self.scaling_pivot.as_slice()) {
+            self.set_scaling_pivot(Vector3::default());
+            reset.push(Self::SCALING_PIVOT);
+        }
+
+        reset
+    }
+
     fn calculate_local_transform(&self) -> Matrix4<f32> {
         // Make shortcuts to remove visual clutter.
         let por = &self.post_rotation_matrix;
@@ -0,0 +1,280 @@
+//! Trigger volume is a simple box or sphere region in space, that could be used to mark areas of
+//! interest for game logic.
+//!
+//! For more info see [`TriggerVolume`]
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    engine::resource_manager::ResourceManager,
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait, TypeUuidProvider},
+    },
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Possible shapes of a trigger volume. All sizes are defined in local coordinates of the node.
+#[derive(Copy, Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum TriggerShape {
+    /// Box shape defined by its half extents.
+    Box {
+        /// Half extents of the box. X - half width, Y - half height, Z - half depth.
+        half_extents: Vector3<f32>,
+    },
+    /// Sphere shape defined by its radius.
+    Sphere {
+        /// Radius of the sphere.
+        #[reflect(min_value = 0.0, step = 0.05)]
+        radius: f32,
+    },
+}
+
+impl Default for TriggerShape {
+    fn default() -> Self {
+        Self::Box {
+            half_extents: Vector3::new(0.5, 0.5, 0.5),
+        }
+    }
+}
+
+impl TriggerShape {
+    /// Returns local-space bounding box of the shape.
+    pub fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        match *self {
+            TriggerShape::Box { half_extents } => {
+                AxisAlignedBoundingBox::from_min_max(-half_extents, half_extents)
+            }
+            TriggerShape::Sphere { radius } => AxisAlignedBoundingBox::from_radius(radius),
+        }
+    }
+
+    /// Checks whether the given point (in local coordinates of the volume) is inside the shape.
+    pub fn contains_local_point(&self, point: Vector3<f32>) -> bool {
+        match *self {
+            TriggerShape::Box { half_extents } => {
+                point.x.abs() <= half_extents.x
+                    && point.y.abs() <= half_extents.y
+                    && point.z.abs() <= half_extents.z
+            }
+            TriggerShape::Sphere { radius } => point.norm_squared() <= radius * radius,
+        }
+    }
+
+    /// Returns a copy of the shape with its size multiplied by the given per-axis factors. Sphere
+    /// uses the factor that differs from `1.0` the most to stay a sphere.
+    pub fn scaled(&self, factors: Vector3<f32>) -> Self {
+        match *self {
+            TriggerShape::Box { half_extents } => TriggerShape::Box {
+                half_extents: half_extents
+                    .component_mul(&factors)
+                    .sup(&Vector3::repeat(f32::EPSILON)),
+            },
+            TriggerShape::Sphere { radius } => {
+                let factor = factors.iter().fold(1.0f32, |acc, &f| {
+                    if (f - 1.0).abs() > (acc - 1.0).abs() {
+                        f
+                    } else {
+                        acc
+                    }
+                });
+                TriggerShape::Sphere {
+                    radius: (radius * factor).max(f32::EPSILON),
+                }
+            }
+        }
+    }
+}
+
+/// Trigger volume is a box or sphere region in space, that does nothing on its own, but could be
+/// used by game logic to detect whether something is inside of it (see
+/// [`TriggerVolume::contains_point`]). It is not tied to physics in any way, if you need physical
+/// intersection events use a sensor collider instead.
+///
+/// # Editor
+///
+/// The editor draws trigger volumes as translucent wireframe gizmos, they're not visible in the
+/// game.
+///
+/// # Example
+///
+/// ```
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         graph::Graph,
+///         node::Node,
+///         trigger::{TriggerShape, TriggerVolumeBuilder},
+///     },
+/// };
+///
+/// fn create_spawn_area(graph: &mut Graph) -> Handle<Node> {
+///     TriggerVolumeBuilder::new(BaseBuilder::new().with_name("SpawnArea"))
+///         .with_shape(TriggerShape::Box {
+///             half_extents: Vector3::new(2.0, 1.0, 2.0),
+///         })
+///         .build(graph)
+/// }
+/// ```
+#[derive(Debug, Visit, Default, Clone, Reflect)]
+pub struct TriggerVolume {
+    base: Base,
+
+    #[reflect(setter = "set_shape")]
+    shape: InheritableVariable<TriggerShape>,
+}
+
+impl Deref for TriggerVolume {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for TriggerVolume {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for TriggerVolume {
+    fn type_uuid() -> Uuid {
+        uuid!("5ab8cd2d-4d5c-4ae4-9a3c-0b2f5c0bb8f4")
+    }
+}
+
+impl TriggerVolume {
+    /// Sets new shape of the volume and returns the old one.
+    pub fn set_shape(&mut self, shape: TriggerShape) -> TriggerShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns current shape of the volume.
+    pub fn shape(&self) -> TriggerShape {
+        *self.shape
+    }
+
+    /// Checks whether the given world-space point is inside the volume.
+    pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+        self.global_transform().try_inverse().map_or(false, |inv| {
+            self.shape
+                .contains_local_point(inv.transform_point(&point.into()).coords)
+        })
+    }
+}
+
+impl NodeTrait for TriggerVolume {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.shape.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.shape
+            .local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn restore_resources(&mut self, resource_manager: ResourceManager) {
+        self.base.restore_resources(resource_manager)
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Allows you to create trigger volume in declarative manner.
+pub struct TriggerVolumeBuilder {
+    base_builder: BaseBuilder,
+    shape: TriggerShape,
+}
+
+impl TriggerVolumeBuilder {
+    /// Creates new trigger volume builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: Default::default(),
+        }
+    }
+
+    /// Sets desired shape of the volume.
+    pub fn with_shape(mut self, shape: TriggerShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Creates new trigger volume.
+    pub fn build_trigger_volume(self) -> TriggerVolume {
+        TriggerVolume {
+            base: self.base_builder.build_base(),
+            shape: self.shape.into(),
+        }
+    }
+
+    /// Creates new trigger volume node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_trigger_volume())
+    }
+
+    /// Creates new trigger volume and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, reflect::Reflect, variable::try_inherit_properties},
+        scene::{
+            base::{test::check_inheritable_properties_equality, BaseBuilder},
+            trigger::{TriggerShape, TriggerVolume, TriggerVolumeBuilder},
+        },
+    };
+
+    #[test]
+    fn test_trigger_volume_inheritance() {
+        let parent = TriggerVolumeBuilder::new(BaseBuilder::new())
+            .with_shape(TriggerShape::Sphere { radius: 2.0 })
+            .build_node();
+
+        let mut child = TriggerVolumeBuilder::new(BaseBuilder::new()).build_trigger_volume();
+
+        try_inherit_properties(child.as_reflect_mut(), parent.as_reflect()).unwrap();
+
+        let parent = parent.cast::<TriggerVolume>().unwrap();
+
+        check_inheritable_properties_equality(&child.base, &parent.base);
+        check_inheritable_properties_equality(&child, parent);
+    }
+
+    #[test]
+    fn test_trigger_volume_contains_point() {
+        let volume = TriggerVolumeBuilder::new(BaseBuilder::new())
+            .with_shape(TriggerShape::Box {
+                half_extents: Vector3::new(1.0, 2.0, 3.0),
+            })
+            .build_trigger_volume();
+
+        assert!(volume.contains_point(Vector3::new(0.5, -1.5, 2.5)));
+        assert!(!volume.contains_point(Vector3::new(1.5, 0.0, 0.0)));
+
+        let sphere = TriggerShape::Sphere { radius: 1.0 };
+        assert!(sphere.contains_local_point(Vector3::new(0.0, 0.9, 0.0)));
+        assert!(!sphere.contains_local_point(Vector3::new(0.8, 0.8, 0.0)));
+    }
+}
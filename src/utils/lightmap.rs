@@ -348,7 +348,7 @@ impl Lightmap {
                     Err(LightmapGenerationError::Cancelled)
                 } else {
                     let mut data = data.lock();
-                    let patch = uvgen::generate_uvs(&mut data, 0.005)?;
+                    let patch = uvgen::generate_uvs(&mut data, uvgen::DEFAULT_SPACING)?;
                     progress_indicator.advance_progress();
                     Ok((patch.data_id, patch))
                 }
@@ -389,9 +389,7 @@ impl Lightmap {
                             WorldVertex {
                                 world_normal,
                                 world_position,
-                                second_tex_coord: view
-                                    .read_2_f32(VertexAttributeUsage::TexCoord1)
-                                    .unwrap(),
+                                second_tex_coord: uvgen::read_second_tex_coord(&view).unwrap(),
                             }
                         })
                         .collect::<Vec<_>>();
@@ -56,7 +56,8 @@ pub enum MessageKind {
 }
 
 impl MessageKind {
-    fn as_str(self) -> &'static str {
+    /// Returns a prefix that is used by the logger for messages of this kind.
+    pub fn as_str(self) -> &'static str {
         match self {
             MessageKind::Information => "[INFO]: ",
             MessageKind::Warning => "[WARNING]: ",
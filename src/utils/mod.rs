@@ -14,7 +14,9 @@ pub mod watcher;
 
 use crate::{
     core::algebra::Vector2,
-    event::{ElementState, ModifiersState, MouseScrollDelta, VirtualKeyCode, WindowEvent},
+    event::{
+        ElementState, ModifiersState, MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent,
+    },
     gui::{
         draw,
         message::{ButtonState, KeyCode, KeyboardModifiers, OsEvent},
@@ -277,6 +279,13 @@ pub fn translate_event(event: &WindowEvent) -> Option<OsEvent> {
         &WindowEvent::ModifiersChanged(modifiers) => Some(OsEvent::KeyboardModifiers(
             translate_keyboard_modifiers(modifiers),
         )),
+        WindowEvent::Touch(touch) => match touch.phase {
+            // Pen (or finger) is lifted, so next events will most likely come from a mouse.
+            TouchPhase::Ended | TouchPhase::Cancelled => Some(OsEvent::PointerPressure(1.0)),
+            TouchPhase::Started | TouchPhase::Moved => touch
+                .force
+                .map(|force| OsEvent::PointerPressure(force.normalized() as f32)),
+        },
         _ => None,
     }
 }
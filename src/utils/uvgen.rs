@@ -18,6 +18,12 @@ use crate::{
 };
 use rayon::prelude::*;
 
+/// Default spacing between separate parts of a generated UV map.
+pub const DEFAULT_SPACING: f32 = 0.005;
+
+/// Resolution of the grid used to estimate UV map statistics.
+const ANALYSIS_RESOLUTION: usize = 256;
+
 /// A part of uv map.
 #[derive(Debug)]
 pub struct UvMesh {
@@ -433,3 +439,76 @@ pub fn generate_uvs_mesh(
 
     Ok(patches)
 }
+
+/// Reads second texture coordinates of a vertex. Vertices without a second UV channel fall back
+/// to the primary texture coordinates.
+pub fn read_second_tex_coord<T: VertexReadTrait>(
+    vertex: &T,
+) -> Result<Vector2<f32>, VertexFetchError> {
+    vertex
+        .read_2_f32(VertexAttributeUsage::TexCoord1)
+        .or_else(|_| vertex.read_2_f32(VertexAttributeUsage::TexCoord0))
+}
+
+/// Quality metrics of a UV map.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct UvMapStats {
+    /// Fraction of the `[0; 1]` square that is covered by at least one triangle.
+    pub packing_efficiency: f32,
+    /// Fraction of the covered area that is covered by more than one triangle. Overlapping
+    /// parts of a lightmap will receive the same lighting, so this should be zero.
+    pub overlap: f32,
+}
+
+/// Estimates packing efficiency and overlap of the second UV channel of given surface data
+/// (or the primary one, if there is no second channel). The estimation is done by rasterizing
+/// triangles of the UV map into a grid, so it is precise up to grid resolution.
+pub fn analyze_uvs(data: &SurfaceData) -> Result<UvMapStats, VertexFetchError> {
+    let uvs = data
+        .vertex_buffer
+        .iter()
+        .map(|vertex| read_second_tex_coord(&vertex).map(|uv| uv.scale(ANALYSIS_RESOLUTION as f32)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut coverage = vec![0u8; ANALYSIS_RESOLUTION * ANALYSIS_RESOLUTION];
+    for triangle in data.geometry_buffer.iter() {
+        let (a, b, c) = match (
+            uvs.get(triangle[0] as usize),
+            uvs.get(triangle[1] as usize),
+            uvs.get(triangle[2] as usize),
+        ) {
+            (Some(a), Some(b), Some(c)) => (*a, *b, *c),
+            _ => continue,
+        };
+
+        if math::get_signed_triangle_area(a, b, c).abs() <= f32::EPSILON {
+            continue;
+        }
+
+        let min = a.per_component_min(&b).per_component_min(&c);
+        let max = a.per_component_max(&b).per_component_max(&c);
+        let to_cell = |v: f32| (v.max(0.0) as usize).min(ANALYSIS_RESOLUTION);
+
+        for y in to_cell(min.y.floor())..to_cell(max.y.ceil()) {
+            for x in to_cell(min.x.floor())..to_cell(max.x.ceil()) {
+                let center = Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+                if math::is_point_inside_2d_triangle(center, a, b, c) {
+                    let cell = &mut coverage[y * ANALYSIS_RESOLUTION + x];
+                    *cell = cell.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    let covered = coverage.iter().filter(|&&count| count > 0).count();
+    let overlapped = coverage.iter().filter(|&&count| count > 1).count();
+
+    Ok(UvMapStats {
+        packing_efficiency: covered as f32 / coverage.len() as f32,
+        overlap: if covered > 0 {
+            overlapped as f32 / covered as f32
+        } else {
+            0.0
+        },
+    })
+}